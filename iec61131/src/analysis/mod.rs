@@ -4,9 +4,12 @@
 //! - Control flow graph (CFG) construction
 //! - Cyclomatic complexity calculation
 //! - Nesting depth analysis
+//! - Unused method parameters
 
 mod cfg;
 mod nesting;
+mod unused_params;
 
 pub use cfg::{Cfg, CfgBuilder, CfgNode, CfgEdge, NodeId, NodeKind, EdgeKind, count_expression_decisions};
 pub use nesting::max_nesting_depth;
+pub use unused_params::{unused_method_parameters, UnusedParameter};
@@ -0,0 +1,183 @@
+//! Unused method parameter analysis.
+//!
+//! Finds the `VAR_INPUT` parameters of methods that the method never reads.
+//! A parameter is read when its name appears in an expression, an array
+//! index or the initial value of a local; assigning to it or binding it to
+//! a call output (`Q => p`) is not a read. Names match case-insensitively.
+
+use crate::generated::ast::{Argument, CompilationUnit, PouDeclaration, Statement, Variable};
+use crate::generated::lexer::Span;
+use crate::visitor::{walk_argument, walk_method, walk_stmt, walk_variable, Visitor};
+
+/// An input parameter that its method never reads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnusedParameter {
+    /// Function block or class declaring the method
+    pub owner: String,
+    pub method: String,
+    pub parameter: String,
+    /// Whether the method overrides a base method, whose signature it has
+    /// to keep
+    pub is_override: bool,
+    /// Span of the parameter declaration
+    pub span: Span,
+}
+
+/// Find the unused input parameters of the methods of a compilation unit.
+///
+/// Methods of function blocks and classes are checked, including those in
+/// namespaces. Abstract methods have no body and are skipped.
+///
+/// # Example
+///
+/// ```
+/// use iec61131::{Parser, analysis::unused_method_parameters};
+///
+/// let code = "CLASS Motor
+///     METHOD Start : BOOL
+///         VAR_INPUT speed : INT; ramp : TIME; END_VAR
+///         Start := speed > 0;
+///     END_METHOD
+/// END_CLASS";
+/// let unit = Parser::new(code).parse().unwrap();
+/// let unused = unused_method_parameters(&unit);
+/// assert_eq!(unused.len(), 1);
+/// assert_eq!((unused[0].method.as_str(), unused[0].parameter.as_str()), ("Start", "ramp"));
+/// ```
+pub fn unused_method_parameters(unit: &CompilationUnit) -> Vec<UnusedParameter> {
+    let mut unused = Vec::new();
+    collect(&unit.declarations, &mut unused);
+    unused
+}
+
+fn collect(declarations: &[PouDeclaration], unused: &mut Vec<UnusedParameter>) {
+    for declaration in declarations {
+        let (owner, methods) = match declaration {
+            PouDeclaration::FunctionBlock(fb) => (&fb.name, &fb.methods),
+            PouDeclaration::Class(class) => (&class.name, &class.methods),
+            PouDeclaration::Namespace(namespace) => {
+                collect(&namespace.elements, unused);
+                continue;
+            }
+            _ => continue,
+        };
+        for method in methods.iter().filter(|method| !method.is_abstract) {
+            let mut reads = Reads::default();
+            walk_method(&mut reads, method);
+            for input in &method.inputs {
+                if !reads.0.iter().any(|name| name.eq_ignore_ascii_case(&input.name)) {
+                    unused.push(UnusedParameter {
+                        owner: owner.clone(),
+                        method: method.name.clone(),
+                        parameter: input.name.clone(),
+                        is_override: method.is_override,
+                        span: input.span,
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Names of the variables read by a method.
+#[derive(Default)]
+struct Reads<'ast>(Vec<&'ast str>);
+
+impl<'ast> Reads<'ast> {
+    /// Visit a written variable: only its array indices are read.
+    fn write(&mut self, variable: &'ast Variable) {
+        match variable {
+            Variable::MemberAccess { base, .. } | Variable::Dereference { base } => self.write(base),
+            Variable::ArrayAccess { base, indices } => {
+                self.write(base);
+                for index in indices {
+                    self.visit_expr(index);
+                }
+            }
+            Variable::Direct(_) | Variable::Simple(_) => {}
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for Reads<'ast> {
+    fn visit_stmt(&mut self, stmt: &'ast Statement) {
+        match stmt {
+            Statement::Assignment { target, value, .. } => {
+                self.write(target);
+                self.visit_expr(value);
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+
+    fn visit_argument(&mut self, argument: &'ast Argument) {
+        match argument {
+            Argument::Output { variable, .. } => self.write(variable),
+            _ => walk_argument(self, argument),
+        }
+    }
+
+    fn visit_variable(&mut self, variable: &'ast Variable) {
+        if let Variable::Simple(name) = variable {
+            self.0.push(name);
+        }
+        walk_variable(self, variable);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    fn unused(code: &str) -> Vec<(String, String, bool)> {
+        let unit = Parser::new(code).parse().unwrap();
+        unused_method_parameters(&unit)
+            .into_iter()
+            .map(|p| (p.method, p.parameter, p.is_override))
+            .collect()
+    }
+
+    #[test]
+    fn test_writes_are_not_reads() {
+        let code = "FUNCTION_BLOCK Tank
+            VAR level : INT; buffer : ARRAY[0..9] OF INT; END_VAR
+            METHOD Fill
+                VAR_INPUT amount : INT; slot : INT; target : INT; result : INT; END_VAR
+                target := 5;
+                buffer[slot] := AMOUNT;
+                Pump(Done => result);
+            END_METHOD
+        END_FUNCTION_BLOCK";
+        assert_eq!(
+            unused(code),
+            [
+                ("Fill".to_string(), "target".to_string(), false),
+                ("Fill".to_string(), "result".to_string(), false),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_reads_in_calls_conditions_and_initializers() {
+        let code = "CLASS Valve
+            METHOD Open : BOOL
+                VAR_INPUT a : BOOL; b : INT; c : INT; END_VAR
+                VAR limit : INT := c; END_VAR
+                IF a THEN Open := LIMIT(MN := 0, IN := b, MX := limit) > 0; END_IF;
+            END_METHOD
+        END_CLASS";
+        assert!(unused(code).is_empty());
+    }
+
+    #[test]
+    fn test_override_flag() {
+        let code = "CLASS Child EXTENDS Base
+            METHOD PUBLIC OVERRIDE Run
+                VAR_INPUT mode : INT; END_VAR
+                count := count + 1;
+            END_METHOD
+        END_CLASS";
+        assert_eq!(unused(code), [("Run".to_string(), "mode".to_string(), true)]);
+    }
+}
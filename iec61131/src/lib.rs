@@ -92,6 +92,11 @@
 //!     println!("Nesting depth: {}", max_nesting_depth(&func.body));
 //! }
 //! ```
+//!
+//! ## Traversal
+//!
+//! Custom analyses implement [`Visitor`], overriding only the nodes they
+//! inspect, and start from [`walk_pou`]; see the [`visitor`] module.
 
 // Generated parser components
 mod generated;
@@ -102,11 +107,14 @@ pub mod security;
 // Analysis features
 pub mod analysis;
 
+// AST traversal
+pub mod visitor;
+
 // Re-export the main types
 pub use generated::ast::{
     CompilationUnit, PouDeclaration, FunctionDecl, FunctionBlockDecl, ProgramDecl, ClassDecl,
     InterfaceDecl, MethodDecl, Statement, Expression, VarDecl, TypeSpec,
-    Variable, Argument, StatementList, BinaryOp, UnaryOp, Literal,
+    Variable, Argument, StatementList, BinaryOp, UnaryOp, Literal, CaseItem, CaseSelector,
 };
 
 pub use generated::lexer::{Token, Lexer, Span};
@@ -115,6 +123,26 @@ pub use generated::parser::{Parser, ParseError};
 // Re-export security types
 pub use security::{ParserLimits, ParserState, SecurityError};
 
+// Re-export the AST visitor
+pub use visitor::{walk_pou, Visitor};
+
+/// Check whether a name is an IEC 61131-3 reserved word.
+///
+/// The comparison is case-insensitive, like IEC 61131-3 identifiers.
+/// Keywords include elementary type names (e.g. `INT`, `TIME`).
+pub fn is_keyword(name: &str) -> bool {
+    let mut chars = name.chars();
+    let starts_like_identifier = chars
+        .next()
+        .is_some_and(|c| c.is_alphabetic() || c == '_');
+    if !starts_like_identifier || !chars.all(|c| c.is_alphanumeric() || c == '_') {
+        return false;
+    }
+
+    let spanned = Lexer::new(name).next_token();
+    spanned.span.end == name.len() && !matches!(spanned.token, Token::Identifier(_))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
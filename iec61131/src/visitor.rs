@@ -0,0 +1,349 @@
+//! Traversal of the IEC 61131-3 AST.
+//!
+//! [`Visitor`] has one method per node type. Each default method calls the
+//! matching `walk_*` function, which visits the children of the node, so an
+//! analysis overrides only the nodes it cares about. An overriding method
+//! calls the `walk_*` function itself to keep descending, or returns to
+//! skip the children.
+//!
+//! Collect the names of all function calls of a compilation unit:
+//!
+//! ```
+//! use iec61131::{walk_pou, Expression, Parser, Visitor};
+//! use iec61131::visitor::walk_expr;
+//!
+//! #[derive(Default)]
+//! struct Calls<'ast>(Vec<&'ast str>);
+//!
+//! impl<'ast> Visitor<'ast> for Calls<'ast> {
+//!     fn visit_expr(&mut self, expr: &'ast Expression) {
+//!         if let Expression::Call { function, .. } = expr {
+//!             self.0.push(function);
+//!         }
+//!         walk_expr(self, expr);
+//!     }
+//! }
+//!
+//! let code = "FUNCTION F : INT VAR x : INT; END_VAR F := ABS(IN := x) + MAX(1, 2); END_FUNCTION";
+//! let unit = Parser::new(code).parse().unwrap();
+//! let mut calls = Calls::default();
+//! for pou in &unit.declarations {
+//!     walk_pou(&mut calls, pou);
+//! }
+//! assert_eq!(calls.0, ["ABS", "MAX"]);
+//! ```
+
+use crate::generated::ast::*;
+
+/// Visitor over the nodes of an IEC 61131-3 AST.
+///
+/// Nodes are visited in source order; a node is visited before its
+/// children. `'ast` is the lifetime of the tree, so a visitor can keep
+/// references to the nodes it visits.
+pub trait Visitor<'ast> {
+    /// Visit a POU declaration: its variable declarations, methods and body.
+    fn visit_pou(&mut self, pou: &'ast PouDeclaration) {
+        walk_pou(self, pou);
+    }
+
+    /// Visit a method: its variable declarations, then its body.
+    fn visit_method(&mut self, method: &'ast MethodDecl) {
+        walk_method(self, method);
+    }
+
+    /// Visit a variable declaration and its initial value.
+    fn visit_var_decl(&mut self, decl: &'ast VarDecl) {
+        walk_var_decl(self, decl);
+    }
+
+    /// Visit a statement list: a POU body or a branch or loop body.
+    fn visit_body(&mut self, body: &'ast [Statement]) {
+        walk_body(self, body);
+    }
+
+    /// Visit a statement.
+    fn visit_stmt(&mut self, stmt: &'ast Statement) {
+        walk_stmt(self, stmt);
+    }
+
+    /// Visit a CASE item: its selectors, then its body.
+    fn visit_case_item(&mut self, item: &'ast CaseItem) {
+        walk_case_item(self, item);
+    }
+
+    /// Visit a call argument.
+    fn visit_argument(&mut self, argument: &'ast Argument) {
+        walk_argument(self, argument);
+    }
+
+    /// Visit an expression.
+    fn visit_expr(&mut self, expr: &'ast Expression) {
+        walk_expr(self, expr);
+    }
+
+    /// Visit a variable access (assignment target, operand or output binding).
+    fn visit_variable(&mut self, variable: &'ast Variable) {
+        walk_variable(self, variable);
+    }
+}
+
+/// Visit each declaration of a compilation unit.
+pub fn walk_compilation_unit<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, unit: &'ast CompilationUnit) {
+    for pou in &unit.declarations {
+        visitor.visit_pou(pou);
+    }
+}
+
+/// Visit the variable declarations, methods and body of a POU declaration.
+///
+/// Initial values of data type declarations are visited as expressions;
+/// namespaces visit the declarations they contain.
+pub fn walk_pou<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, pou: &'ast PouDeclaration) {
+    let visit_vars = |visitor: &mut V, groups: &[&'ast Vec<VarDecl>]| {
+        for decl in groups.iter().flat_map(|group| group.iter()) {
+            visitor.visit_var_decl(decl);
+        }
+    };
+    match pou {
+        PouDeclaration::Function(f) => {
+            visit_vars(visitor, &[&f.inputs, &f.outputs, &f.in_outs, &f.vars]);
+            visitor.visit_body(&f.body);
+        }
+        PouDeclaration::FunctionBlock(fb) => {
+            visit_vars(visitor, &[&fb.inputs, &fb.outputs, &fb.in_outs, &fb.vars]);
+            for method in &fb.methods {
+                visitor.visit_method(method);
+            }
+            if let Some(body) = &fb.body {
+                visitor.visit_body(body);
+            }
+        }
+        PouDeclaration::Program(program) => {
+            visit_vars(visitor, &[&program.vars]);
+            visitor.visit_body(&program.body);
+        }
+        PouDeclaration::Class(class) => {
+            visit_vars(visitor, &[&class.vars]);
+            for method in &class.methods {
+                visitor.visit_method(method);
+            }
+        }
+        PouDeclaration::Interface(interface) => {
+            for prototype in &interface.methods {
+                visit_vars(visitor, &[&prototype.inputs, &prototype.outputs, &prototype.in_outs]);
+            }
+        }
+        PouDeclaration::DataType(data_type) => match data_type {
+            DataTypeDecl::Simple { init_value, .. } => {
+                if let Some(value) = init_value {
+                    visitor.visit_expr(value);
+                }
+            }
+            DataTypeDecl::Subrange { min, max, .. } => {
+                visitor.visit_expr(min);
+                visitor.visit_expr(max);
+            }
+            DataTypeDecl::Struct { fields, .. } => {
+                for value in fields.iter().filter_map(|field| field.init_value.as_ref()) {
+                    visitor.visit_expr(value);
+                }
+            }
+            DataTypeDecl::Enum { .. } | DataTypeDecl::Array { .. } => {}
+        },
+        PouDeclaration::GlobalVar(globals) => visit_vars(visitor, &[&globals.vars]),
+        PouDeclaration::Namespace(namespace) => {
+            for element in &namespace.elements {
+                visitor.visit_pou(element);
+            }
+        }
+    }
+}
+
+/// Visit the variable declarations and the body of a method.
+pub fn walk_method<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, method: &'ast MethodDecl) {
+    for decl in [&method.inputs, &method.outputs, &method.in_outs, &method.vars].into_iter().flatten() {
+        visitor.visit_var_decl(decl);
+    }
+    visitor.visit_body(&method.body);
+}
+
+/// Visit the initial value of a variable declaration.
+pub fn walk_var_decl<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, decl: &'ast VarDecl) {
+    if let Some(value) = &decl.init_value {
+        visitor.visit_expr(value);
+    }
+}
+
+/// Visit each statement of a statement list.
+pub fn walk_body<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, body: &'ast [Statement]) {
+    for stmt in body {
+        visitor.visit_stmt(stmt);
+    }
+}
+
+/// Visit the expressions and nested bodies of a statement.
+pub fn walk_stmt<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, stmt: &'ast Statement) {
+    match stmt {
+        Statement::Assignment { target, value, .. } => {
+            visitor.visit_variable(target);
+            visitor.visit_expr(value);
+        }
+        Statement::If { condition, then_body, elsif_parts, else_body, .. } => {
+            visitor.visit_expr(condition);
+            visitor.visit_body(then_body);
+            for (condition, body) in elsif_parts {
+                visitor.visit_expr(condition);
+                visitor.visit_body(body);
+            }
+            if let Some(else_body) = else_body {
+                visitor.visit_body(else_body);
+            }
+        }
+        Statement::Case { selector, cases, else_body, .. } => {
+            visitor.visit_expr(selector);
+            for item in cases {
+                visitor.visit_case_item(item);
+            }
+            if let Some(else_body) = else_body {
+                visitor.visit_body(else_body);
+            }
+        }
+        Statement::For { start, end, step, body, .. } => {
+            visitor.visit_expr(start);
+            visitor.visit_expr(end);
+            if let Some(step) = step {
+                visitor.visit_expr(step);
+            }
+            visitor.visit_body(body);
+        }
+        Statement::While { condition, body, .. } => {
+            visitor.visit_expr(condition);
+            visitor.visit_body(body);
+        }
+        Statement::Repeat { body, condition, .. } => {
+            visitor.visit_body(body);
+            visitor.visit_expr(condition);
+        }
+        Statement::Return { value: Some(value), .. } => visitor.visit_expr(value),
+        Statement::FunctionCall { arguments, .. } | Statement::FbInvocation { arguments, .. } => {
+            for argument in arguments {
+                visitor.visit_argument(argument);
+            }
+        }
+        Statement::Return { value: None, .. } | Statement::Exit { .. } | Statement::Continue { .. } => {}
+    }
+}
+
+/// Visit the selectors and the body of a CASE item.
+pub fn walk_case_item<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, item: &'ast CaseItem) {
+    for selector in &item.selectors {
+        match selector {
+            CaseSelector::Value(value) => visitor.visit_expr(value),
+            CaseSelector::Range(low, high) => {
+                visitor.visit_expr(low);
+                visitor.visit_expr(high);
+            }
+        }
+    }
+    visitor.visit_body(&item.body);
+}
+
+/// Visit the value or the output variable of a call argument.
+pub fn walk_argument<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, argument: &'ast Argument) {
+    match argument {
+        Argument::Positional(value) | Argument::Named { value, .. } => visitor.visit_expr(value),
+        Argument::Output { variable, .. } => visitor.visit_variable(variable),
+    }
+}
+
+/// Visit the subexpressions and variables of an expression.
+pub fn walk_expr<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, expr: &'ast Expression) {
+    match expr {
+        Expression::Variable(variable) => visitor.visit_variable(variable),
+        Expression::Unary { operand, .. } => visitor.visit_expr(operand),
+        Expression::Binary { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        Expression::Call { arguments, .. } => {
+            for argument in arguments {
+                visitor.visit_argument(argument);
+            }
+        }
+        Expression::Parenthesized(inner) => visitor.visit_expr(inner),
+        Expression::Literal(_) => {}
+    }
+}
+
+/// Visit the base and the index expressions of a variable access.
+pub fn walk_variable<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, variable: &'ast Variable) {
+    match variable {
+        Variable::MemberAccess { base, .. } | Variable::Dereference { base } => visitor.visit_variable(base),
+        Variable::ArrayAccess { base, indices } => {
+            visitor.visit_variable(base);
+            for index in indices {
+                visitor.visit_expr(index);
+            }
+        }
+        Variable::Direct(_) | Variable::Simple(_) => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Parser;
+
+    /// Records simple variable names and the statements it enters.
+    #[derive(Default)]
+    struct Recorder<'ast> {
+        names: Vec<&'ast str>,
+        statements: usize,
+        skip_loops: bool,
+    }
+
+    impl<'ast> Visitor<'ast> for Recorder<'ast> {
+        fn visit_stmt(&mut self, stmt: &'ast Statement) {
+            self.statements += 1;
+            if self.skip_loops && matches!(stmt, Statement::For { .. }) {
+                return;
+            }
+            walk_stmt(self, stmt);
+        }
+
+        fn visit_variable(&mut self, variable: &'ast Variable) {
+            if let Variable::Simple(name) = variable {
+                self.names.push(name);
+            }
+            walk_variable(self, variable);
+        }
+    }
+
+    const SOURCE: &str = "PROGRAM Main
+        VAR n : INT := Init; END_VAR
+        IF Run THEN
+            n := n + 1;
+        END_IF;
+        FOR i := 0 TO Max DO
+            a[i] := n;
+        END_FOR;
+    END_PROGRAM";
+
+    #[test]
+    fn test_walk_in_source_order() {
+        let unit = Parser::new(SOURCE).parse().unwrap();
+        let mut recorder = Recorder::default();
+        walk_compilation_unit(&mut recorder, &unit);
+        assert_eq!(recorder.names, ["Init", "Run", "n", "n", "Max", "a", "i", "n"]);
+        assert_eq!(recorder.statements, 4);
+    }
+
+    #[test]
+    fn test_override_skips_children() {
+        let unit = Parser::new(SOURCE).parse().unwrap();
+        let mut recorder = Recorder { skip_loops: true, ..Recorder::default() };
+        walk_compilation_unit(&mut recorder, &unit);
+        assert_eq!(recorder.names, ["Init", "Run", "n", "n"]);
+        assert_eq!(recorder.statements, 3);
+    }
+}
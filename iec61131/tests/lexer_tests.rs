@@ -1,4 +1,4 @@
-use iec61131::{Lexer, Token};
+use iec61131::{is_keyword, Lexer, Token};
 
 #[test]
 fn test_dotdot_token() {
@@ -17,3 +17,15 @@ fn test_dotdot_token() {
     println!("Token 3: {:?}", t3);
     assert!(matches!(t3.token, Token::IntLiteral(_)));
 }
+
+#[test]
+fn test_is_keyword() {
+    assert!(is_keyword("IF"));
+    assert!(is_keyword("end_function_block"));
+    assert!(is_keyword("Time"));
+    assert!(is_keyword("method"));
+    assert!(!is_keyword("Motor1"));
+    assert!(!is_keyword("IF_Done"));
+    assert!(!is_keyword("123"));
+    assert!(!is_keyword(""));
+}
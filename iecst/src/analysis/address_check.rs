@@ -0,0 +1,62 @@
+//! Direct address checks.
+//!
+//! Detects direct addresses such as `%MW100` or `%QX2.3` used in statements.
+//! Logic written against absolute addresses breaks when the I/O mapping
+//! changes and does not say what the signal means. Mapping a named variable
+//! to the address in its declaration (`Valve AT %QX2.3 : BOOL;`) is the
+//! symbolic alternative; such declarations are not reported.
+
+use crate::ast::*;
+use crate::analysis::dataflow::direct_address;
+use crate::analysis::visit::visit_expressions;
+use crate::analysis::{Diagnostic, DiagnosticKind};
+
+/// Check a POU body for direct addresses used in expressions, assignment
+/// targets and call arguments.
+///
+/// Each occurrence is reported as a hint with the address as written in
+/// canonical form (`%IX0.0` for `%I0.0`).
+pub fn check_direct_addresses(pou: &Pou) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    visit_expressions(&pou.body, &mut |expr| {
+        if let ExprKind::DirectAddress(address) = &expr.kind {
+            diagnostics.push(Diagnostic::hint(
+                DiagnosticKind::HardcodedAddress { address: direct_address(address) },
+                expr.span,
+            ));
+        }
+    });
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    #[test]
+    fn test_direct_addresses_in_body() {
+        let pou = parse_pou(
+            "PROGRAM Main
+            VAR
+                Valve AT %QX2.3 : BOOL;
+                Level : INT;
+            END_VAR
+            Level := %IW4 + 1;
+            %QX0.1 := Valve;
+            IF %MX10.0 THEN Valve := TRUE; END_IF;
+            Valve := Level > 10;
+            END_PROGRAM",
+        )
+        .unwrap();
+        let diags: Vec<String> = check_direct_addresses(&pou).iter().map(|d| d.kind.to_string()).collect();
+        assert_eq!(
+            diags,
+            [
+                "direct address '%IW4' used in logic; map a named variable to it with AT",
+                "direct address '%QX0.1' used in logic; map a named variable to it with AT",
+                "direct address '%MX10.0' used in logic; map a named variable to it with AT",
+            ]
+        );
+    }
+}
@@ -0,0 +1,168 @@
+//! Bit operation checks.
+//!
+//! `AND`, `OR` and `XOR` are logical on BOOL operands and bitwise on
+//! integer and bit-string operands. Mixing the two usually means one of
+//! them was not intended; masking and shifting signed integers depends on
+//! the sign bit.
+
+use std::collections::HashMap;
+
+use crate::ast::*;
+use crate::analysis::visit::visit_expressions;
+use crate::analysis::{build_symbol_table, Diagnostic, DiagnosticKind, Type, TypeChecker};
+
+/// Standard shift and rotate functions.
+const SHIFT_FUNCTIONS: &[&str] = &["SHL", "SHR", "ROL", "ROR"];
+
+/// Check a POU for mixed-type and signed bit operations.
+///
+/// Reports `AND`/`OR`/`XOR` with one BOOL and one integer or bit-string
+/// operand (`MixedBitOperation`, warning), and `AND`/`OR`/`XOR` or a
+/// shift/rotate applied to a signed integer (`SignedBitOperation`, hint).
+/// Integer literals are untyped and never make an operation suspicious.
+/// Operand types come from the POU's declarations and the return types in
+/// `functions` (see [`check_types`](super::check_types)).
+pub fn check_bit_operations(pou: &Pou, functions: &HashMap<String, Type>) -> Vec<Diagnostic> {
+    let symbols = build_symbol_table(pou, &mut Vec::new());
+    let mut type_checker = TypeChecker::new(&symbols);
+    for (name, return_type) in functions {
+        type_checker.register_function(name, return_type.clone());
+    }
+    let type_of = |operand: &Expr| {
+        (!is_int_literal(operand)).then(|| type_checker.infer_expr_type(operand, &mut Vec::new()).ty)
+    };
+
+    let mut diagnostics = Vec::new();
+    visit_expressions(&pou.body, &mut |expr| match &expr.kind {
+        ExprKind::BinaryOp { left, op: op @ (BinaryOp::And | BinaryOp::Or | BinaryOp::Xor), right } => {
+            let op = match op {
+                BinaryOp::And => "AND",
+                BinaryOp::Or => "OR",
+                _ => "XOR",
+            };
+            let (Some(left), Some(right)) = (type_of(left), type_of(right)) else {
+                // One side is a literal: only the signedness of the other matters
+                if let Some(ty) = type_of(left).or_else(|| type_of(right)).filter(is_signed) {
+                    diagnostics.push(signed(op, &ty, expr.span));
+                }
+                return;
+            };
+            if (left.is_bool() && right.is_integer()) || (left.is_integer() && right.is_bool()) {
+                diagnostics.push(Diagnostic::warning(
+                    DiagnosticKind::MixedBitOperation {
+                        op: op.to_string(),
+                        left: left.display_name(),
+                        right: right.display_name(),
+                    },
+                    expr.span,
+                ));
+            } else if let Some(ty) = [left, right].into_iter().find(is_signed) {
+                diagnostics.push(signed(op, &ty, expr.span));
+            }
+        }
+        ExprKind::FunctionCall { name, args } if SHIFT_FUNCTIONS.iter().any(|f| f.eq_ignore_ascii_case(name)) => {
+            // IN is the first argument or the one named IN
+            let input = args
+                .iter()
+                .find(|arg| arg.name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case("IN")))
+                .or_else(|| args.first().filter(|arg| arg.name.is_none()))
+                .and_then(|arg| arg.value.as_ref());
+            if let Some(ty) = input.and_then(type_of).filter(is_signed) {
+                diagnostics.push(signed(&name.to_uppercase(), &ty, expr.span));
+            }
+        }
+        _ => {}
+    });
+    diagnostics
+}
+
+/// Hint for a bitwise operation on a signed integer.
+fn signed(op: &str, ty: &Type, span: crate::Span) -> Diagnostic {
+    Diagnostic::hint(
+        DiagnosticKind::SignedBitOperation {
+            op: op.to_string(),
+            ty: ty.display_name(),
+        },
+        span,
+    )
+}
+
+/// Whether a type is a signed integer.
+fn is_signed(ty: &Type) -> bool {
+    matches!(ty, Type::SInt | Type::Int | Type::DInt | Type::LInt)
+}
+
+/// Whether an expression is an integer literal, possibly negated or in parentheses.
+fn is_int_literal(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::IntLiteral(_) => true,
+        ExprKind::Paren(inner) | ExprKind::UnaryOp { op: UnaryOp::Neg, expr: inner } => is_int_literal(inner),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::Severity;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<Diagnostic> {
+        let code = format!(
+            "PROGRAM Main
+            VAR status : WORD; mask : WORD; count : DINT; index : UINT; run : BOOL; ok : BOOL; END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        check_bit_operations(&parse_pou(&code).unwrap(), &HashMap::new())
+    }
+
+    #[test]
+    fn test_mixed_bool_and_word() {
+        let diags = check("ok := status AND run; status := mask OR ok;");
+        assert_eq!(diags.len(), 2);
+        assert_eq!(
+            diags[0].kind,
+            DiagnosticKind::MixedBitOperation {
+                op: "AND".to_string(),
+                left: "WORD".to_string(),
+                right: "BOOL".to_string(),
+            }
+        );
+        assert_eq!(diags[0].severity, Severity::Warning);
+        assert_eq!(
+            diags[1].kind.to_string(),
+            "OR of WORD and BOOL mixes bitwise and logical use (BOOL operands are logical, WORD operands bitwise)"
+        );
+    }
+
+    #[test]
+    fn test_signed_operands_and_shifts() {
+        let diags = check(
+            "count := count AND 16#FF;
+            count := SHL(count, 2);
+            count := SHR(IN := count, N := 1);
+            index := SHL(index, 1);",
+        );
+        let ops: Vec<_> = diags
+            .iter()
+            .map(|d| match &d.kind {
+                DiagnosticKind::SignedBitOperation { op, ty } => format!("{} {}", op, ty),
+                other => other.to_string(),
+            })
+            .collect();
+        assert_eq!(ops, ["AND DINT", "SHL DINT", "SHR DINT"]);
+        assert_eq!(diags[0].severity, Severity::Hint);
+    }
+
+    #[test]
+    fn test_logical_and_bitwise_use_not_flagged() {
+        let diags = check(
+            "ok := run AND NOT ok OR (status = mask);
+            status := status AND mask XOR 16#0F;
+            status := SHL(status, 4);",
+        );
+        assert!(diags.is_empty());
+    }
+}
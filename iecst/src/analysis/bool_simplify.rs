@@ -0,0 +1,277 @@
+//! Redundant boolean expression checks.
+//!
+//! Detects AND/OR chains with a repeated operand (`X AND X` is `X`), with
+//! an operand and its negation (`X AND NOT X` is FALSE, `X OR NOT X` is
+//! TRUE), and double negations (`NOT NOT X` is `X`), which refactoring
+//! tends to leave behind.
+
+use crate::ast::*;
+use crate::analysis::{build_symbol_table, Diagnostic, DiagnosticKind, TypeChecker};
+use crate::visitor::{walk_body, walk_expr, Visitor};
+
+/// Check a POU for redundant boolean expressions.
+///
+/// Chains of the same operator are flattened, so `a AND b AND a` is found
+/// too, and operands are compared structurally, ignoring parentheses and
+/// the case of identifiers. Operands containing function calls are never
+/// considered equal, since each call may return a different value. `AND`
+/// and `OR` of an operand and its negation are only reported when the
+/// operand is not an integer or bit string, whose bitwise result is not a
+/// boolean constant. Each finding is a warning carrying the simplified
+/// expression.
+pub fn check_redundant_booleans(pou: &Pou) -> Vec<Diagnostic> {
+    let symbols = build_symbol_table(pou, &mut Vec::new());
+    let type_checker = TypeChecker::new(&symbols);
+    let mut checker = RedundantBooleans {
+        is_integer: &|expr: &Expr| type_checker.infer_expr_type(expr, &mut Vec::new()).ty.is_integer(),
+        diagnostics: Vec::new(),
+    };
+    walk_body(&mut checker, &pou.body);
+    checker.diagnostics
+}
+
+struct RedundantBooleans<'a> {
+    is_integer: &'a dyn Fn(&Expr) -> bool,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'ast> Visitor<'ast> for RedundantBooleans<'_> {
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match &expr.kind {
+            ExprKind::BinaryOp { op: op @ (BinaryOp::And | BinaryOp::Or), .. } => {
+                let mut operands = Vec::new();
+                flatten(expr, *op, &mut operands);
+                if let Some(simplified) = self.simplify_chain(*op, &operands) {
+                    self.report(expr, simplified);
+                }
+                // Nested chains of the other operator are separate chains
+                for operand in operands {
+                    self.visit_expr(operand);
+                }
+            }
+            ExprKind::UnaryOp { op: UnaryOp::Not, expr: inner } => match &strip_parens(inner).kind {
+                ExprKind::UnaryOp { op: UnaryOp::Not, expr: operand } => {
+                    self.report(expr, expr_text(strip_parens(operand)));
+                    self.visit_expr(operand);
+                }
+                _ => walk_expr(self, expr),
+            },
+            _ => walk_expr(self, expr),
+        }
+    }
+}
+
+impl RedundantBooleans<'_> {
+    /// Simplified text of an AND/OR chain with a complementary or repeated
+    /// operand.
+    fn simplify_chain(&self, op: BinaryOp, operands: &[&Expr]) -> Option<String> {
+        let complement = operands.iter().any(|a| {
+            operands.iter().any(|b| match &strip_parens(b).kind {
+                ExprKind::UnaryOp { op: UnaryOp::Not, expr: negated } => same_expr(a, negated),
+                _ => false,
+            })
+        });
+        if complement && !operands.iter().any(|operand| (self.is_integer)(operand)) {
+            return Some(if op == BinaryOp::And { "FALSE" } else { "TRUE" }.to_string());
+        }
+
+        let mut kept: Vec<&Expr> = Vec::new();
+        for operand in operands {
+            if !kept.iter().any(|k| same_expr(k, operand)) {
+                kept.push(operand);
+            }
+        }
+        (kept.len() < operands.len()).then(|| {
+            let separator = if op == BinaryOp::And { " AND " } else { " OR " };
+            kept.iter().map(|operand| expr_text(operand)).collect::<Vec<_>>().join(separator)
+        })
+    }
+
+    fn report(&mut self, expr: &Expr, simplified: String) {
+        self.diagnostics.push(Diagnostic::warning(
+            DiagnosticKind::RedundantBoolean { expr: expr_text(expr), simplified },
+            expr.span,
+        ));
+    }
+}
+
+/// Collect the operands of a chain of `op`, looking through parentheses.
+fn flatten<'a>(expr: &'a Expr, op: BinaryOp, operands: &mut Vec<&'a Expr>) {
+    match &strip_parens(expr).kind {
+        ExprKind::BinaryOp { left, op: inner, right } if *inner == op => {
+            flatten(left, op, operands);
+            flatten(right, op, operands);
+        }
+        _ => operands.push(expr),
+    }
+}
+
+fn strip_parens(expr: &Expr) -> &Expr {
+    match &expr.kind {
+        ExprKind::Paren(inner) => strip_parens(inner),
+        _ => expr,
+    }
+}
+
+/// Check whether two expressions are structurally equal and free of calls.
+fn same_expr(a: &Expr, b: &Expr) -> bool {
+    let (a, b) = (strip_parens(a), strip_parens(b));
+    match (&a.kind, &b.kind) {
+        (ExprKind::Ident(x), ExprKind::Ident(y)) => x.eq_ignore_ascii_case(y),
+        (ExprKind::BoolLiteral(x), ExprKind::BoolLiteral(y)) => x == y,
+        (ExprKind::IntLiteral(x), ExprKind::IntLiteral(y)) => x == y,
+        (ExprKind::DirectAddress(x), ExprKind::DirectAddress(y)) => x == y,
+        (ExprKind::MemberAccess { expr: x, member: mx }, ExprKind::MemberAccess { expr: y, member: my }) => {
+            mx.eq_ignore_ascii_case(my) && same_expr(x, y)
+        }
+        (ExprKind::ArrayIndex { array: x, indices: ix }, ExprKind::ArrayIndex { array: y, indices: iy }) => {
+            ix.len() == iy.len() && ix.iter().zip(iy).all(|(i, j)| same_expr(i, j)) && same_expr(x, y)
+        }
+        (ExprKind::UnaryOp { op: ox, expr: x }, ExprKind::UnaryOp { op: oy, expr: y }) => ox == oy && same_expr(x, y),
+        (
+            ExprKind::BinaryOp { left: lx, op: ox, right: rx },
+            ExprKind::BinaryOp { left: ly, op: oy, right: ry },
+        ) => ox == oy && same_expr(lx, ly) && same_expr(rx, ry),
+        _ => false,
+    }
+}
+
+/// Render an expression as source text.
+fn expr_text(expr: &Expr) -> String {
+    match &expr.kind {
+        ExprKind::IntLiteral(value) => value.to_string(),
+        ExprKind::RealLiteral(value) => format!("{:?}", value),
+        ExprKind::StringLiteral(value) => format!("'{}'", value),
+        ExprKind::WStringLiteral(value) => format!("\"{}\"", value),
+        ExprKind::BoolLiteral(value) => if *value { "TRUE" } else { "FALSE" }.to_string(),
+        ExprKind::TimeLiteral(text)
+        | ExprKind::DateLiteral(text)
+        | ExprKind::TodLiteral(text)
+        | ExprKind::DateTimeLiteral(text)
+        | ExprKind::Ident(text) => text.clone(),
+        ExprKind::DirectAddress(address) => {
+            let location = match address.location {
+                LocationPrefix::Input => "I",
+                LocationPrefix::Output => "Q",
+                LocationPrefix::Memory => "M",
+            };
+            let size = match address.size {
+                SizePrefix::Bit => "X",
+                SizePrefix::Byte => "B",
+                SizePrefix::Word => "W",
+                SizePrefix::DoubleWord => "D",
+                SizePrefix::LongWord => "L",
+            };
+            format!("%{}{}{}", location, size, address.address)
+        }
+        ExprKind::BinaryOp { left, op, right } => {
+            format!("{} {} {}", expr_text(left), op_text(*op), expr_text(right))
+        }
+        ExprKind::UnaryOp { op: UnaryOp::Neg, expr } => format!("-{}", expr_text(expr)),
+        ExprKind::UnaryOp { op: UnaryOp::Not, expr } => format!("NOT {}", expr_text(expr)),
+        ExprKind::FunctionCall { name, args } => {
+            let args: Vec<String> = args
+                .iter()
+                .map(|arg| {
+                    let value = arg.value.as_ref().map(expr_text).unwrap_or_default();
+                    match &arg.name {
+                        Some(name) if arg.output => format!("{} => {}", name, value),
+                        Some(name) => format!("{} := {}", name, value),
+                        None => value,
+                    }
+                })
+                .collect();
+            format!("{}({})", name, args.join(", "))
+        }
+        ExprKind::ArrayIndex { array, indices } => {
+            let indices: Vec<String> = indices.iter().map(expr_text).collect();
+            format!("{}[{}]", expr_text(array), indices.join(", "))
+        }
+        ExprKind::MemberAccess { expr, member } => format!("{}.{}", expr_text(expr), member),
+        ExprKind::Paren(inner) => format!("({})", expr_text(inner)),
+    }
+}
+
+fn op_text(op: BinaryOp) -> &'static str {
+    match op {
+        BinaryOp::Add => "+",
+        BinaryOp::Sub => "-",
+        BinaryOp::Mul => "*",
+        BinaryOp::Div => "/",
+        BinaryOp::Mod => "MOD",
+        BinaryOp::Power => "**",
+        BinaryOp::Eq => "=",
+        BinaryOp::Ne => "<>",
+        BinaryOp::Lt => "<",
+        BinaryOp::Le => "<=",
+        BinaryOp::Gt => ">",
+        BinaryOp::Ge => ">=",
+        BinaryOp::And => "AND",
+        BinaryOp::Or => "OR",
+        BinaryOp::Xor => "XOR",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<String> {
+        let code = format!(
+            "PROGRAM Main
+            VAR enable : BOOL; a : BOOL; b : BOOL; x : BOOL; mask : WORD; arr : ARRAY[0..9] OF BOOL; END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        check_redundant_booleans(&parse_pou(&code).unwrap())
+            .iter()
+            .map(|d| d.kind.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_repeated_operands() {
+        let diags = check("x := enable AND ENABLE; x := a OR b OR (a); x := arr[1] AND arr[1] AND b;");
+        assert_eq!(
+            diags,
+            vec![
+                "'enable AND ENABLE' simplifies to 'enable'",
+                "'a OR b OR (a)' simplifies to 'a OR b'",
+                "'arr[1] AND arr[1] AND b' simplifies to 'arr[1] AND b'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_complements_and_double_negation() {
+        let diags = check("x := a AND NOT a; x := b OR (NOT b); x := NOT NOT a; IF NOT (NOT b) THEN x := a; END_IF;");
+        assert_eq!(
+            diags,
+            vec![
+                "'a AND NOT a' simplifies to 'FALSE'",
+                "'b OR (NOT b)' simplifies to 'TRUE'",
+                "'NOT NOT a' simplifies to 'a'",
+                "'NOT (NOT b)' simplifies to 'b'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_chains() {
+        let diags = check("x := (a OR a) AND b; x := a AND (b OR x) AND a;");
+        assert_eq!(
+            diags,
+            vec!["'a OR a' simplifies to 'a'", "'a AND (b OR x) AND a' simplifies to 'a AND (b OR x)'"]
+        );
+    }
+
+    #[test]
+    fn test_conservative_cases_not_flagged() {
+        let diags = check(
+            "x := Rising() AND Rising(); x := a AND b; x := a OR NOT b; mask := mask AND NOT mask; x := a XOR a;",
+        );
+        assert!(diags.is_empty(), "{:?}", diags);
+    }
+}
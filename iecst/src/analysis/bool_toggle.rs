@@ -0,0 +1,196 @@
+//! Boolean variables set to TRUE and FALSE in one unconditional sequence.
+//!
+//! `Out := TRUE; Out := FALSE;` leaves `Out` FALSE: nothing between the two
+//! assignments can see the TRUE, so the first one has no effect. It is
+//! usually a latch or pulse whose condition got lost. Dead store checks
+//! catch some of these, but not for outputs, globals or members, and the
+//! contradiction is worth its own message.
+
+use crate::ast::*;
+use crate::analysis::visit::{visit_expr, visit_statements};
+use crate::analysis::{Diagnostic, DiagnosticKind};
+
+/// Check a POU for sequences of unconditional assignments of both TRUE and
+/// FALSE to the same variable or member path.
+///
+/// The assignments must be in the same statement list; statements between
+/// them may only be assignments that do not mention the variable, so an IF,
+/// a call or a read in between ends the sequence. Assignments in separate
+/// branches are guarded and never form a sequence. Each sequence is one
+/// warning at its first assignment, with the last assignment's span and
+/// the value the variable ends up with.
+pub fn check_bool_toggles(pou: &Pou) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    check_statements(&pou.body, &mut diagnostics);
+    visit_statements(&pou.body, &mut |stmt| {
+        for body in nested_bodies(stmt) {
+            check_statements(body, &mut diagnostics);
+        }
+    });
+    diagnostics.sort_by_key(|d| d.span.start);
+    diagnostics
+}
+
+/// Report the toggling sequences of one statement list.
+fn check_statements(stmts: &[Stmt], diagnostics: &mut Vec<Diagnostic>) {
+    let mut index = 0;
+    while index < stmts.len() {
+        let Some((path, first)) = bool_assignment(&stmts[index]) else {
+            index += 1;
+            continue;
+        };
+        let root = root_name(&path);
+        let mut last = (index, first);
+        let mut toggled = false;
+        for (next, stmt) in stmts.iter().enumerate().skip(index + 1) {
+            match bool_assignment(stmt) {
+                Some((other, value)) if other.eq_ignore_ascii_case(&path) => {
+                    toggled |= value != first;
+                    last = (next, value);
+                }
+                _ if mentions(stmt, root) => break,
+                _ if matches!(stmt.kind, StmtKind::Assignment { .. } | StmtKind::Empty) => {}
+                _ => break,
+            }
+        }
+        if toggled {
+            diagnostics.push(Diagnostic::warning(
+                DiagnosticKind::BoolToggle {
+                    path,
+                    value: last.1,
+                    last: stmts[last.0].span,
+                },
+                stmts[index].span,
+            ));
+        }
+        index = last.0 + 1;
+    }
+}
+
+/// The path and value of an assignment of a boolean literal to a variable
+/// or member path.
+fn bool_assignment(stmt: &Stmt) -> Option<(String, bool)> {
+    let StmtKind::Assignment { target, value } = &stmt.kind else {
+        return None;
+    };
+    match value.kind {
+        ExprKind::BoolLiteral(value) => Some((path_text(target)?, value)),
+        _ => None,
+    }
+}
+
+/// Whether an assignment mentions the variable `root` anywhere; any other
+/// statement always counts as mentioning it.
+fn mentions(stmt: &Stmt, root: &str) -> bool {
+    let StmtKind::Assignment { target, value } = &stmt.kind else {
+        return !matches!(stmt.kind, StmtKind::Empty);
+    };
+    let mut found = false;
+    let mut check = |expr: &Expr| {
+        if let ExprKind::Ident(name) = &expr.kind {
+            found |= name.eq_ignore_ascii_case(root);
+        }
+    };
+    visit_expr(target, &mut check);
+    visit_expr(value, &mut check);
+    found
+}
+
+/// The statement lists directly inside a compound statement.
+fn nested_bodies(stmt: &Stmt) -> Vec<&[Stmt]> {
+    match &stmt.kind {
+        StmtKind::If { then_body, elsif_branches, else_body, .. } => std::iter::once(then_body.as_slice())
+            .chain(elsif_branches.iter().map(|(_, body)| body.as_slice()))
+            .chain(else_body.as_deref())
+            .collect(),
+        StmtKind::Case { cases, else_body, .. } => cases
+            .iter()
+            .map(|branch| branch.body.as_slice())
+            .chain(else_body.as_deref())
+            .collect(),
+        StmtKind::For { body, .. } | StmtKind::While { body, .. } | StmtKind::Repeat { body, .. } => {
+            vec![body.as_slice()]
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Render a variable or member path as source text.
+fn path_text(expr: &Expr) -> Option<String> {
+    match &expr.kind {
+        ExprKind::Ident(name) => Some(name.clone()),
+        ExprKind::Paren(inner) => path_text(inner),
+        ExprKind::MemberAccess { expr, member } => Some(format!("{}.{}", path_text(expr)?, member)),
+        _ => None,
+    }
+}
+
+/// The variable a path starts with.
+fn root_name(path: &str) -> &str {
+    path.split('.').next().unwrap_or(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<String> {
+        let code = format!(
+            "PROGRAM Main
+            VAR Out : BOOL; Lamp : BOOL; Start : BOOL; x : INT; cell : Cell; END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        let pou = parse_pou(&code).unwrap();
+        check_bool_toggles(&pou).iter().map(|d| d.kind.to_string()).collect()
+    }
+
+    #[test]
+    fn test_toggles_flagged() {
+        let diags = check(
+            "Out := TRUE;
+            Out := FALSE;
+            x := 1;
+            cell.Run := FALSE;
+            Lamp := Start;
+            Cell.run := TRUE;
+            IF Start THEN
+                Lamp := TRUE; ; Lamp := FALSE; Lamp := TRUE;
+            END_IF;",
+        );
+        assert_eq!(
+            diags,
+            [
+                "'Out' is set to both TRUE and FALSE with no condition in between; \
+                 the earlier assignments have no effect and it ends FALSE",
+                "'cell.Run' is set to both TRUE and FALSE with no condition in between; \
+                 the earlier assignments have no effect and it ends TRUE",
+                "'Lamp' is set to both TRUE and FALSE with no condition in between; \
+                 the earlier assignments have no effect and it ends TRUE",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_toggles_not_flagged() {
+        let diags = check(
+            "Out := TRUE;
+            Out := TRUE;
+            Lamp := TRUE;
+            x := BOOL_TO_INT(Lamp);
+            Lamp := FALSE;
+            Start := TRUE;
+            IF x > 0 THEN x := 0; END_IF;
+            Start := FALSE;
+            IF x > 1 THEN Out := TRUE; ELSE Out := FALSE; END_IF;
+            Out := Start;
+            Out := FALSE;
+            cell.Run := TRUE;
+            cell := cell;
+            cell.Run := FALSE;",
+        );
+        assert!(diags.is_empty(), "{:?}", diags);
+    }
+}
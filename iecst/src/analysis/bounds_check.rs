@@ -0,0 +1,278 @@
+//! Constant array index bounds checks.
+//!
+//! Detects array accesses `arr[K]` whose index folds to a constant outside
+//! the declared `ARRAY[low..high]` range, which faults at runtime, and
+//! declared ranges whose high bound is below the low bound.
+
+use std::collections::HashMap;
+
+use crate::ast::*;
+use crate::analysis::const_eval::{eval_const_int, pou_constants};
+use crate::analysis::visit::visit_expressions;
+use crate::analysis::{Diagnostic, DiagnosticKind};
+use crate::span::Span;
+
+/// Check a POU for constant array indices outside the declared bounds.
+///
+/// Array types are declared inline or through `types` (following aliases).
+/// Indices and range bounds are folded to integer constants, using the
+/// POU's `VAR CONSTANT` values; non-constant indices are skipped. Each
+/// index of a multi-dimensional access is checked against its dimension.
+pub fn check_array_bounds(pou: &Pou, types: &[TypeDecl]) -> Vec<Diagnostic> {
+    let constants = pou_constants(pou);
+    let mut diagnostics = Vec::new();
+    visit_expressions(&pou.body, &mut |expr| {
+        let ExprKind::ArrayIndex { array, indices } = &expr.kind else {
+            return;
+        };
+        let ExprKind::Ident(name) = &array.kind else {
+            return;
+        };
+        let Some(ranges) = array_ranges(pou, types, name) else {
+            return;
+        };
+        for (dimension, (index, range)) in indices.iter().zip(ranges).enumerate() {
+            let Some(value) = eval_const_int(index, &constants) else {
+                continue;
+            };
+            let Some((low, high)) = fold_range(range, &constants) else {
+                continue;
+            };
+            if value < low || value > high {
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::ArrayIndexOutOfBounds {
+                        name: name.clone(),
+                        index: value,
+                        low,
+                        high,
+                        dimension: (ranges.len() > 1).then_some(dimension + 1),
+                    },
+                    index.span,
+                ));
+            }
+        }
+    });
+    diagnostics
+}
+
+/// Resolve the declared ranges of an array variable.
+fn array_ranges<'a>(pou: &'a Pou, types: &'a [TypeDecl], name: &str) -> Option<&'a [ArrayRange]> {
+    let var = pou
+        .var_blocks
+        .iter()
+        .flat_map(|block| &block.vars)
+        .find(|var| var.name.eq_ignore_ascii_case(name))?;
+
+    let mut type_spec = &var.var_type;
+    // Follow aliases, bounded in case of cyclic declarations
+    for _ in 0..=types.len() {
+        let type_name = match &type_spec.kind {
+            TypeKind::Array { ranges, .. } => return Some(ranges),
+            TypeKind::Simple(type_name) => type_name,
+            _ => return None,
+        };
+        let decl = types.iter().find(|decl| decl.name.eq_ignore_ascii_case(type_name))?;
+        match &decl.definition {
+            TypeDef::Array { ranges, .. } => return Some(ranges),
+            TypeDef::Alias(spec) => type_spec = spec,
+            _ => return None,
+        }
+    }
+    None
+}
+
+/// Check the array declarations of a POU for empty and single-element ranges.
+///
+/// Every dimension of the array variables (including arrays of arrays) is
+/// folded with the POU's `VAR CONSTANT` values. A range with a high bound
+/// below its low bound (`ARRAY[10..1]`) has no elements and is an error; a
+/// range of one element (`ARRAY[5..5]`) is a warning. Non-constant bounds
+/// are skipped.
+///
+/// # Example
+///
+/// ```
+/// use iecst::{check_array_ranges, parse_pou, DiagnosticKind};
+///
+/// let pou = parse_pou("PROGRAM Main VAR buf : ARRAY[10..1] OF INT; END_VAR END_PROGRAM").unwrap();
+/// let diags = check_array_ranges(&pou);
+/// assert!(matches!(diags[0].kind, DiagnosticKind::EmptyArrayRange { low: 10, high: 1, .. }));
+/// ```
+pub fn check_array_ranges(pou: &Pou) -> Vec<Diagnostic> {
+    let constants = pou_constants(pou);
+    let mut diagnostics = Vec::new();
+    for var in pou.var_blocks.iter().flat_map(|block| &block.vars) {
+        check_spec_ranges(&var.name, &var.var_type, var.span, &constants, &mut diagnostics);
+    }
+    diagnostics
+}
+
+/// Check the array type declarations and the array fields of structure
+/// declarations for empty and single-element ranges, as
+/// [`check_array_ranges`] does for variables. Struct fields are named
+/// `Type.field`.
+pub fn check_type_ranges(types: &[TypeDecl]) -> Vec<Diagnostic> {
+    let constants = HashMap::new();
+    let mut diagnostics = Vec::new();
+    for decl in types {
+        match &decl.definition {
+            TypeDef::Array { ranges, element } => {
+                check_ranges(&decl.name, ranges, decl.span, &constants, &mut diagnostics);
+                check_spec_ranges(&decl.name, element, decl.span, &constants, &mut diagnostics);
+            }
+            TypeDef::Alias(spec) => check_spec_ranges(&decl.name, spec, decl.span, &constants, &mut diagnostics),
+            TypeDef::Struct { fields } => {
+                for field in fields {
+                    let name = format!("{}.{}", decl.name, field.name);
+                    check_spec_ranges(&name, &field.var_type, field.span, &constants, &mut diagnostics);
+                }
+            }
+            TypeDef::Enum { .. } | TypeDef::Subrange { .. } => {}
+        }
+    }
+    diagnostics
+}
+
+/// Check the ranges of an inline array type and of its element types.
+fn check_spec_ranges(
+    name: &str,
+    spec: &TypeSpec,
+    span: Span,
+    constants: &HashMap<String, i64>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    if let TypeKind::Array { ranges, element } = &spec.kind {
+        check_ranges(name, ranges, span, constants, diagnostics);
+        check_spec_ranges(name, element, span, constants, diagnostics);
+    }
+}
+
+fn check_ranges(
+    name: &str,
+    ranges: &[ArrayRange],
+    span: Span,
+    constants: &HashMap<String, i64>,
+    diagnostics: &mut Vec<Diagnostic>,
+) {
+    for (dimension, range) in ranges.iter().enumerate() {
+        let Some((low, high)) = fold_range(range, constants) else {
+            continue;
+        };
+        let dimension = (ranges.len() > 1).then_some(dimension + 1);
+        // Parsed ranges carry their own span; synthesized ones do not
+        let span = if range.span == Span::default() { span } else { range.span };
+        if high < low {
+            diagnostics.push(Diagnostic::error(
+                DiagnosticKind::EmptyArrayRange { name: name.to_string(), low, high, dimension },
+                span,
+            ));
+        } else if high == low {
+            diagnostics.push(Diagnostic::warning(
+                DiagnosticKind::SingleElementArrayRange { name: name.to_string(), bound: low, dimension },
+                span,
+            ));
+        }
+    }
+}
+
+/// Fold the bounds of a declared range, if both are constant.
+fn fold_range(range: &ArrayRange, constants: &HashMap<String, i64>) -> Option<(i64, i64)> {
+    Some((eval_const_int(&range.low, constants)?, eval_const_int(&range.high, constants)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_pou, parse_type_block};
+
+    fn check(decls: &str, body: &str) -> Vec<Diagnostic> {
+        let code = format!("PROGRAM Main\n{}\n{}\nEND_PROGRAM", decls, body);
+        check_array_bounds(&parse_pou(&code).unwrap(), &[])
+    }
+
+    #[test]
+    fn test_constant_index_out_of_bounds() {
+        let diags = check(
+            "VAR buf : ARRAY[1..10] OF INT; i : INT; END_VAR",
+            "buf[0] := 1; buf[10] := 2; buf[11] := 3; buf[i] := 4; buf[2 * 6] := 5;",
+        );
+        assert_eq!(diags.len(), 3);
+        assert_eq!(
+            diags[0].kind,
+            DiagnosticKind::ArrayIndexOutOfBounds { name: "buf".to_string(), index: 0, low: 1, high: 10, dimension: None }
+        );
+        assert_eq!(diags[0].kind.to_string(), "index 0 is outside the declared range of 'buf' [1..10]");
+        assert!(matches!(diags[2].kind, DiagnosticKind::ArrayIndexOutOfBounds { index: 12, .. }));
+    }
+
+    #[test]
+    fn test_multi_dimensional_and_constants() {
+        let diags = check(
+            "VAR CONSTANT ROWS : INT := 3; END_VAR
+            VAR grid : ARRAY[0..ROWS - 1, 0..4] OF BOOL; END_VAR",
+            "grid[ROWS, 0] := TRUE; grid[2, 4] := TRUE; grid[1, 5] := grid[0, 0];",
+        );
+        assert_eq!(diags.len(), 2);
+        assert!(matches!(diags[0].kind, DiagnosticKind::ArrayIndexOutOfBounds { index: 3, high: 2, dimension: Some(1), .. }));
+        assert!(matches!(diags[1].kind, DiagnosticKind::ArrayIndexOutOfBounds { index: 5, high: 4, dimension: Some(2), .. }));
+    }
+
+    #[test]
+    fn test_empty_and_single_element_ranges() {
+        let pou = parse_pou(
+            "PROGRAM Main
+            VAR CONSTANT N : INT := 4; END_VAR
+            VAR
+                down : ARRAY[10..1] OF INT;
+                one : ARRAY[5..5] OF INT;
+                grid : ARRAY[0..N, N..0] OF BOOL;
+                nested : ARRAY[1..2] OF ARRAY[3..2] OF INT;
+                ok : ARRAY[0..N - 1] OF INT;
+                open : ARRAY[0..M] OF INT;
+            END_VAR
+            END_PROGRAM",
+        )
+        .unwrap();
+        let kinds: Vec<DiagnosticKind> = check_array_ranges(&pou).into_iter().map(|d| d.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                DiagnosticKind::EmptyArrayRange { name: "down".to_string(), low: 10, high: 1, dimension: None },
+                DiagnosticKind::SingleElementArrayRange { name: "one".to_string(), bound: 5, dimension: None },
+                DiagnosticKind::EmptyArrayRange { name: "grid".to_string(), low: 4, high: 0, dimension: Some(2) },
+                DiagnosticKind::EmptyArrayRange { name: "nested".to_string(), low: 3, high: 2, dimension: None },
+            ]
+        );
+        assert_eq!(kinds[0].to_string(), "declared range of 'down' [10..1] has no elements");
+        assert_eq!(kinds[2].to_string(), "declared range of 'grid' [4..0] in dimension 2 has no elements");
+    }
+
+    #[test]
+    fn test_type_ranges() {
+        let types = parse_type_block(
+            "TYPE
+                Buffer : ARRAY[7..0] OF BYTE;
+                Frame : STRUCT data : ARRAY[1..0] OF BYTE; size : INT; END_STRUCT;
+                Good : ARRAY[0..7] OF BYTE;
+            END_TYPE",
+        )
+        .unwrap();
+        let names: Vec<String> = check_type_ranges(&types)
+            .into_iter()
+            .filter_map(|d| match d.kind {
+                DiagnosticKind::EmptyArrayRange { name, .. } => Some(name),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(names, ["Buffer", "Frame.data"]);
+    }
+
+    #[test]
+    fn test_declared_array_type() {
+        let types = parse_type_block("TYPE Buffer : ARRAY[0..7] OF BYTE; RxBuffer : Buffer; END_TYPE").unwrap();
+        let pou = parse_pou("PROGRAM Main VAR rx : RxBuffer; END_VAR rx[8] := 0; rx[7] := 0; END_PROGRAM").unwrap();
+        let diags = check_array_bounds(&pou, &types);
+        assert_eq!(diags.len(), 1);
+        assert!(check_array_bounds(&pou, &[]).is_empty());
+    }
+}
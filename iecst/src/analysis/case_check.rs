@@ -0,0 +1,421 @@
+//! CASE statement checks.
+//!
+//! Checks that CASE statements over enumerated types cover every enum
+//! member (or have an ELSE branch), and that no two labels match the same
+//! value.
+
+use std::collections::HashMap;
+
+use crate::ast::*;
+use crate::analysis::const_eval::{eval_const_int, pou_constants};
+use crate::analysis::{Diagnostic, DiagnosticKind};
+use crate::span::Span;
+
+/// Check CASE statements in a POU for uncovered enum members.
+///
+/// `types` holds the type declarations visible to the POU; selectors whose
+/// declared type is an enumeration among them are checked. Integer
+/// selectors are not required to be exhaustive.
+pub fn check_case_coverage(pou: &Pou, types: &[TypeDecl]) -> Vec<Diagnostic> {
+    let mut checker = CaseChecker {
+        pou,
+        types,
+        diagnostics: Vec::new(),
+    };
+    checker.check_body(&pou.body);
+    checker.diagnostics
+}
+
+/// Check CASE statements in a POU for duplicate or overlapping labels.
+///
+/// Label expressions are folded to integer constants, using the POU's
+/// `VAR CONSTANT` declarations. Identifiers that are not variables are
+/// compared by name, so repeated enum members are found too. Labels that
+/// are not constant are skipped.
+pub fn check_case_labels(pou: &Pou) -> Vec<Diagnostic> {
+    let constants = pou_constants(pou);
+    let mut diagnostics = Vec::new();
+    visit_cases(&pou.body, &mut |_, cases| {
+        let mut seen: Vec<(Label, Span)> = Vec::new();
+        for branch in cases {
+            for value in &branch.values {
+                let Some(label) = Label::from_value(value, pou, &constants) else {
+                    continue;
+                };
+                if let Some((other, original)) = seen.iter().find(|(other, _)| other.overlaps(&label)) {
+                    diagnostics.push(Diagnostic::warning(
+                        DiagnosticKind::OverlappingCaseLabels {
+                            label: label.to_string(),
+                            other: other.to_string(),
+                            original: *original,
+                        },
+                        branch.span,
+                    ));
+                }
+                seen.push((label, branch.span));
+            }
+        }
+    });
+    diagnostics
+}
+
+/// Call `f` for every CASE statement in a body, including nested ones.
+fn visit_cases(body: &[Stmt], f: &mut impl FnMut(&Stmt, &[CaseBranch])) {
+    for stmt in body {
+        match &stmt.kind {
+            StmtKind::Case { cases, else_body, .. } => {
+                f(stmt, cases);
+                for branch in cases {
+                    visit_cases(&branch.body, f);
+                }
+                if let Some(else_stmts) = else_body {
+                    visit_cases(else_stmts, f);
+                }
+            }
+
+            StmtKind::If { then_body, elsif_branches, else_body, .. } => {
+                visit_cases(then_body, f);
+                for (_, body) in elsif_branches {
+                    visit_cases(body, f);
+                }
+                if let Some(else_stmts) = else_body {
+                    visit_cases(else_stmts, f);
+                }
+            }
+
+            StmtKind::For { body, .. }
+            | StmtKind::While { body, .. }
+            | StmtKind::Repeat { body, .. } => visit_cases(body, f),
+
+            _ => {}
+        }
+    }
+}
+
+/// A CASE label reduced to a comparable form.
+#[derive(Debug, Clone, PartialEq)]
+enum Label {
+    /// Inclusive integer range (a single value has `from == to`)
+    Range(i64, i64),
+    /// Symbolic name, such as an enum member
+    Name(String),
+}
+
+impl Label {
+    fn from_value(value: &CaseValue, pou: &Pou, constants: &HashMap<String, i64>) -> Option<Self> {
+        match value {
+            CaseValue::Single(expr) => {
+                if let Some(v) = eval_const_int(expr, constants) {
+                    return Some(Label::Range(v, v));
+                }
+                let name = enum_label(expr)?;
+                let is_variable = matches!(expr.kind, ExprKind::Ident(_))
+                    && pou
+                        .var_blocks
+                        .iter()
+                        .flat_map(|block| &block.vars)
+                        .any(|var| var.name.eq_ignore_ascii_case(name));
+                (!is_variable).then(|| Label::Name(name.to_string()))
+            }
+            CaseValue::Range { from, to } => {
+                let from = eval_const_int(from, constants)?;
+                let to = eval_const_int(to, constants)?;
+                Some(Label::Range(from.min(to), from.max(to)))
+            }
+        }
+    }
+
+    fn overlaps(&self, other: &Label) -> bool {
+        match (self, other) {
+            (Label::Range(a_from, a_to), Label::Range(b_from, b_to)) => a_from <= b_to && b_from <= a_to,
+            (Label::Name(a), Label::Name(b)) => a.eq_ignore_ascii_case(b),
+            _ => false,
+        }
+    }
+}
+
+impl std::fmt::Display for Label {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Label::Range(from, to) if from == to => write!(f, "{}", from),
+            Label::Range(from, to) => write!(f, "{}..{}", from, to),
+            Label::Name(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+struct CaseChecker<'a> {
+    pou: &'a Pou,
+    types: &'a [TypeDecl],
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl<'a> CaseChecker<'a> {
+    fn check_body(&mut self, body: &[Stmt]) {
+        let mut found = Vec::new();
+        visit_cases(body, &mut |stmt, cases| {
+            let StmtKind::Case { expr, else_body: None, .. } = &stmt.kind else {
+                return;
+            };
+            if let Some(members) = self.selector_enum(expr) {
+                let missing = uncovered_members(members, cases);
+                if !missing.is_empty() {
+                    found.push(Diagnostic::warning(
+                        DiagnosticKind::NonExhaustiveCase { missing },
+                        stmt.span,
+                    ));
+                }
+            }
+        });
+        self.diagnostics.extend(found);
+    }
+
+    /// Get the enum members of the selector's declared type, if it is an enum.
+    fn selector_enum(&self, selector: &Expr) -> Option<&'a [EnumValue]> {
+        let name = match &selector.kind {
+            ExprKind::Ident(name) => name,
+            ExprKind::Paren(inner) => return self.selector_enum(inner),
+            _ => return None,
+        };
+
+        let var = self
+            .pou
+            .var_blocks
+            .iter()
+            .flat_map(|block| &block.vars)
+            .find(|var| var.name.eq_ignore_ascii_case(name))?;
+
+        let mut type_spec = &var.var_type;
+        // Follow aliases, bounded in case of cyclic declarations
+        for _ in 0..=self.types.len() {
+            let TypeKind::Simple(type_name) = &type_spec.kind else {
+                return None;
+            };
+            let decl = self
+                .types
+                .iter()
+                .find(|decl| decl.name.eq_ignore_ascii_case(type_name))?;
+            match &decl.definition {
+                TypeDef::Enum { values } => return Some(values),
+                TypeDef::Alias(spec) => type_spec = spec,
+                _ => return None,
+            }
+        }
+        None
+    }
+}
+
+/// List enum members not matched by any CASE label.
+fn uncovered_members(members: &[EnumValue], cases: &[CaseBranch]) -> Vec<String> {
+    let position = |expr: &Expr| {
+        let name = enum_label(expr)?;
+        members.iter().position(|m| m.name.eq_ignore_ascii_case(name))
+    };
+
+    let mut covered = vec![false; members.len()];
+    for value in cases.iter().flat_map(|branch| &branch.values) {
+        match value {
+            CaseValue::Single(expr) => {
+                if let Some(idx) = position(expr) {
+                    covered[idx] = true;
+                }
+            }
+            CaseValue::Range { from, to } => {
+                if let (Some(from), Some(to)) = (position(from), position(to)) {
+                    for flag in covered.iter_mut().take(to + 1).skip(from) {
+                        *flag = true;
+                    }
+                }
+            }
+        }
+    }
+
+    members
+        .iter()
+        .zip(covered)
+        .filter(|(_, covered)| !covered)
+        .map(|(member, _)| member.name.clone())
+        .collect()
+}
+
+/// Get the member name of an enum CASE label (`Idle` or `State.Idle`).
+fn enum_label(expr: &Expr) -> Option<&str> {
+    match &expr.kind {
+        ExprKind::Ident(name) => Some(name),
+        ExprKind::MemberAccess { member, .. } => Some(member),
+        ExprKind::Paren(inner) => enum_label(inner),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_pou, parse_type_block};
+
+    const TYPES: &str = "TYPE
+        MachineState : (Idle, Starting, Running, Stopping, Faulted);
+        State : MachineState;
+    END_TYPE";
+
+    fn check(code: &str) -> Vec<Diagnostic> {
+        let types = parse_type_block(TYPES).unwrap();
+        let pou = parse_pou(code).unwrap();
+        check_case_coverage(&pou, &types)
+    }
+
+    fn missing(diags: &[Diagnostic]) -> Vec<Vec<String>> {
+        diags
+            .iter()
+            .filter_map(|d| match &d.kind {
+                DiagnosticKind::NonExhaustiveCase { missing } => Some(missing.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_missing_members_reported() {
+        let diags = check(
+            "PROGRAM Main
+            VAR state : MachineState; x : INT; END_VAR
+            CASE state OF
+                Idle: x := 0;
+                Running, Stopping: x := 1;
+            END_CASE;
+            END_PROGRAM",
+        );
+        assert_eq!(missing(&diags), vec![vec!["Starting".to_string(), "Faulted".to_string()]]);
+    }
+
+    #[test]
+    fn test_range_covers_members() {
+        let diags = check(
+            "PROGRAM Main
+            VAR state : State; x : INT; END_VAR
+            CASE state OF
+                Idle..Stopping: x := 0;
+                Faulted: x := 1;
+            END_CASE;
+            END_PROGRAM",
+        );
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_else_and_integer_selectors_ignored() {
+        let diags = check(
+            "PROGRAM Main
+            VAR state : MachineState; x : INT; END_VAR
+            CASE state OF
+                Idle: x := 0;
+            ELSE
+                x := 1;
+            END_CASE;
+            CASE x OF
+                1: x := 2;
+            END_CASE;
+            END_PROGRAM",
+        );
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_nested_case_checked() {
+        let diags = check(
+            "PROGRAM Main
+            VAR state : MachineState; x : INT; END_VAR
+            IF x > 0 THEN
+                CASE state OF
+                    Idle, Starting, Running, Stopping: x := 0;
+                END_CASE;
+            END_IF;
+            END_PROGRAM",
+        );
+        assert_eq!(missing(&diags), vec![vec!["Faulted".to_string()]]);
+    }
+
+    fn overlaps(code: &str) -> Vec<String> {
+        let pou = parse_pou(code).unwrap();
+        check_case_labels(&pou).iter().map(|d| d.kind.to_string()).collect()
+    }
+
+    #[test]
+    fn test_duplicate_and_overlapping_labels() {
+        let diags = overlaps(
+            "PROGRAM Main
+            VAR x : INT; y : INT; END_VAR
+            CASE x OF
+                1..10: y := 0;
+                5..15: y := 1;
+                20: y := 2;
+                21, 20: y := 3;
+            END_CASE;
+            END_PROGRAM",
+        );
+        assert_eq!(
+            diags,
+            vec![
+                "CASE label '5..15' overlaps '1..10'".to_string(),
+                "duplicate CASE label '20'".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_folded_and_enum_labels() {
+        let diags = overlaps(
+            "PROGRAM Main
+            VAR CONSTANT STEP : INT := 10; END_VAR
+            VAR state : MachineState; x : INT; END_VAR
+            CASE x OF
+                STEP: x := 0;
+                2 * 5: x := 1;
+            END_CASE;
+            CASE state OF
+                Idle: x := 0;
+                Running, MachineState.Idle: x := 1;
+            END_CASE;
+            END_PROGRAM",
+        );
+        assert_eq!(
+            diags,
+            vec!["duplicate CASE label '10'".to_string(), "duplicate CASE label 'Idle'".to_string()]
+        );
+    }
+
+    #[test]
+    fn test_non_constant_labels_skipped() {
+        let diags = overlaps(
+            "PROGRAM Main
+            VAR x : INT; a : INT; END_VAR
+            CASE x OF
+                a: x := 0;
+                a: x := 1;
+                1: x := 2;
+            END_CASE;
+            END_PROGRAM",
+        );
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_overlap_reports_both_branches() {
+        let pou = parse_pou(
+            "PROGRAM Main
+            VAR x : INT; END_VAR
+            CASE x OF
+                1: x := 0;
+                1: x := 1;
+            END_CASE;
+            END_PROGRAM",
+        )
+        .unwrap();
+        let diags = check_case_labels(&pou);
+        assert_eq!(diags.len(), 1);
+        match &diags[0].kind {
+            DiagnosticKind::OverlappingCaseLabels { original, .. } => assert!(original.start < diags[0].span.start),
+            other => panic!("unexpected diagnostic: {:?}", other),
+        }
+    }
+}
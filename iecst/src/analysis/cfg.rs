@@ -29,6 +29,7 @@
 //! ```
 
 use crate::ast::{Stmt, StmtKind, Expr, ExprKind, BinaryOp};
+use crate::Span;
 use std::collections::{HashMap, HashSet};
 
 /// Unique identifier for a CFG node.
@@ -49,6 +50,9 @@ pub struct CfgNode {
     pub kind: NodeKind,
     /// Statements in this basic block (empty for Entry/Exit).
     pub statements: Vec<StmtRef>,
+    /// Expression evaluated by a branch or loop header (IF/ELSIF/WHILE/UNTIL
+    /// condition or CASE selector).
+    pub condition: Option<Expr>,
 }
 
 /// Reference to a statement (for tracking without cloning).
@@ -58,6 +62,8 @@ pub struct StmtRef {
     pub index: usize,
     /// Copy of the statement kind for analysis.
     pub kind: StmtKind,
+    /// Source span of the statement.
+    pub span: Span,
 }
 
 /// Kind of CFG node.
@@ -324,12 +330,13 @@ impl CfgBuilder {
             id,
             kind,
             statements: Vec::new(),
+            condition: None,
         });
         id
     }
 
     /// Create a basic block node with a statement.
-    fn create_basic_block(&mut self, stmt_index: usize, kind: &StmtKind) -> NodeId {
+    fn create_basic_block(&mut self, stmt_index: usize, stmt: &Stmt) -> NodeId {
         let id = NodeId(self.next_id);
         self.next_id += 1;
         self.nodes.push(CfgNode {
@@ -337,12 +344,21 @@ impl CfgBuilder {
             kind: NodeKind::Basic,
             statements: vec![StmtRef {
                 index: stmt_index,
-                kind: kind.clone(),
+                kind: stmt.kind.clone(),
+                span: stmt.span,
             }],
+            condition: None,
         });
         id
     }
 
+    /// Create a branch or loop header node evaluating `condition`.
+    fn create_condition_node(&mut self, kind: NodeKind, condition: &Expr) -> NodeId {
+        let id = self.create_node(kind);
+        self.nodes[id.0].condition = Some(condition.clone());
+        id
+    }
+
     /// Add an edge between two nodes.
     fn add_edge(&mut self, from: NodeId, to: NodeId, kind: EdgeKind) {
         self.edges.push(CfgEdge { from, to, kind });
@@ -388,38 +404,38 @@ impl CfgBuilder {
     fn process_statement(&mut self, stmt: &Stmt, stmt_index: usize) -> (NodeId, Vec<NodeId>, bool) {
         match &stmt.kind {
             StmtKind::Assignment { .. } | StmtKind::Call { .. } | StmtKind::Empty => {
-                let node = self.create_basic_block(stmt_index, &stmt.kind);
+                let node = self.create_basic_block(stmt_index, stmt);
                 (node, vec![node], false)
             }
 
-            StmtKind::If { condition: _, then_body, elsif_branches, else_body } => {
-                self.process_if(stmt_index, then_body, elsif_branches, else_body)
+            StmtKind::If { condition, then_body, elsif_branches, else_body } => {
+                self.process_if(stmt_index, condition, then_body, elsif_branches, else_body)
             }
 
-            StmtKind::Case { expr: _, cases, else_body } => {
-                self.process_case(stmt_index, cases, else_body)
+            StmtKind::Case { expr, cases, else_body } => {
+                self.process_case(stmt_index, expr, cases, else_body)
             }
 
             StmtKind::For { body, .. } => {
-                self.process_for(stmt_index, body)
+                self.process_for(stmt_index, stmt, body)
             }
 
-            StmtKind::While { body, .. } => {
-                self.process_while(stmt_index, body)
+            StmtKind::While { condition, body } => {
+                self.process_while(stmt_index, condition, body)
             }
 
-            StmtKind::Repeat { body, .. } => {
-                self.process_repeat(stmt_index, body)
+            StmtKind::Repeat { body, until } => {
+                self.process_repeat(stmt_index, body, until)
             }
 
             StmtKind::Return { .. } => {
-                let node = self.create_basic_block(stmt_index, &stmt.kind);
+                let node = self.create_basic_block(stmt_index, stmt);
                 // Return is terminal - no exits (will be connected to function exit)
                 (node, vec![], true)
             }
 
             StmtKind::Exit => {
-                let node = self.create_basic_block(stmt_index, &stmt.kind);
+                let node = self.create_basic_block(stmt_index, stmt);
                 // Connect to loop exit if in a loop
                 if let Some(&loop_exit) = self.loop_exits.last() {
                     self.add_edge(node, loop_exit, EdgeKind::LoopExit);
@@ -428,7 +444,7 @@ impl CfgBuilder {
             }
 
             StmtKind::Continue => {
-                let node = self.create_basic_block(stmt_index, &stmt.kind);
+                let node = self.create_basic_block(stmt_index, stmt);
                 // Connect to loop header if in a loop
                 if let Some(&loop_header) = self.loop_headers.last() {
                     self.add_edge(node, loop_header, EdgeKind::LoopBack);
@@ -442,14 +458,16 @@ impl CfgBuilder {
     fn process_if(
         &mut self,
         stmt_index: usize,
+        condition: &Expr,
         then_body: &[Stmt],
         elsif_branches: &[(Expr, Vec<Stmt>)],
         else_body: &Option<Vec<Stmt>>,
     ) -> (NodeId, Vec<NodeId>, bool) {
-        let branch = self.create_node(NodeKind::Branch);
+        let branch = self.create_condition_node(NodeKind::Branch, condition);
         self.nodes[branch.0].statements.push(StmtRef {
             index: stmt_index,
             kind: StmtKind::Empty, // Placeholder for condition
+            span: condition.span,
         });
 
         let mut all_exits = Vec::new();
@@ -463,8 +481,8 @@ impl CfgBuilder {
         let mut false_target = branch;
 
         // ELSIF branches
-        for (_, elsif_body) in elsif_branches {
-            let elsif_branch = self.create_node(NodeKind::Branch);
+        for (elsif_condition, elsif_body) in elsif_branches {
+            let elsif_branch = self.create_condition_node(NodeKind::Branch, elsif_condition);
             self.add_edge(false_target, elsif_branch, EdgeKind::FalseBranch);
 
             let (elsif_first, elsif_exits) = self.process_statements(elsif_body, stmt_index + 1);
@@ -491,10 +509,11 @@ impl CfgBuilder {
     fn process_case(
         &mut self,
         stmt_index: usize,
+        selector: &Expr,
         cases: &[crate::ast::CaseBranch],
         else_body: &Option<Vec<Stmt>>,
     ) -> (NodeId, Vec<NodeId>, bool) {
-        let branch = self.create_node(NodeKind::Branch);
+        let branch = self.create_condition_node(NodeKind::Branch, selector);
         let mut all_exits = Vec::new();
 
         // Each case branch
@@ -518,8 +537,35 @@ impl CfgBuilder {
     }
 
     /// Process FOR loop.
-    fn process_for(&mut self, stmt_index: usize, body: &[Stmt]) -> (NodeId, Vec<NodeId>, bool) {
+    ///
+    /// The header holds the FOR statement without its body, since it reads
+    /// the bounds and assigns the control variable.
+    fn process_for(&mut self, stmt_index: usize, stmt: &Stmt, body: &[Stmt]) -> (NodeId, Vec<NodeId>, bool) {
         let header = self.create_node(NodeKind::LoopHeader);
+        if let StmtKind::For { var, from, to, by, .. } = &stmt.kind {
+            self.nodes[header.0].statements.push(StmtRef {
+                index: stmt_index,
+                kind: StmtKind::For {
+                    var: var.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
+                    by: by.clone(),
+                    body: Vec::new(),
+                },
+                span: stmt.span,
+            });
+        }
+        self.process_loop(stmt_index, header, body)
+    }
+
+    /// Process WHILE loop.
+    fn process_while(&mut self, stmt_index: usize, condition: &Expr, body: &[Stmt]) -> (NodeId, Vec<NodeId>, bool) {
+        let header = self.create_condition_node(NodeKind::LoopHeader, condition);
+        self.process_loop(stmt_index, header, body)
+    }
+
+    /// Connect a FOR/WHILE loop header to its body and exit.
+    fn process_loop(&mut self, stmt_index: usize, header: NodeId, body: &[Stmt]) -> (NodeId, Vec<NodeId>, bool) {
         let loop_exit = self.create_node(NodeKind::LoopExit);
 
         // Push loop context for EXIT/CONTINUE
@@ -544,16 +590,10 @@ impl CfgBuilder {
         (header, vec![loop_exit], false)
     }
 
-    /// Process WHILE loop.
-    fn process_while(&mut self, stmt_index: usize, body: &[Stmt]) -> (NodeId, Vec<NodeId>, bool) {
-        // Same structure as FOR
-        self.process_for(stmt_index, body)
-    }
-
     /// Process REPEAT loop.
-    fn process_repeat(&mut self, stmt_index: usize, body: &[Stmt]) -> (NodeId, Vec<NodeId>, bool) {
+    fn process_repeat(&mut self, stmt_index: usize, body: &[Stmt], until: &Expr) -> (NodeId, Vec<NodeId>, bool) {
         let body_start = self.create_node(NodeKind::Basic);
-        let condition = self.create_node(NodeKind::LoopHeader);
+        let condition = self.create_condition_node(NodeKind::LoopHeader, until);
         let loop_exit = self.create_node(NodeKind::LoopExit);
 
         // Push loop context
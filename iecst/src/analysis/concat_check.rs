@@ -0,0 +1,184 @@
+//! String building in loops.
+//!
+//! Detects assignments inside FOR, WHILE and REPEAT bodies that extend a
+//! string with itself as an argument of CONCAT or INSERT, such as
+//! `s := CONCAT(s, x);`. Each iteration copies the whole accumulated string,
+//! so the loop takes quadratic time on many runtimes.
+
+use crate::ast::*;
+use crate::analysis::visit::visit_expr;
+use crate::analysis::{Diagnostic, DiagnosticKind};
+use crate::span::Span;
+
+/// Standard string functions that return a copy of an argument extended by
+/// another.
+const BUILDING_FUNCTIONS: [&str; 2] = ["CONCAT", "INSERT"];
+
+/// Check a POU for strings built up by repeated CONCAT or INSERT in loops.
+///
+/// The target must be a variable or member path (`s`, `msg.text`) passed
+/// as an argument of the call, possibly nested (`CONCAT(CONCAT(s, a), b)`).
+/// Array elements are not accumulators, since the index usually changes
+/// with each iteration. Each statement is reported once as a hint, with the
+/// innermost loop around it.
+pub fn check_string_concat_in_loops(pou: &Pou) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    check_body(&pou.body, None, &mut diagnostics);
+    diagnostics
+}
+
+/// The innermost loop around a statement: its keyword and span.
+type Loop = (&'static str, Span);
+
+fn check_body(body: &[Stmt], enclosing: Option<Loop>, diagnostics: &mut Vec<Diagnostic>) {
+    for stmt in body {
+        match &stmt.kind {
+            StmtKind::Assignment { target, value } => {
+                let Some((loop_kind, loop_span)) = enclosing else {
+                    continue;
+                };
+                let Some(path) = path_text(target) else {
+                    continue;
+                };
+                if let Some(function) = building_call(value, &path) {
+                    diagnostics.push(Diagnostic::hint(
+                        DiagnosticKind::StringConcatInLoop {
+                            target: path,
+                            function,
+                            loop_kind: loop_kind.to_string(),
+                            loop_span,
+                        },
+                        stmt.span,
+                    ));
+                }
+            }
+
+            StmtKind::For { body, .. } => check_body(body, Some(("FOR", stmt.span)), diagnostics),
+            StmtKind::While { body, .. } => check_body(body, Some(("WHILE", stmt.span)), diagnostics),
+            StmtKind::Repeat { body, .. } => check_body(body, Some(("REPEAT", stmt.span)), diagnostics),
+
+            StmtKind::If { then_body, elsif_branches, else_body, .. } => {
+                check_body(then_body, enclosing, diagnostics);
+                for (_, body) in elsif_branches {
+                    check_body(body, enclosing, diagnostics);
+                }
+                if let Some(else_stmts) = else_body {
+                    check_body(else_stmts, enclosing, diagnostics);
+                }
+            }
+
+            StmtKind::Case { cases, else_body, .. } => {
+                for branch in cases {
+                    check_body(&branch.body, enclosing, diagnostics);
+                }
+                if let Some(else_stmts) = else_body {
+                    check_body(else_stmts, enclosing, diagnostics);
+                }
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/// The string function in `value` that takes the variable `path` as an
+/// argument, if any.
+fn building_call(value: &Expr, path: &str) -> Option<String> {
+    let mut found = None;
+    visit_expr(value, &mut |expr| {
+        let ExprKind::FunctionCall { name, args } = &expr.kind else {
+            return;
+        };
+        if found.is_some() || !BUILDING_FUNCTIONS.iter().any(|f| name.eq_ignore_ascii_case(f)) {
+            return;
+        }
+        let passes_target = args
+            .iter()
+            .filter(|arg| !arg.output)
+            .filter_map(|arg| arg.value.as_ref().and_then(path_text))
+            .any(|arg| arg.eq_ignore_ascii_case(path));
+        if passes_target {
+            found = Some(name.to_uppercase());
+        }
+    });
+    found
+}
+
+/// Render a variable or member path as source text.
+fn path_text(expr: &Expr) -> Option<String> {
+    match &expr.kind {
+        ExprKind::Ident(name) => Some(name.clone()),
+        ExprKind::Paren(inner) => path_text(inner),
+        ExprKind::MemberAccess { expr, member } => Some(format!("{}.{}", path_text(expr)?, member)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<String> {
+        let code = format!(
+            "PROGRAM Main
+            VAR s : STRING[200]; t : STRING[200]; msg : Message; lines : ARRAY[1..5] OF STRING; i : INT; END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        let pou = parse_pou(&code).unwrap();
+        check_string_concat_in_loops(&pou).iter().map(|d| d.kind.to_string()).collect()
+    }
+
+    #[test]
+    fn test_concat_in_loops() {
+        let diags = check(
+            "FOR i := 1 TO 5 DO
+                s := CONCAT(s, lines[i]);
+                IF i > 2 THEN msg.text := CONCAT(CONCAT(Msg.Text, ', '), lines[i]); END_IF;
+            END_FOR;
+            WHILE i < 10 DO s := INSERT(IN1 := t, IN2 := s, P := 1); i := i + 1; END_WHILE;
+            REPEAT t := CONCAT('x', t); UNTIL i > 0;",
+        );
+        assert_eq!(
+            diags,
+            [
+                "'s' is extended with CONCAT on every iteration of the FOR loop, copying the whole string each time",
+                "'msg.text' is extended with CONCAT on every iteration of the FOR loop, copying the whole string each time",
+                "'s' is extended with INSERT on every iteration of the WHILE loop, copying the whole string each time",
+                "'t' is extended with CONCAT on every iteration of the REPEAT loop, copying the whole string each time",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_not_flagged() {
+        let diags = check(
+            "s := CONCAT(s, 'x');
+            FOR i := 1 TO 5 DO
+                t := CONCAT(s, lines[i]);
+                lines[i] := CONCAT(lines[i], 'x');
+                s := LEFT(s, 3);
+            END_FOR;",
+        );
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_innermost_loop() {
+        let code = "PROGRAM Main
+            VAR s : STRING; i : INT; j : INT; END_VAR
+            FOR i := 1 TO 5 DO WHILE j < 3 DO s := CONCAT(s, 'x'); END_WHILE; END_FOR;
+            END_PROGRAM";
+        let pou = parse_pou(code).unwrap();
+        let diags = check_string_concat_in_loops(&pou);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].severity, crate::analysis::Severity::Hint);
+        let DiagnosticKind::StringConcatInLoop { loop_kind, loop_span, .. } = &diags[0].kind else {
+            panic!("expected StringConcatInLoop");
+        };
+        assert_eq!(loop_kind, "WHILE");
+        assert!(code[loop_span.start..loop_span.end].starts_with("WHILE j < 3"));
+    }
+}
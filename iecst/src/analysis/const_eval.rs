@@ -0,0 +1,95 @@
+//! Constant folding for integer expressions.
+
+use std::collections::HashMap;
+
+use crate::ast::*;
+
+/// Collect the integer constants declared in a POU's `VAR CONSTANT` blocks.
+///
+/// Keys are uppercase names. Constants whose initial value cannot be folded
+/// are left out.
+pub fn pou_constants(pou: &Pou) -> HashMap<String, i64> {
+    let mut constants = HashMap::new();
+    for block in pou.var_blocks.iter().filter(|b| b.constant) {
+        for var in &block.vars {
+            if let Some(value) = var.initial.as_ref().and_then(|e| eval_const_int(e, &constants)) {
+                constants.insert(var.name.to_uppercase(), value);
+            }
+        }
+    }
+    constants
+}
+
+/// Evaluate an integer constant expression.
+///
+/// Identifiers are resolved through `constants` (uppercase keys). Returns
+/// `None` for anything that is not a compile-time integer constant, and on
+/// overflow or division by zero.
+pub fn eval_const_int(expr: &Expr, constants: &HashMap<String, i64>) -> Option<i64> {
+    match &expr.kind {
+        ExprKind::IntLiteral(value) => Some(*value),
+        ExprKind::Ident(name) => constants.get(&name.to_uppercase()).copied(),
+        ExprKind::Paren(inner) => eval_const_int(inner, constants),
+        ExprKind::UnaryOp { op: UnaryOp::Neg, expr } => eval_const_int(expr, constants)?.checked_neg(),
+        ExprKind::BinaryOp { left, op, right } => {
+            let l = eval_const_int(left, constants)?;
+            let r = eval_const_int(right, constants)?;
+            match op {
+                BinaryOp::Add => l.checked_add(r),
+                BinaryOp::Sub => l.checked_sub(r),
+                BinaryOp::Mul => l.checked_mul(r),
+                BinaryOp::Div => l.checked_div(r),
+                BinaryOp::Mod => l.checked_rem(r),
+                BinaryOp::Power => l.checked_pow(u32::try_from(r).ok()?),
+                BinaryOp::And => Some(l & r),
+                BinaryOp::Or => Some(l | r),
+                BinaryOp::Xor => Some(l ^ r),
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_expression, parse_pou};
+
+    fn eval(code: &str) -> Option<i64> {
+        eval_const_int(&parse_expression(code).unwrap(), &HashMap::new())
+    }
+
+    #[test]
+    fn test_fold_arithmetic() {
+        assert_eq!(eval("1 + 2 * 3"), Some(7));
+        assert_eq!(eval("-(10 - 4) / 2"), Some(-3));
+        assert_eq!(eval("2 ** 10"), Some(1024));
+        assert_eq!(eval("17 MOD 5"), Some(2));
+    }
+
+    #[test]
+    fn test_non_constant_skipped() {
+        assert_eq!(eval("x + 1"), None);
+        assert_eq!(eval("1 / 0"), None);
+        assert_eq!(eval("1.5"), None);
+    }
+
+    #[test]
+    fn test_pou_constants() {
+        let pou = parse_pou(
+            "PROGRAM Main
+            VAR CONSTANT
+                BASE : INT := 10;
+                LIMIT : INT := BASE * 2;
+            END_VAR
+            VAR x : INT := 5; END_VAR
+            END_PROGRAM",
+        )
+        .unwrap();
+        let constants = pou_constants(&pou);
+        assert_eq!(constants.get("LIMIT"), Some(&20));
+        assert_eq!(constants.get("X"), None);
+        assert_eq!(eval_const_int(&parse_expression("limit + 1").unwrap(), &constants), Some(21));
+    }
+}
@@ -0,0 +1,437 @@
+//! Data dependency tracking.
+//!
+//! Answers whether a variable can influence another one: an emergency stop
+//! input that has no path to a given output is a finding for a safety review.
+//!
+//! The graph is flow-insensitive on purpose. A PLC task runs its body every
+//! scan and variables keep their values between scans, so an assignment
+//! late in the body still reaches a read early in the body one scan later.
+//!
+//! Variables are named by their uppercase access path (`MOTOR.RUN`), with
+//! array indices dropped (`VALVES[i].OPEN` is `VALVES.OPEN`). A write to a
+//! path reaches reads of the same path, of a prefix (the whole structure)
+//! and of an extension (a member).
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::ast::*;
+use crate::span::Span;
+
+/// How a value flows from one variable to another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FlowKind {
+    /// Read on the right side of an assignment (or as an array index of the target)
+    Assignment,
+    /// Read in an IF, CASE or loop condition that guards the assignment
+    Condition,
+    /// Passed to a function or function block call
+    CallArgument,
+}
+
+/// A dependency edge: `to` is written from a value that depends on `from`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FlowEdge {
+    /// Variable read
+    pub from: String,
+    /// Variable written
+    pub to: String,
+    /// Kind of dependency
+    pub kind: FlowKind,
+    /// Statement that creates the dependency
+    pub span: Span,
+}
+
+/// Data and control dependencies between the variables of one or more bodies.
+#[derive(Debug, Clone, Default)]
+pub struct DataFlow {
+    /// All dependency edges, in source order
+    pub edges: Vec<FlowEdge>,
+}
+
+impl DataFlow {
+    /// Create an empty dependency graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build the dependency graph of a POU body.
+    pub fn from_pou(pou: &Pou) -> Self {
+        let mut flow = Self::new();
+        flow.add_body(&pou.body);
+        flow
+    }
+
+    /// Add the dependencies of a body. Bodies that share global variables
+    /// (the routines of a controller) can be added to one graph.
+    pub fn add_body(&mut self, body: &[Stmt]) {
+        self.add_statements(body, &mut Vec::new());
+    }
+
+    /// Whether a value of `source` can reach `sink`.
+    pub fn influences(&self, source: &str, sink: &str) -> bool {
+        self.path(source, sink).is_some()
+    }
+
+    /// Shortest chain of dependencies from `source` to `sink`.
+    ///
+    /// Returns an empty path when the two names overlap (same variable, or
+    /// one is a member of the other), and `None` when there is no path.
+    pub fn path(&self, source: &str, sink: &str) -> Option<Vec<FlowEdge>> {
+        let source = source.to_uppercase();
+        let sink = sink.to_uppercase();
+        if overlaps(&source, &sink) {
+            return Some(Vec::new());
+        }
+
+        // Breadth-first search over written paths, remembering for each
+        // edge taken the edge that wrote the variable it reads
+        let mut parents: HashMap<usize, Option<usize>> = HashMap::new();
+        let mut written: HashSet<&str> = HashSet::new();
+        let mut queue: VecDeque<(&str, Option<usize>)> = VecDeque::new();
+        queue.push_back((&source, None));
+        while let Some((current, parent)) = queue.pop_front() {
+            for (index, edge) in self.edges.iter().enumerate() {
+                if !overlaps(&edge.from, current) || edge.to == source || !written.insert(&edge.to) {
+                    continue;
+                }
+                parents.insert(index, parent);
+                if overlaps(&edge.to, &sink) {
+                    let mut path = vec![self.edges[index].clone()];
+                    let mut step = parent;
+                    while let Some(index) = step {
+                        path.push(self.edges[index].clone());
+                        step = parents[&index];
+                    }
+                    path.reverse();
+                    return Some(path);
+                }
+                queue.push_back((&edge.to, Some(index)));
+            }
+        }
+        None
+    }
+
+    fn add_statements(&mut self, body: &[Stmt], guards: &mut Vec<String>) {
+        for stmt in body {
+            let span = stmt.span;
+            match &stmt.kind {
+                StmtKind::Assignment { target, value } => {
+                    let Some(written) = access_path(target) else {
+                        continue;
+                    };
+                    let mut reads = Vec::new();
+                    collect_reads(value, &mut reads);
+                    collect_index_reads(target, &mut reads);
+                    self.add_edges(&reads, &written, FlowKind::Assignment, span);
+                    self.add_guards(guards, &written, span);
+                    self.add_expr_calls(value, guards, span);
+                }
+
+                StmtKind::If { condition, then_body, elsif_branches, else_body } => {
+                    let depth = guards.len();
+                    collect_reads(condition, guards);
+                    self.add_expr_calls(condition, guards, span);
+                    self.add_statements(then_body, guards);
+                    for (condition, body) in elsif_branches {
+                        collect_reads(condition, guards);
+                        self.add_expr_calls(condition, guards, span);
+                        self.add_statements(body, guards);
+                    }
+                    if let Some(body) = else_body {
+                        self.add_statements(body, guards);
+                    }
+                    guards.truncate(depth);
+                }
+
+                StmtKind::Case { expr, cases, else_body } => {
+                    let depth = guards.len();
+                    collect_reads(expr, guards);
+                    self.add_expr_calls(expr, guards, span);
+                    for branch in cases {
+                        self.add_statements(&branch.body, guards);
+                    }
+                    if let Some(body) = else_body {
+                        self.add_statements(body, guards);
+                    }
+                    guards.truncate(depth);
+                }
+
+                StmtKind::For { var, from, to, by, body } => {
+                    let mut reads = Vec::new();
+                    collect_reads(from, &mut reads);
+                    collect_reads(to, &mut reads);
+                    if let Some(by) = by {
+                        collect_reads(by, &mut reads);
+                    }
+                    let var = var.to_uppercase();
+                    self.add_edges(&reads, &var, FlowKind::Assignment, span);
+                    self.add_guards(guards, &var, span);
+
+                    let depth = guards.len();
+                    guards.extend(reads);
+                    guards.push(var);
+                    self.add_statements(body, guards);
+                    guards.truncate(depth);
+                }
+
+                StmtKind::While { condition, body } | StmtKind::Repeat { body, until: condition } => {
+                    let depth = guards.len();
+                    collect_reads(condition, guards);
+                    self.add_expr_calls(condition, guards, span);
+                    self.add_statements(body, guards);
+                    guards.truncate(depth);
+                }
+
+                StmtKind::Call { name, args } => {
+                    let instance = name.to_uppercase();
+                    let mut inputs = Vec::new();
+                    for arg in args.iter().filter(|arg| !arg.output) {
+                        if let Some(value) = &arg.value {
+                            collect_reads(value, &mut inputs);
+                            // Positional variables of instructions like TONR(Timer) are updated in place
+                            if arg.name.is_none() {
+                                if let Some(updated) = access_path(value) {
+                                    self.add_edge(&updated, &updated, FlowKind::CallArgument, span);
+                                    self.add_guards(guards, &updated, span);
+                                }
+                            }
+                            self.add_expr_calls(value, guards, span);
+                        }
+                    }
+                    self.add_edges(&inputs, &instance, FlowKind::CallArgument, span);
+                    self.add_guards(guards, &instance, span);
+                    for arg in args.iter().filter(|arg| arg.output) {
+                        if let Some(written) = arg.value.as_ref().and_then(access_path) {
+                            self.add_edge(&instance, &written, FlowKind::CallArgument, span);
+                            self.add_guards(guards, &written, span);
+                        }
+                    }
+                }
+
+                StmtKind::Return { value: Some(value) } => self.add_expr_calls(value, guards, span),
+
+                _ => {}
+            }
+        }
+    }
+
+    /// Output bindings (`param => var`) of function calls inside an expression.
+    fn add_expr_calls(&mut self, expr: &Expr, guards: &[String], span: Span) {
+        match &expr.kind {
+            ExprKind::FunctionCall { args, .. } => {
+                let mut inputs = Vec::new();
+                for arg in args.iter().filter(|arg| !arg.output) {
+                    if let Some(value) = &arg.value {
+                        collect_reads(value, &mut inputs);
+                        self.add_expr_calls(value, guards, span);
+                    }
+                }
+                for arg in args.iter().filter(|arg| arg.output) {
+                    if let Some(written) = arg.value.as_ref().and_then(access_path) {
+                        self.add_edges(&inputs, &written, FlowKind::CallArgument, span);
+                        self.add_guards(guards, &written, span);
+                    }
+                }
+            }
+            ExprKind::BinaryOp { left, right, .. } => {
+                self.add_expr_calls(left, guards, span);
+                self.add_expr_calls(right, guards, span);
+            }
+            ExprKind::UnaryOp { expr, .. } | ExprKind::Paren(expr) | ExprKind::MemberAccess { expr, .. } => {
+                self.add_expr_calls(expr, guards, span)
+            }
+            ExprKind::ArrayIndex { array, indices } => {
+                self.add_expr_calls(array, guards, span);
+                for index in indices {
+                    self.add_expr_calls(index, guards, span);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn add_guards(&mut self, guards: &[String], written: &str, span: Span) {
+        for guard in guards {
+            self.add_edge(guard, written, FlowKind::Condition, span);
+        }
+    }
+
+    fn add_edges(&mut self, reads: &[String], written: &str, kind: FlowKind, span: Span) {
+        for read in reads {
+            self.add_edge(read, written, kind, span);
+        }
+    }
+
+    fn add_edge(&mut self, from: &str, to: &str, kind: FlowKind, span: Span) {
+        self.edges.push(FlowEdge {
+            from: from.to_string(),
+            to: to.to_string(),
+            kind,
+            span,
+        });
+    }
+}
+
+/// Whether a value of `source` can reach `sink` in a POU.
+pub fn influences(pou: &Pou, source: &str, sink: &str) -> bool {
+    DataFlow::from_pou(pou).influences(source, sink)
+}
+
+/// Shortest chain of dependencies from `source` to `sink` in a POU.
+pub fn influence_path(pou: &Pou, source: &str, sink: &str) -> Option<Vec<FlowEdge>> {
+    DataFlow::from_pou(pou).path(source, sink)
+}
+
+/// Whether one access path equals the other or is one of its members.
+fn overlaps(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    long.starts_with(short) && (long.len() == short.len() || long[short.len()..].starts_with('.'))
+}
+
+/// Uppercase access path of a variable reference, without array indices.
+fn access_path(expr: &Expr) -> Option<String> {
+    match &expr.kind {
+        ExprKind::Ident(name) => Some(name.to_uppercase()),
+        ExprKind::MemberAccess { expr, member } => {
+            access_path(expr).map(|base| format!("{}.{}", base, member.to_uppercase()))
+        }
+        ExprKind::ArrayIndex { array, .. } => access_path(array),
+        ExprKind::Paren(inner) => access_path(inner),
+        ExprKind::DirectAddress(address) => Some(direct_address(address)),
+        _ => None,
+    }
+}
+
+/// `%IX0.0` style name of a direct address.
+pub(crate) fn direct_address(address: &DirectAddress) -> String {
+    let location = match address.location {
+        LocationPrefix::Input => 'I',
+        LocationPrefix::Output => 'Q',
+        LocationPrefix::Memory => 'M',
+    };
+    let size = match address.size {
+        SizePrefix::Bit => 'X',
+        SizePrefix::Byte => 'B',
+        SizePrefix::Word => 'W',
+        SizePrefix::DoubleWord => 'D',
+        SizePrefix::LongWord => 'L',
+    };
+    format!("%{}{}{}", location, size, address.address)
+}
+
+/// Variables read by an expression, including function call inputs.
+fn collect_reads(expr: &Expr, reads: &mut Vec<String>) {
+    if let Some(path) = access_path(expr) {
+        reads.push(path);
+        collect_index_reads(expr, reads);
+        return;
+    }
+    match &expr.kind {
+        ExprKind::BinaryOp { left, right, .. } => {
+            collect_reads(left, reads);
+            collect_reads(right, reads);
+        }
+        ExprKind::UnaryOp { expr, .. } | ExprKind::Paren(expr) | ExprKind::MemberAccess { expr, .. } => {
+            collect_reads(expr, reads)
+        }
+        ExprKind::ArrayIndex { array, indices } => {
+            collect_reads(array, reads);
+            for index in indices {
+                collect_reads(index, reads);
+            }
+        }
+        ExprKind::FunctionCall { args, .. } => {
+            for value in args.iter().filter(|arg| !arg.output).filter_map(|arg| arg.value.as_ref()) {
+                collect_reads(value, reads);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Variables read as array indices of an access path.
+fn collect_index_reads(expr: &Expr, reads: &mut Vec<String>) {
+    match &expr.kind {
+        ExprKind::ArrayIndex { array, indices } => {
+            collect_index_reads(array, reads);
+            for index in indices {
+                collect_reads(index, reads);
+            }
+        }
+        ExprKind::MemberAccess { expr, .. } | ExprKind::Paren(expr) => collect_index_reads(expr, reads),
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn flow(body: &str) -> DataFlow {
+        let code = format!(
+            "PROGRAM Main
+            VAR
+                EStop : BOOL;
+                Start : BOOL;
+                Run : BOOL;
+                Motor : BOOL;
+                Lamp : BOOL;
+                Fault : BOOL;
+                Other : BOOL;
+                Speed : INT;
+                Setpoint : INT;
+                i : INT;
+                Speeds : ARRAY[1..4] OF INT;
+                Timer : TON;
+            END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        DataFlow::from_pou(&parse_pou(&code).unwrap())
+    }
+
+    #[test]
+    fn test_assignment_chain() {
+        let flow = flow("Run := Start AND NOT EStop; Motor := Run; Lamp := Other;");
+        let path = flow.path("estop", "motor").unwrap();
+        let steps: Vec<_> = path.iter().map(|e| (e.from.as_str(), e.to.as_str())).collect();
+        assert_eq!(steps, [("ESTOP", "RUN"), ("RUN", "MOTOR")]);
+        assert!(!flow.influences("EStop", "Lamp"));
+        assert!(!flow.influences("Motor", "EStop"));
+        assert_eq!(flow.path("Motor", "Motor"), Some(Vec::new()));
+    }
+
+    #[test]
+    fn test_conditions() {
+        let flow = flow(
+            "IF EStop THEN Motor := FALSE; ELSIF Start THEN Motor := TRUE; END_IF;
+             CASE Setpoint OF 1: Lamp := TRUE; END_CASE;
+             WHILE Fault DO Speed := 0; END_WHILE;
+             Other := Lamp;",
+        );
+        let path = flow.path("EStop", "Motor").unwrap();
+        assert_eq!(path[0].kind, FlowKind::Condition);
+        assert!(flow.influences("Start", "Motor"));
+        assert!(flow.influences("Setpoint", "Other"));
+        assert!(flow.influences("Fault", "Speed"));
+        assert!(!flow.influences("Start", "Lamp"));
+    }
+
+    #[test]
+    fn test_calls_and_members() {
+        let flow = flow(
+            "Timer(IN := Start, PT := T#5s, Q => Run);
+             Lamp := Timer.Q;
+             Speeds[i] := LIMIT(0, Setpoint, 100);
+             Speed := Speeds[1];",
+        );
+        let path = flow.path("Start", "Run").unwrap();
+        assert!(path.iter().all(|e| e.kind == FlowKind::CallArgument));
+        assert!(flow.influences("Start", "Lamp"));
+        assert!(flow.influences("Setpoint", "Speed"));
+        assert!(flow.influences("i", "Speed"));
+        assert!(!flow.influences("EStop", "Speed"));
+    }
+}
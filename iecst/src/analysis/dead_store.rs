@@ -0,0 +1,328 @@
+//! Dead store checks.
+//!
+//! A backward liveness pass over the POU's control flow graph: a variable
+//! is live after a statement when some path from there reads it before
+//! overwriting it. An assignment whose variable is not live afterwards is
+//! overwritten on every path before anyone reads the value, so it has no
+//! effect; usually a later, unconditional assignment clobbers a value that
+//! was meant to be kept.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::ast::*;
+use crate::analysis::init_check::{expr_reads, stmt_reads_and_writes, Access};
+use crate::analysis::visit::visit_expressions;
+use crate::analysis::{CfgBuilder, CfgNode, Diagnostic, DiagnosticKind, NodeId};
+use crate::span::Span;
+
+/// Check a POU for assignments whose value is overwritten before it is read.
+///
+/// Every variable counts as read at the end of the POU, since outputs,
+/// function block and program variables keep their value after the call;
+/// so only write-after-write on every path is reported (`DeadStore`,
+/// warning, with the span of the overwriting statement). Writes of members
+/// or elements neither read nor overwrite the whole variable. Global,
+/// external and in-out variables, located variables and variables whose
+/// address is taken are not checked, since other code can read them in
+/// between; a `JSR` reads every variable.
+pub fn check_dead_stores(pou: &Pou) -> Vec<Diagnostic> {
+    let tracked = tracked_variables(pou);
+    if tracked.is_empty() {
+        return Vec::new();
+    }
+
+    let cfg = CfgBuilder::new().build(&pou.body);
+    let steps: HashMap<NodeId, Vec<Step>> = cfg.nodes.iter().map(|node| (node.id, node_steps(node))).collect();
+
+    // Live variables at the start of each node; everything is live at the exit
+    let mut live_in: HashMap<NodeId, HashSet<String>> = cfg
+        .nodes
+        .iter()
+        .map(|node| (node.id, if node.id == cfg.exit { tracked.clone() } else { HashSet::new() }))
+        .collect();
+    let live_out = |live_in: &HashMap<NodeId, HashSet<String>>, node: NodeId| {
+        let mut live = HashSet::new();
+        for succ in cfg.successors(node) {
+            live.extend(live_in[succ].iter().cloned());
+        }
+        live
+    };
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for node in cfg.nodes.iter().rev() {
+            if node.id == cfg.exit {
+                continue;
+            }
+            let mut live = live_out(&live_in, node.id);
+            for step in steps[&node.id].iter().rev() {
+                step.transfer(&mut live, &tracked);
+            }
+            if live != live_in[&node.id] {
+                live_in.insert(node.id, live);
+                changed = true;
+            }
+        }
+    }
+
+    let unreachable: HashSet<NodeId> = cfg.unreachable_nodes().into_iter().collect();
+    let mut diagnostics = Vec::new();
+    for node in &cfg.nodes {
+        if node.id == cfg.exit || unreachable.contains(&node.id) {
+            continue;
+        }
+        let node_steps = &steps[&node.id];
+        let mut live = live_out(&live_in, node.id);
+        for (index, step) in node_steps.iter().enumerate().rev() {
+            if let Some(ref name) = step.store {
+                if tracked.contains(name) && !live.contains(name) {
+                    if let Some(overwritten_by) = overwriting_step(&cfg, &steps, node.id, index + 1, name) {
+                        diagnostics.push(Diagnostic::warning(
+                            DiagnosticKind::DeadStore {
+                                name: declared_name(pou, name),
+                                overwritten_by,
+                            },
+                            step.span,
+                        ));
+                    }
+                }
+            }
+            step.transfer(&mut live, &tracked);
+        }
+    }
+
+    diagnostics.sort_by_key(|d| d.span.start);
+    diagnostics
+}
+
+/// A condition or statement of a CFG node.
+struct Step {
+    /// Variable accesses in evaluation order
+    accesses: Vec<Access>,
+    /// Variable an assignment statement writes as a whole (uppercase)
+    store: Option<String>,
+    /// Call of another routine, which can read any variable
+    reads_all: bool,
+    span: Span,
+}
+
+impl Step {
+    /// Turn the variables live after the step into those live before it.
+    fn transfer(&self, live: &mut HashSet<String>, tracked: &HashSet<String>) {
+        for access in self.accesses.iter().rev() {
+            match access {
+                Access::Read(name) => {
+                    live.insert(name.clone());
+                }
+                Access::Write(name) => {
+                    live.remove(name);
+                }
+                Access::Update(_) => {}
+            }
+        }
+        if self.reads_all {
+            live.extend(tracked.iter().cloned());
+        }
+    }
+
+    fn writes(&self, name: &str) -> bool {
+        self.accesses.iter().any(|access| matches!(access, Access::Write(n) if n == name))
+    }
+}
+
+fn node_steps(node: &CfgNode) -> Vec<Step> {
+    let mut steps = Vec::new();
+    if let Some(condition) = &node.condition {
+        let mut accesses = Vec::new();
+        expr_reads(condition, &mut accesses);
+        steps.push(Step {
+            accesses,
+            store: None,
+            reads_all: false,
+            span: condition.span,
+        });
+    }
+    for stmt in &node.statements {
+        let mut accesses = Vec::new();
+        stmt_reads_and_writes(&stmt.kind, &mut accesses);
+        let store = match &stmt.kind {
+            StmtKind::Assignment { target, .. } => whole_variable(target).map(str::to_uppercase),
+            _ => None,
+        };
+        let reads_all = matches!(&stmt.kind, StmtKind::Call { name, .. } if name.eq_ignore_ascii_case("JSR"));
+        steps.push(Step {
+            accesses,
+            store,
+            reads_all,
+            span: stmt.span,
+        });
+    }
+    steps
+}
+
+/// Variable an assignment target writes as a whole.
+fn whole_variable(target: &Expr) -> Option<&str> {
+    match &target.kind {
+        ExprKind::Ident(name) => Some(name),
+        ExprKind::Paren(inner) => whole_variable(inner),
+        _ => None,
+    }
+}
+
+/// Span of the first statement found that overwrites `name`, searching the
+/// paths from step `index` of `node`.
+fn overwriting_step(
+    cfg: &crate::analysis::Cfg,
+    steps: &HashMap<NodeId, Vec<Step>>,
+    node: NodeId,
+    index: usize,
+    name: &str,
+) -> Option<Span> {
+    let mut queue = VecDeque::from([(node, index)]);
+    let mut visited = HashSet::new();
+    while let Some((node, index)) = queue.pop_front() {
+        if let Some(step) = steps[&node].iter().skip(index).find(|step| step.writes(name)) {
+            return Some(step.span);
+        }
+        for succ in cfg.successors(node) {
+            if visited.insert(*succ) {
+                queue.push_back((*succ, 0));
+            }
+        }
+    }
+    None
+}
+
+/// Uppercase names of the variables the check applies to.
+fn tracked_variables(pou: &Pou) -> HashSet<String> {
+    let mut address_taken = HashSet::new();
+    visit_expressions(&pou.body, &mut |expr| {
+        if let ExprKind::FunctionCall { name, args } = &expr.kind {
+            if ["ADR", "REF", "ADRINST"].iter().any(|f| name.eq_ignore_ascii_case(f)) {
+                for arg in args {
+                    if let Some(ExprKind::Ident(var)) = arg.value.as_ref().map(|value| &value.kind) {
+                        address_taken.insert(var.to_uppercase());
+                    }
+                }
+            }
+        }
+    });
+
+    let mut tracked: HashSet<String> = pou
+        .var_blocks
+        .iter()
+        .filter(|block| {
+            !block.constant
+                && matches!(
+                    block.kind,
+                    VarBlockKind::Var | VarBlockKind::VarTemp | VarBlockKind::VarInput | VarBlockKind::VarOutput
+                )
+        })
+        .flat_map(|block| &block.vars)
+        .filter(|var| var.location.is_none())
+        .map(|var| var.name.to_uppercase())
+        .filter(|name| !address_taken.contains(name))
+        .collect();
+    if pou.kind == PouKind::Function {
+        tracked.insert(pou.name.to_uppercase());
+    }
+    tracked
+}
+
+/// Declared spelling of a variable or the function result.
+fn declared_name(pou: &Pou, upper: &str) -> String {
+    pou.var_blocks
+        .iter()
+        .flat_map(|block| &block.vars)
+        .find(|var| var.name.eq_ignore_ascii_case(upper))
+        .map(|var| var.name.clone())
+        .unwrap_or_else(|| pou.name.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::Severity;
+    use crate::parse_pou;
+
+    fn dead_stores(body: &str) -> Vec<(String, String)> {
+        let code = format!(
+            "FUNCTION_BLOCK Filler
+            VAR_INPUT start : BOOL; level : INT; END_VAR
+            VAR_OUTPUT valve : BOOL; count : INT; END_VAR
+            VAR_EXTERNAL alarm : BOOL; END_VAR
+            VAR state : Recipe; END_VAR
+            {}
+            END_FUNCTION_BLOCK",
+            body
+        );
+        check_dead_stores(&parse_pou(&code).unwrap())
+            .into_iter()
+            .map(|d| {
+                assert_eq!(d.severity, Severity::Warning);
+                let DiagnosticKind::DeadStore { name, overwritten_by } = d.kind else {
+                    panic!("unexpected diagnostic {:?}", d.kind);
+                };
+                (name, code[d.span.start..d.span.end].to_string() + " / " + &code[overwritten_by.start..overwritten_by.end])
+            })
+            .map(|(name, stores)| (name, stores.replace(';', "")))
+            .collect()
+    }
+
+    #[test]
+    fn test_overwritten_before_read() {
+        let found = dead_stores(
+            "valve := start AND level < 100;
+            IF level > 50 THEN
+                count := count + 1;
+            END_IF;
+            valve := FALSE;",
+        );
+        assert_eq!(found, [("valve".to_string(), "valve := start AND level < 100 / valve := FALSE".to_string())]);
+    }
+
+    #[test]
+    fn test_accumulation_and_conditional_overwrite_not_reported() {
+        let found = dead_stores(
+            "count := level;
+            count := count + 1;
+            valve := start;
+            IF level > 90 THEN
+                valve := FALSE;
+            END_IF;
+            alarm := TRUE;
+            alarm := FALSE;",
+        );
+        assert!(found.is_empty(), "{:?}", found);
+    }
+
+    #[test]
+    fn test_overwritten_on_every_branch() {
+        let found = dead_stores(
+            "count := 0;
+            IF start THEN
+                count := 1;
+            ELSE
+                count := 2;
+            END_IF;
+            state.step := 1;
+            state := state;",
+        );
+        assert_eq!(found, [("count".to_string(), "count := 0 / count := 1".to_string())]);
+    }
+
+    #[test]
+    fn test_value_read_in_loop_or_by_routine_call() {
+        let found = dead_stores(
+            "count := 0;
+            WHILE count < level DO
+                count := count + 1;
+            END_WHILE;
+            valve := TRUE;
+            JSR(Routine := Fill);
+            valve := FALSE;",
+        );
+        assert!(found.is_empty(), "{:?}", found);
+    }
+}
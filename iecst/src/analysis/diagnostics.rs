@@ -34,8 +34,18 @@ pub enum DiagnosticKind {
     DuplicateDefinition { name: String, original: Span },
     /// Unused variable
     UnusedVariable { name: String },
+    /// Input parameter that is never read
+    UnusedInput { name: String },
+    /// Output parameter that is never assigned
+    UnassignedOutput { name: String },
+    /// Temporary variable that is never used
+    UnusedTemp { name: String },
     /// Uninitialized variable
     UninitializedVariable { name: String },
+    /// Local variable read on a path where it has not been assigned
+    UninitializedRead { name: String },
+    /// Assignment whose value is overwritten on every path before it is read
+    DeadStore { name: String, overwritten_by: Span },
     /// Assignment to constant
     AssignmentToConstant { name: String },
     /// Assignment to input parameter
@@ -56,6 +66,12 @@ pub enum DiagnosticKind {
     NonIntegerArrayIndex,
     /// Array dimension mismatch
     ArrayDimensionMismatch { expected: usize, found: usize },
+    /// Constant array index outside the declared range (dimension is 1-based, multi-dimensional arrays only)
+    ArrayIndexOutOfBounds { name: String, index: i64, low: i64, high: i64, dimension: Option<usize> },
+    /// Declared array range with its high bound below its low bound (dimension is 1-based, multi-dimensional arrays only)
+    EmptyArrayRange { name: String, low: i64, high: i64, dimension: Option<usize> },
+    /// Declared array range of a single element
+    SingleElementArrayRange { name: String, bound: i64, dimension: Option<usize> },
 
     // Code smells
     /// Empty statement block
@@ -78,10 +94,56 @@ pub enum DiagnosticKind {
     ShadowedVariable { name: String, original: Span },
     /// Empty CASE branch
     EmptyCaseBranch,
+    /// Empty IF/ELSIF/ELSE branch or loop body
+    EmptyBody { construct: String },
     /// Missing ELSE in CASE
     MissingCaseElse,
+    /// CASE over an enum misses members and has no ELSE
+    NonExhaustiveCase { missing: Vec<String> },
+    /// CASE label matches values already handled by an earlier label
+    OverlappingCaseLabels { label: String, other: String, original: Span },
     /// Comparison with assignment (= vs :=)
     PossibleAssignmentInCondition,
+    /// FOR control variable written inside the loop body
+    LoopVariableModified { name: String },
+    /// Variable assigned to itself
+    SelfAssignment { path: String },
+    /// Variable set to TRUE and FALSE by unconditional assignments in sequence
+    BoolToggle { path: String, value: bool, last: Span },
+    /// Boolean expression with a repeated or complementary operand, or a double negation
+    RedundantBoolean { expr: String, simplified: String },
+    /// Enum member with the same value as an earlier member
+    DuplicateEnumValue { type_name: String, member: String, value: i64, other: String, original: Span },
+    /// Explicit enum value lower than the preceding member's
+    DecreasingEnumValue { type_name: String, member: String, value: i64, previous: String, previous_value: i64 },
+    /// Structure member declared more than once
+    DuplicateStructMember { type_name: String, member: String, original: Span },
+    /// Structure that contains itself by value; the cycle starts and ends with the structure
+    RecursiveStruct { type_name: String, cycle: Vec<String> },
+    /// Equality comparison of floating-point values
+    FloatEquality { op: String },
+    /// String assigned to a shorter string target
+    StringTruncation { target: String, capacity: u32, length: u32 },
+    /// String extended with itself by CONCAT or INSERT inside a loop body
+    StringConcatInLoop { target: String, function: String, loop_kind: String, loop_span: Span },
+    /// Division or MOD by a constant zero
+    DivisionByZero,
+    /// Division or MOD by a variable not checked against zero
+    UnguardedDivision { divisor: String },
+    /// AND/OR/XOR with one BOOL and one integer or bit-string operand
+    MixedBitOperation { op: String, left: String, right: String },
+    /// Bitwise operation or shift on a signed integer
+    SignedBitOperation { op: String, ty: String },
+    /// Direct address (`%MW100`) used in a statement instead of a named variable
+    HardcodedAddress { address: String },
+    /// IF/ELSIF chain comparing one variable with distinct constants
+    IfChainToCase { selector: String, branches: usize },
+    /// Access chain deeper than the configured maximum
+    DeepMemberAccess { path: String, depth: usize, max_depth: usize },
+    /// Function writes a VAR_GLOBAL or VAR_EXTERNAL variable
+    GlobalWriteInFunction { function: String, name: String },
+    /// Function calls a function block instance
+    StatefulCallInFunction { function: String, instance: String },
 }
 
 impl fmt::Display for DiagnosticKind {
@@ -96,9 +158,24 @@ impl fmt::Display for DiagnosticKind {
             DiagnosticKind::UnusedVariable { name } => {
                 write!(f, "unused variable '{}'", name)
             }
+            DiagnosticKind::UnusedInput { name } => {
+                write!(f, "input '{}' is never read", name)
+            }
+            DiagnosticKind::UnassignedOutput { name } => {
+                write!(f, "output '{}' is never assigned", name)
+            }
+            DiagnosticKind::UnusedTemp { name } => {
+                write!(f, "unused temporary variable '{}'", name)
+            }
             DiagnosticKind::UninitializedVariable { name } => {
                 write!(f, "variable '{}' may be uninitialized", name)
             }
+            DiagnosticKind::UninitializedRead { name } => {
+                write!(f, "variable '{}' may be read before it is assigned", name)
+            }
+            DiagnosticKind::DeadStore { name, .. } => {
+                write!(f, "value assigned to '{}' is overwritten before it is read", name)
+            }
             DiagnosticKind::AssignmentToConstant { name } => {
                 write!(f, "cannot assign to constant '{}'", name)
             }
@@ -126,6 +203,32 @@ impl fmt::Display for DiagnosticKind {
             DiagnosticKind::ArrayDimensionMismatch { expected, found } => {
                 write!(f, "array dimension mismatch: expected {} indices, found {}", expected, found)
             }
+            DiagnosticKind::ArrayIndexOutOfBounds { name, index, low, high, dimension: None } => {
+                write!(f, "index {} is outside the declared range of '{}' [{}..{}]", index, name, low, high)
+            }
+            DiagnosticKind::ArrayIndexOutOfBounds { name, index, low, high, dimension: Some(dimension) } => {
+                write!(
+                    f,
+                    "index {} is outside the declared range of '{}' [{}..{}] in dimension {}",
+                    index, name, low, high, dimension
+                )
+            }
+            DiagnosticKind::EmptyArrayRange { name, low, high, dimension: None } => {
+                write!(f, "declared range of '{}' [{}..{}] has no elements", name, low, high)
+            }
+            DiagnosticKind::EmptyArrayRange { name, low, high, dimension: Some(dimension) } => {
+                write!(f, "declared range of '{}' [{}..{}] in dimension {} has no elements", name, low, high, dimension)
+            }
+            DiagnosticKind::SingleElementArrayRange { name, bound, dimension: None } => {
+                write!(f, "declared range of '{}' [{}..{}] has a single element", name, bound, bound)
+            }
+            DiagnosticKind::SingleElementArrayRange { name, bound, dimension: Some(dimension) } => {
+                write!(
+                    f,
+                    "declared range of '{}' [{}..{}] in dimension {} has a single element",
+                    name, bound, bound, dimension
+                )
+            }
             DiagnosticKind::EmptyBlock { block_type } => {
                 write!(f, "empty {} block", block_type)
             }
@@ -156,12 +259,115 @@ impl fmt::Display for DiagnosticKind {
             DiagnosticKind::EmptyCaseBranch => {
                 write!(f, "empty CASE branch")
             }
+            DiagnosticKind::EmptyBody { construct } => {
+                write!(f, "empty {} body", construct)
+            }
             DiagnosticKind::MissingCaseElse => {
                 write!(f, "CASE statement has no ELSE clause")
             }
+            DiagnosticKind::NonExhaustiveCase { missing } => {
+                write!(f, "CASE statement does not cover enum values: {}", missing.join(", "))
+            }
+            DiagnosticKind::OverlappingCaseLabels { label, other, .. } => {
+                if label == other {
+                    write!(f, "duplicate CASE label '{}'", label)
+                } else {
+                    write!(f, "CASE label '{}' overlaps '{}'", label, other)
+                }
+            }
             DiagnosticKind::PossibleAssignmentInCondition => {
                 write!(f, "possible assignment in condition (did you mean '=' for comparison?)")
             }
+            DiagnosticKind::LoopVariableModified { name } => {
+                write!(f, "FOR loop variable '{}' is modified inside the loop body", name)
+            }
+            DiagnosticKind::SelfAssignment { path } => {
+                write!(f, "assignment of '{}' to itself has no effect", path)
+            }
+            DiagnosticKind::BoolToggle { path, value, .. } => {
+                write!(
+                    f,
+                    "'{}' is set to both TRUE and FALSE with no condition in between; \
+                     the earlier assignments have no effect and it ends {}",
+                    path,
+                    if *value { "TRUE" } else { "FALSE" }
+                )
+            }
+            DiagnosticKind::RedundantBoolean { expr, simplified } => {
+                write!(f, "'{}' simplifies to '{}'", expr, simplified)
+            }
+            DiagnosticKind::DuplicateEnumValue { type_name, member, value, other, .. } => {
+                write!(f, "enum '{}' member '{}' has value {}, the same as '{}'", type_name, member, value, other)
+            }
+            DiagnosticKind::DecreasingEnumValue { type_name, member, value, previous, previous_value } => {
+                write!(
+                    f,
+                    "enum '{}' member '{}' = {} is lower than the preceding '{}' = {}",
+                    type_name, member, value, previous, previous_value
+                )
+            }
+            DiagnosticKind::DuplicateStructMember { type_name, member, .. } => {
+                write!(f, "structure '{}' declares member '{}' more than once", type_name, member)
+            }
+            DiagnosticKind::RecursiveStruct { type_name, cycle } => {
+                write!(f, "structure '{}' contains itself by value: {}", type_name, cycle.join(" -> "))
+            }
+            DiagnosticKind::StringConcatInLoop { target, function, loop_kind, .. } => {
+                write!(
+                    f,
+                    "'{}' is extended with {} on every iteration of the {} loop, copying the whole string each time",
+                    target, function, loop_kind
+                )
+            }
+            DiagnosticKind::FloatEquality { op } => {
+                write!(f, "floating-point comparison with '{}'; compare against a tolerance instead", op)
+            }
+            DiagnosticKind::StringTruncation { target, capacity, length } => {
+                write!(f, "string of length {} assigned to {} is truncated to {} characters", length, target, capacity)
+            }
+            DiagnosticKind::DivisionByZero => write!(f, "division by zero"),
+            DiagnosticKind::UnguardedDivision { divisor } => {
+                write!(f, "divisor '{}' is not checked for zero before the division", divisor)
+            }
+            DiagnosticKind::MixedBitOperation { op, left, right } => {
+                let bits = if left == "BOOL" { right } else { left };
+                write!(
+                    f,
+                    "{} of {} and {} mixes bitwise and logical use (BOOL operands are logical, {} operands bitwise)",
+                    op, left, right, bits
+                )
+            }
+            DiagnosticKind::SignedBitOperation { op, ty } => {
+                write!(f, "bitwise {} on signed {}; the result depends on the sign bit", op, ty)
+            }
+            DiagnosticKind::HardcodedAddress { address } => {
+                write!(f, "direct address '{}' used in logic; map a named variable to it with AT", address)
+            }
+            DiagnosticKind::IfChainToCase { selector, branches } => {
+                write!(
+                    f,
+                    "IF/ELSIF chain of {} branches compares '{}' with constants; use CASE {} OF",
+                    branches, selector, selector
+                )
+            }
+            DiagnosticKind::DeepMemberAccess { path, depth, max_depth } => {
+                let base = path.split(['.', '[']).next().unwrap_or(path);
+                write!(
+                    f,
+                    "access path '{}' has depth {} (max {}); it depends on the internal structure of '{}'",
+                    path, depth, max_depth, base
+                )
+            }
+            DiagnosticKind::GlobalWriteInFunction { function, name } => {
+                write!(f, "function '{}' writes global variable '{}'; functions should have no side effects", function, name)
+            }
+            DiagnosticKind::StatefulCallInFunction { function, instance } => {
+                write!(
+                    f,
+                    "function '{}' calls function block instance '{}', whose state is kept between calls",
+                    function, instance
+                )
+            }
         }
     }
 }
@@ -0,0 +1,311 @@
+//! Division by zero checks.
+//!
+//! Detects `/` and `MOD` whose divisor is a constant zero, or a variable
+//! that is not checked against zero before the division.
+
+use std::collections::HashMap;
+
+use crate::ast::*;
+use crate::analysis::visit::{visit_expr, visit_statements};
+use crate::analysis::{eval_const_int, pou_constants, Diagnostic, DiagnosticKind};
+
+/// Check a POU for divisions by zero and unguarded divisors.
+///
+/// A divisor that folds to zero (a literal or a `VAR CONSTANT`) is an
+/// error. A variable divisor is a warning unless a guard dominates the
+/// division: in structured ST that is an enclosing IF/ELSIF/WHILE branch
+/// whose condition implies the divisor is nonzero (`d <> 0`, `d > 0`,
+/// `NOT (d = 0)`, also as part of an AND), the ELSE side of `IF d = 0`,
+/// or an earlier `IF d = 0 THEN RETURN; END_IF;` in the same block. A
+/// write to the divisor cancels its guard. Other divisors (expressions,
+/// function results, nonzero constants) are not reported.
+pub fn check_division_by_zero(pou: &Pou) -> Vec<Diagnostic> {
+    let mut checker = DivisionChecker {
+        constants: pou_constants(pou),
+        diagnostics: Vec::new(),
+    };
+    checker.check_body(&pou.body, &mut Vec::new());
+    checker.diagnostics
+}
+
+struct DivisionChecker {
+    constants: HashMap<String, i64>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl DivisionChecker {
+    /// Check a statement list; `guards` holds the uppercase paths known to be nonzero.
+    fn check_body(&mut self, body: &[Stmt], guards: &mut Vec<String>) {
+        for stmt in body {
+            self.check_statement(stmt, guards);
+        }
+    }
+
+    fn check_statement(&mut self, stmt: &Stmt, guards: &mut Vec<String>) {
+        match &stmt.kind {
+            StmtKind::Assignment { target, value } => {
+                self.check_expr(value, guards);
+                self.check_expr(target, guards);
+                drop_assigned(std::slice::from_ref(stmt), guards);
+            }
+            StmtKind::If { condition, then_body, elsif_branches, else_body } => {
+                self.check_expr(condition, guards);
+                self.check_body(then_body, &mut self.guarded(guards, condition, true));
+
+                // Later branches run only when the earlier conditions were false
+                let mut otherwise = guards.clone();
+                self.add_guards(condition, false, &mut otherwise);
+                for (cond, body) in elsif_branches {
+                    self.check_expr(cond, &otherwise);
+                    self.check_body(body, &mut self.guarded(&otherwise, cond, true));
+                    self.add_guards(cond, false, &mut otherwise);
+                }
+                if let Some(body) = else_body {
+                    self.check_body(body, &mut otherwise.clone());
+                }
+
+                drop_assigned(std::slice::from_ref(stmt), guards);
+                // IF d = 0 THEN RETURN; END_IF; guards the rest of the block
+                if elsif_branches.is_empty() && else_body.is_none() && ends_in_jump(then_body) {
+                    self.add_guards(condition, false, guards);
+                }
+            }
+            StmtKind::Case { expr, cases, else_body } => {
+                self.check_expr(expr, guards);
+                for case in cases {
+                    self.check_body(&case.body, &mut guards.clone());
+                }
+                if let Some(body) = else_body {
+                    self.check_body(body, &mut guards.clone());
+                }
+                drop_assigned(std::slice::from_ref(stmt), guards);
+            }
+            StmtKind::For { var, from, to, by, body } => {
+                self.check_expr(from, guards);
+                self.check_expr(to, guards);
+                if let Some(by) = by {
+                    self.check_expr(by, guards);
+                }
+                drop_assigned(std::slice::from_ref(stmt), guards);
+                guards.retain(|g| !g.eq_ignore_ascii_case(var));
+                self.check_body(body, &mut guards.clone());
+            }
+            StmtKind::While { condition, body } => {
+                drop_assigned(std::slice::from_ref(stmt), guards);
+                self.check_expr(condition, guards);
+                self.check_body(body, &mut self.guarded(guards, condition, true));
+            }
+            StmtKind::Repeat { body, until } => {
+                drop_assigned(std::slice::from_ref(stmt), guards);
+                self.check_body(body, &mut guards.clone());
+                self.check_expr(until, guards);
+            }
+            StmtKind::Return { value: Some(value) } => self.check_expr(value, guards),
+            StmtKind::Call { args, .. } => {
+                for value in args.iter().filter_map(|arg| arg.value.as_ref()) {
+                    self.check_expr(value, guards);
+                }
+                drop_assigned(std::slice::from_ref(stmt), guards);
+            }
+            _ => {}
+        }
+    }
+
+    /// Report the divisions of an expression.
+    fn check_expr(&mut self, expr: &Expr, guards: &[String]) {
+        visit_expr(expr, &mut |e| {
+            let ExprKind::BinaryOp { op: BinaryOp::Div | BinaryOp::Mod, right, .. } = &e.kind else {
+                return;
+            };
+            if self.is_zero(right) {
+                self.diagnostics.push(Diagnostic::error(DiagnosticKind::DivisionByZero, e.span));
+            } else if eval_const_int(right, &self.constants).is_none() {
+                if let Some(divisor) = path_name(right) {
+                    if !guards.contains(&divisor.to_uppercase()) {
+                        self.diagnostics.push(Diagnostic::warning(DiagnosticKind::UnguardedDivision { divisor }, e.span));
+                    }
+                }
+            }
+        });
+    }
+
+    /// Guards plus the divisors that are nonzero when `condition` is true.
+    fn guarded(&self, guards: &[String], condition: &Expr, truth: bool) -> Vec<String> {
+        let mut guards = guards.to_vec();
+        self.add_guards(condition, truth, &mut guards);
+        guards
+    }
+
+    /// Add the paths that are nonzero when `condition` evaluates to `truth`.
+    fn add_guards(&self, condition: &Expr, truth: bool, guards: &mut Vec<String>) {
+        match &condition.kind {
+            ExprKind::Paren(inner) => self.add_guards(inner, truth, guards),
+            ExprKind::UnaryOp { op: UnaryOp::Not, expr } => self.add_guards(expr, !truth, guards),
+            ExprKind::BinaryOp { left, op: BinaryOp::And, right } if truth => {
+                self.add_guards(left, truth, guards);
+                self.add_guards(right, truth, guards);
+            }
+            ExprKind::BinaryOp { left, op: BinaryOp::Or, right } if !truth => {
+                self.add_guards(left, truth, guards);
+                self.add_guards(right, truth, guards);
+            }
+            ExprKind::BinaryOp { left, op, right } => {
+                // Normalize to `path op 0`
+                let (path, op) = if self.is_zero(right) {
+                    (path_name(left), *op)
+                } else if self.is_zero(left) {
+                    let flipped = match op {
+                        BinaryOp::Lt => BinaryOp::Gt,
+                        BinaryOp::Gt => BinaryOp::Lt,
+                        BinaryOp::Le => BinaryOp::Ge,
+                        BinaryOp::Ge => BinaryOp::Le,
+                        other => *other,
+                    };
+                    (path_name(right), flipped)
+                } else {
+                    return;
+                };
+                let nonzero = match op {
+                    BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Gt => truth,
+                    BinaryOp::Eq | BinaryOp::Le | BinaryOp::Ge => !truth,
+                    _ => false,
+                };
+                if let Some(path) = path.filter(|_| nonzero) {
+                    guards.push(path.to_uppercase());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Whether an expression is an integer or REAL constant zero.
+    fn is_zero(&self, expr: &Expr) -> bool {
+        match &expr.kind {
+            ExprKind::Paren(inner) => self.is_zero(inner),
+            ExprKind::RealLiteral(value) => *value == 0.0,
+            _ => eval_const_int(expr, &self.constants) == Some(0),
+        }
+    }
+}
+
+/// Remove the guards of variables written anywhere in `body`.
+fn drop_assigned(body: &[Stmt], guards: &mut Vec<String>) {
+    visit_statements(body, &mut |stmt| match &stmt.kind {
+        StmtKind::Assignment { target, .. } => {
+            if let Some(path) = path_name(target) {
+                let path = path.to_uppercase();
+                guards.retain(|g| !overlaps(g, &path));
+            }
+        }
+        StmtKind::Call { args, .. } => {
+            for value in args.iter().filter(|arg| arg.output).filter_map(|arg| arg.value.as_ref()) {
+                if let Some(path) = path_name(value) {
+                    let path = path.to_uppercase();
+                    guards.retain(|g| !overlaps(g, &path));
+                }
+            }
+        }
+        _ => {}
+    });
+}
+
+/// Whether a statement list always leaves the enclosing block.
+fn ends_in_jump(body: &[Stmt]) -> bool {
+    matches!(
+        body.last().map(|stmt| &stmt.kind),
+        Some(StmtKind::Return { .. } | StmtKind::Exit | StmtKind::Continue)
+    )
+}
+
+/// Whether one access path equals the other or is one of its members.
+fn overlaps(a: &str, b: &str) -> bool {
+    let (short, long) = if a.len() <= b.len() { (a, b) } else { (b, a) };
+    long.starts_with(short) && (long.len() == short.len() || long[short.len()..].starts_with('.'))
+}
+
+/// Access path of a variable reference as written, without array indices.
+fn path_name(expr: &Expr) -> Option<String> {
+    match &expr.kind {
+        ExprKind::Ident(name) => Some(name.clone()),
+        ExprKind::MemberAccess { expr, member } => path_name(expr).map(|base| format!("{}.{}", base, member)),
+        ExprKind::ArrayIndex { array, .. } => path_name(array),
+        ExprKind::Paren(inner) => path_name(inner),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::Severity;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<Diagnostic> {
+        let code = format!(
+            "PROGRAM Main
+            VAR CONSTANT
+                ZERO : INT := 0;
+                SCALE : INT := 10;
+            END_VAR
+            VAR
+                total : REAL;
+                count : INT;
+                rate : REAL;
+                result : REAL;
+                n : INT;
+            END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        check_division_by_zero(&parse_pou(&code).unwrap())
+    }
+
+    fn divisors(diags: &[Diagnostic]) -> Vec<String> {
+        diags
+            .iter()
+            .map(|d| match &d.kind {
+                DiagnosticKind::UnguardedDivision { divisor } => divisor.clone(),
+                other => other.to_string(),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_constant_zero_and_variables() {
+        let diags = check(
+            "result := total / 0; n := count MOD ZERO; result := total / 0.0;
+            result := total / SCALE; result := total / 2.5;
+            result := total / rate; n := n MOD (count);",
+        );
+        assert_eq!(divisors(&diags), ["division by zero", "division by zero", "division by zero", "rate", "count"]);
+        assert_eq!(diags[0].severity, Severity::Error);
+        assert_eq!(diags[3].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_guards() {
+        let diags = check(
+            "IF rate <> 0.0 THEN result := total / rate; END_IF;
+            IF count > 0 AND rate > 1.0 THEN n := n MOD count; END_IF;
+            IF count = 0 THEN n := 0; ELSE n := 100 / count; END_IF;
+            IF 0 < count THEN n := 1; ELSIF NOT (rate = 0.0) THEN result := total / rate; END_IF;
+            IF count <> 0 OR rate <> 0.0 THEN result := total / rate; END_IF;",
+        );
+        assert_eq!(divisors(&diags), ["rate"]);
+    }
+
+    #[test]
+    fn test_early_return_and_reassignment() {
+        let diags = check(
+            "IF count = 0 THEN RETURN; END_IF;
+            n := 100 / count;
+            count := n - 1;
+            n := 100 / count;",
+        );
+        assert_eq!(divisors(&diags), ["count"]);
+
+        let diags = check("IF rate <> 0.0 THEN rate := total; result := total / rate; END_IF;");
+        assert_eq!(divisors(&diags), ["rate"]);
+    }
+}
@@ -0,0 +1,122 @@
+//! Empty body checks.
+//!
+//! Detects IF/ELSIF/ELSE branches, loop bodies and CASE branches without
+//! statements, which usually signal incomplete logic.
+
+use crate::ast::*;
+use crate::analysis::{Diagnostic, DiagnosticKind};
+use crate::span::Span;
+
+/// Check a POU for empty branches and loop bodies.
+///
+/// Comments are not statements, so a body holding only comments is empty.
+/// A bare `;` counts as empty everywhere except in a CASE branch: there it
+/// marks values that are deliberately handled by doing nothing, and the
+/// branch is not reported.
+pub fn check_empty_bodies(pou: &Pou) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    check_body(&pou.body, &mut diagnostics);
+    diagnostics
+}
+
+fn check_body(body: &[Stmt], diagnostics: &mut Vec<Diagnostic>) {
+    for stmt in body {
+        match &stmt.kind {
+            StmtKind::If { then_body, elsif_branches, else_body, .. } => {
+                check_branch("IF", then_body, stmt.span, diagnostics);
+                for (condition, body) in elsif_branches {
+                    check_branch("ELSIF", body, condition.span, diagnostics);
+                }
+                if let Some(else_stmts) = else_body {
+                    check_branch("ELSE", else_stmts, stmt.span, diagnostics);
+                }
+            }
+
+            StmtKind::Case { cases, else_body, .. } => {
+                for branch in cases {
+                    if branch.body.is_empty() {
+                        diagnostics.push(Diagnostic::hint(DiagnosticKind::EmptyCaseBranch, branch.span));
+                    }
+                    check_body(&branch.body, diagnostics);
+                }
+                if let Some(else_stmts) = else_body {
+                    check_branch("ELSE", else_stmts, stmt.span, diagnostics);
+                }
+            }
+
+            StmtKind::For { body, .. } => check_branch("FOR", body, stmt.span, diagnostics),
+            StmtKind::While { body, .. } => check_branch("WHILE", body, stmt.span, diagnostics),
+            StmtKind::Repeat { body, .. } => check_branch("REPEAT", body, stmt.span, diagnostics),
+
+            _ => {}
+        }
+    }
+}
+
+fn check_branch(construct: &str, body: &[Stmt], span: Span, diagnostics: &mut Vec<Diagnostic>) {
+    if body.iter().all(|stmt| matches!(stmt.kind, StmtKind::Empty)) {
+        diagnostics.push(Diagnostic::hint(
+            DiagnosticKind::EmptyBody { construct: construct.to_string() },
+            span,
+        ));
+    }
+    check_body(body, diagnostics);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<String> {
+        let code = format!(
+            "PROGRAM Main
+            VAR x : INT; END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        check_empty_bodies(&parse_pou(&code).unwrap())
+            .iter()
+            .map(|d| d.kind.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_if_branches() {
+        let diags = check(
+            "IF x > 0 THEN
+                (* TODO *)
+            ELSIF x < 0 THEN
+                x := 0;
+            ELSE
+                ;
+            END_IF;",
+        );
+        assert_eq!(diags, vec!["empty IF body", "empty ELSE body"]);
+    }
+
+    #[test]
+    fn test_empty_loops() {
+        let diags = check(
+            "FOR x := 0 TO 9 DO END_FOR;
+            WHILE x > 0 DO
+                IF x = 5 THEN END_IF;
+            END_WHILE;
+            REPEAT ; UNTIL x > 0;",
+        );
+        assert_eq!(diags, vec!["empty FOR body", "empty IF body", "empty REPEAT body"]);
+    }
+
+    #[test]
+    fn test_case_noop_branch_exempt() {
+        let diags = check(
+            "CASE x OF
+                1: ;
+                2:
+                3: x := 0;
+            END_CASE;",
+        );
+        assert_eq!(diags, vec!["empty CASE branch"]);
+    }
+}
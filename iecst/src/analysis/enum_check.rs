@@ -0,0 +1,134 @@
+//! Enumeration value checks.
+//!
+//! Computes the effective value of each enum member, explicit or one more
+//! than the previous member, and reports members that share a value and
+//! explicit values lower than the preceding member's, after which the
+//! implicit values run into the earlier ones.
+
+use std::collections::HashMap;
+
+use crate::analysis::{eval_const_int, Diagnostic, DiagnosticKind};
+use crate::ast::*;
+
+/// Effective integer value of each member of an enumeration.
+///
+/// A member without an explicit value is one more than the previous member,
+/// and the first member defaults to 0. Explicit values are constant folded
+/// and may name earlier members (`B := A + 2`). A value that cannot be
+/// folded is `None`, as are the implicit values that follow it.
+pub fn enum_member_values(values: &[EnumValue]) -> Vec<Option<i64>> {
+    let mut members = HashMap::new();
+    let mut previous = Some(-1);
+    values
+        .iter()
+        .map(|member| {
+            let value = match &member.value {
+                Some(expr) => eval_const_int(expr, &members),
+                None => previous.and_then(|p: i64| p.checked_add(1)),
+            };
+            if let Some(value) = value {
+                members.insert(member.name.to_uppercase(), value);
+            }
+            previous = value;
+            value
+        })
+        .collect()
+}
+
+/// Check the enumerations of type declarations for members with the same
+/// value and for decreasing values.
+///
+/// A member with the value of an earlier member is a warning carrying the
+/// span of the earlier member. An explicit value lower than the preceding
+/// member's that does not collide is a hint. Members whose value cannot be
+/// computed are skipped.
+pub fn check_enum_values(types: &[TypeDecl]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for decl in types {
+        let TypeDef::Enum { values } = &decl.definition else {
+            continue;
+        };
+        let computed = enum_member_values(values);
+        let mut seen: Vec<(&EnumValue, i64)> = Vec::new();
+        for (i, (member, value)) in values.iter().zip(&computed).enumerate() {
+            let Some(value) = *value else {
+                continue;
+            };
+            let previous_value = if i > 0 { computed[i - 1] } else { None };
+            if let Some((other, _)) = seen.iter().find(|(_, v)| *v == value) {
+                diagnostics.push(Diagnostic::warning(
+                    DiagnosticKind::DuplicateEnumValue {
+                        type_name: decl.name.clone(),
+                        member: member.name.clone(),
+                        value,
+                        other: other.name.clone(),
+                        original: other.span,
+                    },
+                    member.span,
+                ));
+            } else if let Some(previous_value) = previous_value.filter(|&p| member.value.is_some() && value < p) {
+                diagnostics.push(Diagnostic::hint(
+                    DiagnosticKind::DecreasingEnumValue {
+                        type_name: decl.name.clone(),
+                        member: member.name.clone(),
+                        value,
+                        previous: values[i - 1].name.clone(),
+                        previous_value,
+                    },
+                    member.span,
+                ));
+            }
+            seen.push((member, value));
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_type_block;
+
+    fn check(types: &str) -> Vec<String> {
+        let types = parse_type_block(&format!("TYPE {} END_TYPE", types)).unwrap();
+        check_enum_values(&types).iter().map(|d| d.kind.to_string()).collect()
+    }
+
+    #[test]
+    fn test_member_values() {
+        let types = parse_type_block("TYPE E : (A, B := 10, C, D := B * 2, E, F := Unknown, G); END_TYPE").unwrap();
+        let TypeDef::Enum { values } = &types[0].definition else {
+            panic!("expected an enum");
+        };
+        assert_eq!(
+            enum_member_values(values),
+            [Some(0), Some(10), Some(11), Some(20), Some(21), None, None]
+        );
+    }
+
+    #[test]
+    fn test_duplicates_and_decreasing() {
+        let diags = check("Mode : (Idle := 1, Run, Stop := 1); Step : (S1 := 10, S2 := 5, S3, S4 := 4);");
+        assert_eq!(
+            diags,
+            [
+                "enum 'Mode' member 'Stop' has value 1, the same as 'Idle'",
+                "enum 'Step' member 'S2' = 5 is lower than the preceding 'S1' = 10",
+                "enum 'Step' member 'S4' = 4 is lower than the preceding 'S3' = 6",
+            ]
+        );
+        let diags = check("Wrap : (A := 3, B := 1, C, D);");
+        assert_eq!(
+            diags,
+            [
+                "enum 'Wrap' member 'B' = 1 is lower than the preceding 'A' = 3",
+                "enum 'Wrap' member 'D' has value 3, the same as 'A'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_sequential_values_not_flagged() {
+        assert!(check("A : (X, Y, Z); B : (P := 1, Q := 2, R := 4); C : (M := N, O);").is_empty());
+    }
+}
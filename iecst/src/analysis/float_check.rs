@@ -0,0 +1,86 @@
+//! Floating-point equality checks.
+//!
+//! Detects `=` and `<>` comparisons with REAL or LREAL operands, which
+//! rarely hold exactly because of rounding.
+
+use std::collections::HashMap;
+
+use crate::ast::*;
+use crate::analysis::visit::visit_expressions;
+use crate::analysis::{build_symbol_table, Diagnostic, DiagnosticKind, Type, TypeChecker};
+
+/// Check a POU for equality comparisons of floating-point values.
+///
+/// An operand is floating point if it is a REAL literal or its inferred
+/// type is REAL or LREAL. Operand types come from the POU's declarations
+/// and the return types in `functions` (see [`check_types`](super::check_types)).
+/// Comparisons where neither type is known are not reported.
+pub fn check_float_equality(pou: &Pou, functions: &HashMap<String, Type>) -> Vec<Diagnostic> {
+    let symbols = build_symbol_table(pou, &mut Vec::new());
+    let mut type_checker = TypeChecker::new(&symbols);
+    for (name, return_type) in functions {
+        type_checker.register_function(name, return_type.clone());
+    }
+
+    let mut diagnostics = Vec::new();
+    visit_expressions(&pou.body, &mut |expr| {
+        let ExprKind::BinaryOp { left, op: op @ (BinaryOp::Eq | BinaryOp::Ne), right } = &expr.kind else {
+            return;
+        };
+        let is_real = |operand: &Expr| type_checker.infer_expr_type(operand, &mut Vec::new()).ty.is_real();
+        if is_real(left) || is_real(right) {
+            let op = if *op == BinaryOp::Eq { "=" } else { "<>" };
+            diagnostics.push(Diagnostic::hint(
+                DiagnosticKind::FloatEquality { op: op.to_string() },
+                expr.span,
+            ));
+        }
+    });
+    diagnostics
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<Diagnostic> {
+        let code = format!(
+            "PROGRAM Main
+            VAR level : REAL; setpoint : LREAL; count : INT; ok : BOOL; END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        check_float_equality(&parse_pou(&code).unwrap(), &HashMap::new())
+    }
+
+    #[test]
+    fn test_real_literal_and_variables() {
+        let diags = check(
+            "IF level = 3.14 THEN ok := TRUE; END_IF;
+            ok := setpoint <> level * 2;",
+        );
+        assert_eq!(diags.len(), 2);
+        assert_eq!(diags[0].kind, DiagnosticKind::FloatEquality { op: "=".to_string() });
+        assert_eq!(diags[1].kind, DiagnosticKind::FloatEquality { op: "<>".to_string() });
+    }
+
+    #[test]
+    fn test_integer_and_ordering_not_flagged() {
+        let diags = check(
+            "ok := count = 5;
+            ok := level > 3.0 AND level <= setpoint;
+            ok := INT_TO_REAL(count) < 1.5;",
+        );
+        assert!(diags.is_empty());
+    }
+
+    #[test]
+    fn test_user_function_return_type() {
+        let pou = parse_pou("PROGRAM Main VAR ok : BOOL; END_VAR ok := Scale() = 0; END_PROGRAM").unwrap();
+        let functions = HashMap::from([("SCALE".to_string(), Type::Real)]);
+        assert_eq!(check_float_equality(&pou, &functions).len(), 1);
+        assert!(check_float_equality(&pou, &HashMap::new()).is_empty());
+    }
+}
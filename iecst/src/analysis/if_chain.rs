@@ -0,0 +1,154 @@
+//! IF/ELSIF chains that are CASE statements in disguise.
+//!
+//! A chain like `IF state = 1 THEN ... ELSIF state = 2 THEN ...` selects a
+//! branch by the value of one variable. CASE states that directly, lets the
+//! checks for overlapping and missing labels see the values, and evaluates
+//! the selector once.
+
+use std::collections::HashMap;
+
+use crate::ast::*;
+use crate::analysis::const_eval::{eval_const_int, pou_constants};
+use crate::analysis::visit::visit_statements;
+use crate::analysis::{Diagnostic, DiagnosticKind};
+
+/// Check a POU for IF/ELSIF chains of at least `min_branches` conditions
+/// that all compare the same variable with integer constants.
+///
+/// A condition may be a single equality (`x = 1`, `1 = x`) or an OR of
+/// equalities (`x = 1 OR x = 2`), which becomes a label list. Constants are
+/// literals, constant expressions and `VAR CONSTANT` names. Chains with a
+/// repeated value, other comparisons or a second variable are not reported,
+/// since they do not convert to CASE unchanged. Each chain is a hint at its
+/// IF statement.
+pub fn check_if_chains(pou: &Pou, min_branches: usize) -> Vec<Diagnostic> {
+    let constants = pou_constants(pou);
+    let mut diagnostics = Vec::new();
+    visit_statements(&pou.body, &mut |stmt| {
+        let StmtKind::If { condition, elsif_branches, .. } = &stmt.kind else {
+            return;
+        };
+        let branches = 1 + elsif_branches.len();
+        if branches < min_branches.max(2) {
+            return;
+        }
+        let conditions = std::iter::once(condition).chain(elsif_branches.iter().map(|(cond, _)| cond));
+        if let Some(selector) = common_selector(conditions, &constants) {
+            diagnostics.push(Diagnostic::hint(DiagnosticKind::IfChainToCase { selector, branches }, stmt.span));
+        }
+    });
+    diagnostics
+}
+
+/// The variable every condition compares with distinct constants, if any.
+fn common_selector<'a>(
+    conditions: impl Iterator<Item = &'a Expr>,
+    constants: &HashMap<String, i64>,
+) -> Option<String> {
+    let mut selector: Option<String> = None;
+    let mut values = Vec::new();
+    for condition in conditions {
+        let mut comparisons = Vec::new();
+        equalities(condition, constants, &mut comparisons)?;
+        for (path, value) in comparisons {
+            match &selector {
+                Some(selector) if !selector.eq_ignore_ascii_case(&path) => return None,
+                Some(_) => {}
+                None => selector = Some(path),
+            }
+            if values.contains(&value) {
+                return None;
+            }
+            values.push(value);
+        }
+    }
+    selector
+}
+
+/// Collect the `(variable, constant)` pairs of an equality or an OR of
+/// equalities; `None` for any other condition.
+fn equalities(expr: &Expr, constants: &HashMap<String, i64>, out: &mut Vec<(String, i64)>) -> Option<()> {
+    match &expr.kind {
+        ExprKind::Paren(inner) => equalities(inner, constants, out),
+        ExprKind::BinaryOp { left, op: BinaryOp::Or, right } => {
+            equalities(left, constants, out)?;
+            equalities(right, constants, out)
+        }
+        ExprKind::BinaryOp { left, op: BinaryOp::Eq, right } => {
+            let pair = match (eval_const_int(left, constants), eval_const_int(right, constants)) {
+                (None, Some(value)) => (path_text(left)?, value),
+                (Some(value), None) => (path_text(right)?, value),
+                _ => return None,
+            };
+            out.push(pair);
+            Some(())
+        }
+        _ => None,
+    }
+}
+
+/// Render a variable or member path as source text.
+fn path_text(expr: &Expr) -> Option<String> {
+    match &expr.kind {
+        ExprKind::Ident(name) => Some(name.clone()),
+        ExprKind::Paren(inner) => path_text(inner),
+        ExprKind::MemberAccess { expr, member } => Some(format!("{}.{}", path_text(expr)?, member)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<String> {
+        let code = format!(
+            "PROGRAM Main
+            VAR CONSTANT IDLE : INT := 0; END_VAR
+            VAR state : INT; mode : INT; x : INT; cell : Cell; END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        let pou = parse_pou(&code).unwrap();
+        check_if_chains(&pou, 3).iter().map(|d| d.kind.to_string()).collect()
+    }
+
+    #[test]
+    fn test_chains_flagged() {
+        let diags = check(
+            "IF state = IDLE THEN x := 0;
+            ELSIF state = 1 THEN x := 1;
+            ELSIF 2 = State THEN x := 2;
+            ELSE x := 3;
+            END_IF;
+            IF cell.mode = 1 OR cell.mode = 2 THEN x := 1;
+            ELSIF (cell.mode = 3) THEN x := 2;
+            ELSIF cell.mode = -1 THEN
+                IF mode = 1 THEN x := 1; ELSIF mode = 2 THEN x := 2; ELSIF mode = 3 THEN x := 3; END_IF;
+            END_IF;",
+        );
+        assert_eq!(
+            diags,
+            [
+                "IF/ELSIF chain of 3 branches compares 'state' with constants; use CASE state OF",
+                "IF/ELSIF chain of 3 branches compares 'cell.mode' with constants; use CASE cell.mode OF",
+                "IF/ELSIF chain of 3 branches compares 'mode' with constants; use CASE mode OF",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_chains_not_flagged() {
+        let diags = check(
+            "IF state = 1 THEN x := 1; ELSIF state = 2 THEN x := 2; END_IF;
+            IF state = 1 THEN x := 1; ELSIF mode = 2 THEN x := 2; ELSIF state = 3 THEN x := 3; END_IF;
+            IF state = 1 THEN x := 1; ELSIF state > 2 THEN x := 2; ELSIF state = 3 THEN x := 3; END_IF;
+            IF state = 1 THEN x := 1; ELSIF state = 2 THEN x := 2; ELSIF state = 1 THEN x := 3; END_IF;
+            IF state = mode THEN x := 1; ELSIF state = 2 THEN x := 2; ELSIF state = 3 THEN x := 3; END_IF;
+            IF state = 1 AND mode = 1 THEN x := 1; ELSIF state = 2 THEN x := 2; ELSIF state = 3 THEN x := 3; END_IF;",
+        );
+        assert!(diags.is_empty(), "{:?}", diags);
+    }
+}
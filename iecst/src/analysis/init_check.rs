@@ -0,0 +1,310 @@
+//! Use-before-assignment checks.
+//!
+//! A forward "definitely assigned" pass over the POU's control flow graph:
+//! a local is assigned at a node when every path from the entry assigns
+//! it. Reading a local that is not assigned on some path is reported.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::*;
+use crate::analysis::{CfgBuilder, CfgNode, Diagnostic, DiagnosticKind, NodeId, Type};
+use crate::span::Span;
+
+/// Check a POU for reads of local variables before any assignment.
+///
+/// VAR_TEMP variables are checked in every POU, VAR variables only in
+/// functions: function block and program VARs keep their value between
+/// calls, so reading them first is normal. Variables with an initial value,
+/// constants and variables of user-defined types (function block instances,
+/// structures) are not checked. Each variable is reported once per reading
+/// statement.
+pub fn check_uninitialized_reads(pou: &Pou) -> Vec<Diagnostic> {
+    let locals = tracked_locals(pou);
+    if locals.is_empty() {
+        return Vec::new();
+    }
+
+    let cfg = CfgBuilder::new().build(&pou.body);
+
+    // Unreachable nodes keep the full set and report nothing
+    let mut assigned_in: HashMap<NodeId, HashSet<String>> = cfg
+        .nodes
+        .iter()
+        .map(|node| (node.id, if node.id == cfg.entry { HashSet::new() } else { locals.clone() }))
+        .collect();
+    let mut assigned_out = assigned_in.clone();
+
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for node in &cfg.nodes {
+            if node.id != cfg.entry {
+                let mut preds = cfg.predecessors(node.id).iter();
+                let mut incoming = preds
+                    .next()
+                    .map(|p| assigned_out[p].clone())
+                    .unwrap_or_else(|| locals.clone());
+                for pred in preds {
+                    incoming.retain(|name| assigned_out[pred].contains(name));
+                }
+                assigned_in.insert(node.id, incoming);
+            }
+
+            let mut assigned = assigned_in[&node.id].clone();
+            for access in node_accesses(node) {
+                if let Access::Write(name) | Access::Update(name) = access {
+                    if locals.contains(&name) {
+                        assigned.insert(name);
+                    }
+                }
+            }
+            if assigned != assigned_out[&node.id] {
+                assigned_out.insert(node.id, assigned);
+                changed = true;
+            }
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+    let mut reported = HashSet::new();
+    for node in &cfg.nodes {
+        let mut assigned = assigned_in[&node.id].clone();
+        for (access, span) in node_accesses_with_span(node) {
+            match access {
+                Access::Read(name) => {
+                    if locals.contains(&name) && !assigned.contains(&name) && reported.insert((name.clone(), span.start, span.end)) {
+                        diagnostics.push(Diagnostic::warning(
+                            DiagnosticKind::UninitializedRead { name: declared_name(pou, &name) },
+                            span,
+                        ));
+                    }
+                }
+                Access::Write(name) | Access::Update(name) => {
+                    assigned.insert(name);
+                }
+            }
+        }
+    }
+
+    diagnostics.sort_by_key(|d| d.span.start);
+    diagnostics
+}
+
+/// Uppercase names of the locals the check applies to.
+fn tracked_locals(pou: &Pou) -> HashSet<String> {
+    pou.var_blocks
+        .iter()
+        .filter(|block| {
+            !block.constant
+                && match block.kind {
+                    VarBlockKind::VarTemp => true,
+                    VarBlockKind::Var => pou.kind == PouKind::Function,
+                    _ => false,
+                }
+        })
+        .flat_map(|block| &block.vars)
+        .filter(|var| var.initial.is_none() && var.location.is_none())
+        .filter(|var| !is_user_type(&Type::from_spec(&var.var_type)))
+        .map(|var| var.name.to_uppercase())
+        .collect()
+}
+
+fn is_user_type(ty: &Type) -> bool {
+    match ty {
+        Type::Struct { .. } | Type::Enum { .. } | Type::FunctionBlock { .. } => true,
+        Type::Array { element, .. } => is_user_type(element),
+        _ => false,
+    }
+}
+
+/// Declared spelling of a local.
+fn declared_name(pou: &Pou, upper: &str) -> String {
+    pou.var_blocks
+        .iter()
+        .flat_map(|block| &block.vars)
+        .find(|var| var.name.eq_ignore_ascii_case(upper))
+        .map(|var| var.name.clone())
+        .unwrap_or_else(|| upper.to_string())
+}
+
+/// A read or write of a variable (uppercase name).
+pub(super) enum Access {
+    Read(String),
+    /// Write of the whole variable
+    Write(String),
+    /// Write of a member or element; the rest of the variable keeps its value
+    Update(String),
+}
+
+fn node_accesses(node: &CfgNode) -> Vec<Access> {
+    node_accesses_with_span(node).into_iter().map(|(access, _)| access).collect()
+}
+
+/// Variable accesses of a node in evaluation order, with the span of the
+/// statement or condition they belong to.
+fn node_accesses_with_span(node: &CfgNode) -> Vec<(Access, Span)> {
+    let mut accesses = Vec::new();
+    if let Some(condition) = &node.condition {
+        let mut expr_accesses = Vec::new();
+        expr_reads(condition, &mut expr_accesses);
+        accesses.extend(expr_accesses.into_iter().map(|a| (a, condition.span)));
+    }
+    for stmt in &node.statements {
+        let mut stmt_accesses = Vec::new();
+        stmt_reads_and_writes(&stmt.kind, &mut stmt_accesses);
+        accesses.extend(stmt_accesses.into_iter().map(|a| (a, stmt.span)));
+    }
+    accesses
+}
+
+pub(super) fn stmt_reads_and_writes(kind: &StmtKind, accesses: &mut Vec<Access>) {
+    match kind {
+        StmtKind::Assignment { target, value } => {
+            expr_reads(value, accesses);
+            target_write(target, true, accesses);
+        }
+        StmtKind::For { var, from, to, by, .. } => {
+            expr_reads(from, accesses);
+            expr_reads(to, accesses);
+            if let Some(by) = by {
+                expr_reads(by, accesses);
+            }
+            accesses.push(Access::Write(var.to_uppercase()));
+        }
+        StmtKind::Return { value: Some(value) } => expr_reads(value, accesses),
+        StmtKind::Call { args, .. } => {
+            call_args(args.iter().filter_map(|arg| Some((arg.value.as_ref()?, arg.output))), accesses)
+        }
+        _ => {}
+    }
+}
+
+/// Inputs are read before the call, outputs are written after it.
+fn call_args<'a>(args: impl Iterator<Item = (&'a Expr, bool)>, accesses: &mut Vec<Access>) {
+    let (outputs, inputs): (Vec<_>, Vec<_>) = args.partition(|(_, output)| *output);
+    for (value, _) in inputs {
+        expr_reads(value, accesses);
+    }
+    for (value, _) in outputs {
+        target_write(value, true, accesses);
+    }
+}
+
+pub(super) fn expr_reads(expr: &Expr, accesses: &mut Vec<Access>) {
+    match &expr.kind {
+        ExprKind::Ident(name) => accesses.push(Access::Read(name.to_uppercase())),
+        ExprKind::FunctionCall { args, .. } => {
+            call_args(args.iter().filter_map(|arg| Some((arg.value.as_ref()?, arg.output))), accesses)
+        }
+        ExprKind::BinaryOp { left, right, .. } => {
+            expr_reads(left, accesses);
+            expr_reads(right, accesses);
+        }
+        ExprKind::UnaryOp { expr: inner, .. }
+        | ExprKind::Paren(inner)
+        | ExprKind::MemberAccess { expr: inner, .. } => expr_reads(inner, accesses),
+        ExprKind::ArrayIndex { array, indices } => {
+            expr_reads(array, accesses);
+            for index in indices {
+                expr_reads(index, accesses);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The variable an assignment target writes, as a whole or in part; index
+/// expressions are reads.
+fn target_write(target: &Expr, whole: bool, accesses: &mut Vec<Access>) {
+    match &target.kind {
+        ExprKind::Ident(name) if whole => accesses.push(Access::Write(name.to_uppercase())),
+        ExprKind::Ident(name) => accesses.push(Access::Update(name.to_uppercase())),
+        ExprKind::Paren(inner) => target_write(inner, whole, accesses),
+        ExprKind::MemberAccess { expr: inner, .. } => target_write(inner, false, accesses),
+        ExprKind::ArrayIndex { array, indices } => {
+            for index in indices {
+                expr_reads(index, accesses);
+            }
+            target_write(array, false, accesses);
+        }
+        _ => expr_reads(target, accesses),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn uninitialized(code: &str) -> Vec<String> {
+        check_uninitialized_reads(&parse_pou(code).unwrap())
+            .into_iter()
+            .map(|d| match d.kind {
+                DiagnosticKind::UninitializedRead { name } => name,
+                other => panic!("unexpected diagnostic {:?}", other),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_read_before_assignment() {
+        let names = uninitialized(
+            "FUNCTION F : INT
+            VAR_INPUT a : INT; END_VAR
+            VAR x : INT; y : INT; END_VAR
+            y := x + a;
+            x := 1;
+            F := x + y;
+            END_FUNCTION",
+        );
+        assert_eq!(names, vec!["x"]);
+    }
+
+    #[test]
+    fn test_assigned_on_one_branch_only() {
+        let names = uninitialized(
+            "FUNCTION F : INT
+            VAR_INPUT a : BOOL; END_VAR
+            VAR x : INT; y : INT; END_VAR
+            IF a THEN
+                x := 1;
+                y := 1;
+            ELSE
+                y := 2;
+            END_IF;
+            F := x + y;
+            END_FUNCTION",
+        );
+        assert_eq!(names, vec!["x"]);
+    }
+
+    #[test]
+    fn test_initializer_and_loop_and_condition() {
+        let names = uninitialized(
+            "FUNCTION F : INT
+            VAR i : INT; sum : INT := 0; flag : BOOL; END_VAR
+            FOR i := 1 TO 10 DO
+                sum := sum + i;
+            END_FOR;
+            WHILE flag DO
+                flag := FALSE;
+            END_WHILE;
+            F := sum;
+            END_FUNCTION",
+        );
+        assert_eq!(names, vec!["flag"]);
+    }
+
+    #[test]
+    fn test_fb_var_not_checked_but_temp_is() {
+        let names = uninitialized(
+            "FUNCTION_BLOCK FB
+            VAR count : INT; timer : TON; END_VAR
+            VAR_TEMP t : INT; END_VAR
+            count := count + t;
+            timer(IN := TRUE, PT := T#1s);
+            END_FUNCTION_BLOCK",
+        );
+        assert_eq!(names, vec!["t"]);
+    }
+}
@@ -0,0 +1,217 @@
+//! FOR loop checks.
+//!
+//! Detects writes to a FOR loop's control variable inside its body.
+
+use crate::ast::*;
+use crate::analysis::{Diagnostic, DiagnosticKind};
+use crate::span::Span;
+
+/// Check FOR loops in a POU for modifications of the control variable.
+///
+/// Assignments, output bindings (`Q => i`) and nested FOR loops reusing the
+/// variable are reported. Only the plain variable counts: writes to a
+/// member or element with the same name (`fb.i`, `arr[i]`) are not the
+/// control variable.
+pub fn check_loop_variables(pou: &Pou) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    check_body(&pou.body, &mut diagnostics);
+    diagnostics
+}
+
+fn check_body(body: &[Stmt], diagnostics: &mut Vec<Diagnostic>) {
+    for stmt in body {
+        match &stmt.kind {
+            StmtKind::For { var, body, .. } => {
+                let mut writes = Vec::new();
+                collect_writes(body, var, &mut writes);
+                for span in writes {
+                    let diagnostic = Diagnostic::warning(
+                        DiagnosticKind::LoopVariableModified { name: var.clone() },
+                        span,
+                    );
+                    // A nested loop over the same variable reports the same write
+                    if !diagnostics.contains(&diagnostic) {
+                        diagnostics.push(diagnostic);
+                    }
+                }
+                check_body(body, diagnostics);
+            }
+
+            StmtKind::If { then_body, elsif_branches, else_body, .. } => {
+                check_body(then_body, diagnostics);
+                for (_, body) in elsif_branches {
+                    check_body(body, diagnostics);
+                }
+                if let Some(else_stmts) = else_body {
+                    check_body(else_stmts, diagnostics);
+                }
+            }
+
+            StmtKind::Case { cases, else_body, .. } => {
+                for branch in cases {
+                    check_body(&branch.body, diagnostics);
+                }
+                if let Some(else_stmts) = else_body {
+                    check_body(else_stmts, diagnostics);
+                }
+            }
+
+            StmtKind::While { body, .. } | StmtKind::Repeat { body, .. } => {
+                check_body(body, diagnostics);
+            }
+
+            _ => {}
+        }
+    }
+}
+
+/// Collect the spans of statements and arguments that write `var`.
+fn collect_writes(body: &[Stmt], var: &str, writes: &mut Vec<Span>) {
+    for stmt in body {
+        match &stmt.kind {
+            StmtKind::Assignment { target, value } => {
+                if is_var(target, var) {
+                    writes.push(stmt.span);
+                }
+                collect_expr_writes(value, var, writes);
+            }
+
+            StmtKind::Call { args, .. } => {
+                for arg in args {
+                    if let Some(value) = &arg.value {
+                        if arg.output && is_var(value, var) {
+                            writes.push(arg.span);
+                        } else {
+                            collect_expr_writes(value, var, writes);
+                        }
+                    }
+                }
+            }
+
+            StmtKind::For { var: inner, body, .. } => {
+                if inner.eq_ignore_ascii_case(var) {
+                    writes.push(stmt.span);
+                }
+                collect_writes(body, var, writes);
+            }
+
+            StmtKind::If { condition, then_body, elsif_branches, else_body } => {
+                collect_expr_writes(condition, var, writes);
+                collect_writes(then_body, var, writes);
+                for (cond, body) in elsif_branches {
+                    collect_expr_writes(cond, var, writes);
+                    collect_writes(body, var, writes);
+                }
+                if let Some(else_stmts) = else_body {
+                    collect_writes(else_stmts, var, writes);
+                }
+            }
+
+            StmtKind::Case { expr, cases, else_body } => {
+                collect_expr_writes(expr, var, writes);
+                for branch in cases {
+                    collect_writes(&branch.body, var, writes);
+                }
+                if let Some(else_stmts) = else_body {
+                    collect_writes(else_stmts, var, writes);
+                }
+            }
+
+            StmtKind::While { condition, body } | StmtKind::Repeat { body, until: condition } => {
+                collect_expr_writes(condition, var, writes);
+                collect_writes(body, var, writes);
+            }
+
+            StmtKind::Return { value: Some(value) } => collect_expr_writes(value, var, writes),
+
+            _ => {}
+        }
+    }
+}
+
+/// Collect output bindings to `var` in function calls within an expression.
+fn collect_expr_writes(expr: &Expr, var: &str, writes: &mut Vec<Span>) {
+    match &expr.kind {
+        ExprKind::FunctionCall { args, .. } => {
+            for arg in args {
+                if let Some(value) = &arg.value {
+                    if arg.output && is_var(value, var) {
+                        writes.push(arg.span);
+                    } else {
+                        collect_expr_writes(value, var, writes);
+                    }
+                }
+            }
+        }
+        ExprKind::BinaryOp { left, right, .. } => {
+            collect_expr_writes(left, var, writes);
+            collect_expr_writes(right, var, writes);
+        }
+        ExprKind::UnaryOp { expr, .. } | ExprKind::Paren(expr) => collect_expr_writes(expr, var, writes),
+        _ => {}
+    }
+}
+
+fn is_var(expr: &Expr, var: &str) -> bool {
+    match &expr.kind {
+        ExprKind::Ident(name) => name.eq_ignore_ascii_case(var),
+        ExprKind::Paren(inner) => is_var(inner, var),
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<Diagnostic> {
+        let code = format!(
+            "PROGRAM Main
+            VAR i : INT; j : INT; total : INT; fb : Counter; arr : ARRAY[0..9] OF INT; END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        check_loop_variables(&parse_pou(&code).unwrap())
+    }
+
+    #[test]
+    fn test_assignment_to_loop_var() {
+        let diags = check(
+            "FOR i := 0 TO 9 DO
+                IF arr[i] = 0 THEN
+                    i := i + 1;
+                END_IF;
+            END_FOR;",
+        );
+        assert_eq!(diags.len(), 1);
+        assert_eq!(diags[0].kind, DiagnosticKind::LoopVariableModified { name: "i".to_string() });
+    }
+
+    #[test]
+    fn test_output_binding_and_nested_loop() {
+        let diags = check(
+            "FOR i := 0 TO 9 DO
+                fb(CU := TRUE, CV => I);
+                FOR i := 0 TO 3 DO
+                    total := total + i;
+                END_FOR;
+            END_FOR;",
+        );
+        assert_eq!(diags.len(), 2);
+    }
+
+    #[test]
+    fn test_other_targets_not_flagged() {
+        let diags = check(
+            "FOR i := 0 TO 9 DO
+                arr[i] := i;
+                fb.i := i;
+                fb(CV => j);
+            END_FOR;
+            i := 0;",
+        );
+        assert!(diags.is_empty());
+    }
+}
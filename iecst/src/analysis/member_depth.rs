@@ -0,0 +1,133 @@
+//! Member access depth analysis.
+//!
+//! A long access chain like `Line.Station.Axis.Drive.Status.Ready` couples
+//! the code to the internal layout of the structures it reaches through.
+//! The depth of a chain is the number of member accesses and array
+//! subscripts after the base variable.
+
+use crate::ast::*;
+use crate::analysis::visit::visit_expressions;
+use crate::analysis::{Diagnostic, DiagnosticKind};
+
+/// Depth of an access chain: member accesses and array subscripts after
+/// the base variable.
+///
+/// A subscript with several indices (`a[i, j]`) counts as one level.
+///
+/// # Example
+///
+/// ```
+/// use iecst::{parse_expression, member_access_depth};
+///
+/// let expr = parse_expression("line.station[2].axis.ready").unwrap();
+/// assert_eq!(member_access_depth(&expr), 4);
+/// ```
+pub fn member_access_depth(expr: &Expr) -> usize {
+    match &expr.kind {
+        ExprKind::MemberAccess { expr: base, .. } => member_access_depth(base) + 1,
+        ExprKind::ArrayIndex { array, .. } => member_access_depth(array) + 1,
+        _ => 0,
+    }
+}
+
+/// Check a POU for access chains deeper than `max_depth`.
+///
+/// Each chain is reported once, at its full length (`DeepMemberAccess`,
+/// hint). Chains inside array subscripts are checked separately.
+pub fn check_member_access_depth(pou: &Pou, max_depth: usize) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    // Span of the last chain seen; its prefixes start at the same position
+    let mut chain: Option<crate::Span> = None;
+    visit_expressions(&pou.body, &mut |expr| {
+        if !matches!(expr.kind, ExprKind::MemberAccess { .. } | ExprKind::ArrayIndex { .. }) {
+            return;
+        }
+        if chain.is_some_and(|outer| outer.start == expr.span.start && expr.span.end <= outer.end) {
+            return;
+        }
+        chain = Some(expr.span);
+        let depth = member_access_depth(expr);
+        if depth > max_depth {
+            diagnostics.push(Diagnostic::hint(
+                DiagnosticKind::DeepMemberAccess {
+                    path: access_path(expr),
+                    depth,
+                    max_depth,
+                },
+                expr.span,
+            ));
+        }
+    });
+    diagnostics
+}
+
+/// Access path as written; subscripts other than names, integers and
+/// access paths are shown as `..`.
+fn access_path(expr: &Expr) -> String {
+    match &expr.kind {
+        ExprKind::Ident(name) => name.clone(),
+        ExprKind::IntLiteral(value) => value.to_string(),
+        ExprKind::MemberAccess { expr: base, member } => format!("{}.{}", access_path(base), member),
+        ExprKind::ArrayIndex { array, indices } => {
+            let indices: Vec<_> = indices.iter().map(access_path).collect();
+            format!("{}[{}]", access_path(array), indices.join(", "))
+        }
+        _ => "..".to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::Severity;
+    use crate::parse_pou;
+
+    fn check(body: &str, max_depth: usize) -> Vec<Diagnostic> {
+        let code = format!(
+            "PROGRAM Main
+            VAR line : LineData; i : INT; ok : BOOL; END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        check_member_access_depth(&parse_pou(&code).unwrap(), max_depth)
+    }
+
+    #[test]
+    fn test_deep_chain_reported_once() {
+        let diags = check("ok := line.station.axis.drive.status.ready; ok := line.station.axis.ready;", 4);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(
+            diags[0].kind,
+            DiagnosticKind::DeepMemberAccess {
+                path: "line.station.axis.drive.status.ready".to_string(),
+                depth: 5,
+                max_depth: 4,
+            }
+        );
+        assert_eq!(diags[0].severity, Severity::Hint);
+    }
+
+    #[test]
+    fn test_array_subscripts_count_one_level() {
+        let diags = check("line.cells[i, 2].axis[i + 1].ready := ok;", 3);
+        assert_eq!(diags.len(), 1);
+        assert_eq!(
+            diags[0].kind.to_string(),
+            "access path 'line.cells[i, 2].axis[..].ready' has depth 5 (max 3); it depends on the internal structure of 'line'"
+        );
+    }
+
+    #[test]
+    fn test_chains_in_subscripts_checked_separately() {
+        let diags = check("i := line.cells[line.config.limits.max].count;", 2);
+        let paths: Vec<_> = diags
+            .iter()
+            .map(|d| match &d.kind {
+                DiagnosticKind::DeepMemberAccess { path, .. } => path.clone(),
+                other => other.to_string(),
+            })
+            .collect();
+        assert_eq!(paths, ["line.cells[line.config.limits.max].count", "line.config.limits.max"]);
+    }
+}
@@ -6,70 +6,190 @@
 //! - Diagnostics reporting
 //! - Unused variable detection
 //! - Control flow graph (CFG) construction
+//! - CASE coverage and label overlap checks
+//! - IF/ELSIF chains that compare one variable with constants (CASE candidates)
+//! - Constant folding
+//! - FOR loop variable checks
+//! - Empty branch and loop body checks
+//! - Self-assignment checks
+//! - Boolean variables set to TRUE and FALSE in one unconditional sequence
+//! - Floating-point equality checks
+//! - String truncation checks
+//! - Strings built up by CONCAT or INSERT in loops
+//! - Reads of local variables before assignment
+//! - Data dependencies between variables (input to output tracking)
+//! - Constant array indices outside the declared bounds
+//! - Division by zero and unguarded divisors
+//! - Mixed BOOL/bitwise and signed bit operations
+//! - Member access depth (long access chains)
+//! - Direct addresses used in statements instead of named variables
+//! - Function purity (global writes and function block calls in functions)
+//! - Dead stores (assignments overwritten before they are read)
+//! - Redundant boolean expressions (`X AND X`, `X OR NOT X`, `NOT NOT X`)
+//! - Enum members with duplicate or decreasing values
+//! - Duplicate structure members and recursive structures
 
+mod address_check;
+mod bit_check;
+mod bool_simplify;
+mod bool_toggle;
+mod bounds_check;
+mod case_check;
 mod cfg;
+mod concat_check;
+mod const_eval;
+mod dead_store;
+mod dataflow;
+mod division_check;
+mod empty_check;
+mod enum_check;
+mod float_check;
+mod if_chain;
+mod init_check;
+mod loop_check;
+mod member_depth;
+mod navigation;
 mod nesting;
+mod purity;
+mod self_assign;
+mod string_check;
+mod struct_check;
 mod symbol_table;
 mod type_check;
+mod usage;
 mod diagnostics;
+mod visit;
 
+pub use address_check::check_direct_addresses;
+pub use bit_check::check_bit_operations;
+pub use bool_simplify::check_redundant_booleans;
+pub use bool_toggle::check_bool_toggles;
+pub use bounds_check::{check_array_bounds, check_array_ranges, check_type_ranges};
+pub use case_check::{check_case_coverage, check_case_labels};
 pub use cfg::{Cfg, CfgBuilder, CfgNode, CfgEdge, NodeId, NodeKind, EdgeKind, count_expression_decisions};
+pub use concat_check::check_string_concat_in_loops;
+pub use const_eval::{eval_const_int, pou_constants};
+pub use dead_store::check_dead_stores;
+pub use dataflow::{influence_path, influences, DataFlow, FlowEdge, FlowKind};
+pub use division_check::check_division_by_zero;
+pub use empty_check::check_empty_bodies;
+pub use enum_check::{check_enum_values, enum_member_values};
+pub use float_check::check_float_equality;
+pub use if_chain::check_if_chains;
+pub use init_check::check_uninitialized_reads;
+pub use loop_check::check_loop_variables;
+pub use member_depth::{check_member_access_depth, member_access_depth};
+pub use navigation::{references_of, symbol_at, SymbolIndex, SymbolInfo};
 pub use nesting::max_nesting_depth;
+pub use purity::check_function_purity;
+pub use self_assign::check_self_assignments;
+pub use string_check::check_string_truncation;
+pub use struct_check::check_struct_types;
 pub use symbol_table::{Symbol, SymbolKind, SymbolTable, Scope};
-pub use type_check::{TypeChecker, Type, TypeInfo};
+pub use type_check::{ExprTypes, TypeChecker, Type, TypeInfo};
 pub use diagnostics::{Diagnostic, DiagnosticKind, Severity};
 
 use crate::ast::*;
+use std::collections::HashMap;
 
 /// Analyze a POU and return all diagnostics.
 pub fn analyze_pou(pou: &Pou) -> Vec<Diagnostic> {
     let mut diagnostics = Vec::new();
     
     // Build symbol table
+    let mut symbols = build_symbol_table(pou, &mut diagnostics);
+    usage::record_usage(&mut symbols, &pou.body);
+    
+    // Type check the body
+    let mut type_checker = TypeChecker::new(&symbols);
+    for stmt in &pou.body {
+        diagnostics.extend(type_checker.check_statement(stmt));
+    }
+    
+    // Check for unused variables
+    diagnostics.extend(symbols.check_unused());
+    
+    symbols.exit_scope();
+    
+    diagnostics
+}
+
+/// Type check a POU body.
+///
+/// `functions` maps the names of user-defined functions to their return
+/// types, so that calls to them can be checked like builtin functions.
+pub fn check_types(pou: &Pou, functions: &HashMap<String, Type>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    let symbols = build_symbol_table(pou, &mut diagnostics);
+    
+    let mut type_checker = TypeChecker::new(&symbols);
+    for (name, return_type) in functions {
+        type_checker.register_function(name, return_type.clone());
+    }
+    for stmt in &pou.body {
+        diagnostics.extend(type_checker.check_statement(stmt));
+    }
+    
+    diagnostics
+}
+
+/// Check a POU for unused and unassigned variables.
+///
+/// Each VAR block kind has its own diagnostic: `UnusedInput` (hint),
+/// `UnassignedOutput`, `UnusedTemp` and `UnusedVariable`. See
+/// [`SymbolTable::check_unused`].
+pub fn check_unused_variables(pou: &Pou) -> Vec<Diagnostic> {
+    let mut symbols = build_symbol_table(pou, &mut Vec::new());
+    usage::record_usage(&mut symbols, &pou.body);
+    symbols
+        .check_unused()
+        .into_iter()
+        .filter(|d| !matches!(d.kind, DiagnosticKind::UninitializedVariable { .. }))
+        .collect()
+}
+
+/// Build a symbol table with the POU's variables in its own scope.
+fn build_symbol_table(pou: &Pou, diagnostics: &mut Vec<Diagnostic>) -> SymbolTable {
     let mut symbols = SymbolTable::new();
     symbols.enter_scope(&pou.name);
     
+    // A function's name holds its return value
+    if let Some(return_type) = &pou.return_type {
+        let _ = symbols.define(Symbol {
+            name: pou.name.clone(),
+            kind: SymbolKind::Function,
+            type_info: Some(Type::from_spec(return_type)),
+            span: pou.span,
+            mutable: true,
+            used: true,
+            assigned: true,
+            var_block: None,
+        });
+    }
+    
     // Register all variables
     for block in &pou.var_blocks {
         for var in &block.vars {
+            let kind = match block.kind {
+                VarBlockKind::VarInput => SymbolKind::Parameter,
+                VarBlockKind::VarOutput => SymbolKind::Output,
+                VarBlockKind::VarInOut => SymbolKind::InOut,
+                _ => SymbolKind::Variable,
+            };
             if let Err(diag) = symbols.define(Symbol {
                 name: var.name.clone(),
-                kind: SymbolKind::Variable,
-                type_info: Some(type_from_spec(&var.var_type)),
+                kind,
+                type_info: Some(Type::from_spec(&var.var_type)),
                 span: var.span,
                 mutable: !block.constant,
                 used: false,
                 assigned: var.initial.is_some(),
+                var_block: Some(block.kind),
             }) {
                 diagnostics.push(diag);
             }
         }
     }
     
-    // Type check the body
-    let mut type_checker = TypeChecker::new(&symbols);
-    for stmt in &pou.body {
-        diagnostics.extend(type_checker.check_statement(stmt));
-    }
-    
-    // Check for unused variables
-    diagnostics.extend(symbols.check_unused());
-    
-    symbols.exit_scope();
-    
-    diagnostics
-}
-
-/// Convert a TypeSpec to a Type for analysis.
-fn type_from_spec(spec: &TypeSpec) -> Type {
-    match &spec.kind {
-        TypeKind::Simple(name) => Type::from_name(name),
-        TypeKind::Array { element, ranges } => Type::Array {
-            element: Box::new(type_from_spec(element)),
-            dimensions: ranges.len(),
-        },
-        TypeKind::String { length } => Type::String { max_length: *length },
-        TypeKind::WString { length } => Type::WString { max_length: *length },
-        TypeKind::Subrange { base, .. } => Type::from_name(base),
-    }
+    symbols
 }
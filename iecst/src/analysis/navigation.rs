@@ -0,0 +1,436 @@
+//! Symbol lookup by source offset, for editor tooling.
+//!
+//! Indexes the identifiers of a POU (declarations, reads, writes, calls and
+//! named call arguments) so that the symbol under a cursor can be resolved
+//! to its declaration (go-to-definition, hover) and all its occurrences in
+//! the POU can be listed (find-references).
+//!
+//! Members (`motor.speed`, `timer.Q`) are resolved through the declared type
+//! of their base: structure types from the type declarations and function
+//! blocks from the POUs passed to [`SymbolIndex::new`]. Members of unknown
+//! types and names that are not declared (globals, functions) are not
+//! resolved. `FOR` counters are found at their uses, not in the loop header,
+//! which has no span of its own.
+
+use crate::analysis::{SymbolKind, Type};
+use crate::ast::*;
+use crate::visitor::{walk_expr, walk_stmt, walk_var_decl, Visitor};
+use crate::Span;
+
+/// A resolved symbol.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SymbolInfo {
+    /// Name as declared
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Type of the symbol
+    pub type_info: Option<Type>,
+    /// Type as declared
+    pub declared_type: Option<TypeSpec>,
+    /// Span of the name in the declaration; for members, in the source of
+    /// the declaring type or POU
+    pub declaration: Span,
+    /// POU, function block or structure type declaring the symbol
+    pub scope: String,
+    /// VAR block of the declaration (None for struct fields and return values)
+    pub var_block: Option<VarBlockKind>,
+}
+
+/// What an identifier occurrence refers to.
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    /// A variable of the indexed POU, or its return value
+    Local(String),
+    /// A member of a structure type or function block
+    Member { owner: String, name: String },
+}
+
+/// Identifier occurrences of one POU.
+///
+/// # Example
+///
+/// ```
+/// use iecst::{parse_pou, SymbolIndex};
+///
+/// let code = "PROGRAM Main
+/// VAR speed : INT; END_VAR
+/// speed := speed + 1;
+/// END_PROGRAM";
+/// let pou = parse_pou(code).unwrap();
+/// let index = SymbolIndex::new(&pou, &[], &[]);
+///
+/// let offset = code.find("speed + 1").unwrap();
+/// let symbol = index.symbol_at(offset).unwrap();
+/// assert_eq!(symbol.declaration.text(code), "speed");
+/// assert_eq!(index.references_of(&symbol).len(), 3);
+/// ```
+pub struct SymbolIndex<'a> {
+    pou: &'a Pou,
+    types: &'a [TypeDecl],
+    pous: &'a [Pou],
+    occurrences: Vec<(Span, Target)>,
+}
+
+impl<'a> SymbolIndex<'a> {
+    /// Index a POU. `types` and `pous` are the known structure types and
+    /// function blocks, used to resolve members.
+    pub fn new(pou: &'a Pou, types: &'a [TypeDecl], pous: &'a [Pou]) -> Self {
+        let mut index = Self { pou, types, pous, occurrences: Vec::new() };
+        let mut collector = Occurrences { index: &index, found: Vec::new() };
+        collector.visit_pou(pou);
+        index.occurrences = collector.found;
+        index
+    }
+
+    /// Symbol of the identifier at a byte offset, if it resolves.
+    ///
+    /// The innermost identifier containing the offset is taken, so an
+    /// offset in `speed` of `motor.speed` resolves the member.
+    pub fn symbol_at(&self, offset: usize) -> Option<SymbolInfo> {
+        let (_, target) = self
+            .occurrences
+            .iter()
+            .filter(|(span, _)| span.start <= offset && offset < span.end)
+            .min_by_key(|(span, _)| span.len())?;
+        self.resolve(target)
+    }
+
+    /// Spans of all occurrences of a symbol in the POU, in source order,
+    /// including its declaration if the POU declares it.
+    pub fn references_of(&self, symbol: &SymbolInfo) -> Vec<Span> {
+        let mut spans: Vec<Span> = self
+            .occurrences
+            .iter()
+            .filter(|(_, target)| match target {
+                Target::Local(name) => {
+                    name.eq_ignore_ascii_case(&symbol.name) && self.pou.name.eq_ignore_ascii_case(&symbol.scope)
+                }
+                Target::Member { owner, name } => {
+                    name.eq_ignore_ascii_case(&symbol.name) && owner.eq_ignore_ascii_case(&symbol.scope)
+                }
+            })
+            .map(|(span, _)| *span)
+            .collect();
+        spans.sort_by_key(|span| span.start);
+        spans.dedup();
+        spans
+    }
+
+    fn resolve(&self, target: &Target) -> Option<SymbolInfo> {
+        match target {
+            Target::Local(name) => {
+                if let Some((block, var)) = find_var(&self.pou.var_blocks, name) {
+                    return Some(variable(&self.pou.name, block, var));
+                }
+                let return_type = self.pou.return_type.as_ref()?;
+                self.pou.name.eq_ignore_ascii_case(name).then(|| SymbolInfo {
+                    name: self.pou.name.clone(),
+                    kind: SymbolKind::Function,
+                    type_info: Some(Type::from_spec(return_type)),
+                    declared_type: Some(return_type.clone()),
+                    declaration: self.pou.span,
+                    scope: self.pou.name.clone(),
+                    var_block: None,
+                })
+            }
+            Target::Member { owner, name } => match self.owner(owner)? {
+                Owner::Struct(decl, fields) => {
+                    let field = fields.iter().find(|field| field.name.eq_ignore_ascii_case(name))?;
+                    Some(SymbolInfo {
+                        name: field.name.clone(),
+                        kind: SymbolKind::Variable,
+                        type_info: Some(Type::from_spec(&field.var_type)),
+                        declared_type: Some(field.var_type.clone()),
+                        declaration: name_span(field),
+                        scope: decl.name.clone(),
+                        var_block: None,
+                    })
+                }
+                Owner::Pou(pou) => {
+                    let (block, var) = find_var(&pou.var_blocks, name)?;
+                    Some(variable(&pou.name, block, var))
+                }
+            },
+        }
+    }
+
+    /// Declared type of an expression that names a variable, member or
+    /// array element.
+    fn spec_of(&self, expr: &Expr) -> Option<&'a TypeSpec> {
+        match &expr.kind {
+            ExprKind::Ident(name) => find_var(&self.pou.var_blocks, name).map(|(_, var)| &var.var_type),
+            ExprKind::Paren(inner) => self.spec_of(inner),
+            ExprKind::MemberAccess { expr, member } => {
+                let owner = self.type_name(self.spec_of(expr)?)?;
+                match self.owner(owner)? {
+                    Owner::Struct(_, fields) => fields
+                        .iter()
+                        .find(|field| field.name.eq_ignore_ascii_case(member))
+                        .map(|field| &field.var_type),
+                    Owner::Pou(pou) => find_var(&pou.var_blocks, member).map(|(_, var)| &var.var_type),
+                }
+            }
+            ExprKind::ArrayIndex { array, .. } => self.element_of(self.spec_of(array)?),
+            _ => None,
+        }
+    }
+
+    /// Element type of an array type, following declared types and aliases.
+    fn element_of(&self, spec: &'a TypeSpec) -> Option<&'a TypeSpec> {
+        let mut spec = spec;
+        // Bounded in case of cyclic aliases
+        for _ in 0..=self.types.len() {
+            let type_name = match &spec.kind {
+                TypeKind::Array { element, .. } => return Some(element),
+                TypeKind::Simple(type_name) => type_name,
+                _ => return None,
+            };
+            match &self.find_type(type_name)?.definition {
+                TypeDef::Array { element, .. } => return Some(element),
+                TypeDef::Alias(alias) => spec = alias,
+                _ => return None,
+            }
+        }
+        None
+    }
+
+    /// Name of the structure type or function block a type refers to,
+    /// following aliases.
+    fn type_name(&self, spec: &'a TypeSpec) -> Option<&'a str> {
+        let mut spec = spec;
+        for _ in 0..=self.types.len() {
+            let TypeKind::Simple(type_name) = &spec.kind else {
+                return None;
+            };
+            match self.find_type(type_name).map(|decl| &decl.definition) {
+                Some(TypeDef::Alias(alias)) => spec = alias,
+                _ => return Some(type_name),
+            }
+        }
+        None
+    }
+
+    fn owner(&self, name: &str) -> Option<Owner<'a>> {
+        if let Some(decl) = self.find_type(name) {
+            return match &decl.definition {
+                TypeDef::Struct { fields } => Some(Owner::Struct(decl, fields)),
+                _ => None,
+            };
+        }
+        self.pous
+            .iter()
+            .find(|pou| pou.kind == PouKind::FunctionBlock && pou.name.eq_ignore_ascii_case(name))
+            .map(Owner::Pou)
+    }
+
+    fn find_type(&self, name: &str) -> Option<&'a TypeDecl> {
+        self.types.iter().find(|decl| decl.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Resolve the symbol at a byte offset of a POU, without type declarations
+/// or other POUs to resolve members through.
+pub fn symbol_at(pou: &Pou, offset: usize) -> Option<SymbolInfo> {
+    SymbolIndex::new(pou, &[], &[]).symbol_at(offset)
+}
+
+/// Spans of the occurrences of a symbol in a POU, including its declaration.
+///
+/// Members are only found if their type resolves; use [`SymbolIndex`] with
+/// the type declarations and function blocks for those.
+pub fn references_of(pou: &Pou, symbol: &SymbolInfo) -> Vec<Span> {
+    SymbolIndex::new(pou, &[], &[]).references_of(symbol)
+}
+
+/// A type with members.
+enum Owner<'a> {
+    Struct(&'a TypeDecl, &'a [VarDecl]),
+    Pou(&'a Pou),
+}
+
+fn find_var<'a>(blocks: &'a [VarBlock], name: &str) -> Option<(&'a VarBlock, &'a VarDecl)> {
+    blocks
+        .iter()
+        .flat_map(|block| block.vars.iter().map(move |var| (block, var)))
+        .find(|(_, var)| var.name.eq_ignore_ascii_case(name))
+}
+
+fn variable(scope: &str, block: &VarBlock, var: &VarDecl) -> SymbolInfo {
+    let kind = match block.kind {
+        _ if block.constant => SymbolKind::Constant,
+        VarBlockKind::VarInput => SymbolKind::Parameter,
+        VarBlockKind::VarOutput => SymbolKind::Output,
+        VarBlockKind::VarInOut => SymbolKind::InOut,
+        _ => SymbolKind::Variable,
+    };
+    SymbolInfo {
+        name: var.name.clone(),
+        kind,
+        type_info: Some(Type::from_spec(&var.var_type)),
+        declared_type: Some(var.var_type.clone()),
+        declaration: name_span(var),
+        scope: scope.to_string(),
+        var_block: Some(block.kind),
+    }
+}
+
+/// Span of the name of a declaration, which starts the declaration.
+fn name_span(var: &VarDecl) -> Span {
+    Span::new(var.span.start, var.span.start + var.name.len())
+}
+
+/// Span of a name at the start of a node.
+fn leading(span: Span, name: &str) -> Span {
+    Span::new(span.start, span.start + name.len())
+}
+
+/// Collects the identifier occurrences of a POU.
+struct Occurrences<'i, 'a> {
+    index: &'i SymbolIndex<'a>,
+    found: Vec<(Span, Target)>,
+}
+
+impl Occurrences<'_, '_> {
+    fn local(&mut self, span: Span, name: &str) {
+        self.found.push((span, Target::Local(name.to_string())));
+    }
+
+    /// Record the named arguments of a call of the instance `instance`.
+    fn arguments<'n>(&mut self, instance: &str, args: impl Iterator<Item = (Option<&'n String>, Span)>) {
+        let owner = find_var(&self.index.pou.var_blocks, instance)
+            .and_then(|(_, var)| self.index.type_name(&var.var_type))
+            .map(str::to_string);
+        let Some(owner) = owner else {
+            return;
+        };
+        for (name, span) in args {
+            if let Some(name) = name {
+                self.found.push((
+                    leading(span, name),
+                    Target::Member { owner: owner.clone(), name: name.clone() },
+                ));
+            }
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for Occurrences<'_, '_> {
+    fn visit_var_decl(&mut self, decl: &'ast VarDecl) {
+        self.found.push((name_span(decl), Target::Local(decl.name.clone())));
+        walk_var_decl(self, decl);
+    }
+
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        if let StmtKind::Call { name, args } = &stmt.kind {
+            self.local(leading(stmt.span, name), name);
+            self.arguments(name, args.iter().map(|arg| (arg.name.as_ref(), arg.span)));
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match &expr.kind {
+            ExprKind::Ident(name) => self.local(expr.span, name),
+            ExprKind::FunctionCall { name, args } => {
+                self.local(leading(expr.span, name), name);
+                self.arguments(name, args.iter().map(|arg| (arg.name.as_ref(), arg.span)));
+            }
+            ExprKind::MemberAccess { expr: base, member } => {
+                let owner = self.index.spec_of(base).and_then(|spec| self.index.type_name(spec));
+                if let Some(owner) = owner {
+                    let span = Span::new(expr.span.end.saturating_sub(member.len()), expr.span.end);
+                    self.found.push((span, Target::Member { owner: owner.to_string(), name: member.clone() }));
+                }
+            }
+            _ => {}
+        }
+        walk_expr(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_pou, parse_type_block};
+
+    const MAIN: &str = "PROGRAM Main
+VAR
+    motor : Motor;
+    drives : ARRAY[1..2] OF Motor;
+    timer : Delay;
+    count : INT := 0;
+END_VAR
+motor.speed := motor.speed + count;
+drives[1].speed := 0;
+timer(IN := TRUE, PT := T#1s);
+IF timer.Q THEN count := count + 1; END_IF;
+END_PROGRAM";
+
+    fn texts(source: &str, spans: &[Span]) -> Vec<String> {
+        spans.iter().map(|span| span.text(source).to_string()).collect()
+    }
+
+    #[test]
+    fn test_locals() {
+        let pou = parse_pou(MAIN).unwrap();
+        let offset = MAIN.rfind("count + 1").unwrap() + 2;
+        let symbol = symbol_at(&pou, offset).unwrap();
+        assert_eq!(symbol.name, "count");
+        assert_eq!(symbol.kind, SymbolKind::Variable);
+        assert_eq!(symbol.type_info, Some(Type::Int));
+        assert_eq!(symbol.scope, "Main");
+        assert_eq!(symbol.var_block, Some(VarBlockKind::Var));
+        assert_eq!(symbol.declaration.start, MAIN.find("count :").unwrap());
+
+        let references = references_of(&pou, &symbol);
+        assert_eq!(references.len(), 4);
+        assert_eq!(texts(MAIN, &references), ["count"; 4]);
+
+        // Offsets on keywords and literals resolve nothing
+        assert!(symbol_at(&pou, MAIN.find("THEN").unwrap()).is_none());
+        // Without the type declarations, members do not resolve
+        assert!(symbol_at(&pou, MAIN.find("speed").unwrap()).is_none());
+    }
+
+    #[test]
+    fn test_members_through_types_and_function_blocks() {
+        let types = parse_type_block("TYPE Motor : STRUCT speed : INT; running : BOOL; END_STRUCT; END_TYPE").unwrap();
+        let delay = parse_pou(
+            "FUNCTION_BLOCK Delay
+            VAR_INPUT IN : BOOL; PT : TIME; END_VAR
+            VAR_OUTPUT Q : BOOL; END_VAR
+            Q := IN;
+            END_FUNCTION_BLOCK",
+        )
+        .unwrap();
+        let pous = [delay];
+        let pou = parse_pou(MAIN).unwrap();
+        let index = SymbolIndex::new(&pou, &types, &pous);
+
+        let speed = index.symbol_at(MAIN.find("speed :=").unwrap()).unwrap();
+        assert_eq!((speed.name.as_str(), speed.scope.as_str()), ("speed", "Motor"));
+        assert_eq!(speed.var_block, None);
+        let references = index.references_of(&speed);
+        assert_eq!(texts(MAIN, &references), ["speed", "speed", "speed"]);
+
+        let q = index.symbol_at(MAIN.find("Q THEN").unwrap()).unwrap();
+        assert_eq!((q.kind, q.scope.as_str()), (SymbolKind::Output, "Delay"));
+
+        let pt = index.symbol_at(MAIN.find("PT :=").unwrap()).unwrap();
+        assert_eq!((pt.kind, pt.type_info), (SymbolKind::Parameter, Some(Type::Time)));
+
+        // The base of a member access is the instance itself
+        let timer = index.symbol_at(MAIN.find("timer.Q").unwrap()).unwrap();
+        assert_eq!(texts(MAIN, &index.references_of(&timer)), ["timer", "timer", "timer"]);
+    }
+
+    #[test]
+    fn test_function_return_value() {
+        let code = "FUNCTION Twice : INT VAR_INPUT x : INT; END_VAR Twice := x * 2; END_FUNCTION";
+        let pou = parse_pou(code).unwrap();
+        let symbol = symbol_at(&pou, code.find("Twice :=").unwrap()).unwrap();
+        assert_eq!(symbol.kind, SymbolKind::Function);
+        let x = symbol_at(&pou, code.rfind('x').unwrap()).unwrap();
+        assert_eq!(references_of(&pou, &x).len(), 2);
+    }
+}
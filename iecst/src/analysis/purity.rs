@@ -0,0 +1,171 @@
+//! Function purity checks.
+//!
+//! An IEC function has no memory between calls: its result depends only on
+//! its inputs, which is what makes it safe to call from any task. Writing a
+//! global variable or calling a function block instance from a function
+//! breaks that.
+
+use std::collections::HashSet;
+
+use crate::ast::*;
+use crate::analysis::visit::{visit_expressions, visit_statements};
+use crate::analysis::{build_symbol_table, Diagnostic, DiagnosticKind, SymbolTable};
+
+/// Check a FUNCTION for side effects.
+///
+/// Assignments and output bindings (`Q => g`) to VAR_GLOBAL or
+/// VAR_EXTERNAL variables, including their members and elements, are
+/// reported as `GlobalWriteInFunction`; calls of function block instances
+/// as `StatefulCallInFunction`. Both are warnings, reported once per
+/// variable or instance. Function blocks and programs keep state by design
+/// and are not checked.
+pub fn check_function_purity(pou: &Pou) -> Vec<Diagnostic> {
+    if pou.kind != PouKind::Function {
+        return Vec::new();
+    }
+    let symbols = build_symbol_table(pou, &mut Vec::new());
+    let mut checker = PurityChecker {
+        function: &pou.name,
+        symbols: &symbols,
+        reported: HashSet::new(),
+        diagnostics: Vec::new(),
+    };
+
+    visit_statements(&pou.body, &mut |stmt| match &stmt.kind {
+        StmtKind::Assignment { target, .. } => checker.write(target),
+        StmtKind::Call { name, args } => {
+            checker.call(name, stmt.span);
+            for arg in args.iter().filter(|arg| arg.output) {
+                if let Some(ref value) = arg.value {
+                    checker.write(value);
+                }
+            }
+        }
+        _ => {}
+    });
+    visit_expressions(&pou.body, &mut |expr| {
+        if let ExprKind::FunctionCall { name, args } = &expr.kind {
+            checker.call(name, expr.span);
+            for arg in args.iter().filter(|arg| arg.output) {
+                if let Some(ref value) = arg.value {
+                    checker.write(value);
+                }
+            }
+        }
+    });
+
+    let mut diagnostics = checker.diagnostics;
+    diagnostics.sort_by_key(|d| d.span.start);
+    diagnostics
+}
+
+struct PurityChecker<'a> {
+    function: &'a str,
+    symbols: &'a SymbolTable,
+    /// Uppercase names already reported
+    reported: HashSet<String>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl PurityChecker<'_> {
+    /// Check a write to `target`.
+    fn write(&mut self, target: &Expr) {
+        let Some(name) = base_variable(target) else {
+            return;
+        };
+        let Some(symbol) = self.symbols.lookup(name) else {
+            return;
+        };
+        if matches!(symbol.var_block, Some(VarBlockKind::VarGlobal | VarBlockKind::VarExternal))
+            && self.reported.insert(symbol.name.to_uppercase())
+        {
+            self.diagnostics.push(Diagnostic::warning(
+                DiagnosticKind::GlobalWriteInFunction {
+                    function: self.function.to_string(),
+                    name: symbol.name.clone(),
+                },
+                target.span,
+            ));
+        }
+    }
+
+    /// Check a call of `name`; a declared variable is a function block instance.
+    fn call(&mut self, name: &str, span: crate::Span) {
+        let Some(symbol) = self.symbols.lookup(name) else {
+            return;
+        };
+        if symbol.var_block.is_some() && self.reported.insert(symbol.name.to_uppercase()) {
+            self.diagnostics.push(Diagnostic::warning(
+                DiagnosticKind::StatefulCallInFunction {
+                    function: self.function.to_string(),
+                    instance: symbol.name.clone(),
+                },
+                span,
+            ));
+        }
+    }
+}
+
+/// Variable at the root of an access path (`g` in `g.items[i].count`).
+fn base_variable(expr: &Expr) -> Option<&str> {
+    match &expr.kind {
+        ExprKind::Ident(name) => Some(name),
+        ExprKind::MemberAccess { expr: inner, .. } | ExprKind::Paren(inner) => base_variable(inner),
+        ExprKind::ArrayIndex { array, .. } => base_variable(array),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::Severity;
+    use crate::parse_pou;
+
+    fn check(kind: &str, body: &str) -> Vec<String> {
+        let header = if kind == "FUNCTION" { "FUNCTION Scale : INT" } else { "FUNCTION_BLOCK Scale" };
+        let code = format!(
+            "{header}
+            VAR_INPUT raw : INT; END_VAR
+            VAR_EXTERNAL calls : DINT; line : LineData; END_VAR
+            VAR tmp : INT; delay : TON; END_VAR
+            {body}
+            END_{kind}"
+        );
+        let diagnostics = check_function_purity(&parse_pou(&code).unwrap());
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+        diagnostics.iter().map(|d| d.kind.to_string()).collect()
+    }
+
+    #[test]
+    fn test_global_writes() {
+        let found = check(
+            "FUNCTION",
+            "tmp := raw * 2; calls := calls + 1; line.items[tmp].count := 0; calls := 0; Scale := tmp;",
+        );
+        assert_eq!(
+            found,
+            [
+                "function 'Scale' writes global variable 'calls'; functions should have no side effects",
+                "function 'Scale' writes global variable 'line'; functions should have no side effects",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_instance_calls_and_output_bindings() {
+        let found = check("FUNCTION", "delay(IN := raw > 0, PT := T#1s, Q => calls); Scale := ABS(raw);");
+        assert_eq!(
+            found,
+            [
+                "function 'Scale' calls function block instance 'delay', whose state is kept between calls",
+                "function 'Scale' writes global variable 'calls'; functions should have no side effects",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_function_blocks_exempt() {
+        assert!(check("FUNCTION_BLOCK", "calls := calls + 1; delay(IN := TRUE);").is_empty());
+    }
+}
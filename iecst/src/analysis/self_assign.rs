@@ -0,0 +1,132 @@
+//! Self-assignment checks.
+//!
+//! Detects assignments whose target and value are the same variable path,
+//! such as `counter := counter;` or `a.b[1] := a.b[1];`.
+
+use std::collections::HashMap;
+
+use crate::ast::*;
+use crate::analysis::const_eval::{eval_const_int, pou_constants};
+use crate::analysis::visit::visit_statements;
+use crate::analysis::{Diagnostic, DiagnosticKind};
+
+/// Check a POU for assignments of a variable to itself.
+///
+/// Identifiers, member paths and array elements are compared structurally.
+/// Array indices match when they fold to the same constant or are the same
+/// variable path; indices containing function calls are never considered
+/// equal, since the call may have side effects.
+pub fn check_self_assignments(pou: &Pou) -> Vec<Diagnostic> {
+    let constants = pou_constants(pou);
+    let mut diagnostics = Vec::new();
+    visit_statements(&pou.body, &mut |stmt| {
+        if let StmtKind::Assignment { target, value } = &stmt.kind {
+            if same_path(target, value, &constants) {
+                diagnostics.push(Diagnostic::warning(
+                    DiagnosticKind::SelfAssignment { path: path_text(target) },
+                    stmt.span,
+                ));
+            }
+        }
+    });
+    diagnostics
+}
+
+/// Check whether two expressions denote the same variable path.
+fn same_path(a: &Expr, b: &Expr, constants: &HashMap<String, i64>) -> bool {
+    match (&a.kind, &b.kind) {
+        (ExprKind::Paren(inner), _) => same_path(inner, b, constants),
+        (_, ExprKind::Paren(inner)) => same_path(a, inner, constants),
+        (ExprKind::Ident(x), ExprKind::Ident(y)) => x.eq_ignore_ascii_case(y),
+        (
+            ExprKind::MemberAccess { expr: x, member: mx },
+            ExprKind::MemberAccess { expr: y, member: my },
+        ) => mx.eq_ignore_ascii_case(my) && same_path(x, y, constants),
+        (
+            ExprKind::ArrayIndex { array: x, indices: ix },
+            ExprKind::ArrayIndex { array: y, indices: iy },
+        ) => {
+            ix.len() == iy.len()
+                && ix.iter().zip(iy).all(|(i, j)| same_index(i, j, constants))
+                && same_path(x, y, constants)
+        }
+        _ => false,
+    }
+}
+
+/// Check whether two index expressions always select the same element.
+fn same_index(a: &Expr, b: &Expr, constants: &HashMap<String, i64>) -> bool {
+    match (eval_const_int(a, constants), eval_const_int(b, constants)) {
+        (Some(x), Some(y)) => x == y,
+        (None, None) => same_path(a, b, constants),
+        _ => false,
+    }
+}
+
+/// Render a variable path as source text.
+fn path_text(expr: &Expr) -> String {
+    match &expr.kind {
+        ExprKind::Ident(name) => name.clone(),
+        ExprKind::Paren(inner) => path_text(inner),
+        ExprKind::MemberAccess { expr, member } => format!("{}.{}", path_text(expr), member),
+        ExprKind::ArrayIndex { array, indices } => {
+            let indices: Vec<String> = indices.iter().map(index_text).collect();
+            format!("{}[{}]", path_text(array), indices.join(", "))
+        }
+        _ => "?".to_string(),
+    }
+}
+
+fn index_text(expr: &Expr) -> String {
+    match &expr.kind {
+        ExprKind::IntLiteral(value) => value.to_string(),
+        ExprKind::UnaryOp { op: UnaryOp::Neg, expr } => format!("-{}", index_text(expr)),
+        _ => path_text(expr),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<String> {
+        let code = format!(
+            "PROGRAM Main
+            VAR CONSTANT LAST : INT := 9; END_VAR
+            VAR counter : INT; i : INT; a : MyStruct; arr : ARRAY[0..9] OF INT; END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        check_self_assignments(&parse_pou(&code).unwrap())
+            .iter()
+            .map(|d| d.kind.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn test_identifier_and_member_paths() {
+        let diags = check("counter := COUNTER; a.b := a.b; a.b := a.c;");
+        assert_eq!(
+            diags,
+            vec![
+                "assignment of 'counter' to itself has no effect",
+                "assignment of 'a.b' to itself has no effect",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_array_indices() {
+        let diags = check("arr[1] := arr[1]; arr[LAST] := arr[9]; arr[i] := arr[i]; arr[1] := arr[2];");
+        assert_eq!(diags.len(), 3);
+        assert_eq!(diags[1], "assignment of 'arr[LAST]' to itself has no effect");
+    }
+
+    #[test]
+    fn test_side_effecting_index_not_flagged() {
+        let diags = check("arr[NextIndex()] := arr[NextIndex()]; counter := counter + 0;");
+        assert!(diags.is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+//! String truncation checks.
+//!
+//! Detects assignments of a STRING or WSTRING value that is longer than
+//! the declared length of the target, which silently truncates.
+
+use crate::ast::*;
+use crate::analysis::visit::visit_statements;
+use crate::analysis::{build_symbol_table, Diagnostic, DiagnosticKind, Type, TypeChecker};
+
+/// Check a POU for string assignments that may truncate.
+///
+/// The target needs a declared length (`STRING[10]`). The source length is
+/// the character count of a string literal (after `$` escapes), or the
+/// declared length of a string variable. STRING and WSTRING are checked
+/// separately; assignments mixing them are left to type checking.
+pub fn check_string_truncation(pou: &Pou) -> Vec<Diagnostic> {
+    let symbols = build_symbol_table(pou, &mut Vec::new());
+    let type_checker = TypeChecker::new(&symbols);
+
+    let mut diagnostics = Vec::new();
+    visit_statements(&pou.body, &mut |stmt| {
+        let StmtKind::Assignment { target, value } = &stmt.kind else {
+            return;
+        };
+        let target_type = type_checker.infer_expr_type(target, &mut Vec::new()).ty;
+        let Some((wide, Some(capacity))) = string_length(&target_type) else {
+            return;
+        };
+        let Some(length) = source_length(value, wide, &type_checker) else {
+            return;
+        };
+        if length > capacity {
+            diagnostics.push(Diagnostic::warning(
+                DiagnosticKind::StringTruncation {
+                    target: target_type.display_name(),
+                    capacity,
+                    length,
+                },
+                stmt.span,
+            ));
+        }
+    });
+    diagnostics
+}
+
+/// Whether a type is a WSTRING, and its declared length, for string types.
+fn string_length(ty: &Type) -> Option<(bool, Option<u32>)> {
+    match ty {
+        Type::String { max_length } => Some((false, *max_length)),
+        Type::WString { max_length } => Some((true, *max_length)),
+        _ => None,
+    }
+}
+
+/// Known length of a string source of the given width.
+fn source_length(value: &Expr, wide: bool, type_checker: &TypeChecker) -> Option<u32> {
+    match &value.kind {
+        ExprKind::Paren(inner) => source_length(inner, wide, type_checker),
+        ExprKind::StringLiteral(text) if !wide => u32::try_from(text.chars().count()).ok(),
+        ExprKind::WStringLiteral(text) if wide => u32::try_from(text.chars().count()).ok(),
+        ExprKind::Ident(_) | ExprKind::MemberAccess { .. } | ExprKind::ArrayIndex { .. } => {
+            let source_type = type_checker.infer_expr_type(value, &mut Vec::new()).ty;
+            match string_length(&source_type) {
+                Some((source_wide, length)) if source_wide == wide => length,
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    fn check(body: &str) -> Vec<Diagnostic> {
+        let code = format!(
+            "PROGRAM Main
+            VAR
+                code : STRING[4];
+                name : STRING[20];
+                text : STRING;
+                label : WSTRING[3];
+                names : ARRAY[1..2] OF STRING[30];
+            END_VAR
+            {}
+            END_PROGRAM",
+            body
+        );
+        check_string_truncation(&parse_pou(&code).unwrap())
+    }
+
+    #[test]
+    fn test_literal_too_long() {
+        let diags = check("code := 'ABCDE'; code := 'AB$'C'; label := \"abcd\";");
+        assert_eq!(diags.len(), 2);
+        assert_eq!(
+            diags[0].kind,
+            DiagnosticKind::StringTruncation { target: "STRING[4]".to_string(), capacity: 4, length: 5 }
+        );
+        assert_eq!(
+            diags[1].kind,
+            DiagnosticKind::StringTruncation { target: "WSTRING[3]".to_string(), capacity: 3, length: 4 }
+        );
+    }
+
+    #[test]
+    fn test_variable_source() {
+        let diags = check("code := name; name := code; code := names[1]; name := (names[2]);");
+        let lengths: Vec<_> = diags
+            .iter()
+            .map(|d| match d.kind {
+                DiagnosticKind::StringTruncation { capacity, length, .. } => (capacity, length),
+                _ => (0, 0),
+            })
+            .collect();
+        assert_eq!(lengths, [(4, 20), (4, 30), (20, 30)]);
+    }
+
+    #[test]
+    fn test_unknown_lengths_not_flagged() {
+        let diags = check("text := 'a long text without a declared limit'; code := text; code := CONCAT('A', 'B');");
+        assert!(diags.is_empty());
+    }
+}
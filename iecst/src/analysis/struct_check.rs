@@ -0,0 +1,191 @@
+//! Structure declaration checks.
+//!
+//! Reports structure members declared twice and structures that contain
+//! themselves by value, directly or through other structures, aliases and
+//! array elements. Such a structure would have infinite size. The type
+//! specifications have no pointer or reference kinds, so every named member
+//! type is a value dependency.
+
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, VecDeque};
+
+use crate::analysis::{Diagnostic, DiagnosticKind};
+use crate::ast::*;
+
+/// Check the structures of type declarations for duplicate member names and
+/// recursive definitions.
+///
+/// Member names are compared case-insensitively; each duplicate is an error
+/// carrying the span of the first declaration. A structure on a value cycle
+/// is an error listing the cycle, reported once per cycle at the structure
+/// declared first.
+pub fn check_struct_types(types: &[TypeDecl]) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    for decl in types {
+        let TypeDef::Struct { fields } = &decl.definition else {
+            continue;
+        };
+        let mut seen: HashMap<String, &VarDecl> = HashMap::new();
+        for field in fields {
+            if let Some(original) = seen.get(&field.name.to_uppercase()) {
+                diagnostics.push(Diagnostic::error(
+                    DiagnosticKind::DuplicateStructMember {
+                        type_name: decl.name.clone(),
+                        member: field.name.clone(),
+                        original: original.span,
+                    },
+                    field.span,
+                ));
+            } else {
+                seen.insert(field.name.to_uppercase(), field);
+            }
+        }
+    }
+
+    let graph = TypeGraph::new(types);
+    let mut reported: Vec<usize> = Vec::new();
+    for (index, decl) in types.iter().enumerate() {
+        if !matches!(decl.definition, TypeDef::Struct { .. }) || reported.contains(&index) {
+            continue;
+        }
+        let Some(cycle) = graph.cycle_through(index) else {
+            continue;
+        };
+        if cycle.iter().any(|i| reported.contains(i)) {
+            continue;
+        }
+        reported.extend(&cycle);
+        let mut names: Vec<String> = cycle.iter().map(|&i| types[i].name.clone()).collect();
+        names.push(decl.name.clone());
+        diagnostics.push(Diagnostic::error(
+            DiagnosticKind::RecursiveStruct {
+                type_name: decl.name.clone(),
+                cycle: names,
+            },
+            decl.span,
+        ));
+    }
+
+    diagnostics
+}
+
+/// Value dependencies between the declared types.
+struct TypeGraph {
+    /// Indices of the declared types each type contains by value
+    edges: Vec<Vec<usize>>,
+}
+
+impl TypeGraph {
+    fn new(types: &[TypeDecl]) -> Self {
+        let index: HashMap<String, usize> = types
+            .iter()
+            .enumerate()
+            .map(|(i, decl)| (decl.name.to_uppercase(), i))
+            .rev()
+            .collect();
+        let edges = types
+            .iter()
+            .map(|decl| {
+                let mut names = Vec::new();
+                match &decl.definition {
+                    TypeDef::Struct { fields } => {
+                        for field in fields {
+                            value_type_names(&field.var_type, &mut names);
+                        }
+                    }
+                    TypeDef::Alias(spec) => value_type_names(spec, &mut names),
+                    TypeDef::Array { element, .. } => value_type_names(element, &mut names),
+                    TypeDef::Subrange { base, .. } => names.push(base.clone()),
+                    TypeDef::Enum { .. } => {}
+                }
+                names.iter().filter_map(|name| index.get(&name.to_uppercase()).copied()).collect()
+            })
+            .collect();
+        Self { edges }
+    }
+
+    /// Shortest cycle from `start` back to itself, as the types along it
+    /// starting with `start`.
+    fn cycle_through(&self, start: usize) -> Option<Vec<usize>> {
+        let mut parent: HashMap<usize, usize> = HashMap::new();
+        let mut queue = VecDeque::from([start]);
+        while let Some(node) = queue.pop_front() {
+            for &next in &self.edges[node] {
+                if next == start {
+                    let mut cycle = vec![node];
+                    while let Some(&previous) = parent.get(cycle.last()?) {
+                        cycle.push(previous);
+                    }
+                    cycle.reverse();
+                    return Some(cycle);
+                }
+                if let Entry::Vacant(entry) = parent.entry(next) {
+                    entry.insert(node);
+                    queue.push_back(next);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Names of the types a type specification holds by value.
+fn value_type_names(spec: &TypeSpec, names: &mut Vec<String>) {
+    match &spec.kind {
+        TypeKind::Simple(name) => names.push(name.clone()),
+        TypeKind::Array { element, .. } => value_type_names(element, names),
+        TypeKind::Subrange { base, .. } => names.push(base.clone()),
+        TypeKind::String { .. } | TypeKind::WString { .. } => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_type_block;
+
+    fn check(types: &str) -> Vec<String> {
+        let types = parse_type_block(&format!("TYPE {} END_TYPE", types)).unwrap();
+        check_struct_types(&types).iter().map(|d| d.kind.to_string()).collect()
+    }
+
+    #[test]
+    fn test_duplicate_members() {
+        let diags = check("Point : STRUCT x : INT; y : INT; X : REAL; y : BOOL; END_STRUCT;");
+        assert_eq!(
+            diags,
+            [
+                "structure 'Point' declares member 'X' more than once",
+                "structure 'Point' declares member 'y' more than once",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_recursive_structs() {
+        let diags = check(
+            "Node : STRUCT value : INT; next : Node; END_STRUCT;
+             A : STRUCT b : ARRAY[0..1] OF B; END_STRUCT;
+             B : STRUCT c : CAlias; END_STRUCT;
+             CAlias : A;
+             Leaf : STRUCT node : Node; END_STRUCT;",
+        );
+        assert_eq!(
+            diags,
+            [
+                "structure 'Node' contains itself by value: Node -> Node",
+                "structure 'A' contains itself by value: A -> B -> CAlias -> A",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_nested_structs_not_flagged() {
+        assert!(check(
+            "Inner : STRUCT x : INT; END_STRUCT;
+             Outer : STRUCT a : Inner; b : ARRAY[1..2] OF Inner; s : STRING[10]; END_STRUCT;"
+        )
+        .is_empty());
+    }
+}
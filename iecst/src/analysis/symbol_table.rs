@@ -1,6 +1,7 @@
 //! Symbol table for tracking declarations.
 
 use crate::Span;
+use crate::ast::VarBlockKind;
 use crate::analysis::{Diagnostic, DiagnosticKind, Severity, Type};
 use std::collections::HashMap;
 
@@ -21,6 +22,8 @@ pub struct Symbol {
     pub used: bool,
     /// Whether the symbol has been assigned
     pub assigned: bool,
+    /// VAR block the symbol was declared in (None for non-variables)
+    pub var_block: Option<VarBlockKind>,
 }
 
 /// Kind of symbol.
@@ -51,7 +54,7 @@ pub enum SymbolKind {
 pub struct Scope {
     /// Scope name (e.g., function name)
     pub name: String,
-    /// Symbols defined in this scope
+    /// Symbols defined in this scope, keyed by uppercase name
     pub symbols: HashMap<String, Symbol>,
     /// Parent scope index (None for global)
     pub parent: Option<usize>,
@@ -105,7 +108,7 @@ impl SymbolTable {
         let scope = &mut self.scopes[self.current];
         
         // Check for duplicate in current scope
-        if let Some(existing) = scope.symbols.get(&symbol.name) {
+        if let Some(existing) = scope.symbols.get(&symbol.name.to_uppercase()) {
             return Err(Diagnostic {
                 kind: DiagnosticKind::DuplicateDefinition {
                     name: symbol.name.clone(),
@@ -116,16 +119,19 @@ impl SymbolTable {
             });
         }
         
-        scope.symbols.insert(symbol.name.clone(), symbol);
+        scope.symbols.insert(symbol.name.to_uppercase(), symbol);
         Ok(())
     }
 
     /// Look up a symbol by name, searching up the scope chain.
+    ///
+    /// Names are case-insensitive, as in IEC 61131-3.
     pub fn lookup(&self, name: &str) -> Option<&Symbol> {
+        let key = name.to_uppercase();
         let mut scope_idx = Some(self.current);
         
         while let Some(idx) = scope_idx {
-            if let Some(symbol) = self.scopes[idx].symbols.get(name) {
+            if let Some(symbol) = self.scopes[idx].symbols.get(&key) {
                 return Some(symbol);
             }
             scope_idx = self.scopes[idx].parent;
@@ -136,13 +142,14 @@ impl SymbolTable {
 
     /// Look up a symbol mutably.
     pub fn lookup_mut(&mut self, name: &str) -> Option<&mut Symbol> {
+        let key = name.to_uppercase();
         let mut scope_idx = Some(self.current);
         
         // Find which scope contains the symbol
         let target_scope = loop {
             match scope_idx {
                 Some(idx) => {
-                    if self.scopes[idx].symbols.contains_key(name) {
+                    if self.scopes[idx].symbols.contains_key(&key) {
                         break Some(idx);
                     }
                     scope_idx = self.scopes[idx].parent;
@@ -151,7 +158,7 @@ impl SymbolTable {
             }
         };
         
-        target_scope.and_then(move |idx| self.scopes[idx].symbols.get_mut(name))
+        target_scope.and_then(move |idx| self.scopes[idx].symbols.get_mut(&key))
     }
 
     /// Mark a symbol as used.
@@ -169,27 +176,48 @@ impl SymbolTable {
     }
 
     /// Check for unused variables and return diagnostics.
+    ///
+    /// The diagnostic depends on the VAR block kind: inputs that are never
+    /// read are only a hint, outputs that are never assigned and unused
+    /// temporaries are warnings. In/out, global and external variables are
+    /// not checked, since they may be used elsewhere.
     pub fn check_unused(&self) -> Vec<Diagnostic> {
         let mut diagnostics = Vec::new();
         
         for scope in &self.scopes {
             for symbol in scope.symbols.values() {
-                // Skip checking outputs and in/outs (they might be used externally)
-                if matches!(symbol.kind, SymbolKind::Output | SymbolKind::InOut) {
-                    continue;
-                }
-                
-                if !symbol.used && symbol.kind == SymbolKind::Variable {
+                let name = symbol.name.clone();
+                let unused = match symbol.var_block {
+                    // Unused inputs are often kept for interface compatibility
+                    Some(VarBlockKind::VarInput) if !symbol.used => {
+                        Some((DiagnosticKind::UnusedInput { name }, Severity::Hint))
+                    }
+                    Some(VarBlockKind::VarOutput) if !symbol.assigned => {
+                        Some((DiagnosticKind::UnassignedOutput { name }, Severity::Warning))
+                    }
+                    Some(VarBlockKind::VarTemp) if !symbol.used => {
+                        Some((DiagnosticKind::UnusedTemp { name }, Severity::Warning))
+                    }
+                    Some(VarBlockKind::Var) | None
+                        if !symbol.used && symbol.kind == SymbolKind::Variable =>
+                    {
+                        Some((DiagnosticKind::UnusedVariable { name }, Severity::Warning))
+                    }
+                    _ => None,
+                };
+                if let Some((kind, severity)) = unused {
                     diagnostics.push(Diagnostic {
-                        kind: DiagnosticKind::UnusedVariable {
-                            name: symbol.name.clone(),
-                        },
+                        kind,
                         span: symbol.span,
-                        severity: Severity::Warning,
+                        severity,
                     });
                 }
                 
-                if !symbol.assigned && symbol.mutable && symbol.kind == SymbolKind::Variable {
+                let local = matches!(
+                    symbol.var_block,
+                    Some(VarBlockKind::Var | VarBlockKind::VarTemp) | None
+                );
+                if !symbol.assigned && symbol.mutable && symbol.kind == SymbolKind::Variable && local {
                     diagnostics.push(Diagnostic {
                         kind: DiagnosticKind::UninitializedVariable {
                             name: symbol.name.clone(),
@@ -211,7 +239,7 @@ impl SymbolTable {
 
     /// Check if a name is defined in the current scope only.
     pub fn is_defined_locally(&self, name: &str) -> bool {
-        self.scopes[self.current].symbols.contains_key(name)
+        self.scopes[self.current].symbols.contains_key(&name.to_uppercase())
     }
 }
 
@@ -237,6 +265,7 @@ mod tests {
             mutable: true,
             used: false,
             assigned: false,
+            var_block: None,
         };
         
         assert!(table.define(symbol).is_ok());
@@ -257,6 +286,7 @@ mod tests {
             mutable: true,
             used: false,
             assigned: true,
+            var_block: None,
         }).unwrap();
         
         // Enter new scope
@@ -274,6 +304,7 @@ mod tests {
             mutable: true,
             used: false,
             assigned: true,
+            var_block: None,
         }).unwrap();
         
         assert!(table.lookup("local_var").is_some());
@@ -298,6 +329,7 @@ mod tests {
             mutable: true,
             used: false,
             assigned: false,
+            var_block: None,
         };
         
         let symbol2 = Symbol {
@@ -308,6 +340,7 @@ mod tests {
             mutable: true,
             used: false,
             assigned: false,
+            var_block: None,
         };
         
         assert!(table.define(symbol1).is_ok());
@@ -326,6 +359,7 @@ mod tests {
             mutable: true,
             used: true,
             assigned: true,
+            var_block: None,
         }).unwrap();
         
         table.define(Symbol {
@@ -336,6 +370,7 @@ mod tests {
             mutable: true,
             used: false,
             assigned: true,
+            var_block: None,
         }).unwrap();
         
         let diagnostics = table.check_unused();
@@ -345,4 +380,57 @@ mod tests {
             DiagnosticKind::UnusedVariable { ref name } if name == "unused_var"
         ));
     }
+
+    #[test]
+    fn test_unused_by_block_kind() {
+        let mut table = SymbolTable::new();
+        let blocks = [
+            ("enable", SymbolKind::Parameter, VarBlockKind::VarInput),
+            ("done", SymbolKind::Output, VarBlockKind::VarOutput),
+            ("scratch", SymbolKind::Variable, VarBlockKind::VarTemp),
+            ("shared", SymbolKind::Variable, VarBlockKind::VarExternal),
+        ];
+        for (name, kind, block) in blocks {
+            table.define(Symbol {
+                name: name.to_string(),
+                kind,
+                type_info: None,
+                span: Span::default(),
+                mutable: true,
+                used: false,
+                assigned: block == VarBlockKind::VarTemp,
+                var_block: Some(block),
+            }).unwrap();
+        }
+        
+        let mut diagnostics = table.check_unused();
+        diagnostics.sort_by_key(|d| d.kind.to_string());
+        let found: Vec<_> = diagnostics.iter().map(|d| (d.kind.to_string(), d.severity)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("input 'enable' is never read".to_string(), Severity::Hint),
+                ("output 'done' is never assigned".to_string(), Severity::Warning),
+                ("unused temporary variable 'scratch'".to_string(), Severity::Warning),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lookup_case_insensitive() {
+        let mut table = SymbolTable::new();
+        table.define(Symbol {
+            name: "Counter".to_string(),
+            kind: SymbolKind::Variable,
+            type_info: None,
+            span: Span::default(),
+            mutable: true,
+            used: false,
+            assigned: false,
+            var_block: None,
+        }).unwrap();
+        
+        table.mark_used("COUNTER");
+        assert!(table.lookup("counter").is_some_and(|s| s.used));
+    }
 }
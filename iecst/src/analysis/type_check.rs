@@ -2,7 +2,9 @@
 
 use crate::ast::*;
 use crate::Span;
-use crate::analysis::{Diagnostic, DiagnosticKind, SymbolTable};
+use crate::analysis::{build_symbol_table, Diagnostic, DiagnosticKind, SymbolTable};
+use std::borrow::Cow;
+use std::collections::HashMap;
 
 /// Built-in and derived types for type checking.
 #[derive(Debug, Clone, PartialEq)]
@@ -67,10 +69,25 @@ impl Type {
             "DT" | "DATE_AND_TIME" => Type::DateTime,
             "STRING" => Type::String { max_length: None },
             "WSTRING" => Type::WString { max_length: None },
+            "ANY" => Type::Any,
             _ => Type::Struct { name: name.to_string() },
         }
     }
 
+    /// Create a type from a declared type specification.
+    pub fn from_spec(spec: &TypeSpec) -> Self {
+        match &spec.kind {
+            TypeKind::Simple(name) => Type::from_name(name),
+            TypeKind::Array { element, ranges } => Type::Array {
+                element: Box::new(Type::from_spec(element)),
+                dimensions: ranges.len(),
+            },
+            TypeKind::String { length } => Type::String { max_length: *length },
+            TypeKind::WString { length } => Type::WString { max_length: *length },
+            TypeKind::Subrange { base, .. } => Type::from_name(base),
+        }
+    }
+
     /// Check if this is a numeric type.
     pub fn is_numeric(&self) -> bool {
         matches!(
@@ -150,6 +167,55 @@ impl Type {
         }
     }
 
+    /// Check if a value of this type converts implicitly to `target`.
+    ///
+    /// Follows the IEC 61131-3 implicit conversion rules: only widening
+    /// conversions that cannot lose information are allowed.
+    pub fn widens_to(&self, target: &Type) -> bool {
+        if self == target {
+            return true;
+        }
+
+        match self {
+            Type::SInt => matches!(target, Type::Int | Type::DInt | Type::LInt | Type::Real | Type::LReal),
+            Type::Int => matches!(target, Type::DInt | Type::LInt | Type::Real | Type::LReal),
+            Type::DInt => matches!(target, Type::LInt | Type::LReal),
+            Type::USInt => matches!(
+                target,
+                Type::UInt | Type::UDInt | Type::ULInt |
+                Type::Int | Type::DInt | Type::LInt |
+                Type::Real | Type::LReal
+            ),
+            Type::UInt => matches!(
+                target,
+                Type::UDInt | Type::ULInt | Type::DInt | Type::LInt | Type::Real | Type::LReal
+            ),
+            Type::UDInt => matches!(target, Type::ULInt | Type::LInt | Type::LReal),
+            Type::Real => matches!(target, Type::LReal),
+            Type::Byte => matches!(target, Type::Word | Type::DWord | Type::LWord),
+            Type::Word => matches!(target, Type::DWord | Type::LWord),
+            Type::DWord => matches!(target, Type::LWord),
+            _ => false,
+        }
+    }
+
+    /// Find the smallest type both operands convert to implicitly.
+    pub fn common_type(a: &Type, b: &Type) -> Option<Type> {
+        if a.widens_to(b) {
+            return Some(b.clone());
+        }
+        if b.widens_to(a) {
+            return Some(a.clone());
+        }
+
+        [
+            Type::Int, Type::DInt, Type::LInt,
+            Type::Real, Type::LReal,
+        ]
+        .into_iter()
+        .find(|candidate| a.widens_to(candidate) && b.widens_to(candidate))
+    }
+
     /// Check if two types are compatible for assignment.
     pub fn is_assignable_from(&self, other: &Type) -> bool {
         if self == other {
@@ -166,16 +232,9 @@ impl Type {
             return true;
         }
         
-        // Numeric promotions
+        // Numeric values may only be widened implicitly
         if self.is_numeric() && other.is_numeric() {
-            // Allow integer to real
-            if self.is_real() && other.is_integer() {
-                return true;
-            }
-            // Allow smaller integers to larger (simplified)
-            if self.is_integer() && other.is_integer() {
-                return true; // Simplified - allow all integer conversions
-            }
+            return other.widens_to(self);
         }
         
         // String compatibility
@@ -183,8 +242,26 @@ impl Type {
             return true;
         }
         
+        // User-defined type names are case-insensitive
+        if let (Type::Struct { name: a }, Type::Struct { name: b }) = (self, other) {
+            return a.eq_ignore_ascii_case(b);
+        }
+        
         false
     }
+
+    /// Check if values of the two types can be compared with each other.
+    pub fn is_comparable_with(&self, other: &Type) -> bool {
+        if matches!(self, Type::Any | Type::Unknown) || matches!(other, Type::Any | Type::Unknown) {
+            return true;
+        }
+
+        (self.is_numeric() && other.is_numeric())
+            || (self.is_bool() && other.is_bool())
+            || (self.is_string() && other.is_string())
+            || self.is_assignable_from(other)
+            || other.is_assignable_from(self)
+    }
 }
 
 /// Extended type information.
@@ -224,15 +301,60 @@ impl TypeInfo {
     }
 }
 
+/// Types of the expressions in a POU body.
+///
+/// Resolves variables through the POU's declarations and calls through the
+/// return types of user-defined functions, like [`check_types`](super::check_types),
+/// for analyses that need the type of individual expressions.
+pub struct ExprTypes {
+    symbols: SymbolTable,
+    /// Return types of user-defined functions (keyed by uppercase name)
+    functions: HashMap<String, Type>,
+}
+
+impl ExprTypes {
+    /// Collect the declarations of a POU.
+    pub fn new(pou: &Pou, functions: &HashMap<String, Type>) -> Self {
+        Self {
+            symbols: build_symbol_table(pou, &mut Vec::new()),
+            functions: functions.iter().map(|(name, ty)| (name.to_uppercase(), ty.clone())).collect(),
+        }
+    }
+
+    /// Type of an expression, or `None` if it is unknown or an untyped
+    /// numeric literal, which takes the type its context expects.
+    pub fn type_of(&self, expr: &Expr) -> Option<Type> {
+        if is_untyped_literal(expr) {
+            return None;
+        }
+        let checker = TypeChecker {
+            symbols: &self.symbols,
+            functions: Cow::Borrowed(&self.functions),
+        };
+        let ty = checker.infer_expr_type(expr, &mut Vec::new()).ty;
+        (!matches!(ty, Type::Unknown | Type::Any)).then_some(ty)
+    }
+}
+
 /// Type checker for expressions and statements.
 pub struct TypeChecker<'a> {
     symbols: &'a SymbolTable,
+    /// Return types of user-defined functions (keyed by uppercase name)
+    functions: Cow<'a, HashMap<String, Type>>,
 }
 
 impl<'a> TypeChecker<'a> {
     /// Create a new type checker.
     pub fn new(symbols: &'a SymbolTable) -> Self {
-        Self { symbols }
+        Self {
+            symbols,
+            functions: Cow::Owned(HashMap::new()),
+        }
+    }
+
+    /// Register the return type of a user-defined function.
+    pub fn register_function(&mut self, name: &str, return_type: Type) {
+        self.functions.to_mut().insert(name.to_uppercase(), return_type);
     }
 
     /// Check a statement and return any diagnostics.
@@ -244,7 +366,14 @@ impl<'a> TypeChecker<'a> {
                 let target_type = self.infer_expr_type(target, &mut diagnostics);
                 let value_type = self.infer_expr_type(value, &mut diagnostics);
                 
-                if !target_type.ty.is_assignable_from(&value_type.ty) {
+                // Untyped literals take the type of their target
+                let compatible = if is_untyped_literal(value) {
+                    accepts_literal(&target_type.ty, &value_type.ty)
+                } else {
+                    target_type.ty.is_assignable_from(&value_type.ty)
+                };
+                
+                if !compatible {
                     diagnostics.push(Diagnostic::error(
                         DiagnosticKind::TypeMismatch {
                             expected: target_type.ty.display_name(),
@@ -430,7 +559,14 @@ impl<'a> TypeChecker<'a> {
                 let left_type = self.infer_expr_type(left, diagnostics);
                 let right_type = self.infer_expr_type(right, diagnostics);
                 
-                let result_type = self.binary_op_result_type(*op, &left_type.ty, &right_type.ty, expr.span, diagnostics);
+                let operands = Operands {
+                    left: &left_type.ty,
+                    left_literal: is_untyped_literal(left),
+                    right: &right_type.ty,
+                    right_literal: is_untyped_literal(right),
+                    right_span: right.span,
+                };
+                let result_type = self.binary_op_result_type(*op, &operands, expr.span, diagnostics);
                 TypeInfo::new(result_type)
             }
             
@@ -474,15 +610,20 @@ impl<'a> TypeChecker<'a> {
             }
             
             ExprKind::FunctionCall { name, args } => {
-                // Check argument types
+                // Check argument types; untyped literals carry no type information
+                let mut arg_types = Vec::with_capacity(args.len());
                 for arg in args {
                     if let Some(value) = &arg.value {
-                        self.infer_expr_type(value, diagnostics);
+                        let info = self.infer_expr_type(value, diagnostics);
+                        arg_types.push(if is_untyped_literal(value) { None } else { Some(info.ty) });
                     }
                 }
                 
-                // Return type based on known functions
-                let return_type = self.builtin_function_type(name);
+                // Return type based on user-defined or known functions
+                let return_type = match self.functions.get(&name.to_uppercase()) {
+                    Some(ty) => ty.clone(),
+                    None => self.builtin_function_type(name, &arg_types),
+                };
                 TypeInfo::new(return_type)
             }
             
@@ -533,21 +674,16 @@ impl<'a> TypeChecker<'a> {
     fn binary_op_result_type(
         &self,
         op: BinaryOp,
-        left: &Type,
-        right: &Type,
+        operands: &Operands<'_>,
         span: Span,
         diagnostics: &mut Vec<Diagnostic>,
     ) -> Type {
+        let (left, right) = (operands.left, operands.right);
         match op {
             // Arithmetic operators
             BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod | BinaryOp::Power => {
                 if left.is_numeric() && right.is_numeric() {
-                    // Promote to larger type
-                    if left.is_real() || right.is_real() {
-                        Type::LReal
-                    } else {
-                        Type::DInt
-                    }
+                    operands.numeric_result()
                 } else if left.is_string() && right.is_string() && op == BinaryOp::Add {
                     Type::String { max_length: None }
                 } else if left.is_time() && right.is_time() && matches!(op, BinaryOp::Add | BinaryOp::Sub) {
@@ -569,6 +705,15 @@ impl<'a> TypeChecker<'a> {
             
             // Comparison operators
             BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge => {
+                if !left.is_comparable_with(right) {
+                    diagnostics.push(Diagnostic::error(
+                        DiagnosticKind::TypeMismatch {
+                            expected: left.display_name(),
+                            found: right.display_name(),
+                        },
+                        operands.right_span,
+                    ));
+                }
                 Type::Bool
             }
             
@@ -578,7 +723,7 @@ impl<'a> TypeChecker<'a> {
                     Type::Bool
                 } else if left.is_integer() && right.is_integer() {
                     // Bitwise operation
-                    Type::DInt
+                    operands.numeric_result()
                 } else if !matches!(left, Type::Unknown) && !matches!(right, Type::Unknown) {
                     diagnostics.push(Diagnostic::error(
                         DiagnosticKind::IncompatibleTypes {
@@ -597,27 +742,54 @@ impl<'a> TypeChecker<'a> {
     }
 
     /// Get the return type of a builtin function.
-    fn builtin_function_type(&self, name: &str) -> Type {
-        match name.to_uppercase().as_str() {
+    ///
+    /// `args` holds the argument types, with `None` for untyped literals.
+    fn builtin_function_type(&self, name: &str, args: &[Option<Type>]) -> Type {
+        let upper = name.to_uppercase();
+
+        // Type conversion: <FROM>_TO_<TO> and TO_<TO>
+        let target = upper
+            .split_once("_TO_")
+            .map(|(_, to)| to)
+            .or_else(|| upper.strip_prefix("TO_"));
+        if let Some(target) = target {
+            return match Type::from_name(target) {
+                Type::Struct { .. } => Type::Unknown,
+                ty => ty,
+            };
+        }
+
+        // Generic functions return the type of their first typed argument
+        let generic = |skip: usize| {
+            args.iter()
+                .skip(skip)
+                .flatten()
+                .next()
+                .cloned()
+                .unwrap_or(Type::Any)
+        };
+
+        match upper.as_str() {
             // Math functions
-            "ABS" | "SQRT" | "LN" | "LOG" | "EXP" | "SIN" | "COS" | "TAN" | 
-            "ASIN" | "ACOS" | "ATAN" | "ATAN2" => Type::LReal,
+            "ABS" | "MAX" | "MIN" | "LIMIT" => generic(0),
+            "SQRT" | "LN" | "LOG" | "EXP" | "SIN" | "COS" | "TAN" |
+            "ASIN" | "ACOS" | "ATAN" | "ATAN2" => match generic(0) {
+                ty if ty.is_real() => ty,
+                _ => Type::LReal,
+            },
             
-            // Type conversion
-            "BOOL_TO_INT" | "REAL_TO_INT" | "TRUNC" | "ROUND" => Type::DInt,
-            "INT_TO_REAL" | "DINT_TO_REAL" => Type::LReal,
-            "INT_TO_STRING" | "REAL_TO_STRING" => Type::String { max_length: None },
-            "STRING_TO_INT" => Type::DInt,
-            "STRING_TO_REAL" => Type::LReal,
+            // Generic ANY_INT results
+            "TRUNC" | "ROUND" => Type::Any,
             
             // Bit operations
-            "SHL" | "SHR" | "ROL" | "ROR" => Type::DWord,
+            "SHL" | "SHR" | "ROL" | "ROR" => generic(0),
             
             // Selection
-            "SEL" | "MAX" | "MIN" | "LIMIT" | "MUX" => Type::Any,
+            "SEL" => generic(1),
+            "MUX" => Type::Any,
             
             // String
-            "LEN" | "FIND" => Type::DInt,
+            "LEN" | "FIND" => Type::Int,
             "LEFT" | "RIGHT" | "MID" | "CONCAT" | "INSERT" | "DELETE" | "REPLACE" => {
                 Type::String { max_length: None }
             }
@@ -627,6 +799,73 @@ impl<'a> TypeChecker<'a> {
     }
 }
 
+/// Operand types of a binary operation.
+struct Operands<'t> {
+    left: &'t Type,
+    left_literal: bool,
+    right: &'t Type,
+    right_literal: bool,
+    right_span: Span,
+}
+
+impl Operands<'_> {
+    /// Result type of a numeric operation, widening to a common type.
+    ///
+    /// An untyped literal adopts the type of the other operand.
+    fn numeric_result(&self) -> Type {
+        let (left, right) = (self.left, self.right);
+        match (self.left_literal, self.right_literal) {
+            (true, true) => {
+                if left.is_real() || right.is_real() {
+                    Type::LReal
+                } else {
+                    Type::DInt
+                }
+            }
+            (true, false) => literal_result(right, left),
+            (false, true) => literal_result(left, right),
+            (false, false) => Type::common_type(left, right).unwrap_or(Type::Unknown),
+        }
+    }
+}
+
+/// Result type of an operation between a typed operand and an untyped literal.
+fn literal_result(typed: &Type, literal: &Type) -> Type {
+    if literal.is_real() && !typed.is_real() {
+        Type::LReal
+    } else {
+        typed.clone()
+    }
+}
+
+/// Check if an expression consists only of untyped numeric literals.
+///
+/// Such expressions take their type from the context they are used in.
+fn is_untyped_literal(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::IntLiteral(_) | ExprKind::RealLiteral(_) => true,
+        ExprKind::Paren(inner) => is_untyped_literal(inner),
+        ExprKind::UnaryOp { op: UnaryOp::Neg, expr } => is_untyped_literal(expr),
+        ExprKind::BinaryOp { left, op, right } => {
+            matches!(
+                op,
+                BinaryOp::Add | BinaryOp::Sub | BinaryOp::Mul | BinaryOp::Div | BinaryOp::Mod | BinaryOp::Power
+            ) && is_untyped_literal(left)
+                && is_untyped_literal(right)
+        }
+        _ => false,
+    }
+}
+
+/// Check if a target of the given type accepts an untyped literal.
+fn accepts_literal(target: &Type, literal: &Type) -> bool {
+    match target {
+        Type::Any | Type::Unknown => true,
+        _ if literal.is_real() => target.is_real(),
+        _ => target.is_numeric(),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -660,4 +899,128 @@ mod tests {
         // Same types are compatible
         assert!(int_type.is_assignable_from(&int_type));
     }
+
+    #[test]
+    fn test_implicit_widening() {
+        assert!(Type::DInt.is_assignable_from(&Type::Int));
+        assert!(Type::LReal.is_assignable_from(&Type::Real));
+        assert!(Type::DInt.is_assignable_from(&Type::UInt));
+        // Narrowing is not implicit
+        assert!(!Type::Int.is_assignable_from(&Type::DInt));
+        assert!(!Type::Int.is_assignable_from(&Type::Real));
+        assert!(!Type::Real.is_assignable_from(&Type::LReal));
+        assert!(!Type::Real.is_assignable_from(&Type::DInt));
+    }
+
+    #[test]
+    fn test_common_type() {
+        assert_eq!(Type::common_type(&Type::Int, &Type::DInt), Some(Type::DInt));
+        assert_eq!(Type::common_type(&Type::Int, &Type::UInt), Some(Type::DInt));
+        assert_eq!(Type::common_type(&Type::DInt, &Type::Real), Some(Type::LReal));
+        assert_eq!(Type::common_type(&Type::LInt, &Type::ULInt), None);
+    }
+
+    fn check(decls: &[(&str, Type)], code: &str) -> Vec<Diagnostic> {
+        use crate::analysis::{Symbol, SymbolKind};
+
+        let mut symbols = SymbolTable::new();
+        for (name, ty) in decls {
+            symbols.define(Symbol {
+                name: name.to_string(),
+                kind: SymbolKind::Variable,
+                type_info: Some(ty.clone()),
+                span: Span::default(),
+                mutable: true,
+                used: false,
+                assigned: true,
+                var_block: Some(VarBlockKind::Var),
+            }).unwrap();
+        }
+        let mut checker = TypeChecker::new(&symbols);
+        checker.register_function("ScaleValue", Type::Real);
+        crate::parse_statements(code)
+            .unwrap()
+            .iter()
+            .flat_map(|stmt| checker.check_statement(stmt))
+            .collect()
+    }
+
+    fn mismatches(diags: &[Diagnostic]) -> Vec<(String, String)> {
+        diags
+            .iter()
+            .filter_map(|d| match &d.kind {
+                DiagnosticKind::TypeMismatch { expected, found } => Some((expected.clone(), found.clone())),
+                _ => None,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_assignment_narrowing_flagged() {
+        let decls = [("i", Type::Int), ("d", Type::DInt), ("r", Type::Real)];
+        let diags = check(&decls, "i := r; i := d; d := i; r := i;");
+        assert_eq!(
+            mismatches(&diags),
+            vec![
+                ("INT".to_string(), "REAL".to_string()),
+                ("INT".to_string(), "DINT".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_literals_take_target_type() {
+        let decls = [("i", Type::Int), ("r", Type::Real), ("b", Type::Bool)];
+        let diags = check(&decls, "i := 5; i := -(2 * 3); r := 1.5; i := i + 1; r := r * 2;");
+        assert!(mismatches(&diags).is_empty(), "{:?}", diags);
+
+        let diags = check(&decls, "i := 2.5; b := 1;");
+        assert_eq!(mismatches(&diags).len(), 2);
+    }
+
+    #[test]
+    fn test_function_call_return_type() {
+        let decls = [("i", Type::Int), ("d", Type::DInt), ("r", Type::Real)];
+        let diags = check(&decls, "i := REAL_TO_INT(r); d := INT_TO_DINT(i); i := MAX(i, 0);");
+        assert!(mismatches(&diags).is_empty(), "{:?}", diags);
+
+        let diags = check(&decls, "i := REAL_TO_DINT(r); i := ScaleValue(d);");
+        assert_eq!(
+            mismatches(&diags),
+            vec![
+                ("INT".to_string(), "DINT".to_string()),
+                ("INT".to_string(), "REAL".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_comparison_operands() {
+        let decls = [("i", Type::Int), ("r", Type::Real), ("b", Type::Bool)];
+        let diags = check(&decls, "IF i < r THEN b := TRUE; END_IF;");
+        assert!(mismatches(&diags).is_empty(), "{:?}", diags);
+
+        let diags = check(&decls, "IF b = i THEN b := FALSE; END_IF;");
+        assert_eq!(mismatches(&diags), vec![("BOOL".to_string(), "INT".to_string())]);
+    }
+
+    #[test]
+    fn test_expr_types() {
+        let pou = crate::parse_pou(
+            "PROGRAM Main VAR i : INT; r : REAL; s : STRING[20]; END_VAR i := 1; END_PROGRAM",
+        )
+        .unwrap();
+        let functions = HashMap::from([("Scale".to_string(), Type::LReal)]);
+        let types = ExprTypes::new(&pou, &functions);
+        let type_of = |code: &str| types.type_of(&crate::parse_expression(code).unwrap());
+
+        assert_eq!(type_of("i"), Some(Type::Int));
+        assert_eq!(type_of("i + r"), Some(Type::Real));
+        assert_eq!(type_of("SCALE(i)"), Some(Type::LReal));
+        assert_eq!(type_of("s"), Some(Type::String { max_length: Some(20) }));
+        // Untyped literals and unresolved names have no type of their own
+        assert_eq!(type_of("-5"), None);
+        assert_eq!(type_of("2.5 * 2"), None);
+        assert_eq!(type_of("missing"), None);
+    }
 }
@@ -0,0 +1,167 @@
+//! Variable usage tracking.
+//!
+//! Marks the symbols a POU body reads and writes, for unused and
+//! unassigned variable checks.
+
+use crate::ast::*;
+use crate::analysis::visit::visit_statements;
+use crate::analysis::SymbolTable;
+
+/// Mark every symbol read or written in `body` as used or assigned.
+pub(crate) fn record_usage(symbols: &mut SymbolTable, body: &[Stmt]) {
+    visit_statements(body, &mut |stmt| match &stmt.kind {
+        StmtKind::Assignment { target, value } => {
+            write(symbols, target);
+            read(symbols, value);
+        }
+        StmtKind::If { condition, elsif_branches, .. } => {
+            read(symbols, condition);
+            for (cond, _) in elsif_branches {
+                read(symbols, cond);
+            }
+        }
+        StmtKind::Case { expr, cases, .. } => {
+            read(symbols, expr);
+            for value in cases.iter().flat_map(|branch| &branch.values) {
+                match value {
+                    CaseValue::Single(label) => read(symbols, label),
+                    CaseValue::Range { from, to } => {
+                        read(symbols, from);
+                        read(symbols, to);
+                    }
+                }
+            }
+        }
+        StmtKind::For { var, from, to, by, .. } => {
+            symbols.mark_assigned(var);
+            symbols.mark_used(var);
+            read(symbols, from);
+            read(symbols, to);
+            if let Some(by) = by {
+                read(symbols, by);
+            }
+        }
+        StmtKind::While { condition, .. } | StmtKind::Repeat { until: condition, .. } => {
+            read(symbols, condition);
+        }
+        StmtKind::Return { value: Some(value) } => read(symbols, value),
+        StmtKind::Call { name, args } => {
+            symbols.mark_used(name);
+            for arg in args {
+                if let Some(value) = &arg.value {
+                    if arg.output {
+                        write(symbols, value);
+                    } else {
+                        read(symbols, value);
+                    }
+                }
+            }
+        }
+        _ => {}
+    });
+}
+
+/// Mark the symbols an expression reads.
+fn read(symbols: &mut SymbolTable, expr: &Expr) {
+    match &expr.kind {
+        ExprKind::Ident(name) => symbols.mark_used(name),
+        ExprKind::FunctionCall { name, args } => {
+            // The name may be a function block instance
+            symbols.mark_used(name);
+            for arg in args {
+                if let Some(value) = &arg.value {
+                    if arg.output {
+                        write(symbols, value);
+                    } else {
+                        read(symbols, value);
+                    }
+                }
+            }
+        }
+        ExprKind::BinaryOp { left, right, .. } => {
+            read(symbols, left);
+            read(symbols, right);
+        }
+        ExprKind::UnaryOp { expr: inner, .. }
+        | ExprKind::Paren(inner)
+        | ExprKind::MemberAccess { expr: inner, .. } => read(symbols, inner),
+        ExprKind::ArrayIndex { array, indices } => {
+            read(symbols, array);
+            for index in indices {
+                read(symbols, index);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Mark the variable an assignment target writes; index expressions are reads.
+fn write(symbols: &mut SymbolTable, target: &Expr) {
+    match &target.kind {
+        ExprKind::Ident(name) => symbols.mark_assigned(name),
+        ExprKind::Paren(inner) | ExprKind::MemberAccess { expr: inner, .. } => write(symbols, inner),
+        ExprKind::ArrayIndex { array, indices } => {
+            write(symbols, array);
+            for index in indices {
+                read(symbols, index);
+            }
+        }
+        _ => read(symbols, target),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::analysis::{check_unused_variables, DiagnosticKind};
+    use crate::parse_pou;
+
+    fn unused(code: &str) -> Vec<DiagnosticKind> {
+        let mut kinds: Vec<_> = check_unused_variables(&parse_pou(code).unwrap())
+            .into_iter()
+            .map(|d| d.kind)
+            .collect();
+        kinds.sort_by_key(|k| k.to_string());
+        kinds
+    }
+
+    #[test]
+    fn test_function_block_interface() {
+        let kinds = unused(
+            "FUNCTION_BLOCK Valve
+            VAR_INPUT open : BOOL; spare : BOOL; END_VAR
+            VAR_OUTPUT isOpen : BOOL; fault : BOOL; END_VAR
+            VAR_TEMP t : INT; END_VAR
+            VAR timer : TON; END_VAR
+            timer(IN := OPEN, PT := T#2s, Q => isOpen);
+            END_FUNCTION_BLOCK",
+        );
+        assert_eq!(
+            kinds,
+            vec![
+                DiagnosticKind::UnusedInput { name: "spare".to_string() },
+                DiagnosticKind::UnassignedOutput { name: "fault".to_string() },
+                DiagnosticKind::UnusedTemp { name: "t".to_string() },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_member_and_index_usage() {
+        let kinds = unused(
+            "PROGRAM Main
+            VAR data : Recipe; arr : ARRAY[0..9] OF INT; i : INT; total : INT; END_VAR
+            FOR i := 0 TO 9 DO
+                arr[i] := data.amount;
+            END_FOR;
+            total := 0;
+            END_PROGRAM",
+        );
+        assert_eq!(
+            kinds,
+            vec![
+                DiagnosticKind::UnusedVariable { name: "arr".to_string() },
+                DiagnosticKind::UnusedVariable { name: "total".to_string() },
+            ]
+        );
+    }
+}
@@ -0,0 +1,73 @@
+//! Statement traversal helpers over the [`Visitor`](crate::visitor::Visitor).
+
+use crate::ast::*;
+use crate::visitor::{walk_expr, walk_stmt, Visitor};
+
+/// Call `f` for every statement in a body, including nested statements.
+///
+/// Statements are visited in source order; a compound statement is visited
+/// before the statements it contains.
+pub(crate) fn visit_statements(body: &[Stmt], f: &mut impl FnMut(&Stmt)) {
+    struct Statements<F>(F);
+
+    impl<'ast, F: FnMut(&Stmt)> Visitor<'ast> for Statements<F> {
+        fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+            (self.0)(stmt);
+            walk_stmt(self, stmt);
+        }
+
+        fn visit_expr(&mut self, _expr: &'ast Expr) {}
+    }
+
+    Statements(f).visit_body(body);
+}
+
+/// Call `f` for every expression in a body, including subexpressions.
+///
+/// CASE labels are not visited.
+pub(crate) fn visit_expressions(body: &[Stmt], f: &mut impl FnMut(&Expr)) {
+    visit_statements(body, &mut |stmt| match &stmt.kind {
+        StmtKind::Assignment { target, value } => {
+            visit_expr(target, f);
+            visit_expr(value, f);
+        }
+        StmtKind::If { condition, elsif_branches, .. } => {
+            visit_expr(condition, f);
+            for (cond, _) in elsif_branches {
+                visit_expr(cond, f);
+            }
+        }
+        StmtKind::Case { expr, .. } => visit_expr(expr, f),
+        StmtKind::For { from, to, by, .. } => {
+            visit_expr(from, f);
+            visit_expr(to, f);
+            if let Some(by) = by {
+                visit_expr(by, f);
+            }
+        }
+        StmtKind::While { condition, .. } | StmtKind::Repeat { until: condition, .. } => {
+            visit_expr(condition, f);
+        }
+        StmtKind::Return { value: Some(value) } => visit_expr(value, f),
+        StmtKind::Call { args, .. } => {
+            for value in args.iter().filter_map(|arg| arg.value.as_ref()) {
+                visit_expr(value, f);
+            }
+        }
+        _ => {}
+    });
+}
+
+/// Call `f` for an expression and each of its subexpressions.
+pub(crate) fn visit_expr(expr: &Expr, f: &mut impl FnMut(&Expr)) {
+    struct Expressions<F>(F);
+
+    impl<'ast, F: FnMut(&Expr)> Visitor<'ast> for Expressions<F> {
+        fn visit_expr(&mut self, expr: &'ast Expr) {
+            (self.0)(expr);
+            walk_expr(self, expr);
+        }
+    }
+
+    Expressions(f).visit_expr(expr);
+}
@@ -114,6 +114,14 @@ impl BinaryOp {
     pub fn is_right_assoc(&self) -> bool {
         matches!(self, BinaryOp::Power)
     }
+
+    /// Is this a comparison operator (`=`, `<>`, `<`, `<=`, `>`, `>=`)?
+    pub fn is_comparison(&self) -> bool {
+        matches!(
+            self,
+            BinaryOp::Eq | BinaryOp::Ne | BinaryOp::Lt | BinaryOp::Le | BinaryOp::Gt | BinaryOp::Ge
+        )
+    }
 }
 
 /// Unary operators.
@@ -173,6 +181,8 @@ pub struct FunctionArg {
     pub name: Option<String>,
     /// The argument value (None for empty arguments in Rockwell ST)
     pub value: Option<Expr>,
+    /// Whether this is an output binding (param => var)
+    pub output: bool,
     pub span: Span,
 }
 
@@ -274,6 +284,8 @@ pub struct CallArg {
     pub name: Option<String>,
     /// The value (None for empty arguments in Rockwell ST)
     pub value: Option<Expr>,
+    /// Whether this is an output binding (param => var)
+    pub output: bool,
     pub span: Span,
 }
 
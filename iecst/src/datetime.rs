@@ -0,0 +1,325 @@
+//! Values of DATE, TIME_OF_DAY and DATE_AND_TIME literals.
+//!
+//! Literals are parsed with their long, short and `L` prefixes
+//! (`DATE#`/`D#`/`LDATE#`, `TIME_OF_DAY#`/`TOD#`/`LTOD#`,
+//! `DATE_AND_TIME#`/`DT#`/`LDT#`) and validated: a month 13, a February 30
+//! or an hour 25 is an error. Values compare chronologically and convert to
+//! and from a count since the epoch 1970-01-01-00:00:00, which makes
+//! differences and offsets plain integer arithmetic.
+//!
+//! ```
+//! use iecst::{Date, DateAndTime, TimeOfDay};
+//!
+//! let start = DateAndTime::parse("DT#2024-01-15-12:30:00").unwrap();
+//! let end = DateAndTime::parse("DT#2024-01-16-06:00:00.5").unwrap();
+//! assert!(start < end);
+//! assert_eq!(end.epoch_nanos() - start.epoch_nanos(), 63_000_500_000_000);
+//! assert_eq!(Date::parse("D#1970-01-02").unwrap().days_since_epoch(), 1);
+//! assert!(TimeOfDay::parse("TOD#25:00:00").is_err());
+//! ```
+
+use std::fmt;
+
+use crate::{ParseError, ParseErrorKind, ParseResult, Span};
+
+const NANOS_PER_SECOND: u64 = 1_000_000_000;
+const NANOS_PER_DAY: u64 = 86_400 * NANOS_PER_SECOND;
+
+const DATE_PREFIXES: &[&str] = &["DATE#", "D#", "LDATE#", "LD#"];
+const TOD_PREFIXES: &[&str] = &["TIME_OF_DAY#", "TOD#", "LTIME_OF_DAY#", "LTOD#"];
+const DT_PREFIXES: &[&str] = &["DATE_AND_TIME#", "DT#", "LDATE_AND_TIME#", "LDT#"];
+
+/// A calendar date, from year 0 to 9999.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    year: u16,
+    month: u8,
+    day: u8,
+}
+
+impl Date {
+    /// Create a date, or `None` if the month or day does not exist.
+    pub fn new(year: u16, month: u8, day: u8) -> Option<Self> {
+        let valid = year <= 9999 && (1..=12).contains(&month) && day >= 1 && day <= days_in_month(year, month);
+        valid.then_some(Self { year, month, day })
+    }
+
+    /// Parse a date literal such as `DATE#2024-01-15` or `D#2024-01-15`.
+    pub fn parse(literal: &str) -> ParseResult<Self> {
+        let value = strip_prefix(literal, DATE_PREFIXES)?;
+        parse_date(value).ok_or_else(|| invalid(literal))
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    pub fn month(&self) -> u8 {
+        self.month
+    }
+
+    pub fn day(&self) -> u8 {
+        self.day
+    }
+
+    /// Days since 1970-01-01, negative for earlier dates.
+    pub fn days_since_epoch(&self) -> i64 {
+        // Civil-from-days inverse with years starting in March
+        let year = i64::from(self.year) - i64::from(self.month <= 2);
+        let era = year.div_euclid(400);
+        let year_of_era = year - era * 400;
+        let month = i64::from(self.month);
+        let day_of_year = (153 * (month + if month > 2 { -3 } else { 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+        era * 146_097 + day_of_era - 719_468
+    }
+
+    /// The date `days` after 1970-01-01, or `None` outside years 0 to 9999.
+    pub fn from_days_since_epoch(days: i64) -> Option<Self> {
+        let days = days.checked_add(719_468)?;
+        let era = days.div_euclid(146_097);
+        let day_of_era = days - era * 146_097;
+        let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+        let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+        let mp = (5 * day_of_year + 2) / 153;
+        let day = day_of_year - (153 * mp + 2) / 5 + 1;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 };
+        let year = year_of_era + era * 400 + i64::from(month <= 2);
+        Self::new(u16::try_from(year).ok()?, month as u8, day as u8)
+    }
+}
+
+impl fmt::Display for Date {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "D#{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+/// A time of day with nanosecond resolution.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimeOfDay {
+    nanos: u64,
+}
+
+impl TimeOfDay {
+    /// Create a time of day, or `None` if a component is out of range.
+    pub fn new(hour: u8, minute: u8, second: u8, nanosecond: u32) -> Option<Self> {
+        let valid = hour < 24 && minute < 60 && second < 60 && u64::from(nanosecond) < NANOS_PER_SECOND;
+        valid.then(|| Self {
+            nanos: (u64::from(hour) * 3600 + u64::from(minute) * 60 + u64::from(second)) * NANOS_PER_SECOND
+                + u64::from(nanosecond),
+        })
+    }
+
+    /// Parse a time of day literal such as `TOD#12:30:00.5`; the seconds
+    /// are optional.
+    pub fn parse(literal: &str) -> ParseResult<Self> {
+        let value = strip_prefix(literal, TOD_PREFIXES)?;
+        parse_time_of_day(value).ok_or_else(|| invalid(literal))
+    }
+
+    pub fn hour(&self) -> u8 {
+        (self.nanos / (3600 * NANOS_PER_SECOND)) as u8
+    }
+
+    pub fn minute(&self) -> u8 {
+        (self.nanos / (60 * NANOS_PER_SECOND) % 60) as u8
+    }
+
+    pub fn second(&self) -> u8 {
+        (self.nanos / NANOS_PER_SECOND % 60) as u8
+    }
+
+    pub fn nanosecond(&self) -> u32 {
+        (self.nanos % NANOS_PER_SECOND) as u32
+    }
+
+    /// Nanoseconds since midnight.
+    pub fn nanos_since_midnight(&self) -> u64 {
+        self.nanos
+    }
+
+    /// The time `nanos` after midnight, or `None` from 24:00 on.
+    pub fn from_nanos_since_midnight(nanos: u64) -> Option<Self> {
+        (nanos < NANOS_PER_DAY).then_some(Self { nanos })
+    }
+}
+
+impl fmt::Display for TimeOfDay {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "TOD#")?;
+        write_time(f, self)
+    }
+}
+
+/// A date and a time of day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DateAndTime {
+    pub date: Date,
+    pub time: TimeOfDay,
+}
+
+impl DateAndTime {
+    pub fn new(date: Date, time: TimeOfDay) -> Self {
+        Self { date, time }
+    }
+
+    /// Parse a date and time literal such as `DT#2024-01-15-12:30:00`.
+    pub fn parse(literal: &str) -> ParseResult<Self> {
+        let value = strip_prefix(literal, DT_PREFIXES)?;
+        parse_date_and_time(value).ok_or_else(|| invalid(literal))
+    }
+
+    /// Nanoseconds since 1970-01-01-00:00:00, negative for earlier values.
+    pub fn epoch_nanos(&self) -> i128 {
+        i128::from(self.date.days_since_epoch()) * i128::from(NANOS_PER_DAY)
+            + i128::from(self.time.nanos_since_midnight())
+    }
+
+    /// The value `nanos` after the epoch, or `None` outside years 0 to 9999.
+    pub fn from_epoch_nanos(nanos: i128) -> Option<Self> {
+        let per_day = i128::from(NANOS_PER_DAY);
+        let date = Date::from_days_since_epoch(i64::try_from(nanos.div_euclid(per_day)).ok()?)?;
+        let time = TimeOfDay::from_nanos_since_midnight(nanos.rem_euclid(per_day) as u64)?;
+        Some(Self { date, time })
+    }
+}
+
+impl fmt::Display for DateAndTime {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "DT#{:04}-{:02}-{:02}-", self.date.year, self.date.month, self.date.day)?;
+        write_time(f, &self.time)
+    }
+}
+
+/// Write `hh:mm:ss` with the fraction of the second, if any, without
+/// trailing zeros.
+fn write_time(f: &mut fmt::Formatter<'_>, time: &TimeOfDay) -> fmt::Result {
+    write!(f, "{:02}:{:02}:{:02}", time.hour(), time.minute(), time.second())?;
+    if time.nanosecond() > 0 {
+        let fraction = format!("{:09}", time.nanosecond());
+        write!(f, ".{}", fraction.trim_end_matches('0'))?;
+    }
+    Ok(())
+}
+
+fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The value after one of `prefixes`, matched case-insensitively.
+fn strip_prefix<'a>(literal: &'a str, prefixes: &[&str]) -> ParseResult<&'a str> {
+    prefixes
+        .iter()
+        .find(|prefix| literal.get(..prefix.len()).is_some_and(|head| head.eq_ignore_ascii_case(prefix)))
+        .map(|prefix| &literal[prefix.len()..])
+        .ok_or_else(|| invalid(literal))
+}
+
+fn invalid(literal: &str) -> ParseError {
+    ParseError::new(ParseErrorKind::InvalidDateLiteral, Span::new(0, literal.len()))
+}
+
+/// A number of one to `max_digits` ASCII digits.
+fn number(text: &str, max_digits: usize) -> Option<u16> {
+    let valid = !text.is_empty() && text.len() <= max_digits && text.bytes().all(|b| b.is_ascii_digit());
+    valid.then(|| text.parse().ok()).flatten()
+}
+
+/// `yyyy-mm-dd`
+fn parse_date(value: &str) -> Option<Date> {
+    let mut parts = value.splitn(3, '-');
+    let year = number(parts.next()?, 4)?;
+    let month = number(parts.next()?, 2)?;
+    let day = number(parts.next()?, 2)?;
+    Date::new(year, month as u8, day as u8)
+}
+
+/// `hh:mm[:ss[.fraction]]`
+fn parse_time_of_day(value: &str) -> Option<TimeOfDay> {
+    let mut parts = value.splitn(3, ':');
+    let hour = number(parts.next()?, 2)?;
+    let minute = number(parts.next()?, 2)?;
+    let (second, nanosecond) = match parts.next() {
+        Some(seconds) => {
+            let (whole, fraction) = seconds.split_once('.').unwrap_or((seconds, ""));
+            let nanosecond = match fraction {
+                "" if !seconds.contains('.') => 0,
+                _ if fraction.len() <= 9 && !fraction.is_empty() && fraction.bytes().all(|b| b.is_ascii_digit()) => {
+                    format!("{:0<9}", fraction).parse().ok()?
+                }
+                _ => return None,
+            };
+            (number(whole, 2)?, nanosecond)
+        }
+        None => (0, 0),
+    };
+    TimeOfDay::new(u8::try_from(hour).ok()?, u8::try_from(minute).ok()?, u8::try_from(second).ok()?, nanosecond)
+}
+
+/// `yyyy-mm-dd-hh:mm[:ss[.fraction]]`
+fn parse_date_and_time(value: &str) -> Option<DateAndTime> {
+    let split = value.match_indices('-').nth(2)?.0;
+    let date = parse_date(&value[..split])?;
+    let time = parse_time_of_day(&value[split + 1..])?;
+    Some(DateAndTime { date, time })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_prefixes() {
+        let date = Date::parse("DATE#2024-01-15").unwrap();
+        assert_eq!((date.year(), date.month(), date.day()), (2024, 1, 15));
+        assert_eq!(Date::parse("d#2024-01-15").unwrap(), date);
+        assert_eq!(Date::parse("LDATE#2024-01-15").unwrap(), date);
+
+        let time = TimeOfDay::parse("TOD#12:30:00.5").unwrap();
+        assert_eq!((time.hour(), time.minute(), time.second(), time.nanosecond()), (12, 30, 0, 500_000_000));
+        assert_eq!(TimeOfDay::parse("TIME_OF_DAY#12:30").unwrap(), TimeOfDay::new(12, 30, 0, 0).unwrap());
+
+        let dt = DateAndTime::parse("DT#2024-01-15-12:30:00").unwrap();
+        assert_eq!(dt.date, date);
+        assert_eq!(DateAndTime::parse("date_and_time#2024-01-15-12:30:00").unwrap(), dt);
+        assert_eq!(dt.to_string(), "DT#2024-01-15-12:30:00");
+        assert_eq!(time.to_string(), "TOD#12:30:00.5");
+        assert_eq!(date.to_string(), "D#2024-01-15");
+    }
+
+    #[test]
+    fn test_impossible_values_rejected() {
+        for literal in ["D#2024-13-01", "D#2023-02-29", "D#2024-04-31", "D#2024-00-10", "D#24-1", "T#2024-01-01"] {
+            let error = Date::parse(literal).unwrap_err();
+            assert_eq!(error.kind, ParseErrorKind::InvalidDateLiteral, "{}", literal);
+        }
+        for literal in ["TOD#25:00:00", "TOD#24:00:00", "TOD#12:60:00", "TOD#12:30:60", "TOD#12:30:00.", "TOD#1:2:3.1234567890"] {
+            assert!(TimeOfDay::parse(literal).is_err(), "{}", literal);
+        }
+        assert!(DateAndTime::parse("DT#2024-01-15-25:00:00").is_err());
+        assert!(DateAndTime::parse("DT#2024-01-15").is_err());
+        assert!(Date::parse("D#2024-02-29").is_ok());
+    }
+
+    #[test]
+    fn test_epoch_conversion_and_order() {
+        assert_eq!(Date::new(1970, 1, 1).unwrap().days_since_epoch(), 0);
+        assert_eq!(Date::new(2000, 3, 1).unwrap().days_since_epoch(), 11_017);
+        assert_eq!(Date::new(1969, 12, 31).unwrap().days_since_epoch(), -1);
+        for days in [-719_528, -1, 0, 59, 11_016, 19_737, 2_932_896] {
+            assert_eq!(Date::from_days_since_epoch(days).unwrap().days_since_epoch(), days);
+        }
+        assert_eq!(Date::from_days_since_epoch(2_932_897), None);
+
+        let dt = DateAndTime::parse("DT#2024-01-15-12:30:00.25").unwrap();
+        assert_eq!(DateAndTime::from_epoch_nanos(dt.epoch_nanos()), Some(dt));
+        assert!(DateAndTime::parse("DT#2024-01-15-23:59:59").unwrap() < DateAndTime::parse("DT#2024-01-16-00:00:00").unwrap());
+        assert!(TimeOfDay::parse("TOD#08:00:00").unwrap() < TimeOfDay::parse("TOD#08:00:00.001").unwrap());
+    }
+}
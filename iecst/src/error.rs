@@ -16,6 +16,8 @@ pub enum ParseErrorKind {
     InvalidString,
     /// Invalid time literal
     InvalidTimeLiteral,
+    /// Invalid or impossible date, time of day or date and time literal
+    InvalidDateLiteral,
     /// Unknown keyword or identifier
     UnknownKeyword,
     /// Missing semicolon
@@ -32,6 +34,9 @@ pub enum ParseErrorKind {
     InvalidExpression,
     /// Invalid statement
     InvalidStatement,
+    /// Comparison used as a statement (`x = y;`), which has no effect;
+    /// `suggestion` is the assignment that was probably meant
+    ComparisonStatement { expr: String, suggestion: Option<String> },
     /// Invalid declaration
     InvalidDeclaration,
     /// Invalid type specification
@@ -54,6 +59,7 @@ impl fmt::Display for ParseErrorKind {
             ParseErrorKind::InvalidNumber => write!(f, "invalid number literal"),
             ParseErrorKind::InvalidString => write!(f, "invalid string literal"),
             ParseErrorKind::InvalidTimeLiteral => write!(f, "invalid time literal"),
+            ParseErrorKind::InvalidDateLiteral => write!(f, "invalid date or time of day literal"),
             ParseErrorKind::UnknownKeyword => write!(f, "unknown keyword"),
             ParseErrorKind::MissingSemicolon => write!(f, "missing semicolon"),
             ParseErrorKind::MissingEndKeyword { keyword } => {
@@ -64,6 +70,13 @@ impl fmt::Display for ParseErrorKind {
             ParseErrorKind::InvalidOperator => write!(f, "invalid operator"),
             ParseErrorKind::InvalidExpression => write!(f, "invalid expression"),
             ParseErrorKind::InvalidStatement => write!(f, "invalid statement"),
+            ParseErrorKind::ComparisonStatement { expr, suggestion } => {
+                write!(f, "comparison '{}' used as a statement has no effect", expr)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, "; did you mean '{}'?", suggestion),
+                    None => Ok(()),
+                }
+            }
             ParseErrorKind::InvalidDeclaration => write!(f, "invalid declaration"),
             ParseErrorKind::InvalidType => write!(f, "invalid type specification"),
             ParseErrorKind::DuplicateIdentifier => write!(f, "duplicate identifier"),
@@ -1,10 +1,18 @@
 //! Lexer for IEC 61131-3 Structured Text.
 //!
-//! Tokenizes ST source code into a stream of tokens.
+//! Tokenizes ST source code into a stream of tokens. [`tokenize`] exposes
+//! the token stream, optionally with the comments, pragmas and whitespace
+//! the parser skips, for editor features such as syntax highlighting.
 
 use crate::Span;
 
 /// Token types for Structured Text.
+///
+/// Keywords are matched case-insensitively and carry no text; literals
+/// carry their value (time and date literals their source text), and
+/// identifiers their name as written. The trivia tokens `Comment`,
+/// `Pragma` and `Whitespace` only appear in the output of [`tokenize`]
+/// with `trivia` set; their text is the source within the token's span.
 #[derive(Debug, Clone, PartialEq)]
 pub enum Token {
     // Literals
@@ -131,6 +139,14 @@ pub enum Token {
     /// Direct address: %IX0.0, %MW100, etc.
     DirectAddress(String),
 
+    // Trivia
+    /// Comment: `// ...`, `(* ... *)` or `/* ... */`, delimiters included
+    Comment,
+    /// Pragma: `{attribute 'hide'}`, braces included
+    Pragma,
+    /// Run of whitespace between tokens
+    Whitespace,
+
     // Special
     /// End of file
     Eof,
@@ -212,15 +228,25 @@ impl SpannedToken {
 }
 
 /// Lexer for Structured Text.
+#[derive(Clone)]
 pub struct Lexer<'a> {
     input: &'a str,
     pos: usize,
+    /// Spans of the comments skipped so far
+    comments: Vec<Span>,
+    /// Spans of the pragmas skipped so far
+    pragmas: Vec<Span>,
 }
 
 impl<'a> Lexer<'a> {
     /// Create a new lexer for the given input.
     pub fn new(input: &'a str) -> Self {
-        Self { input, pos: 0 }
+        Self { input, pos: 0, comments: Vec::new(), pragmas: Vec::new() }
+    }
+
+    /// Get the whole input.
+    pub(crate) fn source(&self) -> &'a str {
+        self.input
     }
 
     /// Get remaining input.
@@ -245,7 +271,7 @@ impl<'a> Lexer<'a> {
         Some(c)
     }
 
-    /// Skip whitespace and comments.
+    /// Skip whitespace, comments and pragmas.
     fn skip_whitespace_and_comments(&mut self) {
         loop {
             // Skip whitespace
@@ -255,11 +281,13 @@ impl<'a> Lexer<'a> {
 
             // Check for comments
             let remaining = self.remaining();
+            let start = self.pos;
             if remaining.starts_with("//") {
                 // Line comment
                 while self.peek().map(|c| c != '\n').unwrap_or(false) {
                     self.advance();
                 }
+                self.comments.push(Span::new(start, self.pos));
             } else if remaining.starts_with("(*") {
                 // Block comment (* ... *)
                 self.advance(); // (
@@ -278,6 +306,7 @@ impl<'a> Lexer<'a> {
                         self.advance();
                     }
                 }
+                self.comments.push(Span::new(start, self.pos));
             } else if remaining.starts_with("/*") {
                 // C-style block comment /* ... */
                 self.advance(); // /
@@ -289,6 +318,20 @@ impl<'a> Lexer<'a> {
                     self.advance();
                     self.advance();
                 }
+                self.comments.push(Span::new(start, self.pos));
+            } else if remaining.starts_with('{') {
+                // Pragma { ... }; braces inside quoted text don't end it
+                self.advance();
+                let mut quote = None;
+                while let Some(c) = self.advance() {
+                    match (quote, c) {
+                        (None, '}') => break,
+                        (None, '\'' | '"') => quote = Some(c),
+                        (Some(q), c) if c == q => quote = None,
+                        _ => {}
+                    }
+                }
+                self.pragmas.push(Span::new(start, self.pos));
             } else {
                 break;
             }
@@ -569,17 +612,11 @@ impl<'a> Lexer<'a> {
         let span = Span::new(start, self.pos);
 
         // Determine the type based on prefix
-        let upper = text.to_uppercase();
-        let token = if upper.starts_with("TIME#") || upper.starts_with("T#") {
-            Token::TimeLiteral(text.to_string())
-        } else if upper.starts_with("DATE#") || upper.starts_with("D#") {
-            Token::DateLiteral(text.to_string())
-        } else if upper.starts_with("TIME_OF_DAY#") || upper.starts_with("TOD#") {
-            Token::TodLiteral(text.to_string())
-        } else if upper.starts_with("DATE_AND_TIME#") || upper.starts_with("DT#") {
-            Token::DateTimeLiteral(text.to_string())
-        } else {
-            Token::TimeLiteral(text.to_string())
+        let token = match time_literal_kind(text) {
+            Some(TimeLiteralKind::Date) => Token::DateLiteral(text.to_string()),
+            Some(TimeLiteralKind::TimeOfDay) => Token::TodLiteral(text.to_string()),
+            Some(TimeLiteralKind::DateAndTime) => Token::DateTimeLiteral(text.to_string()),
+            Some(TimeLiteralKind::Time) | None => Token::TimeLiteral(text.to_string()),
         };
 
         SpannedToken::new(token, span)
@@ -597,16 +634,7 @@ impl<'a> Lexer<'a> {
 
         // Check for time/date literals first (before identifiers)
         if c.is_alphabetic() || c == '_' {
-            let remaining = self.remaining().to_uppercase();
-            if c.is_alphabetic() && (remaining.starts_with("TIME#")
-                || remaining.starts_with("T#")
-                || remaining.starts_with("DATE#")
-                || remaining.starts_with("D#")
-                || remaining.starts_with("TIME_OF_DAY#")
-                || remaining.starts_with("TOD#")
-                || remaining.starts_with("DATE_AND_TIME#")
-                || remaining.starts_with("DT#"))
-            {
+            if c.is_alphabetic() && time_literal_kind(self.remaining()).is_some() {
                 return self.lex_time_literal();
             }
             return self.lex_ident();
@@ -706,7 +734,6 @@ impl<'a> Lexer<'a> {
     }
 
     /// Tokenize the entire input.
-    #[allow(dead_code)]
     pub fn tokenize(&mut self) -> Vec<SpannedToken> {
         let mut tokens = Vec::new();
         loop {
@@ -721,6 +748,125 @@ impl<'a> Lexer<'a> {
     }
 }
 
+/// Split ST source into tokens with their spans.
+///
+/// The last token is always [`Token::Eof`]. Invalid characters and
+/// malformed numbers become [`Token::Unknown`] rather than failing, so any
+/// input can be tokenized. With `trivia`, the comments, pragmas and runs of
+/// whitespace between tokens are included as [`Token::Comment`],
+/// [`Token::Pragma`] and [`Token::Whitespace`], and the spans of all tokens
+/// cover the source without gaps.
+///
+/// ```
+/// use iecst::{tokenize, Token};
+///
+/// let tokens = tokenize("x := 1; // done", true);
+/// let kinds: Vec<&Token> = tokens.iter().map(|t| &t.token).collect();
+/// assert_eq!(kinds[..4], [&Token::Ident("x".into()), &Token::Whitespace, &Token::Assign, &Token::Whitespace]);
+/// assert_eq!(tokens[tokens.len() - 2].token, Token::Comment);
+/// ```
+pub fn tokenize(source: &str, trivia: bool) -> Vec<SpannedToken> {
+    let mut lexer = Lexer::new(source);
+    let mut tokens = lexer.tokenize();
+    if !trivia {
+        return tokens;
+    }
+
+    tokens.extend(lexer.comments.iter().map(|&span| SpannedToken::new(Token::Comment, span)));
+    tokens.extend(lexer.pragmas.iter().map(|&span| SpannedToken::new(Token::Pragma, span)));
+    // Eof sorts last: it is the only empty token at the end of the source
+    tokens.sort_by_key(|t| (t.span.start, t.token == Token::Eof));
+
+    // Whatever the lexer skipped between tokens is whitespace
+    let mut result = Vec::with_capacity(tokens.len() * 2);
+    let mut end = 0;
+    for token in tokens {
+        if token.span.start > end {
+            result.push(SpannedToken::new(Token::Whitespace, Span::new(end, token.span.start)));
+        }
+        end = end.max(token.span.end);
+        result.push(token);
+    }
+    result
+}
+
+/// Find the comments in ST source.
+///
+/// Returns the spans of `//`, `(* ... *)` (nested) and `/* ... */`
+/// comments, including their delimiters. Comment markers inside string
+/// literals are not comments.
+pub fn comment_spans(source: &str) -> Vec<Span> {
+    let mut lexer = Lexer::new(source);
+    while lexer.next_token().token != Token::Eof {}
+    lexer.comments
+}
+
+/// A pragma such as `{attribute 'hide'}` or `{warning disable C0195}`.
+///
+/// Pragmas are compiler directives of CODESYS and TIA Portal; the parser
+/// skips them like comments.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pragma {
+    /// Text between the braces, trimmed
+    pub text: String,
+    /// Span including the braces
+    pub span: Span,
+}
+
+impl Pragma {
+    /// Name of an attribute pragma: `hide` for `{attribute 'hide'}`.
+    pub fn attribute(&self) -> Option<&str> {
+        let rest = self.text.strip_prefix("attribute")?.trim_start();
+        let rest = rest.strip_prefix('\'')?;
+        rest.split('\'').next()
+    }
+}
+
+/// Find the pragmas in ST source.
+///
+/// Returns the `{ ... }` blocks outside comments and string literals, in
+/// source order.
+pub fn pragmas(source: &str) -> Vec<Pragma> {
+    let mut lexer = Lexer::new(source);
+    while lexer.next_token().token != Token::Eof {}
+    lexer
+        .pragmas
+        .into_iter()
+        .map(|span| {
+            let inner = &source[span.start + 1..span.end];
+            let inner = inner.strip_suffix('}').unwrap_or(inner);
+            Pragma { text: inner.trim().to_string(), span }
+        })
+        .collect()
+}
+
+/// Kind of a time or date literal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TimeLiteralKind {
+    Time,
+    Date,
+    TimeOfDay,
+    DateAndTime,
+}
+
+/// Kind of the time or date literal `text` starts with, by its prefix.
+/// An `L` before the prefix marks the 64-bit variant (`LTIME#`, `LDT#`).
+fn time_literal_kind(text: &str) -> Option<TimeLiteralKind> {
+    let end = text.find(|c: char| !(c.is_alphabetic() || c == '_'))?;
+    if !text[end..].starts_with('#') {
+        return None;
+    }
+    let prefix = text[..end].to_uppercase();
+    let kind = |name: &str| match name {
+        "TIME" | "T" => Some(TimeLiteralKind::Time),
+        "DATE" | "D" => Some(TimeLiteralKind::Date),
+        "TIME_OF_DAY" | "TOD" => Some(TimeLiteralKind::TimeOfDay),
+        "DATE_AND_TIME" | "DT" => Some(TimeLiteralKind::DateAndTime),
+        _ => None,
+    };
+    kind(&prefix).or_else(|| kind(prefix.strip_prefix('L')?))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -790,6 +936,12 @@ mod tests {
 
         let tokens = tokenize("D#2024-01-15");
         assert!(matches!(tokens[0], Token::DateLiteral(_)));
+
+        let tokens = tokenize("LTIME#5s LDT#2024-01-15-12:00:00 LTOD#12:00:00 LD#2024-01-15");
+        assert!(matches!(tokens[0], Token::TimeLiteral(_)));
+        assert!(matches!(tokens[1], Token::DateTimeLiteral(_)));
+        assert!(matches!(tokens[2], Token::TodLiteral(_)));
+        assert!(matches!(tokens[3], Token::DateLiteral(_)));
     }
 
     #[test]
@@ -814,6 +966,30 @@ mod tests {
         assert_eq!(tokens[1], Token::Ident("y".to_string()));
     }
 
+    #[test]
+    fn test_comment_spans() {
+        let source = "x := 1; // set x\n(* outer (* inner *) still *) s := '(* no *)';";
+        let spans = comment_spans(source);
+        assert_eq!(spans.len(), 2);
+        assert_eq!(&source[spans[0].start..spans[0].end], "// set x");
+        assert_eq!(&source[spans[1].start..spans[1].end], "(* outer (* inner *) still *)");
+    }
+
+    #[test]
+    fn test_pragmas() {
+        let source = "{attribute 'hide'} x := 1; { warning disable C0195 } (* {not} *) s := '{no}';\n{attribute 'plceye:disable' := '}'}";
+        let tokens = tokenize(source);
+        assert_eq!(tokens[0], Token::Ident("x".to_string()));
+        assert_eq!(tokens.len(), 9);
+
+        let found = pragmas(source);
+        let texts: Vec<_> = found.iter().map(|p| p.text.as_str()).collect();
+        assert_eq!(texts, ["attribute 'hide'", "warning disable C0195", "attribute 'plceye:disable' := '}'"]);
+        assert_eq!(&source[found[1].span.start..found[1].span.end], "{ warning disable C0195 }");
+        let attributes: Vec<_> = found.iter().map(Pragma::attribute).collect();
+        assert_eq!(attributes, [Some("hide"), None, Some("plceye:disable")]);
+    }
+
     #[test]
     fn test_case_insensitive_keywords() {
         let tokens = tokenize("if IF If");
@@ -821,4 +997,25 @@ mod tests {
         assert_eq!(tokens[1], Token::If);
         assert_eq!(tokens[2], Token::If);
     }
+
+    #[test]
+    fn test_tokenize_with_trivia() {
+        let source = "{attribute 'hide'}\nIF a (* check *) THEN\n  b := 1;\nEND_IF; // done";
+        let tokens = super::tokenize(source, true);
+        let rebuilt: String = tokens.iter().map(|t| &source[t.span.start..t.span.end]).collect();
+        assert_eq!(rebuilt, source);
+        let trivia: Vec<&str> = tokens
+            .iter()
+            .filter(|t| matches!(t.token, Token::Comment | Token::Pragma))
+            .map(|t| &source[t.span.start..t.span.end])
+            .collect();
+        assert_eq!(trivia, ["{attribute 'hide'}", "(* check *)", "// done"]);
+        assert_eq!(tokens.last().map(|t| &t.token), Some(&Token::Eof));
+
+        let is_trivia = |t: &&SpannedToken| matches!(t.token, Token::Comment | Token::Pragma | Token::Whitespace);
+        let plain = super::tokenize(source, false);
+        assert!(!plain.iter().any(|t| is_trivia(&t)));
+        assert_eq!(plain.len(), tokens.iter().filter(|t| !is_trivia(t)).count());
+        assert_eq!(super::tokenize("", true), [SpannedToken::new(Token::Eof, Span::new(0, 0))]);
+    }
 }
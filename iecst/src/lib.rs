@@ -16,7 +16,8 @@
 //! # Supported Constructs
 //!
 //! ## Expressions
-//! - Literals: integers, reals, strings, booleans, time literals
+//! - Literals: integers, reals, strings, booleans, time and date literals
+//!   (date, time of day and date and time values are validated, see [`Date`])
 //! - Identifiers and qualified names
 //! - Binary operators: +, -, *, /, MOD, **, AND, OR, XOR, NOT, comparisons
 //! - Unary operators: -, NOT
@@ -42,22 +43,43 @@
 //! ## Declarations
 //! - VAR/VAR_INPUT/VAR_OUTPUT/VAR_IN_OUT/VAR_TEMP/VAR_GLOBAL
 //! - TYPE/END_TYPE (structures, enums, arrays, subranges)
+//!
+//! # Traversal
+//!
+//! Custom analyses implement [`Visitor`], overriding only the nodes they
+//! inspect, and start from [`walk_pou`]; see the [`visitor`] module.
 
 pub mod analysis;
 mod ast;
+mod datetime;
 mod error;
 mod lexer;
 mod parser;
 pub mod security;
 mod span;
+pub mod visitor;
 
 pub use ast::*;
+pub use datetime::{Date, DateAndTime, TimeOfDay};
 pub use error::{ParseError, ParseErrorKind, ParseResult};
-pub use parser::{parse_expression, parse_statement, parse_statements, parse_pou, parse_type_block};
+pub use lexer::{comment_spans, pragmas, tokenize, Pragma, SpannedToken, Token};
+pub use parser::{
+    parse_expression, parse_statement, parse_statements, parse_statements_recovering, parse_pou, parse_type_block,
+};
 pub use security::{ParserLimits, ParserState, SecurityError};
 pub use span::Span;
+pub use visitor::{walk_pou, Visitor};
 
 // Re-export key analysis types
-pub use analysis::{analyze_pou, Diagnostic, DiagnosticKind, Severity, Type};
+pub use analysis::{analyze_pou, check_types, Diagnostic, DiagnosticKind, ExprTypes, Severity, Type};
+pub use analysis::{references_of, symbol_at, SymbolIndex, SymbolInfo, SymbolKind};
 pub use analysis::{Cfg, CfgBuilder, CfgNode, NodeId, NodeKind};
-pub use analysis::max_nesting_depth;
+pub use analysis::{influence_path, influences, DataFlow, FlowEdge, FlowKind};
+pub use analysis::{
+    check_array_bounds, check_array_ranges, check_bit_operations, check_case_coverage, check_case_labels, check_bool_toggles, check_dead_stores, check_direct_addresses, check_redundant_booleans, check_division_by_zero, check_empty_bodies, check_enum_values,
+    check_float_equality, check_function_purity, check_if_chains, check_loop_variables, check_member_access_depth, check_self_assignments,
+    check_string_concat_in_loops, check_string_truncation, check_struct_types, check_type_ranges,
+    check_uninitialized_reads, check_unused_variables,
+    enum_member_values, eval_const_int,
+    max_nesting_depth, member_access_depth, pou_constants,
+};
@@ -3,9 +3,11 @@
 //! Uses a Pratt parser for expressions and recursive descent for statements.
 
 use crate::ast::*;
+use crate::datetime::{Date, DateAndTime, TimeOfDay};
 use crate::error::{ParseError, ParseErrorKind, ParseResult};
 use crate::lexer::{Lexer, SpannedToken, Token};
 use crate::security::{ParserLimits, ParserState};
+use crate::Span;
 
 /// Parser state.
 pub struct Parser<'a> {
@@ -14,6 +16,10 @@ pub struct Parser<'a> {
     previous: SpannedToken,
     security: ParserState,
     depth: usize,
+    /// Skip broken statements instead of failing (see `parse_statements_recovering`)
+    recover: bool,
+    /// Errors of the statements skipped in recovery mode
+    recovered: Vec<ParseError>,
 }
 
 impl<'a> Parser<'a> {
@@ -29,7 +35,6 @@ impl<'a> Parser<'a> {
         // Check input size
         if input.len() > limits.max_input_size {
             use crate::security::SecurityError;
-            use crate::Span;
             return Err(ParseError::new(
                 ParseErrorKind::Security(SecurityError::InputTooLarge {
                     size: input.len(),
@@ -47,6 +52,8 @@ impl<'a> Parser<'a> {
             previous: current,
             security: ParserState::new(limits),
             depth: 0,
+            recover: false,
+            recovered: Vec::new(),
         })
     }
 
@@ -56,6 +63,11 @@ impl<'a> Parser<'a> {
         self.current = self.lexer.next_token();
     }
 
+    /// Look at the token after the current one without consuming anything.
+    fn peek_token(&self) -> Token {
+        self.lexer.clone().next_token().token
+    }
+
     /// Check if current token matches the expected type.
     fn check(&self, token: &Token) -> bool {
         std::mem::discriminant(&self.current.token) == std::mem::discriminant(token)
@@ -297,16 +309,19 @@ impl<'a> Parser<'a> {
             }
             Token::DateLiteral(s) => {
                 let s = s.clone();
+                Date::parse(&s).map_err(|e| ParseError::new(e.kind, tok.span))?;
                 self.advance();
                 Ok(Expr::new(ExprKind::DateLiteral(s), tok.span))
             }
             Token::TodLiteral(s) => {
                 let s = s.clone();
+                TimeOfDay::parse(&s).map_err(|e| ParseError::new(e.kind, tok.span))?;
                 self.advance();
                 Ok(Expr::new(ExprKind::TodLiteral(s), tok.span))
             }
             Token::DateTimeLiteral(s) => {
                 let s = s.clone();
+                DateAndTime::parse(&s).map_err(|e| ParseError::new(e.kind, tok.span))?;
                 self.advance();
                 Ok(Expr::new(ExprKind::DateTimeLiteral(s), tok.span))
             }
@@ -394,6 +409,7 @@ impl<'a> Parser<'a> {
                     args.push(FunctionArg {
                         name: None,
                         value: None,
+                        output: false,
                         span,
                     });
                 } else {
@@ -416,13 +432,15 @@ impl<'a> Parser<'a> {
             
             // Peek ahead to see if this is a named argument
             self.advance();
-            if self.eat(&Token::Assign) {
-                // Named argument
+            let output = self.check(&Token::OutputAssign);
+            if self.eat(&Token::Assign) || self.eat(&Token::OutputAssign) {
+                // Named argument or output binding
                 let value = self.parse_expression()?;
                 let span = start.merge(&value.span);
                 return Ok(FunctionArg {
                     name: Some(name),
                     value: Some(value),
+                    output,
                     span,
                 });
             } else {
@@ -436,6 +454,7 @@ impl<'a> Parser<'a> {
                 return Ok(FunctionArg {
                     name: None,
                     value: Some(value),
+                    output: false,
                     span,
                 });
             }
@@ -447,6 +466,7 @@ impl<'a> Parser<'a> {
         Ok(FunctionArg {
             name: None,
             value: Some(value),
+            output: false,
             span,
         })
     }
@@ -613,6 +633,22 @@ impl<'a> Parser<'a> {
         // Assignment or function call
         let target = self.parse_expression()?;
 
+        // A comparison on its own (`x = y;`) is usually a mistyped assignment
+        if let ExprKind::BinaryOp { left, op, right } = &target.kind {
+            if op.is_comparison() && self.check(&Token::Semicolon) {
+                let source = self.lexer.source();
+                let suggestion = (*op == BinaryOp::Eq)
+                    .then(|| format!("{} := {}", left.span.text(source), right.span.text(source)));
+                return Err(ParseError::new(
+                    ParseErrorKind::ComparisonStatement {
+                        expr: target.span.text(source).to_string(),
+                        suggestion,
+                    },
+                    target.span,
+                ));
+            }
+        }
+
         if self.eat(&Token::Assign) {
             let value = self.parse_expression()?;
             self.expect(&Token::Semicolon, ";")?;
@@ -630,6 +666,7 @@ impl<'a> Parser<'a> {
                     name: arg.name,
                     span: arg.span,
                     value: arg.value,
+                    output: arg.output,
                 })
                 .collect();
             return Ok(Stmt::new(StmtKind::Call { name, args: call_args }, span));
@@ -645,20 +682,97 @@ impl<'a> Parser<'a> {
     pub fn parse_statements(&mut self) -> ParseResult<Vec<Stmt>> {
         let mut stmts = Vec::new();
         while !self.at_end() {
-            stmts.push(self.parse_statement()?);
+            stmts.extend(self.parse_list_statement()?);
         }
         Ok(stmts)
     }
 
+    /// Parse statements, skipping the ones with syntax errors.
+    ///
+    /// On a statement error the parser records the error, skips to the
+    /// next `;` or block keyword (IF, CASE, END_IF, ELSE, ...) and
+    /// continues, also inside nested bodies. The span of each returned
+    /// error covers the skipped source. Security limit errors still abort.
+    pub fn parse_statements_recovering(&mut self) -> ParseResult<(Vec<Stmt>, Vec<ParseError>)> {
+        self.recover = true;
+        let stmts = self.parse_statements();
+        self.recover = false;
+        Ok((stmts?, std::mem::take(&mut self.recovered)))
+    }
+
     /// Parse statements until one of the given end tokens.
     fn parse_statements_until(&mut self, end_tokens: &[Token]) -> ParseResult<Vec<Stmt>> {
         let mut stmts = Vec::new();
         while !self.at_end() && !end_tokens.iter().any(|t| self.check(t)) {
-            stmts.push(self.parse_statement()?);
+            stmts.extend(self.parse_list_statement()?);
         }
         Ok(stmts)
     }
 
+    /// Parse one statement of a statement list.
+    ///
+    /// In recovery mode a syntax error is recorded and the broken
+    /// statement skipped, returning `None`.
+    fn parse_list_statement(&mut self) -> ParseResult<Option<Stmt>> {
+        let start = self.current.span;
+        let depth = self.depth;
+        match self.parse_statement() {
+            Ok(stmt) => Ok(Some(stmt)),
+            Err(e) if !self.recover || matches!(e.kind, ParseErrorKind::Security(_)) => Err(e),
+            Err(e) => {
+                // Leave the blocks the broken statement opened
+                while self.depth > depth {
+                    self.security.exit_depth();
+                    self.depth -= 1;
+                }
+                self.synchronize(start);
+                let end = self.previous.span.end.max(start.start);
+                self.recovered.push(ParseError::new(e.kind, Span::new(start.start, end)));
+                Ok(None)
+            }
+        }
+    }
+
+    /// Skip past the next `;`, or up to the next block keyword.
+    ///
+    /// Always consumes at least one token when the broken statement
+    /// consumed none, so recovery makes progress.
+    fn synchronize(&mut self, start: Span) {
+        if self.current.span == start && !self.at_end() {
+            self.advance();
+            if self.previous.token == Token::Semicolon {
+                return;
+            }
+        }
+        while !self.at_end() {
+            if self.eat(&Token::Semicolon) {
+                return;
+            }
+            if matches!(
+                self.current.token,
+                Token::If
+                    | Token::Case
+                    | Token::For
+                    | Token::While
+                    | Token::Repeat
+                    | Token::Elsif
+                    | Token::Else
+                    | Token::Until
+                    | Token::EndIf
+                    | Token::EndCase
+                    | Token::EndFor
+                    | Token::EndWhile
+                    | Token::EndRepeat
+                    | Token::EndProgram
+                    | Token::EndFunction
+                    | Token::EndFunctionBlock
+            ) {
+                return;
+            }
+            self.advance();
+        }
+    }
+
     /// Parse IF statement.
     fn parse_if(&mut self) -> ParseResult<Stmt> {
         let start = self.current.span;
@@ -804,7 +918,7 @@ impl<'a> Parser<'a> {
             if self.looks_like_case_label() {
                 break;
             }
-            stmts.push(self.parse_statement()?);
+            stmts.extend(self.parse_list_statement()?);
         }
         Ok(stmts)
     }
@@ -814,15 +928,13 @@ impl<'a> Parser<'a> {
         // A case label starts with an expression that's followed by ':', ',', or '..'
         // We look for patterns like:
         // - IntLiteral followed by : or , or ..
-        // - Ident that is NOT followed by := (which would be assignment)
+        // - Ident (enum value) followed by : or , or .. (not := which would be assignment)
         match &self.current.token {
             Token::IntLiteral(_) | Token::RealLiteral(_) | Token::StringLiteral(_) => true,
-            Token::Ident(_) => {
-                // Need to peek at next token - if it's := it's assignment, else maybe case label
-                // For now, simple heuristic: identifiers alone don't start case labels
-                // This handles common case where assignments start case bodies
-                false
-            }
+            Token::Ident(_) => matches!(
+                self.peek_token(),
+                Token::Colon | Token::Comma | Token::DotDot
+            ),
             _ => false,
         }
     }
@@ -1322,6 +1434,17 @@ pub fn parse_statements(source: &str) -> ParseResult<Vec<Stmt>> {
     Parser::new(source)?.parse_statements()
 }
 
+/// Parse multiple statements from source, skipping the ones with syntax errors.
+///
+/// Returns the statements that parsed and one error per skipped region.
+/// See [`Parser::parse_statements_recovering`].
+pub fn parse_statements_recovering(source: &str) -> (Vec<Stmt>, Vec<ParseError>) {
+    match Parser::new(source).and_then(|mut parser| parser.parse_statements_recovering()) {
+        Ok(result) => result,
+        Err(e) => (Vec::new(), vec![e]),
+    }
+}
+
 /// Parse a POU from source.
 pub fn parse_pou(source: &str) -> ParseResult<Pou> {
     Parser::new(source)?.parse_pou()
@@ -1365,12 +1488,52 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_parse_date_literals() {
+        let expr = parse_expression("stamp < DT#2024-01-15-12:30:00").unwrap();
+        assert!(matches!(expr.kind, ExprKind::BinaryOp { op: BinaryOp::Lt, .. }));
+        assert!(parse_expression("TOD#12:30:00.5").is_ok());
+
+        let error = parse_statement("d := D#2024-13-01;").unwrap_err();
+        assert_eq!(error.kind, ParseErrorKind::InvalidDateLiteral);
+        assert_eq!(error.span, Span::new(5, 17));
+        assert!(parse_expression("TOD#25:00:00").is_err());
+    }
+
     #[test]
     fn test_parse_assignment() {
         let stmt = parse_statement("x := 1 + 2;").unwrap();
         assert!(matches!(stmt.kind, StmtKind::Assignment { .. }));
     }
 
+    #[test]
+    fn test_parse_case_enum_labels() {
+        let stmt = parse_statement(
+            "CASE state OF Idle: x := 0; Running, Stopping: x := 1; Faulted..Halted: x := 2; END_CASE;",
+        )
+        .unwrap();
+        if let StmtKind::Case { cases, .. } = &stmt.kind {
+            assert_eq!(cases.len(), 3);
+            assert_eq!(cases[1].values.len(), 2);
+            assert!(matches!(cases[2].values[0], CaseValue::Range { .. }));
+        } else {
+            panic!("Expected Case");
+        }
+    }
+
+    #[test]
+    fn test_parse_output_binding() {
+        let stmt = parse_statement("Timer(IN := start, PT := T#1s, Q => done);").unwrap();
+        if let StmtKind::Call { args, .. } = &stmt.kind {
+            assert_eq!(args.len(), 3);
+            assert!(!args[0].output);
+            assert!(args[2].output);
+            assert_eq!(args[2].name.as_deref(), Some("Q"));
+        } else {
+            panic!("Expected Call");
+        }
+    }
+
     #[test]
     fn test_parse_if_statement() {
         let stmt = parse_statement("IF x > 0 THEN y := 1; END_IF;").unwrap();
@@ -1449,4 +1612,67 @@ mod tests {
         assert_eq!(pou.var_blocks.len(), 2);
         assert_eq!(pou.body.len(), 1);
     }
+
+    #[test]
+    fn test_parse_statements_recovering() {
+        let source = "a := 1;\nb := ;\nIF a > 0 THEN\n    c := (2;\n    d := 3;\nEND_IF;\ne := 4;";
+        let (stmts, errors) = parse_statements_recovering(source);
+        assert_eq!(stmts.len(), 3);
+        assert!(matches!(&stmts[1].kind, StmtKind::If { then_body, .. } if then_body.len() == 1));
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].span.text(source), "b := ;");
+        assert_eq!(errors[1].span.text(source), "c := (2;");
+
+        // A broken block header is skipped up to the next block keyword
+        let (stmts, errors) = parse_statements_recovering("IF THEN x := 1; END_IF; y := 2;");
+        assert_eq!(errors.len(), 2);
+        assert!(matches!(&stmts.last().unwrap().kind, StmtKind::Assignment { .. }));
+
+        assert!(parse_statements("a := 1; b := ;").is_err());
+    }
+
+    #[test]
+    fn test_comparison_statement() {
+        let err = parse_statements("count = count + 1;").unwrap_err();
+        assert_eq!(
+            err.kind,
+            ParseErrorKind::ComparisonStatement {
+                expr: "count = count + 1".to_string(),
+                suggestion: Some("count := count + 1".to_string()),
+            }
+        );
+        assert_eq!(
+            err.kind.to_string(),
+            "comparison 'count = count + 1' used as a statement has no effect; did you mean 'count := count + 1'?"
+        );
+
+        let (stmts, errors) = parse_statements_recovering("IF run THEN a.b < 10; END_IF; x := a.b = 1;");
+        assert_eq!(stmts.len(), 2);
+        assert_eq!(errors[0].kind.to_string(), "comparison 'a.b < 10' used as a statement has no effect");
+        assert_eq!(errors[0].span.text("IF run THEN a.b < 10; END_IF; x := a.b = 1;"), "a.b < 10;");
+    }
+
+    #[test]
+    fn test_parse_pou_with_pragmas() {
+        let pou = parse_pou(
+            "{attribute 'qualified_only'}
+            FUNCTION_BLOCK Axis
+            VAR_INPUT
+                {attribute 'hide'}
+                enable : BOOL;
+                speed : REAL; {warning 'check units'}
+            END_VAR
+            VAR
+                {attribute 'monitoring' := 'call'}
+                state : INT;
+            END_VAR
+            {warning disable C0195}
+            IF enable THEN state := 1; END_IF;
+            END_FUNCTION_BLOCK",
+        )
+        .unwrap();
+        let names: Vec<_> = pou.var_blocks.iter().flat_map(|b| &b.vars).map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["enable", "speed", "state"]);
+        assert_eq!(pou.body.len(), 1);
+    }
 }
@@ -0,0 +1,314 @@
+//! Traversal of the Structured Text AST.
+//!
+//! [`Visitor`] has one method per node type. Each default method calls the
+//! matching `walk_*` function, which visits the children of the node, so an
+//! analysis overrides only the nodes it cares about. An overriding method
+//! calls the `walk_*` function itself to keep descending, or returns to
+//! skip the children.
+//!
+//! Collect the names of all function calls of a POU:
+//!
+//! ```
+//! use iecst::{parse_pou, walk_pou, Expr, ExprKind, Visitor};
+//! use iecst::visitor::walk_expr;
+//!
+//! #[derive(Default)]
+//! struct Calls<'ast>(Vec<&'ast str>);
+//!
+//! impl<'ast> Visitor<'ast> for Calls<'ast> {
+//!     fn visit_expr(&mut self, expr: &'ast Expr) {
+//!         if let ExprKind::FunctionCall { name, .. } = &expr.kind {
+//!             self.0.push(name);
+//!         }
+//!         walk_expr(self, expr);
+//!     }
+//! }
+//!
+//! let pou = parse_pou("FUNCTION F : INT VAR x : INT; END_VAR F := MAX(ABS(x), 1); END_FUNCTION").unwrap();
+//! let mut calls = Calls::default();
+//! walk_pou(&mut calls, &pou);
+//! assert_eq!(calls.0, ["MAX", "ABS"]);
+//! ```
+
+use crate::ast::*;
+
+/// Visitor over the nodes of a Structured Text AST.
+///
+/// Nodes are visited in source order; a node is visited before its
+/// children. `'ast` is the lifetime of the tree, so a visitor can keep
+/// references to the nodes it visits.
+pub trait Visitor<'ast> {
+    /// Visit a POU: its variable declarations, then its body.
+    fn visit_pou(&mut self, pou: &'ast Pou) {
+        walk_pou(self, pou);
+    }
+
+    /// Visit a type declaration.
+    fn visit_type_decl(&mut self, decl: &'ast TypeDecl) {
+        walk_type_decl(self, decl);
+    }
+
+    /// Visit a variable block (VAR, VAR_INPUT, ...).
+    fn visit_var_block(&mut self, block: &'ast VarBlock) {
+        walk_var_block(self, block);
+    }
+
+    /// Visit a variable declaration: its type, then its initial value.
+    fn visit_var_decl(&mut self, decl: &'ast VarDecl) {
+        walk_var_decl(self, decl);
+    }
+
+    /// Visit a type specification, with its array and subrange bounds.
+    fn visit_type_spec(&mut self, spec: &'ast TypeSpec) {
+        walk_type_spec(self, spec);
+    }
+
+    /// Visit a statement list: a POU body or a branch or loop body.
+    fn visit_body(&mut self, body: &'ast [Stmt]) {
+        walk_body(self, body);
+    }
+
+    /// Visit a statement.
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        walk_stmt(self, stmt);
+    }
+
+    /// Visit a CASE branch: its labels, then its body.
+    fn visit_case_branch(&mut self, branch: &'ast CaseBranch) {
+        walk_case_branch(self, branch);
+    }
+
+    /// Visit an expression.
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        walk_expr(self, expr);
+    }
+}
+
+/// Visit the variable blocks and the body of a POU.
+pub fn walk_pou<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, pou: &'ast Pou) {
+    if let Some(return_type) = &pou.return_type {
+        visitor.visit_type_spec(return_type);
+    }
+    for block in &pou.var_blocks {
+        visitor.visit_var_block(block);
+    }
+    visitor.visit_body(&pou.body);
+}
+
+/// Visit the types and values of a type declaration.
+pub fn walk_type_decl<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, decl: &'ast TypeDecl) {
+    match &decl.definition {
+        TypeDef::Alias(spec) => visitor.visit_type_spec(spec),
+        TypeDef::Struct { fields } => {
+            for field in fields {
+                visitor.visit_var_decl(field);
+            }
+        }
+        TypeDef::Enum { values } => {
+            for value in values.iter().filter_map(|v| v.value.as_ref()) {
+                visitor.visit_expr(value);
+            }
+        }
+        TypeDef::Subrange { low, high, .. } => {
+            visitor.visit_expr(low);
+            visitor.visit_expr(high);
+        }
+        TypeDef::Array { ranges, element } => {
+            for range in ranges {
+                visitor.visit_expr(&range.low);
+                visitor.visit_expr(&range.high);
+            }
+            visitor.visit_type_spec(element);
+        }
+    }
+}
+
+/// Visit the declarations of a variable block.
+pub fn walk_var_block<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, block: &'ast VarBlock) {
+    for decl in &block.vars {
+        visitor.visit_var_decl(decl);
+    }
+}
+
+/// Visit the type and the initial value of a variable declaration.
+pub fn walk_var_decl<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, decl: &'ast VarDecl) {
+    visitor.visit_type_spec(&decl.var_type);
+    if let Some(initial) = &decl.initial {
+        visitor.visit_expr(initial);
+    }
+}
+
+/// Visit the bounds and element type of a type specification.
+pub fn walk_type_spec<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, spec: &'ast TypeSpec) {
+    match &spec.kind {
+        TypeKind::Array { ranges, element } => {
+            for range in ranges {
+                visitor.visit_expr(&range.low);
+                visitor.visit_expr(&range.high);
+            }
+            visitor.visit_type_spec(element);
+        }
+        TypeKind::Subrange { low, high, .. } => {
+            visitor.visit_expr(low);
+            visitor.visit_expr(high);
+        }
+        TypeKind::Simple(_) | TypeKind::String { .. } | TypeKind::WString { .. } => {}
+    }
+}
+
+/// Visit each statement of a statement list.
+pub fn walk_body<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, body: &'ast [Stmt]) {
+    for stmt in body {
+        visitor.visit_stmt(stmt);
+    }
+}
+
+/// Visit the expressions and nested bodies of a statement.
+pub fn walk_stmt<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, stmt: &'ast Stmt) {
+    match &stmt.kind {
+        StmtKind::Assignment { target, value } => {
+            visitor.visit_expr(target);
+            visitor.visit_expr(value);
+        }
+        StmtKind::If { condition, then_body, elsif_branches, else_body } => {
+            visitor.visit_expr(condition);
+            visitor.visit_body(then_body);
+            for (condition, body) in elsif_branches {
+                visitor.visit_expr(condition);
+                visitor.visit_body(body);
+            }
+            if let Some(else_body) = else_body {
+                visitor.visit_body(else_body);
+            }
+        }
+        StmtKind::Case { expr, cases, else_body } => {
+            visitor.visit_expr(expr);
+            for branch in cases {
+                visitor.visit_case_branch(branch);
+            }
+            if let Some(else_body) = else_body {
+                visitor.visit_body(else_body);
+            }
+        }
+        StmtKind::For { from, to, by, body, .. } => {
+            visitor.visit_expr(from);
+            visitor.visit_expr(to);
+            if let Some(by) = by {
+                visitor.visit_expr(by);
+            }
+            visitor.visit_body(body);
+        }
+        StmtKind::While { condition, body } => {
+            visitor.visit_expr(condition);
+            visitor.visit_body(body);
+        }
+        StmtKind::Repeat { body, until } => {
+            visitor.visit_body(body);
+            visitor.visit_expr(until);
+        }
+        StmtKind::Return { value: Some(value) } => visitor.visit_expr(value),
+        StmtKind::Call { args, .. } => {
+            for value in args.iter().filter_map(|arg| arg.value.as_ref()) {
+                visitor.visit_expr(value);
+            }
+        }
+        StmtKind::Return { value: None } | StmtKind::Exit | StmtKind::Continue | StmtKind::Empty => {}
+    }
+}
+
+/// Visit the labels and the body of a CASE branch.
+pub fn walk_case_branch<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, branch: &'ast CaseBranch) {
+    for value in &branch.values {
+        match value {
+            CaseValue::Single(expr) => visitor.visit_expr(expr),
+            CaseValue::Range { from, to } => {
+                visitor.visit_expr(from);
+                visitor.visit_expr(to);
+            }
+        }
+    }
+    visitor.visit_body(&branch.body);
+}
+
+/// Visit the subexpressions of an expression.
+pub fn walk_expr<'ast, V: Visitor<'ast> + ?Sized>(visitor: &mut V, expr: &'ast Expr) {
+    match &expr.kind {
+        ExprKind::BinaryOp { left, right, .. } => {
+            visitor.visit_expr(left);
+            visitor.visit_expr(right);
+        }
+        ExprKind::UnaryOp { expr: inner, .. } | ExprKind::Paren(inner) => visitor.visit_expr(inner),
+        ExprKind::MemberAccess { expr: inner, .. } => visitor.visit_expr(inner),
+        ExprKind::ArrayIndex { array, indices } => {
+            visitor.visit_expr(array);
+            for index in indices {
+                visitor.visit_expr(index);
+            }
+        }
+        ExprKind::FunctionCall { args, .. } => {
+            for value in args.iter().filter_map(|arg| arg.value.as_ref()) {
+                visitor.visit_expr(value);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_pou;
+
+    /// Records identifiers and the statements it enters.
+    #[derive(Default)]
+    struct Recorder<'ast> {
+        idents: Vec<&'ast str>,
+        statements: usize,
+        skip_loops: bool,
+    }
+
+    impl<'ast> Visitor<'ast> for Recorder<'ast> {
+        fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+            self.statements += 1;
+            if self.skip_loops && matches!(stmt.kind, StmtKind::For { .. }) {
+                return;
+            }
+            walk_stmt(self, stmt);
+        }
+
+        fn visit_expr(&mut self, expr: &'ast Expr) {
+            if let ExprKind::Ident(name) = &expr.kind {
+                self.idents.push(name);
+            }
+            walk_expr(self, expr);
+        }
+    }
+
+    const SOURCE: &str = "FUNCTION_BLOCK FB
+        VAR n : INT := Init; a : ARRAY[0..Max] OF INT; END_VAR
+        CASE Mode OF
+            Idle: n := 0;
+        END_CASE;
+        FOR i := 0 TO Max DO
+            a[i] := n;
+        END_FOR;
+    END_FUNCTION_BLOCK";
+
+    #[test]
+    fn test_walk_pou_in_source_order() {
+        let pou = parse_pou(SOURCE).unwrap();
+        let mut recorder = Recorder::default();
+        walk_pou(&mut recorder, &pou);
+        assert_eq!(recorder.idents, ["Init", "Max", "Mode", "Idle", "n", "Max", "a", "i", "n"]);
+        assert_eq!(recorder.statements, 4);
+    }
+
+    #[test]
+    fn test_override_skips_children() {
+        let pou = parse_pou(SOURCE).unwrap();
+        let mut recorder = Recorder { skip_loops: true, ..Recorder::default() };
+        recorder.visit_body(&pou.body);
+        assert_eq!(recorder.idents, ["Mode", "Idle", "n"]);
+        assert_eq!(recorder.statements, 3);
+    }
+}
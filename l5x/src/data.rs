@@ -0,0 +1,294 @@
+//! Decoded tag data values.
+//!
+//! L5X exports store the value of a tag in `Data` elements, usually twice:
+//! once in the compact `L5K` text format and once in the `Decorated` XML
+//! format. This module decodes the `Decorated` format into a [`TagValue`]
+//! tree with member values by name. Other formats (`L5K`, `String`, ...) are
+//! kept as raw text.
+//!
+//! # Example
+//!
+//! ```
+//! use l5x::{Tag, TagValue};
+//!
+//! let xml = r#"<Tag Name="Setpoint" DataType="REAL">
+//!     <Data Format="Decorated"><DataValue DataType="REAL" Radix="Float" Value="42.5"/></Data>
+//! </Tag>"#;
+//! let tag: Tag = l5x::from_str(xml).unwrap();
+//! assert_eq!(tag.decoded_value().and_then(|v| v.as_f64()), Some(42.5));
+//! ```
+
+use crate::{DataArray, DataStructure, DataValue, DataWide, DataWideContent, Tag, TagContent};
+
+/// A decoded tag value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TagValue {
+    /// Atomic value (BOOL, DINT, REAL, ...) as written in the export
+    Scalar {
+        /// Data type (e.g., "DINT")
+        data_type: Option<String>,
+        /// Radix the value is written in (e.g., "Decimal", "Hex", "Float")
+        radix: Option<String>,
+        /// The value text (e.g., "42", "16#00ff", "1.5")
+        value: String,
+    },
+    /// Structure (UDT, AOI, STRING, TIMER, ...) with its members in order
+    Structure {
+        /// Data type (e.g., "TIMER")
+        data_type: Option<String>,
+        /// Members by name
+        members: Vec<(String, TagValue)>,
+    },
+    /// Array with its elements in order
+    Array {
+        /// Element data type
+        data_type: Option<String>,
+        /// Dimensions as written (e.g., "10" or "2,3")
+        dimensions: Option<String>,
+        /// Elements in index order
+        elements: Vec<TagValue>,
+    },
+    /// Value in a format that is not decoded (e.g., `L5K`)
+    Raw {
+        /// Data format (e.g., "L5K", "String")
+        format: Option<String>,
+        /// The text content
+        text: String,
+    },
+}
+
+impl TagValue {
+    /// Get a structure member by name (case-insensitive, as in Logix)
+    pub fn member(&self, name: &str) -> Option<&TagValue> {
+        match self {
+            TagValue::Structure { members, .. } => members
+                .iter()
+                .find(|(member, _)| member.eq_ignore_ascii_case(name))
+                .map(|(_, value)| value),
+            _ => None,
+        }
+    }
+
+    /// Get an array element by its position
+    pub fn element(&self, index: usize) -> Option<&TagValue> {
+        match self {
+            TagValue::Array { elements, .. } => elements.get(index),
+            _ => None,
+        }
+    }
+
+    /// Get the value text of a scalar
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            TagValue::Scalar { value, .. } => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Get a scalar as an integer (handles `16#`, `8#` and `2#` radix prefixes)
+    pub fn as_i64(&self) -> Option<i64> {
+        let value = self.as_str()?.replace('_', "");
+        let (digits, radix) = if let Some(hex) = value.strip_prefix("16#") {
+            (hex, 16)
+        } else if let Some(oct) = value.strip_prefix("8#") {
+            (oct, 8)
+        } else if let Some(bin) = value.strip_prefix("2#") {
+            (bin, 2)
+        } else {
+            (value.as_str(), 10)
+        };
+        i64::from_str_radix(digits, radix).ok()
+    }
+
+    /// Get a scalar as a floating-point number
+    pub fn as_f64(&self) -> Option<f64> {
+        self.as_str()?.parse::<f64>().ok().or_else(|| self.as_i64().map(|v| v as f64))
+    }
+
+    /// Get a scalar as a boolean (`1`/`0`)
+    pub fn as_bool(&self) -> Option<bool> {
+        self.as_i64().map(|v| v != 0)
+    }
+}
+
+impl DataWide {
+    /// Decode this data element.
+    ///
+    /// Returns `None` for a `Decorated` element without a value.
+    pub fn decode(&self) -> Option<TagValue> {
+        if !self.is_decorated() {
+            let text: String = self
+                .content
+                .iter()
+                .filter_map(|content| match content {
+                    DataWideContent::TextContent(text) => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect();
+            return Some(TagValue::Raw {
+                format: self.format.clone(),
+                text: text.trim().to_string(),
+            });
+        }
+
+        self.content.iter().find_map(|content| match content {
+            DataWideContent::DataValue(value) => Some(decode_data_value(value)),
+            DataWideContent::Structure(structure) => Some(decode_structure(structure)),
+            DataWideContent::Array(array) => Some(decode_array(array)),
+            _ => None,
+        })
+    }
+
+    /// Returns true if this element is in the `Decorated` format
+    pub fn is_decorated(&self) -> bool {
+        self.format.as_deref().is_some_and(|f| f.eq_ignore_ascii_case("Decorated"))
+    }
+}
+
+impl Tag {
+    /// Decode the tag's data, preferring the `Decorated` format.
+    ///
+    /// Returns `None` if the tag has no `Data` element (e.g., alias tags).
+    pub fn decoded_value(&self) -> Option<TagValue> {
+        let data: Vec<&DataWide> = self
+            .content
+            .iter()
+            .filter_map(|content| match content {
+                TagContent::Data(data) => Some(data),
+                _ => None,
+            })
+            .collect();
+
+        data.iter()
+            .find(|d| d.is_decorated())
+            .or_else(|| data.first())
+            .and_then(|d| d.decode())
+    }
+}
+
+fn decode_data_value(value: &DataValue) -> TagValue {
+    TagValue::Scalar {
+        data_type: value.data_type.clone(),
+        radix: value.radix.clone(),
+        value: value
+            .value
+            .clone()
+            .or_else(|| value.text.clone())
+            .unwrap_or_default(),
+    }
+}
+
+fn decode_structure(structure: &DataStructure) -> TagValue {
+    let mut members = Vec::new();
+    for member in &structure.data_value_member {
+        members.push((member.name.clone().unwrap_or_default(), decode_data_value(member)));
+    }
+    for member in &structure.structure_member {
+        members.push((member.name.clone().unwrap_or_default(), decode_structure(member)));
+    }
+    for member in &structure.array_member {
+        members.push((member.name.clone().unwrap_or_default(), decode_array(member)));
+    }
+    TagValue::Structure {
+        data_type: structure.data_type.clone(),
+        members,
+    }
+}
+
+fn decode_array(array: &DataArray) -> TagValue {
+    let elements = array
+        .element
+        .iter()
+        .map(|element| match element.structure.first() {
+            Some(structure) => decode_structure(structure),
+            None => TagValue::Scalar {
+                data_type: array.data_type.clone(),
+                radix: array.radix.clone(),
+                value: element.value.clone().unwrap_or_default(),
+            },
+        })
+        .collect();
+    TagValue::Array {
+        data_type: array.data_type.clone(),
+        dimensions: array.dimensions.clone(),
+        elements,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(xml: &str) -> Tag {
+        crate::from_str(xml).expect("Should parse")
+    }
+
+    #[test]
+    fn test_decorated_scalar() {
+        let tag = tag(r#"<Tag Name="Mask" TagType="Base" DataType="DINT" Radix="Hex">
+            <Data Format="L5K"><![CDATA[255]]></Data>
+            <Data Format="Decorated"><DataValue DataType="DINT" Radix="Hex" Value="16#0000_00ff"/></Data>
+        </Tag>"#);
+        let value = tag.decoded_value().unwrap();
+        assert_eq!(value.as_i64(), Some(255));
+        assert_eq!(value.as_str(), Some("16#0000_00ff"));
+    }
+
+    #[test]
+    fn test_decorated_structure_and_array() {
+        let tag = tag(r#"<Tag Name="Recipe" TagType="Base" DataType="Recipe_UDT">
+            <Data Format="Decorated">
+                <Structure DataType="Recipe_UDT">
+                    <DataValueMember Name="Enabled" DataType="BOOL" Value="1"/>
+                    <StructureMember Name="Delay" DataType="TIMER">
+                        <DataValueMember Name="PRE" DataType="DINT" Radix="Decimal" Value="5000"/>
+                    </StructureMember>
+                    <ArrayMember Name="Speeds" DataType="REAL" Dimensions="3" Radix="Float">
+                        <Element Index="[0]" Value="1.5"/>
+                        <Element Index="[1]" Value="2.0"/>
+                        <Element Index="[2]" Value="0.0"/>
+                    </ArrayMember>
+                </Structure>
+            </Data>
+        </Tag>"#);
+        let value = tag.decoded_value().unwrap();
+        assert_eq!(value.member("enabled").and_then(|v| v.as_bool()), Some(true));
+        assert_eq!(value.member("Delay").and_then(|v| v.member("PRE")).and_then(|v| v.as_i64()), Some(5000));
+        assert_eq!(value.member("Speeds").and_then(|v| v.element(1)).and_then(|v| v.as_f64()), Some(2.0));
+        assert!(value.member("Missing").is_none());
+    }
+
+    #[test]
+    fn test_array_of_structures() {
+        let tag = tag(r#"<Tag Name="Axes" TagType="Base" DataType="Axis_UDT" Dimensions="2">
+            <Data Format="Decorated">
+                <Array DataType="Axis_UDT" Dimensions="2">
+                    <Element Index="[0]">
+                        <Structure DataType="Axis_UDT">
+                            <DataValueMember Name="Id" DataType="DINT" Value="1"/>
+                        </Structure>
+                    </Element>
+                    <Element Index="[1]">
+                        <Structure DataType="Axis_UDT">
+                            <DataValueMember Name="Id" DataType="DINT" Value="2"/>
+                        </Structure>
+                    </Element>
+                </Array>
+            </Data>
+        </Tag>"#);
+        let value = tag.decoded_value().unwrap();
+        assert_eq!(value.element(1).and_then(|v| v.member("Id")).and_then(|v| v.as_i64()), Some(2));
+    }
+
+    #[test]
+    fn test_l5k_only_is_raw() {
+        let tag = tag(r#"<Tag Name="Count" TagType="Base" DataType="DINT">
+            <Data Format="L5K"><![CDATA[42]]></Data>
+        </Tag>"#);
+        assert_eq!(
+            tag.decoded_value(),
+            Some(TagValue::Raw { format: Some("L5K".to_string()), text: "42".to_string() })
+        );
+        assert!(self::tag(r#"<Tag Name="Alias" TagType="Alias" AliasFor="Count"/>"#).decoded_value().is_none());
+    }
+}
@@ -8,6 +8,7 @@
 //! - Fast, type-safe parsing using quick-xml and serde
 //! - Generated types from the official L5X XSD schema
 //! - RLL (Relay Ladder Logic) instruction parsing
+//! - Decoded tag data values (`Tag::decoded_value`)
 //!
 //! # Example
 //!
@@ -39,6 +40,13 @@ pub use security::{SecurityLimits, SecurityError, validate_xml};
 // RLL (Relay Ladder Logic) parser
 pub mod rll;
 
+// Decoded tag data values
+pub mod data;
+pub use data::TagValue;
+
+// Source protection of routines and AOIs
+pub mod protection;
+
 // Include pre-generated types (no build.rs needed)
 #[path = "../generated/generated.rs"]
 mod generated;
@@ -0,0 +1,90 @@
+//! Source protection of routines and Add-On Instructions.
+//!
+//! Source-protected components carry a source key or protection type, or
+//! encrypted content; without the key, their logic is not in the export.
+
+use crate::{Routine, RoutineContent, UDIDefinition, UDIDefinitionContent};
+
+impl Routine {
+    /// Returns true if the routine is source protected.
+    pub fn is_source_protected(&self) -> bool {
+        self.source_key.is_some()
+            || self.encoded_source_key.is_some()
+            || self.source_protection_type.is_some()
+            || self.content.iter().any(|content| {
+                matches!(content, RoutineContent::EncryptionInfo(_) | RoutineContent::EncryptedContent(_))
+            })
+    }
+
+    /// Returns true if the routine's logic (RLL, ST, FBD, SFC, ...) is in the export.
+    pub fn has_logic(&self) -> bool {
+        self.content.iter().any(|content| {
+            matches!(
+                content,
+                RoutineContent::RLLContent(_)
+                    | RoutineContent::STContent(_)
+                    | RoutineContent::FBDContent(_)
+                    | RoutineContent::SFCContent(_)
+                    | RoutineContent::SEQContent(_)
+                    | RoutineContent::ExternalContent(_)
+            )
+        })
+    }
+}
+
+impl UDIDefinition {
+    /// Returns true if the Add-On Instruction is source protected.
+    pub fn is_source_protected(&self) -> bool {
+        self.source_key.is_some()
+            || self.encoded_source_key.is_some()
+            || self.source_protection_type.is_some()
+            || self.is_encrypted.as_deref().is_some_and(|value| value.eq_ignore_ascii_case("true"))
+            || self.content.iter().any(|content| {
+                matches!(
+                    content,
+                    UDIDefinitionContent::EncryptionInfo(_) | UDIDefinitionContent::EncryptedAOIContent(_)
+                )
+            })
+    }
+
+    /// Returns true if the logic of any of the AOI's routines is in the export.
+    pub fn has_logic(&self) -> bool {
+        self.content.iter().any(|content| match content {
+            UDIDefinitionContent::Routines(routines) => routines.routine.iter().any(Routine::has_logic),
+            _ => false,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protected_routine() {
+        let routine: Routine =
+            crate::from_str(r#"<Routine Name="Secret" Type="RLL" SourceKey="[all]" SourceProtectionType="Key"/>"#)
+                .expect("Should parse");
+        assert!(routine.is_source_protected());
+        assert!(!routine.has_logic());
+
+        let routine: Routine = crate::from_str(
+            r#"<Routine Name="Main" Type="RLL"><RLLContent><Rung Number="0"><Text>NOP();</Text></Rung></RLLContent></Routine>"#,
+        )
+        .expect("Should parse");
+        assert!(!routine.is_source_protected());
+        assert!(routine.has_logic());
+    }
+
+    #[test]
+    fn test_protected_aoi() {
+        let aoi: UDIDefinition = crate::from_str(
+            r#"<AddOnInstructionDefinition Name="Valve" EncodedSourceKey="abc">
+                <Routines><Routine Name="Logic" Type="RLL"/></Routines>
+            </AddOnInstructionDefinition>"#,
+        )
+        .expect("Should parse");
+        assert!(aoi.is_source_protected());
+        assert!(!aoi.has_logic());
+    }
+}
@@ -1,6 +1,7 @@
 //! AST types for RLL (Relay Ladder Logic).
 
 use crate::rll::error::RllError;
+use crate::rll::expression::{expression_operand_index, ExpressionOperand};
 
 /// A parsed rung of ladder logic.
 #[derive(Debug, Clone, PartialEq)]
@@ -67,6 +68,76 @@ impl RungContent {
         }
         refs
     }
+
+    /// All instructions in rung order, with their position in the branch structure
+    pub fn instructions(&self) -> Vec<(BranchPath, &Instruction)> {
+        let mut result = Vec::new();
+        collect_instructions(&self.elements, &mut Vec::new(), &mut result);
+        result
+    }
+
+    /// Maximum nesting depth of parallel branches (0 for a purely serial rung)
+    pub fn branch_depth(&self) -> usize {
+        self.instructions()
+            .iter()
+            .map(|(path, _)| path.depth())
+            .max()
+            .unwrap_or(0)
+    }
+}
+
+/// Collect instructions from a sequence of elements, tracking the branch path
+fn collect_instructions<'a>(
+    elements: &'a [RungElement],
+    path: &mut Vec<BranchStep>,
+    result: &mut Vec<(BranchPath, &'a Instruction)>,
+) {
+    for (element_index, element) in elements.iter().enumerate() {
+        match element {
+            RungElement::Instruction(instr) => {
+                result.push((BranchPath(path.clone()), instr));
+            }
+            RungElement::Parallel(branches) => {
+                for (leg, branch) in branches.iter().enumerate() {
+                    path.push(BranchStep { element: element_index, leg });
+                    collect_instructions(&branch.elements, path, result);
+                    path.pop();
+                }
+            }
+        }
+    }
+}
+
+/// One level of a branch path: which parallel element and which leg of it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BranchStep {
+    /// Index of the parallel element within its enclosing sequence
+    pub element: usize,
+    /// Index of the leg (branch) within the parallel element
+    pub leg: usize,
+}
+
+/// Position of an instruction within the parallel branches of a rung.
+///
+/// Empty for instructions on the main (serial) path of the rung; one step
+/// per enclosing parallel structure otherwise, outermost first.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct BranchPath(pub Vec<BranchStep>);
+
+impl BranchPath {
+    /// Number of enclosing parallel structures
+    pub fn depth(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns true if the two positions are on different legs of the same
+    /// parallel structure (OR logic), false if they are in series (AND logic)
+    pub fn is_parallel_to(&self, other: &BranchPath) -> bool {
+        match self.0.iter().zip(&other.0).find(|(a, b)| a != b) {
+            Some((a, b)) => a.element == b.element && a.leg != b.leg,
+            None => false,
+        }
+    }
 }
 
 /// Element in a rung (instruction or parallel branch).
@@ -97,6 +168,8 @@ impl RungElement {
 }
 
 /// A branch within a parallel structure.
+///
+/// A branch may be empty (`[XIC(A),]`), which shorts around the other legs.
 #[derive(Debug, Clone, PartialEq)]
 pub struct Branch {
     /// Elements in this branch
@@ -128,13 +201,25 @@ impl Instruction {
         }
     }
 
+    /// Parse the expression operand of a compute instruction (CPT, CMP, FAL, FSC)
+    pub fn expression(&self) -> Option<ExpressionOperand> {
+        let index = expression_operand_index(&self.mnemonic)?;
+        let value = self.operands.get(index)?.as_value()?;
+        Some(ExpressionOperand::parse(value))
+    }
+
     /// Collect tag references from this instruction's operands
     fn collect_tag_references(&self, refs: &mut Vec<TagReference>) {
+        let expression_index = expression_operand_index(&self.mnemonic);
         for (index, operand) in self.operands.iter().enumerate() {
             if let Operand::Value(value) = operand {
                 // Parse the operand to extract actual tag references
-                let parsed = crate::rll::operand::parse_operand_value(value);
-                for tag in parsed.all_tags() {
+                let tags = if expression_index == Some(index) {
+                    ExpressionOperand::parse(value).all_tags()
+                } else {
+                    crate::rll::operand::parse_operand_value(value).all_tags()
+                };
+                for tag in tags {
                     refs.push(TagReference {
                         name: tag,
                         full_operand: value.clone(),
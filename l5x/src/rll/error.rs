@@ -43,6 +43,44 @@ impl fmt::Display for Span {
     }
 }
 
+/// A line and column in a text, both from 1; the column counts characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextPosition {
+    /// Line number
+    pub line: usize,
+    /// Column number
+    pub column: usize,
+}
+
+impl TextPosition {
+    /// Position of a byte offset in `text`.
+    pub fn of(text: &str, offset: usize) -> Self {
+        let offset = offset.min(text.len());
+        let before = text.get(..offset).unwrap_or(text);
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// Translate a position in a fragment to the enclosing text, where the
+    /// fragment starts at `start`.
+    pub fn within(self, start: TextPosition) -> Self {
+        if self.line == 1 {
+            Self { line: start.line, column: start.column + self.column - 1 }
+        } else {
+            Self { line: start.line + self.line - 1, column: self.column }
+        }
+    }
+}
+
+impl fmt::Display for TextPosition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.line, self.column)
+    }
+}
+
 /// Errors that can occur during RLL parsing.
 #[derive(Debug, Clone, PartialEq)]
 pub enum RllError {
@@ -204,6 +242,9 @@ pub struct ParseError {
     pub source: String,
     /// Optional file/location context
     pub context: Option<ErrorContext>,
+    /// Position of the start of the source text in the file it was read
+    /// from, if known
+    pub text_start: Option<TextPosition>,
 }
 
 /// Additional context for where an error occurred.
@@ -240,6 +281,7 @@ impl ParseError {
             error,
             source: source.into(),
             context: None,
+            text_start: None,
         }
     }
 
@@ -249,6 +291,32 @@ impl ParseError {
         self
     }
 
+    /// Record where the source text starts in the file it was read from.
+    pub fn with_text_start(mut self, start: TextPosition) -> Self {
+        self.text_start = Some(start);
+        self
+    }
+
+    /// Record where the source text starts in the file it was read from,
+    /// from the file content and the byte offset of the text in it.
+    pub fn with_file_offset(self, file: &str, offset: usize) -> Self {
+        self.with_text_start(TextPosition::of(file, offset))
+    }
+
+    /// Position of the error in the source text.
+    pub fn rung_position(&self) -> Option<TextPosition> {
+        self.error.position().map(|pos| TextPosition::of(&self.source, pos))
+    }
+
+    /// Position of the error in the file, if the start of the source text
+    /// in the file is known. Errors without a position are placed at the
+    /// end of the source text.
+    pub fn file_position(&self) -> Option<TextPosition> {
+        let position = self.error.position().unwrap_or(self.source.len());
+        let start = self.text_start?;
+        Some(TextPosition::of(&self.source, position).within(start))
+    }
+
     /// Format the error with full source context.
     pub fn format(&self) -> String {
         let mut result = String::new();
@@ -257,6 +325,9 @@ impl ParseError {
         if let Some(ctx) = &self.context {
             result.push_str(&format!("in {}\n", ctx.path()));
         }
+        if let Some(position) = self.file_position() {
+            result.push_str(&format!("at file position {}\n", position));
+        }
         
         // Delegate to RllError's context formatting
         result.push_str(&self.error.format_with_context(&self.source));
@@ -332,6 +403,24 @@ mod tests {
         assert!(formatted.contains("missing rung terminator"));
     }
 
+    #[test]
+    fn test_file_position() {
+        let file = "<Rung Number=\"0\">\n<Text>\n<![CDATA[XIC(A)\nOTE(!);]]>\n</Text>";
+        let offset = file.find("XIC").unwrap();
+        let source = "XIC(A)\nOTE(!);";
+        let err = ParseError::new(RllError::UnexpectedChar { char: '!', position: 11 }, source)
+            .with_file_offset(file, offset);
+
+        assert_eq!(err.rung_position(), Some(TextPosition { line: 2, column: 5 }));
+        assert_eq!(err.file_position(), Some(TextPosition { line: 4, column: 5 }));
+        assert!(err.format().contains("at file position 4:5"));
+
+        let err = ParseError::new(RllError::Expected { expected: "operand", position: 4 }, "XIC();")
+            .with_text_start(TextPosition { line: 7, column: 10 });
+        assert_eq!(err.file_position(), Some(TextPosition { line: 7, column: 14 }));
+        assert_eq!(ParseError::new(RllError::EmptyInput, "").file_position(), None);
+    }
+
     #[test]
     fn test_long_line_truncation() {
         // Create a long line with error near the middle
@@ -0,0 +1,147 @@
+//! Expression operands of compute instructions.
+//!
+//! CPT, CMP, FAL and FSC take an expression string as one of their
+//! operands (e.g. `CPT(Dest, A + B * 3)`, `CMP(Level > Max AND NOT Alarm)`).
+//! The expression is parsed with the IEC 61131-3 ST expression parser, so
+//! word operators (`AND`, `MOD`, `NOT`) and nested function calls are
+//! understood. Expressions the ST parser rejects (e.g. module I/O tags like
+//! `Local:1:I.Data`) are kept as raw text with the parse error, and their
+//! tags are extracted with the heuristic operand parser.
+
+use iecst::{Expr, ExprKind, ParseError, ParseErrorKind, Span};
+
+use crate::rll::operand::parse_operand_value;
+
+/// Get the index of the expression operand of a compute instruction.
+pub fn expression_operand_index(mnemonic: &str) -> Option<usize> {
+    match mnemonic.to_uppercase().as_str() {
+        "CMP" => Some(0),
+        "CPT" => Some(1),
+        "FSC" => Some(4),
+        "FAL" => Some(5),
+        _ => None,
+    }
+}
+
+/// A parsed expression operand.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExpressionOperand {
+    /// Expression parsed into the ST expression AST
+    Parsed(Expr),
+    /// Expression the ST parser could not handle, kept as written
+    Raw {
+        /// The expression text
+        text: String,
+        /// Why the expression could not be parsed
+        error: ParseError,
+    },
+}
+
+impl ExpressionOperand {
+    /// Parse an expression operand.
+    ///
+    /// This is permissive: malformed expressions become [`ExpressionOperand::Raw`].
+    pub fn parse(text: &str) -> Self {
+        let trimmed = text.trim();
+        match iecst::parse_expression(trimmed) {
+            // Trailing input means only a prefix was understood
+            Ok(expr) if expr.span.end < trimmed.len() => Self::Raw {
+                text: trimmed.to_string(),
+                error: ParseError::new(
+                    ParseErrorKind::UnexpectedToken { expected: "end of expression" },
+                    Span::new(expr.span.end, trimmed.len()),
+                ),
+            },
+            Ok(expr) => Self::Parsed(expr),
+            Err(error) => Self::Raw {
+                text: trimmed.to_string(),
+                error,
+            },
+        }
+    }
+
+    /// Get the parse error if the expression could not be parsed
+    pub fn error(&self) -> Option<&ParseError> {
+        match self {
+            Self::Parsed(_) => None,
+            Self::Raw { error, .. } => Some(error),
+        }
+    }
+
+    /// Extract all tag base names referenced in the expression
+    pub fn all_tags(&self) -> Vec<String> {
+        match self {
+            Self::Parsed(expr) => {
+                let mut tags = Vec::new();
+                collect_expr_tags(expr, &mut tags);
+                tags
+            }
+            Self::Raw { text, .. } => parse_operand_value(text).all_tags(),
+        }
+    }
+}
+
+/// Collect tag base names from an expression (function names are not tags).
+fn collect_expr_tags(expr: &Expr, tags: &mut Vec<String>) {
+    match &expr.kind {
+        ExprKind::Ident(name) => tags.push(name.clone()),
+        ExprKind::MemberAccess { expr, .. } | ExprKind::Paren(expr) | ExprKind::UnaryOp { expr, .. } => {
+            collect_expr_tags(expr, tags);
+        }
+        ExprKind::ArrayIndex { array, indices } => {
+            collect_expr_tags(array, tags);
+            for index in indices {
+                collect_expr_tags(index, tags);
+            }
+        }
+        ExprKind::BinaryOp { left, right, .. } => {
+            collect_expr_tags(left, tags);
+            collect_expr_tags(right, tags);
+        }
+        ExprKind::FunctionCall { args, .. } => {
+            for arg in args.iter().filter_map(|arg| arg.value.as_ref()) {
+                collect_expr_tags(arg, tags);
+            }
+        }
+        _ => {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_word_operators() {
+        let expr = ExpressionOperand::parse("Level > Max AND NOT Alarm.Active");
+        assert!(expr.error().is_none());
+        assert_eq!(expr.all_tags(), ["Level", "Max", "Alarm"]);
+
+        let expr = ExpressionOperand::parse("(Count MOD 10) * Scale[Index] ** 2");
+        assert_eq!(expr.all_tags(), ["Count", "Scale", "Index"]);
+    }
+
+    #[test]
+    fn test_function_arguments() {
+        let expr = ExpressionOperand::parse("SQRT(ABS(Delta)) + ATN(Ratio)");
+        assert_eq!(expr.all_tags(), ["Delta", "Ratio"]);
+    }
+
+    #[test]
+    fn test_malformed_falls_back_to_raw() {
+        let expr = ExpressionOperand::parse("Local:1:I.Data + Offset");
+        assert!(expr.error().is_some());
+        assert!(matches!(expr, ExpressionOperand::Raw { ref text, .. } if text == "Local:1:I.Data + Offset"));
+        assert_eq!(expr.all_tags(), ["Local", "Offset"]);
+
+        let expr = ExpressionOperand::parse("A + (B");
+        assert!(expr.error().is_some());
+    }
+
+    #[test]
+    fn test_expression_operand_index() {
+        assert_eq!(expression_operand_index("CPT"), Some(1));
+        assert_eq!(expression_operand_index("cmp"), Some(0));
+        assert_eq!(expression_operand_index("MOV"), None);
+    }
+}
@@ -33,10 +33,12 @@
 
 mod ast;
 mod error;
+mod expression;
 mod operand;
 mod parser;
 
 pub use ast::*;
 pub use error::*;
+pub use expression::*;
 pub use operand::*;
 pub use parser::parse_rung;
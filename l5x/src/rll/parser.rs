@@ -5,7 +5,7 @@
 //! rung           = element* ";"
 //! element        = instruction | parallel
 //! parallel       = "[" branch ("," branch)* "]"
-//! branch         = element+
+//! branch         = element*
 //! instruction    = MNEMONIC "(" operand_list? ")"
 //! operand_list   = operand ("," operand)*
 //! operand        = "?" | OPERAND_STRING
@@ -105,12 +105,12 @@ fn ws_comma_ws(input: &mut &str) -> ModalResult<()> {
     Ok(())
 }
 
-/// Parse a branch: element+
+/// Parse a branch: element* (an empty branch shorts the parallel)
 fn branch_parser(input: &mut &str) -> ModalResult<Branch> {
     // Skip leading whitespace
     let _ = take_while(0.., |c: char| c.is_whitespace()).parse_next(input)?;
     
-    let elements: Vec<RungElement> = repeat(1.., branch_element_parser).parse_next(input)?;
+    let elements: Vec<RungElement> = repeat(0.., branch_element_parser).parse_next(input)?;
     Ok(Branch::new(elements))
 }
 
@@ -353,6 +353,72 @@ mod tests {
         assert!(rung.is_parsed());
     }
 
+    #[test]
+    fn test_parse_nested_parallel_structure() {
+        let rung = parse_rung("XIC(A)[XIC(B)[XIC(C)[OTE(D),OTE(E)],OTE(F)],OTE(G)];");
+        assert!(rung.is_parsed(), "Error: {:?}", rung.error);
+        let content = rung.content.unwrap();
+        assert_eq!(content.elements.len(), 2);
+        assert_eq!(content.branch_depth(), 3);
+
+        let RungElement::Parallel(outer) = &content.elements[1] else {
+            panic!("Expected parallel");
+        };
+        assert_eq!(outer.len(), 2);
+        assert_eq!(outer[0].elements.len(), 2);
+        let RungElement::Parallel(middle) = &outer[0].elements[1] else {
+            panic!("Expected nested parallel");
+        };
+        assert_eq!(middle.len(), 2);
+        let RungElement::Parallel(inner) = &middle[0].elements[1] else {
+            panic!("Expected innermost parallel");
+        };
+        assert_eq!(inner.len(), 2);
+
+        // Tag references recurse into every level
+        let names: Vec<String> = content.tag_references().into_iter().map(|r| r.name).collect();
+        assert_eq!(names, ["A", "B", "C", "D", "E", "F", "G"]);
+    }
+
+    #[test]
+    fn test_instruction_paths() {
+        let rung = parse_rung("XIC(A)[XIC(B)[OTE(C),OTE(D)],OTE(E)]OTE(F);");
+        let content = rung.content.unwrap();
+        let instructions = content.instructions();
+        let operand = |i: usize| instructions[i].1.operands[0].as_value().unwrap();
+        let path = |name: &str| {
+            &instructions.iter().find(|(_, instr)| instr.operands[0].as_value() == Some(name)).unwrap().0
+        };
+
+        assert_eq!(instructions.len(), 6);
+        assert_eq!(operand(0), "A");
+        assert_eq!(operand(5), "F");
+        assert_eq!(path("A").depth(), 0);
+        assert_eq!(path("C").depth(), 2);
+
+        // Different legs of the same parallel are OR-ed
+        assert!(path("C").is_parallel_to(path("D")));
+        assert!(path("D").is_parallel_to(path("E")));
+        assert!(path("B").is_parallel_to(path("E")));
+        // Series elements are AND-ed
+        assert!(!path("B").is_parallel_to(path("C")));
+        assert!(!path("A").is_parallel_to(path("C")));
+        assert!(!path("E").is_parallel_to(path("F")));
+    }
+
+    #[test]
+    fn test_parse_empty_branch_leg() {
+        let rung = parse_rung("XIC(A)[XIC(B) ,]OTE(C);");
+        assert!(rung.is_parsed(), "Error: {:?}", rung.error);
+        let content = rung.content.unwrap();
+        let RungElement::Parallel(branches) = &content.elements[1] else {
+            panic!("Expected parallel");
+        };
+        assert_eq!(branches.len(), 2);
+        assert!(branches[1].elements.is_empty());
+        assert_eq!(content.tag_references().len(), 3);
+    }
+
     #[test]
     fn test_parse_structured_tag() {
         let rung = parse_rung("XIC(Timer1.DN)OTE(Motor.Run);");
@@ -397,6 +463,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_compute_expression_tags() {
+        let rung = parse_rung("CMP(Level > Max AND NOT Alarm)CPT(Out,Count MOD 10);");
+        assert!(rung.is_parsed());
+
+        let names: Vec<String> = rung.tag_references().into_iter().map(|r| r.name).collect();
+        assert_eq!(names, ["Level", "Max", "Alarm", "Out", "Count"]);
+
+        let content = rung.content.unwrap();
+        let RungElement::Instruction(cpt) = &content.elements[1] else {
+            panic!("Expected instruction");
+        };
+        assert!(cpt.expression().is_some_and(|e| e.error().is_none()));
+    }
+
     #[test]
     fn test_parse_complex_real_example() {
         // From actual L5X file
@@ -34,8 +34,8 @@ fn main() {
     println!("\nVariables:");
     println!("  - Defined: {}", analysis.defined_variables.len());
     println!("  - Used: {}", analysis.used_variables.len());
-    println!("  - Unused: {}", analysis.unused_variables().len());
-    println!("  - Undefined: {}", analysis.undefined_variables().len());
+    println!("  - Unused: {}", analysis.unused_variables(false).len());
+    println!("  - Undefined: {}", analysis.undefined_variables(false).len());
     println!("\nPOUs:");
     println!("  - Called: {}", analysis.used_pous.len());
     println!("  - Empty: {}", analysis.empty_pous.len());
@@ -64,9 +64,9 @@ fn main() {
     }
 
     // Show unused variables
-    if !analysis.unused_variables().is_empty() {
+    if !analysis.unused_variables(false).is_empty() {
         println!("\n=== Unused Variables ===");
-        let mut unused = analysis.unused_variables();
+        let mut unused = analysis.unused_variables(false);
         unused.sort_by_key(|v| &v.name);
         for var in unused {
             println!("  - {} (in {})", var.name, var.pou_name);
@@ -74,9 +74,9 @@ fn main() {
     }
 
     // Show undefined variables
-    if !analysis.undefined_variables().is_empty() {
+    if !analysis.undefined_variables(false).is_empty() {
         println!("\n=== Undefined Variables ===");
-        let mut undefined = analysis.undefined_variables();
+        let mut undefined = analysis.undefined_variables(false);
         undefined.sort();
         for var in undefined {
             println!("  - {}", var);
@@ -39,7 +39,7 @@ fn main() {
     println!("\n📈 Variable Analysis:");
     println!("   Defined: {}", analysis.defined_variables.len());
     println!("   Used:    {}", analysis.used_variables.len());
-    println!("   Unused:  {}", analysis.unused_variables().len());
+    println!("   Unused:  {}", analysis.unused_variables(false).len());
 
     // Show which variables came from text languages
     let text_lang_vars: Vec<_> = analysis.used_variables.iter()
@@ -61,9 +61,9 @@ fn main() {
     }
 
     // Show unused variables that were defined but not found in code
-    if !analysis.unused_variables().is_empty() {
+    if !analysis.unused_variables(false).is_empty() {
         println!("\n⚠️  Unused Variables (potential issues):");
-        let mut unused = analysis.unused_variables();
+        let mut unused = analysis.unused_variables(false);
         unused.sort_by_key(|v| &v.name);
         for var in unused.iter().take(5) {
             println!("   • {} (in {})", var.name, var.pou_name);
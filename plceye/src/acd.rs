@@ -0,0 +1,151 @@
+//! ACD export hook.
+//!
+//! plceye cannot read Studio 5000 `.ACD` project files. With
+//! `[general] acd_export_cmd` set, an `.ACD` input is first exported to an
+//! L5X file in a temporary folder by that command, and the export is
+//! analyzed in its place.
+//!
+//! The command is split into words at whitespace; double quotes group a
+//! word with spaces. `{input}` is replaced by the path of the `.ACD` file
+//! and `{output}` by the path of the L5X file the command must write. A
+//! command with neither placeholder gets both paths appended, input first.
+//! The command must exit with status 0 and leave the L5X file at
+//! `{output}`; its standard error is shown if it fails.
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::error::{AcdExportErrorKind, Error, Result};
+
+/// Check if a path is a Studio 5000 `.ACD` project file.
+pub fn is_acd_path(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case("acd"))
+}
+
+/// Split an export command into words, `None` if it has no program.
+pub(crate) fn command_words(command: &str) -> Option<Vec<String>> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut quoted = false;
+    for c in command.chars() {
+        match c {
+            '"' => {
+                quoted = !quoted;
+                in_word = true;
+            }
+            c if c.is_whitespace() && !quoted => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                word.push(c);
+                in_word = true;
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    (!words.is_empty()).then_some(words)
+}
+
+/// An L5X export of an `.ACD` file in a temporary folder, removed on drop.
+pub(crate) struct AcdExport {
+    dir: PathBuf,
+    /// Path of the exported L5X file
+    pub l5x: PathBuf,
+}
+
+impl Drop for AcdExport {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Export an `.ACD` file to L5X with the configured command.
+pub(crate) fn export(path: &Path, command: Option<&str>) -> Result<AcdExport> {
+    let error = |kind| Error::AcdExport {
+        path: path.display().to_string(),
+        kind,
+    };
+    let words = command.and_then(command_words).ok_or_else(|| error(AcdExportErrorKind::NoCommand))?;
+
+    static EXPORTS: AtomicUsize = AtomicUsize::new(0);
+    let dir = std::env::temp_dir().join(format!(
+        "plceye_acd_{}_{}",
+        std::process::id(),
+        EXPORTS.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&dir).map_err(|e| error(AcdExportErrorKind::TempDir { message: e.to_string() }))?;
+    let stem = path.file_stem().and_then(|s| s.to_str()).unwrap_or("export");
+    let export = AcdExport {
+        l5x: dir.join(format!("{}.L5X", stem)),
+        dir,
+    };
+
+    let input = path.display().to_string();
+    let output = export.l5x.display().to_string();
+    let has_placeholders = words.iter().any(|w| w.contains("{input}") || w.contains("{output}"));
+    let mut args: Vec<String> = words[1..]
+        .iter()
+        .map(|w| w.replace("{input}", &input).replace("{output}", &output))
+        .collect();
+    if !has_placeholders {
+        args.push(input);
+        args.push(output);
+    }
+
+    let result = Command::new(&words[0]).args(&args).output().map_err(|e| {
+        error(AcdExportErrorKind::Spawn {
+            program: words[0].clone(),
+            message: e.to_string(),
+        })
+    })?;
+    if !result.status.success() {
+        return Err(error(AcdExportErrorKind::Failed {
+            status: result.status.code(),
+            stderr: String::from_utf8_lossy(&result.stderr).trim().to_string(),
+        }));
+    }
+    if !export.l5x.is_file() {
+        return Err(error(AcdExportErrorKind::NoOutput {
+            output: export.l5x.display().to_string(),
+        }));
+    }
+    Ok(export)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_words() {
+        assert_eq!(
+            command_words(r#""C:\Program Files\Export\acd2l5x.exe" --in {input} --out "{output}""#).unwrap(),
+            [r"C:\Program Files\Export\acd2l5x.exe", "--in", "{input}", "--out", "{output}"]
+        );
+        assert_eq!(command_words("export  ").unwrap(), ["export"]);
+        assert_eq!(command_words(r#"export """#).unwrap(), ["export", ""]);
+        assert!(command_words("   ").is_none());
+    }
+
+    #[test]
+    fn test_export_errors() {
+        let path = Path::new("Line1.ACD");
+        assert!(matches!(
+            export(path, None),
+            Err(Error::AcdExport { kind: AcdExportErrorKind::NoCommand, .. })
+        ));
+        assert!(matches!(
+            export(path, Some("plceye-no-such-exporter")),
+            Err(Error::AcdExport { kind: AcdExportErrorKind::Spawn { .. }, .. })
+        ));
+    }
+}
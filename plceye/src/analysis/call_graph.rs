@@ -0,0 +1,189 @@
+//! POU dependency graph.
+//!
+//! Records which POUs call or instantiate which others, to order them
+//! callees first (for code generation, compile order or leaf-first
+//! documentation), to find call cycles and to find the POUs reachable from
+//! entry points.
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+/// Name of a POU in the call graph: a program, AOI, function or function
+/// block name.
+pub type PouId = String;
+
+/// POUs that call each other in a cycle, so they have no dependency order.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CycleError {
+    /// POUs of the cycle in call order; the last one calls the first
+    pub pous: Vec<PouId>,
+}
+
+impl fmt::Display for CycleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "call cycle: {}", self.pous.join(" -> "))?;
+        if let Some(first) = self.pous.first() {
+            write!(f, " -> {}", first)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for CycleError {}
+
+/// Calls between the POUs of a project.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CallGraph {
+    calls: BTreeMap<PouId, BTreeSet<PouId>>,
+}
+
+impl CallGraph {
+    /// Create an empty call graph.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a POU without calls.
+    pub fn add_pou(&mut self, pou: impl Into<PouId>) {
+        self.calls.entry(pou.into()).or_default();
+    }
+
+    /// Add a call from `caller` to `callee`, adding both POUs.
+    pub fn add_call(&mut self, caller: impl Into<PouId>, callee: impl Into<PouId>) {
+        let callee = callee.into();
+        self.add_pou(callee.clone());
+        self.calls.entry(caller.into()).or_default().insert(callee);
+    }
+
+    /// All POUs, by name.
+    pub fn pous(&self) -> impl Iterator<Item = &str> {
+        self.calls.keys().map(String::as_str)
+    }
+
+    /// POUs called by `pou`, by name.
+    pub fn callees(&self, pou: &str) -> impl Iterator<Item = &str> {
+        self.calls.get(pou).into_iter().flatten().map(String::as_str)
+    }
+
+    /// Whether any POU calls `pou`.
+    pub fn is_called(&self, pou: &str) -> bool {
+        self.calls.values().any(|callees| callees.contains(pou))
+    }
+
+    /// POUs reachable from `roots` through calls, the roots included.
+    ///
+    /// Roots that aren't POUs of the graph are ignored.
+    pub fn reachable_from<'a>(&self, roots: impl IntoIterator<Item = &'a str>) -> BTreeSet<&str> {
+        let mut reached = BTreeSet::new();
+        let mut pending: Vec<&str> = roots
+            .into_iter()
+            .filter_map(|root| self.calls.get_key_value(root).map(|(name, _)| name.as_str()))
+            .collect();
+        while let Some(pou) = pending.pop() {
+            if reached.insert(pou) {
+                pending.extend(self.callees(pou));
+            }
+        }
+        reached
+    }
+}
+
+/// Order the POUs of `graph` callees first.
+///
+/// POUs without a dependency between them keep their name order, so the
+/// result is stable. A POU that calls itself, directly or through others,
+/// makes the order impossible; the error names the POUs of the first cycle
+/// found.
+pub fn topo_order(graph: &CallGraph) -> Result<Vec<PouId>, CycleError> {
+    #[derive(Clone, Copy, PartialEq)]
+    enum State {
+        Visiting,
+        Done,
+    }
+
+    let mut state: BTreeMap<&str, State> = BTreeMap::new();
+    let mut order = Vec::new();
+    for root in graph.pous() {
+        if state.contains_key(root) {
+            continue;
+        }
+        // Depth-first walk with an explicit stack of (POU, remaining callees)
+        state.insert(root, State::Visiting);
+        let mut stack = vec![(root, graph.callees(root))];
+        while let Some((pou, callees)) = stack.last_mut() {
+            let pou = *pou;
+            match callees.next() {
+                Some(callee) => match state.get(callee) {
+                    None => {
+                        state.insert(callee, State::Visiting);
+                        stack.push((callee, graph.callees(callee)));
+                    }
+                    Some(State::Visiting) => {
+                        let start = stack.iter().position(|(p, _)| *p == callee).unwrap_or(0);
+                        return Err(CycleError {
+                            pous: stack[start..].iter().map(|(p, _)| p.to_string()).collect(),
+                        });
+                    }
+                    Some(State::Done) => {}
+                },
+                None => {
+                    state.insert(pou, State::Done);
+                    order.push(pou.to_string());
+                    stack.pop();
+                }
+            }
+        }
+    }
+    Ok(order)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_topo_order_callees_first() {
+        let mut graph = CallGraph::new();
+        graph.add_call("Main", "Conveyor");
+        graph.add_call("Main", "Alarm");
+        graph.add_call("Conveyor", "Motor");
+        graph.add_call("Alarm", "Motor");
+        graph.add_pou("Spare");
+
+        assert_eq!(
+            topo_order(&graph).unwrap(),
+            ["Motor", "Alarm", "Conveyor", "Main", "Spare"]
+        );
+    }
+
+    #[test]
+    fn test_reachable_from() {
+        let mut graph = CallGraph::new();
+        graph.add_call("Main", "Conveyor");
+        graph.add_call("Conveyor", "Motor");
+        graph.add_call("Old", "Valve");
+        graph.add_call("Valve", "Old");
+        graph.add_pou("Spare");
+
+        let reached: Vec<&str> = graph.reachable_from(["Main", "Missing"]).into_iter().collect();
+        assert_eq!(reached, ["Conveyor", "Main", "Motor"]);
+        assert!(graph.is_called("Valve"));
+        assert!(!graph.is_called("Spare"));
+    }
+
+    #[test]
+    fn test_topo_order_cycle() {
+        let mut graph = CallGraph::new();
+        graph.add_call("Main", "Fill");
+        graph.add_call("Fill", "Level");
+        graph.add_call("Level", "Fill");
+
+        let err = topo_order(&graph).unwrap_err();
+        assert_eq!(err.pous, ["Fill", "Level"]);
+        assert_eq!(err.to_string(), "call cycle: Fill -> Level -> Fill");
+
+        let mut graph = CallGraph::new();
+        graph.add_call("Retry", "Retry");
+        assert_eq!(topo_order(&graph).unwrap_err().pous, ["Retry"]);
+    }
+}
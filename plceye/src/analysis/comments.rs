@@ -0,0 +1,81 @@
+//! Comment density of ST source.
+//!
+//! Counts the non-whitespace characters inside comments against those of
+//! the code, using the comment spans of the `iecst` lexer. Nested `(* *)`
+//! comments count as one comment, and comment markers inside string
+//! literals are code.
+
+use std::ops::AddAssign;
+
+use serde::Serialize;
+
+/// Comment and code character counts of ST source.
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+pub struct CommentDensity {
+    /// Non-whitespace characters inside comments
+    pub comment_chars: usize,
+    /// Non-whitespace characters outside comments
+    pub code_chars: usize,
+    /// Number of comments
+    pub comments: usize,
+    /// Lines that are not blank
+    pub lines: usize,
+}
+
+impl CommentDensity {
+    /// Measure the comment density of ST source.
+    pub fn of(source: &str) -> Self {
+        let spans = iecst::comment_spans(source);
+        let comment_chars: usize = spans
+            .iter()
+            .map(|span| source[span.start..span.end].chars().filter(|c| !c.is_whitespace()).count())
+            .sum();
+        let total_chars = source.chars().filter(|c| !c.is_whitespace()).count();
+
+        Self {
+            comment_chars,
+            code_chars: total_chars - comment_chars,
+            comments: spans.len(),
+            lines: source.lines().filter(|line| !line.trim().is_empty()).count(),
+        }
+    }
+
+    /// Comment characters as a percentage of all non-whitespace characters.
+    pub fn percent(&self) -> f32 {
+        let total = self.comment_chars + self.code_chars;
+        if total == 0 {
+            0.0
+        } else {
+            self.comment_chars as f32 * 100.0 / total as f32
+        }
+    }
+}
+
+impl AddAssign for CommentDensity {
+    fn add_assign(&mut self, other: Self) {
+        self.comment_chars += other.comment_chars;
+        self.code_chars += other.code_chars;
+        self.comments += other.comments;
+        self.lines += other.lines;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_density() {
+        let density = CommentDensity::of("(* a (* b *) *)\nx := 1; // set\n\ns := '// no';");
+        assert_eq!(density.comments, 2);
+        assert_eq!(density.comment_chars, 15);
+        assert_eq!(density.code_chars, 15);
+        assert_eq!(density.lines, 3);
+        assert!((density.percent() - 50.0).abs() < 0.001);
+    }
+
+    #[test]
+    fn test_empty_source() {
+        assert_eq!(CommentDensity::of("").percent(), 0.0);
+    }
+}
@@ -0,0 +1,132 @@
+//! Cyclomatic complexity and nesting depth distributions of ST routines.
+//!
+//! Counts routines per bucket and keeps every routine's value, so the long
+//! tail can be listed by name.
+
+use serde::Serialize;
+
+/// Complexity buckets as (label, lowest, highest) with inclusive bounds.
+pub const COMPLEXITY_BUCKETS: [(&str, usize, usize); 4] =
+    [("1-5", 0, 5), ("6-10", 6, 10), ("11-20", 11, 20), ("21+", 21, usize::MAX)];
+
+/// Nesting depth buckets as (label, lowest, highest) with inclusive bounds.
+pub const NESTING_BUCKETS: [(&str, usize, usize); 4] =
+    [("0-2", 0, 2), ("3-4", 3, 4), ("5-6", 5, 6), ("7+", 7, usize::MAX)];
+
+/// Cyclomatic complexity of the parsed ST routines of a project.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ComplexityDistribution {
+    /// Routine counts per bucket of [`COMPLEXITY_BUCKETS`]
+    pub buckets: [usize; 4],
+    /// Routine names with their complexity, most complex first
+    pub routines: Vec<(String, usize)>,
+}
+
+impl ComplexityDistribution {
+    /// Add a routine with its complexity.
+    pub fn add(&mut self, name: impl Into<String>, complexity: usize) {
+        add_ranked(&COMPLEXITY_BUCKETS, &mut self.buckets, &mut self.routines, name.into(), complexity);
+    }
+
+    /// The `n` most complex routines.
+    pub fn top(&self, n: usize) -> &[(String, usize)] {
+        &self.routines[..n.min(self.routines.len())]
+    }
+
+    /// Highest complexity (0 without routines).
+    pub fn max(&self) -> usize {
+        self.routines.first().map_or(0, |(_, c)| *c)
+    }
+
+    /// Average complexity (0 without routines).
+    pub fn average(&self) -> f32 {
+        if self.routines.is_empty() {
+            0.0
+        } else {
+            self.routines.iter().map(|(_, c)| c).sum::<usize>() as f32 / self.routines.len() as f32
+        }
+    }
+}
+
+/// Maximum nesting depth of the parsed ST routines of a project.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct NestingDistribution {
+    /// Routine counts per bucket of [`NESTING_BUCKETS`]
+    pub buckets: [usize; 4],
+    /// Routine names with their nesting depth, most deeply nested first
+    pub routines: Vec<(String, usize)>,
+}
+
+impl NestingDistribution {
+    /// Add a routine with its maximum nesting depth.
+    pub fn add(&mut self, name: impl Into<String>, depth: usize) {
+        add_ranked(&NESTING_BUCKETS, &mut self.buckets, &mut self.routines, name.into(), depth);
+    }
+
+    /// The `n` most deeply nested routines.
+    pub fn top(&self, n: usize) -> &[(String, usize)] {
+        &self.routines[..n.min(self.routines.len())]
+    }
+
+    /// Deepest nesting (0 without routines).
+    pub fn max(&self) -> usize {
+        self.routines.first().map_or(0, |(_, d)| *d)
+    }
+
+    /// Average nesting depth (0 without routines).
+    pub fn average(&self) -> f32 {
+        if self.routines.is_empty() {
+            0.0
+        } else {
+            self.routines.iter().map(|(_, d)| d).sum::<usize>() as f32 / self.routines.len() as f32
+        }
+    }
+}
+
+/// Count a value in its bucket and insert the routine into the ranking.
+fn add_ranked(
+    bucket_bounds: &[(&str, usize, usize); 4],
+    buckets: &mut [usize; 4],
+    routines: &mut Vec<(String, usize)>,
+    name: String,
+    value: usize,
+) {
+    if let Some(index) = bucket_bounds.iter().position(|&(_, low, high)| (low..=high).contains(&value)) {
+        buckets[index] += 1;
+    }
+    // Keep the order stable: by value, then by name
+    let position = routines.partition_point(|(other, v)| *v > value || (*v == value && *other <= name));
+    routines.insert(position, (name, value));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_buckets_and_top() {
+        let mut distribution = ComplexityDistribution::default();
+        for (name, complexity) in [("A", 1), ("B", 7), ("C", 25), ("D", 12), ("E", 5), ("F", 12)] {
+            distribution.add(name, complexity);
+        }
+        assert_eq!(distribution.buckets, [2, 1, 2, 1]);
+        let top: Vec<_> = distribution.top(3).iter().map(|(n, c)| (n.as_str(), *c)).collect();
+        assert_eq!(top, [("C", 25), ("D", 12), ("F", 12)]);
+        assert_eq!(distribution.top(10).len(), 6);
+        assert_eq!(distribution.max(), 25);
+        assert_eq!(distribution.average(), 62.0 / 6.0);
+    }
+
+    #[test]
+    fn test_nesting_buckets_and_top() {
+        let mut distribution = NestingDistribution::default();
+        for (name, depth) in [("A", 0), ("B", 3), ("C", 8), ("D", 5), ("E", 2), ("F", 4)] {
+            distribution.add(name, depth);
+        }
+        assert_eq!(distribution.buckets, [2, 2, 1, 1]);
+        let top: Vec<_> = distribution.top(2).iter().map(|(n, d)| (n.as_str(), *d)).collect();
+        assert_eq!(top, [("C", 8), ("D", 5)]);
+        assert_eq!(distribution.max(), 8);
+        assert_eq!(distribution.average(), 22.0 / 6.0);
+    }
+}
@@ -0,0 +1,362 @@
+//! Data type definitions and references of a project.
+//!
+//! [`DataTypeIndex`] lists the user-defined data types of a project and
+//! every place a type is referenced by name: tags, AOI parameters and local
+//! tags, variables, structure members and the element types of arrays. The
+//! unused data type rule looks for definitions without references, the
+//! undefined data type rule for references without a definition.
+
+use std::collections::HashSet;
+
+use l5x::{Controller, UDIDefinitionContent};
+
+use super::portability::{is_standard_function_block, ELEMENTARY_TYPES};
+
+/// Predefined data types of Logix controllers, besides the elementary types.
+const LOGIX_TYPES: &[&str] = &[
+    "BIT", "TIMER", "COUNTER", "CONTROL", "MESSAGE", "PID", "ALARM", "CAM", "CAM_PROFILE", "CONNECTION_STATUS",
+    "COORDINATE_SYSTEM", "HMIBC", "OUTPUT_CAM", "OUTPUT_COMPENSATION", "PHASE", "PHASE_INSTRUCTION",
+    "SEQUENCE", "SERIAL_PORT_CONTROL", "SFC_ACTION", "SFC_STEP", "SFC_STOP", "DATALOG_INSTRUCTION",
+    // Process and drives function block instructions
+    "COORDINATED_CONTROL", "DEADTIME", "DERIVATIVE", "DISCRETE_2STATE", "DISCRETE_3STATE", "DOMINANT_RESET",
+    "DOMINANT_SET", "FILTER_HIGH_PASS", "FILTER_LOW_PASS", "FILTER_NOTCH", "FLIP_FLOP_D", "FLIP_FLOP_JK",
+    "FUNCTION_GENERATOR", "HL_LIMIT", "INTEGRATOR", "INTERNAL_MODEL_CONTROL", "LEAD_LAG", "LEAD_LAG_SEC_ORDER",
+    "MAIN_VALVE_CONTROL", "MAXIMUM_CAPTURE", "MINIMUM_CAPTURE", "MODULAR_MULTIVARIABLE_CONTROL",
+    "MOVING_AVERAGE", "MOVING_STD_DEV", "MULTIPLEXER", "PID_ENHANCED", "PIDE_AUTOTUNE", "POSITION_PROP",
+    "PROP_INT", "PULSE_MULTIPLIER", "RAMP_SOAK", "RATE_LIMITER", "S_CURVE", "SCALE", "SECOND_ORDER_CONTROLLER",
+    "SELECT", "SELECT_ENHANCED", "SELECTABLE_NEGATE", "SELECTED_SUMMER", "SPLIT_RANGE", "TOTALIZER",
+    "UP_DOWN_ACCUM",
+    // Safety instructions
+    "CONFIGURABLE_ROUT", "DCA_INPUT", "DCAF_INPUT", "DIVERSE_INPUT", "DUAL_CHANNEL_INPUT",
+    "EIGHT_POS_MODE_SELECTOR", "EMERGENCY_STOP", "ENABLE_PENDANT", "FIVE_POS_MODE_SELECTOR", "LIGHT_CURTAIN",
+    "REDUNDANT_INPUT", "REDUNDANT_OUTPUT", "SAFETY_MAT", "THREE_POS_MODE_SELECTOR", "TWO_HAND_RUN_STATION",
+];
+
+/// Prefixes of predefined Logix type families (`AXIS_CIP_DRIVE`, `FBD_TIMER`,
+/// `DCI_STOP_TEST`, `MUTING_TWO_SENSOR_ASYM`).
+const LOGIX_TYPE_PREFIXES: &[&str] = &["AXIS_", "FBD_", "MOTION_", "ALARM_", "ENERGY_", "DCI_", "MUTING_"];
+
+/// What references a data type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TypeReferenceKind {
+    /// Controller or program tag
+    Tag,
+    /// AOI parameter
+    Parameter,
+    /// AOI local tag, or POU or global variable
+    Variable,
+    /// Member of a structure type, or the base type of a derived type
+    Member,
+}
+
+impl TypeReferenceKind {
+    /// Noun for messages ("Tag", "Parameter", ...).
+    pub fn describe(&self) -> &'static str {
+        match self {
+            TypeReferenceKind::Tag => "Tag",
+            TypeReferenceKind::Parameter => "Parameter",
+            TypeReferenceKind::Variable => "Variable",
+            TypeReferenceKind::Member => "Member",
+        }
+    }
+}
+
+/// A reference to a data type by name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TypeReference {
+    /// Referenced type name, the element type for arrays
+    pub type_name: String,
+    /// Tag, variable or member (`Type.Member`) that references it
+    pub name: String,
+    /// What the referencing item is
+    pub kind: TypeReferenceKind,
+    /// Location for findings (e.g., "Program:Main", "DataTypes")
+    pub location: String,
+}
+
+/// The data types a project defines and the references to types by name.
+#[derive(Debug, Clone, Default)]
+pub struct DataTypeIndex {
+    /// User-defined data types, in declaration order
+    pub defined: Vec<String>,
+    /// Type references, in declaration order
+    pub references: Vec<TypeReference>,
+    /// Names that can be used as types: data types, AOIs or function blocks
+    type_names: Vec<String>,
+}
+
+impl DataTypeIndex {
+    /// Index the data types, AOIs and type references of an L5X controller.
+    pub fn from_controller(controller: &Controller) -> Self {
+        let mut index = DataTypeIndex::default();
+
+        if let Some(ref datatypes) = controller.data_types {
+            for dt in &datatypes.data_type {
+                index.defined.push(dt.name.clone());
+                for member in dt.members.iter().flat_map(|members| &members.member) {
+                    let name = format!("{}.{}", dt.name, member.name);
+                    index.add(&member.data_type, &name, TypeReferenceKind::Member, "DataTypes");
+                }
+            }
+        }
+        index.type_names = index.defined.clone();
+
+        if let Some(ref tags) = controller.tags {
+            for tag in &tags.tag {
+                if let Some(ref dt) = tag.data_type {
+                    index.add(dt, &tag.name, TypeReferenceKind::Tag, "Controller");
+                }
+            }
+        }
+
+        if let Some(ref programs) = controller.programs {
+            for program in &programs.program {
+                let location = format!("Program:{}", program.name);
+                for tag in program.tags.iter().flat_map(|tags| &tags.tag) {
+                    if let Some(ref dt) = tag.data_type {
+                        index.add(dt, &tag.name, TypeReferenceKind::Tag, &location);
+                    }
+                }
+            }
+        }
+
+        if let Some(ref aois) = controller.add_on_instruction_definitions {
+            for aoi in &aois.add_on_instruction_definition {
+                index.type_names.push(aoi.name.clone());
+                let location = format!("AOI:{}", aoi.name);
+                for content in &aoi.content {
+                    match content {
+                        UDIDefinitionContent::Parameters(params) => {
+                            for param in &params.parameter {
+                                if let Some(ref dt) = param.data_type {
+                                    index.add(dt, &param.name, TypeReferenceKind::Parameter, &location);
+                                }
+                            }
+                        }
+                        UDIDefinitionContent::LocalTags(local_tags) => {
+                            for tag in &local_tags.local_tag {
+                                index.add(&tag.data_type, &tag.name, TypeReferenceKind::Variable, &location);
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        index
+    }
+
+    /// Index the data types, POUs and type references of a PLCopen project.
+    pub fn from_plcopen(project: &plcopen::Project) -> Self {
+        let mut index = DataTypeIndex::default();
+        let types = project.types.as_ref();
+
+        if let Some(data_types) = types.and_then(|t| t.data_types.as_ref()) {
+            for data_type in &data_types.data_type {
+                index.defined.push(data_type.name.clone());
+                if let Some(ref base) = data_type.base_type {
+                    index.add_data(base, &data_type.name, TypeReferenceKind::Member, "dataTypes");
+                }
+            }
+        }
+        index.type_names = index.defined.clone();
+
+        if let Some(pous) = types.and_then(|t| t.pous.as_ref()) {
+            for pou in &pous.pou {
+                index.type_names.push(pou.name.clone());
+                let Some(ref interface) = pou.interface else {
+                    continue;
+                };
+                let vars = (interface.input_vars.iter().flat_map(|list| &list.variable))
+                    .chain(interface.output_vars.iter().flat_map(|list| &list.variable))
+                    .chain(interface.in_out_vars.iter().flat_map(|list| &list.variable))
+                    .chain(interface.local_vars.iter().flat_map(|list| &list.variable))
+                    .chain(interface.temp_vars.iter().flat_map(|list| &list.variable))
+                    .chain(interface.external_vars.iter().flat_map(|list| &list.variable))
+                    .chain(interface.global_vars.iter().flat_map(|list| &list.variable));
+                for var in vars {
+                    if let Some(ref data) = var.r#type {
+                        index.add_data(data, &var.name, TypeReferenceKind::Variable, &pou.name);
+                    }
+                }
+            }
+        }
+
+        let configurations = project
+            .instances
+            .as_ref()
+            .and_then(|i| i.configurations.as_ref())
+            .map(|c| c.configuration.as_slice())
+            .unwrap_or_default();
+        for configuration in configurations {
+            for var in configuration.global_vars.iter().flat_map(|list| &list.variable) {
+                if let Some(ref data) = var.r#type {
+                    index.add_data(data, &var.name, TypeReferenceKind::Variable, &configuration.name);
+                }
+            }
+            for resource in &configuration.resource {
+                let location = format!("{}.{}", configuration.name, resource.name);
+                for var in resource.global_vars.iter().flat_map(|list| &list.variable) {
+                    if let Some(ref data) = var.r#type {
+                        index.add_data(data, &var.name, TypeReferenceKind::Variable, &location);
+                    }
+                }
+            }
+        }
+
+        index
+    }
+
+    fn add(&mut self, type_name: &str, name: &str, kind: TypeReferenceKind, location: &str) {
+        self.references.push(TypeReference {
+            type_name: type_name.to_string(),
+            name: name.to_string(),
+            kind,
+            location: location.to_string(),
+        });
+    }
+
+    /// Add the type names a PLCopen type refers to: a derived type, the
+    /// element type of an array, the base type of an enum, subrange or
+    /// pointer, and the member types of a structure.
+    fn add_data(&mut self, data: &plcopen::Data, name: &str, kind: TypeReferenceKind, location: &str) {
+        if let Some(ref derived) = data.derived {
+            self.add(&derived.name, name, kind, location);
+        }
+        let base_types = [
+            data.array.as_ref().and_then(|a| a.base_type.as_deref()),
+            data.r#enum.as_ref().and_then(|e| e.base_type.as_deref()),
+            data.subrange_signed.as_ref().and_then(|s| s.base_type.as_deref()),
+            data.subrange_unsigned.as_ref().and_then(|s| s.base_type.as_deref()),
+            data.pointer.as_ref().and_then(|p| p.base_type.as_deref()),
+        ];
+        for base in base_types.into_iter().flatten() {
+            self.add_data(base, name, kind, location);
+        }
+        if let Some(ref members) = data.r#struct {
+            for member in &members.variable {
+                if let Some(ref data) = member.r#type {
+                    self.add_data(data, &format!("{}.{}", name, member.name), TypeReferenceKind::Member, location);
+                }
+            }
+        }
+    }
+
+    /// Whether any reference uses the type (case-insensitive).
+    pub fn is_used(&self, type_name: &str) -> bool {
+        self.references.iter().any(|r| r.type_name.eq_ignore_ascii_case(type_name))
+    }
+
+    /// Whether a type name is defined by the project or predefined:
+    /// elementary, standard function block, Logix predefined or
+    /// module-defined (`AB:1756_DI:I:0`) (case-insensitive).
+    pub fn is_defined(&self, type_name: &str) -> bool {
+        let upper = type_name.to_uppercase();
+        self.type_names.iter().any(|name| name.eq_ignore_ascii_case(type_name))
+            || ELEMENTARY_TYPES.contains(&upper.as_str())
+            || LOGIX_TYPES.contains(&upper.as_str())
+            || LOGIX_TYPE_PREFIXES.iter().any(|prefix| upper.starts_with(prefix))
+            || is_standard_function_block(&upper)
+            || upper.contains(':')
+    }
+
+    /// References to types that are not defined.
+    pub fn undefined(&self) -> impl Iterator<Item = &TypeReference> {
+        self.references.iter().filter(|r| !self.is_defined(&r.type_name))
+    }
+
+    /// The project type name closest to `type_name`, if it differs by at most
+    /// a quarter of its characters (at least one), ignoring case.
+    pub fn similar(&self, type_name: &str) -> Option<&str> {
+        let target = type_name.to_uppercase();
+        let limit = (target.chars().count() / 4).max(1);
+        let mut seen = HashSet::new();
+        self.type_names
+            .iter()
+            .filter(|name| seen.insert(name.to_uppercase()))
+            .map(|name| (edit_distance(&target, &name.to_uppercase()), name))
+            .filter(|(distance, _)| *distance <= limit)
+            .min_by_key(|(distance, _)| *distance)
+            .map(|(_, name)| name.as_str())
+    }
+}
+
+/// Levenshtein distance between two strings, by characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::LoadedProject;
+
+    #[test]
+    fn test_l5x_index() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+<Controller Name="Test">
+<DataTypes>
+<DataType Name="Motor_UDT" Family="NoFamily" Class="User"><Members>
+<Member Name="Speed" DataType="REAL" Dimension="0" Radix="Float" Hidden="false"/>
+<Member Name="Delay" DataType="TIMER" Dimension="0" Radix="NullType" Hidden="false"/>
+<Member Name="Valves" DataType="Valve_UDT" Dimension="4" Radix="NullType" Hidden="false"/>
+</Members></DataType>
+</DataTypes>
+<AddOnInstructionDefinitions>
+<AddOnInstructionDefinition Name="Ramp">
+<Parameters><Parameter Name="Target" TagType="Base" DataType="REAL" Usage="Input"/></Parameters>
+<LocalTags><LocalTag Name="State" DataType="Motor_UTD"/></LocalTags>
+</AddOnInstructionDefinition>
+</AddOnInstructionDefinitions>
+<Tags>
+<Tag Name="Motors" TagType="Base" DataType="motor_udt" Dimensions="10"/>
+<Tag Name="Ramp1" TagType="Base" DataType="Ramp"/>
+<Tag Name="Axis1" TagType="Base" DataType="AXIS_CIP_DRIVE"/>
+</Tags>
+</Controller>
+</RSLogix5000Content>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let index = DataTypeIndex::from_controller(project.l5x_controller.as_ref().unwrap());
+
+        assert_eq!(index.defined, ["Motor_UDT"]);
+        assert!(index.is_used("MOTOR_UDT"));
+        let undefined: Vec<_> = index.undefined().map(|r| (r.name.as_str(), r.type_name.as_str())).collect();
+        assert_eq!(undefined, [("Motor_UDT.Valves", "Valve_UDT"), ("State", "Motor_UTD")]);
+        assert_eq!(index.similar("Motor_UTD"), Some("Motor_UDT"));
+        assert_eq!(index.similar("Valve_UDT"), None);
+    }
+
+    #[test]
+    fn test_predefined_types() {
+        let index = DataTypeIndex::default();
+        for name in [
+            "PID_ENHANCED", "TOTALIZER", "Scale", "RAMP_SOAK", "LEAD_LAG", "SELECT_ENHANCED", "FILTER_LOW_PASS",
+            "TIMER", "FBD_TIMER", "DINT", "TON", "EMERGENCY_STOP", "DCI_STOP_TEST", "AB:1756_DI:I:0",
+        ] {
+            assert!(index.is_defined(name), "{}", name);
+        }
+        for name in ["PIDE", "TOT", "SCL", "ADD", "INT_TO_REAL"] {
+            assert!(!index.is_defined(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("MOTOR", "MOTOR"), 0);
+        assert_eq!(edit_distance("MOTOR_UTD", "MOTOR_UDT"), 2);
+        assert_eq!(edit_distance("VALVE", "VALVES"), 1);
+        assert_eq!(edit_distance("", "AB"), 2);
+    }
+}
@@ -0,0 +1,322 @@
+//! Structurally duplicated routines.
+//!
+//! Each routine is reduced to a sequence of normalized units: one per RLL
+//! rung, or one per ST statement (split at `;`). Tag and variable names are
+//! replaced by their order of first use in the routine (`#0`, `#1`, ...),
+//! so a copy that only renames tags stays identical. Comments and
+//! whitespace are dropped; keywords, literals, operators and instructions
+//! are kept.
+//!
+//! Routines are compared by the longest common subsequence of their units;
+//! the similarity of two routines is `2 * common / (len_a + len_b)`.
+
+use std::collections::HashMap;
+
+use l5x::rll::{Operand, RungElement};
+
+use super::{LocatedRung, ParsedSTRoutine, ProjectAnalysis};
+
+/// ST words kept as they are; other identifiers are abstracted.
+const ST_KEYWORDS: &[&str] = &[
+    "IF", "THEN", "ELSIF", "ELSE", "END_IF", "CASE", "OF", "END_CASE", "FOR", "TO", "BY", "DO", "END_FOR",
+    "WHILE", "END_WHILE", "REPEAT", "UNTIL", "END_REPEAT", "RETURN", "EXIT", "AND", "OR", "XOR", "NOT",
+    "MOD", "TRUE", "FALSE",
+];
+
+/// A routine reduced to its normalized logic.
+#[derive(Debug, Clone)]
+pub struct RoutineShape {
+    /// Routine path (e.g., "MainProgram/Conveyor1")
+    pub path: String,
+    /// Normalized rungs or statements
+    pub units: Vec<String>,
+}
+
+/// A group of routines with the same or nearly the same logic.
+#[derive(Debug, Clone)]
+pub struct DuplicateGroup {
+    /// Routine paths, in project order
+    pub paths: Vec<String>,
+    /// Lowest similarity between linked routines (1.0 if identical)
+    pub similarity: f64,
+    /// Number of units of the first routine
+    pub size: usize,
+}
+
+/// Normalized logic of the routines of a project.
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateLogic {
+    /// RLL and ST routines
+    pub routines: Vec<RoutineShape>,
+}
+
+impl DuplicateLogic {
+    /// Collect the RLL and ST routines of an analyzed L5X controller.
+    pub fn from_controller(analysis: &ProjectAnalysis) -> Self {
+        let mut logic = Self::default();
+
+        let mut rll: Vec<(String, Vec<&LocatedRung>)> = Vec::new();
+        for rung in &analysis.rungs {
+            let path = format!("{}/{}", rung.location.program, rung.location.routine);
+            match rll.last_mut() {
+                Some((last, rungs)) if *last == path => rungs.push(rung),
+                _ => rll.push((path, vec![rung])),
+            }
+        }
+        for (path, rungs) in rll {
+            let mut names = HashMap::new();
+            let units = rungs
+                .iter()
+                .filter_map(|rung| rung.parsed.content.as_ref())
+                .map(|content| {
+                    let mut tokens = Vec::new();
+                    normalize_elements(&content.elements, &mut names, &mut tokens);
+                    tokens.concat()
+                })
+                .collect();
+            logic.routines.push(RoutineShape { path, units });
+        }
+
+        logic.add_st(&analysis.st_routines);
+        logic
+    }
+
+    /// Collect the ST bodies of a PLCopen project.
+    pub fn from_plcopen(st_routines: &[ParsedSTRoutine]) -> Self {
+        let mut logic = Self::default();
+        logic.add_st(st_routines);
+        logic
+    }
+
+    fn add_st(&mut self, st_routines: &[ParsedSTRoutine]) {
+        for routine in st_routines {
+            let location = &routine.location;
+            let path = if location.program == location.routine {
+                location.routine.clone()
+            } else {
+                format!("{}/{}", location.program, location.routine)
+            };
+            self.routines.push(RoutineShape {
+                path,
+                units: normalize_st(&routine.source),
+            });
+        }
+    }
+
+    /// Group routines of at least `min_size` units whose similarity is at
+    /// least `min_similarity` (0.0 to 1.0).
+    pub fn groups(&self, min_size: usize, min_similarity: f64) -> Vec<DuplicateGroup> {
+        let candidates: Vec<&RoutineShape> = self
+            .routines
+            .iter()
+            .filter(|r| r.units.len() >= min_size.max(1))
+            .collect();
+
+        // Union-find over linked pairs, remembering the weakest link of each group
+        let mut parent: Vec<usize> = (0..candidates.len()).collect();
+        let mut weakest = vec![1.0f64; candidates.len()];
+        fn root(parent: &mut [usize], mut i: usize) -> usize {
+            while parent[i] != i {
+                parent[i] = parent[parent[i]];
+                i = parent[i];
+            }
+            i
+        }
+
+        for i in 0..candidates.len() {
+            for j in i + 1..candidates.len() {
+                let (a, b) = (&candidates[i].units, &candidates[j].units);
+                // Similarity can't exceed 2 * shorter / (shorter + longer)
+                let (short, long) = (a.len().min(b.len()), a.len().max(b.len()));
+                if (2 * short) as f64 / ((short + long) as f64) < min_similarity {
+                    continue;
+                }
+                let similarity = similarity(a, b);
+                if similarity >= min_similarity {
+                    let (ri, rj) = (root(&mut parent, i), root(&mut parent, j));
+                    let link = similarity.min(weakest[ri]).min(weakest[rj]);
+                    parent[rj] = ri;
+                    weakest[ri] = link;
+                }
+            }
+        }
+
+        let mut groups: Vec<(usize, DuplicateGroup)> = Vec::new();
+        for (i, routine) in candidates.iter().enumerate() {
+            let r = root(&mut parent, i);
+            match groups.iter_mut().find(|(root, _)| *root == r) {
+                Some((_, group)) => group.paths.push(routine.path.clone()),
+                None => groups.push((
+                    r,
+                    DuplicateGroup {
+                        paths: vec![routine.path.clone()],
+                        similarity: weakest[r],
+                        size: routine.units.len(),
+                    },
+                )),
+            }
+        }
+        groups
+            .into_iter()
+            .map(|(_, group)| group)
+            .filter(|group| group.paths.len() > 1)
+            .collect()
+    }
+}
+
+/// Similarity of two unit sequences from their longest common subsequence.
+fn similarity(a: &[String], b: &[String]) -> f64 {
+    if a == b {
+        return 1.0;
+    }
+    let mut row = vec![0usize; b.len() + 1];
+    for unit in a {
+        let mut diagonal = 0;
+        for (j, other) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if unit == other { diagonal + 1 } else { above.max(row[j]) };
+            diagonal = above;
+        }
+    }
+    (2 * row[b.len()]) as f64 / (a.len() + b.len()) as f64
+}
+
+/// Abstract a name to its order of first use.
+fn abstract_name(name: &str, names: &mut HashMap<String, usize>) -> String {
+    let next = names.len();
+    format!("#{}", names.entry(name.to_uppercase()).or_insert(next))
+}
+
+/// Normalize RLL elements, abstracting the base tag of each operand.
+fn normalize_elements(elements: &[RungElement], names: &mut HashMap<String, usize>, tokens: &mut Vec<String>) {
+    for element in elements {
+        match element {
+            RungElement::Instruction(instruction) => {
+                let operands: Vec<String> = instruction
+                    .operands
+                    .iter()
+                    .map(|operand| match operand {
+                        Operand::Value(value) if value.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') => {
+                            let end = value.find(['.', '[']).unwrap_or(value.len());
+                            format!("{}{}", abstract_name(&value[..end], names), &value[end..])
+                        }
+                        Operand::Value(value) => value.clone(),
+                        _ => "?".to_string(),
+                    })
+                    .collect();
+                tokens.push(format!("{}({})", instruction.mnemonic.to_uppercase(), operands.join(",")));
+            }
+            RungElement::Parallel(branches) => {
+                tokens.push("[".to_string());
+                for (index, branch) in branches.iter().enumerate() {
+                    if index > 0 {
+                        tokens.push(",".to_string());
+                    }
+                    normalize_elements(&branch.elements, names, tokens);
+                }
+                tokens.push("]".to_string());
+            }
+        }
+    }
+}
+
+/// Normalize ST source into one unit per statement.
+fn normalize_st(source: &str) -> Vec<String> {
+    let mut code: Vec<char> = source.chars().collect();
+    for span in iecst::comment_spans(source) {
+        let start = source[..span.start].chars().count();
+        let end = start + source[span.start..span.end].chars().count();
+        code[start..end].fill(' ');
+    }
+
+    let mut names = HashMap::new();
+    let mut units = Vec::new();
+    let mut unit = String::new();
+    let mut i = 0;
+    while i < code.len() {
+        let c = code[i];
+        if c.is_whitespace() {
+            i += 1;
+        } else if c.is_ascii_alphabetic() || c == '_' {
+            let start = i;
+            while i < code.len() && (code[i].is_ascii_alphanumeric() || code[i] == '_') {
+                i += 1;
+            }
+            let word: String = code[start..i].iter().collect::<String>().to_uppercase();
+            // Typed literals (T#5s, INT#3) keep their prefix
+            if ST_KEYWORDS.contains(&word.as_str()) || code.get(i) == Some(&'#') {
+                unit.push_str(&word);
+            } else {
+                unit.push_str(&abstract_name(&word, &mut names));
+            }
+            unit.push(' ');
+        } else if c == '\'' || c == '"' {
+            i += 1;
+            while i < code.len() && code[i] != c {
+                i += if code[i] == '$' { 2 } else { 1 };
+            }
+            i += 1;
+            unit.push_str("'' ");
+        } else if c == ';' {
+            i += 1;
+            if !unit.is_empty() {
+                units.push(std::mem::take(&mut unit));
+            }
+        } else {
+            unit.push(c);
+            i += 1;
+            if !code.get(i).is_some_and(|next| "=<>*".contains(*next)) {
+                unit.push(' ');
+            }
+        }
+    }
+    if !unit.trim().is_empty() {
+        units.push(unit);
+    }
+    units
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shape(path: &str, source: &str) -> RoutineShape {
+        RoutineShape {
+            path: path.to_string(),
+            units: normalize_st(source),
+        }
+    }
+
+    #[test]
+    fn test_normalize_st_abstracts_names() {
+        let a = normalize_st("(* conveyor 1 *)\nIF Run1 AND NOT Jam1 THEN Motor1 := TRUE; END_IF;");
+        let b = normalize_st("IF run2 AND NOT jam2 THEN\n    // conveyor 2\n    motor2 := TRUE;\nEND_IF;");
+        assert_eq!(a, b);
+        assert_eq!(a[0], "IF #0 AND NOT #1 THEN #2 := TRUE ");
+        assert_ne!(a, normalize_st("IF Run1 OR NOT Jam1 THEN Motor1 := TRUE; END_IF;"));
+    }
+
+    #[test]
+    fn test_groups() {
+        let body = "A := B + 1; C := D * 2; IF A > C THEN E := TRUE; END_IF; F := A;";
+        let logic = DuplicateLogic {
+            routines: vec![
+                shape("Main/Line1", body),
+                shape("Main/Other", "X := 1; Y := 2; Z := 3; W := 4; V := 5;"),
+                shape("Main/Line2", &body.replace('A', "Q")),
+                shape("Main/Line3", &format!("{} G := 0;", body)),
+                shape("Main/Short", "A := 1;"),
+                shape("Main/Short2", "B := 1;"),
+            ],
+        };
+
+        let groups = logic.groups(3, 1.0);
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].paths, ["Main/Line1", "Main/Line2"]);
+        assert_eq!(groups[0].similarity, 1.0);
+
+        let groups = logic.groups(3, 0.8);
+        assert_eq!(groups[0].paths, ["Main/Line1", "Main/Line2", "Main/Line3"]);
+        assert!(groups[0].similarity < 1.0);
+    }
+}
@@ -0,0 +1,339 @@
+//! Wiring of FBD networks.
+//!
+//! Elements of a PLCopen FBD body are connected through `connectionPointIn`
+//! references to the `localId` of the element (and output pin) feeding
+//! them; a connector and the continuations of the same name join two
+//! parts of a network. Wiring mistakes show up as:
+//!
+//! - required block inputs with no connection: all inputs of a function,
+//!   the VAR_IN_OUT pins of any block, and the inputs of the standard
+//!   function blocks that have no meaningful default (`TON.IN`, `CTU.PV`, ...)
+//! - function outputs that feed nothing, so the result is lost (function
+//!   block outputs stay readable through the instance and are not checked)
+//! - parts of the network that reach no output variable, function block
+//!   instance, jump or return
+
+use std::collections::{HashMap, HashSet};
+
+use plcopen::{Body_FBD_Inline, Project};
+
+/// Inputs of standard function blocks that must be connected.
+const REQUIRED_FB_INPUTS: &[(&str, &[&str])] = &[
+    ("TON", &["IN", "PT"]),
+    ("TOF", &["IN", "PT"]),
+    ("TP", &["IN", "PT"]),
+    ("CTU", &["CU", "PV"]),
+    ("CTD", &["CD", "PV"]),
+    ("CTUD", &["PV"]),
+    ("R_TRIG", &["CLK"]),
+    ("F_TRIG", &["CLK"]),
+    ("SR", &["S1", "R"]),
+    ("RS", &["S", "R1"]),
+];
+
+/// Kind of wiring problem in an FBD network.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FbdIssueKind {
+    /// A required input pin has no connection
+    UnconnectedInput,
+    /// A function output pin feeds nothing
+    FloatingOutput,
+    /// Part of the network reaches no output, instance, jump or return
+    Disconnected,
+}
+
+/// A wiring problem in an FBD network.
+#[derive(Debug, Clone)]
+pub struct FbdIssue {
+    /// Kind of problem
+    pub kind: FbdIssueKind,
+    /// Block instance (`Timer1`) or function (`ADD #3`), or the elements of a disconnected part
+    pub element: String,
+    /// Pin name, for unconnected inputs and floating outputs
+    pub pin: Option<String>,
+}
+
+/// Wiring problems of one FBD body.
+#[derive(Debug, Clone)]
+pub struct FbdNetwork {
+    /// POU name
+    pub pou: String,
+    /// Problems found: disconnected parts first, then block pins
+    pub issues: Vec<FbdIssue>,
+}
+
+impl FbdNetwork {
+    /// Check the wiring of an FBD body.
+    pub fn check(pou: &str, fbd: &Body_FBD_Inline) -> Self {
+        let mut elements: Vec<(String, String)> = Vec::new();
+        let mut sinks: HashSet<String> = HashSet::new();
+        let mut links: Vec<(String, String)> = Vec::new();
+        // (localId, formal parameter) of every output referenced by a connection
+        let mut fed: HashSet<(String, Option<String>)> = HashSet::new();
+        let mut connect = |id: &str, point: Option<&plcopen::ConnectionPointIn>, links: &mut Vec<(String, String)>| {
+            for connection in point.map(|p| p.connection.as_slice()).unwrap_or_default() {
+                links.push((id.to_string(), connection.ref_local_id.clone()));
+                fed.insert((connection.ref_local_id.clone(), connection.formal_parameter.clone()));
+            }
+        };
+
+        for block in &fbd.block {
+            elements.push((block.local_id.clone(), block_name(block)));
+            if block.instance_name.as_deref().is_some_and(|n| !n.trim().is_empty()) {
+                sinks.insert(block.local_id.clone());
+            }
+            for pin in block.input_variables.iter().flat_map(|v| &v.variable) {
+                connect(&block.local_id, pin.connection_point_in.as_ref(), &mut links);
+            }
+            for pin in block.in_out_variables.iter().flat_map(|v| &v.variable) {
+                connect(&block.local_id, pin.connection_point_in.as_ref(), &mut links);
+            }
+        }
+        for variable in &fbd.in_variable {
+            elements.push((variable.local_id.clone(), expression(variable.expression.as_deref())));
+        }
+        for variable in &fbd.out_variable {
+            elements.push((variable.local_id.clone(), expression(variable.expression.as_deref())));
+            sinks.insert(variable.local_id.clone());
+            connect(&variable.local_id, variable.connection_point_in.as_ref(), &mut links);
+        }
+        for variable in &fbd.in_out_variable {
+            elements.push((variable.local_id.clone(), expression(variable.expression.as_deref())));
+            sinks.insert(variable.local_id.clone());
+            connect(&variable.local_id, variable.connection_point_in.as_ref(), &mut links);
+        }
+        for jump in &fbd.jump {
+            elements.push((jump.local_id.clone(), format!("JMP {}", jump.label)));
+            sinks.insert(jump.local_id.clone());
+            connect(&jump.local_id, jump.connection_point_in.as_ref(), &mut links);
+        }
+        for ret in &fbd.r#return {
+            elements.push((ret.local_id.clone(), "RETURN".to_string()));
+            sinks.insert(ret.local_id.clone());
+            connect(&ret.local_id, ret.connection_point_in.as_ref(), &mut links);
+        }
+        for connector in &fbd.connector {
+            elements.push((connector.local_id.clone(), connector.name.clone()));
+            connect(&connector.local_id, connector.connection_point_in.as_ref(), &mut links);
+            for continuation in fbd.continuation.iter().filter(|c| c.name.eq_ignore_ascii_case(&connector.name)) {
+                links.push((connector.local_id.clone(), continuation.local_id.clone()));
+            }
+        }
+        for continuation in &fbd.continuation {
+            elements.push((continuation.local_id.clone(), continuation.name.clone()));
+        }
+
+        elements.sort_by_key(|(id, _)| (id.trim().parse::<u64>().unwrap_or(u64::MAX), id.clone()));
+
+        // Connected parts of the network
+        let mut parent: HashMap<&str, &str> = elements.iter().map(|(id, _)| (id.as_str(), id.as_str())).collect();
+        fn root<'a>(parent: &HashMap<&'a str, &'a str>, mut id: &'a str) -> &'a str {
+            while let Some(&next) = parent.get(id).filter(|&&next| next != id) {
+                id = next;
+            }
+            id
+        }
+        for (a, b) in &links {
+            if parent.contains_key(b.as_str()) {
+                let (ra, rb) = (root(&parent, a), root(&parent, b));
+                parent.insert(ra, rb);
+            }
+        }
+        let live: HashSet<&str> = sinks.iter().map(|id| root(&parent, id)).collect();
+
+        let mut issues = Vec::new();
+        let mut dead: Vec<(&str, Vec<&str>)> = Vec::new();
+        for (id, name) in &elements {
+            let r = root(&parent, id);
+            if live.contains(r) {
+                continue;
+            }
+            match dead.iter_mut().find(|(other, _)| *other == r) {
+                Some((_, names)) => names.push(name),
+                None => dead.push((r, vec![name])),
+            }
+        }
+        for (_, names) in &dead {
+            issues.push(FbdIssue {
+                kind: FbdIssueKind::Disconnected,
+                element: names.join(", "),
+                pin: None,
+            });
+        }
+
+        for block in &fbd.block {
+            if !live.contains(root(&parent, &block.local_id)) {
+                continue;
+            }
+            let name = block_name(block);
+            let is_function = block.instance_name.as_deref().map_or(true, |n| n.trim().is_empty());
+            let required = REQUIRED_FB_INPUTS
+                .iter()
+                .find(|(fb, _)| fb.eq_ignore_ascii_case(block.type_name.trim()))
+                .map_or(&[][..], |(_, pins)| *pins);
+
+            let inputs = block.input_variables.iter().flat_map(|v| &v.variable).filter(|pin| {
+                is_function || required.iter().any(|r| r.eq_ignore_ascii_case(&pin.formal_parameter))
+            });
+            let in_outs = block.in_out_variables.iter().flat_map(|v| &v.variable);
+            let unconnected = inputs
+                .filter(|pin| !is_hidden(pin.hidden.as_deref()) && !is_connected(pin.connection_point_in.as_ref()))
+                .map(|pin| &pin.formal_parameter)
+                .chain(
+                    in_outs
+                        .filter(|pin| !is_connected(pin.connection_point_in.as_ref()))
+                        .map(|pin| &pin.formal_parameter),
+                );
+            for pin in unconnected {
+                issues.push(FbdIssue {
+                    kind: FbdIssueKind::UnconnectedInput,
+                    element: name.clone(),
+                    pin: Some(pin.clone()),
+                });
+            }
+
+            if is_function {
+                let outputs: Vec<_> = block.output_variables.iter().flat_map(|v| &v.variable).collect();
+                for (index, pin) in outputs.iter().enumerate() {
+                    let id = block.local_id.clone();
+                    let used = fed.contains(&(id.clone(), Some(pin.formal_parameter.clone())))
+                        || (index == 0 && fed.contains(&(id, None)));
+                    if !used && !is_hidden(pin.hidden.as_deref()) {
+                        issues.push(FbdIssue {
+                            kind: FbdIssueKind::FloatingOutput,
+                            element: name.clone(),
+                            pin: Some(pin.formal_parameter.clone()),
+                        });
+                    }
+                }
+            }
+        }
+
+        Self {
+            pou: pou.to_string(),
+            issues,
+        }
+    }
+}
+
+/// Check the wiring of the FBD bodies of a PLCopen project.
+pub fn fbd_networks_from_plcopen(project: &Project) -> Vec<FbdNetwork> {
+    let Some(pous) = project.types.as_ref().and_then(|t| t.pous.as_ref()) else {
+        return Vec::new();
+    };
+
+    pous.pou
+        .iter()
+        .flat_map(|pou| {
+            pou.body
+                .iter()
+                .filter_map(|body| body.fbd.as_ref())
+                .map(|fbd| FbdNetwork::check(&pou.name, fbd))
+        })
+        .collect()
+}
+
+/// Instance name of a function block, or type name and local ID of a function.
+fn block_name(block: &plcopen::FbdObjects_block_Inline) -> String {
+    match block.instance_name.as_deref().map(str::trim) {
+        Some(name) if !name.is_empty() => name.to_string(),
+        _ => format!("{} #{}", block.type_name.trim(), block.local_id),
+    }
+}
+
+fn expression(expression: Option<&str>) -> String {
+    expression.map(str::trim).unwrap_or_default().to_string()
+}
+
+fn is_connected(point: Option<&plcopen::ConnectionPointIn>) -> bool {
+    point.is_some_and(|p| !p.connection.is_empty() || p.expression.as_deref().is_some_and(|e| !e.trim().is_empty()))
+}
+
+fn is_hidden(hidden: Option<&str>) -> bool {
+    hidden.is_some_and(|h| h.trim().eq_ignore_ascii_case("true"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::LoadedProject;
+
+    #[test]
+    fn test_fbd_wiring() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="Test" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Main" pouType="program">
+                        <body>
+                            <FBD>
+                                <inVariable localId="1"><connectionPointOut/><expression>A</expression></inVariable>
+                                <inVariable localId="2"><connectionPointOut/><expression>B</expression></inVariable>
+                                <block localId="3" typeName="ADD">
+                                    <inputVariables>
+                                        <variable formalParameter="IN1"><connectionPointIn><connection refLocalId="1"/></connectionPointIn></variable>
+                                        <variable formalParameter="IN2"><connectionPointIn/></variable>
+                                    </inputVariables>
+                                    <inOutVariables/>
+                                    <outputVariables><variable formalParameter="OUT"><connectionPointOut/></variable></outputVariables>
+                                </block>
+                                <outVariable localId="4"><connectionPointIn><connection refLocalId="3" formalParameter="OUT"/></connectionPointIn><expression>C</expression></outVariable>
+                                <block localId="5" typeName="TON" instanceName="Timer1">
+                                    <inputVariables>
+                                        <variable formalParameter="IN"><connectionPointIn><connection refLocalId="2"/></connectionPointIn></variable>
+                                        <variable formalParameter="PT"><connectionPointIn/></variable>
+                                    </inputVariables>
+                                    <inOutVariables/>
+                                    <outputVariables>
+                                        <variable formalParameter="Q"><connectionPointOut/></variable>
+                                        <variable formalParameter="ET"><connectionPointOut/></variable>
+                                    </outputVariables>
+                                </block>
+                                <block localId="6" typeName="AND">
+                                    <inputVariables>
+                                        <variable formalParameter="IN1"><connectionPointIn><connection refLocalId="1"/></connectionPointIn></variable>
+                                        <variable formalParameter="IN2"><connectionPointIn><connection refLocalId="2"/></connectionPointIn></variable>
+                                    </inputVariables>
+                                    <inOutVariables/>
+                                    <outputVariables><variable formalParameter="OUT"><connectionPointOut/></variable></outputVariables>
+                                </block>
+                                <inVariable localId="7"><connectionPointOut/><expression>D</expression></inVariable>
+                                <inVariable localId="8"><connectionPointOut/><expression>E</expression></inVariable>
+                                <block localId="9" typeName="NOT">
+                                    <inputVariables>
+                                        <variable formalParameter="IN"><connectionPointIn><connection refLocalId="8"/></connectionPointIn></variable>
+                                    </inputVariables>
+                                    <inOutVariables/>
+                                    <outputVariables><variable formalParameter="OUT"><connectionPointOut/></variable></outputVariables>
+                                </block>
+                            </FBD>
+                        </body>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        let project = LoadedProject::from_str(xml, None).unwrap();
+        let networks = fbd_networks_from_plcopen(project.plcopen_project.as_ref().unwrap());
+        assert_eq!(networks.len(), 1);
+        assert_eq!(networks[0].pou, "Main");
+
+        let issues: Vec<_> = networks[0]
+            .issues
+            .iter()
+            .map(|i| (i.kind, i.element.as_str(), i.pin.as_deref()))
+            .collect();
+        assert_eq!(
+            issues,
+            [
+                (FbdIssueKind::Disconnected, "D", None),
+                (FbdIssueKind::Disconnected, "E, NOT #9", None),
+                (FbdIssueKind::UnconnectedInput, "ADD #3", Some("IN2")),
+                (FbdIssueKind::UnconnectedInput, "Timer1", Some("PT")),
+                (FbdIssueKind::FloatingOutput, "AND #6", Some("OUT")),
+            ]
+        );
+    }
+}
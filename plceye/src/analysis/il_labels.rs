@@ -0,0 +1,167 @@
+//! Jump labels of IL bodies.
+//!
+//! An IL label is an identifier followed by `:` at the start of a line,
+//! either alone or in front of an instruction (`Loop: LD Count`). The
+//! `JMP`, `JMPC` and `JMPCN` instructions take a label as their operand.
+//! Labels are local to the POU body. Comments are blanked first, keeping
+//! line numbers intact.
+
+use plcopen::Project;
+
+/// A label defined in an IL body.
+#[derive(Debug, Clone)]
+pub struct IlLabel {
+    /// Label name
+    pub name: String,
+    /// Line number (1-based)
+    pub line: usize,
+}
+
+/// A jump instruction in an IL body.
+#[derive(Debug, Clone)]
+pub struct IlJump {
+    /// Jump instruction (`JMP`, `JMPC` or `JMPCN`)
+    pub instruction: String,
+    /// Target label name
+    pub target: String,
+    /// Line number (1-based)
+    pub line: usize,
+}
+
+/// Labels and jumps of one IL body.
+#[derive(Debug, Clone)]
+pub struct IlLabels {
+    /// POU name
+    pub pou: String,
+    /// Defined labels
+    pub labels: Vec<IlLabel>,
+    /// Jump instructions
+    pub jumps: Vec<IlJump>,
+}
+
+impl IlLabels {
+    /// Collect the labels and jumps of IL source.
+    pub fn scan(pou: &str, source: &str) -> Self {
+        let mut code = source.to_string();
+        for span in iecst::comment_spans(source) {
+            let blank: String = source[span.start..span.end]
+                .chars()
+                .map(|c| if c == '\n' { "\n".to_string() } else { " ".repeat(c.len_utf8()) })
+                .collect();
+            code.replace_range(span.start..span.end, &blank);
+        }
+
+        let mut labels = Vec::new();
+        let mut jumps = Vec::new();
+        for (index, line) in code.lines().enumerate() {
+            let mut rest = line.trim();
+            if let Some((label, after)) = rest.split_once(':') {
+                let label = label.trim();
+                if is_label_name(label) && !after.starts_with('=') {
+                    labels.push(IlLabel {
+                        name: label.to_string(),
+                        line: index + 1,
+                    });
+                    rest = after.trim();
+                }
+            }
+
+            let mut parts = rest.split_whitespace();
+            let (Some(instruction), Some(target)) = (parts.next(), parts.next()) else {
+                continue;
+            };
+            let instruction = instruction.to_uppercase();
+            if matches!(instruction.as_str(), "JMP" | "JMPC" | "JMPCN") {
+                jumps.push(IlJump {
+                    instruction,
+                    target: target.to_string(),
+                    line: index + 1,
+                });
+            }
+        }
+
+        Self {
+            pou: pou.to_string(),
+            labels,
+            jumps,
+        }
+    }
+
+    /// Get the jumps whose target label is not defined in the body.
+    pub fn undefined_jumps(&self) -> Vec<&IlJump> {
+        self.jumps
+            .iter()
+            .filter(|jump| !self.labels.iter().any(|l| l.name.eq_ignore_ascii_case(&jump.target)))
+            .collect()
+    }
+
+    /// Get the labels that no jump targets.
+    pub fn unused_labels(&self) -> Vec<&IlLabel> {
+        self.labels
+            .iter()
+            .filter(|label| !self.jumps.iter().any(|j| j.target.eq_ignore_ascii_case(&label.name)))
+            .collect()
+    }
+}
+
+/// Collect the labels and jumps of the IL bodies of a PLCopen project.
+pub fn il_labels_from_plcopen(project: &Project) -> Vec<IlLabels> {
+    let Some(pous) = project.types.as_ref().and_then(|t| t.pous.as_ref()) else {
+        return Vec::new();
+    };
+
+    pous.pou
+        .iter()
+        .flat_map(|pou| {
+            pou.body
+                .iter()
+                .filter_map(|body| body.il.as_ref().and_then(|il| il.text.as_deref()))
+                .map(|source| IlLabels::scan(&pou.name, source))
+        })
+        .collect()
+}
+
+/// Check that a word is a plain identifier usable as a label.
+fn is_label_name(word: &str) -> bool {
+    let mut chars = word.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "\
+    LD Count
+    GT 10
+    JMPC Done (* skip the reset *)
+(* Spare: JMP Nowhere
+   still a comment *)
+Loop: LD Count
+    ADD 1
+    ST Count
+    JMPCN Missing
+Done:
+    RET
+Spare: LD 0
+";
+
+    #[test]
+    fn test_scan() {
+        let labels = IlLabels::scan("Counter", SOURCE);
+        let names: Vec<_> = labels.labels.iter().map(|l| (l.name.as_str(), l.line)).collect();
+        assert_eq!(names, [("Loop", 6), ("Done", 10), ("Spare", 12)]);
+        assert_eq!(labels.jumps.len(), 2);
+        assert_eq!(labels.jumps[0].instruction, "JMPC");
+    }
+
+    #[test]
+    fn test_undefined_and_unused() {
+        let labels = IlLabels::scan("Counter", SOURCE);
+        let undefined: Vec<_> = labels.undefined_jumps().iter().map(|j| (j.target.as_str(), j.line)).collect();
+        assert_eq!(undefined, [("Missing", 9)]);
+        let unused: Vec<_> = labels.unused_labels().iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(unused, ["Loop", "Spare"]);
+    }
+}
@@ -0,0 +1,112 @@
+//! Declared variables and their initial values.
+//!
+//! Collects the tags and variables of a project together with their data
+//! type and whether they declare an initial value. For L5X tags a `Data`
+//! element is the initial value; for PLCopen variables it is the
+//! `initialValue` element. Alias tags, in-out and external variables refer
+//! to storage declared elsewhere and are left out.
+
+use l5x::{Controller, Tag, TagContent};
+use plcopen::VarListPlain_variable_Inline as Variable;
+
+use super::plcopen_analysis::extract_type_name;
+
+/// A declared tag or variable.
+#[derive(Debug, Clone)]
+pub struct DeclaredVariable {
+    /// Tag or variable name
+    pub name: String,
+    /// Data type name (empty if unknown)
+    pub data_type: String,
+    /// Location for findings (e.g., "Program:Main")
+    pub location: String,
+    /// Whether an initial value is declared
+    pub initialized: bool,
+}
+
+/// Collect the controller and program tags of an L5X controller.
+pub fn variables_from_controller(controller: &Controller) -> Vec<DeclaredVariable> {
+    let mut variables = Vec::new();
+
+    let mut push_tags = |tags: &[Tag], location: &str| {
+        for tag in tags.iter().filter(|tag| tag.alias_for.is_none()) {
+            variables.push(DeclaredVariable {
+                name: tag.name.clone(),
+                data_type: tag.data_type.clone().unwrap_or_default(),
+                location: location.to_string(),
+                initialized: tag.content.iter().any(|c| matches!(c, TagContent::Data(_))),
+            });
+        }
+    };
+
+    if let Some(ref tags) = controller.tags {
+        push_tags(&tags.tag, "Controller");
+    }
+
+    if let Some(ref programs) = controller.programs {
+        for program in &programs.program {
+            if let Some(ref tags) = program.tags {
+                push_tags(&tags.tag, &format!("Program:{}", program.name));
+            }
+        }
+    }
+
+    variables
+}
+
+/// Collect the POU and configuration variables of a PLCopen project.
+pub fn variables_from_plcopen(project: &plcopen::Project) -> Vec<DeclaredVariable> {
+    let mut variables = Vec::new();
+
+    let mut push_vars = |vars: &[Box<Variable>], location: &str| {
+        for var in vars {
+            variables.push(DeclaredVariable {
+                name: var.name.clone(),
+                data_type: var.r#type.as_deref().and_then(extract_type_name).unwrap_or_default(),
+                location: location.to_string(),
+                initialized: var.initial_value.is_some(),
+            });
+        }
+    };
+
+    let pous = project.types.as_ref().and_then(|t| t.pous.as_ref());
+    for pou in pous.map(|p| p.pou.as_slice()).unwrap_or_default() {
+        if let Some(ref interface) = pou.interface {
+            for list in &interface.input_vars {
+                push_vars(&list.variable, &pou.name);
+            }
+            for list in &interface.output_vars {
+                push_vars(&list.variable, &pou.name);
+            }
+            for list in &interface.local_vars {
+                push_vars(&list.variable, &pou.name);
+            }
+            for list in &interface.temp_vars {
+                push_vars(&list.variable, &pou.name);
+            }
+            for list in &interface.global_vars {
+                push_vars(&list.variable, &pou.name);
+            }
+        }
+    }
+
+    let configurations = project
+        .instances
+        .as_ref()
+        .and_then(|i| i.configurations.as_ref())
+        .map(|c| c.configuration.as_slice())
+        .unwrap_or_default();
+    for configuration in configurations {
+        for list in &configuration.global_vars {
+            push_vars(&list.variable, &configuration.name);
+        }
+        for resource in &configuration.resource {
+            let location = format!("{}.{}", configuration.name, resource.name);
+            for list in &resource.global_vars {
+                push_vars(&list.variable, &location);
+            }
+        }
+    }
+
+    variables
+}
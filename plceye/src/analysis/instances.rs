@@ -0,0 +1,246 @@
+//! Function block and AOI instances and their invocations.
+//!
+//! An instance is a tag or variable whose type is an AOI, a user function
+//! block or a standard IEC function block. It is invoked by:
+//!
+//! - an RLL instruction of its AOI type with the instance as first operand
+//! - an ST call of the instance (`Timer1(IN := Start)`), or of its AOI type
+//!   with the instance as first argument (`Valve(Valve1, Open)`)
+//! - a PLCopen FBD/LD block naming the instance
+//!
+//! L5X programs and AOIs with FBD or SFC routines are not analyzed, so
+//! instances that may be invoked there are left out.
+
+use std::collections::HashSet;
+
+use iecst::{Expr, ExprKind, Stmt, StmtKind};
+use l5x::{Controller, Tag, UDIDefinitionContent};
+
+use super::plcopen_analysis::extract_type_name;
+use super::{PlcopenAnalysis, ProjectAnalysis, StUnits};
+
+/// Standard IEC 61131-3 function blocks.
+const STANDARD_FBS: &[&str] = &[
+    "TON", "TOF", "TP", "CTU", "CTD", "CTUD", "R_TRIG", "F_TRIG", "SR", "RS",
+];
+
+/// A function block or AOI instance.
+#[derive(Debug, Clone)]
+pub struct FbInstance {
+    /// Instance tag or variable name
+    pub name: String,
+    /// Function block or AOI type
+    pub fb_type: String,
+    /// Location for findings (e.g., "Program:Main")
+    pub location: String,
+}
+
+/// Instances of a project and the names of those that are invoked.
+#[derive(Debug, Clone, Default)]
+pub struct InstanceUsage {
+    /// Declared instances
+    pub instances: Vec<FbInstance>,
+    /// Uppercase names of invoked instances
+    pub invoked: HashSet<String>,
+}
+
+impl InstanceUsage {
+    /// Collect the AOI instances of an L5X controller and their invocations.
+    pub fn from_controller(controller: &Controller, analysis: &ProjectAnalysis, st_units: &StUnits) -> Self {
+        let aoi_names: HashSet<String> = analysis.aoi_definitions.iter().map(|n| n.to_uppercase()).collect();
+        let mut instances = Vec::new();
+
+        let opaque = |routines: Option<&l5x::RoutineCollection>| {
+            routines.is_some_and(|r| {
+                r.routine.iter().any(|routine| !matches!(routine.r#type.as_str(), "RLL" | "ST"))
+            })
+        };
+        let mut push_tags = |tags: &[Tag], location: &str| {
+            for tag in tags {
+                if let Some(data_type) = tag.data_type.as_ref().filter(|dt| aoi_names.contains(&dt.to_uppercase())) {
+                    instances.push(FbInstance {
+                        name: tag.name.clone(),
+                        fb_type: data_type.clone(),
+                        location: location.to_string(),
+                    });
+                }
+            }
+        };
+
+        let programs = controller.programs.as_ref().map(|p| p.program.as_slice()).unwrap_or_default();
+        if !programs.iter().any(|program| opaque(program.routines.as_ref())) {
+            if let Some(ref tags) = controller.tags {
+                push_tags(&tags.tag, "Controller");
+            }
+        }
+        for program in programs.iter().filter(|program| !opaque(program.routines.as_ref())) {
+            if let Some(ref tags) = program.tags {
+                push_tags(&tags.tag, &format!("Program:{}", program.name));
+            }
+        }
+        if let Some(ref aois) = controller.add_on_instruction_definitions {
+            for aoi in &aois.add_on_instruction_definition {
+                let aoi_opaque = aoi.content.iter().any(|content| {
+                    matches!(content, UDIDefinitionContent::Routines(routines) if opaque(Some(routines)))
+                });
+                if aoi_opaque {
+                    continue;
+                }
+                for content in &aoi.content {
+                    if let UDIDefinitionContent::LocalTags(local_tags) = content {
+                        for tag in &local_tags.local_tag {
+                            if aoi_names.contains(&tag.data_type.to_uppercase()) {
+                                instances.push(FbInstance {
+                                    name: tag.name.clone(),
+                                    fb_type: tag.data_type.clone(),
+                                    location: format!("AOI:{}", aoi.name),
+                                });
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut invoked = HashSet::new();
+        for tag_ref in analysis.rungs.iter().flat_map(|rung| rung.tag_references()) {
+            let reference = &tag_ref.reference;
+            if reference.operand_index == 0 && aoi_names.contains(&reference.instruction.to_uppercase()) {
+                invoked.insert(reference.name.to_uppercase());
+            }
+        }
+        for pou in st_units.units.iter().filter_map(|unit| unit.pou.as_ref()) {
+            collect_st_invocations(&pou.body, &aoi_names, &mut invoked);
+        }
+
+        Self { instances, invoked }
+    }
+
+    /// Collect the function block instances of a PLCopen project and their invocations.
+    pub fn from_plcopen(project: &plcopen::Project, analysis: &PlcopenAnalysis) -> Self {
+        let pous = project.types.as_ref().and_then(|t| t.pous.as_ref());
+        let fb_names: HashSet<String> = pous
+            .map(|pous| {
+                pous.pou
+                    .iter()
+                    .filter(|pou| pou.pou_type.eq_ignore_ascii_case("functionBlock"))
+                    .map(|pou| pou.name.to_uppercase())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        let mut instances = Vec::new();
+        for pou in pous.map(|p| p.pou.as_slice()).unwrap_or_default() {
+            let Some(ref interface) = pou.interface else {
+                continue;
+            };
+            let variables = interface
+                .local_vars
+                .iter()
+                .flat_map(|list| &list.variable)
+                .chain(interface.temp_vars.iter().flat_map(|list| &list.variable))
+                .chain(interface.output_vars.iter().flat_map(|list| &list.variable));
+            for var in variables {
+                let Some(fb_type) = var.r#type.as_deref().and_then(extract_type_name) else {
+                    continue;
+                };
+                if fb_names.contains(&fb_type) || STANDARD_FBS.contains(&fb_type.as_str()) {
+                    instances.push(FbInstance {
+                        name: var.name.clone(),
+                        fb_type,
+                        location: pou.name.clone(),
+                    });
+                }
+            }
+        }
+
+        let invoked = analysis
+            .called_names
+            .iter()
+            .chain(&analysis.block_instances)
+            .map(|name| name.to_uppercase())
+            .collect();
+
+        Self { instances, invoked }
+    }
+
+    /// Get the instances that are never invoked.
+    pub fn uninvoked(&self) -> Vec<&FbInstance> {
+        self.instances
+            .iter()
+            .filter(|instance| !self.invoked.contains(&instance.name.to_uppercase()))
+            .collect()
+    }
+}
+
+/// Record the instances invoked by ST calls in `body`.
+fn collect_st_invocations(body: &[Stmt], aoi_names: &HashSet<String>, invoked: &mut HashSet<String>) {
+    let call = |name: &str, first_arg: Option<&Expr>, invoked: &mut HashSet<String>| {
+        invoked.insert(name.to_uppercase());
+        if aoi_names.contains(&name.to_uppercase()) {
+            if let Some(ExprKind::Ident(instance)) = first_arg.map(|e| &e.kind) {
+                invoked.insert(instance.to_uppercase());
+            }
+        }
+    };
+
+    for stmt in body {
+        let mut exprs: Vec<&Expr> = Vec::new();
+        match &stmt.kind {
+            StmtKind::Call { name, args } => {
+                call(name, args.first().and_then(|a| a.value.as_ref()), invoked);
+                exprs.extend(args.iter().filter_map(|a| a.value.as_ref()));
+            }
+            StmtKind::Assignment { target, value } => exprs.extend([target, value]),
+            StmtKind::If { condition, then_body, elsif_branches, else_body } => {
+                exprs.push(condition);
+                collect_st_invocations(then_body, aoi_names, invoked);
+                for (cond, body) in elsif_branches {
+                    exprs.push(cond);
+                    collect_st_invocations(body, aoi_names, invoked);
+                }
+                if let Some(else_stmts) = else_body {
+                    collect_st_invocations(else_stmts, aoi_names, invoked);
+                }
+            }
+            StmtKind::Case { expr, cases, else_body } => {
+                exprs.push(expr);
+                for branch in cases {
+                    collect_st_invocations(&branch.body, aoi_names, invoked);
+                }
+                if let Some(else_stmts) = else_body {
+                    collect_st_invocations(else_stmts, aoi_names, invoked);
+                }
+            }
+            StmtKind::For { from, to, by, body, .. } => {
+                exprs.extend([from, to]);
+                exprs.extend(by.as_ref());
+                collect_st_invocations(body, aoi_names, invoked);
+            }
+            StmtKind::While { condition, body } | StmtKind::Repeat { body, until: condition } => {
+                exprs.push(condition);
+                collect_st_invocations(body, aoi_names, invoked);
+            }
+            StmtKind::Return { value } => exprs.extend(value.as_ref()),
+            _ => {}
+        }
+
+        while let Some(expr) = exprs.pop() {
+            match &expr.kind {
+                ExprKind::FunctionCall { name, args } => {
+                    call(name, args.first().and_then(|a| a.value.as_ref()), invoked);
+                    exprs.extend(args.iter().filter_map(|a| a.value.as_ref()));
+                }
+                ExprKind::BinaryOp { left, right, .. } => exprs.extend([&**left, &**right]),
+                ExprKind::UnaryOp { expr: inner, .. }
+                | ExprKind::Paren(inner)
+                | ExprKind::MemberAccess { expr: inner, .. } => exprs.push(inner),
+                ExprKind::ArrayIndex { array, indices } => {
+                    exprs.push(array);
+                    exprs.extend(indices);
+                }
+                _ => {}
+            }
+        }
+    }
+}
@@ -0,0 +1,106 @@
+//! RLL instruction usage.
+//!
+//! Tallies the instruction mnemonics of the parsed RLL rungs and the calls
+//! of each Add-On Instruction, to see which instructions a project relies
+//! on (for example when planning a migration to another platform).
+
+use std::collections::BTreeMap;
+
+use super::{extract_st_calls, ProjectAnalysis};
+
+/// Number of occurrences of each RLL instruction mnemonic.
+///
+/// Covers the parsed rungs of programs and AOIs; calls of AOIs in rungs
+/// appear under the AOI name. Rungs that failed to parse are not counted.
+pub fn instruction_histogram(analysis: &ProjectAnalysis) -> BTreeMap<String, usize> {
+    let mut histogram = BTreeMap::new();
+    for rung in &analysis.rungs {
+        let Some(ref content) = rung.parsed.content else {
+            continue;
+        };
+        for (_, instruction) in content.instructions() {
+            *histogram.entry(instruction.mnemonic.clone()).or_default() += 1;
+        }
+    }
+    histogram
+}
+
+/// Number of calls of each AOI defined in the project, from RLL rungs and
+/// ST routines. AOIs that are never called are listed with 0.
+pub fn aoi_call_histogram(analysis: &ProjectAnalysis) -> BTreeMap<String, usize> {
+    let mut histogram: BTreeMap<String, usize> =
+        analysis.aoi_definitions.iter().map(|name| (name.clone(), 0)).collect();
+    let mut count = |name: &str, calls: usize| {
+        if let Some((_, total)) = histogram.iter_mut().find(|(aoi, _)| aoi.eq_ignore_ascii_case(name)) {
+            *total += calls;
+        }
+    };
+
+    for (mnemonic, occurrences) in instruction_histogram(analysis) {
+        count(&mnemonic, occurrences);
+    }
+    for st_routine in &analysis.st_routines {
+        if let Some(ref pou) = st_routine.pou {
+            for name in extract_st_calls(pou) {
+                count(&name, 1);
+            }
+        }
+    }
+    histogram
+}
+
+/// Histogram entries, most frequent first; ties are ordered by name.
+pub fn by_frequency(histogram: &BTreeMap<String, usize>) -> Vec<(&str, usize)> {
+    let mut entries: Vec<_> = histogram.iter().map(|(name, &count)| (name.as_str(), count)).collect();
+    entries.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::analyze_controller;
+    use crate::loader::LoadedProject;
+
+    #[test]
+    fn test_instruction_histogram() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Line">
+                <AddOnInstructionDefinitions>
+                    <AddOnInstructionDefinition Name="Valve"/>
+                    <AddOnInstructionDefinition Name="Spare"/>
+                </AddOnInstructionDefinitions>
+                <Programs>
+                    <Program Name="Main">
+                        <Routines>
+                            <Routine Name="Ladder" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0"><Text>XIC(Start)[XIC(Run),XIO(Stop)]OTE(Run);</Text></Rung>
+                                    <Rung Number="1"><Text>XIC(Run)CPT(Speed,Speed*2)Valve(V1);</Text></Rung>
+                                    <Rung Number="2"><Text>NOP();</Text></Rung>
+                                </RLLContent>
+                            </Routine>
+                            <Routine Name="Logic" Type="ST">
+                                <STContent>
+                                    <Line Number="0"><![CDATA[Valve(Input := V2); Valve(Input := V3);]]></Line>
+                                </STContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let analysis = analyze_controller(project.l5x_controller.as_ref().unwrap());
+
+        let instructions = instruction_histogram(&analysis);
+        assert_eq!(
+            by_frequency(&instructions),
+            [("XIC", 3), ("CPT", 1), ("NOP", 1), ("OTE", 1), ("Valve", 1), ("XIO", 1)]
+        );
+        let aoi_calls = aoi_call_histogram(&analysis);
+        assert_eq!(by_frequency(&aoi_calls), [("Valve", 3), ("Spare", 0)]);
+    }
+}
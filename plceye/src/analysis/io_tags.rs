@@ -0,0 +1,258 @@
+//! Tags defined outside the routine scope.
+//!
+//! I/O modules define their input, output and configuration tags in the L5X
+//! `Modules` section rather than in a `Tags` collection. A module in the
+//! controller's chassis names them after its slot (`Local:1:I`), a module
+//! reached over a network after itself (`Drive1:I`). Modules of a remote
+//! chassis with a rack-optimized connection also get slot names
+//! (`Rack1:2:I`), and the adapter of that chassis gets the rack image tags
+//! (`Rack1:I`). A connection or configuration tag with a `Suffix` uses it
+//! instead of `I`, `O` or `C`.
+//!
+//! Produced and consumed tags are controller tags shared with other
+//! controllers; the producer of a consumed tag is kept as its source.
+
+use l5x::{Controller, MapDevice, TagContent};
+
+/// What defines an external tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IoTagKind {
+    /// Module input data
+    Input,
+    /// Module output data
+    Output,
+    /// Module configuration data
+    Config,
+    /// Controller tag produced for other controllers
+    Produced,
+    /// Controller tag consumed from another controller
+    Consumed,
+}
+
+/// A tag defined by an I/O module or shared between controllers.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IoTag {
+    /// Tag name as referenced in code (e.g., "Local:1:I")
+    pub name: String,
+    /// What defines the tag
+    pub kind: IoTagKind,
+    /// Module that defines the tag, or the producing controller of a
+    /// consumed tag (the controller itself for produced tags)
+    pub source: String,
+}
+
+/// I/O module and produced/consumed tags of a controller.
+#[derive(Debug, Clone, Default)]
+pub struct IoTags {
+    /// All tags, modules first in export order
+    pub tags: Vec<IoTag>,
+}
+
+impl IoTags {
+    /// Collect the module and produced/consumed tags of an L5X controller.
+    ///
+    /// Returns `None` if the export has no `Modules` section (e.g. a single
+    /// program export), since module tags cannot be resolved then.
+    pub fn from_controller(controller: &Controller) -> Option<Self> {
+        let modules = &controller.modules.as_ref()?.module;
+        let mut tags = Vec::new();
+
+        // The controller is the module that is its own parent
+        let local = modules
+            .iter()
+            .find(|m| m.name.is_some() && m.name == m.parent_module)
+            .and_then(|m| m.name.as_deref())
+            .unwrap_or("Local");
+
+        for module in modules {
+            let Some(name) = module.name.as_deref() else {
+                continue;
+            };
+            if name == local {
+                continue;
+            }
+            let parent = module.parent_module.as_deref().unwrap_or(local);
+            let slot = upstream_slot(module);
+
+            let mut prefixes = Vec::new();
+            match slot {
+                Some(slot) if parent == local => prefixes.push(format!("{}:{}", local, slot)),
+                Some(slot) => {
+                    prefixes.push(name.to_string());
+                    if has_rack_connection(modules, parent) {
+                        prefixes.push(format!("{}:{}", parent, slot));
+                    }
+                }
+                None => prefixes.push(name.to_string()),
+            }
+
+            let mut suffixes = module_suffixes(module);
+            if has_rack_connection(modules, name) {
+                suffixes.push(("I".to_string(), IoTagKind::Input));
+                suffixes.push(("O".to_string(), IoTagKind::Output));
+            }
+            for prefix in &prefixes {
+                for (suffix, kind) in &suffixes {
+                    let tag = IoTag {
+                        name: format!("{}:{}", prefix, suffix),
+                        kind: *kind,
+                        source: name.to_string(),
+                    };
+                    if !tags.contains(&tag) {
+                        tags.push(tag);
+                    }
+                }
+            }
+        }
+
+        for tag in controller.tags.iter().flat_map(|t| &t.tag) {
+            let kind = match tag.tag_type.as_deref() {
+                Some(t) if t.eq_ignore_ascii_case("Produced") => IoTagKind::Produced,
+                Some(t) if t.eq_ignore_ascii_case("Consumed") => IoTagKind::Consumed,
+                _ => continue,
+            };
+            let producer = tag.content.iter().find_map(|content| match content {
+                TagContent::ConsumeInfo(info) => Some(info.producer.clone()),
+                _ => None,
+            });
+            tags.push(IoTag {
+                name: tag.name.clone(),
+                kind,
+                source: producer.unwrap_or_else(|| controller.name.clone()),
+            });
+        }
+
+        Some(Self { tags })
+    }
+
+    /// Tags defined by I/O modules.
+    pub fn module_tags(&self) -> impl Iterator<Item = &IoTag> {
+        self.tags
+            .iter()
+            .filter(|t| matches!(t.kind, IoTagKind::Input | IoTagKind::Output | IoTagKind::Config))
+    }
+}
+
+/// Slot of a module in its parent's chassis: the numeric address of its
+/// upstream port. Network addresses such as IP addresses are not slots.
+fn upstream_slot(module: &MapDevice) -> Option<u32> {
+    module
+        .ports
+        .as_ref()?
+        .port
+        .iter()
+        .find(|port| port.upstream.as_deref() == Some("true"))
+        .and_then(|port| port.address.as_deref()?.trim().parse().ok())
+}
+
+/// Whether the module `name` is a chassis adapter with a rack-optimized
+/// connection.
+fn has_rack_connection(modules: &[MapDevice], name: &str) -> bool {
+    modules.iter().any(|m| {
+        m.name.as_deref() == Some(name)
+            && m.communications
+                .as_ref()
+                .and_then(|c| c.connections.as_ref())
+                .is_some_and(|c| c.rack_connection.is_some())
+    })
+}
+
+/// Suffixes and kinds of the tags a module's connections and configuration
+/// define.
+fn module_suffixes(module: &MapDevice) -> Vec<(String, IoTagKind)> {
+    let mut suffixes = Vec::new();
+    let Some(communications) = &module.communications else {
+        return suffixes;
+    };
+    if let Some(config) = &communications.config_tag {
+        suffixes.push((config.suffix.clone().unwrap_or_else(|| "C".to_string()), IoTagKind::Config));
+    }
+    for connection in communications.connections.iter().flat_map(|c| &c.connection) {
+        if let Some(input) = &connection.input_tag {
+            let suffix = input.suffix.clone().or_else(|| connection.input_tag_suffix.clone());
+            suffixes.push((suffix.unwrap_or_else(|| "I".to_string()), IoTagKind::Input));
+        }
+        if let Some(output) = &connection.output_tag {
+            let suffix = output.suffix.clone().or_else(|| connection.output_tag_suffix.clone());
+            suffixes.push((suffix.unwrap_or_else(|| "O".to_string()), IoTagKind::Output));
+        }
+    }
+    suffixes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::LoadedProject;
+
+    fn io_tags(xml: &str) -> Option<IoTags> {
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        IoTags::from_controller(project.l5x_controller.as_ref()?)
+    }
+
+    #[test]
+    fn test_module_and_produced_consumed_tags() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Line1">
+                <Modules>
+                    <Module Name="Local" ParentModule="Local">
+                        <Ports><Port Id="1" Address="0" Type="ICP" Upstream="false"/></Ports>
+                    </Module>
+                    <Module Name="DI16" ParentModule="Local">
+                        <Ports><Port Id="1" Address="1" Type="ICP" Upstream="true"/></Ports>
+                        <Communications>
+                            <ConfigTag DataType="AB:1756_DI:C:0"/>
+                            <Connections>
+                                <Connection Name="Data" RPI="20000"><InputTag DataType="AB:1756_DI:I:0"/></Connection>
+                            </Connections>
+                        </Communications>
+                    </Module>
+                    <Module Name="Drive1" ParentModule="ENBT">
+                        <Ports><Port Id="2" Address="192.168.1.20" Type="Ethernet" Upstream="true"/></Ports>
+                        <Communications>
+                            <Connections>
+                                <Connection Name="Exclusive" RPI="10000">
+                                    <InputTag Suffix="I1" DataType="AB:PF525:I:0"/>
+                                    <OutputTag DataType="AB:PF525:O:0"/>
+                                </Connection>
+                            </Connections>
+                        </Communications>
+                    </Module>
+                </Modules>
+                <Tags>
+                    <Tag Name="Speed" TagType="Produced" DataType="DINT"/>
+                    <Tag Name="Remote" TagType="Consumed" DataType="DINT">
+                        <ConsumeInfo Producer="Line2" RemoteTag="Speed" RPI="20"/>
+                    </Tag>
+                    <Tag Name="Plain" TagType="Base" DataType="DINT"/>
+                </Tags>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let tags = io_tags(xml).expect("Should have modules");
+        let names: Vec<(&str, IoTagKind, &str)> =
+            tags.tags.iter().map(|t| (t.name.as_str(), t.kind, t.source.as_str())).collect();
+        assert_eq!(
+            names,
+            [
+                ("Local:1:C", IoTagKind::Config, "DI16"),
+                ("Local:1:I", IoTagKind::Input, "DI16"),
+                ("Drive1:I1", IoTagKind::Input, "Drive1"),
+                ("Drive1:O", IoTagKind::Output, "Drive1"),
+                ("Speed", IoTagKind::Produced, "Line1"),
+                ("Remote", IoTagKind::Consumed, "Line2"),
+            ]
+        );
+        assert_eq!(tags.module_tags().count(), 4);
+    }
+
+    #[test]
+    fn test_no_modules_section() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Line1"/>
+        </RSLogix5000Content>"#;
+        assert!(io_tags(xml).is_none());
+    }
+}
@@ -5,14 +5,19 @@
 
 use std::collections::{HashMap, HashSet};
 
-use l5x::rll::{Rung as ParsedRung, TagReference, ErrorContext, ParseError};
+use l5x::rll::{Rung as ParsedRung, TagReference, ErrorContext, ParseError, TextPosition};
 use l5x::{
     Controller,
     UDIDefinition, UDIDefinitionContent,
 };
+use serde::Serialize;
 
+use super::call_graph::{topo_order, CallGraph, CycleError, PouId};
+use super::comments::CommentDensity;
+use super::complexity::{ComplexityDistribution, NestingDistribution};
 use super::iec61131_adapter::Pou;
-use super::rll_parsing::parse_routine;
+use super::rll_parsing::{parse_routine, rung_text_offsets};
+use super::size::{CodeSize, SizeMetrics};
 use super::st_parsing::{
     parse_st_routines_from_program, parse_st_routines_from_aoi,
     extract_st_call_names,
@@ -48,6 +53,9 @@ impl RungLocation {
 pub struct LocatedRung {
     pub location: RungLocation,
     pub parsed: ParsedRung,
+    /// Position of the rung text in the L5X file, once located with
+    /// `ProjectAnalysis::locate_rungs`
+    pub text_start: Option<TextPosition>,
 }
 
 impl LocatedRung {
@@ -62,13 +70,19 @@ impl LocatedRung {
             .collect()
     }
 
+    /// The parse error of the rung, with its position in the file if the
+    /// rung has been located.
     pub fn parse_error(&self) -> Option<ParseError> {
         self.parsed.error.as_ref().map(|err| {
-            ParseError::new(err.clone(), &self.parsed.raw_text).with_context(ErrorContext::new(
+            let error = ParseError::new(err.clone(), &self.parsed.raw_text).with_context(ErrorContext::new(
                 &self.location.program,
                 &self.location.routine,
                 self.location.rung_number,
-            ))
+            ));
+            match self.text_start {
+                Some(start) => error.with_text_start(start),
+                None => error,
+            }
         })
     }
 
@@ -118,19 +132,69 @@ impl STLocation {
     }
 }
 
+/// Kind of POU an ST routine belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RoutineKind {
+    Program,
+    Function,
+    FunctionBlock,
+    Aoi,
+}
+
+impl std::fmt::Display for RoutineKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RoutineKind::Program => write!(f, "program"),
+            RoutineKind::Function => write!(f, "function"),
+            RoutineKind::FunctionBlock => write!(f, "function_block"),
+            RoutineKind::Aoi => write!(f, "aoi"),
+        }
+    }
+}
+
 /// A parsed ST routine with its location.
 #[derive(Debug)]
 pub struct ParsedSTRoutine {
     pub location: STLocation,
+    pub kind: RoutineKind,
     pub source: String,
     pub pou: Option<Pou>,
     pub parse_error: Option<super::iec61131_adapter::ParseError>,
+    /// Statements skipped by error recovery when the routine didn't parse
+    pub skipped: Vec<iecst::ParseError>,
 }
 
 impl ParsedSTRoutine {
     pub fn is_parsed(&self) -> bool {
         self.pou.is_some()
     }
+
+    /// Whether the routine has parse errors but its remaining statements
+    /// parsed, so `pou` holds a partial body.
+    pub fn is_recovered(&self) -> bool {
+        self.pou.is_some() && self.parse_error.is_some()
+    }
+
+    /// Line (1-based) and message of each skipped statement.
+    pub fn skipped_lines(&self) -> Vec<(usize, String)> {
+        self.skipped
+            .iter()
+            .map(|error| {
+                let start = error.span.start.min(self.source.len());
+                let line = self.source.as_bytes()[..start].iter().filter(|&&b| b == b'\n').count() + 1;
+                (line, error.kind.to_string())
+            })
+            .collect()
+    }
+
+    /// Location for findings: the POU name for PLCopen functions and
+    /// function blocks, "Program:<program>" otherwise.
+    pub fn scope(&self) -> String {
+        match self.kind {
+            RoutineKind::Function | RoutineKind::FunctionBlock => self.location.program.clone(),
+            RoutineKind::Program | RoutineKind::Aoi => format!("Program:{}", self.location.program),
+        }
+    }
 }
 
 /// Location where an AOI is called.
@@ -179,7 +243,7 @@ impl AoiReference {
 }
 
 /// Statistics from parsing a project.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct ParseStats {
     pub programs: usize,
     pub aois: usize,
@@ -196,11 +260,23 @@ pub struct ParseStats {
     pub st_routines_aois: usize,
     pub st_parsed_ok: usize,
     pub st_parsed_err: usize,
+    // ST routines with parse errors whose other statements were analyzed
+    pub st_recovered: usize,
+    // Statements skipped by error recovery, as "Program/Routine line N: message"
+    pub st_skipped: Vec<String>,
     // Complexity metrics for ST routines
     pub st_max_complexity: usize,
     pub st_avg_complexity: f32,
     pub st_max_nesting: usize,
     pub st_avg_nesting: f32,
+    // Complexity of each parsed ST routine, by bucket and by name
+    pub st_complexity: ComplexityDistribution,
+    // Maximum nesting depth of each parsed ST routine, by bucket and by name
+    pub st_nesting: NestingDistribution,
+    // Comment and code characters of all ST routines
+    pub st_comments: CommentDensity,
+    // Lines and statements of each RLL and ST routine, by "Program/Routine"
+    pub sizes: SizeMetrics,
 }
 
 /// Summary of a single routine.
@@ -214,6 +290,8 @@ pub struct RoutineSummary {
     pub parse_errors: usize,
     pub tags_used: Vec<String>,
     pub instructions: HashMap<String, usize>,
+    /// Source protected, with its logic not in the export
+    pub protected: bool,
 }
 
 /// Result of analyzing a controller.
@@ -227,6 +305,9 @@ pub struct ProjectAnalysis {
     pub instruction_usage: HashMap<String, usize>,
     pub aoi_definitions: Vec<String>,
     pub aoi_usage: HashMap<String, Vec<AoiReference>>,
+    /// Source-protected routines ("Program/Routine") and AOIs ("AOI:Name")
+    /// whose logic is not in the export, so they are not analyzed
+    pub protected: Vec<String>,
     pub stats: ParseStats,
 }
 
@@ -257,6 +338,23 @@ impl ProjectAnalysis {
         self.rungs.iter().filter_map(|rung| rung.parse_error()).collect()
     }
 
+    /// Find the rung texts in `file`, the content of the L5X file the
+    /// project was loaded from, so parse errors report file positions.
+    ///
+    /// Rungs whose text is not found verbatim (for example text with XML
+    /// escapes instead of CDATA) keep no position.
+    pub fn locate_rungs(&mut self, file: &str) {
+        let offsets = rung_text_offsets(file);
+        for rung in &mut self.rungs {
+            let location = &rung.location;
+            let key = (location.program.clone(), location.routine.clone(), location.rung_number);
+            rung.text_start = offsets
+                .get(&key)
+                .filter(|&&offset| file.get(offset..).is_some_and(|text| text.starts_with(&rung.parsed.raw_text)))
+                .map(|&offset| TextPosition::of(file, offset));
+        }
+    }
+
     /// Get unused AOIs.
     pub fn unused_aois(&self) -> Vec<&str> {
         self.aoi_definitions
@@ -268,6 +366,32 @@ impl ProjectAnalysis {
             .collect()
     }
 
+    /// Calls of AOIs from programs and other AOIs, in RLL and ST.
+    ///
+    /// Programs appear under their name and AOIs under their AOI name,
+    /// without the `AOI:` prefix of their locations.
+    pub fn call_graph(&self) -> CallGraph {
+        let mut graph = CallGraph::new();
+        for routine in &self.routines {
+            graph.add_pou(routine.program.as_str());
+        }
+        for aoi in &self.aoi_definitions {
+            graph.add_pou(aoi.as_str());
+        }
+        for (aoi, references) in &self.aoi_usage {
+            for reference in references {
+                let caller = reference.program.strip_prefix("AOI:").unwrap_or(&reference.program);
+                graph.add_call(caller, aoi.as_str());
+            }
+        }
+        graph
+    }
+
+    /// Programs and AOIs ordered callees first, or the POUs of a call cycle.
+    pub fn topo_order(&self) -> Result<Vec<PouId>, CycleError> {
+        topo_order(&self.call_graph())
+    }
+
     /// Get references to a specific AOI.
     pub fn aoi_references(&self, aoi_name: &str) -> Vec<&AoiReference> {
         self.aoi_usage
@@ -297,6 +421,7 @@ fn parse_rll_from_aoi(aoi: &UDIDefinition) -> Vec<LocatedRung> {
 pub fn analyze_controller(controller: &Controller) -> ProjectAnalysis {
     let mut rungs = Vec::new();
     let mut routine_summaries = Vec::new();
+    let mut protected = Vec::new();
     let mut stats = ParseStats::default();
 
     // Parse programs
@@ -305,8 +430,15 @@ pub fn analyze_controller(controller: &Controller) -> ProjectAnalysis {
             stats.programs += 1;
 
             if let Some(routines) = &program.routines {
+                for encoded in &routines.encoded_data {
+                    protected.push(format!("{}/{}", program.name, encoded.name.as_deref().unwrap_or("?")));
+                }
                 for routine in &routines.routine {
                     stats.routines += 1;
+                    let is_protected = routine.is_source_protected() && !routine.has_logic();
+                    if is_protected {
+                        protected.push(format!("{}/{}", program.name, routine.name));
+                    }
 
                     let start_idx = rungs.len();
                     let routine_rungs = parse_routine(routine, &program.name);
@@ -342,6 +474,7 @@ pub fn analyze_controller(controller: &Controller) -> ProjectAnalysis {
                         parse_errors,
                         tags_used: routine_tags,
                         instructions: routine_instructions,
+                        protected: is_protected,
                     });
 
                     rungs.extend(routine_rungs);
@@ -354,7 +487,13 @@ pub fn analyze_controller(controller: &Controller) -> ProjectAnalysis {
     let mut aoi_definitions: Vec<String> = Vec::new();
     if let Some(aois) = &controller.add_on_instruction_definitions {
         stats.aois = aois.add_on_instruction_definition.len();
+        for encoded in &aois.encoded_data {
+            protected.push(format!("AOI:{}", encoded.name.as_deref().unwrap_or("?")));
+        }
         for aoi in &aois.add_on_instruction_definition {
+            if aoi.is_source_protected() && !aoi.has_logic() {
+                protected.push(format!("AOI:{}", aoi.name));
+            }
             aoi_definitions.push(aoi.name.clone());
             let aoi_rungs = parse_rll_from_aoi(aoi);
             stats.rll_rungs_aois += aoi_rungs.len();
@@ -364,6 +503,23 @@ pub fn analyze_controller(controller: &Controller) -> ProjectAnalysis {
 
     let aoi_name_set: HashSet<&str> = aoi_definitions.iter().map(|s| s.as_str()).collect();
 
+    // Size of each RLL routine; the rungs of a routine are consecutive
+    let mut start = 0;
+    while start < rungs.len() {
+        let location = &rungs[start].location;
+        let end = start
+            + rungs[start..]
+                .iter()
+                .take_while(|rung| rung.location.program == location.program && rung.location.routine == location.routine)
+                .count();
+        stats.sizes.add(
+            format!("{}/{}", location.program, location.routine),
+            "RLL",
+            CodeSize::of_rungs(rungs[start..end].iter().map(|rung| &rung.parsed)),
+        );
+        start = end;
+    }
+
     // Build tag cross-reference and track AOI usage
     stats.rungs = rungs.len();
     let mut all_refs = Vec::new();
@@ -439,7 +595,16 @@ pub fn analyze_controller(controller: &Controller) -> ProjectAnalysis {
 
     stats.st_routines = st_routines.len();
     for st_routine in &st_routines {
-        if st_routine.is_parsed() {
+        if st_routine.is_recovered() {
+            stats.st_parsed_err += 1;
+            stats.st_recovered += 1;
+            for (line, message) in st_routine.skipped_lines() {
+                stats.st_skipped.push(format!(
+                    "{}/{} line {}: {}",
+                    st_routine.location.program, st_routine.location.routine, line, message
+                ));
+            }
+        } else if st_routine.is_parsed() {
             stats.st_parsed_ok += 1;
         } else {
             stats.st_parsed_err += 1;
@@ -458,6 +623,9 @@ pub fn analyze_controller(controller: &Controller) -> ProjectAnalysis {
             
             complexities.push(complexity);
             nestings.push(nesting);
+            let name = format!("{}/{}", st_routine.location.program, st_routine.location.routine);
+            stats.st_nesting.add(name.clone(), nesting);
+            stats.st_complexity.add(name, complexity);
         }
     }
     
@@ -471,6 +639,15 @@ pub fn analyze_controller(controller: &Controller) -> ProjectAnalysis {
         stats.st_avg_nesting = nestings.iter().sum::<usize>() as f32 / nestings.len() as f32;
     }
 
+    for st_routine in &st_routines {
+        stats.st_comments += CommentDensity::of(&st_routine.source);
+        stats.sizes.add(
+            st_routine.location.path(),
+            "ST",
+            CodeSize::of_st(&st_routine.source, st_routine.pou.as_ref()),
+        );
+    }
+
     stats.tag_references = all_refs.len();
     stats.unique_tags = tag_xref.len();
 
@@ -483,6 +660,7 @@ pub fn analyze_controller(controller: &Controller) -> ProjectAnalysis {
         instruction_usage,
         aoi_definitions,
         aoi_usage,
+        protected,
         stats,
     }
 }
@@ -536,4 +714,108 @@ mod tests {
         assert!(complexity >= 3, "Expected complexity >= 3, got {}", complexity);
         assert_eq!(nesting, 2, "Expected nesting depth 2, got {}", nesting);
     }
+
+    #[test]
+    fn test_recovered_st_routine() {
+        let code = "IF a THEN\n    IF b THEN\n        x := ;\n        y := 1;\n    END_IF;\nEND_IF;\nz := (2;";
+        let routine = super::super::parse_st_body(STLocation::new("Main", "Logic"), RoutineKind::Program, code.to_string());
+        assert!(routine.is_recovered());
+        assert!(routine.parse_error.is_some());
+        assert_eq!(
+            routine.skipped_lines(),
+            [(3, "invalid expression".to_string()), (7, "unexpected token, expected )".to_string())]
+        );
+        let pou = routine.pou.as_ref().unwrap();
+        assert_eq!(iec61131::analysis::max_nesting_depth(&pou.body), 2);
+
+        let routine = super::super::parse_st_body(STLocation::new("Main", "Ok"), RoutineKind::Program, "x := 1;".to_string());
+        assert!(routine.is_parsed() && !routine.is_recovered());
+    }
+
+    #[test]
+    fn test_topo_order() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Line">
+                <AddOnInstructionDefinitions>
+                    <AddOnInstructionDefinition Name="Valve">
+                        <Routines>
+                            <Routine Name="Logic" Type="RLL">
+                                <RLLContent><Rung Number="0"><Text>Actuator(a);</Text></Rung></RLLContent>
+                            </Routine>
+                        </Routines>
+                    </AddOnInstructionDefinition>
+                    <AddOnInstructionDefinition Name="Actuator"/>
+                </AddOnInstructionDefinitions>
+                <Programs>
+                    <Program Name="Main">
+                        <Routines>
+                            <Routine Name="Logic" Type="ST">
+                                <STContent>
+                                    <Line Number="0"><![CDATA[Valve(Input := V1);]]></Line>
+                                </STContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let project = crate::loader::LoadedProject::from_str(xml, None).expect("Should parse");
+        let analysis = analyze_controller(project.l5x_controller.as_ref().unwrap());
+        assert_eq!(analysis.topo_order().unwrap(), ["Actuator", "Valve", "Main"]);
+
+        let cyclic = xml.replace("<AddOnInstructionDefinition Name=\"Actuator\"/>", r#"
+            <AddOnInstructionDefinition Name="Actuator">
+                <Routines>
+                    <Routine Name="Logic" Type="RLL">
+                        <RLLContent><Rung Number="0"><Text>Valve(v);</Text></Rung></RLLContent>
+                    </Routine>
+                </Routines>
+            </AddOnInstructionDefinition>"#);
+        let project = crate::loader::LoadedProject::from_str(&cyclic, None).expect("Should parse");
+        let analysis = analyze_controller(project.l5x_controller.as_ref().unwrap());
+        assert_eq!(analysis.topo_order().unwrap_err().pous, ["Actuator", "Valve"]);
+    }
+
+    #[test]
+    fn test_locate_rungs() {
+        let xml = r#"<?xml version="1.0"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+<Controller Name="Line">
+<Programs>
+<Program Name="Main">
+<Routines>
+<Routine Name="Ladder" Type="RLL">
+<RLLContent>
+<Rung Number="0" Type="N">
+<Text>
+<![CDATA[XIC(Start)OTE(Run);]]>
+</Text>
+</Rung>
+<Rung Number="1" Type="N">
+<Text>
+<![CDATA[XIC(Run)OTE(Motor;]]>
+</Text>
+</Rung>
+</RLLContent>
+</Routine>
+</Routines>
+</Program>
+</Programs>
+</Controller>
+</RSLogix5000Content>"#;
+
+        let project = crate::loader::LoadedProject::from_str(xml, None).expect("Should parse");
+        let mut analysis = analyze_controller(project.l5x_controller.as_ref().unwrap());
+        assert!(analysis.parse_errors()[0].file_position().is_none());
+
+        analysis.locate_rungs(xml);
+        assert_eq!(analysis.rungs[0].text_start, Some(TextPosition { line: 11, column: 10 }));
+        let errors = analysis.parse_errors();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].rung_position(), Some(TextPosition { line: 1, column: 4 }));
+        assert_eq!(errors[0].file_position(), Some(TextPosition { line: 16, column: 13 }));
+        assert!(errors[0].format().contains("Main/Ladder/Rung#1\nat file position 16:13"));
+    }
 }
@@ -0,0 +1,368 @@
+//! Marker keywords in comments and descriptions.
+//!
+//! Collects the free text of a project: ST comments (the comment spans of
+//! the `iecst` lexer), L5X descriptions and rung comments, and PLCopen
+//! documentation. Leftover markers such as `TODO` or `FIXME` in that text
+//! are found by whole-word, case-sensitive matching, so prose like
+//! "todo" (Spanish "all") or "hackathon" is not a marker.
+
+use l5x::{
+    CommentWide, CommentWideContent, Controller, Description, DescriptionContent, RoutineContent, RungContent,
+    Tag, TagContent, UDIDefinitionContent,
+};
+use plcopen::{FormattedText, VarListPlain_variable_Inline as Variable};
+
+use super::StUnits;
+
+/// Free text of a project: a comment or description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProjectText {
+    /// Location for findings (e.g., "Program:Main/Ladder/Rung#3")
+    pub location: String,
+    /// Line in the ST routine or POU body, for ST comments
+    pub line: Option<usize>,
+    /// The text, with comment delimiters for ST comments
+    pub text: String,
+}
+
+/// A marker keyword found in a comment or description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentMarker {
+    /// The keyword found (e.g., "TODO")
+    pub keyword: String,
+    /// Location for findings
+    pub location: String,
+    /// Line in the ST routine or POU body, for ST comments
+    pub line: Option<usize>,
+    /// The line of the text with the marker, trimmed and shortened
+    pub snippet: String,
+}
+
+/// Longest snippet kept, in characters.
+const MAX_SNIPPET: usize = 80;
+
+/// Comments of the ST units, one per comment.
+pub fn texts_from_st(st_units: &StUnits) -> Vec<ProjectText> {
+    let mut texts = Vec::new();
+    for unit in &st_units.units {
+        let location = if unit.location.ends_with(&unit.name) {
+            unit.location.clone()
+        } else {
+            format!("{}/{}", unit.location, unit.name)
+        };
+        for span in iecst::comment_spans(&unit.source) {
+            texts.push(ProjectText {
+                location: location.clone(),
+                line: Some(unit.line_of(span.start)),
+                text: unit.source[span.start..span.end].to_string(),
+            });
+        }
+    }
+    texts
+}
+
+/// Descriptions and rung comments of an L5X controller.
+pub fn texts_from_controller(controller: &Controller) -> Vec<ProjectText> {
+    let mut texts = Vec::new();
+    let mut push = |location: String, text: Option<String>| {
+        if let Some(text) = text {
+            texts.push(ProjectText { location, line: None, text });
+        }
+    };
+
+    push("Controller".to_string(), controller.description.as_ref().and_then(description_text));
+
+    if let Some(ref datatypes) = controller.data_types {
+        for dt in &datatypes.data_type {
+            push(format!("DataType:{}", dt.name), dt.description.as_ref().and_then(description_text));
+        }
+    }
+
+    if let Some(ref tags) = controller.tags {
+        for tag in &tags.tag {
+            for text in tag_texts(tag) {
+                push(format!("Controller/{}", tag.name), Some(text));
+            }
+        }
+    }
+
+    if let Some(ref programs) = controller.programs {
+        for program in &programs.program {
+            let location = format!("Program:{}", program.name);
+            push(location.clone(), program.description.as_ref().and_then(description_text));
+            if let Some(ref tags) = program.tags {
+                for tag in &tags.tag {
+                    for text in tag_texts(tag) {
+                        push(format!("{}/{}", location, tag.name), Some(text));
+                    }
+                }
+            }
+            if let Some(ref routines) = program.routines {
+                for routine in &routines.routine {
+                    for (routine_location, text) in routine_texts(&location, &routine.name, &routine.content) {
+                        push(routine_location, Some(text));
+                    }
+                }
+            }
+        }
+    }
+
+    if let Some(ref aois) = controller.add_on_instruction_definitions {
+        for aoi in &aois.add_on_instruction_definition {
+            let location = format!("AOI:{}", aoi.name);
+            for content in &aoi.content {
+                match content {
+                    UDIDefinitionContent::Description(description) => {
+                        push(location.clone(), description_text(description))
+                    }
+                    UDIDefinitionContent::Parameters(params) => {
+                        for param in &params.parameter {
+                            let text = param.description.as_ref().and_then(description_text);
+                            push(format!("{}/{}", location, param.name), text);
+                        }
+                    }
+                    UDIDefinitionContent::LocalTags(local_tags) => {
+                        for tag in &local_tags.local_tag {
+                            let text = tag.description.as_ref().and_then(description_text);
+                            push(format!("{}/{}", location, tag.name), text);
+                        }
+                    }
+                    UDIDefinitionContent::Routines(routines) => {
+                        for routine in &routines.routine {
+                            for (routine_location, text) in routine_texts(&location, &routine.name, &routine.content) {
+                                push(routine_location, Some(text));
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    texts
+}
+
+/// Description of a routine and the comments of its rungs.
+fn routine_texts(location: &str, routine: &str, content: &[RoutineContent]) -> Vec<(String, String)> {
+    let mut texts = Vec::new();
+    for item in content {
+        match item {
+            RoutineContent::Description(description) => {
+                texts.extend(description_text(description).map(|text| (format!("{}/{}", location, routine), text)));
+            }
+            RoutineContent::RLLContent(rungs) => {
+                for rung in &rungs.rung {
+                    let number = rung.number.as_deref().unwrap_or("0");
+                    for rung_item in &rung.content {
+                        if let RungContent::Comment(comment) = rung_item {
+                            texts.extend(
+                                comment_text(comment)
+                                    .map(|text| (format!("{}/{}/Rung#{}", location, routine, number), text)),
+                            );
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    texts
+}
+
+/// Description and operand comments of a tag.
+fn tag_texts(tag: &Tag) -> Vec<String> {
+    let mut texts = Vec::new();
+    for content in &tag.content {
+        match content {
+            TagContent::Description(description) => texts.extend(description_text(description)),
+            TagContent::Comments(comments) => texts.extend(comments.comment.iter().filter_map(comment_text)),
+            _ => {}
+        }
+    }
+    texts
+}
+
+fn description_text(description: &Description) -> Option<String> {
+    join_text(description.content.iter().filter_map(|content| match content {
+        DescriptionContent::TextContent(text) => Some(text.as_str()),
+        DescriptionContent::LocalizedDescription(localized) => localized.text.as_deref(),
+        _ => None,
+    }))
+}
+
+fn comment_text(comment: &CommentWide) -> Option<String> {
+    join_text(comment.content.iter().filter_map(|content| match content {
+        CommentWideContent::TextContent(text) => Some(text.as_str()),
+        CommentWideContent::LocalizedComment(localized) => localized.text.as_deref(),
+        _ => None,
+    }))
+}
+
+fn join_text<'a>(parts: impl Iterator<Item = &'a str>) -> Option<String> {
+    let text = parts.map(str::trim).filter(|part| !part.is_empty()).collect::<Vec<_>>().join("\n");
+    (!text.is_empty()).then_some(text)
+}
+
+/// Documentation of a PLCopen project's data types, POUs and variables.
+///
+/// Only the plain text of `<documentation>` is seen; text inside XHTML
+/// child elements is not part of the parsed model.
+pub fn texts_from_plcopen(project: &plcopen::Project) -> Vec<ProjectText> {
+    let mut texts = Vec::new();
+    let mut push = |location: String, text: Option<&FormattedText>| {
+        if let Some(text) = text.and_then(|t| t.text.as_deref()).and_then(|t| join_text(std::iter::once(t))) {
+            texts.push(ProjectText { location, line: None, text });
+        }
+    };
+    let push_vars = |push: &mut dyn FnMut(String, Option<&FormattedText>), vars: &[Box<Variable>], location: &str| {
+        for var in vars {
+            push(format!("{}/{}", location, var.name), var.documentation.as_ref());
+        }
+    };
+
+    let types = project.types.as_ref();
+    if let Some(data_types) = types.and_then(|t| t.data_types.as_ref()) {
+        for data_type in &data_types.data_type {
+            push(format!("dataTypes/{}", data_type.name), data_type.documentation.as_ref());
+        }
+    }
+
+    if let Some(pous) = types.and_then(|t| t.pous.as_ref()) {
+        for pou in &pous.pou {
+            push(pou.name.clone(), pou.documentation.as_ref());
+            if let Some(ref interface) = pou.interface {
+                let lists = interface
+                    .input_vars
+                    .iter()
+                    .map(|list| &list.variable)
+                    .chain(interface.output_vars.iter().map(|list| &list.variable))
+                    .chain(interface.in_out_vars.iter().map(|list| &list.variable))
+                    .chain(interface.local_vars.iter().map(|list| &list.variable))
+                    .chain(interface.temp_vars.iter().map(|list| &list.variable))
+                    .chain(interface.external_vars.iter().map(|list| &list.variable))
+                    .chain(interface.global_vars.iter().map(|list| &list.variable));
+                for vars in lists {
+                    push_vars(&mut push, vars, &pou.name);
+                }
+            }
+        }
+    }
+
+    texts
+}
+
+/// Markers in the texts: each whole-word occurrence of a keyword, with the
+/// line of the text it is on. A keyword is reported once per text line.
+pub fn find_markers(texts: &[ProjectText], keywords: &[String]) -> Vec<CommentMarker> {
+    let mut markers = Vec::new();
+    for text in texts {
+        for (index, line) in text.text.lines().enumerate() {
+            for keyword in keywords.iter().filter(|keyword| !keyword.is_empty()) {
+                if contains_word(line, keyword) {
+                    markers.push(CommentMarker {
+                        keyword: keyword.clone(),
+                        location: text.location.clone(),
+                        line: text.line.map(|line| line + index),
+                        snippet: snippet(line),
+                    });
+                }
+            }
+        }
+    }
+    markers
+}
+
+/// Whether `word` occurs in `line` not preceded or followed by an
+/// identifier character.
+fn contains_word(line: &str, word: &str) -> bool {
+    let is_ident = |c: char| c.is_alphanumeric() || c == '_';
+    line.match_indices(word).any(|(start, _)| {
+        let before = line[..start].chars().next_back();
+        let after = line[start + word.len()..].chars().next();
+        !before.is_some_and(is_ident) && !after.is_some_and(is_ident)
+    })
+}
+
+fn snippet(line: &str) -> String {
+    let line = line.trim();
+    if line.chars().count() <= MAX_SNIPPET {
+        line.to_string()
+    } else {
+        let short: String = line.chars().take(MAX_SNIPPET).collect();
+        format!("{}...", short.trim_end())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::LoadedProject;
+
+    fn keywords() -> Vec<String> {
+        ["TODO", "FIXME", "XXX", "HACK"].iter().map(|k| k.to_string()).collect()
+    }
+
+    #[test]
+    fn test_markers_in_text() {
+        let texts = [ProjectText {
+            location: "Main".to_string(),
+            line: Some(3),
+            text: "(* Fill sequence\n   TODO: check level sensor, FIXME timeout *)".to_string(),
+        }];
+        let found: Vec<_> = find_markers(&texts, &keywords())
+            .into_iter()
+            .map(|m| (m.keyword, m.line, m.snippet))
+            .collect();
+        let line = "TODO: check level sensor, FIXME timeout *)".to_string();
+        assert_eq!(found, [("TODO".to_string(), Some(4), line.clone()), ("FIXME".to_string(), Some(4), line)]);
+
+        for text in ["todo lo demás", "TODOS", "hackathon", "MY_TODO", "XXXL"] {
+            assert!(!contains_word(text, "TODO") && !contains_word(text, "HACK") && !contains_word(text, "XXX"), "{}", text);
+        }
+        assert!(snippet(&"x".repeat(100)).ends_with("..."));
+    }
+
+    #[test]
+    fn test_texts_from_controller() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+<Controller Name="Line">
+<Tags>
+<Tag Name="Level" DataType="REAL">
+<Description><![CDATA[Tank level, HACK: scaled twice]]></Description>
+</Tag>
+</Tags>
+<Programs>
+<Program Name="Main">
+<Routines>
+<Routine Name="Ladder" Type="RLL">
+<Description><![CDATA[Pump control]]></Description>
+<RLLContent>
+<Rung Number="2" Type="N">
+<Comment><![CDATA[XXX remove bypass before release]]></Comment>
+<Text><![CDATA[XIC(Start)OTE(Pump);]]></Text>
+</Rung>
+</RLLContent>
+</Routine>
+</Routines>
+</Program>
+</Programs>
+</Controller>
+</RSLogix5000Content>"#;
+
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let texts = texts_from_controller(project.l5x_controller.as_ref().unwrap());
+        let found: Vec<_> = find_markers(&texts, &keywords())
+            .into_iter()
+            .map(|m| (m.keyword, m.location, m.line))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("HACK".to_string(), "Controller/Level".to_string(), None),
+                ("XXX".to_string(), "Program:Main/Ladder/Rung#2".to_string(), None),
+            ]
+        );
+    }
+}
@@ -5,33 +5,112 @@
 //!
 //! - `l5x_analysis` - L5X-specific detailed analysis
 //! - `plcopen_analysis` - PLCopen-specific analysis
+//! - `call_graph` - POU dependencies, their order and call cycles
+//! - `comments` - comment density of ST source
+//! - `complexity` - cyclomatic complexity distribution of ST routines
+//! - `datatypes` - defined data types and the references to types by name
+//! - `duplicates` - normalized routine logic for copy-paste detection
+//! - `fbd_network` - wiring of FBD networks (unconnected pins, disconnected parts)
+//! - `il_labels` - jump labels of IL bodies
+//! - `names` - declared names, their naming categories and matching keys
+//! - `initializers` - declared variables and whether they have initial values
+//! - `instances` - function block and AOI instances and their invocations
+//! - `instructions` - RLL instruction and AOI call counts
+//! - `io_tags` - I/O module tags and produced/consumed tags
+//! - `markers` - TODO/FIXME markers in comments and descriptions
+//! - `portability` - calls of functions outside the IEC standard library
+//! - `size` - lines and statements of POUs by language
+//! - `st_units` - ST code prepared for `iecst` semantic analysis
+//! - `tag_access` - tag reads and writes by program
+//! - `tasks` - tasks and the programs they run
+//! - `timers` - timer and counter instances and their resets
 
+mod call_graph;
+mod comments;
+mod complexity;
+mod datatypes;
+mod duplicates;
+mod fbd_network;
 mod iec61131_adapter;
+mod il_labels;
+mod initializers;
+mod instances;
+mod instructions;
+mod io_tags;
 mod l5x_analysis;
+mod markers;
+mod names;
 mod plcopen_analysis;
+mod portability;
 mod rll_parsing;
+mod size;
 mod st_parsing;
+mod st_units;
+mod tag_access;
+mod tasks;
+mod timers;
 
+pub use call_graph::{topo_order, CallGraph, CycleError, PouId};
 pub use iec61131_adapter::{Pou, ParseError, parse_pou};
 
 pub use l5x_analysis::{
     ProjectAnalysis, ParseStats, RoutineSummary,
     RungLocation, LocatedRung, LocatedTagReference,
-    STLocation, ParsedSTRoutine,
+    STLocation, ParsedSTRoutine, RoutineKind,
     AoiReference, AoiCallSource,
     analyze_controller,
 };
 
 pub use plcopen_analysis::{
     PlcopenAnalysis, PlcopenStats, VariableDef, VarClass,
-    analyze_project as analyze_plcopen_project,
+    analyze_project as analyze_plcopen_project, analyze_projects as analyze_plcopen_projects, parse_st_bodies,
+    st_declarations,
 };
+pub(crate) use plcopen_analysis::extract_type_name;
 
 pub use rll_parsing::{
     parse_routine, parse_rung_collection, extract_rung_text, extract_text_content,
 };
 
+pub use instructions::{aoi_call_histogram, by_frequency, instruction_histogram};
+
+pub use comments::CommentDensity;
+
+pub use complexity::{ComplexityDistribution, NestingDistribution, COMPLEXITY_BUCKETS, NESTING_BUCKETS};
+
+pub use datatypes::{DataTypeIndex, TypeReference, TypeReferenceKind};
+
+pub use duplicates::{DuplicateGroup, DuplicateLogic, RoutineShape};
+
+pub use fbd_network::{FbdIssue, FbdIssueKind, FbdNetwork, fbd_networks_from_plcopen};
+
+pub use il_labels::{IlJump, IlLabel, IlLabels, il_labels_from_plcopen};
+
+pub use names::{name_key, NameCategory, NamedItem, names_from_controller, names_from_plcopen};
+
+pub use initializers::{DeclaredVariable, variables_from_controller, variables_from_plcopen};
+
+pub use instances::{FbInstance, InstanceUsage};
+
+pub use io_tags::{IoTag, IoTagKind, IoTags};
+
+pub use st_units::{Parameter, ParameterKind, Signature, StUnit, StUnits};
+
+pub use markers::{
+    find_markers, texts_from_controller, texts_from_plcopen, texts_from_st, CommentMarker, ProjectText,
+};
+
+pub use size::{CodeSize, PouSize, SizeMetrics};
+
+pub use portability::{is_standard_function, vendor_calls, VendorCall};
+
+pub use tag_access::{rll_operand_access, AccessKind, TagAccess, TagAccesses, TagUsage};
+
+pub use tasks::{Task, TaskBindings, TaskTrigger};
+
+pub use timers::{TimerUsage, TimerUse};
+
 pub use st_parsing::{
-    parse_st_routine, parse_st_routines_from_program, extract_st_source,
-    extract_st_call_names,
+    parse_st_routine, parse_st_routines_from_program, parse_st_body, extract_st_source,
+    extract_st_call_names, extract_st_calls,
 };
@@ -0,0 +1,251 @@
+//! Named project items for naming rules.
+//!
+//! Collects the names declared in a project (tags, variables, data types and
+//! POUs) together with the category that naming conventions apply to. The
+//! category of a variable comes from its scope: AOI parameters and PLCopen
+//! interface blocks give input/output/in-out, program tags and local
+//! variables are local, and controller tags and configuration variables are
+//! global. Variables whose type is a function block are FB instances.
+
+use std::collections::HashSet;
+
+use l5x::{Controller, UDIDefinitionContent};
+use plcopen::VarListPlain_variable_Inline as Variable;
+
+/// Key that matches a tag or variable name against other spellings of it.
+///
+/// IEC 61131-3 identifiers are case-insensitive, so `Motor` and `MOTOR` get
+/// the same key; with `case_sensitive` (for vendors that enforce case) the
+/// name is its own key.
+pub fn name_key(name: &str, case_sensitive: bool) -> String {
+    if case_sensitive {
+        name.to_string()
+    } else {
+        name.to_uppercase()
+    }
+}
+
+/// Category of a named item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NameCategory {
+    /// Function block / AOI instance
+    FbInstance,
+    /// Input parameter
+    Input,
+    /// Output parameter
+    Output,
+    /// In-out parameter
+    InOut,
+    /// Local variable or program-scope tag
+    Local,
+    /// Global variable or controller-scope tag
+    Global,
+    /// User-defined data type
+    DataType,
+    /// Program, function, function block or AOI
+    Pou,
+}
+
+impl NameCategory {
+    /// Human-readable name used in messages.
+    pub fn describe(&self) -> &'static str {
+        match self {
+            NameCategory::FbInstance => "FB instance",
+            NameCategory::Input => "Input",
+            NameCategory::Output => "Output",
+            NameCategory::InOut => "In-out",
+            NameCategory::Local => "Local variable",
+            NameCategory::Global => "Global variable",
+            NameCategory::DataType => "Data type",
+            NameCategory::Pou => "POU",
+        }
+    }
+}
+
+/// A declared name with its category and location.
+#[derive(Debug, Clone)]
+pub struct NamedItem {
+    /// Naming category
+    pub category: NameCategory,
+    /// Declared name
+    pub name: String,
+    /// Location for findings (e.g., "Program:Main")
+    pub location: String,
+}
+
+/// Standard IEC 61131-3 function blocks.
+const STANDARD_FBS: &[&str] = &[
+    "TON", "TOF", "TP", "CTU", "CTD", "CTUD", "R_TRIG", "F_TRIG", "SR", "RS",
+];
+
+/// Collect the named items of an L5X controller.
+pub fn names_from_controller(controller: &Controller) -> Vec<NamedItem> {
+    let mut items = Vec::new();
+
+    let aoi_names: HashSet<String> = controller
+        .add_on_instruction_definitions
+        .as_ref()
+        .map(|aois| {
+            aois.add_on_instruction_definition
+                .iter()
+                .map(|aoi| aoi.name.to_uppercase())
+                .collect()
+        })
+        .unwrap_or_default();
+    let variable = |name: &str, data_type: Option<&str>, category: NameCategory, location: &str| {
+        let is_instance = data_type.is_some_and(|dt| aoi_names.contains(&dt.to_uppercase()));
+        NamedItem {
+            category: if is_instance { NameCategory::FbInstance } else { category },
+            name: name.to_string(),
+            location: location.to_string(),
+        }
+    };
+
+    if let Some(ref datatypes) = controller.data_types {
+        for dt in &datatypes.data_type {
+            items.push(item(NameCategory::DataType, &dt.name, "DataTypes"));
+        }
+    }
+
+    if let Some(ref tags) = controller.tags {
+        for tag in &tags.tag {
+            items.push(variable(&tag.name, tag.data_type.as_deref(), NameCategory::Global, "Controller"));
+        }
+    }
+
+    if let Some(ref programs) = controller.programs {
+        for program in &programs.program {
+            let location = format!("Program:{}", program.name);
+            items.push(item(NameCategory::Pou, &program.name, &location));
+            if let Some(ref tags) = program.tags {
+                for tag in &tags.tag {
+                    items.push(variable(&tag.name, tag.data_type.as_deref(), NameCategory::Local, &location));
+                }
+            }
+        }
+    }
+
+    if let Some(ref aois) = controller.add_on_instruction_definitions {
+        for aoi in &aois.add_on_instruction_definition {
+            let location = format!("AOI:{}", aoi.name);
+            items.push(item(NameCategory::Pou, &aoi.name, &location));
+            for content in &aoi.content {
+                match content {
+                    UDIDefinitionContent::Parameters(params) => {
+                        for param in &params.parameter {
+                            if param.name.eq_ignore_ascii_case("EnableIn")
+                                || param.name.eq_ignore_ascii_case("EnableOut")
+                            {
+                                continue;
+                            }
+                            let category = match param.usage.as_str() {
+                                "Output" => NameCategory::Output,
+                                "InOut" => NameCategory::InOut,
+                                _ => NameCategory::Input,
+                            };
+                            items.push(variable(&param.name, param.data_type.as_deref(), category, &location));
+                        }
+                    }
+                    UDIDefinitionContent::LocalTags(local_tags) => {
+                        for tag in &local_tags.local_tag {
+                            items.push(variable(&tag.name, Some(&tag.data_type), NameCategory::Local, &location));
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
+    items
+}
+
+/// Collect the named items of a PLCopen project.
+pub fn names_from_plcopen(project: &plcopen::Project) -> Vec<NamedItem> {
+    let mut items = Vec::new();
+
+    let types = project.types.as_ref();
+    let pous = types.and_then(|t| t.pous.as_ref());
+
+    let fb_names: HashSet<String> = pous
+        .map(|pous| {
+            pous.pou
+                .iter()
+                .filter(|pou| pou.pou_type.eq_ignore_ascii_case("functionBlock"))
+                .map(|pou| pou.name.to_uppercase())
+                .collect()
+        })
+        .unwrap_or_default();
+    let is_fb = |data: Option<&plcopen::Data>| {
+        data.and_then(|d| d.derived.as_ref()).is_some_and(|derived| {
+            let name = derived.name.to_uppercase();
+            fb_names.contains(&name) || STANDARD_FBS.contains(&name.as_str())
+        })
+    };
+    let push_vars = |items: &mut Vec<NamedItem>, vars: &[Box<Variable>], category: NameCategory, location: &str| {
+        for var in vars {
+            let category = if is_fb(var.r#type.as_deref()) { NameCategory::FbInstance } else { category };
+            items.push(item(category, &var.name, location));
+        }
+    };
+
+    if let Some(data_types) = types.and_then(|t| t.data_types.as_ref()) {
+        for data_type in &data_types.data_type {
+            items.push(item(NameCategory::DataType, &data_type.name, "dataTypes"));
+        }
+    }
+
+    if let Some(pous) = pous {
+        for pou in &pous.pou {
+            items.push(item(NameCategory::Pou, &pou.name, &pou.name));
+            if let Some(ref interface) = pou.interface {
+                for list in &interface.input_vars {
+                    push_vars(&mut items, &list.variable, NameCategory::Input, &pou.name);
+                }
+                for list in &interface.output_vars {
+                    push_vars(&mut items, &list.variable, NameCategory::Output, &pou.name);
+                }
+                for list in &interface.in_out_vars {
+                    push_vars(&mut items, &list.variable, NameCategory::InOut, &pou.name);
+                }
+                for list in &interface.local_vars {
+                    push_vars(&mut items, &list.variable, NameCategory::Local, &pou.name);
+                }
+                for list in &interface.temp_vars {
+                    push_vars(&mut items, &list.variable, NameCategory::Local, &pou.name);
+                }
+                for list in &interface.global_vars {
+                    push_vars(&mut items, &list.variable, NameCategory::Global, &pou.name);
+                }
+            }
+        }
+    }
+
+    let configurations = project
+        .instances
+        .as_ref()
+        .and_then(|i| i.configurations.as_ref())
+        .map(|c| c.configuration.as_slice())
+        .unwrap_or_default();
+    for configuration in configurations {
+        for list in &configuration.global_vars {
+            push_vars(&mut items, &list.variable, NameCategory::Global, &configuration.name);
+        }
+        for resource in &configuration.resource {
+            let location = format!("{}.{}", configuration.name, resource.name);
+            for list in &resource.global_vars {
+                push_vars(&mut items, &list.variable, NameCategory::Global, &location);
+            }
+        }
+    }
+
+    items
+}
+
+fn item(category: NameCategory, name: &str, location: &str) -> NamedItem {
+    NamedItem {
+        category,
+        name: name.to_string(),
+        location: location.to_string(),
+    }
+}
@@ -12,9 +12,19 @@ use plcopen::{
     Root_project_InlineType_types_InlineType_pous_InlineType_pou_Inline as Pou,
     VarListPlain_variable_Inline as Variable,
 };
+use serde::Serialize;
+
+use crate::bundle::Bundle;
+
+use super::comments::CommentDensity;
+use super::complexity::{ComplexityDistribution, NestingDistribution};
+use super::call_graph::{topo_order, CallGraph, CycleError, PouId};
+use super::names::name_key;
+use super::size::{CodeSize, SizeMetrics};
+use super::{parse_st_body, ParsedSTRoutine, RoutineKind, STLocation};
 
 /// Statistics from parsing a PLCopen project.
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct PlcopenStats {
     pub pous: usize,
     pub programs: usize,
@@ -27,6 +37,14 @@ pub struct PlcopenStats {
     pub sfc_bodies: usize,
     pub il_bodies: usize,
     pub empty_pous: usize,
+    /// Comment and code characters of all ST bodies
+    pub st_comments: CommentDensity,
+    /// Complexity of each parsed ST body, by bucket and by POU name
+    pub st_complexity: ComplexityDistribution,
+    /// Maximum nesting depth of each parsed ST body, by bucket and by POU name
+    pub st_nesting: NestingDistribution,
+    /// Lines and statements of each ST and IL body, by POU name
+    pub sizes: SizeMetrics,
 }
 
 /// A variable definition with its scope.
@@ -58,6 +76,13 @@ pub struct PlcopenAnalysis {
     
     /// Set of all defined variable names (for quick lookup)
     pub defined_var_names: HashSet<String>,
+
+    /// VAR_GLOBAL variables of configurations and resources, with the
+    /// configuration (`Config`) or resource (`Config.Resource`) as `pou_name`
+    pub global_variables: Vec<VariableDef>,
+
+    /// VAR_EXTERNAL variables of the POUs
+    pub external_variables: Vec<VariableDef>,
     
     /// Variables referenced in code
     pub used_variables: HashSet<String>,
@@ -65,43 +90,151 @@ pub struct PlcopenAnalysis {
     /// POUs that are called/instantiated
     pub used_pous: HashSet<String>,
     
+    /// Names called like a function in ST code (`Name(...)`)
+    pub called_names: HashSet<String>,
+    
+    /// FB instances named by FBD/LD/SFC blocks
+    pub block_instances: HashSet<String>,
+    
     /// POUs with empty bodies
     pub empty_pous: Vec<String>,
     
+    /// Source-protected POUs without a body, which are not analyzed
+    pub protected_pous: Vec<String>,
+    
     /// All POU names
     pub pou_names: HashSet<String>,
     
+    /// Names each POU calls or instantiates: ST calls, FBD/LD block types
+    /// and the data types of its variables (resolved in `call_graph`)
+    pub pou_dependencies: HashMap<String, HashSet<String>>,
+    
     /// Statistics
     pub stats: PlcopenStats,
 }
 
 impl PlcopenAnalysis {
+    /// Calls and instantiations between the POUs of the project.
+    ///
+    /// Names are matched case-insensitively; calls of functions, types and
+    /// blocks that aren't POUs of the project are left out.
+    pub fn call_graph(&self) -> CallGraph {
+        let pous: HashMap<String, &str> =
+            self.pou_names.iter().map(|name| (name.to_lowercase(), name.as_str())).collect();
+        let mut graph = CallGraph::new();
+        for name in &self.pou_names {
+            graph.add_pou(name.as_str());
+        }
+        for (caller, names) in &self.pou_dependencies {
+            for name in names {
+                if let Some(&callee) = pous.get(&name.to_lowercase()) {
+                    graph.add_call(caller.as_str(), callee);
+                }
+            }
+        }
+        graph
+    }
+
+    /// POUs ordered callees first, or the POUs of a call cycle.
+    pub fn topo_order(&self) -> Result<Vec<PouId>, CycleError> {
+        topo_order(&self.call_graph())
+    }
+
     /// Get unused variables (defined but not used).
-    pub fn unused_variables(&self) -> Vec<&VariableDef> {
+    ///
+    /// Names are matched case-insensitively unless `case_sensitive`.
+    pub fn unused_variables(&self, case_sensitive: bool) -> Vec<&VariableDef> {
+        let used = name_keys(&self.used_variables, case_sensitive);
         self.defined_variables
             .values()
-            .filter(|v| !self.used_variables.contains(&v.name))
+            .filter(|v| !used.contains(&name_key(&v.name, case_sensitive)))
             .collect()
     }
     
     /// Get undefined variables (used but not defined).
-    pub fn undefined_variables(&self) -> Vec<&String> {
-        self.used_variables
+    ///
+    /// Names that are called like a function are reported by
+    /// [`undefined_calls`](Self::undefined_calls) instead. Names are matched
+    /// case-insensitively unless `case_sensitive`, and each name is returned
+    /// in one of its spellings.
+    pub fn undefined_variables(&self, case_sensitive: bool) -> Vec<&String> {
+        let defined = name_keys(&self.defined_var_names, case_sensitive);
+        let called = name_keys(&self.called_names, case_sensitive);
+        let mut seen = HashSet::new();
+        let mut undefined: Vec<&String> = self.used_variables.iter().collect();
+        undefined.sort();
+        undefined.retain(|v| {
+            let key = name_key(v, case_sensitive);
+            !defined.contains(&key) && !called.contains(&key) && !is_builtin(v) && seen.insert(key)
+        });
+        undefined
+    }
+    
+    /// Get undefined calls (called in ST but neither a POU nor a variable).
+    ///
+    /// Names are matched case-insensitively unless `case_sensitive`.
+    pub fn undefined_calls(&self, case_sensitive: bool) -> Vec<&String> {
+        let defined = name_keys(&self.defined_var_names, case_sensitive);
+        let pous = name_keys(&self.pou_names, case_sensitive);
+        let mut seen = HashSet::new();
+        let mut undefined: Vec<&String> = self.called_names.iter().collect();
+        undefined.sort();
+        undefined.retain(|name| {
+            let key = name_key(name, case_sensitive);
+            !defined.contains(&key) && !pous.contains(&key) && !is_builtin(name) && seen.insert(key)
+        });
+        undefined
+    }
+
+    /// Get configuration and resource globals that no POU references.
+    ///
+    /// Names are matched case-insensitively unless `case_sensitive`.
+    pub fn unused_globals(&self, case_sensitive: bool) -> Vec<&VariableDef> {
+        let used = name_keys(&self.used_variables, case_sensitive);
+        self.global_variables
             .iter()
-            .filter(|v| !self.defined_var_names.contains(*v) && !is_builtin(v))
+            .filter(|v| !used.contains(&name_key(&v.name, case_sensitive)))
+            .collect()
+    }
+
+    /// Get VAR_EXTERNAL variables that no configuration or resource
+    /// declares as VAR_GLOBAL.
+    ///
+    /// Empty for projects without configuration globals (libraries), whose
+    /// externals are declared by the project that uses them. Names are
+    /// matched case-insensitively unless `case_sensitive`.
+    pub fn undeclared_externals(&self, case_sensitive: bool) -> Vec<&VariableDef> {
+        if self.global_variables.is_empty() {
+            return Vec::new();
+        }
+        let globals: HashSet<String> =
+            self.global_variables.iter().map(|v| name_key(&v.name, case_sensitive)).collect();
+        self.external_variables
+            .iter()
+            .filter(|v| !globals.contains(&name_key(&v.name, case_sensitive)))
             .collect()
     }
 }
 
+fn name_keys(names: &HashSet<String>, case_sensitive: bool) -> HashSet<String> {
+    names.iter().map(|name| name_key(name, case_sensitive)).collect()
+}
+
 /// Analyze a PLCopen project.
 pub fn analyze_project(project: &Project) -> PlcopenAnalysis {
     let mut analysis = PlcopenAnalysis {
         defined_variables: HashMap::new(),
         defined_var_names: HashSet::new(),
+        global_variables: Vec::new(),
+        external_variables: Vec::new(),
         used_variables: HashSet::new(),
         used_pous: HashSet::new(),
+        called_names: HashSet::new(),
+        block_instances: HashSet::new(),
         empty_pous: Vec::new(),
+        protected_pous: Vec::new(),
         pou_names: HashSet::new(),
+        pou_dependencies: HashMap::new(),
         stats: PlcopenStats::default(),
     };
     
@@ -113,10 +246,108 @@ pub fn analyze_project(project: &Project) -> PlcopenAnalysis {
             }
         }
     }
+
+    // Globals of configurations and resources
+    let configurations = project
+        .instances
+        .as_ref()
+        .and_then(|i| i.configurations.as_ref())
+        .map(|c| c.configuration.as_slice())
+        .unwrap_or_default();
+    for configuration in configurations {
+        for var_list in &configuration.global_vars {
+            for var in &var_list.variable {
+                add_global(var, &configuration.name, &mut analysis);
+            }
+        }
+        for resource in &configuration.resource {
+            let scope = format!("{}.{}", configuration.name, resource.name);
+            for var_list in &resource.global_vars {
+                for var in &var_list.variable {
+                    add_global(var, &scope, &mut analysis);
+                }
+            }
+        }
+    }
+
+    for st_body in parse_st_bodies(project) {
+        if let Some(ref pou) = st_body.pou {
+            let complexity = iec61131::analysis::CfgBuilder::new().build(&pou.body).cyclomatic_complexity();
+            analysis.stats.st_complexity.add(st_body.location.routine.clone(), complexity);
+            let nesting = iec61131::analysis::max_nesting_depth(&pou.body);
+            analysis.stats.st_nesting.add(st_body.location.routine.clone(), nesting);
+        }
+        let size = CodeSize::of_st(&st_body.source, st_body.pou.as_ref());
+        analysis.stats.sizes.add(st_body.location.routine, "ST", size);
+    }
     
     analysis
 }
 
+/// Analyze PLCopen projects split across several files (for example a
+/// library and a project) as one project.
+///
+/// Data types and POUs are combined, so calls and types defined in another
+/// file resolve; see [`Bundle::merge_plcopen`] for duplicate definitions.
+pub fn analyze_projects(projects: &[Project]) -> PlcopenAnalysis {
+    let members = projects.iter().enumerate().map(|(i, project)| (i.to_string(), project.clone())).collect();
+    match Bundle::merge_plcopen(members) {
+        Some((merged, _)) => analyze_project(&merged),
+        None => analyze_project(&Project::default()),
+    }
+}
+
+/// Parse the ST bodies of all POUs for metrics (complexity, nesting).
+pub fn parse_st_bodies(project: &Project) -> Vec<ParsedSTRoutine> {
+    let Some(pous) = project.types.as_ref().and_then(|t| t.pous.as_ref()) else {
+        return Vec::new();
+    };
+
+    pous.pou
+        .iter()
+        .filter_map(|pou| {
+            let source = pou
+                .body
+                .iter()
+                .find_map(|body| body.st.as_ref().and_then(|st| st.text.clone()))?;
+            let kind = match pou.pou_type.to_lowercase().as_str() {
+                "function" => RoutineKind::Function,
+                "functionblock" => RoutineKind::FunctionBlock,
+                _ => RoutineKind::Program,
+            };
+            Some(parse_st_body(STLocation::new(&pou.name, &pou.name), kind, source))
+        })
+        .collect()
+}
+
+/// Parse the ST bodies that hold complete declarations.
+///
+/// Some exports keep a function block or class with its methods as text in
+/// the body of a POU. Bodies starting with `FUNCTION_BLOCK`, `CLASS` or
+/// `NAMESPACE` are parsed as compilation units, by POU name; bodies that
+/// fail to parse are skipped.
+pub fn st_declarations(project: &Project) -> Vec<(String, iec61131::CompilationUnit)> {
+    let Some(pous) = project.types.as_ref().and_then(|t| t.pous.as_ref()) else {
+        return Vec::new();
+    };
+
+    pous.pou
+        .iter()
+        .filter_map(|pou| {
+            let source = pou
+                .body
+                .iter()
+                .find_map(|body| body.st.as_ref().and_then(|st| st.text.as_deref()))?;
+            let first = source.split_whitespace().next()?.to_uppercase();
+            if !matches!(first.as_str(), "FUNCTION_BLOCK" | "CLASS" | "NAMESPACE") {
+                return None;
+            }
+            let unit = iec61131::Parser::new(source).parse().ok()?;
+            Some((pou.name.clone(), unit))
+        })
+        .collect()
+}
+
 fn analyze_pou(pou: &Pou, analysis: &mut PlcopenAnalysis) {
     analysis.stats.pous += 1;
     analysis.pou_names.insert(pou.name.clone());
@@ -181,12 +412,23 @@ fn analyze_pou(pou: &Pou, analysis: &mut PlcopenAnalysis) {
         }
     }
     
-    // Analyze body
+    // Analyze body, collecting the POUs it uses separately to record them
+    let used_pous = std::mem::take(&mut analysis.used_pous);
+    let called_names = std::mem::take(&mut analysis.called_names);
     let has_code = analyze_bodies(&pou.body, &pou.name, analysis);
+    let dependencies = analysis.pou_dependencies.entry(pou.name.clone()).or_default();
+    dependencies.extend(analysis.used_pous.iter().cloned());
+    dependencies.extend(analysis.called_names.iter().cloned());
+    analysis.used_pous.extend(used_pous);
+    analysis.called_names.extend(called_names);
     
     if !has_code {
-        analysis.empty_pous.push(pou.name.clone());
-        analysis.stats.empty_pous += 1;
+        if pou.is_source_protected() {
+            analysis.protected_pous.push(pou.name.clone());
+        } else {
+            analysis.empty_pous.push(pou.name.clone());
+            analysis.stats.empty_pous += 1;
+        }
     }
 }
 
@@ -195,6 +437,9 @@ fn add_variable(var: &Variable, pou_name: &str, var_class: VarClass, analysis: &
     
     let data_type = var.r#type.as_ref().and_then(|t| extract_type_name(t.as_ref()));
     
+    if let Some(ref data_type) = data_type {
+        analysis.pou_dependencies.entry(pou_name.to_string()).or_default().insert(data_type.clone());
+    }
     let def = VariableDef {
         name: var.name.clone(),
         pou_name: pou_name.to_string(),
@@ -202,11 +447,26 @@ fn add_variable(var: &Variable, pou_name: &str, var_class: VarClass, analysis: &
         data_type,
     };
     
+    if var_class == VarClass::External {
+        analysis.external_variables.push(def.clone());
+    }
     analysis.defined_var_names.insert(var.name.clone());
     analysis.defined_variables.insert(var.name.clone(), def);
 }
 
-fn analyze_bodies(bodies: &[Box<Body>], _pou_name: &str, analysis: &mut PlcopenAnalysis) -> bool {
+/// Record a VAR_GLOBAL of a configuration or resource (`scope`).
+fn add_global(var: &Variable, scope: &str, analysis: &mut PlcopenAnalysis) {
+    analysis.stats.variables += 1;
+    analysis.defined_var_names.insert(var.name.clone());
+    analysis.global_variables.push(VariableDef {
+        name: var.name.clone(),
+        pou_name: scope.to_string(),
+        var_class: VarClass::Global,
+        data_type: var.r#type.as_ref().and_then(|t| extract_type_name(t.as_ref())),
+    });
+}
+
+fn analyze_bodies(bodies: &[Box<Body>], pou_name: &str, analysis: &mut PlcopenAnalysis) -> bool {
     let mut has_code = false;
     
     for body in bodies {
@@ -214,6 +474,7 @@ fn analyze_bodies(bodies: &[Box<Body>], _pou_name: &str, analysis: &mut PlcopenA
         if let Some(ref st) = body.st {
             analysis.stats.st_bodies += 1;
             if let Some(text) = extract_formatted_text(st) {
+                analysis.stats.st_comments += CommentDensity::of(&text);
                 if !text.trim().is_empty() {
                     has_code = true;
                     extract_references_from_st(&text, analysis);
@@ -225,6 +486,7 @@ fn analyze_bodies(bodies: &[Box<Body>], _pou_name: &str, analysis: &mut PlcopenA
         if let Some(ref il) = body.il {
             analysis.stats.il_bodies += 1;
             if let Some(text) = extract_formatted_text(il) {
+                analysis.stats.sizes.add(pou_name, "IL", CodeSize::of_il(&text));
                 if !text.trim().is_empty() {
                     has_code = true;
                     extract_references_from_il(&text, analysis);
@@ -274,14 +536,22 @@ fn extract_references_from_st(code: &str, analysis: &mut PlcopenAnalysis) {
     let code = remove_plc_comments(code);
     
     // Simple extraction: find identifiers that could be variables
-    for word in code.split(|c: char| !c.is_alphanumeric() && c != '_') {
-        let word = word.trim();
-        if !word.is_empty() 
-            && is_identifier(word)
-            && !is_st_keyword(word)
-        {
+    let mut start = None;
+    for (i, c) in code.char_indices().chain(std::iter::once((code.len(), ' '))) {
+        if c.is_alphanumeric() || c == '_' {
+            start.get_or_insert(i);
+            continue;
+        }
+        let Some(word_start) = start.take() else {
+            continue;
+        };
+        let word = &code[word_start..i];
+        if is_identifier(word) && !is_st_keyword(word) {
             // Could be a variable or POU call
             analysis.used_variables.insert(word.to_string());
+            if code[i..].trim_start().starts_with('(') {
+                analysis.called_names.insert(word.to_string());
+            }
         }
     }
 }
@@ -339,6 +609,7 @@ fn extract_references_from_fbd(fbd: &plcopen::Body_FBD_Inline, analysis: &mut Pl
             let name = instance_name.trim();
             if !name.is_empty() {
                 analysis.used_variables.insert(name.to_string());
+                analysis.block_instances.insert(name.to_string());
             }
         }
     }
@@ -411,6 +682,7 @@ fn extract_references_from_ld(ld: &plcopen::Body_LD_Inline, analysis: &mut Plcop
             let name = instance_name.trim();
             if !name.is_empty() {
                 analysis.used_variables.insert(name.to_string());
+                analysis.block_instances.insert(name.to_string());
             }
         }
     }
@@ -501,6 +773,7 @@ fn extract_references_from_sfc(sfc: &plcopen::Body_SFC_Inline, analysis: &mut Pl
             let name = instance_name.trim();
             if !name.is_empty() {
                 analysis.used_variables.insert(name.to_string());
+                analysis.block_instances.insert(name.to_string());
             }
         }
     }
@@ -659,10 +932,35 @@ fn is_identifier(s: &str) -> bool {
     s.chars().all(|c| c.is_alphanumeric() || c == '_')
 }
 
-fn extract_type_name(_data: &plcopen::Data) -> Option<String> {
-    // Data type extraction - the generated struct doesn't capture type details yet
-    // TODO: Improve PLCopen codegen to capture type information
-    None
+/// Name of an elementary, string or derived PLCopen data type.
+pub(crate) fn extract_type_name(data: &plcopen::Data) -> Option<String> {
+    let elementary = [
+        (data.bool.is_some(), "BOOL"),
+        (data.byte.is_some(), "BYTE"),
+        (data.word.is_some(), "WORD"),
+        (data.dword.is_some(), "DWORD"),
+        (data.lword.is_some(), "LWORD"),
+        (data.sint.is_some(), "SINT"),
+        (data.int.is_some(), "INT"),
+        (data.dint.is_some(), "DINT"),
+        (data.lint.is_some(), "LINT"),
+        (data.usint.is_some(), "USINT"),
+        (data.uint.is_some(), "UINT"),
+        (data.udint.is_some(), "UDINT"),
+        (data.ulint.is_some(), "ULINT"),
+        (data.real.is_some(), "REAL"),
+        (data.lreal.is_some(), "LREAL"),
+        (data.time.is_some(), "TIME"),
+        (data.date.is_some(), "DATE"),
+        (data.dt.is_some(), "DT"),
+        (data.tod.is_some(), "TOD"),
+        (data.string.is_some(), "STRING"),
+        (data.wstring.is_some(), "WSTRING"),
+    ];
+    if let Some((_, name)) = elementary.iter().find(|(present, _)| *present) {
+        return Some(name.to_string());
+    }
+    data.derived.as_ref().map(|derived| derived.name.clone())
 }
 
 fn is_st_keyword(word: &str) -> bool {
@@ -753,4 +1051,28 @@ mod tests {
         assert!(is_builtin("CTU"));
         assert!(!is_builtin("MyFB"));
     }
+    
+    #[test]
+    fn test_topo_order() {
+        let project = plcopen::from_str(
+            r#"<project xmlns="http://www.plcopen.org/xml/tc6_0200"><contentHeader name="Line"/><types><pous>
+                <pou name="Main" pouType="program">
+                    <interface><localVars>
+                        <variable name="conveyor"><type><derived name="Conveyor"/></type></variable>
+                    </localVars></interface>
+                    <body><ST><![CDATA[conveyor(speed := SCALE(10));]]></ST></body>
+                </pou>
+                <pou name="Conveyor" pouType="functionBlock">
+                    <body><ST><![CDATA[out := Scale(speed);]]></ST></body>
+                </pou>
+                <pou name="Scale" pouType="function">
+                    <body><ST><![CDATA[Scale := raw * 2;]]></ST></body>
+                </pou>
+            </pous></types></project>"#,
+        )
+        .unwrap();
+
+        let analysis = analyze_project(&project);
+        assert_eq!(analysis.topo_order().unwrap(), ["Scale", "Conveyor", "Main"]);
+    }
 }
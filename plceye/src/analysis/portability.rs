@@ -0,0 +1,269 @@
+//! Vendor-specific function calls.
+//!
+//! Lists the ST calls of functions and function blocks that are neither in
+//! the IEC 61131-3 standard library nor defined by the project, to estimate
+//! the work of moving a project to another vendor's platform.
+
+use std::collections::HashSet;
+
+use iecst::{Expr, ExprKind, Stmt, StmtKind, TypeKind};
+
+use super::StUnits;
+
+/// Standard functions of IEC 61131-3 (3rd edition), besides the type
+/// conversions (`INT_TO_REAL`, `TO_REAL`, `TRUNC_INT`, `BCD_TO_INT`).
+const STANDARD_FUNCTIONS: &[&str] = &[
+    // Numeric
+    "ABS", "SQRT", "LN", "LOG", "EXP", "SIN", "COS", "TAN", "ASIN", "ACOS", "ATAN", "ATAN2",
+    // Arithmetic
+    "ADD", "MUL", "SUB", "DIV", "MOD", "EXPT", "MOVE", "TRUNC",
+    // Bit shift and bitwise
+    "SHL", "SHR", "ROL", "ROR", "AND", "OR", "XOR", "NOT",
+    // Selection
+    "SEL", "MAX", "MIN", "LIMIT", "MUX",
+    // Comparison
+    "GT", "GE", "EQ", "LE", "LT", "NE",
+    // String
+    "LEN", "LEFT", "RIGHT", "MID", "CONCAT", "INSERT", "DELETE", "REPLACE", "FIND",
+    // Time and date
+    "ADD_TIME", "ADD_TOD_TIME", "ADD_DT_TIME", "SUB_TIME", "SUB_DATE_DATE", "SUB_TOD_TIME", "SUB_TOD_TOD",
+    "SUB_DT_TIME", "SUB_DT_DT", "MUL_TIME", "DIV_TIME", "CONCAT_DATE_TOD", "CONCAT_DATE", "CONCAT_TOD",
+    "CONCAT_DT", "SPLIT_DATE", "SPLIT_TOD", "SPLIT_DT", "DAY_OF_WEEK",
+    // References
+    "REF",
+];
+
+/// Standard function blocks of IEC 61131-3 (3rd edition).
+const STANDARD_FUNCTION_BLOCKS: &[&str] = &[
+    "SR", "RS", "R_TRIG", "F_TRIG", "CTU", "CTD", "CTUD", "CTU_INT", "CTU_DINT", "CTU_LINT", "CTU_UDINT",
+    "CTU_ULINT", "CTD_INT", "CTD_DINT", "CTD_LINT", "CTD_UDINT", "CTD_ULINT", "CTUD_INT", "CTUD_DINT",
+    "CTUD_LINT", "CTUD_UDINT", "CTUD_ULINT", "TP", "TON", "TOF", "TP_TIME", "TON_TIME", "TOF_TIME", "TP_LTIME",
+    "TON_LTIME", "TOF_LTIME",
+];
+
+/// Elementary data types, the operands of the standard conversions.
+pub(crate) const ELEMENTARY_TYPES: &[&str] = &[
+    "BOOL", "BYTE", "WORD", "DWORD", "LWORD", "SINT", "INT", "DINT", "LINT", "USINT", "UINT", "UDINT", "ULINT",
+    "REAL", "LREAL", "TIME", "LTIME", "DATE", "LDATE", "TIME_OF_DAY", "TOD", "LTOD", "DATE_AND_TIME", "DT", "LDT",
+    "STRING", "WSTRING", "CHAR", "WCHAR", "BCD",
+];
+
+/// Whether `name` is a standard IEC 61131-3 function, type conversion or
+/// function block (case-insensitive).
+pub fn is_standard_function(name: &str) -> bool {
+    let upper = name.to_uppercase();
+    if STANDARD_FUNCTIONS.contains(&upper.as_str()) || STANDARD_FUNCTION_BLOCKS.contains(&upper.as_str()) {
+        return true;
+    }
+    let is_type = |ty: &str| ELEMENTARY_TYPES.contains(&ty);
+    if let Some((from, to)) = upper.split_once("_TO_") {
+        return is_type(from) && is_type(to);
+    }
+    upper.strip_prefix("TO_").or_else(|| upper.strip_prefix("TRUNC_")).is_some_and(is_type)
+}
+
+/// Whether `name` is a standard IEC 61131-3 function block (case-insensitive).
+pub(crate) fn is_standard_function_block(name: &str) -> bool {
+    STANDARD_FUNCTION_BLOCKS.contains(&name.to_uppercase().as_str())
+}
+
+/// A call of a function or function block outside the standard library and
+/// the project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VendorCall {
+    /// Called function, or function block type of a called instance
+    pub name: String,
+    /// Called instance, for function block calls
+    pub instance: Option<String>,
+    /// Location for findings (e.g., "Program:Main")
+    pub location: String,
+    /// Routine or POU name
+    pub routine: String,
+    /// Line in the routine, from 1
+    pub line: usize,
+}
+
+/// Calls in the ST units that are vendor extensions.
+///
+/// Calls of `project_pous` (the project's own POUs and AOIs) and of the
+/// `extra_standard` names are not reported, nor calls of instances whose
+/// type is one of those. Each name is reported once per line.
+pub fn vendor_calls<'a>(
+    st_units: &StUnits,
+    project_pous: impl IntoIterator<Item = &'a str>,
+    extra_standard: &[String],
+) -> Vec<VendorCall> {
+    let known: HashSet<String> = project_pous
+        .into_iter()
+        .map(str::to_uppercase)
+        .chain(extra_standard.iter().map(|name| name.to_uppercase()))
+        .collect();
+    let is_vendor = |name: &str| !known.contains(&name.to_uppercase()) && !is_standard_function(name);
+
+    let mut calls = Vec::new();
+    for unit in &st_units.units {
+        let Some(ref pou) = unit.pou else {
+            continue;
+        };
+        let mut found = Vec::new();
+        collect_calls(&pou.body, &mut found);
+
+        let mut seen = HashSet::new();
+        for (name, offset) in found {
+            let declared = pou
+                .var_blocks
+                .iter()
+                .flat_map(|block| &block.vars)
+                .find(|var| var.name.eq_ignore_ascii_case(&name));
+            let (called, instance) = match declared {
+                Some(var) => match var.var_type.kind {
+                    TypeKind::Simple(ref fb_type) => (fb_type.clone(), Some(var.name.clone())),
+                    _ => continue,
+                },
+                None => (name, None),
+            };
+            let line = unit.line_of(offset);
+            if is_vendor(&called) && seen.insert((called.to_uppercase(), line)) {
+                calls.push(VendorCall {
+                    name: called,
+                    instance,
+                    location: unit.location.clone(),
+                    routine: unit.name.clone(),
+                    line,
+                });
+            }
+        }
+    }
+    calls
+}
+
+/// Record the names and offsets of the calls in `body`.
+fn collect_calls(body: &[Stmt], calls: &mut Vec<(String, usize)>) {
+    for stmt in body {
+        let mut exprs: Vec<&Expr> = Vec::new();
+        match &stmt.kind {
+            StmtKind::Call { name, args } => {
+                calls.push((name.clone(), stmt.span.start));
+                exprs.extend(args.iter().filter_map(|a| a.value.as_ref()));
+            }
+            StmtKind::Assignment { target, value } => exprs.extend([target, value]),
+            StmtKind::If { condition, then_body, elsif_branches, else_body } => {
+                exprs.push(condition);
+                collect_calls(then_body, calls);
+                for (cond, body) in elsif_branches {
+                    exprs.push(cond);
+                    collect_calls(body, calls);
+                }
+                if let Some(else_stmts) = else_body {
+                    collect_calls(else_stmts, calls);
+                }
+            }
+            StmtKind::Case { expr, cases, else_body } => {
+                exprs.push(expr);
+                for branch in cases {
+                    collect_calls(&branch.body, calls);
+                }
+                if let Some(else_stmts) = else_body {
+                    collect_calls(else_stmts, calls);
+                }
+            }
+            StmtKind::For { from, to, by, body, .. } => {
+                exprs.extend([from, to]);
+                exprs.extend(by.as_ref());
+                collect_calls(body, calls);
+            }
+            StmtKind::While { condition, body } | StmtKind::Repeat { body, until: condition } => {
+                exprs.push(condition);
+                collect_calls(body, calls);
+            }
+            StmtKind::Return { value } => exprs.extend(value.as_ref()),
+            _ => {}
+        }
+
+        while let Some(expr) = exprs.pop() {
+            match &expr.kind {
+                ExprKind::FunctionCall { name, args } => {
+                    calls.push((name.clone(), expr.span.start));
+                    exprs.extend(args.iter().filter_map(|a| a.value.as_ref()));
+                }
+                ExprKind::BinaryOp { left, right, .. } => exprs.extend([&**left, &**right]),
+                ExprKind::UnaryOp { expr: inner, .. }
+                | ExprKind::Paren(inner)
+                | ExprKind::MemberAccess { expr: inner, .. } => exprs.push(inner),
+                ExprKind::ArrayIndex { array, indices } => {
+                    exprs.push(array);
+                    exprs.extend(indices);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::loader::LoadedProject;
+
+    #[test]
+    fn test_standard_functions() {
+        for name in ["abs", "LIMIT", "INT_TO_REAL", "time_of_day_to_string", "TO_DINT", "TRUNC_INT", "BCD_TO_UINT", "TON", "CTU_DINT"] {
+            assert!(is_standard_function(name), "{}", name);
+        }
+        for name in ["ADR", "SIZEOF", "MC_Power", "FOO_TO_INT", "TO_MOTOR", "JSR"] {
+            assert!(!is_standard_function(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_vendor_calls() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test">
+    <coordinateInfo>
+      <fbd><scaling x="1" y="1"/></fbd>
+      <ld><scaling x="1" y="1"/></ld>
+      <sfc><scaling x="1" y="1"/></sfc>
+    </coordinateInfo>
+  </contentHeader>
+  <types>
+    <dataTypes/>
+    <pous>
+      <pou name="Scale" pouType="function">
+        <interface>
+          <returnType><REAL/></returnType>
+          <inputVars><variable name="raw"><type><INT/></type></variable></inputVars>
+        </interface>
+        <body><ST><![CDATA[Scale := INT_TO_REAL(raw) / 10.0;]]></ST></body>
+      </pou>
+      <pou name="Main" pouType="program">
+        <interface>
+          <localVars>
+            <variable name="level"><type><REAL/></type></variable>
+            <variable name="delay"><type><derived name="TON"/></type></variable>
+            <variable name="axis"><type><derived name="MC_Power"/></type></variable>
+            <variable name="size"><type><DINT/></type></variable>
+          </localVars>
+        </interface>
+        <body><ST><![CDATA[level := LIMIT(0.0, Scale(raw := 5), 100.0);
+delay(IN := level > 50.0, PT := T#2s);
+axis(Enable := delay.Q);
+size := SIZEOF(level) + SIZEOF(size);
+size := ADR(level);]]></ST></body>
+      </pou>
+    </pous>
+  </types>
+  <instances><configurations/></instances>
+</project>"#;
+
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let calls = vendor_calls(&st_units, ["Scale", "Main"], &["adr".to_string()]);
+        let found: Vec<_> = calls
+            .iter()
+            .map(|call| (call.name.as_str(), call.instance.as_deref(), call.routine.as_str(), call.line))
+            .collect();
+        assert_eq!(found, [("MC_Power", Some("axis"), "Main", 3), ("SIZEOF", None, "Main", 4)]);
+    }
+}
@@ -2,6 +2,8 @@
 //!
 //! Extract and parse ladder logic rungs from L5X structures.
 
+use std::collections::HashMap;
+
 use l5x::rll;
 use l5x::{
     Routine, RoutineContent, Rung, RungContent,
@@ -55,6 +57,7 @@ pub fn parse_rung_collection(
             results.push(LocatedRung {
                 location: RungLocation::new(program, routine, rung_number),
                 parsed,
+                text_start: None,
             });
         }
     }
@@ -79,3 +82,70 @@ pub fn parse_routine(routine: &Routine, program: &str) -> Vec<LocatedRung> {
 
     results
 }
+
+/// Byte offsets of the rung texts in L5X file content, by program (`AOI:`
+/// prefixed for AOIs), routine and rung number.
+///
+/// The offset is that of the trimmed text, as `extract_rung_text` returns
+/// it. Returns an empty map if the content is not well-formed XML.
+pub fn rung_text_offsets(content: &str) -> HashMap<(String, String, u32), usize> {
+    use quick_xml::events::{BytesStart, Event};
+    use quick_xml::Reader;
+
+    let name = |element: &BytesStart, attr: &str| {
+        element
+            .try_get_attribute(attr)
+            .ok()
+            .flatten()
+            .and_then(|a| a.unescape_value().ok())
+            .map(|v| v.into_owned())
+    };
+
+    let mut reader = Reader::from_str(content);
+    let mut path: Vec<String> = Vec::new();
+    let (mut program, mut routine, mut rung) = (String::new(), String::new(), None);
+    let mut offsets = HashMap::new();
+    loop {
+        let start = reader.buffer_position() as usize;
+        let Ok(event) = reader.read_event() else {
+            return HashMap::new();
+        };
+        match event {
+            Event::Start(ref element) => {
+                let local = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+                match local.as_str() {
+                    "Program" => program = name(element, "Name").unwrap_or_default(),
+                    "AddOnInstructionDefinition" => {
+                        program = format!("AOI:{}", name(element, "Name").unwrap_or_default())
+                    }
+                    "Routine" => routine = name(element, "Name").unwrap_or_default(),
+                    "Rung" => rung = Some(name(element, "Number").and_then(|n| n.parse().ok()).unwrap_or(0)),
+                    _ => {}
+                }
+                path.push(local);
+            }
+            Event::End(_) if path.pop().as_deref() == Some("Rung") => rung = None,
+            Event::CData(_) | Event::Text(_) => {
+                let in_rung_text = path.len() >= 2 && path[path.len() - 2] == "Rung" && path[path.len() - 1] == "Text";
+                let Some(number) = rung.filter(|_| in_rung_text) else {
+                    continue;
+                };
+                let (raw, delimiter) = match event {
+                    Event::CData(ref text) => (String::from_utf8_lossy(text.as_ref()).into_owned(), "<![CDATA[".len()),
+                    Event::Text(ref text) => (String::from_utf8_lossy(text.as_ref()).into_owned(), 0),
+                    _ => continue,
+                };
+                let trimmed = raw.trim_start();
+                if trimmed.trim_end().is_empty() {
+                    continue;
+                }
+                offsets
+                    .entry((program.clone(), routine.clone(), number))
+                    .or_insert(start + delimiter + raw.len() - trimmed.len());
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    offsets
+}
@@ -0,0 +1,187 @@
+//! Code size of POUs by language.
+//!
+//! Physical lines are counted in the raw source, logical statements in the
+//! parsed code: ST statements from the AST (nested statements included),
+//! RLL instructions from the parsed rungs and IL instructions from the
+//! lines that are not only a label. Graphical bodies have no source lines
+//! and are not measured.
+
+use std::collections::BTreeMap;
+use std::ops::AddAssign;
+
+use iec61131::visitor::{walk_body, walk_stmt};
+use iec61131::{Statement, Visitor};
+use l5x::rll::Rung;
+use serde::Serialize;
+
+use super::iec61131_adapter::Pou;
+
+/// Line and statement counts of a piece of code.
+#[derive(Debug, Clone, Copy, Default, Serialize, PartialEq, Eq)]
+pub struct CodeSize {
+    /// Physical lines (rungs for RLL)
+    pub lines: usize,
+    /// Lines with code outside comments
+    pub code_lines: usize,
+    /// Logical statements (instructions for RLL and IL)
+    pub statements: usize,
+}
+
+impl CodeSize {
+    /// Measure ST source, counting the statements of its parsed POU.
+    pub fn of_st(source: &str, pou: Option<&Pou>) -> Self {
+        #[derive(Default)]
+        struct Statements(usize);
+
+        impl<'ast> Visitor<'ast> for Statements {
+            fn visit_stmt(&mut self, stmt: &'ast Statement) {
+                self.0 += 1;
+                walk_stmt(self, stmt);
+            }
+        }
+
+        let mut statements = Statements::default();
+        if let Some(pou) = pou {
+            walk_body(&mut statements, &pou.body);
+        }
+        Self {
+            lines: source.lines().count(),
+            code_lines: code_lines(source).count(),
+            statements: statements.0,
+        }
+    }
+
+    /// Measure IL source: each code line is an instruction unless it only
+    /// holds a label.
+    pub fn of_il(source: &str) -> Self {
+        let code: Vec<&str> = code_lines(source).collect();
+        let statements = code
+            .iter()
+            .filter(|line| match line.split_once(':') {
+                Some((label, rest)) if !rest.starts_with('=') && !label.trim().contains(char::is_whitespace) => {
+                    !rest.trim().is_empty()
+                }
+                _ => true,
+            })
+            .count();
+        Self {
+            lines: source.lines().count(),
+            code_lines: code.len(),
+            statements,
+        }
+    }
+
+    /// Measure the rungs of an RLL routine by their instructions.
+    pub fn of_rungs<'a>(rungs: impl IntoIterator<Item = &'a Rung>) -> Self {
+        let mut size = Self::default();
+        for rung in rungs {
+            size.lines += 1;
+            if let Some(content) = &rung.content {
+                size.code_lines += 1;
+                size.statements += content.instructions().len();
+            }
+        }
+        size
+    }
+}
+
+impl AddAssign for CodeSize {
+    fn add_assign(&mut self, other: Self) {
+        self.lines += other.lines;
+        self.code_lines += other.code_lines;
+        self.statements += other.statements;
+    }
+}
+
+/// Size of one POU or routine.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct PouSize {
+    /// POU name, "Program/Routine" for L5X routines
+    pub name: String,
+    /// Language of the body: "ST", "RLL" or "IL"
+    pub language: String,
+    pub size: CodeSize,
+}
+
+/// Code size of the POUs of a project.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SizeMetrics {
+    /// Measured POUs in project order
+    pub pous: Vec<PouSize>,
+}
+
+impl SizeMetrics {
+    /// Add a POU with the language and size of its body.
+    pub fn add(&mut self, name: impl Into<String>, language: &str, size: CodeSize) {
+        self.pous.push(PouSize {
+            name: name.into(),
+            language: language.to_string(),
+            size,
+        });
+    }
+
+    /// Total size per language, by language name.
+    pub fn by_language(&self) -> BTreeMap<String, CodeSize> {
+        let mut totals: BTreeMap<String, CodeSize> = BTreeMap::new();
+        for pou in &self.pous {
+            *totals.entry(pou.language.clone()).or_default() += pou.size;
+        }
+        totals
+    }
+
+    /// Total size of all POUs.
+    pub fn total(&self) -> CodeSize {
+        let mut total = CodeSize::default();
+        for pou in &self.pous {
+            total += pou.size;
+        }
+        total
+    }
+}
+
+/// The lines of `source` with code outside comments.
+fn code_lines(source: &str) -> impl Iterator<Item = &str> + '_ {
+    let comments = iecst::comment_spans(source);
+    let mut offset = 0;
+    source.split_inclusive('\n').filter_map(move |line| {
+        let start = offset;
+        offset += line.len();
+        let has_code = line.char_indices().any(|(i, c)| {
+            !c.is_whitespace() && !comments.iter().any(|span| (span.start..span.end).contains(&(start + i)))
+        });
+        has_code.then(|| line.trim_end())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{parse_st_body, RoutineKind, STLocation};
+
+    #[test]
+    fn test_st_size() {
+        let source = "(* header\n   comment *)\nIF a THEN\n    b := 1; // set\n    c := 2;\nEND_IF;\n\n";
+        let routine = parse_st_body(STLocation::new("Main", "Logic"), RoutineKind::Program, source.to_string());
+        let size = CodeSize::of_st(source, routine.pou.as_ref());
+        assert_eq!(size, CodeSize { lines: 7, code_lines: 4, statements: 3 });
+        assert_eq!(CodeSize::of_st(source, None).statements, 0);
+    }
+
+    #[test]
+    fn test_il_size() {
+        let source = "LD a (* load *)\nST b\n\nLoop:\n  ADD 1\nEnd: RET\n(* done *)";
+        assert_eq!(CodeSize::of_il(source), CodeSize { lines: 7, code_lines: 5, statements: 4 });
+    }
+
+    #[test]
+    fn test_totals_by_language() {
+        let mut metrics = SizeMetrics::default();
+        metrics.add("A", "ST", CodeSize { lines: 10, code_lines: 8, statements: 5 });
+        metrics.add("B", "RLL", CodeSize { lines: 3, code_lines: 3, statements: 7 });
+        metrics.add("C", "ST", CodeSize { lines: 2, code_lines: 2, statements: 1 });
+        let by_language = metrics.by_language();
+        assert_eq!(by_language["ST"], CodeSize { lines: 12, code_lines: 10, statements: 6 });
+        assert_eq!(by_language["RLL"].statements, 7);
+        assert_eq!(metrics.total(), CodeSize { lines: 15, code_lines: 13, statements: 13 });
+    }
+}
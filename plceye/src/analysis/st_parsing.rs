@@ -9,7 +9,7 @@ use l5x::{
 };
 
 use super::iec61131_adapter::{Pou, parse_pou};
-use super::{STLocation, ParsedSTRoutine};
+use super::{STLocation, ParsedSTRoutine, RoutineKind};
 
 /// Extract the ST source code from an STLine element.
 fn extract_st_line_text(line: &STLine) -> String {
@@ -72,32 +72,73 @@ pub fn parse_st_routine(routine: &Routine, program: &str) -> Option<ParsedSTRout
     let source = extract_st_source(st_content);
     let location = STLocation::new(program, &routine.name);
 
+    Some(parse_st_body(location, RoutineKind::Program, source))
+}
+
+/// Parse the ST source of a routine body.
+pub fn parse_st_body(location: STLocation, kind: RoutineKind, source: String) -> ParsedSTRoutine {
     // Wrap in synthetic PROGRAM for parsing
     let wrapped_source = format!(
         "PROGRAM {}\nVAR\nEND_VAR\n{}\nEND_PROGRAM",
-        routine.name, source
+        location.routine, source
     );
 
     match parse_pou(&wrapped_source) {
-        Ok(pou) => {
-            Some(ParsedSTRoutine {
-                location,
-                source,
-                pou: Some(pou),
-                parse_error: None,
-            })
-        }
+        Ok(pou) => ParsedSTRoutine {
+            location,
+            kind,
+            source,
+            pou: Some(pou),
+            parse_error: None,
+            skipped: Vec::new(),
+        },
         Err(e) => {
-            Some(ParsedSTRoutine {
+            let (pou, skipped) = match recover_pou(&location, &source) {
+                Some((pou, skipped)) => (Some(pou), skipped),
+                None => (None, Vec::new()),
+            };
+            ParsedSTRoutine {
                 location,
+                kind,
                 source,
-                pou: None,
+                pou,
                 parse_error: Some(e),
-            })
+                skipped,
+            }
         }
     }
 }
 
+/// Parse the statements of a routine that failed to parse, without the
+/// broken ones.
+///
+/// The iecst parser finds the statements it can't parse; they are blanked
+/// (keeping line breaks) and the rest is parsed again, so the metrics still
+/// cover the parseable portion. Returns `None` if nothing could be skipped
+/// or the remainder still doesn't parse.
+fn recover_pou(location: &STLocation, source: &str) -> Option<(Pou, Vec<iecst::ParseError>)> {
+    let (_, skipped) = iecst::parse_statements_recovering(source);
+    if skipped.is_empty() {
+        return None;
+    }
+
+    let mut bytes = source.as_bytes().to_vec();
+    for error in &skipped {
+        let end = error.span.end.min(bytes.len());
+        for byte in &mut bytes[error.span.start.min(end)..end] {
+            if *byte != b'\n' {
+                *byte = b' ';
+            }
+        }
+    }
+    let remainder = String::from_utf8(bytes).ok()?;
+    let wrapped_source = format!(
+        "PROGRAM {}\nVAR\nEND_VAR\n{}\nEND_PROGRAM",
+        location.routine, remainder
+    );
+    parse_pou(&wrapped_source).ok().map(|pou| (pou, skipped))
+}
+
 /// Parse all ST routines from a Program.
 pub fn parse_st_routines_from_program(program: &AProgram) -> Vec<ParsedSTRoutine> {
     let mut results = Vec::new();
@@ -121,7 +162,8 @@ pub fn parse_st_routines_from_aoi(aoi: &UDIDefinition) -> Vec<ParsedSTRoutine> {
         if let UDIDefinitionContent::Routines(routine_collection) = content {
             for routine in &routine_collection.routine {
                 let aoi_name = format!("AOI:{}", aoi.name);
-                if let Some(parsed) = parse_st_routine(routine, &aoi_name) {
+                if let Some(mut parsed) = parse_st_routine(routine, &aoi_name) {
+                    parsed.kind = RoutineKind::Aoi;
                     results.push(parsed);
                 }
             }
@@ -131,129 +173,43 @@ pub fn parse_st_routines_from_aoi(aoi: &UDIDefinition) -> Vec<ParsedSTRoutine> {
     results
 }
 
-/// Extract all function/FB call names from an ST POU.
+/// Extract all function/FB call names from an ST POU, sorted and without duplicates.
 pub fn extract_st_call_names(pou: &Pou) -> Vec<String> {
-    use iec61131::{Statement, Expression, Variable};
-    
-    let mut calls = Vec::new();
-    
-    fn visit_var(var: &Variable, calls: &mut Vec<String>) {
-        match var {
-            Variable::MemberAccess { base, .. } => visit_var(base, calls),
-            Variable::ArrayAccess { base, indices } => {
-                visit_var(base, calls);
-                for idx in indices {
-                    visit_expr(idx, calls);
-                }
-            }
-            Variable::Dereference { base } => visit_var(base, calls),
-            _ => {}
-        }
-    }
-    
-    fn visit_expr(expr: &Expression, calls: &mut Vec<String>) {
-        use iec61131::Argument;
-        match expr {
-            Expression::Call { function, arguments } => {
-                calls.push(function.clone());
-                for arg in arguments {
-                    match arg {
-                        Argument::Positional(e) => visit_expr(e, calls),
-                        Argument::Named { value, .. } => visit_expr(value, calls),
-                        Argument::Output { variable, .. } => visit_var(variable, calls),
-                    }
-                }
-            }
-            Expression::Binary { left, right, .. } => {
-                visit_expr(left, calls);
-                visit_expr(right, calls);
-            }
-            Expression::Unary { operand, .. } => {
-                visit_expr(operand, calls);
-            }
-            Expression::Variable(var) => {
-                visit_var(var, calls);
-            }
-            Expression::Parenthesized(inner) => {
-                visit_expr(inner, calls);
+    let mut calls = extract_st_calls(pou);
+    calls.sort();
+    calls.dedup();
+    calls
+}
+
+/// Extract the function/FB call names of an ST POU, one per call, in source order.
+///
+/// Function block invocations are named by their instance.
+pub fn extract_st_calls(pou: &Pou) -> Vec<String> {
+    use iec61131::visitor::{walk_body, walk_expr, walk_stmt};
+    use iec61131::{Expression, Statement, Visitor};
+
+    #[derive(Default)]
+    struct Calls(Vec<String>);
+
+    impl<'ast> Visitor<'ast> for Calls {
+        fn visit_stmt(&mut self, stmt: &'ast Statement) {
+            match stmt {
+                Statement::FunctionCall { name, .. } => self.0.push(name.clone()),
+                Statement::FbInvocation { instance, .. } => self.0.push(instance.clone()),
+                _ => {}
             }
-            _ => {}
+            walk_stmt(self, stmt);
         }
-    }
-    
-    fn visit_stmt(stmt: &Statement, calls: &mut Vec<String>) {
-        use iec61131::Argument;
-        match stmt {
-            Statement::Assignment { target, value, .. } => {
-                visit_var(target, calls);
-                visit_expr(value, calls);
-            }
-            Statement::If { condition, then_body, elsif_parts, else_body, .. } => {
-                visit_expr(condition, calls);
-                for s in then_body { visit_stmt(s, calls); }
-                for (cond, body) in elsif_parts {
-                    visit_expr(cond, calls);
-                    for s in body { visit_stmt(s, calls); }
-                }
-                if let Some(else_stmts) = else_body {
-                    for s in else_stmts { visit_stmt(s, calls); }
-                }
-            }
-            Statement::Case { selector, cases, else_body, .. } => {
-                visit_expr(selector, calls);
-                for case in cases {
-                    for s in &case.body { visit_stmt(s, calls); }
-                }
-                if let Some(else_stmts) = else_body {
-                    for s in else_stmts { visit_stmt(s, calls); }
-                }
-            }
-            Statement::For { start, end, step, body, .. } => {
-                visit_expr(start, calls);
-                visit_expr(end, calls);
-                if let Some(step_expr) = step { visit_expr(step_expr, calls); }
-                for s in body { visit_stmt(s, calls); }
-            }
-            Statement::While { condition, body, .. } => {
-                visit_expr(condition, calls);
-                for s in body { visit_stmt(s, calls); }
-            }
-            Statement::Repeat { body, condition, .. } => {
-                for s in body { visit_stmt(s, calls); }
-                visit_expr(condition, calls);
-            }
-            Statement::Return { value: Some(v), .. } => {
-                visit_expr(v, calls);
-            }
-            Statement::FunctionCall { name, arguments, .. } => {
-                calls.push(name.clone());
-                for arg in arguments {
-                    match arg {
-                        Argument::Positional(e) => visit_expr(e, calls),
-                        Argument::Named { value, .. } => visit_expr(value, calls),
-                        Argument::Output { variable, .. } => visit_var(variable, calls),
-                    }
-                }
-            }
-            Statement::FbInvocation { instance, arguments, .. } => {
-                calls.push(instance.clone());
-                for arg in arguments {
-                    match arg {
-                        Argument::Positional(e) => visit_expr(e, calls),
-                        Argument::Named { value, .. } => visit_expr(value, calls),
-                        Argument::Output { variable, .. } => visit_var(variable, calls),
-                    }
-                }
+
+        fn visit_expr(&mut self, expr: &'ast Expression) {
+            if let Expression::Call { function, .. } = expr {
+                self.0.push(function.clone());
             }
-            _ => {}
+            walk_expr(self, expr);
         }
     }
-    
-    for stmt in &pou.body {
-        visit_stmt(stmt, &mut calls);
-    }
-    
-    calls.sort();
-    calls.dedup();
-    calls
+
+    let mut calls = Calls::default();
+    walk_body(&mut calls, &pou.body);
+    calls.0
 }
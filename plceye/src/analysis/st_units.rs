@@ -0,0 +1,716 @@
+//! Structured Text units for semantic analysis.
+//!
+//! Builds `iecst` POUs from L5X routines and PLCopen POUs so that the
+//! semantic checks of `iecst` (type checking, symbol analysis) can run on
+//! them. L5X routines carry no declarations of their own, so the variable
+//! blocks are synthesized from controller, program and AOI tags.
+
+use std::collections::HashMap;
+
+use iecst::{
+    ArrayRange, EnumValue, Expr, ExprKind, ParseError, ParseErrorKind, Pou, PouKind, RetainKind, Span, Stmt, Type,
+    TypeDecl, TypeDef, TypeKind, TypeSpec, VarBlock, VarBlockKind, VarDecl,
+};
+use l5x::{Controller, RoutineContent, Tag, UDIDefinition, UDIDefinitionContent};
+
+use super::extract_st_source;
+use crate::loader::LoadedProject;
+
+/// A unit of ST code prepared for semantic analysis.
+#[derive(Debug)]
+pub struct StUnit {
+    /// Location for findings (e.g., "Program:Main" or the PLCopen POU name)
+    pub location: String,
+    /// Routine or POU name
+    pub name: String,
+    /// ST source code of the body
+    pub source: String,
+    /// POU with declarations and parsed body (None if the body failed to parse)
+    pub pou: Option<Pou>,
+    /// Statements the parser skipped; only comparisons used as statements
+    /// (`x = y;`) are skipped in a unit that has a POU, as they have no effect
+    pub skipped: Vec<iecst::ParseError>,
+    /// Whether this is the main body of its POU; AOI scan-mode routines
+    /// (Prescan, EnableInFalse, Postscan) share the AOI's interface but are not
+    pub main_body: bool,
+}
+
+impl StUnit {
+    /// Get the 1-based line number of a byte offset in the source.
+    pub fn line_of(&self, offset: usize) -> usize {
+        let end = offset.min(self.source.len());
+        self.source.as_bytes()[..end].iter().filter(|&&b| b == b'\n').count() + 1
+    }
+}
+
+/// Direction of a POU parameter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParameterKind {
+    Input,
+    Output,
+    InOut,
+}
+
+/// A parameter of a user-defined POU.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub kind: ParameterKind,
+    /// Whether positional arguments bind to it: inputs and in-outs of
+    /// PLCopen POUs, required parameters of AOIs
+    pub positional: bool,
+    /// Whether it has an initial value: PLCopen parameters with an
+    /// `initialValue`, AOI parameters that are not required (they take
+    /// their default data)
+    pub initialized: bool,
+    /// Declared type (`Type::Unknown` if the export has none)
+    pub data_type: Type,
+}
+
+/// Parameters of a user-defined function, function block or AOI.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Signature {
+    /// POU name as declared
+    pub name: String,
+    /// Parameters in declaration order
+    pub parameters: Vec<Parameter>,
+    /// Whether a call by POU name passes the instance as the first argument
+    /// (`MyAoi(MyAoi_01, ...)` in Rockwell ST)
+    pub instance_argument: bool,
+    /// Whether the POU is a function, whose parameters do not keep their
+    /// values between calls
+    pub function: bool,
+}
+
+impl Signature {
+    /// The parameter a named argument binds to, matched case-insensitively.
+    pub fn parameter(&self, name: &str) -> Option<&Parameter> {
+        self.parameters.iter().find(|p| p.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The parameter the positional argument at `index` binds to, not
+    /// counting the instance argument.
+    pub fn positional(&self, index: usize) -> Option<&Parameter> {
+        self.parameters.iter().filter(|p| p.positional).nth(index)
+    }
+}
+
+/// All ST units of a project.
+#[derive(Debug, Default)]
+pub struct StUnits {
+    /// Units in project order
+    pub units: Vec<StUnit>,
+    /// Return types of user-defined functions
+    pub functions: HashMap<String, Type>,
+    /// Parameters of user-defined POUs and AOIs, by lowercase name
+    pub signatures: HashMap<String, Signature>,
+    /// User-defined type declarations
+    pub types: Vec<TypeDecl>,
+    /// Declarations of the PLCopen POUs without an ST body, as POUs with
+    /// an empty body
+    pub declarations: Vec<Pou>,
+}
+
+impl StUnits {
+    /// Parameters of the user-defined POU or AOI `name`, matched
+    /// case-insensitively.
+    pub fn signature(&self, name: &str) -> Option<&Signature> {
+        self.signatures.get(&name.to_lowercase())
+    }
+
+    /// Collect the ST routines of an L5X controller.
+    pub fn from_controller(controller: &Controller) -> Self {
+        let mut units = Vec::new();
+
+        let controller_tags: Vec<&Tag> = controller
+            .tags
+            .as_ref()
+            .map(|tags| tags.tag.iter().collect())
+            .unwrap_or_default();
+
+        if let Some(programs) = &controller.programs {
+            for program in &programs.program {
+                let program_tags: Vec<&Tag> = program
+                    .tags
+                    .as_ref()
+                    .map(|tags| tags.tag.iter().collect())
+                    .unwrap_or_default();
+
+                let mut var_blocks = vec![
+                    tag_block(VarBlockKind::Var, &program_tags, &[]),
+                    tag_block(VarBlockKind::VarExternal, &controller_tags, &program_tags),
+                ];
+                var_blocks.retain(|block| !block.vars.is_empty());
+
+                if let Some(routines) = &program.routines {
+                    for routine in &routines.routine {
+                        if let Some(unit) = l5x_unit(
+                            routine,
+                            format!("Program:{}", program.name),
+                            &var_blocks,
+                            true,
+                        ) {
+                            units.push(unit);
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut signatures = HashMap::new();
+        if let Some(aois) = &controller.add_on_instruction_definitions {
+            for aoi in &aois.add_on_instruction_definition {
+                signatures.insert(aoi.name.to_lowercase(), aoi_signature(aoi));
+                let var_blocks = aoi_var_blocks(aoi);
+                for content in &aoi.content {
+                    if let UDIDefinitionContent::Routines(routines) = content {
+                        for routine in &routines.routine {
+                            if let Some(unit) = l5x_unit(
+                                routine,
+                                format!("Program:AOI:{}", aoi.name),
+                                &var_blocks,
+                                routine.name.eq_ignore_ascii_case("Logic"),
+                            ) {
+                                units.push(unit);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        // User-defined types as structs of their visible members; hidden
+        // members hold the bits of BOOL members
+        let types = controller
+            .data_types
+            .iter()
+            .flat_map(|data_types| &data_types.data_type)
+            .map(|data_type| {
+                let fields = data_type
+                    .members
+                    .iter()
+                    .flat_map(|members| &members.member)
+                    .filter(|member| !is_true(member.hidden.as_deref()))
+                    .map(|member| var_decl(&member.name, &member.data_type, member.dimension.as_deref()))
+                    .collect();
+                TypeDecl {
+                    name: data_type.name.clone(),
+                    definition: TypeDef::Struct { fields },
+                    span: Span::default(),
+                }
+            })
+            .collect();
+
+        Self {
+            units,
+            signatures,
+            types,
+            ..Self::default()
+        }
+    }
+
+    /// Collect the ST units of a loaded project in either format.
+    pub fn from_project(project: &LoadedProject) -> Self {
+        if let Some(ref controller) = project.l5x_controller {
+            Self::from_controller(controller)
+        } else if let Some(ref plcopen) = project.plcopen_project {
+            Self::from_plcopen(plcopen)
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Collect the ST bodies of a PLCopen project.
+    pub fn from_plcopen(project: &plcopen::Project) -> Self {
+        let mut st_units = Self::default();
+
+        if let Some(data_types) = project.types.as_ref().and_then(|t| t.data_types.as_ref()) {
+            st_units.types = data_types
+                .data_type
+                .iter()
+                .filter_map(|data_type| {
+                    let definition = type_def_from_data(data_type.base_type.as_deref()?)?;
+                    Some(TypeDecl {
+                        name: data_type.name.clone(),
+                        definition,
+                        span: Span::default(),
+                    })
+                })
+                .collect();
+        }
+
+        let Some(pous) = project.types.as_ref().and_then(|t| t.pous.as_ref()) else {
+            return st_units;
+        };
+
+        for pou in &pous.pou {
+            let kind = match pou.pou_type.to_lowercase().as_str() {
+                "function" => PouKind::Function,
+                "functionblock" => PouKind::FunctionBlock,
+                _ => PouKind::Program,
+            };
+
+            let return_type = pou
+                .interface
+                .as_ref()
+                .and_then(|i| i.return_type.as_ref())
+                .and_then(|data| type_spec_from_data(data));
+
+            if let Some(ref return_type) = return_type {
+                st_units
+                    .functions
+                    .insert(pou.name.clone(), Type::from_spec(return_type));
+            }
+
+            if let Some(ref interface) = pou.interface {
+                st_units
+                    .signatures
+                    .insert(pou.name.to_lowercase(), plcopen_signature(&pou.name, kind, interface));
+            }
+
+            let var_blocks = pou
+                .interface
+                .as_ref()
+                .map(|interface| plcopen_var_blocks(interface))
+                .unwrap_or_default();
+
+            let Some(source) = pou
+                .body
+                .iter()
+                .find_map(|body| body.st.as_ref().and_then(|st| st.text.clone()))
+            else {
+                st_units.declarations.push(Pou {
+                    kind,
+                    name: pou.name.clone(),
+                    return_type,
+                    var_blocks,
+                    body: Vec::new(),
+                    span: Span::default(),
+                });
+                continue;
+            };
+
+            let (body, skipped) = parse_body(&source);
+            let parsed = body.map(|body| Pou {
+                kind,
+                name: pou.name.clone(),
+                return_type,
+                var_blocks,
+                body,
+                span: Span::default(),
+            });
+
+            st_units.units.push(StUnit {
+                location: pou.name.clone(),
+                name: pou.name.clone(),
+                source,
+                pou: parsed,
+                skipped,
+                main_body: true,
+            });
+        }
+
+        st_units
+    }
+}
+
+/// Build a unit from an L5X ST routine.
+fn l5x_unit(
+    routine: &l5x::Routine,
+    location: String,
+    var_blocks: &[VarBlock],
+    main_body: bool,
+) -> Option<StUnit> {
+    if routine.r#type != "ST" {
+        return None;
+    }
+
+    let st_content = routine.content.iter().find_map(|c| match c {
+        RoutineContent::STContent(st) => Some(st),
+        _ => None,
+    })?;
+
+    let source = extract_st_source(st_content);
+    let (body, skipped) = parse_body(&source);
+    let pou = body.map(|body| Pou {
+        kind: PouKind::Program,
+        name: routine.name.clone(),
+        return_type: None,
+        var_blocks: var_blocks.to_vec(),
+        body,
+        span: Span::default(),
+    });
+
+    Some(StUnit {
+        location,
+        name: routine.name.clone(),
+        source,
+        pou,
+        skipped,
+        main_body,
+    })
+}
+
+/// Parse an ST body, skipping broken statements.
+///
+/// The body is `None` if a statement with an effect had to be skipped:
+/// semantic checks on the rest would miss its assignments and calls.
+fn parse_body(source: &str) -> (Option<Vec<Stmt>>, Vec<ParseError>) {
+    let (body, skipped) = iecst::parse_statements_recovering(source);
+    let complete = skipped
+        .iter()
+        .all(|error| matches!(error.kind, ParseErrorKind::ComparisonStatement { .. }));
+    (complete.then_some(body), skipped)
+}
+
+/// Check if an AOI parameter is one the controller adds to every AOI.
+fn is_system_parameter(name: &str) -> bool {
+    name.eq_ignore_ascii_case("EnableIn") || name.eq_ignore_ascii_case("EnableOut")
+}
+
+/// Build a variable block from L5X tags, skipping names shadowed by `shadowing`.
+fn tag_block(kind: VarBlockKind, tags: &[&Tag], shadowing: &[&Tag]) -> VarBlock {
+    let vars = tags
+        .iter()
+        .filter(|tag| !shadowing.iter().any(|s| s.name.eq_ignore_ascii_case(&tag.name)))
+        .filter_map(|tag| {
+            let data_type = tag.data_type.as_deref()?;
+            Some(var_decl(&tag.name, data_type, tag.dimensions.as_deref()))
+        })
+        .collect();
+
+    var_block(kind, vars)
+}
+
+/// Build the variable blocks of an AOI from its parameters and local tags.
+fn aoi_var_blocks(aoi: &UDIDefinition) -> Vec<VarBlock> {
+    let mut inputs = Vec::new();
+    let mut outputs = Vec::new();
+    let mut in_outs = Vec::new();
+    let mut locals = Vec::new();
+    let mut system = Vec::new();
+
+    for content in &aoi.content {
+        match content {
+            UDIDefinitionContent::Parameters(params) => {
+                for param in &params.parameter {
+                    let Some(data_type) = param.data_type.as_deref() else {
+                        continue;
+                    };
+                    let decl = var_decl(&param.name, data_type, param.dimensions.as_deref());
+                    // EnableIn/EnableOut are managed by the controller
+                    if is_system_parameter(&param.name) {
+                        system.push(decl);
+                        continue;
+                    }
+                    match param.usage.as_str() {
+                        "Output" => outputs.push(decl),
+                        "InOut" => in_outs.push(decl),
+                        _ => inputs.push(decl),
+                    }
+                }
+            }
+            UDIDefinitionContent::LocalTags(local_tags) => {
+                for tag in &local_tags.local_tag {
+                    locals.push(var_decl(&tag.name, &tag.data_type, tag.dimensions.as_deref()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    [
+        var_block(VarBlockKind::VarInput, inputs),
+        var_block(VarBlockKind::VarOutput, outputs),
+        var_block(VarBlockKind::VarInOut, in_outs),
+        var_block(VarBlockKind::Var, locals),
+        var_block(VarBlockKind::VarExternal, system),
+    ]
+    .into_iter()
+    .filter(|block| !block.vars.is_empty())
+    .collect()
+}
+
+/// Collect the parameters of an AOI; positional arguments bind to the
+/// required ones after the instance.
+fn aoi_signature(aoi: &UDIDefinition) -> Signature {
+    let parameters = aoi
+        .content
+        .iter()
+        .filter_map(|content| match content {
+            UDIDefinitionContent::Parameters(params) => Some(&params.parameter),
+            _ => None,
+        })
+        .flatten()
+        .filter(|param| !is_system_parameter(&param.name))
+        .map(|param| {
+            let kind = match param.usage.as_str() {
+                "Output" => ParameterKind::Output,
+                "InOut" => ParameterKind::InOut,
+                _ => ParameterKind::Input,
+            };
+            let required = is_true(param.required.as_deref());
+            let data_type = param.data_type.as_deref().map_or(Type::Unknown, |data_type| {
+                Type::from_spec(&var_decl(&param.name, data_type, param.dimensions.as_deref()).var_type)
+            });
+            Parameter {
+                name: param.name.clone(),
+                kind,
+                positional: kind == ParameterKind::InOut || required,
+                initialized: !required,
+                data_type,
+            }
+        })
+        .collect();
+
+    Signature {
+        name: aoi.name.clone(),
+        parameters,
+        instance_argument: true,
+        function: false,
+    }
+}
+
+/// Collect the parameters of a PLCopen POU; positional arguments bind to
+/// the inputs, then the in-outs.
+fn plcopen_signature(
+    name: &str,
+    kind: PouKind,
+    interface: &plcopen::Root_project_InlineType_types_InlineType_pous_InlineType_pou_InlineType_interface_Inline,
+) -> Signature {
+    let inputs = interface.input_vars.iter().flat_map(|list| &list.variable);
+    let in_outs = interface.in_out_vars.iter().flat_map(|list| &list.variable);
+    let outputs = interface.output_vars.iter().flat_map(|list| &list.variable);
+    let parameters = inputs
+        .map(|var| (ParameterKind::Input, var))
+        .chain(in_outs.map(|var| (ParameterKind::InOut, var)))
+        .chain(outputs.map(|var| (ParameterKind::Output, var)))
+        .map(|(kind, var)| Parameter {
+            name: var.name.clone(),
+            kind,
+            positional: kind != ParameterKind::Output,
+            initialized: var.initial_value.is_some(),
+            data_type: var
+                .r#type
+                .as_ref()
+                .and_then(|data| type_spec_from_data(data))
+                .map_or(Type::Unknown, |spec| Type::from_spec(&spec)),
+        })
+        .collect();
+
+    Signature {
+        name: name.to_string(),
+        parameters,
+        instance_argument: false,
+        function: kind == PouKind::Function,
+    }
+}
+
+/// Build the variable blocks of a PLCopen POU interface.
+fn plcopen_var_blocks(
+    interface: &plcopen::Root_project_InlineType_types_InlineType_pous_InlineType_pou_InlineType_interface_Inline,
+) -> Vec<VarBlock> {
+    let mut blocks = Vec::new();
+
+    let mut push = |kind: VarBlockKind, constant: bool, vars: &[Box<plcopen::VarListPlain_variable_Inline>]| {
+        let decls: Vec<VarDecl> = vars.iter().map(|var| plcopen_var_decl(var)).collect();
+        let mut block = var_block(kind, decls);
+        block.constant = constant;
+        blocks.push(block);
+    };
+
+    for list in &interface.input_vars {
+        push(VarBlockKind::VarInput, false, &list.variable);
+    }
+    for list in &interface.output_vars {
+        push(VarBlockKind::VarOutput, false, &list.variable);
+    }
+    for list in &interface.in_out_vars {
+        push(VarBlockKind::VarInOut, false, &list.variable);
+    }
+    for list in &interface.local_vars {
+        push(VarBlockKind::Var, is_true(list.constant.as_deref()), &list.variable);
+    }
+    for list in &interface.temp_vars {
+        push(VarBlockKind::VarTemp, false, &list.variable);
+    }
+    for list in &interface.external_vars {
+        push(VarBlockKind::VarExternal, is_true(list.constant.as_deref()), &list.variable);
+    }
+    for list in &interface.global_vars {
+        push(VarBlockKind::VarGlobal, is_true(list.constant.as_deref()), &list.variable);
+    }
+
+    blocks
+}
+
+/// Declare a variable from a PLCopen variable element.
+fn plcopen_var_decl(var: &plcopen::VarListPlain_variable_Inline) -> VarDecl {
+    VarDecl {
+        name: var.name.clone(),
+        var_type: var
+            .r#type
+            .as_ref()
+            .and_then(|data| type_spec_from_data(data))
+            .unwrap_or_else(|| simple_type("ANY")),
+        initial: var
+            .initial_value
+            .as_ref()
+            .and_then(|value| value.simple_value.as_ref())
+            .and_then(|simple| simple.value.as_deref())
+            .and_then(|value| iecst::parse_expression(value).ok()),
+        location: var.address.clone(),
+        span: Span::default(),
+    }
+}
+
+/// Convert the base type of a PLCopen data type declaration to a type definition.
+fn type_def_from_data(data: &plcopen::Data) -> Option<TypeDef> {
+    if let Some(enumeration) = &data.r#enum {
+        let values = enumeration
+            .values
+            .iter()
+            .flat_map(|values| &values.value)
+            .map(|value| EnumValue {
+                name: value.name.clone(),
+                value: value.value.as_deref().and_then(|v| iecst::parse_expression(v).ok()),
+                span: Span::default(),
+            })
+            .collect();
+        return Some(TypeDef::Enum { values });
+    }
+
+    if let Some(fields) = &data.r#struct {
+        return Some(TypeDef::Struct {
+            fields: fields.variable.iter().map(|var| plcopen_var_decl(var)).collect(),
+        });
+    }
+
+    type_spec_from_data(data).map(TypeDef::Alias)
+}
+
+/// Convert a PLCopen data type to a type specification.
+fn type_spec_from_data(data: &plcopen::Data) -> Option<TypeSpec> {
+    let elementary = [
+        (data.bool.is_some(), "BOOL"),
+        (data.byte.is_some(), "BYTE"),
+        (data.word.is_some(), "WORD"),
+        (data.dword.is_some(), "DWORD"),
+        (data.lword.is_some(), "LWORD"),
+        (data.sint.is_some(), "SINT"),
+        (data.int.is_some(), "INT"),
+        (data.dint.is_some(), "DINT"),
+        (data.lint.is_some(), "LINT"),
+        (data.usint.is_some(), "USINT"),
+        (data.uint.is_some(), "UINT"),
+        (data.udint.is_some(), "UDINT"),
+        (data.ulint.is_some(), "ULINT"),
+        (data.real.is_some(), "REAL"),
+        (data.lreal.is_some(), "LREAL"),
+        (data.time.is_some(), "TIME"),
+        (data.date.is_some(), "DATE"),
+        (data.dt.is_some(), "DT"),
+        (data.tod.is_some(), "TOD"),
+    ];
+    if let Some((_, name)) = elementary.iter().find(|(present, _)| *present) {
+        return Some(simple_type(name));
+    }
+
+    if let Some(string) = &data.string {
+        let length = string.length.as_deref().and_then(|l| l.parse().ok());
+        return Some(TypeSpec::new(TypeKind::String { length }, Span::default()));
+    }
+    if let Some(wstring) = &data.wstring {
+        let length = wstring.length.as_deref().and_then(|l| l.parse().ok());
+        return Some(TypeSpec::new(TypeKind::WString { length }, Span::default()));
+    }
+    if let Some(derived) = &data.derived {
+        return Some(simple_type(&derived.name));
+    }
+    if let Some(array) = &data.array {
+        let element = array.base_type.as_ref().and_then(|b| type_spec_from_data(b))?;
+        let ranges = array
+            .dimension
+            .iter()
+            .map(|dim| {
+                let low = dim.lower.trim().parse().ok()?;
+                let high = dim.upper.trim().parse().ok()?;
+                Some(array_range(low, high))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        return Some(TypeSpec::new(
+            TypeKind::Array {
+                ranges,
+                element: Box::new(element),
+            },
+            Span::default(),
+        ));
+    }
+
+    None
+}
+
+/// Character capacity of the built-in Logix `STRING` type.
+const L5X_STRING_LENGTH: u32 = 82;
+
+/// Declare a variable from an L5X data type and dimension list (e.g., "10" or "4 8").
+fn var_decl(name: &str, data_type: &str, dimensions: Option<&str>) -> VarDecl {
+    let element = if data_type.eq_ignore_ascii_case("STRING") {
+        TypeSpec::new(TypeKind::String { length: Some(L5X_STRING_LENGTH) }, Span::default())
+    } else {
+        simple_type(data_type)
+    };
+    let ranges: Vec<ArrayRange> = dimensions
+        .unwrap_or_default()
+        .split_whitespace()
+        .filter_map(|d| d.parse::<i64>().ok())
+        .filter(|&n| n > 0)
+        .map(|n| array_range(0, n - 1))
+        .collect();
+
+    let var_type = if ranges.is_empty() {
+        element
+    } else {
+        TypeSpec::new(
+            TypeKind::Array {
+                ranges,
+                element: Box::new(element),
+            },
+            Span::default(),
+        )
+    };
+
+    VarDecl {
+        name: name.to_string(),
+        var_type,
+        initial: None,
+        location: None,
+        span: Span::default(),
+    }
+}
+
+fn var_block(kind: VarBlockKind, vars: Vec<VarDecl>) -> VarBlock {
+    VarBlock {
+        kind,
+        constant: false,
+        retain: RetainKind::None,
+        vars,
+        span: Span::default(),
+    }
+}
+
+fn simple_type(name: &str) -> TypeSpec {
+    TypeSpec::new(TypeKind::Simple(name.to_string()), Span::default())
+}
+
+fn array_range(low: i64, high: i64) -> ArrayRange {
+    ArrayRange {
+        low: Expr::new(ExprKind::IntLiteral(low), Span::default()),
+        high: Expr::new(ExprKind::IntLiteral(high), Span::default()),
+        span: Span::default(),
+    }
+}
+
+fn is_true(value: Option<&str>) -> bool {
+    matches!(value, Some("true") | Some("1"))
+}
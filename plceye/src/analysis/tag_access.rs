@@ -0,0 +1,298 @@
+//! Reads and writes of tags by program.
+//!
+//! RLL operands are classified by instruction and operand position: the
+//! destination of an output, move or math instruction is written, timer,
+//! counter and one-shot instances are read and written, and all other
+//! operands are read. In ST, assignment targets and output bindings
+//! (`Q => x`) are written, called instances are read and written, and every
+//! other identifier is read. Accesses are recorded by base tag name
+//! (`Tank` for `Tank.Level[2]`); AOI logic is not included, as it cannot
+//! reach controller tags.
+//!
+//! [`TagUsage`] answers the simpler question whether any logic, AOIs
+//! included, names a tag at all, and whether all of the logic could be read.
+
+use std::collections::HashSet;
+
+use iecst::visitor::{walk_expr, walk_stmt};
+use iecst::{Expr, ExprKind, Stmt, StmtKind, Visitor};
+use l5x::{Controller, UDIDefinitionContent};
+
+use super::{name_key, ProjectAnalysis, StUnits};
+
+/// RLL instructions writing their first operand
+const WRITE_FIRST: &[&str] = &["OTE", "OTL", "OTU", "RES", "CLR", "CPT"];
+/// RLL instructions writing their second operand
+const WRITE_SECOND: &[&str] = &[
+    "MOV", "COP", "CPS", "FLL", "NOT", "NEG", "ABS", "SQR", "SQRT", "TOD", "FRD", "DEG", "RAD", "TRN", "OSR", "OSF",
+];
+/// RLL instructions writing their third operand
+const WRITE_THIRD: &[&str] = &["ADD", "SUB", "MUL", "DIV", "MOD", "AND", "OR", "XOR", "XPY", "MVM", "SWPB"];
+/// RLL instructions that read and write their first operand
+const READ_WRITE_FIRST: &[&str] = &["TON", "TOF", "RTO", "CTU", "CTD", "ONS", "OSR", "OSF"];
+
+/// Whether code reads or writes a tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessKind {
+    Read,
+    Write,
+}
+
+/// One read or write of a tag.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TagAccess {
+    /// Base tag name as written
+    pub tag: String,
+    /// Program containing the code
+    pub program: String,
+    /// Location for findings (e.g., "MainProgram/MainRoutine/Rung#3")
+    pub location: String,
+    pub kind: AccessKind,
+}
+
+/// Tag reads and writes of the programs of a project.
+#[derive(Debug, Clone, Default)]
+pub struct TagAccesses {
+    /// Accesses in project order
+    pub accesses: Vec<TagAccess>,
+}
+
+impl TagAccesses {
+    /// Collect the tag accesses of the RLL and ST routines of L5X programs.
+    pub fn from_controller(analysis: &ProjectAnalysis, st_units: &StUnits) -> Self {
+        let mut accesses = Self::default();
+
+        for reference in &analysis.tag_references {
+            let program = &reference.location.program;
+            if program.starts_with("AOI:") {
+                continue;
+            }
+            let location = reference.location.path();
+            let instruction = reference.instruction().to_uppercase();
+            for kind in rll_operand_access(&instruction, reference.reference.operand_index) {
+                accesses.add(reference.tag_name(), program, &location, *kind);
+            }
+        }
+
+        for unit in &st_units.units {
+            let Some(program) = unit.location.strip_prefix("Program:") else {
+                continue;
+            };
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+            if program.starts_with("AOI:") {
+                continue;
+            }
+            let mut collector = StAccesses::default();
+            collector.visit_body(&pou.body);
+            let location = format!("{}/{}", program, unit.name);
+            for (tag, kind) in collector.0 {
+                accesses.add(tag, program, &location, kind);
+            }
+        }
+
+        accesses
+    }
+
+    fn add(&mut self, tag: &str, program: &str, location: &str, kind: AccessKind) {
+        self.accesses.push(TagAccess {
+            tag: tag.to_string(),
+            program: program.to_string(),
+            location: location.to_string(),
+            kind,
+        });
+    }
+}
+
+/// Base names referenced by the RLL and ST logic of a controller, AOIs
+/// included, matched case-insensitively.
+#[derive(Debug, Clone, Default)]
+pub struct TagUsage {
+    names: HashSet<String>,
+    complete: bool,
+}
+
+impl TagUsage {
+    /// Collect the names referenced by the logic of a controller.
+    ///
+    /// Usage is complete only if every routine is RLL or ST, not source
+    /// protected, and parsed without skipping any rung or statement.
+    pub fn from_controller(controller: &Controller, analysis: &ProjectAnalysis, st_units: &StUnits) -> Self {
+        let mut names: HashSet<String> = analysis.tag_xref.keys().map(|name| name_key(name, false)).collect();
+        for pou in st_units.units.iter().filter_map(|unit| unit.pou.as_ref()) {
+            let mut collector = StAccesses::default();
+            collector.visit_body(&pou.body);
+            names.extend(collector.0.into_iter().map(|(name, _)| name_key(name, false)));
+        }
+
+        let program_routines = controller
+            .programs
+            .iter()
+            .flat_map(|programs| &programs.program)
+            .flat_map(|program| program.routines.iter().flat_map(|routines| &routines.routine));
+        let aoi_routines = controller
+            .add_on_instruction_definitions
+            .iter()
+            .flat_map(|aois| &aois.add_on_instruction_definition)
+            .flat_map(|aoi| &aoi.content)
+            .filter_map(|content| match content {
+                UDIDefinitionContent::Routines(routines) => Some(&routines.routine),
+                _ => None,
+            })
+            .flatten();
+        let complete = program_routines.chain(aoi_routines).all(|routine| matches!(routine.r#type.as_str(), "RLL" | "ST"))
+            && analysis.protected.is_empty()
+            && analysis.stats.parsed_err == 0
+            && st_units.units.iter().all(|unit| unit.pou.is_some() && unit.skipped.is_empty());
+
+        Self { names, complete }
+    }
+
+    /// Whether the logic references the tag `name`.
+    pub fn references(&self, name: &str) -> bool {
+        self.names.contains(&name_key(name, false))
+    }
+
+    /// Whether all logic was read, so a tag [`references`](Self::references)
+    /// doesn't name is unused.
+    pub fn is_complete(&self) -> bool {
+        self.complete
+    }
+}
+
+/// How an RLL instruction accesses the operand at `index`.
+pub fn rll_operand_access(mnemonic: &str, index: usize) -> &'static [AccessKind] {
+    let writes = match index {
+        0 => WRITE_FIRST,
+        1 => WRITE_SECOND,
+        2 => WRITE_THIRD,
+        _ => &[],
+    };
+    if index == 0 && READ_WRITE_FIRST.contains(&mnemonic) {
+        &[AccessKind::Read, AccessKind::Write]
+    } else if writes.contains(&mnemonic) {
+        &[AccessKind::Write]
+    } else {
+        &[AccessKind::Read]
+    }
+}
+
+/// Base identifiers read and written by ST statements.
+#[derive(Default)]
+struct StAccesses<'ast>(Vec<(&'ast str, AccessKind)>);
+
+impl<'ast> StAccesses<'ast> {
+    /// Record the base of a written expression and read its indices.
+    fn write(&mut self, target: &'ast Expr) {
+        match &target.kind {
+            ExprKind::Ident(name) => self.0.push((name, AccessKind::Write)),
+            ExprKind::MemberAccess { expr, .. } | ExprKind::Paren(expr) => self.write(expr),
+            ExprKind::ArrayIndex { array, indices } => {
+                self.write(array);
+                for index in indices {
+                    self.visit_expr(index);
+                }
+            }
+            _ => self.visit_expr(target),
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for StAccesses<'ast> {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        match &stmt.kind {
+            StmtKind::Assignment { target, value } => {
+                self.write(target);
+                self.visit_expr(value);
+            }
+            StmtKind::Call { name, args } => {
+                self.0.push((name, AccessKind::Read));
+                self.0.push((name, AccessKind::Write));
+                for arg in args {
+                    match &arg.value {
+                        Some(value) if arg.output => self.write(value),
+                        Some(value) => self.visit_expr(value),
+                        None => {}
+                    }
+                }
+            }
+            _ => walk_stmt(self, stmt),
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        match &expr.kind {
+            ExprKind::Ident(name) => self.0.push((name, AccessKind::Read)),
+            ExprKind::FunctionCall { args, .. } => {
+                for arg in args {
+                    match &arg.value {
+                        Some(value) if arg.output => self.write(value),
+                        Some(value) => self.visit_expr(value),
+                        None => {}
+                    }
+                }
+            }
+            _ => walk_expr(self, expr),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::analyze_controller;
+    use crate::loader::LoadedProject;
+
+    fn accesses(xml: &str) -> Vec<(String, String, AccessKind)> {
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let controller = project.l5x_controller.expect("Should be L5X");
+        let analysis = analyze_controller(&controller);
+        let st_units = StUnits::from_controller(&controller);
+        TagAccesses::from_controller(&analysis, &st_units)
+            .accesses
+            .into_iter()
+            .map(|a| (a.tag, a.program, a.kind))
+            .collect()
+    }
+
+    #[test]
+    fn test_rll_operand_access() {
+        assert_eq!(rll_operand_access("MOV", 0), [AccessKind::Read]);
+        assert_eq!(rll_operand_access("MOV", 1), [AccessKind::Write]);
+        assert_eq!(rll_operand_access("ADD", 2), [AccessKind::Write]);
+        assert_eq!(rll_operand_access("OTE", 0), [AccessKind::Write]);
+        assert_eq!(rll_operand_access("TON", 0), [AccessKind::Read, AccessKind::Write]);
+        assert_eq!(rll_operand_access("XIC", 0), [AccessKind::Read]);
+    }
+
+    #[test]
+    fn test_rll_and_st_accesses() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+<Controller Name="Test">
+<Programs>
+<Program Name="Fill">
+<Routines>
+<Routine Name="Main" Type="RLL"><RLLContent><Rung Number="0"><Text>XIC(Start)MOV(Speed,Line.Rate);</Text></Rung></RLLContent></Routine>
+<Routine Name="Calc" Type="ST"><STContent><Line Number="0">Data[i].Value := Level + 1;</Line><Line Number="1">Tmr(IN := Run, Q => Done);</Line></STContent></Routine>
+</Routines>
+</Program>
+</Programs>
+</Controller>
+</RSLogix5000Content>"#;
+        let kinds = |tag: &str| -> Vec<AccessKind> {
+            accesses(xml).into_iter().filter(|(t, _, _)| t == tag).map(|(_, _, k)| k).collect()
+        };
+        assert_eq!(kinds("Start"), [AccessKind::Read]);
+        assert_eq!(kinds("Speed"), [AccessKind::Read]);
+        assert_eq!(kinds("Line"), [AccessKind::Write]);
+        assert_eq!(kinds("Data"), [AccessKind::Write]);
+        assert_eq!(kinds("i"), [AccessKind::Read]);
+        assert_eq!(kinds("Level"), [AccessKind::Read]);
+        assert_eq!(kinds("Tmr"), [AccessKind::Read, AccessKind::Write]);
+        assert_eq!(kinds("Run"), [AccessKind::Read]);
+        assert_eq!(kinds("Done"), [AccessKind::Write]);
+        assert!(accesses(xml).iter().all(|(_, program, _)| program == "Fill"));
+    }
+}
@@ -0,0 +1,248 @@
+//! Task scheduling of programs.
+//!
+//! A neutral view of the tasks of a project and the programs they run,
+//! built from the L5X `Tasks` section or the PLCopen
+//! `configurations/resources/tasks`. A program that no task runs never
+//! executes.
+//!
+//! PLCopen program instances declared directly in a resource, without a
+//! task, run at the lowest priority and count as scheduled.
+
+use std::fmt;
+
+use l5x::Controller;
+
+/// What starts a task.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TaskTrigger {
+    /// Runs continuously in the background
+    Continuous,
+    /// Runs at a fixed interval (e.g., "10" ms for L5X, "T#10ms" for PLCopen)
+    Periodic { interval: Option<String> },
+    /// Runs when an event or trigger variable fires
+    Event { trigger: Option<String> },
+}
+
+impl fmt::Display for TaskTrigger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TaskTrigger::Continuous => write!(f, "continuous"),
+            TaskTrigger::Periodic { interval: Some(interval) } => write!(f, "periodic ({})", interval),
+            TaskTrigger::Periodic { interval: None } => write!(f, "periodic"),
+            TaskTrigger::Event { trigger: Some(trigger) } => write!(f, "event ({})", trigger),
+            TaskTrigger::Event { trigger: None } => write!(f, "event"),
+        }
+    }
+}
+
+/// A task and the programs bound to it.
+#[derive(Debug, Clone)]
+pub struct Task {
+    /// Task name
+    pub name: String,
+    /// What starts the task
+    pub trigger: TaskTrigger,
+    /// Names of the programs the task runs, in scan order
+    pub programs: Vec<String>,
+}
+
+/// Tasks and programs of a project.
+#[derive(Debug, Clone, Default)]
+pub struct TaskBindings {
+    /// All tasks
+    pub tasks: Vec<Task>,
+    /// All programs in the project
+    pub programs: Vec<String>,
+    /// Programs that run without a task (PLCopen resource-level instances)
+    pub unbound_instances: Vec<String>,
+}
+
+impl TaskBindings {
+    /// Collect the tasks and programs of an L5X controller.
+    ///
+    /// Returns `None` if the export has no `Tasks` section (e.g. a single
+    /// program export), since scheduling cannot be checked then.
+    pub fn from_controller(controller: &Controller) -> Option<Self> {
+        let tasks = controller.tasks.as_ref()?;
+
+        let tasks = tasks
+            .task
+            .iter()
+            .map(|task| Task {
+                name: task.name.clone(),
+                trigger: match task.r#type.to_uppercase().as_str() {
+                    "PERIODIC" => TaskTrigger::Periodic { interval: task.rate.clone() },
+                    "EVENT" => TaskTrigger::Event {
+                        trigger: task.event_info.as_ref().and_then(|e| e.event_trigger.clone()),
+                    },
+                    _ => TaskTrigger::Continuous,
+                },
+                programs: task
+                    .scheduled_programs
+                    .as_ref()
+                    .map(|s| s.scheduled_program.iter().map(|p| p.name.clone()).collect())
+                    .unwrap_or_default(),
+            })
+            .collect();
+
+        let programs = controller
+            .programs
+            .as_ref()
+            .map(|p| p.program.iter().map(|program| program.name.clone()).collect())
+            .unwrap_or_default();
+
+        Some(Self {
+            tasks,
+            programs,
+            unbound_instances: Vec::new(),
+        })
+    }
+
+    /// Collect the tasks and program POUs of a PLCopen project.
+    ///
+    /// Task bindings refer to program POUs through the `typeName` of their
+    /// instances. Returns `None` if the project declares no configuration.
+    pub fn from_plcopen(project: &plcopen::Project) -> Option<Self> {
+        let configurations = project.instances.as_ref()?.configurations.as_ref()?;
+        if configurations.configuration.is_empty() {
+            return None;
+        }
+
+        let mut tasks = Vec::new();
+        let mut unbound_instances = Vec::new();
+        for resource in configurations.configuration.iter().flat_map(|c| &c.resource) {
+            for task in &resource.task {
+                tasks.push(Task {
+                    name: task.name.clone(),
+                    trigger: match (&task.single, &task.interval) {
+                        (Some(single), _) => TaskTrigger::Event { trigger: Some(single.clone()) },
+                        (None, Some(interval)) => TaskTrigger::Periodic { interval: Some(interval.clone()) },
+                        (None, None) => TaskTrigger::Continuous,
+                    },
+                    programs: task.pou_instance.iter().map(|i| i.type_name.clone()).collect(),
+                });
+            }
+            unbound_instances.extend(resource.pou_instance.iter().map(|i| i.type_name.clone()));
+        }
+
+        let programs = project
+            .types
+            .as_ref()
+            .and_then(|t| t.pous.as_ref())
+            .map(|pous| {
+                pous.pou
+                    .iter()
+                    .filter(|pou| pou.pou_type.eq_ignore_ascii_case("program"))
+                    .map(|pou| pou.name.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Some(Self {
+            tasks,
+            programs,
+            unbound_instances,
+        })
+    }
+
+    /// Get the task that runs a program.
+    pub fn task_of(&self, program: &str) -> Option<&Task> {
+        self.tasks
+            .iter()
+            .find(|task| task.programs.iter().any(|p| p.eq_ignore_ascii_case(program)))
+    }
+
+    /// Get the programs that no task runs.
+    pub fn unscheduled_programs(&self) -> Vec<&str> {
+        self.programs
+            .iter()
+            .filter(|program| self.task_of(program).is_none())
+            .filter(|program| !self.unbound_instances.iter().any(|i| i.eq_ignore_ascii_case(program)))
+            .map(|program| program.as_str())
+            .collect()
+    }
+
+    /// Get the programs that run: those of the tasks and the unbound
+    /// instances, the entry points of the call graph.
+    pub fn entry_points(&self) -> impl Iterator<Item = &str> {
+        self.tasks
+            .iter()
+            .flat_map(|task| &task.programs)
+            .chain(&self.unbound_instances)
+            .map(String::as_str)
+    }
+
+    /// Get the tasks that run no programs.
+    pub fn empty_tasks(&self) -> Vec<&Task> {
+        self.tasks.iter().filter(|task| task.programs.is_empty()).collect()
+    }
+
+    /// Guess the task an unscheduled program belongs to.
+    ///
+    /// Picks the task whose name or scheduled program names share the
+    /// longest common prefix with the program name (at least 3 characters,
+    /// case-insensitive).
+    pub fn suggest_task(&self, program: &str) -> Option<&Task> {
+        self.tasks
+            .iter()
+            .map(|task| {
+                let best = std::iter::once(task.name.as_str())
+                    .chain(task.programs.iter().map(|p| p.as_str()))
+                    .map(|name| common_prefix_len(name, program))
+                    .max()
+                    .unwrap_or(0);
+                (task, best)
+            })
+            .filter(|(_, len)| *len >= 3)
+            .max_by_key(|(_, len)| *len)
+            .map(|(task, _)| task)
+    }
+}
+
+/// Length of the common prefix of two names, ignoring case.
+fn common_prefix_len(a: &str, b: &str) -> usize {
+    a.chars()
+        .zip(b.chars())
+        .take_while(|(x, y)| x.eq_ignore_ascii_case(y))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bindings() -> TaskBindings {
+        TaskBindings {
+            tasks: vec![
+                Task {
+                    name: "MainTask".to_string(),
+                    trigger: TaskTrigger::Continuous,
+                    programs: vec!["Conveyor_Main".to_string()],
+                },
+                Task {
+                    name: "Fast".to_string(),
+                    trigger: TaskTrigger::Periodic { interval: Some("10".to_string()) },
+                    programs: vec![],
+                },
+            ],
+            programs: vec!["Conveyor_Main".to_string(), "Conveyor_Aux".to_string(), "Spare".to_string()],
+            unbound_instances: vec![],
+        }
+    }
+
+    #[test]
+    fn test_unscheduled_and_empty() {
+        let bindings = bindings();
+        assert_eq!(bindings.unscheduled_programs(), vec!["Conveyor_Aux", "Spare"]);
+        let empty: Vec<_> = bindings.empty_tasks().iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(empty, vec!["Fast"]);
+        assert_eq!(bindings.tasks[1].trigger.to_string(), "periodic (10)");
+    }
+
+    #[test]
+    fn test_suggest_task() {
+        let bindings = bindings();
+        assert_eq!(bindings.suggest_task("Conveyor_Aux").map(|t| t.name.as_str()), Some("MainTask"));
+        assert!(bindings.suggest_task("Spare").is_none());
+    }
+}
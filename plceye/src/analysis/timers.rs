@@ -0,0 +1,344 @@
+//! Timer and counter resets.
+//!
+//! Retentive timers (`RTO`, `RTOR`) and counters (`CTU`, `CTD`, `CTUD`)
+//! keep their accumulated value until reset. An instance is reset by:
+//!
+//! - an RLL `RES` of the instance, or an RLL write of its `.RES` or `.ACC`
+//!   member
+//! - an ST assignment to its reset input or `.ACC` member
+//! - an IEC call passing a reset input (`R` of `CTU`/`CTUD`, `LD` of
+//!   `CTD`) that is not the constant `FALSE`
+//!
+//! `TON`/`TOF` timers whose enable is constantly true are collected as
+//! well: an RLL rung that starts with the timer instruction, or an IEC call
+//! with `IN := TRUE`.
+
+use std::collections::HashSet;
+
+use iecst::{Expr, ExprKind, Stmt, StmtKind, TypeKind};
+use l5x::rll::RungElement;
+
+use super::{LocatedRung, StUnits};
+
+/// Retentive RLL and Rockwell ST instructions
+const RETENTIVE_INSTRUCTIONS: &[&str] = &["RTO", "CTU", "CTD", "RTOR", "CTUD"];
+/// Retentive IEC 61131-3 function blocks
+const RETENTIVE_FBS: &[&str] = &["CTU", "CTD", "CTUD"];
+/// Members that reset an instance when written
+const RESET_MEMBERS: &[&str] = &["RES", "RESET", "ACC", "R", "LD"];
+
+/// A timer or counter instance used by an instruction.
+#[derive(Debug, Clone)]
+pub struct TimerUse {
+    /// Instance tag or variable name
+    pub name: String,
+    /// Timer or counter instruction (e.g., "RTO", "CTU")
+    pub instruction: String,
+    /// Location for findings (e.g., "MainProgram/MainRoutine/Rung#3")
+    pub location: String,
+}
+
+/// Timer and counter instances of a project and how they are reset.
+#[derive(Debug, Clone, Default)]
+pub struct TimerUsage {
+    /// Retentive timer and counter uses, one per instance
+    pub retentive: Vec<TimerUse>,
+    /// Uppercase names of instances that are reset somewhere
+    pub reset: HashSet<String>,
+    /// `TON`/`TOF` uses whose enable is constantly true
+    pub always_enabled: Vec<TimerUse>,
+}
+
+impl TimerUsage {
+    /// Collect the timers and counters of L5X rungs and ST routines.
+    pub fn from_controller(rungs: &[LocatedRung], st_units: &StUnits) -> Self {
+        let mut usage = Self::default();
+
+        for rung in rungs {
+            let Some(ref content) = rung.parsed.content else {
+                continue;
+            };
+            let location = rung.location.path();
+            for (_, instruction) in content.instructions() {
+                let mnemonic = instruction.mnemonic.to_uppercase();
+                let first = instruction.operands.first().and_then(|o| o.as_value());
+                match mnemonic.as_str() {
+                    "RTO" | "CTU" | "CTD" => {
+                        if let Some(name) = first {
+                            usage.add_retentive(base_name(name), &mnemonic, &location);
+                        }
+                    }
+                    "RES" => {
+                        if let Some(name) = first {
+                            usage.reset.insert(base_name(name).to_uppercase());
+                        }
+                    }
+                    _ => {}
+                }
+
+                // Writes of the reset or accumulator member
+                let destination = match mnemonic.as_str() {
+                    "OTE" | "OTL" | "CLR" | "CPT" => instruction.operands.first(),
+                    "MOV" | "COP" => instruction.operands.get(1),
+                    _ => None,
+                };
+                if let Some(target) = destination.and_then(|o| o.as_value()) {
+                    if let Some(name) = reset_member_target(target) {
+                        usage.reset.insert(name.to_uppercase());
+                    }
+                }
+            }
+
+            if let Some(RungElement::Instruction(first)) = content.elements.first() {
+                let mnemonic = first.mnemonic.to_uppercase();
+                if matches!(mnemonic.as_str(), "TON" | "TOF") {
+                    if let Some(name) = first.operands.first().and_then(|o| o.as_value()) {
+                        usage.always_enabled.push(TimerUse {
+                            name: base_name(name).to_string(),
+                            instruction: mnemonic,
+                            location: location.clone(),
+                        });
+                    }
+                }
+            }
+        }
+
+        usage.collect_st(st_units);
+        usage
+    }
+
+    /// Collect the timers and counters of PLCopen ST bodies.
+    pub fn from_plcopen(st_units: &StUnits) -> Self {
+        let mut usage = Self::default();
+        usage.collect_st(st_units);
+        usage
+    }
+
+    /// Get the retentive instances that are never reset.
+    pub fn never_reset(&self) -> Vec<&TimerUse> {
+        self.retentive
+            .iter()
+            .filter(|timer| !self.reset.contains(&timer.name.to_uppercase()))
+            .collect()
+    }
+
+    fn add_retentive(&mut self, name: &str, instruction: &str, location: &str) {
+        if !self.retentive.iter().any(|t| t.name.eq_ignore_ascii_case(name)) {
+            self.retentive.push(TimerUse {
+                name: name.to_string(),
+                instruction: instruction.to_string(),
+                location: location.to_string(),
+            });
+        }
+    }
+
+    /// Collect calls and resets in ST units.
+    fn collect_st(&mut self, st_units: &StUnits) {
+        for unit in &st_units.units {
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+            // IEC instances are declared with their function block type
+            let fb_types: Vec<(String, String)> = pou
+                .var_blocks
+                .iter()
+                .flat_map(|block| &block.vars)
+                .filter_map(|var| match &var.var_type.kind {
+                    TypeKind::Simple(type_name) => Some((var.name.to_uppercase(), type_name.to_uppercase())),
+                    _ => None,
+                })
+                .collect();
+            let mut calls = Vec::new();
+            collect_st_statements(&pou.body, &mut calls, &mut self.reset);
+
+            for (name, args) in calls {
+                let upper = name.to_uppercase();
+                let first_ident = args.first().and_then(|(_, value)| match value.map(|v| &v.kind) {
+                    Some(ExprKind::Ident(instance)) => Some(instance.clone()),
+                    _ => None,
+                });
+                // Rockwell ST: RTOR(Timer), CTUD(Counter)
+                if RETENTIVE_INSTRUCTIONS.contains(&upper.as_str()) {
+                    if let Some(instance) = first_ident {
+                        self.add_retentive(&instance, &upper, &unit.location);
+                    }
+                    continue;
+                }
+
+                let Some((_, fb_type)) = fb_types.iter().find(|(var, _)| *var == upper) else {
+                    continue;
+                };
+                let arg = |param: &str| {
+                    args.iter()
+                        .find(|(arg_name, _)| arg_name.as_deref().is_some_and(|n| n.eq_ignore_ascii_case(param)))
+                        .and_then(|(_, value)| *value)
+                };
+                if RETENTIVE_FBS.contains(&fb_type.as_str()) {
+                    self.add_retentive(&name, fb_type, &unit.location);
+                    let reset_input = if fb_type == "CTD" { "LD" } else { "R" };
+                    if arg(reset_input).is_some_and(|value| !is_constant(value, false)) {
+                        self.reset.insert(upper);
+                    }
+                } else if matches!(fb_type.as_str(), "TON" | "TOF") && arg("IN").is_some_and(|value| is_constant(value, true)) {
+                    self.always_enabled.push(TimerUse {
+                        name: name.clone(),
+                        instruction: fb_type.clone(),
+                        location: unit.location.clone(),
+                    });
+                }
+            }
+        }
+    }
+}
+
+/// Named arguments of an ST call
+type CallArgs<'a> = Vec<(Option<String>, Option<&'a Expr>)>;
+
+/// Collect the statement calls and the instances whose reset members are assigned.
+fn collect_st_statements<'a>(body: &'a [Stmt], calls: &mut Vec<(String, CallArgs<'a>)>, reset: &mut HashSet<String>) {
+    for stmt in body {
+        match &stmt.kind {
+            StmtKind::Call { name, args } => {
+                calls.push((name.clone(), args.iter().map(|a| (a.name.clone(), a.value.as_ref())).collect()));
+            }
+            StmtKind::Assignment { target, value } => {
+                if let ExprKind::MemberAccess { expr, member } = &target.kind {
+                    let is_reset = RESET_MEMBERS.iter().any(|m| m.eq_ignore_ascii_case(member))
+                        || member.eq_ignore_ascii_case("ResetIn");
+                    if let (true, ExprKind::Ident(instance)) = (is_reset, &expr.kind) {
+                        if !is_constant(value, false) || member.eq_ignore_ascii_case("ACC") {
+                            reset.insert(instance.to_uppercase());
+                        }
+                    }
+                }
+            }
+            StmtKind::If { then_body, elsif_branches, else_body, .. } => {
+                collect_st_statements(then_body, calls, reset);
+                for (_, branch) in elsif_branches {
+                    collect_st_statements(branch, calls, reset);
+                }
+                if let Some(else_stmts) = else_body {
+                    collect_st_statements(else_stmts, calls, reset);
+                }
+            }
+            StmtKind::Case { cases, else_body, .. } => {
+                for branch in cases {
+                    collect_st_statements(&branch.body, calls, reset);
+                }
+                if let Some(else_stmts) = else_body {
+                    collect_st_statements(else_stmts, calls, reset);
+                }
+            }
+            StmtKind::For { body, .. } | StmtKind::While { body, .. } | StmtKind::Repeat { body, .. } => {
+                collect_st_statements(body, calls, reset);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Check whether an expression is the boolean constant `value` (or 0/1).
+fn is_constant(expr: &Expr, value: bool) -> bool {
+    match &expr.kind {
+        ExprKind::BoolLiteral(b) => *b == value,
+        ExprKind::IntLiteral(i) => *i == i64::from(value),
+        ExprKind::Paren(inner) => is_constant(inner, value),
+        _ => false,
+    }
+}
+
+/// Base tag of an operand (`Timer1` of `Timer1.DN` or `Timers[2]`).
+fn base_name(operand: &str) -> &str {
+    let end = operand.find(['.', '[']).unwrap_or(operand.len());
+    operand[..end].trim()
+}
+
+/// Instance whose reset or accumulator member an operand names.
+fn reset_member_target(operand: &str) -> Option<&str> {
+    let (instance, member) = operand.rsplit_once('.')?;
+    let is_reset = ["RES", "ACC"].iter().any(|m| m.eq_ignore_ascii_case(member.trim()));
+    (is_reset && !instance.contains('.')).then(|| base_name(instance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{analyze_controller, StUnits};
+    use crate::loader::LoadedProject;
+
+    fn usage(xml: &str) -> TimerUsage {
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        match project.l5x_controller {
+            Some(ref controller) => {
+                let analysis = analyze_controller(controller);
+                TimerUsage::from_controller(&analysis.rungs, &StUnits::from_controller(controller))
+            }
+            None => TimerUsage::from_plcopen(&StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap())),
+        }
+    }
+
+    #[test]
+    fn test_l5x_rll_timers() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <Programs>
+                    <Program Name="Main">
+                        <Routines>
+                            <Routine Name="Logic" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0"><Text>XIC(Run)RTO(RunTime,?,?);</Text></Rung>
+                                    <Rung Number="1"><Text>XIC(Part)CTU(Parts,?,?);</Text></Rung>
+                                    <Rung Number="2"><Text>XIC(Reset)RES(Parts);</Text></Rung>
+                                    <Rung Number="3"><Text>XIC(Box)CTU(Boxes,?,?);</Text></Rung>
+                                    <Rung Number="4"><Text>XIC(Clear)MOV(0,Boxes.ACC);</Text></Rung>
+                                    <Rung Number="5"><Text>TON(Pulse,?,?);</Text></Rung>
+                                    <Rung Number="6"><Text>XIC(Start)TON(Delay,?,?);</Text></Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+        let usage = usage(xml);
+        assert_eq!(usage.retentive.len(), 3);
+        let never: Vec<_> = usage.never_reset().iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(never, ["RunTime"]);
+        let always: Vec<_> = usage.always_enabled.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(always, ["Pulse"]);
+        assert_eq!(usage.always_enabled[0].location, "Main/Logic/Rung#5");
+    }
+
+    #[test]
+    fn test_plcopen_counters() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Main" pouType="program">
+      <interface><localVars>
+        <variable name="Parts"><type><derived name="CTU"/></type></variable>
+        <variable name="Boxes"><type><derived name="CTU"/></type></variable>
+        <variable name="Stock"><type><derived name="CTD"/></type></variable>
+        <variable name="Blink"><type><derived name="TON"/></type></variable>
+        <variable name="Part"><type><BOOL/></type></variable>
+      </localVars></interface>
+      <body><ST><![CDATA[
+Parts(CU := Part, R := FALSE, PV := 10);
+Boxes(CU := Part, R := Parts.Q, PV := 5);
+Stock(CD := Part, PV := 100);
+Blink(IN := TRUE, PT := T#1s);
+      ]]></ST></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let usage = usage(xml);
+        let never: Vec<_> = usage.never_reset().iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(never, ["Parts", "Stock"]);
+        assert_eq!(usage.always_enabled.len(), 1);
+        assert_eq!(usage.always_enabled[0].instruction, "TON");
+    }
+}
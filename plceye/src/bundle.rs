@@ -0,0 +1,404 @@
+//! Project bundles: several L5X or PLCopen files analyzed as one project.
+//!
+//! Large projects are often exported per program (partial exports), as a
+//! folder or a ZIP archive. The members are merged into one controller so
+//! that cross-file references (a JSR to a routine in another program, a
+//! controller tag defined in another export) resolve. PLCopen projects
+//! split into a library and a project file are merged the same way: their
+//! data types, POUs and configurations are combined into one project.
+//!
+//! Partial exports repeat the data types, AOIs and tags they depend on as
+//! context (`Use="Context"`). A definition with `Use="Target"` (or none)
+//! replaces a context copy; of two target definitions with the same name
+//! the first is kept and the other is recorded as a duplicate. PLCopen
+//! files have no context copies: identical definitions are merged, and of
+//! two different definitions with the same name the first is kept.
+
+use std::fmt;
+
+use l5x::Controller;
+use plcopen::Project;
+
+/// Kind of a top-level definition in an L5X export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DefinitionKind {
+    Program,
+    AddOnInstruction,
+    DataType,
+    Tag,
+    Task,
+    /// PLCopen program, function block or function
+    Pou,
+    /// PLCopen configuration
+    Configuration,
+}
+
+impl fmt::Display for DefinitionKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DefinitionKind::Program => write!(f, "program"),
+            DefinitionKind::AddOnInstruction => write!(f, "AOI"),
+            DefinitionKind::DataType => write!(f, "data type"),
+            DefinitionKind::Tag => write!(f, "controller tag"),
+            DefinitionKind::Task => write!(f, "task"),
+            DefinitionKind::Pou => write!(f, "POU"),
+            DefinitionKind::Configuration => write!(f, "configuration"),
+        }
+    }
+}
+
+/// A definition and the member file it was taken from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Definition {
+    /// Kind of definition
+    pub kind: DefinitionKind,
+    /// Definition name
+    pub name: String,
+    /// Member file (path inside the archive or folder)
+    pub member: String,
+}
+
+/// A definition found in more than one member file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateDefinition {
+    /// Kind of definition
+    pub kind: DefinitionKind,
+    /// Definition name
+    pub name: String,
+    /// Member whose definition is used
+    pub kept: String,
+    /// Member whose definition is ignored
+    pub ignored: String,
+}
+
+impl fmt::Display for DuplicateDefinition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} '{}' is defined in both {} and {}; using {}",
+            self.kind, self.name, self.kept, self.ignored, self.kept
+        )
+    }
+}
+
+/// Member files of a bundle and where each definition came from.
+#[derive(Debug, Clone, Default)]
+pub struct Bundle {
+    /// Member files, in load order
+    pub members: Vec<String>,
+    /// Definitions of the merged controller
+    pub definitions: Vec<Definition>,
+    /// Conflicting definitions that were dropped
+    pub duplicates: Vec<DuplicateDefinition>,
+}
+
+impl Bundle {
+    /// Merge the controllers of the member files into one.
+    ///
+    /// Controller attributes come from the first member. Returns `None`
+    /// when there are no members.
+    pub fn merge(members: Vec<(String, Controller)>) -> Option<(Controller, Bundle)> {
+        let mut bundle = Bundle::default();
+        let mut merged: Option<Controller> = None;
+
+        for (member, mut controller) in members {
+            let data_types = controller.data_types.take().map(|c| c.data_type).unwrap_or_default();
+            let aois = controller
+                .add_on_instruction_definitions
+                .take()
+                .map(|c| c.add_on_instruction_definition)
+                .unwrap_or_default();
+            let tags = controller.tags.take().map(|c| c.tag).unwrap_or_default();
+            let programs = controller.programs.take().map(|c| c.program).unwrap_or_default();
+            let tasks = controller.tasks.take().map(|c| c.task).unwrap_or_default();
+            let target = merged.get_or_insert(controller);
+
+            bundle.add(
+                &mut target.data_types.get_or_insert_with(Default::default).data_type,
+                data_types,
+                DefinitionKind::DataType,
+                &member,
+                |d| (&d.name, &d.r#use),
+            );
+            bundle.add(
+                &mut target
+                    .add_on_instruction_definitions
+                    .get_or_insert_with(Default::default)
+                    .add_on_instruction_definition,
+                aois,
+                DefinitionKind::AddOnInstruction,
+                &member,
+                |d| (&d.name, &d.r#use),
+            );
+            bundle.add(
+                &mut target.tags.get_or_insert_with(Default::default).tag,
+                tags,
+                DefinitionKind::Tag,
+                &member,
+                |d| (&d.name, &d.r#use),
+            );
+            bundle.add(
+                &mut target.programs.get_or_insert_with(Default::default).program,
+                programs,
+                DefinitionKind::Program,
+                &member,
+                |d| (&d.name, &d.r#use),
+            );
+            bundle.add(
+                &mut target.tasks.get_or_insert_with(Default::default).task,
+                tasks,
+                DefinitionKind::Task,
+                &member,
+                |d| (&d.name, &d.r#use),
+            );
+            bundle.members.push(member);
+        }
+
+        merged.map(|controller| (controller, bundle))
+    }
+
+    /// Merge the PLCopen projects of the member files into one.
+    ///
+    /// File and content headers come from the first member. Returns `None`
+    /// when there are no members.
+    pub fn merge_plcopen(members: Vec<(String, Project)>) -> Option<(Project, Bundle)> {
+        let mut bundle = Bundle::default();
+        let mut merged: Option<Project> = None;
+
+        for (member, mut project) in members {
+            let mut types = project.types.take().map(|t| *t).unwrap_or_default();
+            let data_types = types.data_types.take().map(|d| d.data_type).unwrap_or_default();
+            let pous = types.pous.take().map(|p| p.pou).unwrap_or_default();
+            let configurations = project
+                .instances
+                .take()
+                .and_then(|i| i.configurations)
+                .map(|c| c.configuration)
+                .unwrap_or_default();
+            let target = merged.get_or_insert(project);
+            let types = target.types.get_or_insert_with(Default::default);
+
+            bundle.add_plcopen(
+                &mut types.data_types.get_or_insert_with(Default::default).data_type,
+                data_types,
+                DefinitionKind::DataType,
+                &member,
+                |d| &d.name,
+            );
+            bundle.add_plcopen(
+                &mut types.pous.get_or_insert_with(Default::default).pou,
+                pous,
+                DefinitionKind::Pou,
+                &member,
+                |p| &p.name,
+            );
+            if !configurations.is_empty() {
+                bundle.add_plcopen(
+                    &mut target
+                        .instances
+                        .get_or_insert_with(Default::default)
+                        .configurations
+                        .get_or_insert_with(Default::default)
+                        .configuration,
+                    configurations,
+                    DefinitionKind::Configuration,
+                    &member,
+                    |c| &c.name,
+                );
+            }
+            bundle.members.push(member);
+        }
+
+        merged.map(|project| (project, bundle))
+    }
+
+    /// Add the definitions of one PLCopen member to a merged collection.
+    fn add_plcopen<T: PartialEq>(
+        &mut self,
+        merged: &mut Vec<T>,
+        items: Vec<T>,
+        kind: DefinitionKind,
+        member: &str,
+        name: fn(&T) -> &String,
+    ) {
+        for item in items {
+            let Some(existing) = merged.iter().find(|m| name(m).eq_ignore_ascii_case(name(&item))) else {
+                self.record(kind, name(&item), member);
+                merged.push(item);
+                continue;
+            };
+            if *existing == item {
+                continue;
+            }
+            if let Some(kept) = self.find(kind, name(&item)) {
+                self.duplicates.push(DuplicateDefinition {
+                    kind,
+                    name: name(&item).clone(),
+                    kept: kept.to_string(),
+                    ignored: member.to_string(),
+                });
+            }
+        }
+    }
+
+    /// Add the definitions of one member to a merged collection.
+    fn add<T>(
+        &mut self,
+        merged: &mut Vec<T>,
+        items: Vec<T>,
+        kind: DefinitionKind,
+        member: &str,
+        key: fn(&T) -> (&String, &Option<String>),
+    ) {
+        let is_context = |item: &T| key(item).1.as_deref().is_some_and(|u| u.eq_ignore_ascii_case("Context"));
+        for item in items {
+            let name = key(&item).0.clone();
+            let Some(index) = merged.iter().position(|m| key(m).0.eq_ignore_ascii_case(&name)) else {
+                if !is_context(&item) {
+                    self.record(kind, &name, member);
+                }
+                merged.push(item);
+                continue;
+            };
+            if is_context(&item) {
+                continue;
+            }
+            if is_context(&merged[index]) {
+                merged[index] = item;
+                self.record(kind, &name, member);
+            } else if let Some(kept) = self.find(kind, &name) {
+                self.duplicates.push(DuplicateDefinition {
+                    kind,
+                    name,
+                    kept: kept.to_string(),
+                    ignored: member.to_string(),
+                });
+            }
+        }
+    }
+
+    fn record(&mut self, kind: DefinitionKind, name: &str, member: &str) {
+        self.definitions.push(Definition {
+            kind,
+            name: name.to_string(),
+            member: member.to_string(),
+        });
+    }
+
+    /// Member file that defines `name`.
+    pub fn find(&self, kind: DefinitionKind, name: &str) -> Option<&str> {
+        self.definitions
+            .iter()
+            .find(|d| d.kind == kind && d.name.eq_ignore_ascii_case(name))
+            .map(|d| d.member.as_str())
+    }
+
+    /// Member file a finding belongs to, from its location
+    /// (`Program:Main`, `Main/Routine`, `AOI:Valve`, ...) or, for
+    /// controller-scope findings, its identifier.
+    pub fn member_of(&self, location: &str, identifier: &str) -> Option<&str> {
+        let scope = location.strip_prefix("Program:").unwrap_or(location);
+        if let Some(aoi) = scope.strip_prefix("AOI:") {
+            let name = aoi.split('/').next().unwrap_or(aoi);
+            return self.find(DefinitionKind::AddOnInstruction, name);
+        }
+        let head = scope.split('/').next().unwrap_or(scope);
+        let base = identifier.split(['.', '[', '/']).next().unwrap_or(identifier);
+        self.find(DefinitionKind::Program, head)
+            .or_else(|| self.find(DefinitionKind::AddOnInstruction, head))
+            .or_else(|| self.find(DefinitionKind::Pou, head))
+            .or_else(|| self.find(DefinitionKind::Tag, base))
+            .or_else(|| self.find(DefinitionKind::DataType, base))
+            .or_else(|| self.find(DefinitionKind::AddOnInstruction, base))
+            .or_else(|| self.find(DefinitionKind::Program, base))
+            .or_else(|| self.find(DefinitionKind::Pou, base))
+            .or_else(|| self.find(DefinitionKind::Task, base))
+            .or_else(|| self.find(DefinitionKind::Configuration, base))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn controller(xml: &str) -> Controller {
+        let project: l5x::Project = quick_xml::de::from_str(&format!(
+            r#"<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">{}</RSLogix5000Content>"#,
+            xml
+        ))
+        .unwrap();
+        project.controller.unwrap()
+    }
+
+    #[test]
+    fn test_merge_partial_exports() {
+        let filling = controller(
+            r#"<Controller Use="Context" Name="Line">
+                <DataTypes Use="Context"><DataType Name="Recipe" Use="Context"/></DataTypes>
+                <Tags Use="Context"><Tag Name="Speed" Use="Context" TagType="Base" DataType="DINT"/></Tags>
+                <Programs Use="Context"><Program Name="Filling" Use="Target"/></Programs>
+            </Controller>"#,
+        );
+        let capping = controller(
+            r#"<Controller Use="Context" Name="Line">
+                <DataTypes Use="Context"><DataType Name="Recipe" Use="Target"/></DataTypes>
+                <Tags Use="Context"><Tag Name="Speed" TagType="Base" DataType="DINT"/></Tags>
+                <Programs Use="Context"><Program Name="Capping" Use="Target"/><Program Name="Filling" Use="Target"/></Programs>
+            </Controller>"#,
+        );
+
+        let (merged, bundle) =
+            Bundle::merge(vec![("Filling.L5X".to_string(), filling), ("Capping.L5X".to_string(), capping)]).unwrap();
+        assert_eq!(merged.name, "Line");
+        assert_eq!(merged.programs.as_ref().unwrap().program.len(), 2);
+        assert_eq!(merged.data_types.as_ref().unwrap().data_type.len(), 1);
+        assert_eq!(merged.tags.as_ref().unwrap().tag.len(), 1);
+
+        // Context copies are replaced by the defining member
+        assert_eq!(bundle.find(DefinitionKind::DataType, "Recipe"), Some("Capping.L5X"));
+        assert_eq!(bundle.find(DefinitionKind::Tag, "speed"), Some("Capping.L5X"));
+        assert_eq!(bundle.member_of("Filling/Main", "Main"), Some("Filling.L5X"));
+        assert_eq!(bundle.member_of("Program:Capping", "Valve"), Some("Capping.L5X"));
+        assert_eq!(bundle.member_of("Controller", "Speed.ACC"), Some("Capping.L5X"));
+        assert_eq!(bundle.member_of("Controller", "Unknown"), None);
+
+        assert_eq!(bundle.duplicates.len(), 1);
+        assert_eq!(
+            bundle.duplicates[0].to_string(),
+            "program 'Filling' is defined in both Filling.L5X and Capping.L5X; using Filling.L5X"
+        );
+    }
+
+    fn plcopen(pous: &str) -> Project {
+        plcopen::from_str(&format!(
+            r#"<project xmlns="http://www.plcopen.org/xml/tc6_0200">
+                <contentHeader name="Line"/>
+                <types><dataTypes><dataType name="Recipe"><baseType><INT/></baseType></dataType></dataTypes><pous>{}</pous></types>
+            </project>"#,
+            pous
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_merge_plcopen_projects() {
+        let library = plcopen(r#"<pou name="Scale" pouType="function"/><pou name="Helper" pouType="function"/>"#);
+        let project = plcopen(r#"<pou name="Main" pouType="program"/><pou name="Helper" pouType="functionBlock"/>"#);
+
+        let (merged, bundle) =
+            Bundle::merge_plcopen(vec![("lib.xml".to_string(), library), ("project.xml".to_string(), project)]).unwrap();
+        let types = merged.types.as_ref().unwrap();
+        let pous: Vec<_> = types.pous.as_ref().unwrap().pou.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(pous, ["Scale", "Helper", "Main"]);
+        // The identical data type in both files is not a conflict
+        assert_eq!(types.data_types.as_ref().unwrap().data_type.len(), 1);
+
+        assert_eq!(bundle.member_of("Main", "x"), Some("project.xml"));
+        assert_eq!(bundle.member_of("project", "Scale"), Some("lib.xml"));
+        assert_eq!(bundle.duplicates.len(), 1);
+        assert_eq!(
+            bundle.duplicates[0].to_string(),
+            "POU 'Helper' is defined in both lib.xml and project.xml; using lib.xml"
+        );
+    }
+}
@@ -3,18 +3,37 @@
 //! Configuration can be loaded from a `plceye.toml` file.
 
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::Path;
 
+use regex::Regex;
+
+use crate::analysis::{NameCategory, RoutineKind};
+use crate::messages;
+use crate::report::{RuleKind, Severity};
 use crate::{Error, Result};
 use crate::error::ConfigErrorKind;
 
 /// Main configuration for the rule detector.
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-#[serde(default)]
+#[serde(default, deny_unknown_fields)]
 pub struct RuleConfig {
+    /// Profile the settings are based on (see [`RuleConfig::PROFILES`])
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub profile: Option<String>,
+
     /// Global settings
     pub general: GeneralConfig,
 
+    /// Severity escalation by finding count
+    pub escalation: EscalationConfig,
+
+    /// Message templates by rule code or name (see [`crate::messages`])
+    pub messages: BTreeMap<String, String>,
+
+    /// Rules of registered detectors
+    pub custom_rules: CustomRulesConfig,
+
     /// Unused tag detection settings
     pub unused_tags: UnusedTagsConfig,
 
@@ -35,6 +54,144 @@ pub struct RuleConfig {
 
     /// Deep nesting detection settings
     pub nesting: NestingConfig,
+
+    /// Type mismatch detection settings
+    pub type_mismatch: TypeMismatchConfig,
+
+    /// Non-exhaustive CASE detection settings
+    pub non_exhaustive_case: NonExhaustiveCaseConfig,
+
+    /// Overlapping CASE label detection settings
+    pub overlapping_case_labels: OverlappingCaseLabelsConfig,
+
+    /// FOR loop variable modification detection settings
+    pub loop_var_modified: LoopVarModifiedConfig,
+
+    /// Empty branch detection settings
+    pub empty_branches: EmptyBranchesConfig,
+
+    /// Self-assignment detection settings
+    pub self_assignment: SelfAssignmentConfig,
+
+    /// Floating-point equality detection settings
+    pub float_equality: FloatEqualityConfig,
+
+    /// Unused variable detection by VAR block kind settings
+    pub unused_variables: UnusedVariablesConfig,
+
+    /// Naming convention checks settings
+    pub naming: NamingConfig,
+
+    /// Identifier length and reserved word checks settings
+    pub identifiers: IdentifiersConfig,
+
+    /// Uninitialized read detection settings
+    pub uninitialized_read: UninitializedReadConfig,
+
+    /// Required initial value detection settings
+    pub required_init: RequiredInitConfig,
+
+    /// Unscheduled program detection settings
+    pub unscheduled_program: UnscheduledProgramConfig,
+
+    /// Uninvoked instance detection settings
+    pub uninvoked_instance: UninvokedInstanceConfig,
+
+    /// Array bounds detection settings
+    pub array_bounds: ArrayBoundsConfig,
+
+    /// ST text style settings
+    pub style: StyleConfig,
+
+    /// Low comment density detection settings
+    pub low_comment_density: LowCommentDensityConfig,
+
+    /// IL jump label detection settings
+    pub jump_labels: JumpLabelsConfig,
+
+    /// Timer and counter reset detection settings
+    pub timer_no_reset: TimerNoResetConfig,
+
+    /// String truncation detection settings
+    pub string_truncation: StringTruncationConfig,
+
+    /// Duplicate logic detection settings
+    pub duplicate_logic: DuplicateLogicConfig,
+
+    /// Unconnected FBD pin detection settings
+    pub fbd_unconnected: FbdUnconnectedConfig,
+
+    /// Division by zero detection settings
+    pub division_by_zero: DivisionByZeroConfig,
+
+    /// Suspicious bit operation detection settings
+    pub bit_operation: BitOperationConfig,
+
+    /// Deep member access (law of Demeter) detection settings
+    pub law_of_demeter: LawOfDemeterConfig,
+
+    /// Comparison statement detection settings
+    pub comparison_statement: ComparisonStatementConfig,
+
+    /// Impure function detection settings
+    pub impure_function: ImpureFunctionConfig,
+
+    /// Dead store detection settings
+    pub dead_store: DeadStoreConfig,
+
+    /// Nonstandard function detection settings
+    pub nonstandard_function: NonstandardFunctionConfig,
+
+    /// Comment marker detection settings
+    pub comment_markers: CommentMarkersConfig,
+
+    /// Tag scope suggestion detection settings
+    pub tag_scope_suggestion: TagScopeSuggestionConfig,
+
+    /// Redundant boolean expression detection settings
+    pub redundant_boolean: RedundantBooleanConfig,
+
+    /// Enum value conflict detection settings
+    pub enum_value_conflict: EnumValueConflictConfig,
+
+    /// Cross-program shared tag detection settings
+    pub cross_program_shared_tag: CrossProgramSharedTagConfig,
+
+    /// Unused method parameter detection settings
+    pub unused_method_parameter: UnusedMethodParameterConfig,
+
+    /// Unreachable POU detection settings
+    pub unreachable_pou: UnreachablePouConfig,
+
+    /// VAR_IN_OUT binding detection settings
+    pub in_out_not_variable: InOutNotVariableConfig,
+
+    /// Invalid array range detection settings
+    pub invalid_array_range: InvalidArrayRangeConfig,
+
+    /// Invalid struct detection settings
+    pub invalid_struct: InvalidStructConfig,
+
+    /// Unbound input detection settings
+    pub unbound_input: UnboundInputConfig,
+
+    /// String concatenation in loop detection settings
+    pub string_concat_in_loop: StringConcatInLoopConfig,
+
+    /// Hardcoded address detection settings
+    pub hardcoded_address: HardcodedAddressConfig,
+
+    /// IF/ELSIF chain detection settings
+    pub convert_to_case: ConvertToCaseConfig,
+
+    /// Argument conversion detection settings
+    pub argument_conversion: ArgumentConversionConfig,
+
+    /// Bool toggle detection settings
+    pub bool_toggle: BoolToggleConfig,
+
+    /// Undefined data type reference detection settings
+    pub undefined_datatype_ref: UndefinedDataTypeRefConfig,
 }
 
 impl RuleConfig {
@@ -47,255 +204,2301 @@ impl RuleConfig {
         Self::parse(&content)
     }
 
+    /// Load configuration from a TOML file, seeded from `profile` instead of
+    /// the file's own `profile` key when given.
+    pub fn from_file_with_profile(path: &Path, profile: Option<&str>) -> Result<Self> {
+        let content = std::fs::read_to_string(path).map_err(|e| Error::FileRead {
+            path: path.display().to_string(),
+            source: e,
+        })?;
+        Self::parse_with_profile(&content, profile)
+    }
+
     /// Parse configuration from a TOML string.
+    ///
+    /// A top-level `profile = "name"` key seeds the settings from that
+    /// profile; the tables of the file then override individual fields.
     pub fn parse(content: &str) -> Result<Self> {
-        toml::from_str(content).map_err(|_| Error::ConfigParse {
+        Self::parse_with_profile(content, None)
+    }
+
+    /// Parse configuration from a TOML string, seeded from `profile` instead
+    /// of the `profile` key when given.
+    pub fn parse_with_profile(content: &str, profile: Option<&str>) -> Result<Self> {
+        let syntax_error = |_| Error::ConfigParse {
             kind: ConfigErrorKind::TomlSyntax,
-        })
+        };
+        let mut table: toml::Table = toml::from_str(content).map_err(syntax_error)?;
+        // Check the file's own keys and value types first, for errors with a line
+        let own = toml::from_str::<Self>(content).map_err(|e| Error::ConfigParse {
+            kind: schema_error(content, &e),
+        })?;
+        check_messages(content, &own.messages).map_err(|kind| Error::ConfigParse { kind })?;
+
+        let from_key = profile.is_none();
+        let profile = match (profile, table.get("profile")) {
+            (Some(name), _) => Some(name.to_string()),
+            (None, Some(toml::Value::String(name))) => Some(name.clone()),
+            (None, Some(_)) => {
+                return Err(Error::ConfigParse {
+                    kind: ConfigErrorKind::TomlSyntax,
+                })
+            }
+            (None, None) => None,
+        };
+        if let Some(ref name) = profile {
+            // An unknown `profile` key is an error in the file itself
+            let base = Self::profile(name).map_err(|e| match e {
+                Error::UnknownProfile { name } if from_key => Error::ConfigParse {
+                    kind: ConfigErrorKind::UnknownProfile { name },
+                },
+                e => e,
+            })?;
+            let base = toml::Table::try_from(base).map_err(|_| Error::ConfigParse {
+                kind: ConfigErrorKind::TomlSyntax,
+            })?;
+            table = merge_tables(base, table);
+            table.insert("profile".to_string(), toml::Value::String(name.to_lowercase()));
+        }
+
+        let mut config: Self = table.try_into().map_err(syntax_error)?;
+        config.validate().map_err(|e| match e {
+            Error::ConfigParse {
+                kind: ConfigErrorKind::InvalidValue { key, message, .. },
+            } => Error::ConfigParse {
+                kind: ConfigErrorKind::InvalidValue {
+                    line: key_line(content, &key),
+                    key,
+                    message,
+                },
+            },
+            e => e,
+        })?;
+        config.naming.compile()?;
+        Ok(config)
+    }
+
+    /// Message template of a rule, looked up by code (`M0001`) or name
+    /// (`cyclomatic-complexity`).
+    pub fn message_template(&self, code: &str, name: &str) -> Option<&str> {
+        self.messages
+            .iter()
+            .find(|(rule, _)| rule.eq_ignore_ascii_case(code) || rule.eq_ignore_ascii_case(name))
+            .map(|(_, template)| template.as_str())
+    }
+
+    /// Add the templates of a message catalog under those of the
+    /// configuration: a catalog entry is skipped when the configuration has
+    /// one with the same key. Use the same key style (codes or names) in
+    /// both, since [`message_template`](Self::message_template) takes the
+    /// first match.
+    pub fn add_messages(&mut self, catalog: BTreeMap<String, String>) {
+        for (rule, template) in catalog {
+            if !self.messages.keys().any(|key| key.eq_ignore_ascii_case(&rule)) {
+                self.messages.insert(rule, template);
+            }
+        }
+    }
+
+    /// Check that values are in range: thresholds of at least 1, percentages
+    /// and ratios within their bounds, a known minimum severity, and message
+    /// templates with known placeholders.
+    pub fn validate(&self) -> Result<()> {
+        let invalid = |key: &str, message: &str| {
+            Err(Error::ConfigParse {
+                kind: ConfigErrorKind::InvalidValue {
+                    key: key.to_string(),
+                    line: None,
+                    message: message.to_string(),
+                },
+            })
+        };
+
+        if Severity::parse(&self.general.min_severity).is_none() {
+            return invalid("general.min_severity", "expected info, warning or error");
+        }
+        if self.general.acd_export_cmd.as_deref().is_some_and(|cmd| crate::acd::command_words(cmd).is_none()) {
+            return invalid("general.acd_export_cmd", "expected a command");
+        }
+        if Severity::parse(&self.comment_markers.severity).is_none() {
+            return invalid("comment_markers.severity", "expected info, warning or error");
+        }
+        if Severity::parse(&self.cross_program_shared_tag.severity).is_none() {
+            return invalid("cross_program_shared_tag.severity", "expected info, warning or error");
+        }
+        for (rule, &threshold) in &self.escalation.thresholds {
+            if threshold == 0 {
+                return invalid(&format!("escalation.thresholds.{}", rule), "must be at least 1");
+            }
+        }
+        for (rule, template) in &self.messages {
+            if let Err(message) = messages::check_template(rule, template) {
+                return invalid(&format!("messages.{}", rule), &message);
+            }
+        }
+        let thresholds = [
+            ("complexity.max_complexity", Some(self.complexity.max_complexity)),
+            ("complexity.per_kind.function", self.complexity.per_kind.function),
+            ("complexity.per_kind.function_block", self.complexity.per_kind.function_block),
+            ("complexity.per_kind.program", self.complexity.per_kind.program),
+            ("complexity.per_kind.aoi", self.complexity.per_kind.aoi),
+            ("nesting.max_depth", Some(self.nesting.max_depth)),
+            ("nesting.per_kind.function", self.nesting.per_kind.function),
+            ("nesting.per_kind.function_block", self.nesting.per_kind.function_block),
+            ("nesting.per_kind.program", self.nesting.per_kind.program),
+            ("nesting.per_kind.aoi", self.nesting.per_kind.aoi),
+            ("identifiers.max_length", Some(self.identifiers.max_length)),
+            ("style.max_line_length", Some(self.style.max_line_length)),
+            ("duplicate_logic.min_size", Some(self.duplicate_logic.min_size)),
+            ("convert_to_case.min_branches", Some(self.convert_to_case.min_branches)),
+        ];
+        for (key, value) in thresholds {
+            if value == Some(0) {
+                return invalid(key, "must be at least 1");
+            }
+        }
+        if !(0.0..=100.0).contains(&self.low_comment_density.min_percent) {
+            return invalid("low_comment_density.min_percent", "must be between 0 and 100");
+        }
+        if !(0.0..=1.0).contains(&self.duplicate_logic.min_similarity) {
+            return invalid("duplicate_logic.min_similarity", "must be between 0.0 and 1.0");
+        }
+        Ok(())
+    }
+
+    /// Names of the built-in rule-set profiles.
+    pub const PROFILES: &'static [&'static str] = &["default", "strict", "relaxed", "safety"];
+
+    /// Create the configuration of a built-in profile.
+    ///
+    /// - `default` - every rule with its default settings (`style` is opt-in)
+    /// - `strict` - every rule including `style`, with lower complexity,
+    ///   nesting and identifier length limits and a higher comment density
+    /// - `relaxed` - warnings and errors only; naming, identifier, style,
+    ///   comment density and required initial value rules are off, and
+    ///   complexity and nesting limits are higher
+    /// - `safety` - correctness rules only; naming, identifier, style,
+    ///   comment density, unused AOI and unused data type rules are off
+    pub fn profile(name: &str) -> Result<Self> {
+        let mut config = Self::default();
+        match name.to_lowercase().as_str() {
+            "default" => {}
+            "strict" => {
+                config.complexity.max_complexity = 8;
+                config.nesting.max_depth = 3;
+                config.identifiers.max_length = 24;
+                config.style.enabled = true;
+                config.low_comment_density.min_percent = 10.0;
+                config.low_comment_density.max_uncommented_lines = 15;
+            }
+            "relaxed" => {
+                config.general.min_severity = "warning".to_string();
+                config.complexity.max_complexity = 15;
+                config.nesting.max_depth = 6;
+                config.naming.enabled = false;
+                config.identifiers.enabled = false;
+                config.style.enabled = false;
+                config.low_comment_density.enabled = false;
+                config.required_init.enabled = false;
+                config.unused_variables.check_inputs = false;
+                config.jump_labels.check_unused_labels = false;
+                config.timer_no_reset.check_always_enabled = false;
+            }
+            "safety" => {
+                config.naming.enabled = false;
+                config.identifiers.enabled = false;
+                config.style.enabled = false;
+                config.low_comment_density.enabled = false;
+                config.unused_aois.enabled = false;
+                config.unused_datatypes.enabled = false;
+            }
+            _ => {
+                return Err(Error::UnknownProfile {
+                    name: name.to_string(),
+                })
+            }
+        }
+        config.profile = Some(name.to_lowercase());
+        Ok(config)
+    }
+
+    /// Generate a default configuration file as a string.
+    pub fn default_toml() -> String {
+        r#"# plceye.toml - PLC Code Rule Detector Configuration
+
+# Rule-set profile the settings below override: "default", "strict",
+# "relaxed" or "safety"
+# profile = "strict"
+
+[general]
+# Minimum severity to report: "info", "warning", "error"
+min_severity = "info"
+# Match tag and variable names case-sensitively (IEC 61131-3 names are not)
+case_sensitive = false
+# Command that exports a Studio 5000 .ACD file to L5X for analysis;
+# {input} is the .ACD file, {output} the L5X file to write
+# acd_export_cmd = "acd2l5x.exe {input} {output}"
+
+[escalation.thresholds]
+# Raise every finding of a rule one severity level (info -> warning -> error)
+# when the rule has more than this many findings in a file. Keys are rule
+# codes or names. This is a coarse per-file signal of systemic problems,
+# not a judgement of the individual findings.
+# S0001 = 50
+# "empty-routine" = 10
+
+[messages]
+# Replace the English message of a rule's findings, by rule code or name.
+# Placeholders: {code} {rule} {severity} {location} {name} {message}, plus
+# {threshold} and the measured value for M0001, M0003, M0008, M0013 and N0007.
+# Write {{ and }} for literal braces. --lang LANG loads more templates from
+# plceye.LANG.toml.
+# M0001 = "Routine '{name}' is too complex: {complexity} > {threshold}"
+
+[custom_rules]
+# Rules of detectors registered through the library API
+# (RuleDetector::register) to skip, by rule code or name
+disabled = []
+
+[unused_tags]
+# Enable unused tag detection
+enabled = true
+
+# Ignore tags matching these patterns (glob-style)
+ignore_patterns = [
+    "_*",           # Tags starting with underscore (often internal)
+    "HMI_*",        # HMI interface tags
+]
+
+# Ignore tags in these scopes
+ignore_scopes = [
+    # "Program:MainProgram",  # Example: ignore MainProgram
+]
+
+[undefined_tags]
+# Enable undefined tag detection (tags referenced but not declared)
+enabled = true
+
+# Ignore undefined tags matching these patterns
+ignore_patterns = []
+
+# Tags provided by HMI or external systems (glob patterns, e.g. "HMI_*")
+allow = []
+
+# Functions and FBs provided by libraries not included in the export
+allow_calls = []
+
+# Resolve module I/O tags (Local:1:I, Drive1:O) against the exported modules
+module_tags = true
+
+[empty_routines]
+# Enable empty routine detection
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = [
+    # "Unused_*",    # Example: ignore placeholder routines
+]
+
+[unused_aois]
+# Enable unused AOI detection
+enabled = true
+
+# Ignore AOIs matching these patterns
+ignore_patterns = []
+
+[unused_datatypes]
+# Enable unused DataType detection
+enabled = true
+
+# Ignore DataTypes matching these patterns
+ignore_patterns = []
+
+[complexity]
+# Enable cyclomatic complexity detection for ST routines
+enabled = true
+
+# Maximum allowed cyclomatic complexity
+max_complexity = 10
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+# Per-POU-kind overrides (function, function_block, program, aoi)
+# [complexity.per_kind]
+# program = 20
+# function = 8
+
+[nesting]
+# Enable deep nesting detection for ST routines
+enabled = true
+
+# Maximum allowed nesting depth
+max_depth = 4
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+# Per-POU-kind overrides (function, function_block, program, aoi)
+# [nesting.per_kind]
+# program = 6
+
+[type_mismatch]
+# Enable type mismatch detection for ST assignments and comparisons
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[non_exhaustive_case]
+# Enable detection of CASE statements over enums that miss members
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[overlapping_case_labels]
+# Enable detection of duplicate or overlapping CASE labels
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[loop_var_modified]
+# Enable detection of FOR loop variables written inside the loop body
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[empty_branches]
+# Enable detection of empty IF/ELSE branches, loop bodies and CASE branches
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[self_assignment]
+# Enable detection of assignments like `x := x` that have no effect
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[float_equality]
+# Enable detection of `=`/`<>` comparisons on REAL and LREAL values
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[unused_variables]
+# Enable detection of unused inputs, unassigned outputs and unused temporaries
+enabled = true
+
+# Report inputs that are never read (S0007)
+check_inputs = true
+
+# Report outputs that are never assigned (S0008)
+check_outputs = true
+
+# Report VAR_TEMP variables that are never used (S0009)
+check_temps = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[naming]
+# Enable naming convention checks (patterns are regular expressions)
+enabled = true
+
+# Pattern per category; categories without a pattern are not checked
+# fb_instance = "^fb[A-Z]"
+# input = "^i[A-Z]"
+# output = "^q[A-Z]"
+# in_out = "^iq[A-Z]"
+# local = "^[a-z]"
+# global = "^g[A-Z]"
+# data_type = "^[A-Z][A-Za-z0-9]*$"
+# pou = "^[A-Z][A-Za-z0-9]*$"
+
+# Ignore names matching these patterns
+ignore_patterns = []
+
+[identifiers]
+# Enable identifier length and reserved word checks
+enabled = true
+
+# Maximum identifier length (e.g. 128 for TIA Portal, 40 for Logix)
+max_length = 30
+
+# Flag names that are IEC 61131-3 reserved words
+check_reserved_words = true
+
+# Ignore names matching these patterns
+ignore_patterns = []
+
+[uninitialized_read]
+# Enable detection of locals read before any assignment
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[required_init]
+# Enable detection of tags without a required initial value (only the
+# types and name patterns listed below are checked)
+enabled = true
+
+# Data types whose tags must have an initial value (e.g. ["REAL"])
+types = []
+
+# Tags matching these patterns must have an initial value (e.g. ["SP_*"])
+name_patterns = []
+
+# Ignore tags matching these patterns
+ignore_patterns = []
+
+[unscheduled_program]
+# Enable detection of programs not run by any task
+enabled = true
+
+# Report tasks that run no programs
+check_empty_tasks = true
+
+# Ignore programs and tasks matching these patterns
+ignore_patterns = []
+
+[uninvoked_instance]
+# Enable detection of FB and AOI instances that are never invoked
+enabled = true
+
+# Ignore instances matching these patterns
+ignore_patterns = []
+
+[array_bounds]
+# Enable detection of constant array indices outside the declared range
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[style]
+# Enable ST text style checks (opt-in)
+enabled = false
+
+# Maximum line length in characters
+max_line_length = 120
+
+# Allow tab characters
+allow_tabs = false
+
+# Report trailing whitespace
+check_trailing_whitespace = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[low_comment_density]
+# Enable detection of ST routines with too few comments
+enabled = true
+
+# Minimum percentage of comment characters
+min_percent = 5.0
+
+# Minimum non-blank lines before the density is checked
+min_lines = 10
+
+# Maximum non-blank lines of a routine without any comment
+max_uncommented_lines = 20
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[jump_labels]
+# Enable detection of IL jumps to undefined labels and unused labels
+enabled = true
+
+# Report labels that no jump targets (C0009)
+check_unused_labels = true
+
+# Ignore POUs matching these patterns
+ignore_patterns = []
+
+[timer_no_reset]
+# Enable detection of retentive timers and counters that are never reset
+enabled = true
+
+# Report RTO timers and CTU/CTD counters that are never reset
+check_retentive = true
+
+# Report TON/TOF timers whose enable is always true
+check_always_enabled = true
+
+# Ignore timers and counters matching these patterns
+ignore_patterns = []
+
+[string_truncation]
+# Enable detection of string assignments longer than the target length
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[duplicate_logic]
+# Enable detection of routines with duplicated logic
+enabled = true
+
+# Minimum number of rungs or statements for a routine to be compared
+min_size = 5
+
+# Minimum similarity (0.0 to 1.0) to report two routines as duplicates
+min_similarity = 0.9
+
+# Ignore routine paths (Program/Routine) matching these patterns
+ignore_patterns = []
+
+[fbd_unconnected]
+# Enable detection of unconnected FBD block pins and disconnected networks
+enabled = true
+
+# Report required inputs without a connection
+check_inputs = true
+
+# Report function outputs that feed nothing
+check_outputs = true
+
+# Report parts of a network that reach no output
+check_disconnected = true
+
+# Ignore POU names matching these patterns
+ignore_patterns = []
+
+[division_by_zero]
+# Enable detection of divisions by zero and unguarded divisors
+enabled = true
+
+# Report variable divisors that are not checked against zero
+check_variables = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[bit_operation]
+# Enable detection of mixed BOOL/bitwise and signed bit operations
+enabled = true
+
+# Report AND/OR/XOR mixing BOOL with integer or bit-string operands
+check_mixed = true
+
+# Report masks and shifts on signed integers (SINT, INT, DINT, LINT)
+check_signed = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[law_of_demeter]
+# Enable detection of deeply nested member access (A.B.C.D.E)
+enabled = true
+
+# Maximum member accesses and subscripts after the base variable
+max_depth = 4
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[comparison_statement]
+# Enable detection of comparisons used as statements (x = y; instead of x := y;)
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[impure_function]
+# Enable detection of FUNCTIONs that write VAR_GLOBAL/VAR_EXTERNAL variables or call function block instances
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[dead_store]
+# Enable detection of assignments whose value is overwritten before it is read
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[nonstandard_function]
+# Enable detection of calls to vendor functions outside the IEC 61131-3 standard library (portability)
+enabled = false
+
+# Names to treat as standard in addition to the IEC 61131-3 library
+standard_functions = []
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[comment_markers]
+# Report TODO/FIXME markers in ST comments, descriptions and documentation
+enabled = true
+
+# Marker keywords, matched as whole words (case-sensitive)
+keywords = ["TODO", "FIXME", "XXX", "HACK"]
+
+# Severity of the findings: "info", "warning" or "error"
+severity = "info"
+
+# Ignore locations matching these patterns
+ignore_patterns = []
+
+[tag_scope_suggestion]
+# Suggest program scope for controller tags used by only one program (Rockwell)
+enabled = true
+
+# Ignore tags matching these patterns
+ignore_patterns = []
+
+[redundant_boolean]
+# Detect redundant boolean expressions (X AND X, X OR NOT X, NOT NOT X)
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[enum_value_conflict]
+# Enable detection of enum members with duplicate or decreasing values
+enabled = true
+
+# Ignore data types matching these patterns
+ignore_patterns = []
+
+[cross_program_shared_tag]
+# Controller tags written in one program and read in another
+enabled = true
+
+# Severity of the findings: "info", "warning" or "error"
+severity = "info"
+
+# Ignore tags matching these patterns
+ignore_patterns = []
+
+[unused_method_parameter]
+# Method input parameters that the method never reads
+enabled = true
+
+# Skip OVERRIDE methods, which have to keep the base method signature
+ignore_overrides = false
+
+# Ignore parameters matching these patterns
+ignore_patterns = []
+
+[unreachable_pou]
+# POUs with no call path from the programs the tasks run
+enabled = true
+
+# Extra entry points, e.g. exported library POUs: ["Lib_*"]
+entry_points = []
+
+# Ignore POUs matching these patterns
+ignore_patterns = []
+
+[in_out_not_variable]
+# VAR_IN_OUT parameters bound to a literal, expression or function result
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[invalid_array_range]
+# Enable invalid array range detection (ARRAY[10..1], empty ranges)
+enabled = true
+
+# Also report ranges of a single element (ARRAY[5..5])
+single_element = false
+
+# Ignore POUs and types matching these patterns
+ignore_patterns = []
+
+[invalid_struct]
+# Enable invalid struct detection (duplicate members, recursive structs)
+enabled = true
+
+# Ignore types matching these patterns
+ignore_patterns = []
+
+[unbound_input]
+# Enable unbound input detection (FB calls that leave inputs at a stale value)
+enabled = true
+
+# Also report unbound inputs that have an initial value (info)
+initialized_inputs = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[string_concat_in_loop]
+# Enable detection of strings built up by CONCAT or INSERT inside loops
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[hardcoded_address]
+# Enable detection of direct addresses (%MW100, %QX2.3) used in logic
+enabled = true
+
+# Addresses that may be used directly (e.g. ["%MW*"])
+allowed_addresses = []
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[convert_to_case]
+# Enable detection of IF/ELSIF chains that compare one variable with constants
+enabled = true
+
+# Fewest IF/ELSIF conditions a chain needs to be reported
+min_branches = 3
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[argument_conversion]
+# Enable detection of call arguments whose type differs from the parameter type
+enabled = true
+
+# Report widening conversions within a type family (INT to DINT) as info
+widening = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[bool_toggle]
+# Enable detection of variables set to TRUE and FALSE in one unconditional sequence
+enabled = true
+
+# Ignore routines matching these patterns
+ignore_patterns = []
+
+[undefined_datatype_ref]
+# Enable detection of tags and variables whose data type is not defined
+enabled = true
+
+# Treat type names matching these patterns as defined (library or vendor types)
+ignore_patterns = []
+"#
+        .to_string()
+    }
+}
+
+/// General configuration settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct GeneralConfig {
+    /// Minimum severity level to report.
+    pub min_severity: String,
+
+    /// Match tag and variable names case-sensitively. IEC 61131-3
+    /// identifiers are case-insensitive; enable for vendors that enforce case.
+    pub case_sensitive: bool,
+
+    /// Command that exports a Studio 5000 `.ACD` file to L5X, so that `.ACD`
+    /// inputs can be analyzed. `{input}` and `{output}` are replaced by the
+    /// `.ACD` path and the L5X path to write; without them both are appended.
+    pub acd_export_cmd: Option<String>,
+}
+
+impl Default for GeneralConfig {
+    fn default() -> Self {
+        Self {
+            min_severity: "info".to_string(),
+            case_sensitive: false,
+            acd_export_cmd: None,
+        }
+    }
+}
+
+/// Configuration for severity escalation by finding count.
+///
+/// When a rule has more findings in a file than its threshold, all of its
+/// findings in that file are raised one severity level. The count is an
+/// aggregate signal of how widespread a problem is; it says nothing about
+/// the individual findings.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EscalationConfig {
+    /// Finding count above which a rule is escalated, by rule code or name
+    pub thresholds: BTreeMap<String, usize>,
+}
+
+impl EscalationConfig {
+    /// Threshold of a rule, looked up by code (`S0001`) or name (`unused-tag`).
+    pub fn threshold(&self, code: &str, name: &str) -> Option<usize> {
+        self.thresholds
+            .iter()
+            .find(|(rule, _)| rule.eq_ignore_ascii_case(code) || rule.eq_ignore_ascii_case(name))
+            .map(|(_, &threshold)| threshold)
+    }
+}
+
+/// Configuration for the rules of registered detectors.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CustomRulesConfig {
+    /// Rule codes or names of registered rules to skip
+    pub disabled: Vec<String>,
+}
+
+impl CustomRulesConfig {
+    /// Whether a rule is disabled, looked up by code or name.
+    pub fn is_disabled(&self, code: &str, name: &str) -> bool {
+        self.disabled
+            .iter()
+            .any(|rule| rule.eq_ignore_ascii_case(code) || rule.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Configuration for unused tag detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UnusedTagsConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for tags to ignore.
+    pub ignore_patterns: Vec<String>,
+
+    /// Scopes to ignore (e.g., "Program:MainProgram").
+    pub ignore_scopes: Vec<String>,
+}
+
+impl Default for UnusedTagsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec!["_*".to_string()],
+            ignore_scopes: vec![],
+        }
+    }
+}
+
+/// Configuration for undefined tag detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UndefinedTagsConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for tags to ignore.
+    pub ignore_patterns: Vec<String>,
+
+    /// Glob patterns for tags provided outside the project (HMI, external
+    /// systems). Matching names are never reported as undefined.
+    pub allow: Vec<String>,
+
+    /// Glob patterns for functions and function blocks provided outside the
+    /// project (libraries). Matching calls are never reported as undefined.
+    pub allow_calls: Vec<String>,
+
+    /// Resolve module I/O references (`Local:1:I`, `Drive1:O`) against the
+    /// modules of the L5X export. References no module defines are
+    /// reported; exports without modules skip them.
+    pub module_tags: bool,
+}
+
+impl Default for UndefinedTagsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+            allow: vec![],
+            allow_calls: vec![],
+            module_tags: true,
+        }
+    }
+}
+
+/// Configuration for empty routine detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmptyRoutinesConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for EmptyRoutinesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for unused AOI detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UnusedAoisConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for AOIs to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for UnusedAoisConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for unused DataType detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UnusedDataTypesConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for DataTypes to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for UnusedDataTypesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for cyclomatic complexity detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ComplexityConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Maximum allowed cyclomatic complexity before reporting.
+    pub max_complexity: usize,
+
+    /// Per-POU-kind overrides of `max_complexity`.
+    pub per_kind: PouKindThresholds,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for ComplexityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_complexity: 10,
+            per_kind: PouKindThresholds::default(),
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Thresholds for specific POU kinds; unset kinds use the global value.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct PouKindThresholds {
+    /// Threshold for functions.
+    pub function: Option<usize>,
+
+    /// Threshold for function blocks.
+    pub function_block: Option<usize>,
+
+    /// Threshold for programs.
+    pub program: Option<usize>,
+
+    /// Threshold for Add-On Instructions.
+    pub aoi: Option<usize>,
+}
+
+impl PouKindThresholds {
+    /// Get the threshold for a POU kind, if one is set.
+    pub fn get(&self, kind: RoutineKind) -> Option<usize> {
+        match kind {
+            RoutineKind::Function => self.function,
+            RoutineKind::FunctionBlock => self.function_block,
+            RoutineKind::Program => self.program,
+            RoutineKind::Aoi => self.aoi,
+        }
+    }
+}
+
+/// Configuration for deep nesting detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NestingConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Maximum allowed nesting depth before reporting.
+    pub max_depth: usize,
+
+    /// Per-POU-kind overrides of `max_depth`.
+    pub per_kind: PouKindThresholds,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for NestingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_depth: 4,
+            per_kind: PouKindThresholds::default(),
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for type mismatch detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TypeMismatchConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for TypeMismatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for non-exhaustive CASE detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NonExhaustiveCaseConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for NonExhaustiveCaseConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for overlapping CASE label detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct OverlappingCaseLabelsConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for OverlappingCaseLabelsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for FOR loop variable modification detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LoopVarModifiedConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for LoopVarModifiedConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for empty branch detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EmptyBranchesConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for EmptyBranchesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for self-assignment detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct SelfAssignmentConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for SelfAssignmentConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for floating-point equality detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FloatEqualityConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for FloatEqualityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for unused variable detection by VAR block kind.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UnusedVariablesConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Report inputs that are never read (S0007).
+    pub check_inputs: bool,
+
+    /// Report outputs that are never assigned (S0008).
+    pub check_outputs: bool,
+
+    /// Report VAR_TEMP variables that are never used (S0009).
+    pub check_temps: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for UnusedVariablesConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_inputs: true,
+            check_outputs: true,
+            check_temps: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for naming convention checks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NamingConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Pattern for function block and AOI instances.
+    pub fb_instance: Option<String>,
+
+    /// Pattern for input parameters.
+    pub input: Option<String>,
+
+    /// Pattern for output parameters.
+    pub output: Option<String>,
+
+    /// Pattern for in-out parameters.
+    pub in_out: Option<String>,
+
+    /// Pattern for local variables and program-scope tags.
+    pub local: Option<String>,
+
+    /// Pattern for global variables and controller-scope tags.
+    pub global: Option<String>,
+
+    /// Pattern for user-defined data types.
+    pub data_type: Option<String>,
+
+    /// Pattern for programs, functions, function blocks and AOIs.
+    pub pou: Option<String>,
+
+    /// Glob patterns for names to ignore.
+    pub ignore_patterns: Vec<String>,
+
+    /// Compiled patterns, filled by [`NamingConfig::compile`].
+    #[serde(skip)]
+    compiled: Vec<(NameCategory, Regex)>,
+}
+
+impl NamingConfig {
+    /// Compile the configured patterns.
+    ///
+    /// Called when the configuration is parsed; call it again after changing
+    /// the patterns of a configuration built in code.
+    pub fn compile(&mut self) -> Result<()> {
+        let options = [
+            (NameCategory::FbInstance, "fb_instance", &self.fb_instance),
+            (NameCategory::Input, "input", &self.input),
+            (NameCategory::Output, "output", &self.output),
+            (NameCategory::InOut, "in_out", &self.in_out),
+            (NameCategory::Local, "local", &self.local),
+            (NameCategory::Global, "global", &self.global),
+            (NameCategory::DataType, "data_type", &self.data_type),
+            (NameCategory::Pou, "pou", &self.pou),
+        ];
+
+        let mut compiled = Vec::new();
+        for (category, option, pattern) in options {
+            let Some(pattern) = pattern else {
+                continue;
+            };
+            let regex = Regex::new(pattern).map_err(|_| Error::ConfigParse {
+                kind: ConfigErrorKind::InvalidPattern {
+                    option: option.to_string(),
+                    pattern: pattern.clone(),
+                },
+            })?;
+            compiled.push((category, regex));
+        }
+
+        self.compiled = compiled;
+        Ok(())
+    }
+
+    /// Get the compiled pattern for a category, if one is configured.
+    pub fn pattern(&self, category: NameCategory) -> Option<&Regex> {
+        self.compiled
+            .iter()
+            .find(|(c, _)| *c == category)
+            .map(|(_, regex)| regex)
+    }
+}
+
+impl Default for NamingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            fb_instance: None,
+            input: None,
+            output: None,
+            in_out: None,
+            local: None,
+            global: None,
+            data_type: None,
+            pou: None,
+            ignore_patterns: vec![],
+            compiled: vec![],
+        }
+    }
+}
+
+/// Configuration for identifier length and reserved word checks.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IdentifiersConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Maximum identifier length.
+    pub max_length: usize,
+
+    /// Whether to flag names that are IEC 61131-3 reserved words.
+    pub check_reserved_words: bool,
+
+    /// Glob patterns for names to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for IdentifiersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_length: 30,
+            check_reserved_words: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for uninitialized read detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UninitializedReadConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for UninitializedReadConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for required initial value detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RequiredInitConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Data types whose tags must have an initial value (case-insensitive).
+    pub types: Vec<String>,
+
+    /// Glob patterns for tags that must have an initial value.
+    pub name_patterns: Vec<String>,
+
+    /// Glob patterns for tags to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for RequiredInitConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            types: vec![],
+            name_patterns: vec![],
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for unscheduled program detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UnscheduledProgramConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Whether to report tasks that run no programs.
+    pub check_empty_tasks: bool,
+
+    /// Glob patterns for programs and tasks to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for UnscheduledProgramConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_empty_tasks: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for uninvoked instance detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UninvokedInstanceConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for instances to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for UninvokedInstanceConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for array bounds detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ArrayBoundsConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for ArrayBoundsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for ST text style.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StyleConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Maximum line length in characters.
+    pub max_line_length: usize,
+
+    /// Whether tab characters are allowed.
+    pub allow_tabs: bool,
+
+    /// Whether to report trailing whitespace.
+    pub check_trailing_whitespace: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for StyleConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_line_length: 120,
+            allow_tabs: false,
+            check_trailing_whitespace: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for low comment density detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LowCommentDensityConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Minimum percentage of comment characters
+    pub min_percent: f32,
+
+    /// Minimum non-blank lines before the density is checked
+    pub min_lines: usize,
+
+    /// Maximum non-blank lines of a routine without any comment
+    pub max_uncommented_lines: usize,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for LowCommentDensityConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_percent: 5.0,
+            min_lines: 10,
+            max_uncommented_lines: 20,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for IL jump label detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct JumpLabelsConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Report labels that no jump targets (C0009)
+    pub check_unused_labels: bool,
+
+    /// Glob patterns for POUs to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for JumpLabelsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_unused_labels: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for timer and counter reset detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TimerNoResetConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Report retentive timers and counters that are never reset.
+    pub check_retentive: bool,
+
+    /// Report TON/TOF timers whose enable is always true.
+    pub check_always_enabled: bool,
+
+    /// Glob patterns for timers and counters to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for TimerNoResetConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_retentive: true,
+            check_always_enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Map a typed parse error of a config file or message catalog to an
+/// unknown key or invalid value.
+pub(crate) fn schema_error(content: &str, error: &toml::de::Error) -> ConfigErrorKind {
+    let Some(span) = error.span() else {
+        return ConfigErrorKind::TomlSyntax;
+    };
+    let line = content[..span.start].matches('\n').count() + 1;
+    let message = error.message().to_string();
+    if message.starts_with("unknown field") {
+        ConfigErrorKind::UnknownKey {
+            key: qualified_key(content, span.start, &content[span.clone()]),
+            line,
+        }
+    } else {
+        // The span covers the value; the key is in front of `=` on its line
+        let line_start = content[..span.start].rfind('\n').map_or(0, |i| i + 1);
+        let key = content[line_start..span.start].split('=').next().unwrap_or_default().trim();
+        ConfigErrorKind::InvalidValue {
+            key: qualified_key(content, span.start, key),
+            line: Some(line),
+            message,
+        }
+    }
+}
+
+/// Prefix a key with the `[table]` header it appears under. A key inside
+/// a header line is named by the whole header.
+fn qualified_key(content: &str, offset: usize, key: &str) -> String {
+    let line_start = content[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line = content[line_start..].lines().next().unwrap_or_default();
+    if let Some(header) = header_name(line) {
+        return header.to_string();
+    }
+    match content[..line_start].lines().rev().find_map(header_name) {
+        Some(table) => format!("{}.{}", table, key),
+        None => key.to_string(),
+    }
+}
+
+/// Table name of a `[table]` header line.
+fn header_name(line: &str) -> Option<&str> {
+    let rest = line.trim().strip_prefix('[')?;
+    rest.trim_start_matches('[').split(']').next().map(str::trim)
+}
+
+/// Line of a `table.key` setting in a config file, if it is set there.
+fn key_line(content: &str, key: &str) -> Option<usize> {
+    let (table, name) = key.rsplit_once('.').unwrap_or(("", key));
+    let mut current = "";
+    for (index, line) in content.lines().enumerate() {
+        if let Some(header) = header_name(line) {
+            current = header;
+        } else if current == table
+            && line.split('=').next().is_some_and(|k| k.trim().trim_matches('"') == name)
+            && line.contains('=')
+        {
+            return Some(index + 1);
+        }
+    }
+    None
+}
+
+/// Check the `[messages]` table of a config file or message catalog: every
+/// key must name a built-in rule and every template be well-formed.
+pub(crate) fn check_messages(
+    content: &str,
+    templates: &BTreeMap<String, String>,
+) -> std::result::Result<(), ConfigErrorKind> {
+    for (rule, template) in templates {
+        let key = format!("messages.{}", rule);
+        if RuleKind::from_key(rule).is_none() {
+            return Err(ConfigErrorKind::UnknownKey {
+                line: key_line(content, &key).unwrap_or_default(),
+                key,
+            });
+        }
+        if let Err(message) = messages::check_template(rule, template) {
+            return Err(ConfigErrorKind::InvalidValue {
+                line: key_line(content, &key),
+                key,
+                message,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Merge `overrides` into `base`, recursing into tables present in both.
+fn merge_tables(mut base: toml::Table, overrides: toml::Table) -> toml::Table {
+    for (key, value) in overrides {
+        let merged = match (base.remove(&key), value) {
+            (Some(toml::Value::Table(inner)), toml::Value::Table(over)) => {
+                toml::Value::Table(merge_tables(inner, over))
+            }
+            (_, value) => value,
+        };
+        base.insert(key, merged);
+    }
+    base
+}
+
+/// Configuration for string truncation detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct StringTruncationConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for StringTruncationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for duplicate logic detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DuplicateLogicConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Minimum number of rungs or statements for a routine to be compared
+    pub min_size: usize,
+
+    /// Minimum similarity (0.0 to 1.0) to report two routines as duplicates
+    pub min_similarity: f64,
+
+    /// Glob patterns for routine paths (Program/Routine) to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for DuplicateLogicConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            min_size: 5,
+            min_similarity: 0.9,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for unconnected FBD pin detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct FbdUnconnectedConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Report required inputs without a connection
+    pub check_inputs: bool,
+
+    /// Report function outputs that feed nothing
+    pub check_outputs: bool,
+
+    /// Report parts of a network that reach no output
+    pub check_disconnected: bool,
+
+    /// Glob patterns for POU names to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for FbdUnconnectedConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_inputs: true,
+            check_outputs: true,
+            check_disconnected: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for division by zero detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DivisionByZeroConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Report variable divisors without a nonzero guard, not only constant zeros.
+    pub check_variables: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for DivisionByZeroConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_variables: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for suspicious bit operation detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct BitOperationConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Report AND/OR/XOR with one BOOL and one integer or bit-string operand.
+    pub check_mixed: bool,
+
+    /// Report bit operations and shifts on signed integers.
+    pub check_signed: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for BitOperationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            check_mixed: true,
+            check_signed: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for deep member access (law of Demeter) detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LawOfDemeterConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Maximum member access depth before reporting.
+    pub max_depth: usize,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for LawOfDemeterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_depth: 4,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for comparison statement detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ComparisonStatementConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for ComparisonStatementConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for impure function detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct ImpureFunctionConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for ImpureFunctionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for dead store detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DeadStoreConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for DeadStoreConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for nonstandard function detection (disabled by default).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct NonstandardFunctionConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Additional names to treat as standard (e.g. functions every target platform provides)
+    pub standard_functions: Vec<String>,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+/// Configuration for comment marker detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CommentMarkersConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Marker keywords, matched as whole words, case-sensitively
+    pub keywords: Vec<String>,
+
+    /// Severity of the findings: "info", "warning" or "error"
+    pub severity: String,
+
+    /// Glob patterns for locations to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for CommentMarkersConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            keywords: vec!["TODO".to_string(), "FIXME".to_string(), "XXX".to_string(), "HACK".to_string()],
+            severity: "info".to_string(),
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for tag scope suggestion detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct TagScopeSuggestionConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for tags to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for TagScopeSuggestionConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for redundant boolean expression detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct RedundantBooleanConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for RedundantBooleanConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
+
+/// Configuration for enum value conflict detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct EnumValueConflictConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for data types to ignore.
+    pub ignore_patterns: Vec<String>,
+}
+
+impl Default for EnumValueConflictConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
     }
+}
 
-    /// Generate a default configuration file as a string.
-    pub fn default_toml() -> String {
-        r#"# plceye.toml - PLC Code Rule Detector Configuration
+/// Configuration for cross-program shared tag detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct CrossProgramSharedTagConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
 
-[general]
-# Minimum severity to report: "info", "warning", "error"
-min_severity = "info"
+    /// Severity of the findings: "info", "warning" or "error"
+    pub severity: String,
 
-[unused_tags]
-# Enable unused tag detection
-enabled = true
+    /// Glob patterns for tags to ignore.
+    pub ignore_patterns: Vec<String>,
+}
 
-# Ignore tags matching these patterns (glob-style)
-ignore_patterns = [
-    "_*",           # Tags starting with underscore (often internal)
-    "HMI_*",        # HMI interface tags
-]
+impl Default for CrossProgramSharedTagConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            severity: "info".to_string(),
+            ignore_patterns: vec![],
+        }
+    }
+}
 
-# Ignore tags in these scopes
-ignore_scopes = [
-    # "Program:MainProgram",  # Example: ignore MainProgram
-]
+/// Configuration for unused method parameter detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UnusedMethodParameterConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
 
-[undefined_tags]
-# Enable undefined tag detection (tags referenced but not declared)
-enabled = true
+    /// Skip methods declared OVERRIDE, which have to keep the signature of the base method
+    pub ignore_overrides: bool,
 
-# Ignore undefined tags matching these patterns (useful for I/O)
-ignore_patterns = [
-    "Local:*",      # Module I/O references
-]
+    /// Glob patterns for parameters to ignore.
+    pub ignore_patterns: Vec<String>,
+}
 
-[empty_routines]
-# Enable empty routine detection
-enabled = true
+impl Default for UnusedMethodParameterConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_overrides: false,
+            ignore_patterns: vec![],
+        }
+    }
+}
 
-# Ignore routines matching these patterns
-ignore_patterns = [
-    # "Unused_*",    # Example: ignore placeholder routines
-]
+/// Configuration for unreachable POU detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct UnreachablePouConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
 
-[unused_aois]
-# Enable unused AOI detection
-enabled = true
+    /// Glob patterns for POUs called from outside the project (e.g., library POUs).
+    pub entry_points: Vec<String>,
 
-# Ignore AOIs matching these patterns
-ignore_patterns = []
+    /// Glob patterns for POUs to ignore.
+    pub ignore_patterns: Vec<String>,
+}
 
-[unused_datatypes]
-# Enable unused DataType detection
-enabled = true
+impl Default for UnreachablePouConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            entry_points: vec![],
+            ignore_patterns: vec![],
+        }
+    }
+}
 
-# Ignore DataTypes matching these patterns
-ignore_patterns = []
+/// Configuration for VAR_IN_OUT binding detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InOutNotVariableConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
 
-[complexity]
-# Enable cyclomatic complexity detection for ST routines
-enabled = true
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
+}
 
-# Maximum allowed cyclomatic complexity
-max_complexity = 10
+impl Default for InOutNotVariableConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ignore_patterns: vec![],
+        }
+    }
+}
 
-# Ignore routines matching these patterns
-ignore_patterns = []
+/// Configuration for invalid array range detection.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct InvalidArrayRangeConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
 
-[nesting]
-# Enable deep nesting detection for ST routines
-enabled = true
+    /// Also report ranges of a single element (`ARRAY[5..5]`)
+    pub single_element: bool,
 
-# Maximum allowed nesting depth
-max_depth = 4
+    /// Glob patterns for POUs and types to ignore.
+    pub ignore_patterns: Vec<String>,
+}
 
-# Ignore routines matching these patterns
-ignore_patterns = []
-"#
-        .to_string()
+impl Default for InvalidArrayRangeConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            single_element: false,
+            ignore_patterns: vec![],
+        }
     }
 }
 
-/// General configuration settings.
+/// Configuration for invalid struct detection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct GeneralConfig {
-    /// Minimum severity level to report.
-    pub min_severity: String,
+#[serde(default, deny_unknown_fields)]
+pub struct InvalidStructConfig {
+    /// Whether this detector is enabled.
+    pub enabled: bool,
+
+    /// Glob patterns for types to ignore.
+    pub ignore_patterns: Vec<String>,
 }
 
-impl Default for GeneralConfig {
+impl Default for InvalidStructConfig {
     fn default() -> Self {
         Self {
-            min_severity: "info".to_string(),
+            enabled: true,
+            ignore_patterns: vec![],
         }
     }
 }
 
-/// Configuration for unused tag detection.
+/// Configuration for unbound input detection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct UnusedTagsConfig {
+#[serde(default, deny_unknown_fields)]
+pub struct UnboundInputConfig {
     /// Whether this detector is enabled.
     pub enabled: bool,
 
-    /// Glob patterns for tags to ignore.
-    pub ignore_patterns: Vec<String>,
+    /// Also report unbound inputs that have an initial value, as info
+    pub initialized_inputs: bool,
 
-    /// Scopes to ignore (e.g., "Program:MainProgram").
-    pub ignore_scopes: Vec<String>,
+    /// Glob patterns for routines to ignore.
+    pub ignore_patterns: Vec<String>,
 }
 
-impl Default for UnusedTagsConfig {
+impl Default for UnboundInputConfig {
     fn default() -> Self {
         Self {
             enabled: true,
-            ignore_patterns: vec!["_*".to_string()],
-            ignore_scopes: vec![],
+            initialized_inputs: true,
+            ignore_patterns: vec![],
         }
     }
 }
 
-/// Configuration for undefined tag detection.
+/// Configuration for string concatenation in loop detection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct UndefinedTagsConfig {
+#[serde(default, deny_unknown_fields)]
+pub struct StringConcatInLoopConfig {
     /// Whether this detector is enabled.
     pub enabled: bool,
 
-    /// Glob patterns for tags to ignore.
+    /// Glob patterns for routines to ignore.
     pub ignore_patterns: Vec<String>,
 }
 
-impl Default for UndefinedTagsConfig {
+impl Default for StringConcatInLoopConfig {
     fn default() -> Self {
         Self {
             enabled: true,
-            ignore_patterns: vec!["Local:*".to_string()],
+            ignore_patterns: vec![],
         }
     }
 }
 
-/// Configuration for empty routine detection.
+/// Configuration for hardcoded address detection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct EmptyRoutinesConfig {
+#[serde(default, deny_unknown_fields)]
+pub struct HardcodedAddressConfig {
     /// Whether this detector is enabled.
     pub enabled: bool,
 
+    /// Glob patterns for addresses that may be used directly (e.g. "%MW*")
+    pub allowed_addresses: Vec<String>,
+
     /// Glob patterns for routines to ignore.
     pub ignore_patterns: Vec<String>,
 }
 
-impl Default for EmptyRoutinesConfig {
+impl Default for HardcodedAddressConfig {
     fn default() -> Self {
         Self {
             enabled: true,
+            allowed_addresses: vec![],
             ignore_patterns: vec![],
         }
     }
 }
 
-/// Configuration for unused AOI detection.
+/// Configuration for IF/ELSIF chain detection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct UnusedAoisConfig {
+#[serde(default, deny_unknown_fields)]
+pub struct ConvertToCaseConfig {
     /// Whether this detector is enabled.
     pub enabled: bool,
 
-    /// Glob patterns for AOIs to ignore.
+    /// Fewest IF and ELSIF conditions a chain needs to be reported
+    pub min_branches: usize,
+
+    /// Glob patterns for routines to ignore.
     pub ignore_patterns: Vec<String>,
 }
 
-impl Default for UnusedAoisConfig {
+impl Default for ConvertToCaseConfig {
     fn default() -> Self {
         Self {
             enabled: true,
+            min_branches: 3,
             ignore_patterns: vec![],
         }
     }
 }
 
-/// Configuration for unused DataType detection.
+/// Configuration for argument conversion detection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct UnusedDataTypesConfig {
+#[serde(default, deny_unknown_fields)]
+pub struct ArgumentConversionConfig {
     /// Whether this detector is enabled.
     pub enabled: bool,
 
-    /// Glob patterns for DataTypes to ignore.
+    /// Report widening conversions within a type family (INT to DINT) as info
+    pub widening: bool,
+
+    /// Glob patterns for routines to ignore.
     pub ignore_patterns: Vec<String>,
 }
 
-impl Default for UnusedDataTypesConfig {
+impl Default for ArgumentConversionConfig {
     fn default() -> Self {
         Self {
             enabled: true,
+            widening: true,
             ignore_patterns: vec![],
         }
     }
 }
 
-/// Configuration for cyclomatic complexity detection.
+/// Configuration for bool toggle detection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct ComplexityConfig {
+#[serde(default, deny_unknown_fields)]
+pub struct BoolToggleConfig {
     /// Whether this detector is enabled.
     pub enabled: bool,
 
-    /// Maximum allowed cyclomatic complexity before reporting.
-    pub max_complexity: usize,
-
     /// Glob patterns for routines to ignore.
     pub ignore_patterns: Vec<String>,
 }
 
-impl Default for ComplexityConfig {
+impl Default for BoolToggleConfig {
     fn default() -> Self {
         Self {
             enabled: true,
-            max_complexity: 10,
             ignore_patterns: vec![],
         }
     }
 }
 
-/// Configuration for deep nesting detection.
+/// Configuration for undefined data type reference detection.
 #[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(default)]
-pub struct NestingConfig {
+#[serde(default, deny_unknown_fields)]
+pub struct UndefinedDataTypeRefConfig {
     /// Whether this detector is enabled.
     pub enabled: bool,
 
-    /// Maximum allowed nesting depth before reporting.
-    pub max_depth: usize,
-
-    /// Glob patterns for routines to ignore.
+    /// Glob patterns for type names to treat as defined, such as library
+    /// types or vendor types plceye does not know.
     pub ignore_patterns: Vec<String>,
 }
 
-impl Default for NestingConfig {
+impl Default for UndefinedDataTypeRefConfig {
     fn default() -> Self {
         Self {
             enabled: true,
-            max_depth: 4,
             ignore_patterns: vec![],
         }
     }
@@ -335,4 +2538,194 @@ ignore_scopes = ["Program:Debug"]
         let config = RuleConfig::parse(&toml).unwrap();
         assert!(config.unused_tags.enabled);
     }
+
+    #[test]
+    fn test_parse_escalation_thresholds() {
+        let toml = r#"
+[escalation]
+thresholds = { S0001 = 50, "empty-routine" = 10 }
+"#;
+        let config = RuleConfig::parse(toml).unwrap();
+        assert_eq!(config.escalation.threshold("S0001", "unused-tag"), Some(50));
+        assert_eq!(config.escalation.threshold("S0003", "EMPTY-ROUTINE"), Some(10));
+        assert_eq!(config.escalation.threshold("S0002", "undefined-tag"), None);
+
+        let kind = config_error("[escalation]\nthresholds = { S0001 = 0 }\n");
+        assert!(matches!(kind, ConfigErrorKind::InvalidValue { ref key, .. } if key == "escalation.thresholds.S0001"));
+    }
+
+    #[test]
+    fn test_parse_messages() {
+        let toml = r#"
+[messages]
+S0001 = "Tag '{identifier}' wird nicht verwendet"
+"deep-nesting" = "{identifier}: Tiefe {depth} > {threshold}"
+"#;
+        let mut config = RuleConfig::parse(toml).unwrap();
+        assert_eq!(config.message_template("S0001", "unused-tag"), Some("Tag '{identifier}' wird nicht verwendet"));
+        assert!(config.message_template("M0003", "Deep-Nesting").is_some());
+        assert_eq!(config.message_template("S0002", "undefined-tag"), None);
+
+        config.add_messages(BTreeMap::from([
+            ("s0001".to_string(), "ignored".to_string()),
+            ("S0002".to_string(), "Tag '{identifier}' fehlt".to_string()),
+        ]));
+        assert_eq!(config.message_template("S0001", "unused-tag"), Some("Tag '{identifier}' wird nicht verwendet"));
+        assert_eq!(config.message_template("S0002", "undefined-tag"), Some("Tag '{identifier}' fehlt"));
+
+        let kind = config_error("[messages]\nS0001 = \"Tag {identifer}\"\n");
+        assert!(matches!(kind, ConfigErrorKind::InvalidValue { ref key, line: Some(2), .. } if key == "messages.S0001"));
+        let kind = config_error("[messages]\nS0001 = \"{threshold}\"\n");
+        assert!(matches!(kind, ConfigErrorKind::InvalidValue { .. }));
+        let kind = config_error("[messages]\nS0001 = \"{name}\"\n\"depp-nesting\" = \"{threshold}\"\n");
+        assert_eq!(kind, ConfigErrorKind::UnknownKey { key: "messages.depp-nesting".to_string(), line: 3 });
+    }
+
+    #[test]
+    fn test_parse_per_kind_thresholds() {
+        let toml = r#"
+[complexity]
+max_complexity = 10
+
+[complexity.per_kind]
+program = 20
+"#;
+        let config = RuleConfig::parse(toml).unwrap();
+        assert_eq!(config.complexity.per_kind.get(RoutineKind::Program), Some(20));
+        assert_eq!(config.complexity.per_kind.get(RoutineKind::Function), None);
+        assert_eq!(config.nesting.per_kind.get(RoutineKind::Program), None);
+    }
+
+    #[test]
+    fn test_parse_naming_patterns() {
+        let toml = r#"
+[naming]
+fb_instance = "^fb[A-Z]"
+input = "^i[A-Z]"
+"#;
+        let config = RuleConfig::parse(toml).unwrap();
+        let input = config.naming.pattern(NameCategory::Input).unwrap();
+        assert!(input.is_match("iStart"));
+        assert!(!input.is_match("Start"));
+        assert!(config.naming.pattern(NameCategory::Output).is_none());
+    }
+
+    #[test]
+    fn test_invalid_naming_pattern() {
+        let toml = r#"
+[naming]
+output = "^q[A-Z"
+"#;
+        let err = RuleConfig::parse(toml).unwrap_err();
+        assert!(matches!(
+            err,
+            Error::ConfigParse { kind: ConfigErrorKind::InvalidPattern { ref option, .. } } if option == "output"
+        ));
+    }
+
+    #[test]
+    fn test_profiles() {
+        for name in RuleConfig::PROFILES {
+            let config = RuleConfig::profile(name).unwrap();
+            assert_eq!(config.profile.as_deref(), Some(*name));
+        }
+        let strict = RuleConfig::profile("strict").unwrap();
+        assert!(strict.style.enabled);
+        assert_eq!(strict.complexity.max_complexity, 8);
+        let relaxed = RuleConfig::profile("Relaxed").unwrap();
+        assert_eq!(relaxed.general.min_severity, "warning");
+        assert!(!relaxed.naming.enabled);
+    }
+
+    #[test]
+    fn test_unknown_profile() {
+        let err = RuleConfig::profile("paranoid").unwrap_err();
+        assert!(matches!(err, Error::UnknownProfile { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Unknown profile 'paranoid' (available profiles: default, strict, relaxed, safety)"
+        );
+        let err = RuleConfig::parse_with_profile("", Some("paranoid")).unwrap_err();
+        assert!(matches!(err, Error::UnknownProfile { .. }));
+        let err = RuleConfig::parse("profile = \"paranoid\"").unwrap_err();
+        assert!(matches!(err, Error::ConfigParse { kind: ConfigErrorKind::UnknownProfile { .. } }));
+        assert!(err.to_string().contains("expected default, strict, relaxed, safety"));
+    }
+
+    #[test]
+    fn test_profile_key_seeds_defaults() {
+        let toml = r#"
+profile = "strict"
+
+[complexity]
+max_complexity = 12
+"#;
+        let config = RuleConfig::parse(toml).unwrap();
+        assert_eq!(config.complexity.max_complexity, 12);
+        assert_eq!(config.nesting.max_depth, 3);
+        assert!(config.style.enabled);
+
+        let config = RuleConfig::parse_with_profile(toml, Some("relaxed")).unwrap();
+        assert_eq!(config.profile.as_deref(), Some("relaxed"));
+        assert_eq!(config.complexity.max_complexity, 12);
+        assert_eq!(config.nesting.max_depth, 6);
+    }
+
+    fn config_error(toml: &str) -> ConfigErrorKind {
+        match RuleConfig::parse(toml) {
+            Err(Error::ConfigParse { kind }) => kind,
+            other => panic!("expected a config error, got {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn test_unknown_keys() {
+        let kind = config_error("[general]\nmin_severity = \"info\"\n\n[complexity]\nenabled = true\nmax_complexty = 5\n");
+        assert_eq!(kind, ConfigErrorKind::UnknownKey { key: "complexity.max_complexty".to_string(), line: 6 });
+        assert_eq!(kind.to_string(), "unknown key 'complexity.max_complexty' at line 6");
+
+        let kind = config_error("[unused_tag]\nenabled = false\n");
+        assert_eq!(kind, ConfigErrorKind::UnknownKey { key: "unused_tag".to_string(), line: 1 });
+    }
+
+    #[test]
+    fn test_invalid_values() {
+        let kind = config_error("[nesting]\nmax_depth = -1\n");
+        assert!(matches!(kind, ConfigErrorKind::InvalidValue { ref key, line: Some(2), .. } if key == "nesting.max_depth"));
+
+        let kind = config_error("[complexity]\nenabled = true\nmax_complexity = 0\n");
+        assert_eq!(kind.to_string(), "invalid value for 'complexity.max_complexity' at line 3: must be at least 1");
+
+        let kind = config_error("[low_comment_density]\nmin_percent = 150.0\n");
+        assert!(matches!(kind, ConfigErrorKind::InvalidValue { line: Some(2), .. }));
+
+        let kind = config_error("[general]\nmin_severity = \"loud\"\n");
+        assert!(matches!(kind, ConfigErrorKind::InvalidValue { ref key, .. } if key == "general.min_severity"));
+
+        let kind = config_error("[general]\nacd_export_cmd = \"  \"\n");
+        assert!(matches!(kind, ConfigErrorKind::InvalidValue { ref key, .. } if key == "general.acd_export_cmd"));
+
+        let kind = config_error("[comment_markers]\nseverity = \"high\"\n");
+        assert!(matches!(kind, ConfigErrorKind::InvalidValue { ref key, line: Some(2), .. } if key == "comment_markers.severity"));
+
+        // Validating a config built in code reports no line
+        let mut config = RuleConfig::default();
+        config.duplicate_logic.min_similarity = 2.0;
+        assert!(config.validate().is_err());
+        assert!(RuleConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_effective_config_round_trip() {
+        for name in RuleConfig::PROFILES {
+            let mut config = RuleConfig::profile(name).unwrap();
+            config.profile = Some(name.to_string());
+            config.general.min_severity = "error".to_string();
+            config.complexity.per_kind.function = Some(5);
+
+            let text = toml::to_string(&config).unwrap();
+            let parsed = RuleConfig::parse(&text).unwrap();
+            assert_eq!(toml::to_string(&parsed).unwrap(), text, "profile {}", name);
+        }
+    }
 }
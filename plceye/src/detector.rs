@@ -1,42 +1,141 @@
 //! Main rule detector that coordinates all individual detectors.
 
+use std::collections::HashMap;
 use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
 
 use l5x::Controller;
 
-use crate::analysis::{analyze_controller, analyze_plcopen_project, ParseStats, PlcopenStats};
+use crate::acd;
+use crate::analysis::{
+    analyze_controller, analyze_plcopen_project, names_from_controller, names_from_plcopen,
+    variables_from_controller, variables_from_plcopen, il_labels_from_plcopen, fbd_networks_from_plcopen,
+    parse_st_bodies, st_declarations, InstanceUsage, ParseStats, PlcopenStats, StUnits, TagAccesses, TaskBindings,
+    TimerUsage, DuplicateLogic, DataTypeIndex, texts_from_controller, texts_from_plcopen, texts_from_st,
+};
 use crate::config::RuleConfig;
-use crate::loader::LoadedProject;
-use crate::report::{Report, Severity};
+use crate::loader::{ExportKind, FileFormat, LoadedProject};
+use crate::messages;
+use crate::report::{Report, Rule, Severity};
 use crate::rules::{
     ComplexityDetector, EmptyRoutinesDetector, NestingDetector,
     UndefinedTagsDetector, UnusedAoisDetector, UnusedDataTypesDetector, UnusedTagsDetector,
     PlcopenUnusedVarsDetector, PlcopenUndefinedVarsDetector, PlcopenEmptyPousDetector,
+    PlcopenUnusedGlobalsDetector, PlcopenUndeclaredGlobalsDetector,
+    TypeMismatchDetector, NonExhaustiveCaseDetector, OverlappingCaseLabelsDetector,
+    LoopVarModifiedDetector, EmptyBranchesDetector, SelfAssignmentDetector, FloatEqualityDetector,
+    UnusedVariablesDetector, NamingDetector, IdentifiersDetector, UninitializedReadDetector,
+    RequiredInitDetector, UnscheduledProgramDetector, UninvokedInstanceDetector,
+    ArrayBoundsDetector, StyleDetector, LowCommentDensityDetector, JumpLabelsDetector,
+    TimerNoResetDetector, StringTruncationDetector, DuplicateLogicDetector, FbdUnconnectedDetector,
+    DivisionByZeroDetector, BitOperationDetector, LawOfDemeterDetector, ComparisonStatementDetector,
+    ImpureFunctionDetector, DeadStoreDetector, NonstandardFunctionDetector, CommentMarkersDetector,
+    TagScopeSuggestionDetector, RedundantBooleanDetector, EnumValueConflictDetector,
+    CrossProgramSharedTagDetector, UnusedMethodParameterDetector, UnreachablePouDetector, InOutNotVariableDetector,
+    InvalidArrayRangeDetector, InvalidStructDetector, UnboundInputDetector, StringConcatInLoopDetector,
+    HardcodedAddressDetector, ConvertToCaseDetector, ArgumentConversionDetector,
+    BoolToggleDetector, UndefinedDataTypeRefDetector,
 };
+use crate::error::{Error, L5xParseErrorKind};
+use crate::metrics::{ProjectMetrics, ProjectStats};
 use crate::Result;
 
+/// A rule detector that can be registered with [`RuleDetector::register`].
+///
+/// Registered detectors run on every analyzed project after the built-in
+/// rules, in registration order. Their findings are filtered and adjusted
+/// like built-in findings: rules listed in `[custom_rules] disabled` are
+/// dropped, bundle findings get their member file, and the escalation
+/// thresholds and minimum severity apply. Findings use [`RuleKind::Custom`]
+/// with their own code and name; findings a partial export skips are
+/// dropped as well.
+///
+/// Each built-in detector implements the trait too, building its input
+/// from the project. [`RuleDetector`] itself implements the trait, running
+/// the built-in rules on one shared analysis.
+///
+/// [`RuleKind::Custom`]: crate::RuleKind::Custom
+///
+/// # Example
+///
+/// ```
+/// use plceye::{Detector, FileFormat, LoadedProject, Rule, RuleDetector, RuleKind, Severity};
+///
+/// /// Programs must not be named "Test".
+/// struct NoTestProgram;
+///
+/// impl Detector for NoTestProgram {
+///     fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+///         let programs = project
+///             .l5x_controller
+///             .iter()
+///             .filter_map(|controller| controller.programs.as_ref())
+///             .flat_map(|programs| &programs.program);
+///         programs
+///             .filter(|program| program.name == "Test")
+///             .map(|program| {
+///                 let kind = RuleKind::Custom { code: "Z0001", name: "test-program" };
+///                 let location = format!("Program:{}", program.name);
+///                 Rule::new(kind, Severity::Warning, location, &program.name, "Test program in release")
+///             })
+///             .collect()
+///     }
+/// }
+///
+/// let xml = r#"<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+///     <Controller Name="Line"><Programs><Program Name="Test"/></Programs></Controller>
+/// </RSLogix5000Content>"#;
+/// let detector = RuleDetector::new().register(Box::new(NoTestProgram));
+/// let report = detector.analyze_str(xml, FileFormat::L5x).unwrap();
+/// assert!(report.rules().iter().any(|rule| rule.id() == "Z0001"));
+/// ```
+pub trait Detector: Send + Sync {
+    /// Analyze a loaded project and return the findings.
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule>;
+}
+
 /// Main rule detector that runs all enabled detectors.
+#[derive(Clone)]
 pub struct RuleDetector {
     config: RuleConfig,
+    timeout: Option<Duration>,
+    registered: Vec<Arc<dyn Detector>>,
 }
 
 impl RuleDetector {
     /// Create a new rule detector with default configuration.
     pub fn new() -> Self {
-        Self {
-            config: RuleConfig::default(),
-        }
+        Self::with_config(RuleConfig::default())
     }
 
     /// Create a new rule detector with the given configuration.
     pub fn with_config(config: RuleConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            timeout: None,
+            registered: Vec::new(),
+        }
     }
 
     /// Load configuration from a file.
     pub fn from_config_file(path: &Path) -> Result<Self> {
         let config = RuleConfig::from_file(path)?;
-        Ok(Self { config })
+        Ok(Self::with_config(config))
+    }
+
+    /// Limit the wall-clock time `analyze_file` spends on one file.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Register a detector to run after the built-in rules.
+    pub fn register(mut self, detector: Box<dyn Detector>) -> Self {
+        self.registered.push(Arc::from(detector));
+        self
     }
 
     /// Get the current configuration.
@@ -49,26 +148,136 @@ impl RuleDetector {
         Severity::parse(&self.config.general.min_severity).unwrap_or(Severity::Info)
     }
 
-    /// Analyze a file (L5X or PLCopen) and return a report.
+    /// Analyze a file (L5X or PLCopen), or a ZIP archive or folder of L5X
+    /// exports, and return a report.
+    ///
+    /// An `.ACD` file is exported to L5X with `[general] acd_export_cmd`
+    /// first, and fails with [`Error::AcdExport`] if no command is set.
+    ///
+    /// With a timeout ([`RuleDetector::with_timeout`]), loading and analysis
+    /// run on a worker thread and the file fails with [`Error::Timeout`] if
+    /// they take longer.
     pub fn analyze_file(&self, path: &Path) -> Result<Report> {
-        let project = LoadedProject::from_file(path)?;
+        let Some(timeout) = self.timeout else {
+            return self.load_and_analyze(path);
+        };
+
+        // Analyze on a worker thread; on timeout the thread is abandoned and
+        // finishes (or keeps running) in the background
+        let (sender, receiver) = mpsc::channel();
+        let detector = self.clone();
+        let file = path.to_path_buf();
+        let worker = thread::spawn(move || {
+            // The receiver is gone if the analysis timed out
+            let _ = sender.send(detector.load_and_analyze(&file));
+        });
+        match receiver.recv_timeout(timeout) {
+            Ok(result) => result,
+            Err(RecvTimeoutError::Timeout) => Err(Error::Timeout {
+                path: path.display().to_string(),
+                limit: timeout,
+            }),
+            Err(RecvTimeoutError::Disconnected) => {
+                // The worker panicked before sending a result
+                let panic = worker.join().err().unwrap_or_else(|| Box::new("analysis worker stopped"));
+                std::panic::resume_unwind(panic)
+            }
+        }
+    }
+
+    fn load_and_analyze(&self, path: &Path) -> Result<Report> {
+        let project = if LoadedProject::is_bundle_path(path) {
+            LoadedProject::from_bundle(path)?
+        } else if acd::is_acd_path(path) {
+            // Analyze the export, reporting findings against the .ACD file
+            let export = acd::export(path, self.config.general.acd_export_cmd.as_deref())?;
+            let mut project = LoadedProject::from_file(&export.l5x)?;
+            project.source_path = Some(path.display().to_string());
+            project
+        } else {
+            LoadedProject::from_file(path)?
+        };
         let mut report = self.analyze(&project)?;
         report.source_file = project.source_path;
         Ok(report)
     }
 
+    /// Analyze in-memory file content in the given format.
+    pub fn analyze_str(&self, content: &str, format: FileFormat) -> Result<Report> {
+        let project = LoadedProject::from_str_with_format(content, format)?;
+        self.analyze(&project)
+    }
+
     /// Analyze a loaded project.
+    ///
+    /// Findings in a bundle record the member file they came from, and
+    /// the report lists the conflicting definitions of the members.
     pub fn analyze(&self, project: &LoadedProject) -> Result<Report> {
-        if let Some(ref controller) = project.l5x_controller {
-            return self.analyze_controller(controller);
+        let mut report = if let Some(ref controller) = project.l5x_controller {
+            self.analyze_controller(controller)?
+        } else if let Some(ref plcopen) = project.plcopen_project {
+            self.analyze_plcopen(plcopen, project.source_path.clone())?
+        } else {
+            // Unknown format
+            return Ok(Report::new());
+        };
+
+        for detector in &self.registered {
+            let rules = detector.detect(project);
+            report.rules.extend(
+                rules
+                    .into_iter()
+                    .filter(|rule| !self.config.custom_rules.is_disabled(rule.id(), rule.name())),
+            );
         }
-        
-        if let Some(ref plcopen) = project.plcopen_project {
-            return self.analyze_plcopen(plcopen, project.source_path.clone());
+
+        if let Some(export) = project.export.filter(ExportKind::is_partial) {
+            report.rules.retain(|rule| !export.skips(&rule.kind));
+            report.export = Some(export);
+        }
+
+        if let Some(ref bundle) = project.bundle {
+            for rule in &mut report.rules {
+                rule.file = bundle.member_of(&rule.location, &rule.identifier).map(str::to_string);
+            }
+            report.duplicates = bundle.duplicates.clone();
+        }
+        self.escalate(&mut report);
+        self.apply_messages(&mut report);
+        Ok(report)
+    }
+
+    /// Replace the messages of rules with a template in `[messages]`.
+    fn apply_messages(&self, report: &mut Report) {
+        if self.config.messages.is_empty() {
+            return;
+        }
+        for rule in &mut report.rules {
+            if let Some(template) = self.config.message_template(rule.id(), rule.name()) {
+                rule.message = messages::render(template, rule);
+            }
+        }
+    }
+
+    /// Raise the findings of rules with more findings per file than their
+    /// `[escalation]` threshold one severity level.
+    fn escalate(&self, report: &mut Report) {
+        let escalation = &self.config.escalation;
+        if escalation.thresholds.is_empty() {
+            return;
+        }
+        let mut counts: HashMap<(&'static str, Option<String>), usize> = HashMap::new();
+        for rule in &report.rules {
+            *counts.entry((rule.id(), rule.file.clone())).or_default() += 1;
+        }
+        for rule in &mut report.rules {
+            let Some(threshold) = escalation.threshold(rule.id(), rule.name()) else {
+                continue;
+            };
+            if counts[&(rule.id(), rule.file.clone())] > threshold {
+                rule.severity = rule.severity.raised();
+            }
         }
-        
-        // Unknown format
-        Ok(Report::new())
     }
     
     /// Analyze a PLCopen project.
@@ -77,17 +286,186 @@ impl RuleDetector {
         
         let mut report = Report::new();
         report.source_file = source_path;
+        report.protected = analysis.protected_pous.clone();
         
         // Run PLCopen-specific detectors
-        let unused_detector = PlcopenUnusedVarsDetector::new(&self.config.unused_tags);
+        let unused_detector =
+            PlcopenUnusedVarsDetector::new(&self.config.unused_tags).case_sensitive(self.config.general.case_sensitive);
         unused_detector.detect(&analysis, &mut report);
         
-        let undefined_detector = PlcopenUndefinedVarsDetector::new(&self.config.undefined_tags);
+        let undefined_detector = PlcopenUndefinedVarsDetector::new(&self.config.undefined_tags)
+            .case_sensitive(self.config.general.case_sensitive);
         undefined_detector.detect(&analysis, &mut report);
         
+        let unused_globals_detector = PlcopenUnusedGlobalsDetector::new(&self.config.unused_tags)
+            .case_sensitive(self.config.general.case_sensitive);
+        unused_globals_detector.detect(&analysis, &mut report);
+        
+        let undeclared_globals_detector = PlcopenUndeclaredGlobalsDetector::new(&self.config.undefined_tags)
+            .case_sensitive(self.config.general.case_sensitive);
+        undeclared_globals_detector.detect(&analysis, &mut report);
+        
         let empty_detector = PlcopenEmptyPousDetector::new(&self.config.empty_routines);
         empty_detector.detect(&analysis, &mut report);
         
+        // Run metric detectors on ST bodies
+        let st_routines = parse_st_bodies(project);
+        
+        let complexity_detector = ComplexityDetector::new(&self.config.complexity);
+        complexity_detector.detect_routines(&st_routines, &mut report);
+        
+        let nesting_detector = NestingDetector::new(&self.config.nesting);
+        nesting_detector.detect_routines(&st_routines, &mut report);
+        
+        // Run semantic detectors on ST bodies
+        let st_units = StUnits::from_plcopen(project);
+        
+        let type_mismatch_detector = TypeMismatchDetector::new(&self.config.type_mismatch);
+        type_mismatch_detector.detect(&st_units, &mut report);
+        
+        let non_exhaustive_case_detector = NonExhaustiveCaseDetector::new(&self.config.non_exhaustive_case);
+        non_exhaustive_case_detector.detect(&st_units, &mut report);
+        
+        let overlapping_case_labels_detector = OverlappingCaseLabelsDetector::new(&self.config.overlapping_case_labels);
+        overlapping_case_labels_detector.detect(&st_units, &mut report);
+        
+        let loop_var_modified_detector = LoopVarModifiedDetector::new(&self.config.loop_var_modified);
+        loop_var_modified_detector.detect(&st_units, &mut report);
+        
+        let empty_branches_detector = EmptyBranchesDetector::new(&self.config.empty_branches);
+        empty_branches_detector.detect(&st_units, &mut report);
+        
+        let self_assignment_detector = SelfAssignmentDetector::new(&self.config.self_assignment);
+        self_assignment_detector.detect(&st_units, &mut report);
+        
+        let float_equality_detector = FloatEqualityDetector::new(&self.config.float_equality);
+        float_equality_detector.detect(&st_units, &mut report);
+        
+        let unused_variables_detector = UnusedVariablesDetector::new(&self.config.unused_variables);
+        unused_variables_detector.detect(&st_units, &mut report);
+        
+        // Run naming detectors on declared names
+        let names = names_from_plcopen(project);
+        
+        let naming_detector = NamingDetector::new(&self.config.naming);
+        naming_detector.detect(&names, &mut report);
+        
+        let identifiers_detector = IdentifiersDetector::new(&self.config.identifiers);
+        identifiers_detector.detect(&names, &mut report);
+        
+        let uninitialized_read_detector = UninitializedReadDetector::new(&self.config.uninitialized_read);
+        uninitialized_read_detector.detect(&st_units, &mut report);
+        
+        let variables = variables_from_plcopen(project);
+        let required_init_detector = RequiredInitDetector::new(&self.config.required_init);
+        required_init_detector.detect(&variables, &mut report);
+        
+        let undefined_datatype_ref_detector = UndefinedDataTypeRefDetector::new(&self.config.undefined_datatype_ref);
+        undefined_datatype_ref_detector.detect(&DataTypeIndex::from_plcopen(project), &mut report);
+        
+        if let Some(bindings) = TaskBindings::from_plcopen(project) {
+            let unscheduled_program_detector = UnscheduledProgramDetector::new(&self.config.unscheduled_program);
+            unscheduled_program_detector.detect(&bindings, &mut report);
+
+            let unreachable_pou_detector = UnreachablePouDetector::new(&self.config.unreachable_pou);
+            unreachable_pou_detector.detect(&analysis.call_graph(), &bindings, &mut report);
+        }
+        
+        let usage = InstanceUsage::from_plcopen(project, &analysis);
+        let uninvoked_instance_detector = UninvokedInstanceDetector::new(&self.config.uninvoked_instance);
+        uninvoked_instance_detector.detect(&usage, &mut report);
+        
+        let array_bounds_detector = ArrayBoundsDetector::new(&self.config.array_bounds);
+        array_bounds_detector.detect(&st_units, &mut report);
+        
+        let in_out_not_variable_detector = InOutNotVariableDetector::new(&self.config.in_out_not_variable);
+        in_out_not_variable_detector.detect(&st_units, &mut report);
+
+        let invalid_array_range_detector = InvalidArrayRangeDetector::new(&self.config.invalid_array_range);
+        invalid_array_range_detector.detect(&st_units, &mut report);
+
+        let unbound_input_detector = UnboundInputDetector::new(&self.config.unbound_input);
+        unbound_input_detector.detect(&st_units, &mut report);
+
+        let string_concat_in_loop_detector = StringConcatInLoopDetector::new(&self.config.string_concat_in_loop);
+        string_concat_in_loop_detector.detect(&st_units, &mut report);
+
+        let hardcoded_address_detector = HardcodedAddressDetector::new(&self.config.hardcoded_address);
+        hardcoded_address_detector.detect(&st_units, &mut report);
+
+        let convert_to_case_detector = ConvertToCaseDetector::new(&self.config.convert_to_case);
+        convert_to_case_detector.detect(&st_units, &mut report);
+
+        let argument_conversion_detector = ArgumentConversionDetector::new(&self.config.argument_conversion);
+        argument_conversion_detector.detect(&st_units, &mut report);
+
+        let bool_toggle_detector = BoolToggleDetector::new(&self.config.bool_toggle);
+        bool_toggle_detector.detect(&st_units, &mut report);
+        
+        let style_detector = StyleDetector::new(&self.config.style);
+        style_detector.detect_routines(&st_routines, &mut report);
+        
+        let low_comment_density_detector = LowCommentDensityDetector::new(&self.config.low_comment_density);
+        low_comment_density_detector.detect_routines(&st_routines, &mut report);
+        
+        let timers = TimerUsage::from_plcopen(&st_units);
+        let timer_no_reset_detector = TimerNoResetDetector::new(&self.config.timer_no_reset);
+        timer_no_reset_detector.detect(&timers, &mut report);
+        
+        let il_labels = il_labels_from_plcopen(project);
+        let jump_labels_detector = JumpLabelsDetector::new(&self.config.jump_labels);
+        jump_labels_detector.detect(&il_labels, &mut report);
+        
+        let string_truncation_detector = StringTruncationDetector::new(&self.config.string_truncation);
+        string_truncation_detector.detect(&st_units, &mut report);
+        
+        let duplicates = DuplicateLogic::from_plcopen(&st_routines);
+        let duplicate_logic_detector = DuplicateLogicDetector::new(&self.config.duplicate_logic);
+        duplicate_logic_detector.detect(&duplicates, &mut report);
+        
+        let fbd_networks = fbd_networks_from_plcopen(project);
+        let fbd_unconnected_detector = FbdUnconnectedDetector::new(&self.config.fbd_unconnected);
+        fbd_unconnected_detector.detect(&fbd_networks, &mut report);
+        
+        let division_by_zero_detector = DivisionByZeroDetector::new(&self.config.division_by_zero);
+        division_by_zero_detector.detect(&st_units, &mut report);
+        
+        let bit_operation_detector = BitOperationDetector::new(&self.config.bit_operation);
+        bit_operation_detector.detect(&st_units, &mut report);
+        
+        let law_of_demeter_detector = LawOfDemeterDetector::new(&self.config.law_of_demeter);
+        law_of_demeter_detector.detect(&st_units, &mut report);
+        
+        let comparison_statement_detector = ComparisonStatementDetector::new(&self.config.comparison_statement);
+        comparison_statement_detector.detect(&st_units, &mut report);
+        
+        let impure_function_detector = ImpureFunctionDetector::new(&self.config.impure_function);
+        impure_function_detector.detect(&st_units, &mut report);
+        
+        let dead_store_detector = DeadStoreDetector::new(&self.config.dead_store);
+        dead_store_detector.detect(&st_units, &mut report);
+        
+        let nonstandard_function_detector = NonstandardFunctionDetector::new(&self.config.nonstandard_function);
+        nonstandard_function_detector.detect(&st_units, analysis.pou_names.iter().map(String::as_str), &mut report);
+        
+        let mut texts = texts_from_st(&st_units);
+        texts.extend(texts_from_plcopen(project));
+        let comment_markers_detector = CommentMarkersDetector::new(&self.config.comment_markers);
+        comment_markers_detector.detect(&texts, &mut report);
+        
+        let redundant_boolean_detector = RedundantBooleanDetector::new(&self.config.redundant_boolean);
+        redundant_boolean_detector.detect(&st_units, &mut report);
+        
+        let enum_value_conflict_detector = EnumValueConflictDetector::new(&self.config.enum_value_conflict);
+        enum_value_conflict_detector.detect(&st_units, &mut report);
+        
+        let invalid_struct_detector = InvalidStructDetector::new(&self.config.invalid_struct);
+        invalid_struct_detector.detect(&st_units, &mut report);
+        
+        let declarations = st_declarations(project);
+        let unused_method_parameter_detector = UnusedMethodParameterDetector::new(&self.config.unused_method_parameter);
+        unused_method_parameter_detector.detect(&declarations, &mut report);
+        
         Ok(report)
     }
 
@@ -97,13 +475,19 @@ impl RuleDetector {
         let analysis = analyze_controller(controller);
 
         let mut report = Report::new();
+        report.protected = analysis.protected.clone();
+
+        // Semantic detectors work on ST routines with synthesized declarations
+        let st_units = StUnits::from_controller(controller);
 
         // Run unused tags detector
-        let unused_tags_detector = UnusedTagsDetector::new(&self.config.unused_tags);
-        unused_tags_detector.detect(controller, &analysis, &mut report);
+        let unused_tags_detector =
+            UnusedTagsDetector::new(&self.config.unused_tags).case_sensitive(self.config.general.case_sensitive);
+        unused_tags_detector.detect(controller, &analysis, &st_units, &mut report);
 
         // Run undefined tags detector
-        let undefined_tags_detector = UndefinedTagsDetector::new(&self.config.undefined_tags);
+        let undefined_tags_detector =
+            UndefinedTagsDetector::new(&self.config.undefined_tags).case_sensitive(self.config.general.case_sensitive);
         undefined_tags_detector.detect(controller, &analysis, &mut report);
 
         // Run empty routines detector
@@ -118,6 +502,9 @@ impl RuleDetector {
         let unused_datatypes_detector = UnusedDataTypesDetector::new(&self.config.unused_datatypes);
         unused_datatypes_detector.detect(controller, &mut report);
 
+        let undefined_datatype_ref_detector = UndefinedDataTypeRefDetector::new(&self.config.undefined_datatype_ref);
+        undefined_datatype_ref_detector.detect(&DataTypeIndex::from_controller(controller), &mut report);
+
         // Run cyclomatic complexity detector on ST routines
         let complexity_detector = ComplexityDetector::new(&self.config.complexity);
         complexity_detector.detect(&analysis, &mut report);
@@ -126,6 +513,176 @@ impl RuleDetector {
         let nesting_detector = NestingDetector::new(&self.config.nesting);
         nesting_detector.detect(&analysis, &mut report);
 
+        // Run type mismatch detector on ST routines
+        let type_mismatch_detector = TypeMismatchDetector::new(&self.config.type_mismatch);
+        type_mismatch_detector.detect(&st_units, &mut report);
+
+        // Run non-exhaustive CASE detector on ST routines
+        let non_exhaustive_case_detector = NonExhaustiveCaseDetector::new(&self.config.non_exhaustive_case);
+        non_exhaustive_case_detector.detect(&st_units, &mut report);
+
+        // Run overlapping CASE label detector on ST routines
+        let overlapping_case_labels_detector = OverlappingCaseLabelsDetector::new(&self.config.overlapping_case_labels);
+        overlapping_case_labels_detector.detect(&st_units, &mut report);
+
+        // Run loop variable modification detector on ST routines
+        let loop_var_modified_detector = LoopVarModifiedDetector::new(&self.config.loop_var_modified);
+        loop_var_modified_detector.detect(&st_units, &mut report);
+
+        // Run empty branch detector on ST routines
+        let empty_branches_detector = EmptyBranchesDetector::new(&self.config.empty_branches);
+        empty_branches_detector.detect(&st_units, &mut report);
+
+        // Run self-assignment detector on ST routines
+        let self_assignment_detector = SelfAssignmentDetector::new(&self.config.self_assignment);
+        self_assignment_detector.detect(&st_units, &mut report);
+
+        // Run float equality detector on ST routines
+        let float_equality_detector = FloatEqualityDetector::new(&self.config.float_equality);
+        float_equality_detector.detect(&st_units, &mut report);
+
+        // Run unused variables detector on ST routines
+        let unused_variables_detector = UnusedVariablesDetector::new(&self.config.unused_variables);
+        unused_variables_detector.detect(&st_units, &mut report);
+
+        // Naming detectors work on declared names
+        let names = names_from_controller(controller);
+
+        // Run naming convention detector
+        let naming_detector = NamingDetector::new(&self.config.naming);
+        naming_detector.detect(&names, &mut report);
+
+        // Run identifier length and reserved word detector
+        let identifiers_detector = IdentifiersDetector::new(&self.config.identifiers);
+        identifiers_detector.detect(&names, &mut report);
+
+        // Run uninitialized read detection
+        let uninitialized_read_detector = UninitializedReadDetector::new(&self.config.uninitialized_read);
+        uninitialized_read_detector.detect(&st_units, &mut report);
+
+        // Run required initial value detection
+        let variables = variables_from_controller(controller);
+        let required_init_detector = RequiredInitDetector::new(&self.config.required_init);
+        required_init_detector.detect(&variables, &mut report);
+
+        // Run unscheduled program detection
+        let bindings = TaskBindings::from_controller(controller);
+        if let Some(ref bindings) = bindings {
+            let unscheduled_program_detector = UnscheduledProgramDetector::new(&self.config.unscheduled_program);
+            unscheduled_program_detector.detect(bindings, &mut report);
+
+            // Never-called AOIs are reported as unused AOIs
+            let unreachable_pou_detector = UnreachablePouDetector::new(&self.config.unreachable_pou)
+                .location_prefix("AOI:")
+                .skip_uncalled(self.config.unused_aois.enabled);
+            unreachable_pou_detector.detect(&analysis.call_graph(), bindings, &mut report);
+        }
+
+        // Run uninvoked instance detection
+        let usage = InstanceUsage::from_controller(controller, &analysis, &st_units);
+        let uninvoked_instance_detector = UninvokedInstanceDetector::new(&self.config.uninvoked_instance);
+        uninvoked_instance_detector.detect(&usage, &mut report);
+
+        // Run array bounds detector on ST routines
+        let array_bounds_detector = ArrayBoundsDetector::new(&self.config.array_bounds);
+        array_bounds_detector.detect(&st_units, &mut report);
+
+        // Run VAR_IN_OUT binding detection
+        let in_out_not_variable_detector = InOutNotVariableDetector::new(&self.config.in_out_not_variable);
+        in_out_not_variable_detector.detect(&st_units, &mut report);
+
+        let invalid_array_range_detector = InvalidArrayRangeDetector::new(&self.config.invalid_array_range);
+        invalid_array_range_detector.detect(&st_units, &mut report);
+
+        let unbound_input_detector = UnboundInputDetector::new(&self.config.unbound_input);
+        unbound_input_detector.detect(&st_units, &mut report);
+
+        let string_concat_in_loop_detector = StringConcatInLoopDetector::new(&self.config.string_concat_in_loop);
+        string_concat_in_loop_detector.detect(&st_units, &mut report);
+
+        let hardcoded_address_detector = HardcodedAddressDetector::new(&self.config.hardcoded_address);
+        hardcoded_address_detector.detect(&st_units, &mut report);
+
+        let convert_to_case_detector = ConvertToCaseDetector::new(&self.config.convert_to_case);
+        convert_to_case_detector.detect(&st_units, &mut report);
+
+        let argument_conversion_detector = ArgumentConversionDetector::new(&self.config.argument_conversion);
+        argument_conversion_detector.detect(&st_units, &mut report);
+
+        let bool_toggle_detector = BoolToggleDetector::new(&self.config.bool_toggle);
+        bool_toggle_detector.detect(&st_units, &mut report);
+
+        // Run ST style detector
+        let style_detector = StyleDetector::new(&self.config.style);
+        style_detector.detect(&analysis, &mut report);
+
+        // Run comment density detector
+        let low_comment_density_detector = LowCommentDensityDetector::new(&self.config.low_comment_density);
+        low_comment_density_detector.detect(&analysis, &mut report);
+
+        // Run timer and counter reset detection
+        let timers = TimerUsage::from_controller(&analysis.rungs, &st_units);
+        let timer_no_reset_detector = TimerNoResetDetector::new(&self.config.timer_no_reset);
+        timer_no_reset_detector.detect(&timers, &mut report);
+
+        // Run string truncation detector on ST routines
+        let string_truncation_detector = StringTruncationDetector::new(&self.config.string_truncation);
+        string_truncation_detector.detect(&st_units, &mut report);
+
+        // Run duplicate logic detection on RLL and ST routines
+        let duplicates = DuplicateLogic::from_controller(&analysis);
+        let duplicate_logic_detector = DuplicateLogicDetector::new(&self.config.duplicate_logic);
+        duplicate_logic_detector.detect(&duplicates, &mut report);
+
+        // Run division by zero detector on ST routines
+        let division_by_zero_detector = DivisionByZeroDetector::new(&self.config.division_by_zero);
+        division_by_zero_detector.detect(&st_units, &mut report);
+
+        // Run bit operation detector on ST routines
+        let bit_operation_detector = BitOperationDetector::new(&self.config.bit_operation);
+        bit_operation_detector.detect(&st_units, &mut report);
+
+        // Run law of Demeter detector on ST routines
+        let law_of_demeter_detector = LawOfDemeterDetector::new(&self.config.law_of_demeter);
+        law_of_demeter_detector.detect(&st_units, &mut report);
+
+        // Run comparison statement detector on ST routines
+        let comparison_statement_detector = ComparisonStatementDetector::new(&self.config.comparison_statement);
+        comparison_statement_detector.detect(&st_units, &mut report);
+
+        // Run dead store detector on ST routines
+        let dead_store_detector = DeadStoreDetector::new(&self.config.dead_store);
+        dead_store_detector.detect(&st_units, &mut report);
+
+        // Run nonstandard function detector on ST routines
+        let nonstandard_function_detector = NonstandardFunctionDetector::new(&self.config.nonstandard_function);
+        nonstandard_function_detector.detect(&st_units, analysis.aoi_definitions.iter().map(String::as_str), &mut report);
+
+        // Run comment markers detector on comments and descriptions
+        let mut texts = texts_from_st(&st_units);
+        texts.extend(texts_from_controller(controller));
+        let comment_markers_detector = CommentMarkersDetector::new(&self.config.comment_markers);
+        comment_markers_detector.detect(&texts, &mut report);
+
+        // Tag reads and writes of the programs in RLL and ST
+        let accesses = TagAccesses::from_controller(&analysis, &st_units);
+
+        // Run tag scope detector (controller tags used by one program)
+        let tag_scope_suggestion_detector = TagScopeSuggestionDetector::new(&self.config.tag_scope_suggestion)
+            .external(&self.config.undefined_tags.allow)
+            .case_sensitive(self.config.general.case_sensitive);
+        tag_scope_suggestion_detector.detect(controller, &accesses, &mut report);
+
+        // Run redundant boolean detector
+        let redundant_boolean_detector = RedundantBooleanDetector::new(&self.config.redundant_boolean);
+        redundant_boolean_detector.detect(&st_units, &mut report);
+
+        // Run cross-program shared tag detector (written in one program, read in another)
+        let cross_program_shared_tag_detector =
+            CrossProgramSharedTagDetector::new(&self.config.cross_program_shared_tag)
+                .case_sensitive(self.config.general.case_sensitive);
+        cross_program_shared_tag_detector.detect(controller, &accesses, bindings.as_ref(), &mut report);
+
         Ok(report)
     }
 
@@ -135,7 +692,22 @@ impl RuleDetector {
         self.get_stats(&project)
     }
 
+    /// Get the parse statistics of a loaded project in either format,
+    /// without running rule detection.
+    pub fn stats(&self, project: &LoadedProject) -> Result<ProjectStats> {
+        if let Some(ref plcopen) = project.plcopen_project {
+            return Ok(ProjectStats::PlcOpen(analyze_plcopen_project(plcopen).stats));
+        }
+        let controller = project.l5x_controller.as_ref().ok_or(Error::L5xParse {
+            kind: L5xParseErrorKind::MissingElement("Controller"),
+        })?;
+        Ok(ProjectStats::L5x(analyze_controller(controller).stats))
+    }
+
     /// Get statistics for a loaded project (L5X format).
+    ///
+    /// Returns empty statistics for PLCopen projects; [`stats`](Self::stats)
+    /// handles both formats.
     pub fn get_stats(&self, project: &LoadedProject) -> Result<ParseStats> {
         if let Some(ref controller) = project.l5x_controller {
             let analysis = analyze_controller(controller);
@@ -146,7 +718,24 @@ impl RuleDetector {
         Ok(ParseStats::default())
     }
     
+    /// Get the aggregate metrics of a loaded project: statistics and finding counts.
+    pub fn metrics(&self, project: &LoadedProject) -> Result<ProjectMetrics> {
+        let report = self.analyze(project)?;
+        if let Some(ref plcopen) = project.plcopen_project {
+            let analysis = analyze_plcopen_project(plcopen);
+            return Ok(ProjectMetrics::from_plcopen(project, analysis.stats, &report));
+        }
+        let controller = project.l5x_controller.as_ref().ok_or(Error::L5xParse {
+            kind: L5xParseErrorKind::MissingElement("Controller"),
+        })?;
+        let analysis = analyze_controller(controller);
+        Ok(ProjectMetrics::from_l5x(project, controller, analysis.stats, &report))
+    }
+
     /// Get PLCopen statistics for a loaded project.
+    ///
+    /// Returns empty statistics for L5X projects; [`stats`](Self::stats)
+    /// handles both formats.
     pub fn get_plcopen_stats(&self, project: &LoadedProject) -> Result<PlcopenStats> {
         if let Some(ref plcopen) = project.plcopen_project {
             let analysis = analyze_plcopen_project(plcopen);
@@ -163,9 +752,22 @@ impl Default for RuleDetector {
     }
 }
 
+impl Detector for RuleDetector {
+    /// Run the built-in rules and the detectors registered with this one;
+    /// a project that fails to analyze has no findings.
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        self.analyze(project).map(|report| report.rules).unwrap_or_default()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::CodeSize;
+    use crate::bundle::Bundle;
+    use crate::config::{CrossProgramSharedTagConfig, UndefinedTagsConfig, UnusedDataTypesConfig};
+    use crate::error::AcdExportErrorKind;
+    use crate::report::RuleKind;
 
     #[test]
     fn test_detector_default() {
@@ -233,4 +835,1012 @@ mod tests {
         // Should detect empty POU
         assert!(report.rules.iter().any(|s| s.identifier == "Main"));
     }
+
+    #[test]
+    fn test_plcopen_nesting_per_kind() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Scale" pouType="function">
+                        <body><ST><![CDATA[IF a THEN IF b THEN IF c THEN x := 1; END_IF; END_IF; END_IF;]]></ST></body>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        let mut config = RuleConfig::default();
+        config.nesting.max_depth = 2;
+        let report = RuleDetector::with_config(config.clone())
+            .analyze_str(xml, FileFormat::PlcOpen)
+            .expect("Should analyze");
+        let rule = report.rules().iter().find(|r| r.kind == RuleKind::DeepNesting).expect("Should flag nesting");
+        assert_eq!(rule.location, "Scale");
+        assert!(rule.message.contains("(max: 2)"));
+
+        config.nesting.per_kind.function = Some(3);
+        let report = RuleDetector::with_config(config)
+            .analyze_str(xml, FileFormat::PlcOpen)
+            .expect("Should analyze");
+        assert!(!report.rules().iter().any(|r| r.kind == RuleKind::DeepNesting));
+    }
+
+    #[test]
+    fn test_undefined_tags_allowlist() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <Programs>
+                    <Program Name="MainProgram">
+                        <Routines>
+                            <Routine Name="MainRoutine" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0">
+                                        <Text>XIC(HMI_Start)XIC(Strat)OTE(HMI_Running);</Text>
+                                    </Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let mut config = RuleConfig::default();
+        config.undefined_tags.allow = vec!["HMI_*".to_string()];
+        let report = RuleDetector::with_config(config)
+            .analyze_str(xml, FileFormat::L5x)
+            .expect("Should analyze");
+        let undefined: Vec<_> = report.rules().iter().filter(|r| r.kind == RuleKind::UndefinedTag).collect();
+        assert_eq!(undefined.len(), 1);
+        assert_eq!(undefined[0].identifier, "Strat");
+    }
+
+    #[test]
+    fn test_undefined_tags_module_io() {
+        let rung = "XIC(Local:1:I.Data.0)XIC(Local:5:I.Data.0)XIC(Remote)OTE(Drive1:O.Run)OTE(Missing);";
+        let modules = r#"<Modules>
+                    <Module Name="Local" ParentModule="Local"/>
+                    <Module Name="DI16" ParentModule="Local">
+                        <Ports><Port Id="1" Address="1" Type="ICP" Upstream="true"/></Ports>
+                        <Communications><Connections>
+                            <Connection Name="Data" RPI="20000"><InputTag DataType="AB:1756_DI:I:0"/></Connection>
+                        </Connections></Communications>
+                    </Module>
+                    <Module Name="Drive1" ParentModule="Local">
+                        <Ports><Port Id="2" Address="192.168.1.20" Type="Ethernet" Upstream="true"/></Ports>
+                        <Communications><Connections>
+                            <Connection Name="Exclusive" RPI="10000"><OutputTag DataType="AB:PF525:O:0"/></Connection>
+                        </Connections></Communications>
+                    </Module>
+                </Modules>"#;
+        let xml = |modules: &str| {
+            format!(
+                r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                {}
+                <Tags>
+                    <Tag Name="Remote" TagType="Consumed" DataType="DINT">
+                        <ConsumeInfo Producer="Line2" RemoteTag="Speed" RPI="20"/>
+                    </Tag>
+                </Tags>
+                <Programs>
+                    <Program Name="MainProgram">
+                        <Routines>
+                            <Routine Name="MainRoutine" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0"><Text>{}</Text></Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#,
+                modules, rung
+            )
+        };
+        let undefined = |xml: &str, config: RuleConfig| -> Vec<String> {
+            let report = RuleDetector::with_config(config)
+                .analyze_str(xml, FileFormat::L5x)
+                .expect("Should analyze");
+            report
+                .rules()
+                .iter()
+                .filter(|r| r.kind == RuleKind::UndefinedTag)
+                .map(|r| r.identifier.clone())
+                .collect()
+        };
+
+        let found = undefined(&xml(modules), RuleConfig::default());
+        assert_eq!(found, ["Local:5:I", "Missing"]);
+
+        // Without a Modules section module references cannot be resolved
+        let found = undefined(&xml(""), RuleConfig::default());
+        assert_eq!(found, ["Missing"]);
+
+        let mut config = RuleConfig::default();
+        config.undefined_tags.module_tags = false;
+        let found = undefined(&xml(modules), config);
+        assert_eq!(found, ["Drive1:O", "Local:1:I", "Local:5:I", "Missing"]);
+    }
+
+    #[test]
+    fn test_tag_scope_suggestion() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <Tags>
+                    <Tag Name="Motor" TagType="Base" DataType="BOOL"/>
+                    <Tag Name="Shared" TagType="Base" DataType="BOOL"/>
+                    <Tag Name="HMI_Start" TagType="Base" DataType="BOOL"/>
+                    <Tag Name="Sent" TagType="Produced" DataType="DINT"/>
+                </Tags>
+                <Programs>
+                    <Program Name="Line1">
+                        <Routines>
+                            <Routine Name="Main" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0">
+                                        <Text>XIC(HMI_Start)XIC(Shared)OTE(motor);</Text>
+                                    </Rung>
+                                    <Rung Number="1">
+                                        <Text>MOV(1,Sent);</Text>
+                                    </Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                    <Program Name="Line2">
+                        <Routines>
+                            <Routine Name="Main" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0">
+                                        <Text>XIO(Shared)OTE(Local_Out);</Text>
+                                    </Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let config = RuleConfig {
+            undefined_tags: UndefinedTagsConfig {
+                allow: vec!["HMI_*".to_string()],
+                ..UndefinedTagsConfig::default()
+            },
+            ..RuleConfig::default()
+        };
+        let report = RuleDetector::with_config(config)
+            .analyze_str(xml, FileFormat::L5x)
+            .expect("Should analyze");
+        let suggestions: Vec<_> =
+            report.rules().iter().filter(|r| r.kind == RuleKind::TagScopeSuggestion).collect();
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(suggestions[0].identifier, "Motor");
+        assert_eq!(suggestions[0].location, "Controller");
+        assert!(suggestions[0].message.contains("program 'Line1'"));
+    }
+
+    #[test]
+    fn test_tag_scope_suggestion_st() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <Tags>
+                    <Tag Name="Level" TagType="Base" DataType="DINT"/>
+                    <Tag Name="Speed" TagType="Base" DataType="DINT"/>
+                </Tags>
+                <Programs>
+                    <Program Name="Fill">
+                        <Routines>
+                            <Routine Name="Main" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0">
+                                        <Text>MOV(Speed,Level);</Text>
+                                    </Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                    <Program Name="Drain">
+                        <Tags>
+                            <Tag Name="Speed" TagType="Base" DataType="DINT"/>
+                        </Tags>
+                        <Routines>
+                            <Routine Name="Calc" Type="ST">
+                                <STContent>
+                                    <Line Number="0"><![CDATA[Speed := Level / 2;]]></Line>
+                                </STContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let report = RuleDetector::new().analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        // Level is read in ST by Drain; Drain's Speed is its own program tag
+        let suggestions: Vec<_> = report
+            .rules()
+            .iter()
+            .filter(|r| r.kind == RuleKind::TagScopeSuggestion)
+            .map(|r| (r.identifier.as_str(), r.message.contains("program 'Fill'")))
+            .collect();
+        assert_eq!(suggestions, [("Speed", true)]);
+        assert!(report.rules().iter().any(|r| r.kind == RuleKind::CrossProgramSharedTag && r.identifier == "Level"));
+    }
+
+    #[test]
+    fn test_cross_program_shared_tag() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <Tags>
+                    <Tag Name="Speed" TagType="Base" DataType="DINT"/>
+                    <Tag Name="Level" TagType="Base" DataType="DINT"/>
+                    <Tag Name="Mode" TagType="Base" DataType="DINT"/>
+                    <Tag Name="Alarm" TagType="Base" DataType="BOOL"/>
+                </Tags>
+                <Tasks>
+                    <Task Name="Main" Type="CONTINUOUS">
+                        <ScheduledPrograms>
+                            <ScheduledProgram Name="Cap"/>
+                            <ScheduledProgram Name="Fill"/>
+                        </ScheduledPrograms>
+                    </Task>
+                    <Task Name="Fast" Type="PERIODIC" Rate="10">
+                        <ScheduledPrograms>
+                            <ScheduledProgram Name="Safety"/>
+                        </ScheduledPrograms>
+                    </Task>
+                </Tasks>
+                <Programs>
+                    <Program Name="Fill">
+                        <Routines>
+                            <Routine Name="Main" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0"><Text>MOV(100,Speed);</Text></Rung>
+                                    <Rung Number="1"><Text>MOV(Mode,Level);</Text></Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                    <Program Name="Cap">
+                        <Tags>
+                            <Tag Name="Level" TagType="Base" DataType="DINT"/>
+                        </Tags>
+                        <Routines>
+                            <Routine Name="Main" Type="ST">
+                                <STContent>
+                                    <Line Number="0">Mode := Speed + Level;</Line>
+                                </STContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                    <Program Name="Safety">
+                        <Routines>
+                            <Routine Name="Main" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0"><Text>GRT(Speed,500)OTE(Alarm);</Text></Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let report = RuleDetector::new().analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        let shared: Vec<_> = report
+            .rules()
+            .iter()
+            .filter(|r| r.kind == RuleKind::CrossProgramSharedTag)
+            .map(|r| (r.identifier.as_str(), r.message.as_str()))
+            .collect();
+        // Level in Cap is its program tag, Alarm has no reader
+        assert_eq!(
+            shared,
+            [
+                (
+                    "Speed",
+                    "Controller-scoped tag 'Speed' is written by program 'Fill' and read by program 'Cap'; \
+                     'Cap' runs before 'Fill' in task 'Main' and reads the value of the previous scan"
+                ),
+                (
+                    "Speed",
+                    "Controller-scoped tag 'Speed' is written by program 'Fill' and read by program 'Safety'; \
+                     they run in tasks 'Main' and 'Fast', which can interrupt each other"
+                ),
+                (
+                    "Mode",
+                    "Controller-scoped tag 'Mode' is written by program 'Cap' and read by program 'Fill'; \
+                     'Fill' runs after 'Cap' in task 'Main'"
+                ),
+            ]
+        );
+        assert!(report.rules().iter().all(|r| r.kind != RuleKind::CrossProgramSharedTag || r.severity == Severity::Info));
+
+        let config = RuleConfig {
+            cross_program_shared_tag: CrossProgramSharedTagConfig {
+                severity: "warning".to_string(),
+                ignore_patterns: vec!["Sp*".to_string()],
+                ..CrossProgramSharedTagConfig::default()
+            },
+            ..RuleConfig::default()
+        };
+        let report = RuleDetector::with_config(config).analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        let shared: Vec<_> = report.rules().iter().filter(|r| r.kind == RuleKind::CrossProgramSharedTag).collect();
+        assert_eq!(shared.len(), 1);
+        assert_eq!(shared[0].identifier, "Mode");
+        assert_eq!(shared[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_unreachable_pou() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <AddOnInstructionDefinitions>
+                    <AddOnInstructionDefinition Name="Valve"/>
+                    <AddOnInstructionDefinition Name="Filter"/>
+                    <AddOnInstructionDefinition Name="OldValve">
+                        <Routines>
+                            <Routine Name="Logic" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0"><Text>Filter(F1);</Text></Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </AddOnInstructionDefinition>
+                </AddOnInstructionDefinitions>
+                <Tasks>
+                    <Task Name="Main" Type="CONTINUOUS">
+                        <ScheduledPrograms>
+                            <ScheduledProgram Name="Line"/>
+                        </ScheduledPrograms>
+                    </Task>
+                </Tasks>
+                <Programs>
+                    <Program Name="Line">
+                        <Routines>
+                            <Routine Name="Main" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0"><Text>Valve(V1);</Text></Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let report = RuleDetector::new().analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        let unreachable: Vec<_> = report
+            .rules()
+            .iter()
+            .filter(|r| r.kind == RuleKind::UnreachablePou)
+            .map(|r| (r.location.as_str(), r.message.as_str()))
+            .collect();
+        // OldValve is never called and reported as an unused AOI instead
+        assert_eq!(
+            unreachable,
+            [(
+                "AOI:Filter",
+                "POU 'Filter' is only called from unreachable code and has no call path from any entry point"
+            )]
+        );
+    }
+
+    #[test]
+    fn test_protected_routines_are_not_empty() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <AddOnInstructionDefinitions>
+                    <EncodedData EncodedType="AddOnInstructionDefinition" Name="Vendor_PID"/>
+                </AddOnInstructionDefinitions>
+                <Programs>
+                    <Program Name="Line">
+                        <Routines>
+                            <Routine Name="Secret" Type="RLL" SourceKey="[all]" SourceProtectionType="Key"/>
+                            <Routine Name="Stub" Type="RLL"/>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let report = RuleDetector::new().analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        let empty: Vec<_> = report
+            .rules()
+            .iter()
+            .filter(|r| r.kind == RuleKind::EmptyBlock)
+            .map(|r| r.identifier.as_str())
+            .collect();
+        assert_eq!(empty, ["Stub"]);
+        assert_eq!(report.protected, ["Line/Secret", "AOI:Vendor_PID"]);
+    }
+
+    #[test]
+    fn test_plcopen_protected_pou() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="LibBlock" pouType="functionBlock">
+      <addData><data name="http://www.3s-software.com/plcopenxml/protected" handleUnknown="discard"/></addData>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+
+        let report = RuleDetector::new().analyze_str(xml, FileFormat::PlcOpen).expect("Should analyze");
+        assert!(report.rules().iter().all(|r| r.kind != RuleKind::EmptyBlock));
+        assert_eq!(report.protected, ["LibBlock"]);
+    }
+
+    /// Reports controller tags named `Temp*`, as an in-house rule would.
+    struct TempTagDetector;
+
+    impl Detector for TempTagDetector {
+        fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+            let Some(tags) = project.l5x_controller.as_ref().and_then(|c| c.tags.as_ref()) else {
+                return Vec::new();
+            };
+            tags.tag
+                .iter()
+                .filter(|tag| tag.name.starts_with("Temp"))
+                .map(|tag| {
+                    let kind = RuleKind::Custom { code: "Z0001", name: "temp-tag" };
+                    Rule::new(kind, Severity::Info, "Controller", &tag.name, "Temporary tag left in the project")
+                })
+                .collect()
+        }
+    }
+
+    #[test]
+    fn test_registered_detector() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <Tags>
+                    <Tag Name="Temp1" TagType="Base" DataType="DINT"/>
+                    <Tag Name="Temp2" TagType="Base" DataType="DINT"/>
+                </Tags>
+            </Controller>
+        </RSLogix5000Content>"#;
+        let custom = |report: &Report| -> Vec<(String, Severity)> {
+            report
+                .rules()
+                .iter()
+                .filter(|r| r.id() == "Z0001")
+                .map(|r| (r.identifier.clone(), r.severity))
+                .collect()
+        };
+
+        // Runs after the built-in rules
+        let detector = RuleDetector::new().register(Box::new(TempTagDetector));
+        let report = detector.analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        assert_eq!(custom(&report), [("Temp1".to_string(), Severity::Info), ("Temp2".to_string(), Severity::Info)]);
+        assert_eq!(report.rules.last().map(|r| r.name()), Some("temp-tag"));
+
+        // Escalation thresholds apply by code or name
+        let mut config = RuleConfig::default();
+        config.escalation.thresholds.insert("temp-tag".to_string(), 1);
+        let detector = RuleDetector::with_config(config.clone()).register(Box::new(TempTagDetector));
+        let report = detector.analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        assert!(custom(&report).iter().all(|(_, severity)| *severity == Severity::Warning));
+
+        // Disabled rules are dropped
+        config.custom_rules.disabled = vec!["Z0001".to_string()];
+        let detector = RuleDetector::with_config(config).register(Box::new(TempTagDetector));
+        let report = detector.analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        assert!(custom(&report).is_empty());
+    }
+
+    #[test]
+    fn test_plcopen_undefined_call_allowlist() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Main" pouType="program">
+                        <interface>
+                            <localVars>
+                                <variable name="x"><type><INT/></type></variable>
+                            </localVars>
+                        </interface>
+                        <body><ST><![CDATA[x := LibScale(x) + Scael(x);]]></ST></body>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        let mut config = RuleConfig::default();
+        config.undefined_tags.allow_calls = vec!["Lib*".to_string()];
+        let report = RuleDetector::with_config(config)
+            .analyze_str(xml, FileFormat::PlcOpen)
+            .expect("Should analyze");
+        let undefined: Vec<_> = report.rules().iter().filter(|r| r.kind == RuleKind::UndefinedTag).collect();
+        assert_eq!(undefined.len(), 1);
+        assert_eq!(undefined[0].identifier, "Scael");
+        assert!(undefined[0].message.contains("is called but not defined"));
+    }
+
+    #[test]
+    fn test_plcopen_unused_method_parameter() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Valve" pouType="functionBlock">
+                        <body><ST><![CDATA[FUNCTION_BLOCK Valve
+    VAR isOpen : BOOL; END_VAR
+    METHOD Open
+        VAR_INPUT force : BOOL; reason : INT; END_VAR
+        isOpen := force;
+    END_METHOD
+END_FUNCTION_BLOCK]]></ST></body>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        let report = RuleDetector::new().analyze_str(xml, FileFormat::PlcOpen).expect("Should analyze");
+        let unused: Vec<_> = report.rules().iter().filter(|r| r.kind == RuleKind::UnusedMethodParameter).collect();
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].identifier, "Open.reason");
+        assert_eq!(unused[0].location, "Valve");
+    }
+
+    #[test]
+    fn test_tag_names_match_case_insensitively() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Line">
+                <Tags>
+                    <Tag Name="Motor" DataType="BOOL"/>
+                    <Tag Name="START_PB" DataType="BOOL"/>
+                </Tags>
+                <Programs>
+                    <Program Name="MainProgram">
+                        <Routines>
+                            <Routine Name="MainRoutine" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0">
+                                        <Text>XIC(Start_PB)OTE(motor);</Text>
+                                    </Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+        let findings = |case_sensitive: bool| {
+            let mut config = RuleConfig::default();
+            config.general.case_sensitive = case_sensitive;
+            let report = RuleDetector::with_config(config).analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+            let mut found: Vec<_> = report
+                .rules
+                .iter()
+                .filter(|r| matches!(r.kind, RuleKind::UnusedTag | RuleKind::UndefinedTag))
+                .map(|r| (r.kind.code().to_string(), r.identifier.clone()))
+                .collect();
+            found.sort();
+            found
+        };
+
+        assert!(findings(false).is_empty(), "{:?}", findings(false));
+        let code = |c: &str, name: &str| (c.to_string(), name.to_string());
+        assert_eq!(
+            findings(true),
+            [code("S0001", "Motor"), code("S0001", "START_PB"), code("S0002", "Start_PB"), code("S0002", "motor")]
+        );
+    }
+
+    #[test]
+    fn test_plcopen_variable_names_match_case_insensitively() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Scale" pouType="function">
+                        <interface>
+                            <returnType><INT/></returnType>
+                            <inputVars><variable name="raw"><type><INT/></type></variable></inputVars>
+                        </interface>
+                        <body><ST><![CDATA[Scale := raw;]]></ST></body>
+                    </pou>
+                    <pou name="Main" pouType="program">
+                        <interface>
+                            <localVars>
+                                <variable name="Level"><type><INT/></type></variable>
+                            </localVars>
+                        </interface>
+                        <body><ST><![CDATA[LEVEL := SCALE(level);]]></ST></body>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+        let undefined = |case_sensitive: bool| {
+            let mut config = RuleConfig::default();
+            config.general.case_sensitive = case_sensitive;
+            let report =
+                RuleDetector::with_config(config).analyze_str(xml, FileFormat::PlcOpen).expect("Should analyze");
+            let mut found: Vec<_> = report
+                .rules
+                .iter()
+                .filter(|r| matches!(r.kind, RuleKind::UnusedTag | RuleKind::UndefinedTag))
+                .map(|r| r.identifier.clone())
+                .collect();
+            found.sort();
+            found
+        };
+
+        assert!(undefined(false).is_empty(), "{:?}", undefined(false));
+        assert_eq!(undefined(true), ["LEVEL", "Level", "SCALE", "level"]);
+    }
+
+    #[test]
+    fn test_analyze_str() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <Tags>
+                    <Tag Name="Unused" DataType="BOOL"/>
+                </Tags>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let detector = RuleDetector::new();
+        let report = detector.analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+
+        let rule = report.rules().iter().find(|r| r.id() == "S0001").expect("Should find unused tag");
+        assert_eq!(rule.severity(), Severity::Info);
+        assert_eq!(rule.location(), "Controller");
+        assert_eq!(rule.identifier(), "Unused");
+        assert!(rule.message().contains("never used"));
+    }
+
+    #[test]
+    fn test_stats_of_both_formats() {
+        let l5x = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <Programs><Program Name="Main"/></Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+        let plcopen = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types><pous><pou name="Main" pouType="program"/></pous></types>
+        </project>"#;
+
+        let detector = RuleDetector::new();
+        let stats = detector.stats(&LoadedProject::from_str(l5x, None).unwrap()).expect("Should get stats");
+        assert!(matches!(stats, ProjectStats::L5x(ref s) if s.programs == 1));
+        let stats = detector.stats(&LoadedProject::from_str(plcopen, None).unwrap()).expect("Should get stats");
+        assert!(matches!(stats, ProjectStats::PlcOpen(ref s) if s.programs == 1 && s.empty_pous == 1));
+
+        let json = serde_json::to_value(&stats).unwrap();
+        assert_eq!(json["format"], "plcopen");
+        assert_eq!(json["pous"], 1);
+    }
+
+    #[test]
+    fn test_stats_code_size() {
+        let l5x = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <Programs><Program Name="Main"><Routines>
+                    <Routine Name="Ladder" Type="RLL"><RLLContent>
+                        <Rung Number="0" Type="N"><Text><![CDATA[XIC(Start)OTE(Run);]]></Text></Rung>
+                        <Rung Number="1" Type="N"><Text><![CDATA[[XIC(Run),XIC(Jog)]MOV(1,Speed);]]></Text></Rung>
+                    </RLLContent></Routine>
+                    <Routine Name="Logic" Type="ST"><STContent>
+                        <Line Number="0"><![CDATA[// Count]]></Line>
+                        <Line Number="1"><![CDATA[IF Run THEN]]></Line>
+                        <Line Number="2"><![CDATA[    Count := Count + 1;]]></Line>
+                        <Line Number="3"><![CDATA[END_IF;]]></Line>
+                    </STContent></Routine>
+                </Routines></Program></Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let stats = RuleDetector::new().stats(&LoadedProject::from_str(l5x, None).unwrap()).expect("Should get stats");
+        let sizes: Vec<_> = stats.sizes().pous.iter().map(|p| (p.name.as_str(), p.language.as_str(), p.size)).collect();
+        assert_eq!(
+            sizes,
+            [
+                ("Main/Ladder", "RLL", CodeSize { lines: 2, code_lines: 2, statements: 5 }),
+                ("Main/Logic", "ST", CodeSize { lines: 4, code_lines: 3, statements: 2 }),
+            ]
+        );
+        assert_eq!(stats.sizes().total().statements, 7);
+    }
+
+    #[test]
+    fn test_analyze_file_timeout() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="TestController">
+                <Tags><Tag Name="Spare" TagType="Base" DataType="BOOL"/></Tags>
+            </Controller>
+        </RSLogix5000Content>"#;
+        let dir = std::env::temp_dir().join(format!("plceye_timeout_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Line.L5X");
+        std::fs::write(&path, xml).unwrap();
+
+        let report = RuleDetector::new().with_timeout(Duration::from_secs(60)).analyze_file(&path).expect("Should analyze");
+        assert!(report.rules.iter().any(|r| r.kind == RuleKind::UnusedTag));
+
+        // Large enough that the worker cannot finish before the timeout is checked
+        let tags: String = (0..20_000)
+            .map(|i| format!(r#"<Tag Name="Spare{}" TagType="Base" DataType="BOOL"/>"#, i))
+            .collect();
+        std::fs::write(&path, xml.replace(r#"<Tag Name="Spare" TagType="Base" DataType="BOOL"/>"#, &tags)).unwrap();
+        let result = RuleDetector::new().with_timeout(Duration::from_nanos(1)).analyze_file(&path);
+        assert!(matches!(result, Err(Error::Timeout { ref path, .. }) if path.ends_with("Line.L5X")));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_analyze_acd_export() {
+        // An "ACD" holding L5X text, exported by copying it
+        let dir = std::env::temp_dir().join(format!("plceye_acd_test_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("Line.ACD");
+        std::fs::write(
+            &path,
+            r#"<?xml version="1.0"?>
+            <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+                <Controller Name="TestController">
+                    <Tags><Tag Name="Spare" TagType="Base" DataType="BOOL"/></Tags>
+                </Controller>
+            </RSLogix5000Content>"#,
+        )
+        .unwrap();
+
+        let result = RuleDetector::new().analyze_file(&path);
+        assert!(matches!(result, Err(Error::AcdExport { kind: AcdExportErrorKind::NoCommand, .. })));
+
+        let mut config = RuleConfig::default();
+        config.general.acd_export_cmd = Some("cp {input} {output}".to_string());
+        let report = RuleDetector::with_config(config.clone()).analyze_file(&path).expect("Should analyze");
+        assert!(report.rules.iter().any(|r| r.kind == RuleKind::UnusedTag));
+        assert_eq!(report.source_file.as_deref(), Some(path.display().to_string().as_str()));
+
+        config.general.acd_export_cmd = Some("false".to_string());
+        let result = RuleDetector::with_config(config).analyze_file(&path);
+        assert!(matches!(
+            result,
+            Err(Error::AcdExport { kind: AcdExportErrorKind::Failed { status: Some(1), .. }, .. })
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_analyze_bundle() {
+        let member = |xml: &str| {
+            LoadedProject::from_str(
+                &format!(r#"<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">{}</RSLogix5000Content>"#, xml),
+                None,
+            )
+            .unwrap()
+            .l5x_controller
+            .unwrap()
+        };
+        let filling = member(
+            r#"<Controller Use="Context" Name="Line"><Programs><Program Use="Target" Name="Filling"><Routines>
+                <Routine Name="Main" Type="RLL"><RLLContent>
+                    <Rung Number="0" Type="N"><Text><![CDATA[XIC(LineSpeed)OTE(Missing);]]></Text></Rung>
+                </RLLContent></Routine>
+            </Routines></Program></Programs></Controller>"#,
+        );
+        let capping = member(
+            r#"<Controller Use="Context" Name="Line"><Tags>
+                <Tag Name="LineSpeed" TagType="Base" DataType="BOOL"/>
+                <Tag Name="Spare" TagType="Base" DataType="BOOL"/>
+            </Tags></Controller>"#,
+        );
+        let (controller, bundle) =
+            Bundle::merge(vec![("Filling.L5X".to_string(), filling), ("Capping.L5X".to_string(), capping)]).unwrap();
+        let project = LoadedProject {
+            l5x_controller: Some(controller),
+            plcopen_project: None,
+            format: FileFormat::L5x,
+            source_path: None,
+            bundle: Some(bundle),
+            export: None,
+        };
+
+        let report = RuleDetector::new().analyze(&project).expect("Should analyze");
+        let undefined: Vec<_> = report.rules.iter().filter(|r| r.kind == RuleKind::UndefinedTag).collect();
+        assert_eq!(undefined.len(), 1);
+        assert_eq!(undefined[0].identifier, "Missing");
+
+        let spare = report.rules.iter().find(|r| r.identifier == "Spare").unwrap();
+        assert_eq!(spare.file(), Some("Capping.L5X"));
+        assert!(spare.to_string().ends_with(" in Capping.L5X"));
+    }
+
+    #[test]
+    fn test_analyze_data_type_export() {
+        let xml = r#"<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00" TargetType="DataType">
+            <Controller Use="Context" Name="Line">
+                <DataTypes Use="Context">
+                    <DataType Use="Target" Name="Recipe" Family="NoFamily" Class="User"/>
+                </DataTypes>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let report = RuleDetector::new().analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        assert_eq!(report.export, Some(ExportKind::DataType));
+        // The exported data type is the target of the file, not an unused type
+        assert!(!report.rules.iter().any(|r| r.kind == RuleKind::UnusedDataType));
+
+        let whole = xml.replace(r#" TargetType="DataType""#, "");
+        let report = RuleDetector::new().analyze_str(&whole, FileFormat::L5x).expect("Should analyze");
+        assert_eq!(report.export, None);
+        assert!(report.rules.iter().any(|r| r.kind == RuleKind::UnusedDataType));
+
+        // Registered detectors are filtered like the built-in rules
+        let detector = RuleDetector::new().register(Box::new(UnusedDataTypes(UnusedDataTypesConfig::default())));
+        let report = detector.analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        assert!(!report.rules.iter().any(|r| r.kind == RuleKind::UnusedDataType));
+        let report = detector.analyze_str(&whole, FileFormat::L5x).expect("Should analyze");
+        assert_eq!(report.rules.iter().filter(|r| r.kind == RuleKind::UnusedDataType).count(), 2);
+    }
+
+    /// The built-in unused data type rule, registered as an extra detector.
+    struct UnusedDataTypes(UnusedDataTypesConfig);
+
+    impl Detector for UnusedDataTypes {
+        fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+            Detector::detect(&UnusedDataTypesDetector::new(&self.0), project)
+        }
+    }
+
+    #[test]
+    fn test_builtin_detector_trait() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Scale" pouType="function">
+                        <body><ST><![CDATA[IF a THEN IF b THEN IF c THEN x := 1; END_IF; END_IF; END_IF;]]></ST></body>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        let project = LoadedProject::from_str_with_format(xml, FileFormat::PlcOpen).expect("Should parse");
+        let mut config = RuleConfig::default();
+        config.nesting.max_depth = 2;
+        let rules = Detector::detect(&NestingDetector::new(&config.nesting), &project);
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].kind, RuleKind::DeepNesting);
+        assert_eq!(rules[0].location, "Scale");
+
+        // The same finding as from the shared analysis
+        let report = RuleDetector::with_config(config.clone()).analyze(&project).expect("Should analyze");
+        let nesting: Vec<_> = report.rules().iter().filter(|r| r.kind == RuleKind::DeepNesting).collect();
+        assert_eq!(nesting.len(), 1);
+        assert_eq!(nesting[0].message, rules[0].message);
+
+        // L5X-only rules find nothing in a PLCopen project
+        assert!(Detector::detect(&UnusedAoisDetector::new(&config.unused_aois), &project).is_empty());
+    }
+
+    #[test]
+    fn test_analyze_plcopen_bundle() {
+        let member = |pous: &str| {
+            plcopen::from_str(&format!(
+                r#"<project xmlns="http://www.plcopen.org/xml/tc6_0200"><contentHeader name="Line"/><types><pous>{}</pous></types></project>"#,
+                pous
+            ))
+            .unwrap()
+        };
+        let library = member(
+            r#"<pou name="Scale" pouType="function">
+                <interface><inputVars><variable name="raw"><type><INT/></type></variable></inputVars></interface>
+                <body><ST><![CDATA[Scale := raw * 2;]]></ST></body>
+            </pou>"#,
+        );
+        let project = member(
+            r#"<pou name="Main" pouType="program">
+                <interface><localVars>
+                    <variable name="x"><type><INT/></type></variable>
+                    <variable name="spare"><type><INT/></type></variable>
+                </localVars></interface>
+                <body><ST><![CDATA[x := Scale(x) + Clamp(x);]]></ST></body>
+            </pou>"#,
+        );
+        let (merged, bundle) =
+            Bundle::merge_plcopen(vec![("lib.xml".to_string(), library), ("project.xml".to_string(), project)]).unwrap();
+        let project = LoadedProject {
+            l5x_controller: None,
+            plcopen_project: Some(merged),
+            format: FileFormat::PlcOpen,
+            source_path: None,
+            bundle: Some(bundle),
+            export: None,
+        };
+
+        let report = RuleDetector::new().analyze(&project).expect("Should analyze");
+        // Scale is defined in the library file
+        let undefined: Vec<_> = report.rules.iter().filter(|r| r.kind == RuleKind::UndefinedTag).collect();
+        assert_eq!(undefined.len(), 1);
+        assert_eq!(undefined[0].identifier, "Clamp");
+
+        let spare = report.rules.iter().find(|r| r.identifier == "spare").unwrap();
+        assert_eq!(spare.file(), Some("project.xml"));
+        assert!(report.duplicates.is_empty());
+    }
+
+    #[test]
+    fn test_escalation_by_finding_count() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Line">
+                <Tags>
+                    <Tag Name="Spare1" DataType="BOOL"/>
+                    <Tag Name="Spare2" DataType="BOOL"/>
+                    <Tag Name="Spare3" DataType="BOOL"/>
+                </Tags>
+            </Controller>
+        </RSLogix5000Content>"#;
+        let severities = |threshold: usize| {
+            let mut config = RuleConfig::default();
+            config.escalation.thresholds.insert("unused-tag".to_string(), threshold);
+            let report = RuleDetector::with_config(config).analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+            report
+                .rules
+                .iter()
+                .filter(|r| r.kind == RuleKind::UnusedTag)
+                .map(|r| r.severity)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(severities(3), [Severity::Info; 3]);
+        assert_eq!(severities(2), [Severity::Warning; 3]);
+    }
+
+    #[test]
+    fn test_message_templates() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Line">
+                <Tags>
+                    <Tag Name="Spare1" DataType="BOOL"/>
+                </Tags>
+            </Controller>
+        </RSLogix5000Content>"#;
+        let mut config = RuleConfig::default();
+        config
+            .messages
+            .insert("unused-tag".to_string(), "[{code}] Tag '{identifier}' wird nicht verwendet ({severity})".to_string());
+        let report = RuleDetector::with_config(config).analyze_str(xml, FileFormat::L5x).expect("Should analyze");
+        let spare = report.rules.iter().find(|r| r.kind == RuleKind::UnusedTag).expect("Should find unused tag");
+        assert_eq!(spare.message(), "[S0001] Tag 'Spare1' wird nicht verwendet (info)");
+    }
 }
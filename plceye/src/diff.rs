@@ -0,0 +1,635 @@
+//! Semantic diff of two projects.
+//!
+//! Both projects are reduced to a format-neutral [`ProjectSnapshot`] of
+//! tags, POUs, data types and routine bodies, which are then compared by
+//! name. RLL routines are compared rung by rung; ST and IL bodies line by
+//! line. Graphical bodies (FBD, LD, SFC) are only reported as changed.
+//!
+//! Tags are keyed by their scope (`Controller/Speed`, `Program:Main/Count`,
+//! `Main/Count` for PLCopen POU variables), routines by their container
+//! (`Main/Logic` for L5X, the POU name for PLCopen).
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+use l5x::{Controller, RoutineContent, UDIDefinitionContent};
+use serde::Serialize;
+
+use crate::analysis::{
+    extract_rung_text, extract_st_source, extract_type_name, variables_from_controller,
+    variables_from_plcopen,
+};
+use crate::loader::LoadedProject;
+
+/// Kind of project item.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ItemKind {
+    /// Data type (UDT, struct, enum)
+    DataType,
+    /// Program, AOI or PLCopen POU
+    Pou,
+    /// Routine or POU body
+    Routine,
+    /// Tag or variable
+    Tag,
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemKind::DataType => write!(f, "datatype"),
+            ItemKind::Pou => write!(f, "pou"),
+            ItemKind::Routine => write!(f, "routine"),
+            ItemKind::Tag => write!(f, "tag"),
+        }
+    }
+}
+
+/// How an item changed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ChangeKind {
+    /// Only in the new project
+    Added,
+    /// Only in the old project
+    Removed,
+    /// In both projects, with different content
+    Modified,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ChangeKind::Added => write!(f, "+"),
+            ChangeKind::Removed => write!(f, "-"),
+            ChangeKind::Modified => write!(f, "~"),
+        }
+    }
+}
+
+/// A changed project item.
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemChange {
+    /// Kind of item
+    pub kind: ItemKind,
+    /// How the item changed
+    pub change: ChangeKind,
+    /// Scoped item name (e.g., "Program:Main/Count", "Main/Logic")
+    pub name: String,
+    /// Human-readable details of a modification
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub details: Vec<String>,
+    /// Added, removed or modified rung numbers of an RLL routine
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub changed_rungs: Vec<u32>,
+}
+
+impl ItemChange {
+    fn new(kind: ItemKind, change: ChangeKind, name: &str) -> Self {
+        Self {
+            kind,
+            change,
+            name: name.to_string(),
+            details: Vec::new(),
+            changed_rungs: Vec::new(),
+        }
+    }
+}
+
+impl fmt::Display for ItemChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {}", self.change, self.kind, self.name)?;
+        if !self.changed_rungs.is_empty() {
+            let rungs: Vec<String> = self.changed_rungs.iter().map(|n| n.to_string()).collect();
+            write!(f, " (rungs {})", rungs.join(", "))?;
+        }
+        if !self.details.is_empty() {
+            write!(f, ": {}", self.details.join("; "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Differences between two projects.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ProjectDiff {
+    /// Changed items, ordered by kind and name
+    pub changes: Vec<ItemChange>,
+}
+
+impl ProjectDiff {
+    /// Check if the projects are equivalent.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Count the changes of an item kind.
+    pub fn count(&self, kind: ItemKind, change: ChangeKind) -> usize {
+        self.changes
+            .iter()
+            .filter(|c| c.kind == kind && c.change == change)
+            .count()
+    }
+}
+
+impl fmt::Display for ProjectDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "No differences.");
+        }
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        writeln!(f)?;
+        let kinds = [
+            (ItemKind::DataType, "Data types"),
+            (ItemKind::Pou, "POUs"),
+            (ItemKind::Routine, "Routines"),
+            (ItemKind::Tag, "Tags"),
+        ];
+        let mut lines = Vec::new();
+        for (kind, label) in kinds {
+            let counts = [
+                self.count(kind, ChangeKind::Added),
+                self.count(kind, ChangeKind::Removed),
+                self.count(kind, ChangeKind::Modified),
+            ];
+            if counts.iter().any(|&n| n > 0) {
+                lines.push(format!(
+                    "{:<11} {} added, {} removed, {} modified",
+                    format!("{}:", label),
+                    counts[0],
+                    counts[1],
+                    counts[2]
+                ));
+            }
+        }
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+/// Body of a routine, reduced for comparison.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoutineBody {
+    /// RLL rungs by rung number
+    Rungs(BTreeMap<u32, String>),
+    /// Textual source (ST, IL) split into trimmed lines
+    Text(Vec<String>),
+    /// Graphical body with its language and a content fingerprint
+    Graphical { language: String, fingerprint: String },
+}
+
+/// Format-neutral view of a project for comparison.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectSnapshot {
+    /// Data type name to member declarations ("Name : TYPE")
+    pub data_types: BTreeMap<String, Vec<String>>,
+    /// POU name to POU kind (e.g., "Program", "AOI", "functionBlock")
+    pub pous: BTreeMap<String, String>,
+    /// Routine name to body
+    pub routines: BTreeMap<String, RoutineBody>,
+    /// Scoped tag name to data type
+    pub tags: BTreeMap<String, String>,
+}
+
+impl ProjectSnapshot {
+    /// Build the snapshot of a loaded project.
+    pub fn from_project(project: &LoadedProject) -> Self {
+        if let Some(ref controller) = project.l5x_controller {
+            Self::from_controller(controller)
+        } else if let Some(ref plcopen) = project.plcopen_project {
+            Self::from_plcopen(plcopen)
+        } else {
+            Self::default()
+        }
+    }
+
+    /// Build the snapshot of an L5X controller.
+    pub fn from_controller(controller: &Controller) -> Self {
+        let mut snapshot = Self::default();
+
+        for variable in variables_from_controller(controller) {
+            snapshot
+                .tags
+                .insert(format!("{}/{}", variable.location, variable.name), variable.data_type);
+        }
+
+        if let Some(ref data_types) = controller.data_types {
+            for data_type in &data_types.data_type {
+                let members = data_type
+                    .members
+                    .iter()
+                    .flat_map(|m| &m.member)
+                    .filter(|m| m.hidden.as_deref() != Some("true"))
+                    .map(|m| match m.dimension.as_deref() {
+                        Some(dim) if dim != "0" => format!("{} : {}[{}]", m.name, m.data_type, dim),
+                        _ => format!("{} : {}", m.name, m.data_type),
+                    })
+                    .collect();
+                snapshot.data_types.insert(data_type.name.clone(), members);
+            }
+        }
+
+        if let Some(ref programs) = controller.programs {
+            for program in &programs.program {
+                snapshot.pous.insert(program.name.clone(), "Program".to_string());
+                for routine in program.routines.iter().flat_map(|r| &r.routine) {
+                    snapshot
+                        .routines
+                        .insert(format!("{}/{}", program.name, routine.name), l5x_body(routine));
+                }
+            }
+        }
+
+        if let Some(ref aois) = controller.add_on_instruction_definitions {
+            for aoi in &aois.add_on_instruction_definition {
+                snapshot.pous.insert(aoi.name.clone(), "AOI".to_string());
+                let location = format!("AOI:{}", aoi.name);
+                for content in &aoi.content {
+                    match content {
+                        UDIDefinitionContent::Parameters(params) => {
+                            for param in &params.parameter {
+                                snapshot.tags.insert(
+                                    format!("{}/{}", location, param.name),
+                                    param.data_type.clone().unwrap_or_default(),
+                                );
+                            }
+                        }
+                        UDIDefinitionContent::LocalTags(local_tags) => {
+                            for tag in &local_tags.local_tag {
+                                snapshot
+                                    .tags
+                                    .insert(format!("{}/{}", location, tag.name), tag.data_type.clone());
+                            }
+                        }
+                        UDIDefinitionContent::Routines(routines) => {
+                            for routine in &routines.routine {
+                                snapshot
+                                    .routines
+                                    .insert(format!("{}/{}", aoi.name, routine.name), l5x_body(routine));
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        snapshot
+    }
+
+    /// Build the snapshot of a PLCopen project.
+    pub fn from_plcopen(project: &plcopen::Project) -> Self {
+        let mut snapshot = Self::default();
+
+        for variable in variables_from_plcopen(project) {
+            snapshot
+                .tags
+                .insert(format!("{}/{}", variable.location, variable.name), variable.data_type);
+        }
+
+        let types = project.types.as_ref();
+        for data_type in types.and_then(|t| t.data_types.as_ref()).map(|d| d.data_type.as_slice()).unwrap_or_default() {
+            let members = data_type
+                .base_type
+                .as_deref()
+                .map(plcopen_members)
+                .unwrap_or_default();
+            snapshot.data_types.insert(data_type.name.clone(), members);
+        }
+
+        for pou in types.and_then(|t| t.pous.as_ref()).map(|p| p.pou.as_slice()).unwrap_or_default() {
+            snapshot.pous.insert(pou.name.clone(), pou.pou_type.clone());
+            for (index, body) in pou.body.iter().enumerate() {
+                let name = if index == 0 {
+                    pou.name.clone()
+                } else {
+                    format!("{}#{}", pou.name, index + 1)
+                };
+                snapshot.routines.insert(name, plcopen_body(body));
+            }
+        }
+
+        snapshot
+    }
+}
+
+/// Compare two loaded projects.
+pub fn diff_projects(old: &LoadedProject, new: &LoadedProject) -> ProjectDiff {
+    diff_snapshots(&ProjectSnapshot::from_project(old), &ProjectSnapshot::from_project(new))
+}
+
+/// Compare two project snapshots.
+pub fn diff_snapshots(old: &ProjectSnapshot, new: &ProjectSnapshot) -> ProjectDiff {
+    let mut changes = Vec::new();
+
+    diff_maps(ItemKind::DataType, &old.data_types, &new.data_types, &mut changes, |change, old, new| {
+        change.details = list_changes("member", old, new);
+    });
+    diff_maps(ItemKind::Pou, &old.pous, &new.pous, &mut changes, |change, old, new| {
+        change.details.push(format!("kind {} -> {}", old, new));
+    });
+    diff_maps(ItemKind::Routine, &old.routines, &new.routines, &mut changes, describe_routine_change);
+    diff_maps(ItemKind::Tag, &old.tags, &new.tags, &mut changes, |change, old, new| {
+        change.details.push(format!("type {} -> {}", old, new));
+    });
+
+    ProjectDiff { changes }
+}
+
+/// Compare two name-keyed maps, letting `describe` fill in the details of a modification.
+fn diff_maps<T: PartialEq>(
+    kind: ItemKind,
+    old: &BTreeMap<String, T>,
+    new: &BTreeMap<String, T>,
+    changes: &mut Vec<ItemChange>,
+    describe: impl Fn(&mut ItemChange, &T, &T),
+) {
+    let names: BTreeSet<&String> = old.keys().chain(new.keys()).collect();
+    for name in names {
+        match (old.get(name), new.get(name)) {
+            (Some(_), None) => changes.push(ItemChange::new(kind, ChangeKind::Removed, name)),
+            (None, Some(_)) => changes.push(ItemChange::new(kind, ChangeKind::Added, name)),
+            (Some(before), Some(after)) if before != after => {
+                let mut change = ItemChange::new(kind, ChangeKind::Modified, name);
+                describe(&mut change, before, after);
+                changes.push(change);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Describe how a routine body changed.
+fn describe_routine_change(change: &mut ItemChange, old: &RoutineBody, new: &RoutineBody) {
+    match (old, new) {
+        (RoutineBody::Rungs(before), RoutineBody::Rungs(after)) => {
+            let numbers: BTreeSet<u32> = before.keys().chain(after.keys()).copied().collect();
+            change.changed_rungs = numbers
+                .into_iter()
+                .filter(|n| before.get(n) != after.get(n))
+                .collect();
+            if before.len() != after.len() {
+                change.details.push(format!("{} -> {} rungs", before.len(), after.len()));
+            }
+        }
+        (RoutineBody::Text(before), RoutineBody::Text(after)) => {
+            let common = common_lines(before, after);
+            change.details.push(format!(
+                "{} line(s) added, {} line(s) removed",
+                after.len() - common,
+                before.len() - common
+            ));
+        }
+        (RoutineBody::Graphical { language, .. }, RoutineBody::Graphical { language: other, .. })
+            if language == other =>
+        {
+            change.details.push(format!("{} content changed", language));
+        }
+        _ => change
+            .details
+            .push(format!("language {} -> {}", body_language(old), body_language(new))),
+    }
+}
+
+/// List the entries added to and removed from a declaration list.
+fn list_changes(what: &str, old: &[String], new: &[String]) -> Vec<String> {
+    let mut details: Vec<String> = new
+        .iter()
+        .filter(|m| !old.contains(m))
+        .map(|m| format!("{} added: {}", what, m))
+        .collect();
+    details.extend(
+        old.iter()
+            .filter(|m| !new.contains(m))
+            .map(|m| format!("{} removed: {}", what, m)),
+    );
+    if details.is_empty() {
+        details.push(format!("{} order changed", what));
+    }
+    details
+}
+
+/// Number of lines in the longest common subsequence of two line lists.
+fn common_lines(old: &[String], new: &[String]) -> usize {
+    let mut row = vec![0usize; new.len() + 1];
+    for line in old {
+        let mut diagonal = 0;
+        for (j, other) in new.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if line == other { diagonal + 1 } else { above.max(row[j]) };
+            diagonal = above;
+        }
+    }
+    row[new.len()]
+}
+
+fn body_language(body: &RoutineBody) -> &str {
+    match body {
+        RoutineBody::Rungs(_) => "RLL",
+        RoutineBody::Text(_) => "text",
+        RoutineBody::Graphical { language, .. } => language,
+    }
+}
+
+/// Split source into trimmed, non-empty lines.
+fn text_lines(source: &str) -> Vec<String> {
+    source
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Reduce an L5X routine to its body.
+fn l5x_body(routine: &l5x::Routine) -> RoutineBody {
+    for content in &routine.content {
+        match content {
+            RoutineContent::RLLContent(rungs) => {
+                let rungs = rungs
+                    .rung
+                    .iter()
+                    .enumerate()
+                    .map(|(index, rung)| {
+                        let number = rung
+                            .number
+                            .as_ref()
+                            .and_then(|n| n.parse().ok())
+                            .unwrap_or(index as u32);
+                        (number, extract_rung_text(rung).unwrap_or_default())
+                    })
+                    .collect();
+                return RoutineBody::Rungs(rungs);
+            }
+            RoutineContent::STContent(st) => return RoutineBody::Text(text_lines(&extract_st_source(st))),
+            _ => {}
+        }
+    }
+    RoutineBody::Graphical {
+        language: routine.r#type.clone(),
+        fingerprint: format!("{:?}", routine.content),
+    }
+}
+
+/// Reduce a PLCopen POU body to its comparable form.
+fn plcopen_body(body: &plcopen::Body) -> RoutineBody {
+    if let Some(text) = body.st.as_ref().or(body.il.as_ref()).and_then(|t| t.text.as_deref()) {
+        return RoutineBody::Text(text_lines(text));
+    }
+    let (language, fingerprint) = if let Some(ref ld) = body.ld {
+        ("LD", format!("{:?}", ld))
+    } else if let Some(ref fbd) = body.fbd {
+        ("FBD", format!("{:?}", fbd))
+    } else if let Some(ref sfc) = body.sfc {
+        ("SFC", format!("{:?}", sfc))
+    } else {
+        ("empty", String::new())
+    };
+    RoutineBody::Graphical {
+        language: language.to_string(),
+        fingerprint,
+    }
+}
+
+/// Member declarations of a PLCopen data type.
+fn plcopen_members(data: &plcopen::Data) -> Vec<String> {
+    if let Some(ref fields) = data.r#struct {
+        return fields
+            .variable
+            .iter()
+            .map(|var| {
+                let type_name = var
+                    .r#type
+                    .as_deref()
+                    .and_then(extract_type_name)
+                    .unwrap_or_default();
+                format!("{} : {}", var.name, type_name)
+            })
+            .collect();
+    }
+    if let Some(ref enumeration) = data.r#enum {
+        return enumeration
+            .values
+            .iter()
+            .flat_map(|values| &values.value)
+            .map(|value| match value.value {
+                Some(ref v) => format!("{} := {}", value.name, v),
+                None => value.name.clone(),
+            })
+            .collect();
+    }
+    vec![format!("{:?}", data)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn l5x(rungs: &str, tags: &str, members: &str) -> LoadedProject {
+        let xml = format!(
+            r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <DataTypes>
+                    <DataType Name="Motor"><Members>{members}</Members></DataType>
+                </DataTypes>
+                <Tags>{tags}</Tags>
+                <Programs>
+                    <Program Name="Main">
+                        <Routines>
+                            <Routine Name="Logic" Type="RLL"><RLLContent>{rungs}</RLLContent></Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#
+        );
+        LoadedProject::from_str(&xml, None).expect("Should parse")
+    }
+
+    #[test]
+    fn test_identical_projects() {
+        let project = l5x(
+            r#"<Rung Number="0"><Text>XIC(A)OTE(B);</Text></Rung>"#,
+            r#"<Tag Name="A" TagType="Base" DataType="BOOL"/>"#,
+            r#"<Member Name="Speed" DataType="REAL"/>"#,
+        );
+        let diff = diff_projects(&project, &project);
+        assert!(diff.is_empty());
+        assert_eq!(diff.to_string(), "No differences.");
+    }
+
+    #[test]
+    fn test_l5x_changes() {
+        let old = l5x(
+            r#"<Rung Number="0"><Text>XIC(A)OTE(B);</Text></Rung>
+               <Rung Number="1"><Text>XIC(B)OTE(C);</Text></Rung>"#,
+            r#"<Tag Name="A" TagType="Base" DataType="BOOL"/>
+               <Tag Name="Count" TagType="Base" DataType="INT"/>"#,
+            r#"<Member Name="Speed" DataType="REAL"/>"#,
+        );
+        let new = l5x(
+            r#"<Rung Number="0"><Text>XIC(A)OTE(B);</Text></Rung>
+               <Rung Number="1"><Text>XIO(B)OTE(C);</Text></Rung>
+               <Rung Number="2"><Text>OTE(D);</Text></Rung>"#,
+            r#"<Tag Name="Count" TagType="Base" DataType="DINT"/>
+               <Tag Name="Speed" TagType="Base" DataType="REAL"/>"#,
+            r#"<Member Name="Speed" DataType="REAL"/><Member Name="Running" DataType="BOOL"/>"#,
+        );
+        let diff = diff_projects(&old, &new);
+
+        let routine = diff.changes.iter().find(|c| c.kind == ItemKind::Routine).unwrap();
+        assert_eq!(routine.name, "Main/Logic");
+        assert_eq!(routine.changed_rungs, [1, 2]);
+
+        assert_eq!(diff.count(ItemKind::Tag, ChangeKind::Added), 1);
+        assert_eq!(diff.count(ItemKind::Tag, ChangeKind::Removed), 1);
+        let count = diff.changes.iter().find(|c| c.name == "Controller/Count").unwrap();
+        assert_eq!(count.details, ["type INT -> DINT"]);
+
+        let motor = diff.changes.iter().find(|c| c.kind == ItemKind::DataType).unwrap();
+        assert_eq!(motor.details, ["member added: Running : BOOL"]);
+        assert!(diff.to_string().contains("~ routine Main/Logic (rungs 1, 2)"));
+    }
+
+    #[test]
+    fn test_plcopen_changes() {
+        let project = |body: &str, pou: &str| {
+            let xml = format!(
+                r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Main" pouType="program">
+      <interface><localVars>
+        <variable name="Count"><type><INT/></type></variable>
+      </localVars></interface>
+      <body><ST><![CDATA[{body}]]></ST></body>
+    </pou>
+    {pou}
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#
+            );
+            LoadedProject::from_str(&xml, None).expect("Should parse")
+        };
+        let old = project("Count := Count + 1;\nDone := FALSE;", "");
+        let new = project(
+            "Count := Count + 1;\nIF Count > 10 THEN\n  Done := TRUE;\nEND_IF;",
+            r#"<pou name="Helper" pouType="function"><interface><returnType><BOOL/></returnType></interface><body><ST><![CDATA[Helper := TRUE;]]></ST></body></pou>"#,
+        );
+        let diff = diff_projects(&old, &new);
+
+        let main = diff.changes.iter().find(|c| c.name == "Main" && c.kind == ItemKind::Routine).unwrap();
+        assert_eq!(main.change, ChangeKind::Modified);
+        assert_eq!(main.details, ["3 line(s) added, 1 line(s) removed"]);
+        assert_eq!(diff.count(ItemKind::Pou, ChangeKind::Added), 1);
+        assert_eq!(diff.count(ItemKind::Routine, ChangeKind::Added), 1);
+
+        let json = serde_json::to_string(&diff).unwrap();
+        assert!(json.contains(r#""kind":"pou","change":"added","name":"Helper""#));
+    }
+}
@@ -16,17 +16,70 @@ pub enum Error {
         source: std::io::Error,
     },
 
+    /// File content is in an encoding that can't be transcoded to UTF-8
+    #[error("Unsupported file encoding '{encoding}'")]
+    UnsupportedEncoding {
+        encoding: String,
+    },
+
     /// Failed to parse L5X file
     #[error("Failed to parse L5X file: {kind}")]
     L5xParse {
         kind: L5xParseErrorKind,
     },
 
+    /// Failed to load a project bundle (ZIP archive or folder of L5X files)
+    #[error("Failed to load bundle '{path}': {kind}")]
+    Bundle {
+        path: String,
+        kind: BundleErrorKind,
+    },
+
+    /// Failed to decompress a gzip-compressed project file
+    #[error("Failed to decompress '{path}': {kind}")]
+    Decompress {
+        path: String,
+        kind: DecompressErrorKind,
+    },
+
+    /// Failed to export an `.ACD` file to L5X with the configured command
+    #[error("Failed to export '{path}' to L5X: {kind}")]
+    AcdExport {
+        path: String,
+        kind: AcdExportErrorKind,
+    },
+
+    /// Analysis of a file took longer than the timeout
+    #[error("Analysis of '{path}' timed out after {} s", limit.as_secs_f64())]
+    Timeout {
+        path: String,
+        limit: std::time::Duration,
+    },
+
+    /// Unknown rule-set profile name given on the command line
+    #[error("Unknown profile '{name}' (available profiles: {})", crate::RuleConfig::PROFILES.join(", "))]
+    UnknownProfile {
+        name: String,
+    },
+
     /// Failed to parse config file
     #[error("Failed to parse config file: {kind}")]
     ConfigParse {
         kind: ConfigErrorKind,
     },
+
+    /// Failed to load a message catalog file
+    #[error("Failed to load message catalog '{path}': {kind}")]
+    MessageCatalog {
+        path: String,
+        kind: ConfigErrorKind,
+    },
+
+    /// Failed to apply fixes to a file
+    #[error("Failed to apply fixes: {kind}")]
+    Fix {
+        kind: FixErrorKind,
+    },
 }
 
 /// Kinds of L5X parse errors.
@@ -36,6 +89,8 @@ pub enum L5xParseErrorKind {
     XmlDeserialize,
     /// Missing required element
     MissingElement(&'static str),
+    /// Export without a `Controller` element, so there is nothing to analyze
+    NoController { target_type: String },
 }
 
 impl std::fmt::Display for L5xParseErrorKind {
@@ -43,23 +98,213 @@ impl std::fmt::Display for L5xParseErrorKind {
         match self {
             L5xParseErrorKind::XmlDeserialize => write!(f, "XML deserialization failed"),
             L5xParseErrorKind::MissingElement(elem) => write!(f, "missing required element: {}", elem),
+            L5xParseErrorKind::NoController { target_type } => write!(
+                f,
+                "{} export has no Controller element; re-export it with its context or export the whole controller",
+                target_type
+            ),
         }
     }
 }
 
 impl std::error::Error for L5xParseErrorKind {}
 
+/// Kinds of project bundle errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BundleErrorKind {
+    /// Not a readable ZIP archive
+    InvalidArchive,
+    /// Archive entry that is encrypted, ZIP64 or uses an unsupported compression method
+    UnsupportedEntry {
+        name: String,
+    },
+    /// Bundle without L5X or PLCopen members
+    NoMembers,
+    /// Member file that is not a parsable L5X export or PLCopen project
+    InvalidMember {
+        name: String,
+    },
+    /// Bundle with both L5X and PLCopen members
+    MixedFormats,
+    /// Archive entry larger than the size limit once decompressed
+    EntryTooLarge {
+        name: String,
+        limit: usize,
+    },
+    /// Members larger than the size limit together once decompressed
+    TooLarge {
+        limit: usize,
+    },
+}
+
+impl std::fmt::Display for BundleErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BundleErrorKind::InvalidArchive => write!(f, "not a valid ZIP archive"),
+            BundleErrorKind::UnsupportedEntry { name } => {
+                write!(f, "entry '{}' is encrypted or uses an unsupported format", name)
+            }
+            BundleErrorKind::NoMembers => write!(f, "no L5X or PLCopen files found"),
+            BundleErrorKind::InvalidMember { name } => write!(f, "'{}' is not a valid L5X export or PLCopen project", name),
+            BundleErrorKind::MixedFormats => write!(f, "L5X and PLCopen files cannot be analyzed as one project"),
+            BundleErrorKind::EntryTooLarge { name, limit } => {
+                write!(f, "entry '{}' is larger than {} bytes decompressed", name, limit)
+            }
+            BundleErrorKind::TooLarge { limit } => write!(f, "members are larger than {} bytes in total", limit),
+        }
+    }
+}
+
+impl std::error::Error for BundleErrorKind {}
+
+/// Kinds of decompression errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecompressErrorKind {
+    /// Corrupt or truncated compressed data
+    InvalidStream,
+    /// Decompressed content larger than the size limit
+    TooLarge {
+        limit: usize,
+    },
+}
+
+impl std::fmt::Display for DecompressErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DecompressErrorKind::InvalidStream => write!(f, "not a valid gzip stream"),
+            DecompressErrorKind::TooLarge { limit } => write!(f, "content is larger than {} bytes decompressed", limit),
+        }
+    }
+}
+
+impl std::error::Error for DecompressErrorKind {}
+
+/// Kinds of ACD export errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AcdExportErrorKind {
+    /// No `acd_export_cmd` is configured
+    NoCommand,
+    /// The temporary folder for the export could not be created
+    TempDir {
+        message: String,
+    },
+    /// The export command could not be started
+    Spawn {
+        program: String,
+        message: String,
+    },
+    /// The export command exited with a failure status
+    Failed {
+        status: Option<i32>,
+        stderr: String,
+    },
+    /// The export command succeeded but wrote no L5X file
+    NoOutput {
+        output: String,
+    },
+}
+
+impl std::fmt::Display for AcdExportErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AcdExportErrorKind::NoCommand => write!(
+                f,
+                "ACD files can't be read directly; export the project to L5X or set [general] acd_export_cmd"
+            ),
+            AcdExportErrorKind::TempDir { message } => write!(f, "cannot create a temporary folder: {}", message),
+            AcdExportErrorKind::Spawn { program, message } => {
+                write!(f, "cannot run export command '{}': {}", program, message)
+            }
+            AcdExportErrorKind::Failed { status, stderr } => {
+                match status {
+                    Some(code) => write!(f, "export command exited with status {}", code)?,
+                    None => write!(f, "export command was terminated")?,
+                }
+                if !stderr.is_empty() {
+                    write!(f, ": {}", stderr)?;
+                }
+                Ok(())
+            }
+            AcdExportErrorKind::NoOutput { output } => {
+                write!(f, "export command did not write '{}'", output)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AcdExportErrorKind {}
+
+/// Kinds of fix errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FixErrorKind {
+    /// File content that can't be read as XML
+    InvalidXml,
+    /// Fixed content that doesn't parse to the expected project
+    ValidationFailed,
+    /// Fixed content that references a tag it no longer defines
+    UnresolvedReference(String),
+}
+
+impl std::fmt::Display for FixErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FixErrorKind::InvalidXml => write!(f, "file is not valid XML"),
+            FixErrorKind::ValidationFailed => {
+                write!(f, "fixed file does not match the expected project; nothing was written")
+            }
+            FixErrorKind::UnresolvedReference(name) => {
+                write!(f, "fixed file references tag '{}' it no longer defines; nothing was written", name)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FixErrorKind {}
+
 /// Kinds of configuration errors.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ConfigErrorKind {
     /// TOML syntax error
     TomlSyntax,
+    /// Invalid regular expression in a naming pattern
+    InvalidPattern {
+        option: String,
+        pattern: String,
+    },
+    /// Unknown rule-set profile name
+    UnknownProfile {
+        name: String,
+    },
+    /// Key that is not a known setting or table
+    UnknownKey {
+        key: String,
+        line: usize,
+    },
+    /// Value of the wrong type or out of range
+    InvalidValue {
+        key: String,
+        line: Option<usize>,
+        message: String,
+    },
 }
 
 impl std::fmt::Display for ConfigErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ConfigErrorKind::TomlSyntax => write!(f, "TOML syntax error"),
+            ConfigErrorKind::InvalidPattern { option, pattern } => {
+                write!(f, "invalid regex pattern '{}' for naming.{}", pattern, option)
+            }
+            ConfigErrorKind::UnknownProfile { name } => {
+                write!(f, "unknown profile '{}' (expected {})", name, crate::RuleConfig::PROFILES.join(", "))
+            }
+            ConfigErrorKind::UnknownKey { key, line } => write!(f, "unknown key '{}' at line {}", key, line),
+            ConfigErrorKind::InvalidValue { key, line: Some(line), message } => {
+                write!(f, "invalid value for '{}' at line {}: {}", key, line, message)
+            }
+            ConfigErrorKind::InvalidValue { key, line: None, message } => {
+                write!(f, "invalid value for '{}': {}", key, message)
+            }
         }
     }
 }
@@ -0,0 +1,424 @@
+//! Applying fix hints to project files.
+//!
+//! Fixes are applied as edits of the original file text, so everything the
+//! fixes don't touch (formatting, comments, CDATA sections, elements the
+//! model doesn't know) stays as it was. The edited file is parsed again and
+//! must equal the original project with the fixed items removed, and must
+//! not reference any tag the original didn't leave undefined; otherwise
+//! nothing is written.
+//!
+//! Only removal of unused L5X tags is applied so far. Other fix hints are
+//! counted as not applicable.
+
+use std::collections::HashSet;
+
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+
+use crate::analysis::{analyze_controller, StUnits, TagUsage};
+use crate::config::UndefinedTagsConfig;
+use crate::error::{Error, FixErrorKind};
+use crate::report::{Report, Rule, RuleKind};
+use crate::rules::UndefinedTagsDetector;
+use crate::Result;
+
+/// File content with fixes applied.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixResult {
+    /// Content with the fixes applied
+    pub content: String,
+    /// Fixes that were applied, in file order
+    pub applied: Vec<AppliedFix>,
+    /// Findings that have no fix hint or whose fix can't be applied yet
+    pub unfixed: usize,
+}
+
+/// A fix applied to a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AppliedFix {
+    /// Description of the fix hint
+    pub description: String,
+    /// Line of the removed text in the original file, from 1
+    pub line: usize,
+    /// Text removed from the original file
+    pub removed: String,
+}
+
+/// Apply the fix hints of `rules` to the L5X `content`.
+///
+/// Unused tags with a fix hint are removed from the controller or program
+/// `Tags` they are declared in, together with the whitespace of their lines.
+/// Tags that RLL, ST or AOI logic names are kept, and so are all tags when
+/// some logic can't be read. Fails with `FixErrorKind::ValidationFailed`
+/// when the result doesn't parse to the original project minus those tags,
+/// with `UnresolvedReference` when the result references a tag that is no
+/// longer defined, and with `InvalidXml` when the content can't be read.
+pub fn apply_fixes<'a>(content: &str, rules: impl IntoIterator<Item = &'a Rule>) -> Result<FixResult> {
+    let fix_error = |kind| Error::Fix { kind };
+
+    let mut tags: HashSet<(String, String)> = HashSet::new();
+    let mut unfixed = 0;
+    for rule in rules {
+        match rule.fix {
+            Some(_) if rule.kind == RuleKind::UnusedTag => {
+                tags.insert((rule.location.clone(), rule.identifier.clone()));
+            }
+            _ => unfixed += 1,
+        }
+    }
+    if tags.is_empty() {
+        return Ok(FixResult {
+            content: content.to_string(),
+            applied: Vec::new(),
+            unfixed,
+        });
+    }
+
+    let original: l5x::Project =
+        quick_xml::de::from_str(content).map_err(|_| fix_error(FixErrorKind::InvalidXml))?;
+    let usage = original.controller.as_ref().map(tag_usage).unwrap_or_default();
+
+    let mut removals = Vec::new();
+    for (start, end, scope, name) in tag_elements(content).map_err(fix_error)? {
+        // A tag the logic names, or any tag if usage is unknown, stays
+        if !usage.is_complete() || usage.references(&name) {
+            continue;
+        }
+        if tags.remove(&(scope.clone(), name.clone())) {
+            let (start, end) = whole_lines(content, start, end);
+            removals.push((start, end, format!("remove unused tag '{}'", name), scope, name));
+        }
+    }
+    // Tags the file doesn't declare where the finding says, or that are
+    // still used, can't be fixed
+    unfixed += tags.len();
+    if removals.is_empty() {
+        return Ok(FixResult {
+            content: content.to_string(),
+            applied: Vec::new(),
+            unfixed,
+        });
+    }
+
+    let mut fixed = String::with_capacity(content.len());
+    let mut applied = Vec::new();
+    let mut last = 0;
+    for (start, end, description, _, _) in &removals {
+        fixed.push_str(&content[last..*start]);
+        last = *end;
+        applied.push(AppliedFix {
+            description: description.clone(),
+            line: content[..*start].matches('\n').count() + 1,
+            removed: content[*start..*end].to_string(),
+        });
+    }
+    fixed.push_str(&content[last..]);
+
+    // The result must be the original project without the removed tags
+    let removed: Vec<(&str, &str)> = removals.iter().map(|(_, _, _, scope, name)| (scope.as_str(), name.as_str())).collect();
+    let mut expected = original.clone();
+    if let Some(ref mut controller) = expected.controller {
+        remove_tags(controller, &removed);
+    }
+    let actual: l5x::Project =
+        quick_xml::de::from_str(&fixed).map_err(|_| fix_error(FixErrorKind::ValidationFailed))?;
+    if actual != expected {
+        return Err(fix_error(FixErrorKind::ValidationFailed));
+    }
+
+    // No reference may lose its tag
+    if let (Some(before), Some(after)) = (original.controller.as_ref(), actual.controller.as_ref()) {
+        if let Some(name) = new_unresolved(before, after, &removed) {
+            return Err(fix_error(FixErrorKind::UnresolvedReference(name)));
+        }
+    }
+
+    Ok(FixResult {
+        content: fixed,
+        applied,
+        unfixed,
+    })
+}
+
+/// Names the logic of a controller references.
+fn tag_usage(controller: &l5x::Controller) -> TagUsage {
+    TagUsage::from_controller(controller, &analyze_controller(controller), &StUnits::from_controller(controller))
+}
+
+/// A reference of the fixed controller `after` to a tag that neither it
+/// nor, for undefined tags, the original `before` defines.
+fn new_unresolved(before: &l5x::Controller, after: &l5x::Controller, removed: &[(&str, &str)]) -> Option<String> {
+    let undefined = |controller: &l5x::Controller| {
+        let mut report = Report::new();
+        UndefinedTagsDetector::new(&UndefinedTagsConfig::default()).detect(
+            controller,
+            &analyze_controller(controller),
+            &mut report,
+        );
+        report.rules.into_iter().map(|rule| rule.identifier).collect::<HashSet<String>>()
+    };
+    let was_undefined = undefined(before);
+    if let Some(name) = undefined(after).into_iter().find(|name| !was_undefined.contains(name)) {
+        return Some(name);
+    }
+
+    // Undefined tag detection covers RLL; the removed tags are also
+    // checked against ST and AOI logic
+    let usage = tag_usage(after);
+    let defined: HashSet<String> = after
+        .tags
+        .iter()
+        .flat_map(|tags| &tags.tag)
+        .chain(
+            after
+                .programs
+                .iter()
+                .flat_map(|programs| &programs.program)
+                .flat_map(|program| program.tags.iter().flat_map(|tags| &tags.tag)),
+        )
+        .map(|tag| tag.name.to_uppercase())
+        .collect();
+    removed
+        .iter()
+        .map(|(_, name)| *name)
+        .find(|name| usage.references(name) && !defined.contains(&name.to_uppercase()))
+        .map(str::to_string)
+}
+
+/// Byte ranges of the controller and program `Tag` elements, with their
+/// scope (`Controller` or `Program:Name`) and tag name.
+fn tag_elements(content: &str) -> std::result::Result<Vec<(usize, usize, String, String)>, FixErrorKind> {
+    let mut reader = Reader::from_str(content);
+    // Open elements: local name and `Name` attribute
+    let mut path: Vec<(String, Option<String>)> = Vec::new();
+    // Tag element being read: start offset, scope and name
+    let mut open_tag: Option<(usize, usize, String, String)> = None;
+    let mut tags = Vec::new();
+
+    loop {
+        let start = reader.buffer_position() as usize;
+        let event = reader.read_event().map_err(|_| FixErrorKind::InvalidXml)?;
+        let end = reader.buffer_position() as usize;
+        match event {
+            Event::Start(ref element) => {
+                let (local, name) = element_name(element)?;
+                if open_tag.is_none() && local == "Tag" {
+                    if let (Some(scope), Some(name)) = (tag_scope(&path), name.clone()) {
+                        open_tag = Some((start, path.len(), scope, name));
+                    }
+                }
+                path.push((local, name));
+            }
+            Event::Empty(ref element) => {
+                let (local, name) = element_name(element)?;
+                if open_tag.is_none() && local == "Tag" {
+                    if let (Some(scope), Some(name)) = (tag_scope(&path), name) {
+                        tags.push((start, end, scope, name));
+                    }
+                }
+            }
+            Event::End(_) => {
+                path.pop();
+                if open_tag.as_ref().is_some_and(|(_, depth, _, _)| *depth == path.len()) {
+                    if let Some((tag_start, _, scope, name)) = open_tag.take() {
+                        tags.push((tag_start, end, scope, name));
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(tags)
+}
+
+/// Local name and `Name` attribute of an element.
+fn element_name(element: &BytesStart) -> std::result::Result<(String, Option<String>), FixErrorKind> {
+    let local = String::from_utf8_lossy(element.local_name().as_ref()).into_owned();
+    let name = element
+        .try_get_attribute("Name")
+        .map_err(|_| FixErrorKind::InvalidXml)?
+        .map(|attr| attr.unescape_value().map(|value| value.into_owned()))
+        .transpose()
+        .map_err(|_| FixErrorKind::InvalidXml)?;
+    Ok((local, name))
+}
+
+/// Scope of the `Tag` elements inside the open elements `path`, if they
+/// are controller or program tags.
+fn tag_scope(path: &[(String, Option<String>)]) -> Option<String> {
+    let names: Vec<&str> = path.iter().map(|(local, _)| local.as_str()).collect();
+    match names.as_slice() {
+        [.., "Controller", "Tags"] => Some("Controller".to_string()),
+        [.., "Controller", "Programs", "Program", "Tags"] => {
+            let program = path[path.len() - 2].1.as_deref()?;
+            Some(format!("Program:{}", program))
+        }
+        _ => None,
+    }
+}
+
+/// Extend a range to whole lines when nothing else is on its lines.
+fn whole_lines(content: &str, start: usize, end: usize) -> (usize, usize) {
+    let line_start = content[..start].trim_end_matches([' ', '\t']).len();
+    let rest = &content[end..];
+    let trailing = rest.len() - rest.trim_start_matches([' ', '\t']).len();
+    let newline = if rest[trailing..].starts_with("\r\n") {
+        2
+    } else if rest[trailing..].starts_with('\n') {
+        1
+    } else {
+        return (start, end);
+    };
+    if line_start == 0 || content[..line_start].ends_with('\n') {
+        (line_start, end + trailing + newline)
+    } else {
+        (start, end)
+    }
+}
+
+/// Remove tags given as (scope, name) from the model.
+fn remove_tags(controller: &mut l5x::Controller, removed: &[(&str, &str)]) {
+    if let Some(ref mut tags) = controller.tags {
+        tags.tag.retain(|tag| !removed.contains(&("Controller", tag.name.as_str())));
+    }
+    if let Some(ref mut programs) = controller.programs {
+        for program in &mut programs.program {
+            let scope = format!("Program:{}", program.name);
+            if let Some(ref mut tags) = program.tags {
+                tags.tag.retain(|tag| !removed.contains(&(scope.as_str(), tag.name.as_str())));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detector::RuleDetector;
+    use crate::loader::FileFormat;
+
+    const XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00" TargetName="Line" TargetType="Controller">
+<Controller Use="Target" Name="Line">
+<Description>
+<![CDATA[Packaging line]]>
+</Description>
+<Tags>
+<Tag Name="Scratch" TagType="Base" DataType="DINT" Radix="Decimal" ExternalAccess="None">
+<Data Format="L5K">
+<![CDATA[0]]>
+</Data>
+</Tag>
+<Tag Name="Display" TagType="Base" DataType="DINT" ExternalAccess="Read/Write"/>
+<Tag Name="Run" TagType="Base" DataType="BOOL" ExternalAccess="None"/>
+</Tags>
+<Programs>
+<Program Name="Main" MainRoutineName="Logic">
+<Tags>
+<Tag Name="Spare" TagType="Base" DataType="BOOL" ExternalAccess="None"/>
+</Tags>
+<Routines>
+<Routine Name="Logic" Type="RLL">
+<RLLContent>
+<Rung Number="0" Type="N">
+<Text>
+<![CDATA[XIC(Run)OTE(Run);]]>
+</Text>
+</Rung>
+</RLLContent>
+</Routine>
+</Routines>
+</Program>
+</Programs>
+</Controller>
+</RSLogix5000Content>
+"#;
+
+    #[test]
+    fn test_remove_unused_tags() {
+        let report = RuleDetector::new().analyze_str(XML, FileFormat::L5x).expect("Should analyze");
+        let result = apply_fixes(XML, report.rules()).expect("Should fix");
+
+        let fixes: Vec<_> = result.applied.iter().map(|fix| (fix.description.as_str(), fix.line)).collect();
+        assert_eq!(fixes, [("remove unused tag 'Scratch'", 8), ("remove unused tag 'Spare'", 19)]);
+        assert!(result.applied[0].removed.ends_with("</Tag>\n"));
+        // Display is externally accessible
+        assert_eq!(result.unfixed, report.rules().len() - 2);
+
+        let fixed = RuleDetector::new().analyze_str(&result.content, FileFormat::L5x).expect("Should analyze");
+        let unused: Vec<_> = fixed.rules().iter().filter(|r| r.kind == RuleKind::UnusedTag).map(|r| r.identifier.as_str()).collect();
+        assert_eq!(unused, ["Display"]);
+        assert!(result.content.contains("<Tag Name=\"Display\""));
+        assert_eq!(XML.len() - result.content.len(), result.applied.iter().map(|fix| fix.removed.len()).sum::<usize>());
+    }
+
+    const ST_XML: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+<Controller Name="Line">
+<Tags>
+<Tag Name="Setpoint" TagType="Base" DataType="DINT" ExternalAccess="None"/>
+<Tag Name="Out" TagType="Base" DataType="DINT" ExternalAccess="None"/>
+<Tag Name="Scratch" TagType="Base" DataType="DINT" ExternalAccess="None"/>
+</Tags>
+<Programs>
+<Program Name="Main" MainRoutineName="Calc">
+<Routines>
+<Routine Name="Calc" Type="ST">
+<STContent>
+<Line Number="0">
+<![CDATA[Out := Setpoint * 2;]]>
+</Line>
+</STContent>
+</Routine>
+</Routines>
+</Program>
+</Programs>
+</Controller>
+</RSLogix5000Content>
+"#;
+
+    fn unused_tag(name: &str) -> Rule {
+        Rule::new(RuleKind::UnusedTag, crate::Severity::Info, "Controller", name, "unused").with_fix(
+            crate::FixHint {
+                description: format!("remove unused tag '{}'", name),
+                edit: None,
+            },
+        )
+    }
+
+    #[test]
+    fn test_keep_tags_used_in_st() {
+        // Fix hints for tags the ST routine uses, as from an older report
+        let rules = [unused_tag("Setpoint"), unused_tag("Out"), unused_tag("Scratch")];
+        let result = apply_fixes(ST_XML, &rules).expect("Should fix");
+        let fixes: Vec<_> = result.applied.iter().map(|fix| fix.description.as_str()).collect();
+        assert_eq!(fixes, ["remove unused tag 'Scratch'"]);
+        assert_eq!(result.unfixed, 2);
+        assert!(result.content.contains("<Tag Name=\"Setpoint\""));
+    }
+
+    #[test]
+    fn test_unresolved_reference() {
+        let project: l5x::Project = quick_xml::de::from_str(ST_XML).expect("Should parse");
+        let before = project.controller.unwrap();
+
+        let mut after = before.clone();
+        remove_tags(&mut after, &[("Controller", "Setpoint")]);
+        assert_eq!(new_unresolved(&before, &after, &[("Controller", "Setpoint")]), Some("Setpoint".to_string()));
+
+        let mut after = before.clone();
+        remove_tags(&mut after, &[("Controller", "Scratch")]);
+        assert_eq!(new_unresolved(&before, &after, &[("Controller", "Scratch")]), None);
+    }
+
+    #[test]
+    fn test_nothing_to_fix() {
+        let report = RuleDetector::new().analyze_str(XML, FileFormat::L5x).expect("Should analyze");
+        let rules: Vec<_> = report.rules().iter().filter(|r| r.fix.is_none()).collect();
+        let result = apply_fixes(XML, rules.iter().copied()).expect("Should fix");
+        assert!(result.applied.is_empty());
+        assert_eq!(result.content, XML);
+        assert_eq!(result.unfixed, rules.len());
+    }
+}
@@ -0,0 +1,88 @@
+//! Minimal gzip reader.
+//!
+//! Decompresses single-member gzip files (RFC 1952) with the DEFLATE
+//! decoder of the ZIP reader, checking the CRC-32 and size trailer.
+
+use crate::error::DecompressErrorKind;
+use crate::zip::inflate;
+
+/// Check if content starts with the gzip magic bytes.
+pub(crate) fn is_gzip(bytes: &[u8]) -> bool {
+    bytes.starts_with(&[0x1f, 0x8b])
+}
+
+/// Decompress gzip content of at most `max_size` bytes decompressed.
+pub(crate) fn decompress(bytes: &[u8], max_size: usize) -> Result<Vec<u8>, DecompressErrorKind> {
+    let invalid = DecompressErrorKind::InvalidStream;
+    let header = bytes.get(..10).ok_or(invalid.clone())?;
+    if !is_gzip(header) || header[2] != 8 {
+        return Err(invalid);
+    }
+    let flags = header[3];
+
+    // Optional fields: extra field, file name, comment, header CRC
+    let mut start = 10;
+    if flags & 0x04 != 0 {
+        let len = bytes.get(start..start + 2).ok_or(invalid.clone())?;
+        start += 2 + usize::from(u16::from_le_bytes([len[0], len[1]]));
+    }
+    for flag in [0x08, 0x10] {
+        if flags & flag != 0 {
+            let end = bytes.get(start..).and_then(|rest| rest.iter().position(|&b| b == 0)).ok_or(invalid.clone())?;
+            start += end + 1;
+        }
+    }
+    if flags & 0x02 != 0 {
+        start += 2;
+    }
+
+    let trailer_start = bytes.len().checked_sub(8).filter(|&end| end >= start).ok_or(invalid.clone())?;
+    let content = inflate(&bytes[start..trailer_start], max_size)?;
+    let trailer = &bytes[trailer_start..];
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    if crc != crc32(&content) || size != content.len() as u32 {
+        return Err(invalid);
+    }
+    Ok(content)
+}
+
+/// CRC-32 (IEEE) of `bytes`, as stored in the gzip trailer.
+fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xedb8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crc32() {
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(crc32(b"123456789"), 0xcbf4_3926);
+    }
+
+    #[test]
+    fn test_decompress() {
+        // "This is a test" compressed with a fixed Huffman block, named "t"
+        let mut gz = vec![0x1f, 0x8b, 0x08, 0x08, 0, 0, 0, 0, 0x02, 0x03, b't', 0];
+        gz.extend_from_slice(&[0x0b, 0xc9, 0xc8, 0x2c, 0x56, 0x00, 0xa2, 0x44, 0x85, 0x92, 0xd4, 0xe2, 0x12, 0x00]);
+        gz.extend_from_slice(&crc32(b"This is a test").to_le_bytes());
+        gz.extend_from_slice(&14u32.to_le_bytes());
+        assert!(is_gzip(&gz));
+        assert_eq!(decompress(&gz, usize::MAX).unwrap(), b"This is a test");
+        assert_eq!(decompress(&gz, 10), Err(DecompressErrorKind::TooLarge { limit: 10 }));
+
+        let last = gz.len() - 1;
+        gz[last] ^= 1;
+        assert_eq!(decompress(&gz, usize::MAX), Err(DecompressErrorKind::InvalidStream));
+        assert!(!is_gzip(b"<?xml"));
+    }
+}
@@ -17,6 +17,52 @@
 //! - **S0005: unused_datatypes** - User-defined types never used
 //! - **M0001: cyclomatic_complexity** - ST routines with high complexity
 //! - **M0003: deep_nesting** - Control structures nested too deeply
+//! - **C0001: type_mismatch** - ST assignments and comparisons with mismatched types
+//! - **C0002: non_exhaustive_case** - CASE over an enum that misses members without ELSE
+//! - **C0003: overlapping_case_labels** - CASE labels that duplicate or overlap earlier labels
+//! - **C0032: loop_var_modified** - FOR loop control variables written inside the loop body
+//! - **S0006: empty_branches** - IF/ELSE branches, loop bodies and CASE branches without statements
+//! - **C0004: self_assignment** - Assignments of a variable path to itself
+//! - **C0010: float_equality** - Equality comparisons of REAL/LREAL values
+//! - **S0007/S0008/S0009: unused_variables** - Unused inputs, unassigned outputs and unused VAR_TEMP
+//! - **S0010: naming** - Tags, variables, types and POUs that do not match configured name patterns
+//! - **N0007/N0009: identifiers** - Names longer than the maximum length or equal to IEC 61131-3 reserved words
+//! - **C0005: uninitialized_read** - Reads of VAR_TEMP (and function VAR) locals before assignment
+//! - **C0006: required_init** - Configured tags and variables without an initial value
+//! - **S0011: unscheduled_program** - Programs not bound to any task and tasks with no programs
+//! - **S0016: uninvoked_instance** - FB and AOI instances that are declared but never called
+//! - **C0007: array_bounds** - Constant array indices outside the declared ARRAY range
+//! - **S0017: style** - ST lines over the maximum length, with tabs or with trailing whitespace (opt-in)
+//! - **M0006: low_comment_density** - ST routines with too few comments
+//! - **C0008/C0009: jump_labels** - IL jumps to undefined labels, and labels never jumped to
+//! - **C0016: timer_no_reset** - Retentive timers/counters never reset, timers always enabled
+//! - **C0012: string_truncation** - STRING/WSTRING values assigned to a shorter string
+//! - **M0007: duplicate_logic** - Routines with identical or near-identical logic
+//! - **C0013: fbd_unconnected** - FBD required inputs without connection, function outputs feeding nothing, disconnected networks
+//! - **C0014: division_by_zero** - `/` and `MOD` by a constant zero or by a variable with no nonzero guard
+//! - **C0017: bit_operation** - AND/OR/XOR mixing BOOL with integer or bit-string operands, and masks or shifts on signed integers
+//! - **M0008: law_of_demeter** - Member access chains deeper than a maximum
+//! - **C0018: comparison_statement** - Comparisons used as statements, likely `=` written for `:=`
+//! - **S0012: impure_function** - Functions with side effects: global writes and function block instance calls
+//! - **C0019: dead_store** - Assignments overwritten before their value is read
+//! - **M0009: nonstandard_function** - Calls of vendor extensions outside the IEC standard library
+//! - **S0018: comment_markers** - TODO/FIXME/XXX/HACK markers in comments and descriptions
+//! - **S0013: tag_scope_suggestion** - Controller-scoped tags used by only one program
+//! - **C0020: redundant_boolean** - Redundant boolean expressions such as `X AND X` or `X OR NOT X`
+//! - **C0021: enum_value_conflict** - Enum members with duplicate or decreasing values
+//! - **S0014: cross_program_shared_tag** - Controller tags written in one program and read in another
+//! - **S0019: unused_method_parameter** - Method input parameters that the method never reads
+//! - **M0010: unreachable_pou** - POUs with no call path from any task entry point
+//! - **C0022: in_out_not_variable** - VAR_IN_OUT parameters bound to something other than a variable
+//! - **C0023: invalid_array_range** - Array declarations with a high bound below the low bound
+//! - **C0024: invalid_struct** - Structs with duplicate member names or recursive definitions
+//! - **C0025: unbound_input** - FB and AOI calls that leave inputs without an initial value unbound
+//! - **M0011: string_concat_in_loop** - Strings extended with themselves by CONCAT or INSERT inside loops
+//! - **M0012: hardcoded_address** - Direct addresses used in logic instead of variables mapped with AT
+//! - **M0013: convert_to_case** - IF/ELSIF chains that compare one variable with constants, candidates for CASE
+//! - **C0026: argument_conversion** - Call arguments implicitly converted to a parameter type of another size or family
+//! - **C0027: bool_toggle** - Variables set to TRUE and FALSE by unconditional assignments in sequence, where only the last takes effect
+//! - **S0015: undefined_datatype_ref** - Tags, variables and members whose data type is neither predefined nor defined in the project
 //!
 //!
 //! ## CLI Usage
@@ -27,28 +73,91 @@
 //!
 //! # With custom config
 //! plceye --config plceye.toml project.L5X
+//!
+//! # With a rule-set profile
+//! plceye --profile strict project.L5X
+//!
+//! # Compare two versions of a project
+//! plceye diff old.L5X new.L5X
+//!
+//! # Check that every file of a folder loads and analyzes
+//! plceye selftest exports/
+//! ```
+//!
+//! ## Embedding
+//!
+//! The stable API for embedding plceye in other tools is:
+//!
+//! - [`RuleDetector`] - [`RuleDetector::analyze_str`] for in-memory content,
+//!   [`RuleDetector::analyze_file`] for files
+//! - [`Report::rules`] - the findings of a run
+//! - [`Rule`] accessors - [`Rule::id`], [`Rule::severity`], [`Rule::message`],
+//!   [`Rule::location`] and [`Rule::identifier`]
+//! - [`RuleConfig`] - detector settings, e.g. from [`RuleConfig::parse`]
+//! - [`Detector`] - custom rules, added with [`RuleDetector::register`]
+//!
+//! ```no_run
+//! use plceye::{FileFormat, RuleDetector};
+//!
+//! let content = std::fs::read_to_string("project.L5X").unwrap();
+//! let report = RuleDetector::new().analyze_str(&content, FileFormat::L5x).unwrap();
+//! for rule in report.rules() {
+//!     println!("{} {} {}: {}", rule.id(), rule.severity(), rule.location(), rule.message());
+//! }
 //! ```
+//!
+//! The `Display` output of [`Rule`] is meant for the CLI and may change.
 
+mod acd;
 pub mod analysis;
+mod bundle;
 mod config;
 mod detector;
+mod diff;
 mod error;
+mod fix;
+mod gzip;
 mod loader;
+pub mod messages;
+mod metrics;
 mod report;
 mod rules;
+mod selftest;
+mod zip;
 
 // Core types
-pub use config::{RuleConfig, GeneralConfig, UnusedTagsConfig, UndefinedTagsConfig, EmptyRoutinesConfig, UnusedAoisConfig, UnusedDataTypesConfig, ComplexityConfig, NestingConfig};
-pub use detector::RuleDetector;
-pub use error::{Error, Result, L5xParseErrorKind, ConfigErrorKind};
-pub use loader::{LoadedProject, FileFormat};
-pub use report::{Report, Rule, Severity, RuleKind};
+pub use config::{RuleConfig, GeneralConfig, EscalationConfig, CustomRulesConfig, UnusedTagsConfig, UndefinedTagsConfig, EmptyRoutinesConfig, UnusedAoisConfig, UnusedDataTypesConfig, ComplexityConfig, NestingConfig, TypeMismatchConfig, NonExhaustiveCaseConfig, OverlappingCaseLabelsConfig, LoopVarModifiedConfig, EmptyBranchesConfig, SelfAssignmentConfig, FloatEqualityConfig, UnusedVariablesConfig, NamingConfig, IdentifiersConfig, PouKindThresholds, UninitializedReadConfig, RequiredInitConfig, UnscheduledProgramConfig, UninvokedInstanceConfig, ArrayBoundsConfig, StyleConfig, LowCommentDensityConfig, JumpLabelsConfig, TimerNoResetConfig, StringTruncationConfig, DuplicateLogicConfig, FbdUnconnectedConfig, DivisionByZeroConfig, BitOperationConfig, LawOfDemeterConfig, ComparisonStatementConfig, ImpureFunctionConfig, DeadStoreConfig, NonstandardFunctionConfig, CommentMarkersConfig, TagScopeSuggestionConfig, RedundantBooleanConfig, EnumValueConflictConfig, CrossProgramSharedTagConfig, UnusedMethodParameterConfig, UnreachablePouConfig, InOutNotVariableConfig, InvalidArrayRangeConfig, InvalidStructConfig, UnboundInputConfig, StringConcatInLoopConfig, HardcodedAddressConfig, ConvertToCaseConfig, ArgumentConversionConfig, BoolToggleConfig, UndefinedDataTypeRefConfig};
+pub use detector::{Detector, RuleDetector};
+pub use diff::{diff_projects, diff_snapshots, ChangeKind, ItemChange, ItemKind, ProjectDiff, ProjectSnapshot, RoutineBody};
+pub use bundle::{Bundle, Definition, DefinitionKind, DuplicateDefinition};
+pub use acd::is_acd_path;
+pub use error::{Error, Result, AcdExportErrorKind, L5xParseErrorKind, BundleErrorKind, DecompressErrorKind, ConfigErrorKind, FixErrorKind};
+pub use fix::{apply_fixes, AppliedFix, FixResult};
+pub use loader::{LoadedProject, FileFormat, ExportKind};
+pub use metrics::{ProjectMetrics, ProjectStats, MetricsCounts, ComplexityMetrics, ComplexityBucket, FindingMetrics, RuleMetric, METRICS_SCHEMA_VERSION};
+pub use selftest::{Corpus, CorpusFile, CorpusOutcome, FormatCounts};
+pub use report::{Report, Rule, ColoredRule, Severity, RuleKind, Summary, RuleCount, FixHint, TextEdit, PouPath};
 
 // Analysis types (for extensions)
-pub use analysis::{ProjectAnalysis, ParseStats, analyze_controller};
-pub use analysis::{PlcopenAnalysis, PlcopenStats, analyze_plcopen_project};
+pub use analysis::{ProjectAnalysis, ParseStats, RoutineKind, analyze_controller};
+pub use analysis::{PlcopenAnalysis, PlcopenStats, analyze_plcopen_project, analyze_plcopen_projects};
+pub use analysis::{StUnit, StUnits};
+pub use analysis::{Task, TaskBindings, TaskTrigger};
+pub use analysis::{TimerUsage, TimerUse};
+pub use analysis::{FbInstance, InstanceUsage};
+pub use analysis::{IoTag, IoTagKind, IoTags};
+pub use analysis::CommentDensity;
+pub use analysis::{DataTypeIndex, TypeReference, TypeReferenceKind};
+pub use analysis::{ComplexityDistribution, NestingDistribution, COMPLEXITY_BUCKETS, NESTING_BUCKETS};
+pub use analysis::{CodeSize, PouSize, SizeMetrics};
+pub use analysis::{DuplicateGroup, DuplicateLogic, RoutineShape};
+pub use analysis::{FbdIssue, FbdIssueKind, FbdNetwork};
+pub use analysis::{IlJump, IlLabel, IlLabels};
+pub use analysis::{CallGraph, CycleError, PouId};
+pub use analysis::VendorCall;
 
 // Re-export parser crates for extensions
 pub use l5x;
 pub use plcopen;
 pub use iec61131;
+pub use iecst;
@@ -1,10 +1,19 @@
 //! Unified project loading from multiple formats.
 //!
 //! This module provides format detection and loading for L5X and PLCopen files.
+//! Files are transcoded to UTF-8 before parsing: a byte order mark or the
+//! XML declaration selects UTF-16 or a single-byte encoding (Windows-1252,
+//! ISO-8859-1), as written by older Studio 5000 exports.
+//!
+//! A ZIP archive or a folder of L5X exports, or of PLCopen files, is loaded
+//! as one bundle (see [`Bundle`]).
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::error::{Error, L5xParseErrorKind, Result};
+use crate::bundle::Bundle;
+use crate::error::{BundleErrorKind, Error, L5xParseErrorKind, Result};
+use crate::gzip;
+use crate::report::RuleKind;
 
 /// Detected file format.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -56,6 +65,74 @@ impl FileFormat {
     }
 }
 
+/// What an L5X file exports, from its `TargetType` attribute.
+///
+/// Program, AOI, data type, routine and rung exports hold the target with
+/// only the definitions it depends on as context, so project-wide checks
+/// (unused AOIs, unused data types, unscheduled programs) see a fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportKind {
+    /// Whole controller (project) export
+    Controller,
+    /// Single program
+    Program,
+    /// Single Add-On Instruction
+    AddOnInstruction,
+    /// Single user-defined data type
+    DataType,
+    /// Single routine
+    Routine,
+    /// Selected rungs of a routine
+    Rung,
+    /// Other partial export (module, task, ...)
+    Other,
+}
+
+impl ExportKind {
+    /// Export kind of a `TargetType` value; files without one are controller exports.
+    pub fn from_target_type(target_type: Option<&str>) -> Self {
+        match target_type.map(str::to_ascii_lowercase).as_deref() {
+            None | Some("controller") => ExportKind::Controller,
+            Some("program") => ExportKind::Program,
+            Some("addoninstructiondefinition") => ExportKind::AddOnInstruction,
+            Some("datatype") => ExportKind::DataType,
+            Some("routine") => ExportKind::Routine,
+            Some("rung") => ExportKind::Rung,
+            Some(_) => ExportKind::Other,
+        }
+    }
+
+    /// Whether the file exports less than a whole controller.
+    pub fn is_partial(&self) -> bool {
+        *self != ExportKind::Controller
+    }
+
+    /// Whether findings of a rule are meaningless for this export.
+    ///
+    /// The exported AOI or data type is the target of the file, not used
+    /// by anything in it, so it would always be reported as unused.
+    pub fn skips(&self, kind: &RuleKind) -> bool {
+        matches!(
+            (self, kind),
+            (ExportKind::AddOnInstruction, RuleKind::UnusedAoi) | (ExportKind::DataType, RuleKind::UnusedDataType)
+        )
+    }
+}
+
+impl std::fmt::Display for ExportKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportKind::Controller => write!(f, "Controller export"),
+            ExportKind::Program => write!(f, "Program export: analyzing single program"),
+            ExportKind::AddOnInstruction => write!(f, "AOI export: analyzing single Add-On Instruction"),
+            ExportKind::DataType => write!(f, "Data type export: analyzing single data type"),
+            ExportKind::Routine => write!(f, "Routine export: analyzing single routine"),
+            ExportKind::Rung => write!(f, "Rung export: analyzing selected rungs"),
+            ExportKind::Other => write!(f, "Partial export: analysis is limited to the exported components"),
+        }
+    }
+}
+
 /// A loaded project with its format-specific data.
 pub struct LoadedProject {
     /// The L5X controller (for L5X files)
@@ -69,21 +146,145 @@ pub struct LoadedProject {
     
     /// Source file path (if loaded from file)
     pub source_path: Option<String>,
+    
+    /// Member files and definition origins (for bundles)
+    pub bundle: Option<Bundle>,
+
+    /// What a single L5X file exports (`None` for PLCopen files and bundles)
+    pub export: Option<ExportKind>,
 }
 
 impl LoadedProject {
     /// Load a project from file.
+    ///
+    /// Gzip-compressed files (`.L5X.gz`, `.xml.gz`) are decompressed first,
+    /// up to the file size limit of the L5X security limits.
     pub fn from_file(path: &Path) -> Result<Self> {
-        let content = std::fs::read_to_string(path).map_err(|e| Error::FileRead {
+        let bytes = std::fs::read(path).map_err(|e| Error::FileRead {
             path: path.display().to_string(),
             source: e,
         })?;
         
-        let mut project = Self::from_str(&content, Some(path))?;
+        let mut project = if gzip::is_gzip(&bytes) {
+            let bytes = gzip::decompress(&bytes, max_decompressed_size()).map_err(|kind| Error::Decompress {
+                path: path.display().to_string(),
+                kind,
+            })?;
+            Self::from_bytes(&bytes, Some(&strip_gz_extension(path)))?
+        } else {
+            Self::from_bytes(&bytes, Some(path))?
+        };
         project.source_path = Some(path.display().to_string());
         Ok(project)
     }
     
+    /// Load a ZIP archive or a folder of L5X exports or PLCopen files as one
+    /// project.
+    ///
+    /// Members are the `.l5x` files and the PLCopen `.xml` files of the
+    /// archive, or of the folder and its subfolders, in name order; other
+    /// `.xml` files are skipped. Their controllers or PLCopen projects are
+    /// merged; member names are relative to the archive or folder.
+    pub fn from_bundle(path: &Path) -> Result<Self> {
+        let bundle_error = |kind| Error::Bundle {
+            path: path.display().to_string(),
+            kind,
+        };
+
+        let mut files: Vec<(String, Vec<u8>)> = if path.is_dir() {
+            let mut found = Vec::new();
+            collect_member_files(path, &mut found);
+            let limit = max_decompressed_size();
+            let mut total = 0;
+            found
+                .into_iter()
+                .map(|file| {
+                    let bytes = std::fs::read(&file).map_err(|e| Error::FileRead {
+                        path: file.display().to_string(),
+                        source: e,
+                    })?;
+                    total += bytes.len();
+                    if total > limit {
+                        return Err(bundle_error(BundleErrorKind::TooLarge { limit }));
+                    }
+                    let name = file.strip_prefix(path).unwrap_or(&file).to_string_lossy().replace('\\', "/");
+                    Ok((name, bytes))
+                })
+                .collect::<Result<_>>()?
+        } else {
+            let bytes = std::fs::read(path).map_err(|e| Error::FileRead {
+                path: path.display().to_string(),
+                source: e,
+            })?;
+            crate::zip::read_entries(&bytes, max_decompressed_size())
+                .map_err(bundle_error)?
+                .into_iter()
+                .filter(|(name, _)| is_member_name(name))
+                .collect()
+        };
+        files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut controllers = Vec::new();
+        let mut projects = Vec::new();
+        for (name, bytes) in files {
+            // Only PLCopen projects among the .xml files are members
+            if !is_l5x_name(&name)
+                && decode(&bytes).ok().and_then(|content| FileFormat::from_content(&content)) != Some(FileFormat::PlcOpen)
+            {
+                continue;
+            }
+            let project = Self::from_bytes(&bytes, Some(Path::new(&name)))
+                .map_err(|_| bundle_error(BundleErrorKind::InvalidMember { name: name.clone() }))?;
+            if let Some(controller) = project.l5x_controller {
+                controllers.push((name, controller));
+            } else if let Some(plcopen) = project.plcopen_project {
+                projects.push((name, plcopen));
+            }
+        }
+
+        let source_path = Some(path.display().to_string());
+        if projects.is_empty() {
+            let (controller, bundle) =
+                Bundle::merge(controllers).ok_or_else(|| bundle_error(BundleErrorKind::NoMembers))?;
+            Ok(LoadedProject {
+                l5x_controller: Some(controller),
+                plcopen_project: None,
+                format: FileFormat::L5x,
+                source_path,
+                bundle: Some(bundle),
+                export: None,
+            })
+        } else if controllers.is_empty() {
+            let (project, bundle) =
+                Bundle::merge_plcopen(projects).ok_or_else(|| bundle_error(BundleErrorKind::NoMembers))?;
+            Ok(LoadedProject {
+                l5x_controller: None,
+                plcopen_project: Some(project),
+                format: FileFormat::PlcOpen,
+                source_path,
+                bundle: Some(bundle),
+                export: None,
+            })
+        } else {
+            Err(bundle_error(BundleErrorKind::MixedFormats))
+        }
+    }
+    
+    /// Check if a path is loaded as a bundle: a folder or a `.zip` file.
+    pub fn is_bundle_path(path: &Path) -> bool {
+        path.is_dir()
+            || path
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| e.eq_ignore_ascii_case("zip"))
+    }
+    
+    /// Load a project from raw file content in any supported encoding.
+    pub fn from_bytes(bytes: &[u8], path: Option<&Path>) -> Result<Self> {
+        let content = decode(bytes)?;
+        Self::from_str(&content, path)
+    }
+    
     /// Load a project from string content.
     pub fn from_str(content: &str, path: Option<&Path>) -> Result<Self> {
         let format = path
@@ -93,6 +294,11 @@ impl LoadedProject {
                 kind: L5xParseErrorKind::XmlDeserialize,
             })?;
         
+        Self::from_str_with_format(content, format)
+    }
+    
+    /// Load a project from string content in a known format.
+    pub fn from_str_with_format(content: &str, format: FileFormat) -> Result<Self> {
         match format {
             FileFormat::L5x => Self::load_l5x(content),
             FileFormat::PlcOpen => Self::load_plcopen(content),
@@ -105,11 +311,22 @@ impl LoadedProject {
                 kind: L5xParseErrorKind::XmlDeserialize,
             })?;
         
+        let export = ExportKind::from_target_type(project.target_type.as_deref());
+        let Some(controller) = project.controller else {
+            return Err(Error::L5xParse {
+                kind: L5xParseErrorKind::NoController {
+                    target_type: project.target_type.unwrap_or_else(|| "Controller".to_string()),
+                },
+            });
+        };
+
         Ok(LoadedProject {
-            l5x_controller: project.controller,
+            l5x_controller: Some(controller),
             plcopen_project: None,
             format: FileFormat::L5x,
             source_path: None,
+            bundle: None,
+            export: Some(export),
         })
     }
     
@@ -124,6 +341,8 @@ impl LoadedProject {
             plcopen_project: Some(project),
             format: FileFormat::PlcOpen,
             source_path: None,
+            bundle: None,
+            export: None,
         })
     }
     
@@ -151,9 +370,141 @@ impl LoadedProject {
     }
 }
 
+/// Detect the format of a project file from its extension and content,
+/// without parsing it. Gzip-compressed files are decompressed first.
+pub(crate) fn detect_file_format(path: &Path) -> Option<FileFormat> {
+    let bytes = std::fs::read(path).ok()?;
+    let path = strip_gz_extension(path);
+    if let Some(format) = FileFormat::from_extension(&path) {
+        return Some(format);
+    }
+    let bytes = if gzip::is_gzip(&bytes) {
+        gzip::decompress(&bytes, max_decompressed_size()).ok()?
+    } else {
+        bytes
+    };
+    FileFormat::from_content(&decode(&bytes).ok()?)
+}
+
+/// Collect the L5X and XML files of a folder and its subfolders.
+fn collect_member_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if path.is_dir() {
+            collect_member_files(&path, files);
+        } else if is_member_name(&path.to_string_lossy()) {
+            files.push(path);
+        }
+    }
+}
+
+fn is_l5x_name(name: &str) -> bool {
+    name.to_ascii_lowercase().ends_with(".l5x")
+}
+
+fn is_member_name(name: &str) -> bool {
+    is_l5x_name(name) || name.to_ascii_lowercase().ends_with(".xml")
+}
+
+/// Largest decompressed size of a gzip file or of all members of a bundle,
+/// guarding against decompression bombs.
+fn max_decompressed_size() -> usize {
+    l5x::SecurityLimits::default().max_file_size
+}
+
+/// Path without a trailing `.gz` extension, for format detection.
+fn strip_gz_extension(path: &Path) -> PathBuf {
+    match path.extension().and_then(|e| e.to_str()) {
+        Some(ext) if ext.eq_ignore_ascii_case("gz") => path.with_extension(""),
+        _ => path.to_path_buf(),
+    }
+}
+
+/// Transcode file content to UTF-8.
+///
+/// A byte order mark wins over the XML declaration. Content declared (or
+/// assumed) as UTF-8 that is not valid UTF-8 is read as Windows-1252, the
+/// usual encoding of accented comments in older exports.
+fn decode(bytes: &[u8]) -> Result<String> {
+    if let Some(rest) = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF]) {
+        return Ok(decode_utf8(rest));
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFF, 0xFE]) {
+        return Ok(decode_utf16(rest, u16::from_le_bytes));
+    }
+    if let Some(rest) = bytes.strip_prefix(&[0xFE, 0xFF]) {
+        return Ok(decode_utf16(rest, u16::from_be_bytes));
+    }
+    // UTF-16 without a byte order mark: the declaration starts with `<` and a zero byte
+    if bytes.starts_with(b"<\0") {
+        return Ok(decode_utf16(bytes, u16::from_le_bytes));
+    }
+    if bytes.starts_with(b"\0<") {
+        return Ok(decode_utf16(bytes, u16::from_be_bytes));
+    }
+
+    let encoding = declared_encoding(bytes).unwrap_or_default();
+    match encoding.as_str() {
+        "" | "UTF-8" | "UTF8" | "US-ASCII" | "ASCII" => Ok(decode_utf8(bytes)),
+        // Without a byte order mark or zero bytes the content isn't really UTF-16
+        "UTF-16" | "UTF-16LE" | "UTF-16BE" => Ok(decode_utf8(bytes)),
+        "WINDOWS-1252" | "CP1252" => Ok(decode_windows_1252(bytes)),
+        "ISO-8859-1" | "ISO8859-1" | "LATIN1" | "LATIN-1" => Ok(bytes.iter().map(|&b| char::from(b)).collect()),
+        _ => Err(Error::UnsupportedEncoding { encoding }),
+    }
+}
+
+/// Encoding name of the XML declaration, uppercase.
+fn declared_encoding(bytes: &[u8]) -> Option<String> {
+    let end = bytes.windows(2).position(|w| w == b"?>")?;
+    let declaration = String::from_utf8_lossy(&bytes[..end]);
+    let declaration = declaration.trim_start();
+    if !declaration.starts_with("<?xml") {
+        return None;
+    }
+    let value = &declaration[declaration.find("encoding")? + "encoding".len()..];
+    let value = value.trim_start().strip_prefix('=')?.trim_start();
+    let quote = value.chars().next().filter(|c| *c == '"' || *c == '\'')?;
+    let value = &value[1..];
+    Some(value[..value.find(quote)?].trim().to_uppercase())
+}
+
+fn decode_utf8(bytes: &[u8]) -> String {
+    match std::str::from_utf8(bytes) {
+        Ok(text) => text.to_string(),
+        Err(_) => decode_windows_1252(bytes),
+    }
+}
+
+fn decode_utf16(bytes: &[u8], unit: fn([u8; 2]) -> u16) -> String {
+    let units: Vec<u16> = bytes.chunks_exact(2).map(|pair| unit([pair[0], pair[1]])).collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Windows-1252 characters for bytes 0x80 to 0x9F; the other bytes match Unicode.
+const WINDOWS_1252_HIGH: [char; 32] = [
+    '\u{20AC}', '\u{81}', '\u{201A}', '\u{192}', '\u{201E}', '\u{2026}', '\u{2020}', '\u{2021}',
+    '\u{2C6}', '\u{2030}', '\u{160}', '\u{2039}', '\u{152}', '\u{8D}', '\u{17D}', '\u{8F}',
+    '\u{90}', '\u{2018}', '\u{2019}', '\u{201C}', '\u{201D}', '\u{2022}', '\u{2013}', '\u{2014}',
+    '\u{2DC}', '\u{2122}', '\u{161}', '\u{203A}', '\u{153}', '\u{9D}', '\u{17E}', '\u{178}',
+];
+
+fn decode_windows_1252(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|&b| match b {
+            0x80..=0x9F => WINDOWS_1252_HIGH[usize::from(b - 0x80)],
+            _ => char::from(b),
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::DecompressErrorKind;
 
     #[test]
     fn test_format_detection_extension() {
@@ -200,6 +551,36 @@ mod tests {
         assert_eq!(loaded.name(), "TestController");
     }
 
+    #[test]
+    fn test_load_gzip() {
+        let xml = r#"<?xml version="1.0"?><RSLogix5000Content SchemaRevision="1.0"><Controller Name="Line"><Programs><Program Name="Filling"/><Program Name="Capping"/></Programs></Controller></RSLogix5000Content>"#;
+        let gz = [
+            0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0xb3, 0xb1, 0xaf, 0xc8, 0xcd, 0x51, 0x28,
+            0x4b, 0x2d, 0x2a, 0xce, 0xcc, 0xcf, 0xb3, 0x55, 0x32, 0xd4, 0x33, 0x50, 0xb2, 0xb7, 0xb3, 0x09, 0x0a,
+            0xf6, 0xc9, 0x4f, 0xcf, 0xac, 0x30, 0x35, 0x30, 0x30, 0x70, 0xce, 0xcf, 0x2b, 0x49, 0xcd, 0x2b, 0x51,
+            0x08, 0x4e, 0xce, 0x48, 0xcd, 0x4d, 0x0c, 0x4a, 0x2d, 0xcb, 0x44, 0xa8, 0xb4, 0xb3, 0x01, 0xc9, 0x16,
+            0xe5, 0xe7, 0xe4, 0xa4, 0x16, 0x29, 0xf8, 0x25, 0xe6, 0xa6, 0xda, 0x2a, 0xf9, 0x64, 0xe6, 0xa5, 0x02,
+            0xc5, 0x03, 0x8a, 0xf2, 0xd3, 0x8b, 0x12, 0x73, 0x8b, 0xe1, 0x2c, 0xa8, 0xb4, 0x5b, 0x66, 0x4e, 0x4e,
+            0x66, 0x5e, 0xba, 0x92, 0x3e, 0xba, 0x84, 0x73, 0x62, 0x41, 0x01, 0x54, 0x42, 0x1f, 0xa1, 0x59, 0x1f,
+            0x61, 0x3e, 0x90, 0x83, 0xe9, 0x2a, 0x3b, 0x00, 0x07, 0x5a, 0x10, 0x86, 0xbf, 0x00, 0x00, 0x00,
+        ];
+        let dir = std::env::temp_dir().join(format!("plceye_gzip_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("Line.L5X"), xml).unwrap();
+        std::fs::write(dir.join("Line.L5X.gz"), gz).unwrap();
+
+        let plain = LoadedProject::from_file(&dir.join("Line.L5X")).expect("Should load");
+        let compressed = LoadedProject::from_file(&dir.join("Line.L5X.gz")).expect("Should load");
+        assert_eq!(compressed.format, FileFormat::L5x);
+        assert_eq!(format!("{:?}", compressed.l5x_controller), format!("{:?}", plain.l5x_controller));
+        assert_eq!(program_names(&compressed), ["Filling", "Capping"]);
+
+        std::fs::write(dir.join("Broken.L5X.gz"), &gz[..40]).unwrap();
+        let result = LoadedProject::from_file(&dir.join("Broken.L5X.gz"));
+        assert!(matches!(result, Err(Error::Decompress { kind: DecompressErrorKind::InvalidStream, .. })));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
     #[test]
     fn test_load_plcopen() {
         let xml = r#"<?xml version="1.0"?>
@@ -219,4 +600,164 @@ mod tests {
         assert_eq!(loaded.format, FileFormat::PlcOpen);
         assert_eq!(loaded.name(), "Test");
     }
+
+    #[test]
+    fn test_partial_export() {
+        let xml = r#"<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00" TargetType="Program" ContainsContext="true">
+            <Controller Use="Context" Name="Line"><Programs Use="Context"><Program Use="Target" Name="Filling"/></Programs></Controller>
+        </RSLogix5000Content>"#;
+        let loaded = LoadedProject::from_str(xml, None).expect("Should parse");
+        assert_eq!(loaded.export, Some(ExportKind::Program));
+        assert_eq!(loaded.export.unwrap().to_string(), "Program export: analyzing single program");
+
+        let xml = r#"<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00" TargetType="AddOnInstructionDefinition"/>"#;
+        let err = LoadedProject::from_str(xml, None).err().expect("Should fail");
+        assert!(matches!(
+            err,
+            Error::L5xParse { kind: L5xParseErrorKind::NoController { ref target_type } } if target_type == "AddOnInstructionDefinition"
+        ));
+
+        assert_eq!(ExportKind::from_target_type(None), ExportKind::Controller);
+        assert!(!ExportKind::from_target_type(Some("Controller")).is_partial());
+    }
+
+    const ENCODED_L5X: &str = r#"<?xml version="1.0" encoding="ENCODING"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Presse">
+                <Tags>
+                    <Tag Name="Arrêt" TagType="Base" DataType="BOOL">
+                        <Description><![CDATA[Arrêt d'urgence – opérateur]]></Description>
+                    </Tag>
+                </Tags>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+    fn first_tag_name(loaded: &LoadedProject) -> String {
+        loaded.l5x_controller.as_ref().unwrap().tags.as_ref().unwrap().tag[0].name.clone()
+    }
+
+    #[test]
+    fn test_load_utf16le_with_bom() {
+        let xml = ENCODED_L5X.replace("ENCODING", "UTF-16");
+        let mut bytes = vec![0xFF, 0xFE];
+        bytes.extend(xml.encode_utf16().flat_map(u16::to_le_bytes));
+
+        let loaded = LoadedProject::from_bytes(&bytes, Some(Path::new("old.L5X"))).expect("Should parse");
+        assert_eq!(loaded.name(), "Presse");
+        assert_eq!(first_tag_name(&loaded), "Arrêt");
+        assert_eq!(decode(&bytes).unwrap(), xml);
+    }
+
+    #[test]
+    fn test_decode_single_byte() {
+        let xml = ENCODED_L5X.replace("ENCODING", "Windows-1252");
+        let bytes: Vec<u8> = xml
+            .chars()
+            .map(|c| match c {
+                '–' => 0x96,
+                c => c as u8,
+            })
+            .collect();
+        assert_eq!(decode(&bytes).unwrap(), xml);
+        let loaded = LoadedProject::from_bytes(&bytes, None).expect("Should parse");
+        assert_eq!(first_tag_name(&loaded), "Arrêt");
+
+        // Declared as UTF-8 but written as Windows-1252
+        assert_eq!(decode(b"<?xml version=\"1.0\"?><a>Arr\xEAt</a>").unwrap(), "<?xml version=\"1.0\"?><a>Arrêt</a>");
+        assert_eq!(decode(b"<?xml encoding='ISO-8859-1'?><a>\xE9</a>").unwrap(), "<?xml encoding='ISO-8859-1'?><a>é</a>");
+    }
+
+    #[test]
+    fn test_unsupported_encoding() {
+        let result = decode(b"<?xml version=\"1.0\" encoding=\"Shift_JIS\"?><a/>");
+        assert!(matches!(result, Err(Error::UnsupportedEncoding { ref encoding }) if encoding == "SHIFT_JIS"));
+    }
+
+    const PARTIAL_FILLING: &str = r#"<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00" TargetType="Program" ContainsContext="true">
+        <Controller Use="Context" Name="Line">
+            <Programs Use="Context"><Program Use="Target" Name="Filling"/></Programs>
+        </Controller>
+    </RSLogix5000Content>"#;
+
+    const PARTIAL_CAPPING: &str = r#"<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00" TargetType="Program" ContainsContext="true">
+        <Controller Use="Context" Name="Line">
+            <Tags Use="Context"><Tag Name="Speed" TagType="Base" DataType="DINT"/></Tags>
+            <Programs Use="Context"><Program Use="Target" Name="Capping"/></Programs>
+        </Controller>
+    </RSLogix5000Content>"#;
+
+    fn program_names(loaded: &LoadedProject) -> Vec<String> {
+        let programs = loaded.l5x_controller.as_ref().unwrap().programs.as_ref().unwrap();
+        programs.program.iter().map(|p| p.name.clone()).collect()
+    }
+
+    #[test]
+    fn test_bundle_zip() {
+        let dir = std::env::temp_dir().join(format!("plceye_bundle_zip_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let archive = dir.join("Line.zip");
+        let zip = crate::zip::stored_zip(&[
+            ("Line/Filling.L5X", PARTIAL_FILLING),
+            ("Line/readme.txt", "not an export"),
+            ("Line/Capping.L5X", PARTIAL_CAPPING),
+        ]);
+        std::fs::write(&archive, zip).unwrap();
+
+        assert!(LoadedProject::is_bundle_path(&archive));
+        let loaded = LoadedProject::from_bundle(&archive).expect("Should load");
+        let bundle = loaded.bundle.as_ref().unwrap();
+        assert_eq!(bundle.members, ["Line/Capping.L5X", "Line/Filling.L5X"]);
+        assert_eq!(program_names(&loaded), ["Capping", "Filling"]);
+        assert_eq!(loaded.name(), "Line");
+
+        std::fs::write(&archive, b"PK garbage").unwrap();
+        let result = LoadedProject::from_bundle(&archive);
+        assert!(matches!(result, Err(Error::Bundle { kind: BundleErrorKind::InvalidArchive, .. })));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_bundle_folder() {
+        let dir = std::env::temp_dir().join(format!("plceye_bundle_dir_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("programs")).unwrap();
+        std::fs::write(dir.join("programs").join("Filling.L5X"), PARTIAL_FILLING).unwrap();
+        std::fs::write(dir.join("Capping.l5x"), PARTIAL_CAPPING).unwrap();
+
+        let loaded = LoadedProject::from_bundle(&dir).expect("Should load");
+        assert_eq!(loaded.bundle.as_ref().unwrap().members, ["Capping.l5x", "programs/Filling.L5X"]);
+        assert_eq!(program_names(&loaded), ["Capping", "Filling"]);
+
+        std::fs::write(dir.join("Broken.L5X"), "<RSLogix5000Content>").unwrap();
+        let result = LoadedProject::from_bundle(&dir);
+        assert!(
+            matches!(result, Err(Error::Bundle { kind: BundleErrorKind::InvalidMember { ref name }, .. }) if name == "Broken.L5X")
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_bundle_plcopen_folder() {
+        let dir = std::env::temp_dir().join(format!("plceye_bundle_plcopen_{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let member = |pou: &str| {
+            format!(
+                r#"<?xml version="1.0"?><project xmlns="http://www.plcopen.org/xml/tc6_0200"><contentHeader name="Line"/><types><pous><pou name="{}" pouType="program"/></pous></types></project>"#,
+                pou
+            )
+        };
+        std::fs::write(dir.join("library.xml"), member("Scale")).unwrap();
+        std::fs::write(dir.join("project.xml"), member("Main")).unwrap();
+        std::fs::write(dir.join("settings.xml"), "<settings/>").unwrap();
+
+        let loaded = LoadedProject::from_bundle(&dir).expect("Should load");
+        assert_eq!(loaded.format, FileFormat::PlcOpen);
+        assert_eq!(loaded.bundle.as_ref().unwrap().members, ["library.xml", "project.xml"]);
+        let pous = &loaded.plcopen_project.as_ref().unwrap().types.as_ref().unwrap().pous.as_ref().unwrap().pou;
+        assert_eq!(pous.len(), 2);
+
+        std::fs::write(dir.join("Filling.L5X"), PARTIAL_FILLING).unwrap();
+        let result = LoadedProject::from_bundle(&dir);
+        assert!(matches!(result, Err(Error::Bundle { kind: BundleErrorKind::MixedFormats, .. })));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
 }
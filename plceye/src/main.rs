@@ -1,10 +1,14 @@
 //! plceye - PLC Code Rule Detector CLI
 
+mod watch;
+
+use std::io::IsTerminal;
 use std::path::{Path, PathBuf};
 use std::process::ExitCode;
+use std::time::Duration;
 
 use clap::{Parser, Subcommand};
-use plceye::{RuleConfig, RuleDetector, Report, ParseStats};
+use plceye::{RuleConfig, RuleDetector, Report, ParseStats, Severity, Summary};
 
 #[derive(Parser)]
 #[command(name = "plceye")]
@@ -13,35 +17,153 @@ struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
 
-    /// L5X files to analyze
+    /// L5X or PLCopen files, ZIP archives of L5X exports or PLCopen files, or directories containing them, to analyze
+    /// (.ACD files with [general] acd_export_cmd)
     #[arg(value_name = "FILE")]
     files: Vec<PathBuf>,
 
+    /// Analyze each directory as one project bundle of partial L5X exports or PLCopen files
+    #[arg(long)]
+    bundle: bool,
+
     /// Configuration file (default: plceye.toml if exists)
     #[arg(short, long, value_name = "FILE")]
     config: Option<PathBuf>,
 
-    /// Minimum severity to report: info, warning, error
-    #[arg(short, long, value_name = "LEVEL", default_value = "info")]
-    severity: String,
+    /// Rule-set profile: default, strict, relaxed, safety (overrides the config's `profile`)
+    #[arg(short, long, value_name = "NAME")]
+    profile: Option<String>,
+
+    /// Minimum severity to report: info, warning, error (default: from config, else info)
+    #[arg(short, long, value_name = "LEVEL")]
+    severity: Option<String>,
+
+    /// Message catalog for findings: plceye.LANG.toml next to the config file
+    /// (or in the current directory), or the path of a .toml catalog
+    #[arg(long, value_name = "LANG")]
+    lang: Option<String>,
 
     /// Show file statistics only (no rule detection)
     #[arg(long)]
     stats: bool,
+
+    /// Number of most complex and most deeply nested ST routines listed by --stats
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    top: usize,
+
+    /// Give up on a file after this many seconds and continue with the next
+    #[arg(long, value_name = "SECS")]
+    timeout: Option<u64>,
+
+    /// With --stats, list the lines and statements of each POU
+    #[arg(long)]
+    verbose: bool,
+
+    /// Minimum severity that fails the run (exit code 1): info, warning, error
+    #[arg(long, value_name = "LEVEL", default_value = "info")]
+    fail_on: String,
+
+    /// Fail only when there are more than N warnings
+    #[arg(long, value_name = "N")]
+    max_warnings: Option<usize>,
+
+    /// Color the findings: auto (when stdout is a terminal and NO_COLOR is not set), always, never
+    #[arg(long, value_name = "WHEN", default_value = "auto")]
+    color: String,
+
+    /// Watch the input files and directories and re-analyze on change
+    #[arg(short, long)]
+    watch: bool,
+
+    /// Validate the configuration without analyzing
+    #[arg(long)]
+    check_config: bool,
+
+    /// Print the effective configuration (defaults, profile, file and CLI overrides) as TOML and exit
+    #[arg(long)]
+    print_config: bool,
 }
 
 #[derive(Subcommand)]
 enum Commands {
     /// Generate a default plceye.toml configuration file
     Init,
+
+    /// Compare two project files and list changed tags, routines, POUs and data types
+    Diff {
+        /// Old L5X or PLCopen file
+        #[arg(value_name = "OLD")]
+        old: PathBuf,
+
+        /// New L5X or PLCopen file
+        #[arg(value_name = "NEW")]
+        new: PathBuf,
+
+        /// Output format: text, json
+        #[arg(long, value_name = "FORMAT", default_value = "text")]
+        format: String,
+    },
+
+    /// Print whole-project metrics (counts, complexity, findings per rule) for dashboards
+    Metrics {
+        /// L5X or PLCopen file, or a ZIP archive or folder of L5X exports
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Output format: json
+        #[arg(long, value_name = "FORMAT", default_value = "json")]
+        format: String,
+    },
+
+    /// Count the RLL instructions and AOI calls of an L5X project, most frequent first
+    Instructions {
+        /// L5X file, or a ZIP archive or folder of L5X exports
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Output format: text, json
+        #[arg(long, value_name = "FORMAT", default_value = "text")]
+        format: String,
+    },
+
+    /// Apply the safe fixes of the findings (unused tag removal) to an L5X file
+    Fix {
+        /// L5X file to fix in place
+        #[arg(value_name = "FILE")]
+        file: PathBuf,
+
+        /// Show the changes without writing the file
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Load and analyze every L5X and PLCopen file of a folder and report the files that fail or panic
+    Selftest {
+        /// Folder of project files, searched recursively
+        #[arg(value_name = "DIR")]
+        dir: PathBuf,
+    },
 }
 
 fn main() -> ExitCode {
     let cli = Cli::parse();
 
     // Handle subcommands
-    if let Some(Commands::Init) = cli.command {
-        return init_config();
+    match cli.command {
+        Some(Commands::Init) => return init_config(),
+        Some(Commands::Diff { ref old, ref new, ref format }) => return diff_files(old, new, format),
+        Some(Commands::Metrics { ref file, ref format }) => return show_metrics(&cli, file, format),
+        Some(Commands::Instructions { ref file, ref format }) => return show_instructions(file, format),
+        Some(Commands::Fix { ref file, dry_run }) => return fix_file(&cli, file, dry_run),
+        Some(Commands::Selftest { ref dir }) => return selftest(&cli, dir),
+        None => {}
+    }
+
+    if cli.check_config {
+        return check_config(&cli);
+    }
+    if cli.print_config {
+        return print_config(&cli);
     }
 
     // Check for input files
@@ -54,41 +176,191 @@ fn main() -> ExitCode {
 
     // Handle --stats mode
     if cli.stats {
-        return show_stats(&cli.files);
+        return show_stats(&cli.files, cli.top, cli.verbose);
     }
 
-    // Load or create configuration
-    let mut config = if let Some(ref path) = cli.config {
-        match RuleConfig::from_file(path) {
-            Ok(c) => c,
-            Err(e) => {
-                eprintln!("Error loading config: {}", e);
-                return ExitCode::from(1);
-            }
+    let config = match load_config(&cli) {
+        Ok((config, _)) => config,
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            return ExitCode::from(1);
         }
-    } else if Path::new("plceye.toml").exists() {
-        match RuleConfig::from_file(Path::new("plceye.toml")) {
-            Ok(c) => c,
-            Err(e) => {
-                eprintln!("Warning: Failed to load plceye.toml: {}", e);
-                RuleConfig::default()
-            }
+    };
+
+    let Some(fail_on) = Severity::parse(&cli.fail_on) else {
+        eprintln!("Error: Invalid --fail-on level '{}' (expected info, warning or error)", cli.fail_on);
+        return ExitCode::from(2);
+    };
+
+    let Some(color) = use_color(&cli.color) else {
+        eprintln!("Error: Invalid --color '{}' (expected auto, always or never)", cli.color);
+        return ExitCode::from(2);
+    };
+
+    let acd = config.general.acd_export_cmd.is_some();
+    let mut detector = RuleDetector::with_config(config);
+    if let Some(seconds) = cli.timeout {
+        detector = detector.with_timeout(Duration::from_secs(seconds));
+    }
+
+    if cli.watch {
+        return watch::run(&cli.files, acd, color, || {
+            analyze_files(&detector, &collect_files(&cli.files, cli.bundle, acd), fail_on, cli.max_warnings, color)
+        });
+    }
+
+    analyze_files(&detector, &collect_files(&cli.files, cli.bundle, acd), fail_on, cli.max_warnings, color)
+}
+
+/// Whether to color the output for a `--color` value, `None` if invalid.
+///
+/// `auto` colors when stdout is a terminal and `NO_COLOR` is unset or empty.
+fn use_color(when: &str) -> Option<bool> {
+    match when {
+        "always" => Some(true),
+        "never" => Some(false),
+        "auto" => {
+            let no_color = std::env::var_os("NO_COLOR").is_some_and(|value| !value.is_empty());
+            Some(!no_color && std::io::stdout().is_terminal())
         }
-    } else {
-        RuleConfig::default()
+        _ => None,
+    }
+}
+
+/// Load the configuration from `--config`, else `plceye.toml` if it exists,
+/// else the profile defaults, and apply the CLI overrides. Also returns the
+/// file it was read from.
+fn load_config(cli: &Cli) -> plceye::Result<(RuleConfig, Option<PathBuf>)> {
+    let profile = cli.profile.as_deref();
+    let path = cli.config.clone().or_else(|| {
+        let default = PathBuf::from("plceye.toml");
+        default.exists().then_some(default)
+    });
+    let mut config = match path {
+        Some(ref path) => RuleConfig::from_file_with_profile(path, profile)?,
+        None => profile.map(RuleConfig::profile).transpose()?.unwrap_or_default(),
     };
 
     // Apply severity from CLI
-    config.general.min_severity = cli.severity.clone();
+    if let Some(ref severity) = cli.severity {
+        config.general.min_severity = severity.clone();
+        config.validate()?;
+    }
 
-    let detector = RuleDetector::with_config(config);
+    // Catalog templates fill in rules without one in [messages]
+    if let Some(ref lang) = cli.lang {
+        let dir = path.as_deref().and_then(Path::parent);
+        let catalog = plceye::messages::catalog_path(lang, dir);
+        config.add_messages(plceye::messages::load_catalog(&catalog)?);
+        config.validate()?;
+    }
+    Ok((config, path))
+}
+
+/// Validate the configuration.
+fn check_config(cli: &Cli) -> ExitCode {
+    match load_config(cli) {
+        Ok((_, Some(path))) => {
+            println!("Configuration {} is valid", path.display());
+            ExitCode::SUCCESS
+        }
+        Ok((_, None)) => {
+            println!("No configuration file; using the built-in settings");
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
+
+/// Print the effective configuration as TOML that `--config` accepts.
+fn print_config(cli: &Cli) -> ExitCode {
+    let config = match load_config(cli) {
+        Ok((config, _)) => config,
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            return ExitCode::from(1);
+        }
+    };
+    match toml::to_string(&config) {
+        Ok(text) => {
+            print!("{}", text);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+/// Expand directories to the L5X, PLCopen XML and ZIP files they contain,
+/// and `.ACD` files with `acd`, the `acd_export_cmd` being set. With `bundle`,
+/// directories are kept as they are and analyzed as one project.
+fn collect_files(paths: &[PathBuf], bundle: bool, acd: bool) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    for path in paths {
+        if bundle || !path.is_dir() {
+            files.push(path.clone());
+            continue;
+        }
+        let Ok(entries) = std::fs::read_dir(path) else {
+            continue;
+        };
+        let (mut found, skipped): (Vec<PathBuf>, Vec<PathBuf>) = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|p| p.is_file() && is_project_file(p, true))
+            .partition(|p| is_project_file(p, acd));
+        if !skipped.is_empty() {
+            eprintln!(
+                "Warning: {}: skipped {} .ACD file(s); set [general] acd_export_cmd to analyze them",
+                path.display(),
+                skipped.len()
+            );
+        }
+        found.sort();
+        files.extend(found);
+    }
+    files
+}
+
+/// Check if a path has an L5X, XML or ZIP extension, is a gzip-compressed
+/// L5X or XML file, or with `acd` has an ACD extension.
+fn is_project_file(path: &Path, acd: bool) -> bool {
+    has_extension(path, &["l5x", "xml", "zip"])
+        || (has_extension(path, &["gz"]) && has_extension(&path.with_extension(""), &["l5x", "xml"]))
+        || (acd && is_acd_file(path))
+}
+
+/// Check if a path has an ACD extension.
+fn is_acd_file(path: &Path) -> bool {
+    has_extension(path, &["acd"])
+}
+
+/// Check if a path has one of `extensions`, ignoring case.
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.iter().any(|ext| e.eq_ignore_ascii_case(ext)))
+}
+
+/// Analyze files, print the findings and summary, and return the exit code.
+fn analyze_files(
+    detector: &RuleDetector,
+    files: &[PathBuf],
+    fail_on: Severity,
+    max_warnings: Option<usize>,
+    color: bool,
+) -> ExitCode {
     let min_severity = detector.min_severity();
 
     // Collect all results
     let mut all_reports: Vec<(String, Report)> = Vec::new();
     let mut has_errors = false;
 
-    for file in &cli.files {
+    for file in files {
         match detector.analyze_file(file) {
             Ok(report) => {
                 all_reports.push((file.display().to_string(), report));
@@ -100,33 +372,55 @@ fn main() -> ExitCode {
         }
     }
 
-    // Calculate total issues
-    let total_issues: usize = all_reports
-        .iter()
-        .map(|(_, r)| r.filter_by_severity(min_severity).len())
-        .sum();
+    // Summarize reported issues
+    let summary = Summary::from_rules(
+        all_reports
+            .iter()
+            .flat_map(|(_, r)| r.filter_by_severity(min_severity)),
+    );
+    let total_issues = summary.total();
 
     // Output results
     for (file, report) in &all_reports {
+        if let Some(export) = report.export {
+            eprintln!("Note: {} is a partial export. {}", file, export);
+        }
+        for duplicate in &report.duplicates {
+            eprintln!("Warning: {}: {}", file, duplicate);
+        }
+        if !report.protected.is_empty() {
+            eprintln!(
+                "Note: {}: {} source-protected POU(s) not analyzed: {}",
+                file,
+                report.protected.len(),
+                report.protected.join(", ")
+            );
+        }
         let filtered = report.filter_by_severity(min_severity);
         if !filtered.is_empty() {
             println!("\n=== {} ===", file);
             for rule in filtered {
-                println!("{}", rule);
+                if color {
+                    println!("{}", rule.colored());
+                } else {
+                    println!("{}", rule);
+                }
             }
         }
     }
 
     println!();
     if total_issues == 0 {
-        println!("No issues found in {} file(s).", cli.files.len());
+        println!("No issues found in {} file(s).", files.len());
     } else {
-        println!("Found {} issue(s) in {} file(s).", total_issues, cli.files.len());
+        println!("{}", summary);
+        println!();
+        println!("Found {} issue(s) in {} file(s).", total_issues, files.len());
     }
 
     if has_errors {
         ExitCode::from(2)
-    } else if total_issues > 0 {
+    } else if summary.fails(fail_on, max_warnings) {
         ExitCode::from(1)
     } else {
         ExitCode::SUCCESS
@@ -153,7 +447,303 @@ fn init_config() -> ExitCode {
     }
 }
 
-fn show_stats(files: &[PathBuf]) -> ExitCode {
+/// Print the differences between two project files.
+///
+/// Exits with 0 when the projects are equivalent, 1 when they differ and 2 on errors.
+fn diff_files(old: &Path, new: &Path, format: &str) -> ExitCode {
+    let json = match format.to_lowercase().as_str() {
+        "text" => false,
+        "json" => true,
+        _ => {
+            eprintln!("Error: Invalid --format '{}' (expected text or json)", format);
+            return ExitCode::from(2);
+        }
+    };
+
+    let load = |path: &Path| {
+        plceye::LoadedProject::from_file(path).map_err(|e| eprintln!("Error loading {}: {}", path.display(), e))
+    };
+    let (Ok(old_project), Ok(new_project)) = (load(old), load(new)) else {
+        return ExitCode::from(2);
+    };
+
+    let diff = plceye::diff_projects(&old_project, &new_project);
+    if json {
+        match serde_json::to_string_pretty(&diff) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(2);
+            }
+        }
+    } else {
+        println!("{}", diff);
+    }
+
+    if diff.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+fn show_metrics(cli: &Cli, file: &Path, format: &str) -> ExitCode {
+    if !format.eq_ignore_ascii_case("json") {
+        eprintln!("Error: Invalid --format '{}' (expected json)", format);
+        return ExitCode::from(2);
+    }
+
+    let config = match load_config(cli) {
+        Ok((config, _)) => config,
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    let project = if plceye::LoadedProject::is_bundle_path(file) {
+        plceye::LoadedProject::from_bundle(file)
+    } else {
+        plceye::LoadedProject::from_file(file)
+    };
+    let metrics = project.and_then(|project| RuleDetector::with_config(config).metrics(&project));
+    let output = match metrics {
+        Ok(metrics) => serde_json::to_string_pretty(&metrics).map_err(|e| e.to_string()),
+        Err(e) => Err(format!("{}: {}", file.display(), e)),
+    };
+    match output {
+        Ok(output) => {
+            println!("{}", output);
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(2)
+        }
+    }
+}
+
+fn fix_file(cli: &Cli, file: &Path, dry_run: bool) -> ExitCode {
+    if plceye::LoadedProject::is_bundle_path(file) {
+        eprintln!("Error: {}: fixes can only be applied to a single file", file.display());
+        return ExitCode::from(2);
+    }
+    let config = match load_config(cli) {
+        Ok((config, _)) => config,
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    // Edits are made on the text, so only UTF-8 files are written back
+    let bytes = match std::fs::read(file) {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            eprintln!("Error reading {}: {}", file.display(), e);
+            return ExitCode::from(2);
+        }
+    };
+    let (bom, body) = match bytes.strip_prefix(b"\xEF\xBB\xBF") {
+        Some(body) => (&bytes[..3], body),
+        None => (&[][..], &bytes[..]),
+    };
+    let Ok(content) = std::str::from_utf8(body) else {
+        eprintln!("Error: {}: fixes can only be applied to UTF-8 files", file.display());
+        return ExitCode::from(2);
+    };
+
+    let detector = RuleDetector::with_config(config);
+    let project = match plceye::LoadedProject::from_str(content, Some(file)) {
+        Ok(project) => project,
+        Err(e) => {
+            eprintln!("Error loading {}: {}", file.display(), e);
+            return ExitCode::from(2);
+        }
+    };
+    let report = match detector.analyze(&project) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Error analyzing {}: {}", file.display(), e);
+            return ExitCode::from(2);
+        }
+    };
+    let findings = report.filter_by_severity(detector.min_severity());
+    let result = if project.l5x_controller.is_some() {
+        plceye::apply_fixes(content, findings)
+    } else {
+        // No fixes are applied to PLCopen files yet
+        Ok(plceye::FixResult {
+            content: content.to_string(),
+            applied: Vec::new(),
+            unfixed: findings.len(),
+        })
+    };
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => {
+            eprintln!("Error: {}: {}", file.display(), e);
+            return ExitCode::from(2);
+        }
+    };
+
+    if dry_run {
+        for fix in &result.applied {
+            println!("@@ line {} @@ {}", fix.line, fix.description);
+            for line in fix.removed.lines() {
+                println!("-{}", line);
+            }
+        }
+    } else if !result.applied.is_empty() {
+        let mut output = bom.to_vec();
+        output.extend_from_slice(result.content.as_bytes());
+        if let Err(e) = std::fs::write(file, output) {
+            eprintln!("Error writing {}: {}", file.display(), e);
+            return ExitCode::from(2);
+        }
+    }
+
+    println!(
+        "{} fix(es) {}, {} finding(s) without an applicable fix",
+        result.applied.len(),
+        if dry_run { "to apply" } else { "applied" },
+        result.unfixed
+    );
+    ExitCode::SUCCESS
+}
+
+fn show_instructions(file: &Path, format: &str) -> ExitCode {
+    let json = match format.to_lowercase().as_str() {
+        "text" => false,
+        "json" => true,
+        _ => {
+            eprintln!("Error: Invalid --format '{}' (expected text or json)", format);
+            return ExitCode::from(2);
+        }
+    };
+
+    let project = if plceye::LoadedProject::is_bundle_path(file) {
+        plceye::LoadedProject::from_bundle(file)
+    } else {
+        plceye::LoadedProject::from_file(file)
+    };
+    let controller = match project {
+        Ok(plceye::LoadedProject { l5x_controller: Some(controller), .. }) => controller,
+        Ok(_) => {
+            eprintln!("Error: {}: instruction counts need an L5X project", file.display());
+            return ExitCode::from(2);
+        }
+        Err(e) => {
+            eprintln!("Error loading {}: {}", file.display(), e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let analysis = plceye::analyze_controller(&controller);
+    let instructions = plceye::analysis::instruction_histogram(&analysis);
+    let aoi_calls = plceye::analysis::aoi_call_histogram(&analysis);
+    let instructions = plceye::analysis::by_frequency(&instructions);
+    let aoi_calls = plceye::analysis::by_frequency(&aoi_calls);
+
+    if json {
+        let entries = |histogram: &[(&str, usize)]| {
+            histogram
+                .iter()
+                .map(|(name, count)| serde_json::json!({ "name": name, "count": count }))
+                .collect::<Vec<_>>()
+        };
+        let output = serde_json::json!({
+            "instructions": entries(&instructions),
+            "aoi_calls": entries(&aoi_calls),
+        });
+        match serde_json::to_string_pretty(&output) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                return ExitCode::from(2);
+            }
+        }
+        return ExitCode::SUCCESS;
+    }
+
+    let total: usize = instructions.iter().map(|(_, count)| count).sum();
+    println!("Instructions ({} distinct, {} total):", instructions.len(), total);
+    for (name, count) in &instructions {
+        println!("  {:>6}  {}", count, name);
+    }
+    if !aoi_calls.is_empty() {
+        println!();
+        println!("AOI calls (RLL and ST):");
+        for (name, count) in &aoi_calls {
+            println!("  {:>6}  {}", count, name);
+        }
+    }
+    ExitCode::SUCCESS
+}
+
+fn selftest(cli: &Cli, dir: &Path) -> ExitCode {
+    if !dir.is_dir() {
+        eprintln!("Error: {} is not a directory", dir.display());
+        return ExitCode::from(2);
+    }
+    let config = match load_config(cli) {
+        Ok((config, _)) => config,
+        Err(e) => {
+            eprintln!("Error loading config: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+    let mut detector = RuleDetector::with_config(config);
+    if let Some(seconds) = cli.timeout {
+        detector = detector.with_timeout(Duration::from_secs(seconds));
+    }
+
+    let corpus = plceye::Corpus::run(dir, &detector);
+    if corpus.files.is_empty() {
+        println!("No L5X or PLCopen files found in {}.", dir.display());
+        return ExitCode::SUCCESS;
+    }
+
+    println!("{:<10} {:>6} {:>6} {:>6} {:>8}", "Format", "Files", "Passed", "Failed", "Panicked");
+    for counts in corpus.counts() {
+        let format = counts.format.map_or("unknown".to_string(), |f| f.to_string());
+        println!(
+            "{:<10} {:>6} {:>6} {:>6} {:>8}",
+            format, counts.files, counts.passed, counts.failed, counts.panicked
+        );
+    }
+
+    let failures: Vec<_> = corpus.failures().collect();
+    if !failures.is_empty() {
+        println!();
+        println!("Failures:");
+        for file in &failures {
+            match &file.outcome {
+                plceye::CorpusOutcome::Failed(error) => println!("  {}: {}", file.path.display(), error),
+                plceye::CorpusOutcome::Panicked(message) => {
+                    println!("  {}: panicked: {}", file.path.display(), message)
+                }
+                plceye::CorpusOutcome::Passed => {}
+            }
+        }
+    }
+
+    let total = corpus.files.len();
+    println!();
+    println!(
+        "Passed {} of {} file(s) ({:.1}%).",
+        corpus.passed(),
+        total,
+        corpus.passed() as f64 * 100.0 / total as f64
+    );
+
+    if failures.is_empty() {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::from(1)
+    }
+}
+
+fn show_stats(files: &[PathBuf], top: usize, verbose: bool) -> ExitCode {
     let detector = RuleDetector::new();
     let mut has_errors = false;
 
@@ -162,31 +752,20 @@ fn show_stats(files: &[PathBuf]) -> ExitCode {
         
         // Try to load the project to detect format
         match plceye::LoadedProject::from_file(file) {
-            Ok(project) => {
-                if project.format == plceye::FileFormat::PlcOpen {
-                    // PLCopen format - show PLCopen stats
-                    match detector.get_plcopen_stats(&project) {
-                        Ok(stats) => {
-                            print_plcopen_stats(&stats);
-                        }
-                        Err(e) => {
-                            eprintln!("Error: {}", e);
-                            has_errors = true;
-                        }
-                    }
-                } else {
-                    // L5X format - show L5X stats
-                    match detector.get_stats(&project) {
-                        Ok(stats) => {
-                            print_stats(&stats);
-                        }
-                        Err(e) => {
-                            eprintln!("Error: {}", e);
-                            has_errors = true;
-                        }
+            Ok(project) => match detector.stats(&project) {
+                Ok(plceye::ProjectStats::PlcOpen(stats)) => print_plcopen_stats(&stats, top, verbose),
+                Ok(plceye::ProjectStats::L5x(stats)) => {
+                    if let Some(export) = project.export.filter(|e| e.is_partial()) {
+                        println!("{}", export);
+                        println!();
                     }
+                    print_stats(&stats, top, verbose);
                 }
-            }
+                Err(e) => {
+                    eprintln!("Error: {}", e);
+                    has_errors = true;
+                }
+            },
             Err(e) => {
                 eprintln!("Error: {}", e);
                 has_errors = true;
@@ -202,7 +781,7 @@ fn show_stats(files: &[PathBuf]) -> ExitCode {
     }
 }
 
-fn print_stats(stats: &ParseStats) {
+fn print_stats(stats: &ParseStats, top: usize, verbose: bool) {
     println!("Programs:           {:>6}", stats.programs);
     println!("AOIs:               {:>6}", stats.aois);
     println!("Routines:           {:>6}", stats.routines);
@@ -218,22 +797,32 @@ fn print_stats(stats: &ParseStats) {
     println!("  In AOIs:          {:>6}", stats.st_routines_aois);
     println!("  Parsed OK:        {:>6}", stats.st_parsed_ok);
     println!("  Parse errors:     {:>6}", stats.st_parsed_err);
+    if stats.st_recovered > 0 {
+        println!("  Recovered:        {:>6}", stats.st_recovered);
+        for skipped in &stats.st_skipped {
+            println!("    {}", skipped);
+        }
+    }
     println!();
     println!("Tag references:     {:>6}", stats.tag_references);
     println!("Unique tags:        {:>6}", stats.unique_tags);
     
     // Only show complexity stats if there are ST routines
-    if stats.st_parsed_ok > 0 {
+    if stats.st_parsed_ok + stats.st_recovered > 0 {
         println!();
         println!("ST Complexity:");
         println!("  Max complexity:   {:>6}", stats.st_max_complexity);
         println!("  Avg complexity:   {:>6.1}", stats.st_avg_complexity);
         println!("  Max nesting:      {:>6}", stats.st_max_nesting);
         println!("  Avg nesting:      {:>6.1}", stats.st_avg_nesting);
+        println!("  Comment density:  {:>5.1}%", stats.st_comments.percent());
+        print_complexity_distribution(&stats.st_complexity, top);
+        print_nesting_distribution(&stats.st_nesting, top);
     }
+    print_sizes(&stats.sizes, verbose);
 }
 
-fn print_plcopen_stats(stats: &plceye::PlcopenStats) {
+fn print_plcopen_stats(stats: &plceye::PlcopenStats, top: usize, verbose: bool) {
     println!("POUs (total):       {:>6}", stats.pous);
     println!("  Functions:        {:>6}", stats.functions);
     println!("  Function Blocks:  {:>6}", stats.function_blocks);
@@ -246,6 +835,83 @@ fn print_plcopen_stats(stats: &plceye::PlcopenStats) {
     println!("  FBD (Function Block):     {:>6}", stats.fbd_bodies);
     println!("  LD (Ladder Diagram):      {:>6}", stats.ld_bodies);
     println!("  SFC (Sequential Chart):   {:>6}", stats.sfc_bodies);
+    if stats.st_bodies > 0 {
+        println!("  ST comment density:       {:>5.1}%", stats.st_comments.percent());
+    }
     println!();
     println!("Variables:          {:>6}", stats.variables);
+
+    if !stats.st_complexity.routines.is_empty() {
+        println!();
+        println!("ST Complexity:");
+        println!("  Max complexity:   {:>6}", stats.st_complexity.max());
+        println!("  Avg complexity:   {:>6.1}", stats.st_complexity.average());
+        println!("  Max nesting:      {:>6}", stats.st_nesting.max());
+        println!("  Avg nesting:      {:>6.1}", stats.st_nesting.average());
+        print_complexity_distribution(&stats.st_complexity, top);
+        print_nesting_distribution(&stats.st_nesting, top);
+    }
+    print_sizes(&stats.sizes, verbose);
+}
+
+/// Print lines and statements by language, and of each POU if `verbose`.
+fn print_sizes(sizes: &plceye::SizeMetrics, verbose: bool) {
+    if sizes.pous.is_empty() {
+        return;
+    }
+
+    println!();
+    println!("{:<18} {:>9} {:>11} {:>11}", "Code Size:", "Lines", "Code lines", "Statements");
+    for (language, size) in sizes.by_language() {
+        println!("  {:<16} {:>9} {:>11} {:>11}", language, size.lines, size.code_lines, size.statements);
+    }
+    let total = sizes.total();
+    println!("  {:<16} {:>9} {:>11} {:>11}", "Total", total.lines, total.code_lines, total.statements);
+
+    if verbose {
+        println!("  Per POU:");
+        for pou in &sizes.pous {
+            println!(
+                "    {:>6} {:>6} {:>6}  {} ({})",
+                pou.size.lines, pou.size.code_lines, pou.size.statements, pou.name, pou.language
+            );
+        }
+    }
+}
+
+/// Print the complexity histogram and the most complex routines.
+fn print_complexity_distribution(distribution: &plceye::ComplexityDistribution, top: usize) {
+    println!("  Distribution:");
+    print_histogram(&plceye::COMPLEXITY_BUCKETS, &distribution.buckets);
+
+    if top > 0 {
+        println!("  Most complex:");
+        for (name, complexity) in distribution.top(top) {
+            println!("    {:>4}  {}", complexity, name);
+        }
+    }
+}
+
+/// Print the nesting depth histogram and the most deeply nested routines.
+fn print_nesting_distribution(distribution: &plceye::NestingDistribution, top: usize) {
+    println!("  Nesting depth:");
+    print_histogram(&plceye::NESTING_BUCKETS, &distribution.buckets);
+
+    if top > 0 {
+        println!("  Most deeply nested:");
+        for (name, depth) in distribution.top(top) {
+            println!("    {:>4}  {}", depth, name);
+        }
+    }
+}
+
+/// Print one bar per bucket, scaled to the largest count.
+fn print_histogram(labels: &[(&str, usize, usize)], counts: &[usize]) {
+    const BAR_WIDTH: usize = 20;
+    let largest = counts.iter().copied().max().unwrap_or(0).max(1);
+
+    for ((label, _, _), count) in labels.iter().zip(counts) {
+        let bar = "#".repeat((count * BAR_WIDTH + largest - 1) / largest);
+        println!("    {:>5}  {:<width$} {:>4}", label, bar, count, width = BAR_WIDTH);
+    }
 }
@@ -0,0 +1,237 @@
+//! Message templates for findings.
+//!
+//! A template replaces the English message of a rule's findings, for
+//! translations or wording that matches in-house standards. Templates are
+//! keyed by rule code or name in the `[messages]` table of the configuration
+//! or of a message catalog file selected with `--lang`. Placeholders in
+//! braces take the values of the finding:
+//!
+//! - `{code}`, `{rule}` - rule code (`M0001`) and name (`cyclomatic-complexity`)
+//! - `{severity}`, `{location}`, `{identifier}` - as reported
+//! - `{name}` - the identifier, the tag, routine or type the finding is about
+//! - `{message}` - the built-in English message
+//! - rule-specific values such as `{threshold}`, see [`RuleKind::params`]
+//!
+//! `{{` and `}}` stand for literal braces. Rules without a template keep the
+//! English message; [`RuleKind::default_template`] gives the English
+//! template of each rule as a starting point. Keys that name no built-in
+//! rule are rejected.
+
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+
+use crate::config::{check_messages, schema_error};
+use crate::report::{Rule, RuleKind};
+use crate::{Error, Result};
+
+/// Placeholders every template may use.
+pub const PLACEHOLDERS: [&str; 7] = ["code", "rule", "severity", "location", "identifier", "name", "message"];
+
+/// A message catalog file: a `[messages]` table like the configuration's.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct Catalog {
+    messages: BTreeMap<String, String>,
+}
+
+/// Path of the message catalog for `lang`.
+///
+/// A `lang` ending in `.toml` is a path; otherwise the catalog is
+/// `plceye.<lang>.toml` in `dir` (the directory of the configuration file),
+/// or in the current directory.
+pub fn catalog_path(lang: &str, dir: Option<&Path>) -> PathBuf {
+    if lang.ends_with(".toml") {
+        return PathBuf::from(lang);
+    }
+    dir.unwrap_or(Path::new("")).join(format!("plceye.{}.toml", lang))
+}
+
+/// Load the templates of a message catalog file, checked like the
+/// configuration's `[messages]` table.
+pub fn load_catalog(path: &Path) -> Result<BTreeMap<String, String>> {
+    let content = std::fs::read_to_string(path).map_err(|e| Error::FileRead {
+        path: path.display().to_string(),
+        source: e,
+    })?;
+    let error = |kind| Error::MessageCatalog {
+        path: path.display().to_string(),
+        kind,
+    };
+    let catalog: Catalog = toml::from_str(&content).map_err(|e| error(schema_error(&content, &e)))?;
+    check_messages(&content, &catalog.messages).map_err(error)?;
+    Ok(catalog.messages)
+}
+
+/// Check that a template for `rule` (a code or name) is well-formed and
+/// uses only known placeholders.
+///
+/// Returns a description of the first problem.
+pub fn check_template(rule: &str, template: &str) -> std::result::Result<(), String> {
+    let params = RuleKind::from_key(rule).map_or(&[][..], |kind| kind.params());
+    for segment in parse(template)? {
+        if let Segment::Placeholder(name) = segment {
+            if !PLACEHOLDERS.contains(&name) && !params.contains(&name) {
+                let mut known: Vec<&str> = PLACEHOLDERS.to_vec();
+                known.extend(params);
+                return Err(format!("unknown placeholder '{{{}}}' (expected one of {})", name, known.join(", ")));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Render a template with the values of a finding.
+///
+/// Placeholders without a value are kept as written.
+pub fn render(template: &str, rule: &Rule) -> String {
+    let Ok(segments) = parse(template) else {
+        return template.to_string();
+    };
+    let mut message = String::new();
+    for segment in segments {
+        match segment {
+            Segment::Text(text) => message.push_str(text),
+            Segment::Placeholder(name) => match placeholder_value(rule, name) {
+                Some(value) => message.push_str(&value),
+                None => {
+                    message.push('{');
+                    message.push_str(name);
+                    message.push('}');
+                }
+            },
+        }
+    }
+    message
+}
+
+fn placeholder_value(rule: &Rule, name: &str) -> Option<String> {
+    Some(match name {
+        "code" => rule.id().to_string(),
+        "rule" => rule.name().to_string(),
+        "severity" => rule.severity().to_string(),
+        "location" => rule.location().to_string(),
+        "identifier" | "name" => rule.identifier().to_string(),
+        "message" => rule.message().to_string(),
+        _ => rule.param(name)?.to_string(),
+    })
+}
+
+/// A piece of a template.
+enum Segment<'a> {
+    Text(&'a str),
+    Placeholder(&'a str),
+}
+
+/// Split a template into text and placeholders.
+fn parse(template: &str) -> std::result::Result<Vec<Segment<'_>>, String> {
+    let mut segments = Vec::new();
+    let mut rest = template;
+    while let Some(index) = rest.find(['{', '}']) {
+        if index > 0 {
+            segments.push(Segment::Text(&rest[..index]));
+        }
+        let tail = &rest[index..];
+        if tail.starts_with("{{") || tail.starts_with("}}") {
+            segments.push(Segment::Text(&tail[..1]));
+            rest = &tail[2..];
+        } else if tail.starts_with('}') {
+            return Err("unmatched '}' (write '}}' for a literal brace)".to_string());
+        } else {
+            let end = tail.find('}').ok_or("unclosed '{' (write '{{' for a literal brace)")?;
+            let name = &tail[1..end];
+            if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(format!("invalid placeholder '{}'", &tail[..=end]));
+            }
+            segments.push(Segment::Placeholder(name));
+            rest = &tail[end + 1..];
+        }
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    Ok(segments)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ConfigErrorKind;
+    use crate::report::Severity;
+
+    fn finding() -> Rule {
+        Rule::new(
+            RuleKind::CyclomaticComplexity,
+            Severity::Warning,
+            "Program:Main",
+            "Filling",
+            "Routine 'Filling' has cyclomatic complexity of 14 (max: 10)",
+        )
+        .with_param("complexity", 14)
+        .with_param("threshold", 10)
+    }
+
+    #[test]
+    fn test_render() {
+        let template = "{{{code}}} Routine '{identifier}' in {location}: Komplexität {complexity} > {threshold}";
+        assert_eq!(
+            render(template, &finding()),
+            "{M0001} Routine 'Filling' in Program:Main: Komplexität 14 > 10"
+        );
+        assert_eq!(render("{severity}: {message}", &finding()), "warning: Routine 'Filling' has cyclomatic complexity of 14 (max: 10)");
+    }
+
+    #[test]
+    fn test_check_template() {
+        assert!(check_template("M0001", "{identifier}: {complexity} > {threshold}").is_ok());
+        assert!(check_template("cyclomatic-complexity", "{rule} {threshold}").is_ok());
+
+        let error = check_template("S0001", "Tag {identifer} unused").unwrap_err();
+        assert!(error.starts_with("unknown placeholder '{identifer}'"), "{}", error);
+        // Rule-specific values belong to their rule only
+        assert!(check_template("S0001", "{threshold}").is_err());
+        assert!(check_template("S0001", "{identifier").unwrap_err().starts_with("unclosed '{'"));
+        assert!(check_template("S0001", "a } b").is_err());
+        assert!(check_template("S0001", "{not valid}").is_err());
+    }
+
+    #[test]
+    fn test_default_templates() {
+        assert_eq!(
+            render(RuleKind::CyclomaticComplexity.default_template(), &finding()),
+            "Routine 'Filling' has cyclomatic complexity of 14 (max: 10)"
+        );
+        assert_eq!(RuleKind::UnusedTag.default_template(), "{message}");
+        for kind in RuleKind::ALL {
+            assert!(check_template(kind.code(), kind.default_template()).is_ok(), "{}", kind.code());
+        }
+    }
+
+    #[test]
+    fn test_load_catalog() {
+        let path = std::env::temp_dir().join(format!("plceye_catalog_{}.toml", std::process::id()));
+        std::fs::write(&path, "[messages]\nS0001 = \"Tag {name}\"\nX9999 = \"{name}\"\n").unwrap();
+        let error = load_catalog(&path).unwrap_err();
+        std::fs::write(&path, "[messages]\nS0001 = 1\n").unwrap();
+        let syntax = load_catalog(&path).unwrap_err();
+        std::fs::write(&path, "[messages]\nS0001 = \"Tag {name}\"\n").unwrap();
+        let catalog = load_catalog(&path).unwrap();
+        std::fs::remove_file(&path).ok();
+
+        assert!(matches!(
+            error,
+            Error::MessageCatalog { kind: ConfigErrorKind::UnknownKey { ref key, line: 3 }, .. } if key == "messages.X9999"
+        ));
+        assert!(error.to_string().contains(&path.display().to_string()), "{}", error);
+        assert!(matches!(syntax, Error::MessageCatalog { kind: ConfigErrorKind::InvalidValue { line: Some(2), .. }, .. }));
+        assert_eq!(catalog["S0001"], "Tag {name}");
+    }
+
+    #[test]
+    fn test_catalog_path() {
+        assert_eq!(catalog_path("de", Some(Path::new("cfg"))), Path::new("cfg/plceye.de.toml"));
+        assert_eq!(catalog_path("de", None), Path::new("plceye.de.toml"));
+        assert_eq!(catalog_path("texts/fr.toml", Some(Path::new("cfg"))), Path::new("texts/fr.toml"));
+    }
+}
@@ -0,0 +1,362 @@
+//! Whole-project metrics for trend dashboards.
+//!
+//! [`ProjectMetrics`] is one JSON object per analyzed project: size counts,
+//! language breakdown, ST complexity and finding counts per rule, plus the
+//! full L5X or PLCopen statistics. Unlike the findings output it holds only
+//! aggregates, so it can be stored per release and graphed over time.
+//!
+//! The schema is versioned by [`METRICS_SCHEMA_VERSION`]. Fields are only
+//! added within a version; renaming or removing a field, or changing its
+//! meaning, increments the version. Values that a format does not provide
+//! are `null`, never missing.
+
+use std::collections::BTreeMap;
+
+use l5x::{Controller, UDIDefinitionContent};
+use serde::Serialize;
+
+use crate::analysis::{ComplexityDistribution, ParseStats, PlcopenStats, SizeMetrics, COMPLEXITY_BUCKETS};
+use crate::loader::{FileFormat, LoadedProject};
+use crate::report::{Report, Severity, Summary};
+
+/// Version of the [`ProjectMetrics`] JSON schema.
+pub const METRICS_SCHEMA_VERSION: u32 = 1;
+
+/// Parse statistics of a project, in the shape of its format.
+///
+/// Serializes with a `format` field of `"l5x"` or `"plcopen"` next to the
+/// statistics.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "format", rename_all = "lowercase")]
+pub enum ProjectStats {
+    /// Statistics of an L5X project
+    L5x(ParseStats),
+    /// Statistics of a PLCopen project
+    PlcOpen(PlcopenStats),
+}
+
+impl ProjectStats {
+    /// Lines and statements of each POU, with totals by language.
+    pub fn sizes(&self) -> &SizeMetrics {
+        match self {
+            ProjectStats::L5x(stats) => &stats.sizes,
+            ProjectStats::PlcOpen(stats) => &stats.sizes,
+        }
+    }
+}
+
+/// Aggregate metrics of one project.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProjectMetrics {
+    /// Schema version ([`METRICS_SCHEMA_VERSION`])
+    pub schema_version: u32,
+    /// plceye version that produced the metrics
+    pub tool_version: &'static str,
+    /// Project (controller or PLCopen content header) name
+    pub project: String,
+    /// Analyzed file, if loaded from disk
+    pub file: Option<String>,
+    /// Source format: "l5x" or "plcopen"
+    pub format: &'static str,
+    /// Size counts
+    pub counts: MetricsCounts,
+    /// Routines (L5X) or POU bodies (PLCopen) per language: fbd, il, ld, rll, sfc, st
+    pub languages: BTreeMap<&'static str, usize>,
+    /// Complexity of the parsed ST routines
+    pub complexity: ComplexityMetrics,
+    /// Finding counts
+    pub findings: FindingMetrics,
+    /// Full L5X statistics (L5X projects only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub l5x_stats: Option<ParseStats>,
+    /// Full PLCopen statistics (PLCopen projects only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub plcopen_stats: Option<PlcopenStats>,
+}
+
+/// Size counts of a project.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MetricsCounts {
+    /// Programs (L5X programs, PLCopen program POUs)
+    pub programs: usize,
+    /// Add-On Instructions (L5X) or function blocks (PLCopen)
+    pub function_blocks: usize,
+    /// Functions (PLCopen only)
+    pub functions: Option<usize>,
+    /// All POUs: programs, AOIs or function blocks, and functions
+    pub pous: usize,
+    /// Routines (L5X) or POU bodies (PLCopen)
+    pub routines: usize,
+    /// Declared tags (L5X controller and program tags) or variables (PLCopen)
+    pub tags: usize,
+    /// Distinct tags referenced by logic (L5X only)
+    pub referenced_tags: Option<usize>,
+    /// RLL rungs (L5X only)
+    pub rungs: Option<usize>,
+}
+
+/// Complexity of the parsed ST routines of a project.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ComplexityMetrics {
+    /// ST routines or bodies with a computed complexity
+    pub st_routines: usize,
+    /// Highest cyclomatic complexity
+    pub max: usize,
+    /// Average cyclomatic complexity
+    pub avg: f32,
+    /// Deepest nesting (L5X only)
+    pub max_nesting: Option<usize>,
+    /// Average nesting (L5X only)
+    pub avg_nesting: Option<f32>,
+    /// Routines per complexity range ("1-5", "6-10", "11-20", "21+")
+    pub buckets: Vec<ComplexityBucket>,
+    /// Comment share of the ST characters, in percent
+    pub comment_density: f32,
+}
+
+/// Number of routines in one complexity range.
+#[derive(Debug, Clone, Serialize)]
+pub struct ComplexityBucket {
+    /// Complexity range
+    pub range: &'static str,
+    /// Number of routines
+    pub count: usize,
+}
+
+/// Finding counts of a project.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FindingMetrics {
+    /// All findings
+    pub total: usize,
+    /// Findings with severity error
+    pub errors: usize,
+    /// Findings with severity warning
+    pub warnings: usize,
+    /// Findings with severity info
+    pub infos: usize,
+    /// Counts per rule and severity, ordered by rule code
+    pub by_rule: Vec<RuleMetric>,
+}
+
+/// Number of findings of one rule and severity.
+#[derive(Debug, Clone, Serialize)]
+pub struct RuleMetric {
+    /// Rule code (e.g., "S0001")
+    pub code: &'static str,
+    /// Rule name (e.g., "unused-tag")
+    pub name: &'static str,
+    /// Severity: "info", "warning" or "error"
+    pub severity: String,
+    /// Number of findings
+    pub count: usize,
+}
+
+impl ProjectMetrics {
+    /// Metrics of an L5X project from its statistics and findings.
+    pub fn from_l5x(project: &LoadedProject, controller: &Controller, stats: ParseStats, report: &Report) -> Self {
+        let mut languages = languages();
+        let mut routines = 0;
+        let mut count_routine = |kind: &str| {
+            routines += 1;
+            let key = match kind.to_ascii_uppercase().as_str() {
+                "RLL" => "rll",
+                "ST" => "st",
+                "FBD" => "fbd",
+                "SFC" => "sfc",
+                _ => return,
+            };
+            *languages.entry(key).or_default() += 1;
+        };
+        for program in controller.programs.iter().flat_map(|p| &p.program) {
+            for routine in program.routines.iter().flat_map(|r| &r.routine) {
+                count_routine(&routine.r#type);
+            }
+        }
+        for aoi in controller.add_on_instruction_definitions.iter().flat_map(|a| &a.add_on_instruction_definition) {
+            for content in &aoi.content {
+                if let UDIDefinitionContent::Routines(collection) = content {
+                    for routine in &collection.routine {
+                        count_routine(&routine.r#type);
+                    }
+                }
+            }
+        }
+
+        let tags = controller.tags.as_ref().map_or(0, |t| t.tag.len())
+            + controller
+                .programs
+                .iter()
+                .flat_map(|p| &p.program)
+                .map(|program| program.tags.as_ref().map_or(0, |t| t.tag.len()))
+                .sum::<usize>();
+
+        let counts = MetricsCounts {
+            programs: stats.programs,
+            function_blocks: stats.aois,
+            functions: None,
+            pous: stats.programs + stats.aois,
+            routines,
+            tags,
+            referenced_tags: Some(stats.unique_tags),
+            rungs: Some(stats.rungs),
+        };
+        let complexity = ComplexityMetrics {
+            max_nesting: Some(stats.st_max_nesting),
+            avg_nesting: Some(stats.st_avg_nesting),
+            ..complexity(&stats.st_complexity, stats.st_comments.percent())
+        };
+
+        Self {
+            l5x_stats: Some(stats),
+            ..Self::new(project, FileFormat::L5x, counts, languages, complexity, report)
+        }
+    }
+
+    /// Metrics of a PLCopen project from its statistics and findings.
+    pub fn from_plcopen(project: &LoadedProject, stats: PlcopenStats, report: &Report) -> Self {
+        let mut languages = languages();
+        languages.insert("st", stats.st_bodies);
+        languages.insert("il", stats.il_bodies);
+        languages.insert("fbd", stats.fbd_bodies);
+        languages.insert("ld", stats.ld_bodies);
+        languages.insert("sfc", stats.sfc_bodies);
+
+        let counts = MetricsCounts {
+            programs: stats.programs,
+            function_blocks: stats.function_blocks,
+            functions: Some(stats.functions),
+            pous: stats.pous,
+            routines: languages.values().sum(),
+            tags: stats.variables,
+            referenced_tags: None,
+            rungs: None,
+        };
+        let complexity = complexity(&stats.st_complexity, stats.st_comments.percent());
+
+        Self {
+            plcopen_stats: Some(stats),
+            ..Self::new(project, FileFormat::PlcOpen, counts, languages, complexity, report)
+        }
+    }
+
+    fn new(
+        project: &LoadedProject,
+        format: FileFormat,
+        counts: MetricsCounts,
+        languages: BTreeMap<&'static str, usize>,
+        complexity: ComplexityMetrics,
+        report: &Report,
+    ) -> Self {
+        let summary = Summary::from_rules(&report.rules);
+        let findings = FindingMetrics {
+            total: summary.total(),
+            errors: summary.count(Severity::Error),
+            warnings: summary.count(Severity::Warning),
+            infos: summary.count(Severity::Info),
+            by_rule: summary
+                .by_rule
+                .iter()
+                .map(|count| RuleMetric {
+                    code: count.code,
+                    name: count.name,
+                    severity: count.severity.to_string(),
+                    count: count.count,
+                })
+                .collect(),
+        };
+
+        Self {
+            schema_version: METRICS_SCHEMA_VERSION,
+            tool_version: env!("CARGO_PKG_VERSION"),
+            project: project.name(),
+            file: project.source_path.clone(),
+            format: match format {
+                FileFormat::L5x => "l5x",
+                FileFormat::PlcOpen => "plcopen",
+            },
+            counts,
+            languages,
+            complexity,
+            findings,
+            l5x_stats: None,
+            plcopen_stats: None,
+        }
+    }
+}
+
+/// Language counts with every language present.
+fn languages() -> BTreeMap<&'static str, usize> {
+    ["fbd", "il", "ld", "rll", "sfc", "st"].into_iter().map(|language| (language, 0)).collect()
+}
+
+fn complexity(distribution: &ComplexityDistribution, comment_density: f32) -> ComplexityMetrics {
+    let st_routines = distribution.routines.len();
+    let total: usize = distribution.routines.iter().map(|(_, c)| c).sum();
+    ComplexityMetrics {
+        st_routines,
+        max: distribution.max(),
+        avg: if st_routines == 0 { 0.0 } else { total as f32 / st_routines as f32 },
+        max_nesting: None,
+        avg_nesting: None,
+        buckets: COMPLEXITY_BUCKETS
+            .iter()
+            .zip(distribution.buckets)
+            .map(|((range, _, _), count)| ComplexityBucket { range, count })
+            .collect(),
+        comment_density,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::RuleDetector;
+
+    #[test]
+    fn test_l5x_metrics() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Line">
+                <Tags>
+                    <Tag Name="Unused" DataType="BOOL"/>
+                    <Tag Name="Speed" DataType="DINT"/>
+                </Tags>
+                <Programs>
+                    <Program Name="Main">
+                        <Routines>
+                            <Routine Name="Ladder" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0"><Text>MOV(5,Speed);</Text></Rung>
+                                    <Rung Number="1"><Text>NOP();</Text></Rung>
+                                </RLLContent>
+                            </Routine>
+                            <Routine Name="Logic" Type="ST">
+                                <STContent>
+                                    <Line Number="0"><![CDATA[IF Speed > 10 THEN Speed := 10; END_IF;]]></Line>
+                                </STContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let metrics = RuleDetector::new().metrics(&project).expect("Should analyze");
+        assert_eq!(metrics.project, "Line");
+        assert_eq!((metrics.counts.programs, metrics.counts.routines, metrics.counts.tags), (1, 2, 2));
+        assert_eq!(metrics.counts.rungs, Some(2));
+        assert_eq!(metrics.languages["rll"], 1);
+        assert_eq!(metrics.languages["st"], 1);
+        assert_eq!(metrics.complexity.st_routines, 1);
+        assert_eq!(metrics.complexity.max, 2);
+        assert!(metrics.findings.by_rule.iter().any(|rule| rule.code == "S0001" && rule.count == 1));
+
+        let json: serde_json::Value = serde_json::to_value(&metrics).unwrap();
+        assert_eq!(json["schema_version"], METRICS_SCHEMA_VERSION);
+        assert_eq!(json["format"], "l5x");
+        assert!(json["counts"]["functions"].is_null());
+        assert_eq!(json["l5x_stats"]["rungs"], 2);
+        assert!(json.get("plcopen_stats").is_none());
+    }
+}
@@ -1,7 +1,13 @@
 //! Report types for rule detection results.
 
+use std::collections::BTreeMap;
 use std::fmt;
 
+use serde::Serialize;
+
+use crate::bundle::DuplicateDefinition;
+use crate::loader::ExportKind;
+
 /// Severity level of a detected rule.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Severity {
@@ -23,6 +29,16 @@ impl fmt::Display for Severity {
     }
 }
 
+impl From<iecst::Severity> for Severity {
+    fn from(severity: iecst::Severity) -> Self {
+        match severity {
+            iecst::Severity::Hint => Severity::Info,
+            iecst::Severity::Warning => Severity::Warning,
+            iecst::Severity::Error => Severity::Error,
+        }
+    }
+}
+
 impl Severity {
     /// Parse severity from string.
     pub fn parse(s: &str) -> Option<Self> {
@@ -33,6 +49,23 @@ impl Severity {
             _ => None,
         }
     }
+
+    /// The next higher severity; `Error` stays `Error`.
+    pub fn raised(self) -> Self {
+        match self {
+            Severity::Info => Severity::Warning,
+            Severity::Warning | Severity::Error => Severity::Error,
+        }
+    }
+
+    /// ANSI color code of the severity: red, yellow or blue.
+    fn ansi_color(self) -> &'static str {
+        match self {
+            Severity::Info => "34",
+            Severity::Warning => "33",
+            Severity::Error => "31",
+        }
+    }
 }
 
 /// Kind of code rule detected.
@@ -55,10 +88,110 @@ pub enum RuleKind {
     UnusedAoi,
     /// S0005: DataType is defined but never used
     UnusedDataType,
+    /// S0006: Empty IF/ELSE branch, loop body or CASE branch
+    EmptyBranch,
+    /// S0007: Input parameter is never read
+    UnusedInput,
+    /// S0008: Output parameter is never assigned
+    UnassignedOutput,
+    /// S0009: VAR_TEMP variable is never used
+    UnusedTemp,
+    /// S0010: Name does not match the configured naming pattern
+    NamingConvention,
+    /// S0011: Program not run by any task, or task with no programs
+    UnscheduledProgram,
+    /// S0012: Function writing globals or calling function block instances
+    ImpureFunction,
+    /// S0013: Controller-scoped tag used by only one program
+    TagScopeSuggestion,
+    /// S0014: Controller tag written in one program and read in another
+    CrossProgramSharedTag,
+    /// S0015: Tag, variable or member whose data type is not defined
+    UndefinedDataTypeRef,
+    /// S0016: FB or AOI instance declared but never invoked
+    UninvokedInstance,
+    /// S0017: ST line too long, or containing tabs or trailing whitespace
+    StyleViolation,
+    /// S0018: TODO/FIXME marker left in a comment or description
+    CommentMarker,
+    /// S0019: Method input parameter that the method never reads
+    UnusedMethodParameter,
     /// M0001: Cyclomatic complexity too high
     CyclomaticComplexity,
     /// M0003: Deep nesting (> 4 levels)
     DeepNesting,
+    /// M0006: Too few comments in an ST routine
+    LowCommentDensity,
+    /// M0007: Routines with duplicated (copy-pasted) logic
+    DuplicateLogic,
+    /// M0008: Member access chain too deep (coupling to internal structure)
+    LawOfDemeter,
+    /// M0009: Call of a vendor-specific function or function block outside the IEC standard library
+    NonstandardFunction,
+    /// M0010: POU with no call path from any task entry point
+    UnreachablePou,
+    /// M0011: String built up by CONCAT or INSERT inside a loop (quadratic copying)
+    StringConcatInLoop,
+    /// M0012: Direct address used in logic instead of a named variable
+    HardcodedAddress,
+    /// M0013: IF/ELSIF chain comparing one variable with constants (use CASE)
+    ConvertToCase,
+    /// C0001: Type mismatch in assignment or comparison
+    TypeMismatch,
+    /// C0002: CASE over an enum misses members and has no ELSE
+    NonExhaustiveCase,
+    /// C0003: CASE labels match the same value more than once
+    OverlappingCaseLabels,
+    /// C0004: Variable assigned to itself
+    SelfAssignment,
+    /// C0005: Local variable read before it is assigned
+    UninitializedRead,
+    /// C0006: Tag or variable without a required initial value
+    MissingInitialValue,
+    /// C0007: Constant array index outside the declared range
+    ArrayIndexOutOfBounds,
+    /// C0008: IL jump to a label that is not defined
+    UndefinedJumpTarget,
+    /// C0009: IL label that no jump targets
+    UnusedLabel,
+    /// C0010: Floating-point comparison with = or <>
+    FloatEquality,
+    /// C0012: STRING/WSTRING assigned to a shorter string
+    StringTruncation,
+    /// C0013: FBD block pin without connection, or disconnected FBD network
+    FbdUnconnected,
+    /// C0014: Division by zero or by an unguarded variable
+    DivisionByZero,
+    /// C0016: Timer/counter without reset path
+    TimerNoReset,
+    /// C0017: AND/OR/XOR mixing BOOL and bitwise operands, or bit operations on signed integers
+    BitOperation,
+    /// C0018: Comparison used as a statement (`x = y;` instead of `x := y;`)
+    ComparisonStatement,
+    /// C0019: Assignment overwritten on every path before its value is read
+    DeadStore,
+    /// C0020: Boolean expression with a repeated or complementary operand, or a double negation
+    RedundantBoolean,
+    /// C0021: Enum members with the same value or a decreasing explicit value
+    EnumValueConflict,
+    /// C0022: VAR_IN_OUT parameter bound to a literal or expression
+    InOutNotVariable,
+    /// C0023: Array declared with a descending or single-element range
+    InvalidArrayRange,
+    /// C0024: Struct with a duplicate member name or containing itself by value
+    InvalidStruct,
+    /// C0025: Function block or AOI called without binding inputs that have no initial value
+    UnboundInput,
+    /// C0026: Call argument implicitly converted to the parameter type
+    ArgumentConversion,
+    /// C0027: Variable set to TRUE and FALSE by unconditional assignments in sequence
+    BoolToggle,
+    /// C0032: FOR loop variable modified inside loop
+    LoopVarModified,
+    /// N0007: Name longer than the configured maximum length
+    NameTooLong,
+    /// N0009: Name is an IEC 61131-3 reserved word
+    ReservedWordName,
 
     // =========================================================================
     // PRO RULES (detected by plceye-pro - commercial license)
@@ -66,20 +199,12 @@ pub enum RuleKind {
     // =========================================================================
 
     // --- Coding Practice (C) ---
-    /// C0010: Floating-point comparison with = or <>
-    FloatEquality,
     /// C0011: TIME comparison with = or <>
     TimeEquality,
-    /// C0014: Possible division by zero
-    DivisionByZero,
     /// C0015: Magic number (hardcoded literal that should be a constant)
     MagicNumber,
-    /// C0016: Timer/counter without reset path
-    TimerNoReset,
     /// C0031: POU calls itself recursively
     RecursiveCall,
-    /// C0032: FOR loop variable modified inside loop
-    LoopVarModified,
     /// C0050: POU has too many parameters (>7)
     TooManyParameters,
     /// C0060: Too many global variables
@@ -100,14 +225,10 @@ pub enum RuleKind {
     // --- Naming (N) ---
     /// N0006: Name length < 3 characters
     NameTooShort,
-    /// N0007: Name length > 30 characters
-    NameTooLong,
     /// N0008: Inconsistent naming pattern (not following conventions)
     InconsistentNaming,
 
     // --- Code Quality (Q) ---
-    /// Q0001: Duplicate logic that should be refactored into AOI
-    DuplicateLogic,
     /// Q0002: Unreachable code path detected
     UnreachableCode,
 
@@ -128,9 +249,50 @@ pub enum RuleKind {
     AliasChain,
     /// X0010: Large array (>10000 elements)
     LargeArray,
+
+    // =========================================================================
+    // CUSTOM RULES (from detectors registered with `RuleDetector::register`)
+    // =========================================================================
+    /// Rule of a registered detector, with its own code and name
+    Custom { code: &'static str, name: &'static str },
 }
 
 impl RuleKind {
+    /// The built-in rules, in declaration order.
+    pub const ALL: &'static [RuleKind] = &[
+        RuleKind::UnusedTag, RuleKind::UndefinedTag, RuleKind::EmptyBlock, RuleKind::UnusedAoi,
+        RuleKind::UnusedDataType, RuleKind::EmptyBranch, RuleKind::UnusedInput, RuleKind::UnassignedOutput,
+        RuleKind::UnusedTemp, RuleKind::NamingConvention, RuleKind::UnscheduledProgram, RuleKind::ImpureFunction,
+        RuleKind::TagScopeSuggestion, RuleKind::CrossProgramSharedTag, RuleKind::UndefinedDataTypeRef,
+        RuleKind::UninvokedInstance, RuleKind::StyleViolation, RuleKind::CommentMarker,
+        RuleKind::UnusedMethodParameter, RuleKind::CyclomaticComplexity, RuleKind::DeepNesting,
+        RuleKind::LowCommentDensity, RuleKind::DuplicateLogic, RuleKind::LawOfDemeter,
+        RuleKind::NonstandardFunction, RuleKind::UnreachablePou, RuleKind::StringConcatInLoop,
+        RuleKind::HardcodedAddress, RuleKind::ConvertToCase, RuleKind::TypeMismatch, RuleKind::NonExhaustiveCase,
+        RuleKind::OverlappingCaseLabels, RuleKind::SelfAssignment, RuleKind::UninitializedRead,
+        RuleKind::MissingInitialValue, RuleKind::ArrayIndexOutOfBounds, RuleKind::UndefinedJumpTarget,
+        RuleKind::UnusedLabel, RuleKind::FloatEquality, RuleKind::StringTruncation, RuleKind::FbdUnconnected,
+        RuleKind::DivisionByZero, RuleKind::TimerNoReset, RuleKind::BitOperation, RuleKind::ComparisonStatement,
+        RuleKind::DeadStore, RuleKind::RedundantBoolean, RuleKind::EnumValueConflict, RuleKind::InOutNotVariable,
+        RuleKind::InvalidArrayRange, RuleKind::InvalidStruct, RuleKind::UnboundInput, RuleKind::ArgumentConversion,
+        RuleKind::BoolToggle, RuleKind::LoopVarModified, RuleKind::NameTooLong, RuleKind::ReservedWordName,
+        RuleKind::TimeEquality, RuleKind::MagicNumber, RuleKind::RecursiveCall, RuleKind::TooManyParameters,
+        RuleKind::ExcessiveGlobals, RuleKind::ContinueUsed, RuleKind::ExitUsed, RuleKind::IfWithoutElse,
+        RuleKind::CaseWithoutElse, RuleKind::ReturnInMiddle, RuleKind::NameTooShort, RuleKind::InconsistentNaming,
+        RuleKind::UnreachableCode, RuleKind::AoiNoDescription, RuleKind::TagNoDescription,
+        RuleKind::RoutineNoDescription, RuleKind::ProgramNoDescription, RuleKind::TaskWatchdogDisabled,
+        RuleKind::ExcessiveTaskRate, RuleKind::AliasChain, RuleKind::LargeArray,
+    ];
+
+    /// Look up a built-in rule by code (`M0001`) or name
+    /// (`cyclomatic-complexity`), ignoring case.
+    pub fn from_key(key: &str) -> Option<RuleKind> {
+        Self::ALL
+            .iter()
+            .find(|kind| kind.code().eq_ignore_ascii_case(key) || kind.name().eq_ignore_ascii_case(key))
+            .cloned()
+    }
+
     /// Get the rule code (e.g., "S0001").
     pub fn code(&self) -> &'static str {
         match self {
@@ -140,14 +302,52 @@ impl RuleKind {
             RuleKind::EmptyBlock => "S0003",
             RuleKind::UnusedAoi => "S0004",
             RuleKind::UnusedDataType => "S0005",
-            // Pro: Coding Practice
+            RuleKind::EmptyBranch => "S0006",
+            RuleKind::UnusedInput => "S0007",
+            RuleKind::UnassignedOutput => "S0008",
+            RuleKind::UnusedTemp => "S0009",
+            RuleKind::NamingConvention => "S0010",
+            RuleKind::UnscheduledProgram => "S0011",
+            RuleKind::ImpureFunction => "S0012",
+            RuleKind::TagScopeSuggestion => "S0013",
+            RuleKind::CrossProgramSharedTag => "S0014",
+            RuleKind::UndefinedDataTypeRef => "S0015",
+            RuleKind::UninvokedInstance => "S0016",
+            RuleKind::StyleViolation => "S0017",
+            RuleKind::CommentMarker => "S0018",
+            RuleKind::UnusedMethodParameter => "S0019",
+            RuleKind::TypeMismatch => "C0001",
+            RuleKind::NonExhaustiveCase => "C0002",
+            RuleKind::OverlappingCaseLabels => "C0003",
+            RuleKind::SelfAssignment => "C0004",
+            RuleKind::UninitializedRead => "C0005",
+            RuleKind::MissingInitialValue => "C0006",
+            RuleKind::ArrayIndexOutOfBounds => "C0007",
+            RuleKind::UndefinedJumpTarget => "C0008",
+            RuleKind::UnusedLabel => "C0009",
             RuleKind::FloatEquality => "C0010",
-            RuleKind::TimeEquality => "C0011",
+            RuleKind::StringTruncation => "C0012",
+            RuleKind::FbdUnconnected => "C0013",
             RuleKind::DivisionByZero => "C0014",
-            RuleKind::MagicNumber => "C0015",
             RuleKind::TimerNoReset => "C0016",
-            RuleKind::RecursiveCall => "C0031",
+            RuleKind::BitOperation => "C0017",
+            RuleKind::ComparisonStatement => "C0018",
+            RuleKind::DeadStore => "C0019",
+            RuleKind::RedundantBoolean => "C0020",
+            RuleKind::EnumValueConflict => "C0021",
+            RuleKind::InOutNotVariable => "C0022",
+            RuleKind::InvalidArrayRange => "C0023",
+            RuleKind::InvalidStruct => "C0024",
+            RuleKind::UnboundInput => "C0025",
+            RuleKind::ArgumentConversion => "C0026",
+            RuleKind::BoolToggle => "C0027",
             RuleKind::LoopVarModified => "C0032",
+            RuleKind::NameTooLong => "N0007",
+            RuleKind::ReservedWordName => "N0009",
+            // Pro: Coding Practice
+            RuleKind::TimeEquality => "C0011",
+            RuleKind::MagicNumber => "C0015",
+            RuleKind::RecursiveCall => "C0031",
             RuleKind::TooManyParameters => "C0050",
             RuleKind::ExcessiveGlobals => "C0060",
             // Pro: Style
@@ -159,12 +359,18 @@ impl RuleKind {
             // Pro: Metrics
             RuleKind::CyclomaticComplexity => "M0001",
             RuleKind::DeepNesting => "M0003",
+            RuleKind::LowCommentDensity => "M0006",
+            RuleKind::DuplicateLogic => "M0007",
+            RuleKind::LawOfDemeter => "M0008",
+            RuleKind::NonstandardFunction => "M0009",
+            RuleKind::UnreachablePou => "M0010",
+            RuleKind::StringConcatInLoop => "M0011",
+            RuleKind::HardcodedAddress => "M0012",
+            RuleKind::ConvertToCase => "M0013",
             // Pro: Naming
             RuleKind::NameTooShort => "N0006",
-            RuleKind::NameTooLong => "N0007",
             RuleKind::InconsistentNaming => "N0008",
             // Pro: Code Quality
-            RuleKind::DuplicateLogic => "Q0001",
             RuleKind::UnreachableCode => "Q0002",
             // Pro: Vendor-Specific L5X
             RuleKind::AoiNoDescription => "X0001",
@@ -175,6 +381,7 @@ impl RuleKind {
             RuleKind::ExcessiveTaskRate => "X0007",
             RuleKind::AliasChain => "X0009",
             RuleKind::LargeArray => "X0010",
+            RuleKind::Custom { code, .. } => code,
         }
     }
     
@@ -187,13 +394,51 @@ impl RuleKind {
             RuleKind::EmptyBlock => "empty-block",
             RuleKind::UnusedAoi => "unused-aoi",
             RuleKind::UnusedDataType => "unused-datatype",
+            RuleKind::EmptyBranch => "empty-branch",
+            RuleKind::UnusedInput => "unused-input",
+            RuleKind::UnassignedOutput => "unassigned-output",
+            RuleKind::UnusedTemp => "unused-temp",
+            RuleKind::NamingConvention => "naming-convention",
+            RuleKind::UnscheduledProgram => "unscheduled-program",
+            RuleKind::ImpureFunction => "impure-function",
+            RuleKind::TagScopeSuggestion => "tag-scope-suggestion",
+            RuleKind::CrossProgramSharedTag => "cross-program-shared-tag",
+            RuleKind::UndefinedDataTypeRef => "undefined-datatype-ref",
+            RuleKind::UninvokedInstance => "uninvoked-instance",
+            RuleKind::StyleViolation => "style-violation",
+            RuleKind::CommentMarker => "comment-marker",
+            RuleKind::UnusedMethodParameter => "unused-method-parameter",
+            RuleKind::TypeMismatch => "type-mismatch",
+            RuleKind::NonExhaustiveCase => "non-exhaustive-case",
+            RuleKind::OverlappingCaseLabels => "overlapping-case-labels",
+            RuleKind::SelfAssignment => "self-assignment",
+            RuleKind::UninitializedRead => "uninitialized-read",
+            RuleKind::MissingInitialValue => "missing-initial-value",
+            RuleKind::ArrayIndexOutOfBounds => "array-index-out-of-bounds",
+            RuleKind::UndefinedJumpTarget => "undefined-jump-target",
+            RuleKind::UnusedLabel => "unused-label",
+            RuleKind::LoopVarModified => "loop-var-modified",
             RuleKind::FloatEquality => "float-equality",
-            RuleKind::TimeEquality => "time-equality",
+            RuleKind::StringTruncation => "string-truncation",
+            RuleKind::FbdUnconnected => "fbd-unconnected",
             RuleKind::DivisionByZero => "division-by-zero",
-            RuleKind::MagicNumber => "magic-number",
             RuleKind::TimerNoReset => "timer-no-reset",
+            RuleKind::BitOperation => "bit-operation",
+            RuleKind::ComparisonStatement => "comparison-statement",
+            RuleKind::DeadStore => "dead-store",
+            RuleKind::RedundantBoolean => "redundant-boolean",
+            RuleKind::EnumValueConflict => "enum-value-conflict",
+            RuleKind::InOutNotVariable => "in-out-not-variable",
+            RuleKind::InvalidArrayRange => "invalid-array-range",
+            RuleKind::InvalidStruct => "invalid-struct",
+            RuleKind::UnboundInput => "unbound-input",
+            RuleKind::ArgumentConversion => "argument-conversion",
+            RuleKind::BoolToggle => "bool-toggle",
+            RuleKind::NameTooLong => "name-too-long",
+            RuleKind::ReservedWordName => "reserved-word-name",
+            RuleKind::TimeEquality => "time-equality",
+            RuleKind::MagicNumber => "magic-number",
             RuleKind::RecursiveCall => "recursive-call",
-            RuleKind::LoopVarModified => "loop-var-modified",
             RuleKind::TooManyParameters => "too-many-parameters",
             RuleKind::ExcessiveGlobals => "excessive-globals",
             // Style
@@ -205,12 +450,18 @@ impl RuleKind {
             // Metrics
             RuleKind::CyclomaticComplexity => "cyclomatic-complexity",
             RuleKind::DeepNesting => "deep-nesting",
+            RuleKind::LowCommentDensity => "low-comment-density",
+            RuleKind::DuplicateLogic => "duplicate-logic",
+            RuleKind::LawOfDemeter => "law-of-demeter",
+            RuleKind::NonstandardFunction => "nonstandard-function",
+            RuleKind::UnreachablePou => "unreachable-pou",
+            RuleKind::StringConcatInLoop => "string-concat-in-loop",
+            RuleKind::HardcodedAddress => "hardcoded-address",
+            RuleKind::ConvertToCase => "convert-to-case",
             // Naming
             RuleKind::NameTooShort => "name-too-short",
-            RuleKind::NameTooLong => "name-too-long",
             RuleKind::InconsistentNaming => "inconsistent-naming",
             // Code Quality
-            RuleKind::DuplicateLogic => "duplicate-logic",
             RuleKind::UnreachableCode => "unreachable-code",
             // Vendor-Specific L5X
             RuleKind::AoiNoDescription => "aoi-no-description",
@@ -221,6 +472,36 @@ impl RuleKind {
             RuleKind::ExcessiveTaskRate => "excessive-task-rate",
             RuleKind::AliasChain => "alias-chain",
             RuleKind::LargeArray => "large-array",
+            RuleKind::Custom { name, .. } => name,
+        }
+    }
+
+    /// Rule-specific placeholders the rule's message templates may use,
+    /// beyond those of every rule.
+    pub fn params(&self) -> &'static [&'static str] {
+        match self {
+            RuleKind::CyclomaticComplexity => &["complexity", "threshold"],
+            RuleKind::DeepNesting | RuleKind::LawOfDemeter => &["depth", "threshold"],
+            RuleKind::ConvertToCase => &["branches", "threshold"],
+            RuleKind::NameTooLong => &["length", "threshold"],
+            _ => &[],
+        }
+    }
+
+    /// English message template of the rule's findings. Rules without
+    /// values of their own for templates use the built-in message.
+    pub fn default_template(&self) -> &'static str {
+        match self {
+            RuleKind::CyclomaticComplexity => {
+                "Routine '{name}' has cyclomatic complexity of {complexity} (max: {threshold})"
+            }
+            RuleKind::DeepNesting => "Routine '{name}' has nesting depth of {depth} (max: {threshold})",
+            RuleKind::LawOfDemeter => "Access path '{name}' has depth {depth} (max {threshold})",
+            RuleKind::ConvertToCase => {
+                "IF/ELSIF chain of {branches} branches compares '{name}' with constants; use CASE {name} OF"
+            }
+            RuleKind::NameTooLong => "'{name}' is {length} characters long (maximum {threshold})",
+            _ => "{message}",
         }
     }
 }
@@ -244,6 +525,99 @@ pub struct Rule {
     pub identifier: String,
     /// Human-readable message
     pub message: String,
+    /// Member file the finding came from (for bundles)
+    pub file: Option<String>,
+    /// Suggested edit that removes the finding without changing behavior
+    pub fix: Option<FixHint>,
+    /// Rule-specific values for message templates (e.g. "threshold")
+    pub params: Vec<(&'static str, String)>,
+    /// POU and routine the finding belongs to, for grouping
+    pub pou: PouPath,
+}
+
+/// POU and routine of a finding.
+///
+/// Findings outside any POU (controller tags, data types, tasks) belong to
+/// the root path, whose POU name is empty. Paths order by POU, with the
+/// POU-level findings before those of its routines.
+#[derive(Debug, Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+pub struct PouPath {
+    /// Program, AOI or PLCopen POU name (empty for the root)
+    pub pou: String,
+    /// Routine within the POU, for L5X programs and AOIs
+    pub routine: Option<String>,
+}
+
+impl PouPath {
+    /// Path of a routine, or of a whole POU if `routine` is `None`.
+    pub fn new(pou: impl Into<String>, routine: Option<String>) -> Self {
+        Self {
+            pou: pou.into(),
+            routine,
+        }
+    }
+
+    /// Root for findings outside any POU.
+    pub fn root() -> Self {
+        Self::default()
+    }
+
+    /// Whether this is the root for findings outside any POU.
+    pub fn is_root(&self) -> bool {
+        self.pou.is_empty()
+    }
+
+    /// POU of a finding location: `Program:Main`, `AOI:Valve`,
+    /// `Program:AOI:Valve` or a PLCopen POU name (`Filling`). Tags of a
+    /// program (`Program:Main/Count`) belong to the program; the controller,
+    /// tasks, modules and data types to the root.
+    pub fn from_location(location: &str) -> Self {
+        let scope = location.split('/').next().unwrap_or(location);
+        let pou = match scope.strip_prefix("Program:") {
+            Some(rest) => rest.strip_prefix("AOI:").unwrap_or(rest),
+            None => scope.strip_prefix("AOI:").unwrap_or(scope),
+        };
+        let project_level = ["Controller", "DataTypes", "Project"];
+        if pou.contains(':') || project_level.iter().any(|name| pou.eq_ignore_ascii_case(name)) {
+            return Self::root();
+        }
+        Self::new(pou, None)
+    }
+}
+
+impl fmt::Display for PouPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.routine, self.is_root()) {
+            (_, true) => write!(f, "(project)"),
+            (Some(routine), false) => write!(f, "{}/{}", self.pou, routine),
+            (None, false) => write!(f, "{}", self.pou),
+        }
+    }
+}
+
+/// Machine-readable remediation of a finding.
+///
+/// Only attached where the edit provably does not change the behavior of
+/// the program; findings that need judgement have no fix.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct FixHint {
+    /// What the fix does (e.g. "remove unused tag 'Spare'")
+    pub description: String,
+    /// Text edit in the ST source of the routine, for fixes in code
+    pub edit: Option<TextEdit>,
+}
+
+/// Replacement of a range of ST source text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TextEdit {
+    /// Line of `start` in the routine, from 1
+    pub line: usize,
+    /// Byte offset where the replaced text starts
+    pub start: usize,
+    /// Byte offset just past the replaced text
+    pub end: usize,
+    /// Text to put in place of the range
+    pub replacement: String,
 }
 
 impl Rule {
@@ -255,23 +629,138 @@ impl Rule {
         identifier: impl Into<String>,
         message: impl Into<String>,
     ) -> Self {
+        let location = location.into();
         Self {
             kind,
             severity,
-            location: location.into(),
+            pou: PouPath::from_location(&location),
+            location,
             identifier: identifier.into(),
             message: message.into(),
+            file: None,
+            fix: None,
+            params: Vec::new(),
         }
     }
+
+    /// Attach a fix hint.
+    pub fn with_fix(mut self, fix: FixHint) -> Self {
+        self.fix = Some(fix);
+        self
+    }
+
+    /// Place the finding in a routine of its POU.
+    ///
+    /// A routine named like its POU stands for the POU itself (the body of a
+    /// PLCopen POU), and findings outside any POU stay at the root.
+    pub fn in_routine(mut self, routine: impl Into<String>) -> Self {
+        let routine = routine.into();
+        if !self.pou.is_root() && routine != self.pou.pou {
+            self.pou.routine = Some(routine);
+        }
+        self
+    }
+
+    /// Attach a value for the `{name}` placeholder of message templates.
+    pub fn with_param(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.params.push((name, value.to_string()));
+        self
+    }
+
+    /// Value of a rule-specific message template placeholder.
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params.iter().find(|(n, _)| *n == name).map(|(_, v)| v.as_str())
+    }
+
+    /// Rule code (e.g., "S0001").
+    pub fn id(&self) -> &'static str {
+        self.kind.code()
+    }
+
+    /// Rule name (e.g., "unused-tag").
+    pub fn name(&self) -> &'static str {
+        self.kind.name()
+    }
+
+    /// Kind of rule.
+    pub fn kind(&self) -> &RuleKind {
+        &self.kind
+    }
+
+    /// Severity level.
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    /// Location in the project (e.g., "Program:Main").
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+
+    /// The identifier involved (tag name, routine name, etc.).
+    pub fn identifier(&self) -> &str {
+        &self.identifier
+    }
+
+    /// Human-readable message.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// POU and routine the finding belongs to.
+    pub fn pou(&self) -> &PouPath {
+        &self.pou
+    }
+
+    /// Member file the finding came from, for bundles.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Suggested non-behavior-changing edit, if any.
+    pub fn fix(&self) -> Option<&FixHint> {
+        self.fix.as_ref()
+    }
+
+    /// Display the rule with ANSI colors for terminals: the severity in red,
+    /// yellow or blue and the rule code in bold. Without the escape codes
+    /// the text is the same as `Display`.
+    pub fn colored(&self) -> ColoredRule<'_> {
+        ColoredRule(self)
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, color: bool) -> fmt::Result {
+        if color {
+            write!(
+                f,
+                "\x1b[{}m[{}]\x1b[0m \x1b[1m{}\x1b[0m",
+                self.severity.ansi_color(),
+                self.severity,
+                self.kind
+            )?;
+        } else {
+            write!(f, "[{}] {}", self.severity, self.kind)?;
+        }
+        write!(f, ": {} - {} ({})", self.location, self.message, self.identifier)?;
+        if let Some(ref file) = self.file {
+            write!(f, " in {}", file)?;
+        }
+        Ok(())
+    }
 }
 
 impl fmt::Display for Rule {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(
-            f,
-            "[{}] {}: {} - {} ({})",
-            self.severity, self.kind, self.location, self.message, self.identifier
-        )
+        self.write(f, false)
+    }
+}
+
+/// A rule displayed with ANSI colors, see [`Rule::colored`].
+pub struct ColoredRule<'a>(&'a Rule);
+
+impl fmt::Display for ColoredRule<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write(f, true)
     }
 }
 
@@ -282,6 +771,13 @@ pub struct Report {
     pub rules: Vec<Rule>,
     /// Source file that was analyzed
     pub source_file: Option<String>,
+    /// Kind of a partial L5X export (program, AOI, ...), whose analysis is limited
+    pub export: Option<ExportKind>,
+    /// Definitions found in more than one member file of a bundle
+    pub duplicates: Vec<DuplicateDefinition>,
+    /// Source-protected POUs, routines and AOIs whose logic is not in the
+    /// file; they are not analyzed and not reported as empty
+    pub protected: Vec<String>,
 }
 
 impl Report {
@@ -290,6 +786,11 @@ impl Report {
         Self::default()
     }
 
+    /// All detected rules.
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
     /// Add a rule to the report.
     pub fn add(&mut self, rule: Rule) {
         self.rules.push(rule);
@@ -303,6 +804,18 @@ impl Report {
             .collect()
     }
 
+    /// Findings grouped by POU and routine, for tree views.
+    ///
+    /// Findings outside any POU are under [`PouPath::root`], which sorts
+    /// first.
+    pub fn by_pou(&self) -> BTreeMap<PouPath, Vec<&Rule>> {
+        let mut groups: BTreeMap<PouPath, Vec<&Rule>> = BTreeMap::new();
+        for rule in &self.rules {
+            groups.entry(rule.pou.clone()).or_default().push(rule);
+        }
+        groups
+    }
+
     /// Check if report has any rules.
     pub fn is_empty(&self) -> bool {
         self.rules.is_empty()
@@ -313,3 +826,196 @@ impl Report {
         self.rules.len()
     }
 }
+
+/// Count of findings for one rule and severity.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleCount {
+    /// Rule code (e.g., "S0001")
+    pub code: &'static str,
+    /// Rule name (e.g., "unused-tag")
+    pub name: &'static str,
+    /// Severity of the findings
+    pub severity: Severity,
+    /// Number of findings
+    pub count: usize,
+}
+
+/// Summary of findings grouped by rule and severity.
+#[derive(Debug, Clone, Default)]
+pub struct Summary {
+    /// Counts per rule and severity, ordered by rule code then severity
+    pub by_rule: Vec<RuleCount>,
+}
+
+impl Summary {
+    /// Build a summary from a set of findings.
+    pub fn from_rules<'a>(rules: impl IntoIterator<Item = &'a Rule>) -> Self {
+        let mut counts: BTreeMap<(&'static str, Severity), RuleCount> = BTreeMap::new();
+        for rule in rules {
+            counts
+                .entry((rule.kind.code(), rule.severity))
+                .or_insert_with(|| RuleCount {
+                    code: rule.kind.code(),
+                    name: rule.kind.name(),
+                    severity: rule.severity,
+                    count: 0,
+                })
+                .count += 1;
+        }
+        Self {
+            by_rule: counts.into_values().collect(),
+        }
+    }
+
+    /// Total number of findings.
+    pub fn total(&self) -> usize {
+        self.by_rule.iter().map(|c| c.count).sum()
+    }
+
+    /// Number of findings with the given severity.
+    pub fn count(&self, severity: Severity) -> usize {
+        self.by_rule
+            .iter()
+            .filter(|c| c.severity == severity)
+            .map(|c| c.count)
+            .sum()
+    }
+
+    /// Check whether the findings should fail a CI run.
+    ///
+    /// Findings at or above `fail_on` fail the run. When `max_warnings` is
+    /// set, warnings fail only if there are more than that many of them.
+    pub fn fails(&self, fail_on: Severity, max_warnings: Option<usize>) -> bool {
+        let failing = self.by_rule.iter().any(|c| {
+            c.severity >= fail_on && !(c.severity == Severity::Warning && max_warnings.is_some())
+        });
+        let too_many_warnings =
+            max_warnings.is_some_and(|max| self.count(Severity::Warning) > max);
+        failing || too_many_warnings
+    }
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Summary:")?;
+        for count in &self.by_rule {
+            writeln!(
+                f,
+                "  {} {:<28} {:<8} {:>5}",
+                count.code,
+                count.name,
+                count.severity.to_string(),
+                count.count
+            )?;
+        }
+        write!(
+            f,
+            "  {} error(s), {} warning(s), {} info",
+            self.count(Severity::Error),
+            self.count(Severity::Warning),
+            self.count(Severity::Info)
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn summary(findings: &[(RuleKind, Severity)]) -> Summary {
+        let rules: Vec<Rule> = findings
+            .iter()
+            .map(|(kind, severity)| Rule::new(kind.clone(), *severity, "Controller", "x", "message"))
+            .collect();
+        Summary::from_rules(&rules)
+    }
+
+    #[test]
+    fn test_colored_rule() {
+        let rule = Rule::new(RuleKind::UnusedTag, Severity::Warning, "Controller", "Spare", "Tag 'Spare' is unused");
+        assert_eq!(
+            rule.colored().to_string(),
+            "\x1b[33m[warning]\x1b[0m \x1b[1mS0001\x1b[0m: Controller - Tag 'Spare' is unused (Spare)"
+        );
+        let plain = rule.colored().to_string().replace("\x1b[33m", "").replace("\x1b[1m", "").replace("\x1b[0m", "");
+        assert_eq!(plain, rule.to_string());
+    }
+
+    #[test]
+    fn test_summary_groups_by_rule_and_severity() {
+        let summary = summary(&[
+            (RuleKind::UndefinedTag, Severity::Warning),
+            (RuleKind::UnusedTag, Severity::Info),
+            (RuleKind::UnusedTag, Severity::Warning),
+            (RuleKind::UnusedTag, Severity::Info),
+        ]);
+        let rows: Vec<(&str, Severity, usize)> = summary
+            .by_rule
+            .iter()
+            .map(|c| (c.code, c.severity, c.count))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                ("S0001", Severity::Info, 2),
+                ("S0001", Severity::Warning, 1),
+                ("S0002", Severity::Warning, 1),
+            ]
+        );
+        assert_eq!(summary.total(), 4);
+        assert_eq!(summary.count(Severity::Warning), 2);
+    }
+
+    #[test]
+    fn test_summary_fail_on() {
+        let summary = summary(&[
+            (RuleKind::UnusedTag, Severity::Info),
+            (RuleKind::UndefinedTag, Severity::Warning),
+        ]);
+        assert!(summary.fails(Severity::Info, None));
+        assert!(summary.fails(Severity::Warning, None));
+        assert!(!summary.fails(Severity::Error, None));
+        assert!(!Summary::default().fails(Severity::Info, None));
+    }
+
+    #[test]
+    fn test_summary_max_warnings() {
+        let warnings = summary(&[
+            (RuleKind::UndefinedTag, Severity::Warning),
+            (RuleKind::UndefinedTag, Severity::Warning),
+        ]);
+        assert!(!warnings.fails(Severity::Warning, Some(2)));
+        assert!(warnings.fails(Severity::Warning, Some(1)));
+        assert!(warnings.fails(Severity::Error, Some(1)));
+
+        let with_error = summary(&[(RuleKind::TypeMismatch, Severity::Error)]);
+        assert!(with_error.fails(Severity::Error, Some(10)));
+    }
+
+    #[test]
+    fn test_pou_path_from_location() {
+        assert_eq!(PouPath::from_location("Program:Main"), PouPath::new("Main", None));
+        assert_eq!(PouPath::from_location("Program:Main/Count"), PouPath::new("Main", None));
+        assert_eq!(PouPath::from_location("Program:AOI:Valve"), PouPath::new("Valve", None));
+        assert_eq!(PouPath::from_location("AOI:Valve"), PouPath::new("Valve", None));
+        assert_eq!(PouPath::from_location("Filling"), PouPath::new("Filling", None));
+        for location in ["Controller", "Controller/Speed", "DataTypes", "project", "Task:Fast", "DataType:Recipe"] {
+            assert!(PouPath::from_location(location).is_root(), "{}", location);
+        }
+    }
+
+    #[test]
+    fn test_report_by_pou() {
+        let mut report = Report::new();
+        report.add(Rule::new(RuleKind::UnusedTag, Severity::Info, "Program:Main/Spare", "Spare", "unused"));
+        report.add(Rule::new(RuleKind::EmptyBlock, Severity::Info, "Program:Main", "Fill", "empty").in_routine("Fill"));
+        report.add(Rule::new(RuleKind::UnusedTag, Severity::Info, "Controller", "Speed", "unused").in_routine("Fill"));
+        report.add(Rule::new(RuleKind::DeepNesting, Severity::Warning, "Filling", "Filling", "deep").in_routine("Filling"));
+
+        let groups = report.by_pou();
+        let keys: Vec<String> = groups.keys().map(PouPath::to_string).collect();
+        assert_eq!(keys, ["(project)", "Filling", "Main", "Main/Fill"]);
+        assert_eq!(groups[&PouPath::root()][0].identifier, "Speed");
+        assert_eq!(groups[&PouPath::new("Main", Some("Fill".to_string()))][0].identifier, "Fill");
+    }
+}
@@ -0,0 +1,392 @@
+//! Argument conversion detector.
+//!
+//! Detects arguments of ST calls of user-defined functions, function blocks
+//! and AOIs whose type differs from the declared parameter type (C0026).
+//! The call then relies on an implicit conversion, which some runtimes do
+//! not perform and which loses information when it narrows. Narrowing and
+//! conversions between type families (integer to REAL, BOOL to WORD) are
+//! warnings; widening within a family (INT to DINT) is info. In-out
+//! parameters are passed by reference and are not converted, so any other
+//! type is a warning. Argument types come from the calling POU's
+//! declarations; untyped literals take the parameter type and are not
+//! checked.
+
+use iecst::visitor::{walk_expr, walk_stmt};
+use iecst::{Expr, ExprKind, ExprTypes, Pou, Stmt, StmtKind, Type, TypeKind, Visitor};
+
+use crate::analysis::{Parameter, ParameterKind, Signature, StUnits};
+use crate::config::ArgumentConversionConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for call arguments converted implicitly to the parameter type.
+pub struct ArgumentConversionDetector<'a> {
+    config: &'a ArgumentConversionConfig,
+}
+
+impl<'a> ArgumentConversionDetector<'a> {
+    /// Create a new argument conversion detector with the given configuration.
+    pub fn new(config: &'a ArgumentConversionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            let mut arguments = Arguments {
+                st_units,
+                pou,
+                types: ExprTypes::new(pou, &st_units.functions),
+                found: Vec::new(),
+            };
+            arguments.visit_body(&pou.body);
+
+            for argument in arguments.found {
+                let severity = match argument.conversion {
+                    Conversion::Widening if !self.config.widening => continue,
+                    Conversion::Widening => Severity::Info,
+                    _ => Severity::Warning,
+                };
+                let text = unit
+                    .source
+                    .get(argument.argument.span.start..argument.argument.span.end)
+                    .unwrap_or("?");
+                report.add(Rule::new(
+                    RuleKind::ArgumentConversion,
+                    severity,
+                    unit.location.clone(),
+                    format!("{}.{}", argument.signature.name, argument.parameter.name),
+                    format!(
+                        "Routine '{}' line {}: call of '{}' passes {} '{}' to {} parameter '{}' ({})",
+                        unit.name,
+                        unit.line_of(argument.argument.span.start),
+                        argument.callee,
+                        argument.provided.display_name(),
+                        text,
+                        argument.parameter.data_type.display_name(),
+                        argument.parameter.name,
+                        argument.conversion
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for ArgumentConversionDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+/// How an argument's type relates to its parameter's.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Conversion {
+    /// To a larger type of the same family (INT to DINT)
+    Widening,
+    /// To a smaller type of the same family (DINT to INT)
+    Narrowing,
+    /// To a type of another family (INT to REAL)
+    CrossFamily,
+    /// Any other type for an in-out parameter
+    Reference,
+}
+
+impl std::fmt::Display for Conversion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Conversion::Widening => write!(f, "widening conversion"),
+            Conversion::Narrowing => write!(f, "narrowing conversion"),
+            Conversion::CrossFamily => write!(f, "conversion between type families"),
+            Conversion::Reference => write!(f, "in-out parameters are passed by reference and not converted"),
+        }
+    }
+}
+
+impl Conversion {
+    /// The conversion a value of type `provided` needs for a parameter of
+    /// type `expected`; `None` if the types match or either has no family.
+    fn between(provided: &Type, expected: &Type, kind: ParameterKind) -> Option<Self> {
+        let (from, to) = (family(provided)?, family(expected)?);
+        if provided == expected {
+            return None;
+        }
+        Some(if kind == ParameterKind::InOut {
+            Conversion::Reference
+        } else if from != to {
+            Conversion::CrossFamily
+        } else if provided.widens_to(expected) {
+            Conversion::Widening
+        } else {
+            Conversion::Narrowing
+        })
+    }
+}
+
+/// Type family of an elementary type; strings and derived types have none.
+fn family(ty: &Type) -> Option<&'static str> {
+    Some(match ty {
+        Type::SInt | Type::Int | Type::DInt | Type::LInt => "signed integer",
+        Type::USInt | Type::UInt | Type::UDInt | Type::ULInt => "unsigned integer",
+        Type::Real | Type::LReal => "real",
+        Type::Byte | Type::Word | Type::DWord | Type::LWord => "bit string",
+        Type::Bool => "boolean",
+        Type::Time => "duration",
+        Type::Date | Type::TimeOfDay | Type::DateTime => "date and time",
+        _ => return None,
+    })
+}
+
+/// An argument whose type differs from its parameter's.
+struct Argument<'ast> {
+    /// Called POU or instance as written
+    callee: &'ast str,
+    signature: &'ast Signature,
+    parameter: &'ast Parameter,
+    argument: &'ast Expr,
+    /// Type of the argument
+    provided: Type,
+    conversion: Conversion,
+}
+
+/// Collects the converted arguments of the calls in a body.
+struct Arguments<'ast> {
+    st_units: &'ast StUnits,
+    pou: &'ast Pou,
+    types: ExprTypes,
+    found: Vec<Argument<'ast>>,
+}
+
+impl<'ast> Arguments<'ast> {
+    /// Check the arguments of a call: (parameter name, value, output binding).
+    fn check_call(
+        &mut self,
+        callee: &'ast str,
+        args: impl Iterator<Item = (Option<&'ast str>, Option<&'ast Expr>, bool)>,
+    ) {
+        let Some((signature, skip)) = self.resolve(callee) else {
+            return;
+        };
+
+        let mut position = 0;
+        for (name, value, output) in args {
+            let parameter = match name {
+                Some(name) => signature.parameter(name),
+                None => {
+                    position += 1;
+                    (position > skip).then(|| signature.positional(position - 1 - skip)).flatten()
+                }
+            };
+            let (Some(parameter), Some(argument)) = (parameter, value) else {
+                continue;
+            };
+            if output || parameter.kind == ParameterKind::Output {
+                continue;
+            }
+            let Some(provided) = self.types.type_of(argument) else {
+                continue;
+            };
+            if let Some(conversion) = Conversion::between(&provided, &parameter.data_type, parameter.kind) {
+                self.found.push(Argument {
+                    callee,
+                    signature,
+                    parameter,
+                    argument,
+                    provided,
+                    conversion,
+                });
+            }
+        }
+    }
+
+    /// The signature of a called POU, or of the type of a called instance,
+    /// and the number of leading positional arguments that are not parameters.
+    fn resolve(&self, callee: &str) -> Option<(&'ast Signature, usize)> {
+        if let Some(signature) = self.st_units.signature(callee) {
+            return Some((signature, usize::from(signature.instance_argument)));
+        }
+        let instance = self
+            .pou
+            .var_blocks
+            .iter()
+            .flat_map(|block| &block.vars)
+            .find(|var| var.name.eq_ignore_ascii_case(callee))?;
+        match &instance.var_type.kind {
+            TypeKind::Simple(type_name) => Some((self.st_units.signature(type_name)?, 0)),
+            _ => None,
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for Arguments<'ast> {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        if let StmtKind::Call { name, args } = &stmt.kind {
+            self.check_call(
+                name,
+                args.iter().map(|arg| (arg.name.as_deref(), arg.value.as_ref(), arg.output)),
+            );
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let ExprKind::FunctionCall { name, args } = &expr.kind {
+            self.check_call(
+                name,
+                args.iter().map(|arg| (arg.name.as_deref(), arg.value.as_ref(), arg.output)),
+            );
+        }
+        walk_expr(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn findings(st_units: &StUnits, config: &ArgumentConversionConfig) -> Vec<(Severity, String)> {
+        let mut report = Report::new();
+        ArgumentConversionDetector::new(config).detect(st_units, &mut report);
+        report.rules.into_iter().map(|r| (r.severity, r.message)).collect()
+    }
+
+    #[test]
+    fn test_plcopen_calls() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Ramp" pouType="functionBlock">
+      <interface>
+        <inputVars>
+          <variable name="Target"><type><REAL/></type></variable>
+          <variable name="Steps"><type><DINT/></type></variable>
+        </inputVars>
+        <outputVars><variable name="Out"><type><INT/></type></variable></outputVars>
+        <inOutVars><variable name="Count"><type><INT/></type></variable></inOutVars>
+      </interface>
+      <body><ST><![CDATA[Out := REAL_TO_INT(Target);]]></ST></body>
+    </pou>
+    <pou name="Limit" pouType="function">
+      <interface>
+        <returnType><INT/></returnType>
+        <inputVars><variable name="Value"><type><INT/></type></variable></inputVars>
+      </interface>
+      <body><ST><![CDATA[Limit := Value;]]></ST></body>
+    </pou>
+    <pou name="Main" pouType="program">
+      <interface><localVars>
+        <variable name="Ramp1"><type><derived name="Ramp"/></type></variable>
+        <variable name="Level"><type><INT/></type></variable>
+        <variable name="Total"><type><DINT/></type></variable>
+        <variable name="Speed"><type><REAL/></type></variable>
+        <variable name="Counter"><type><DINT/></type></variable>
+        <variable name="x"><type><INT/></type></variable>
+      </localVars></interface>
+      <body><ST><![CDATA[Ramp1(Target := Level, Steps := Level, Count := Counter, Out => Total);
+x := Limit(Total);
+Ramp1(Target := Speed, Steps := 10, Count := x);
+x := Limit(5);]]></ST></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        assert_eq!(
+            findings(&st_units, &ArgumentConversionConfig::default()),
+            [
+                (
+                    Severity::Warning,
+                    "Routine 'Main' line 1: call of 'Ramp1' passes INT 'Level' to REAL parameter 'Target' \
+                     (conversion between type families)"
+                        .to_string()
+                ),
+                (
+                    Severity::Info,
+                    "Routine 'Main' line 1: call of 'Ramp1' passes INT 'Level' to DINT parameter 'Steps' \
+                     (widening conversion)"
+                        .to_string()
+                ),
+                (
+                    Severity::Warning,
+                    "Routine 'Main' line 1: call of 'Ramp1' passes DINT 'Counter' to INT parameter 'Count' \
+                     (in-out parameters are passed by reference and not converted)"
+                        .to_string()
+                ),
+                (
+                    Severity::Warning,
+                    "Routine 'Main' line 2: call of 'Limit' passes DINT 'Total' to INT parameter 'Value' \
+                     (narrowing conversion)"
+                        .to_string()
+                ),
+            ]
+        );
+
+        let config = ArgumentConversionConfig {
+            widening: false,
+            ..ArgumentConversionConfig::default()
+        };
+        assert!(findings(&st_units, &config).iter().all(|(severity, _)| *severity == Severity::Warning));
+    }
+
+    #[test]
+    fn test_l5x_aoi_call() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+<Controller Name="Test">
+<AddOnInstructionDefinitions>
+<AddOnInstructionDefinition Name="Filter">
+<Parameters>
+<Parameter Name="EnableIn" TagType="Base" DataType="BOOL" Usage="Input" Required="false"/>
+<Parameter Name="Gain" TagType="Base" DataType="REAL" Usage="Input" Required="true"/>
+<Parameter Name="Mask" TagType="Base" DataType="DINT" Usage="Input" Required="true"/>
+</Parameters>
+</AddOnInstructionDefinition>
+</AddOnInstructionDefinitions>
+<Programs>
+<Program Name="Line">
+<Tags>
+<Tag Name="Filter_01" TagType="Base" DataType="Filter"/>
+<Tag Name="Raw" TagType="Base" DataType="REAL"/>
+<Tag Name="Flag" TagType="Base" DataType="BOOL"/>
+</Tags>
+<Routines>
+<Routine Name="Calc" Type="ST"><STContent>
+<Line Number="0">Filter(Filter_01, Raw, Flag);</Line>
+</STContent></Routine>
+</Routines>
+</Program>
+</Programs>
+</Controller>
+</RSLogix5000Content>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_controller(project.l5x_controller.as_ref().unwrap());
+        let found = findings(&st_units, &ArgumentConversionConfig::default());
+        assert_eq!(found.len(), 1);
+        assert!(found[0].1.starts_with("Routine 'Calc' line 1: call of 'Filter' passes BOOL 'Flag' to DINT parameter 'Mask'"));
+    }
+}
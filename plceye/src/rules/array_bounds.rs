@@ -0,0 +1,112 @@
+//! Array bounds detector.
+//!
+//! Detects constant array indices outside the declared `ARRAY` range,
+//! which fault at runtime (C0007).
+
+use iecst::check_array_bounds;
+
+use crate::analysis::StUnits;
+use crate::config::ArrayBoundsConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for constant array indices out of bounds.
+pub struct ArrayBoundsDetector<'a> {
+    config: &'a ArrayBoundsConfig,
+}
+
+impl<'a> ArrayBoundsDetector<'a> {
+    /// Create a new array bounds detector with the given configuration.
+    pub fn new(config: &'a ArrayBoundsConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_array_bounds(pou, &st_units.types) {
+                report.add(Rule::new(
+                    RuleKind::ArrayIndexOutOfBounds,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for ArrayBoundsDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+    use crate::report::Severity;
+
+    fn detect_l5x(st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Idx" TagType="Base" DataType="DINT"/>
+               <Tag Name="Values" TagType="Base" DataType="DINT" Dimensions="10"/>
+               <Tag Name="Grid" TagType="Base" DataType="REAL" Dimensions="4 3"/>"#,
+            "Fill",
+            st_lines,
+        );
+        let config = ArrayBoundsConfig::default();
+        let mut report = Report::new();
+        ArrayBoundsDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_constant_index_out_of_bounds() {
+        let report = detect_l5x(&["Values[0] := 1;", "Values[10] := Values[9];", "Values[Idx] := 0;"]);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::ArrayIndexOutOfBounds);
+        assert_eq!(report.rules[0].severity, Severity::Error);
+        assert_eq!(
+            report.rules[0].message,
+            "Routine 'Fill' line 2: index 10 is outside the declared range of 'Values' [0..9]"
+        );
+    }
+
+    #[test]
+    fn test_multi_dimensional() {
+        let report = detect_l5x(&["Grid[3, 2] := 0.0;", "Grid[2, 3] := 0.0;"]);
+        assert_eq!(report.rules.len(), 1);
+        assert!(report.rules[0].message.contains("line 2"));
+        assert!(report.rules[0].message.contains("in dimension 2"));
+    }
+}
@@ -0,0 +1,133 @@
+//! Bit operation detector.
+//!
+//! Detects `AND`/`OR`/`XOR` mixing BOOL with integer or bit-string
+//! operands, where logical and bitwise use are confused, and masks or
+//! shifts on signed integers (C0017).
+
+use iecst::{check_bit_operations, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::BitOperationConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for suspicious bit operations.
+pub struct BitOperationDetector<'a> {
+    config: &'a BitOperationConfig,
+}
+
+impl<'a> BitOperationDetector<'a> {
+    /// Create a new bit operation detector with the given configuration.
+    pub fn new(config: &'a BitOperationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_bit_operations(pou, &st_units.functions) {
+                let enabled = match diagnostic.kind {
+                    DiagnosticKind::MixedBitOperation { .. } => self.config.check_mixed,
+                    _ => self.config.check_signed,
+                };
+                if !enabled {
+                    continue;
+                }
+                report.add(Rule::new(
+                    RuleKind::BitOperation,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for BitOperationDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+    use crate::report::Severity;
+
+    fn detect_l5x(config: &BitOperationConfig, st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Status" TagType="Base" DataType="INT"/>
+               <Tag Name="Flags" TagType="Base" DataType="DWORD"/>
+               <Tag Name="Ready" TagType="Base" DataType="BOOL"/>"#,
+            "Bits",
+            st_lines,
+        );
+        let mut report = Report::new();
+        BitOperationDetector::new(config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_mixed_and_signed() {
+        let report = detect_l5x(
+            &BitOperationConfig::default(),
+            &["Ready := Flags AND Ready;", "Status := Status AND 16#FF;", "Flags := SHL(Flags, 2);"],
+        );
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.rules[0].kind, RuleKind::BitOperation);
+        assert_eq!(report.rules[0].severity, Severity::Warning);
+        assert!(report.rules[0].message.contains("AND of DWORD and BOOL"));
+        assert_eq!(report.rules[1].severity, Severity::Info);
+        assert!(report.rules[1].message.contains("line 2: bitwise AND on signed INT"));
+    }
+
+    #[test]
+    fn test_checks_optional() {
+        let lines = ["Ready := Flags AND Ready;", "Status := Status OR 1;"];
+        let config = BitOperationConfig {
+            check_signed: false,
+            ..BitOperationConfig::default()
+        };
+        let report = detect_l5x(&config, &lines);
+        assert_eq!(report.rules.len(), 1);
+        assert!(report.rules[0].message.contains("line 1"));
+
+        let config = BitOperationConfig {
+            check_mixed: false,
+            ..BitOperationConfig::default()
+        };
+        let report = detect_l5x(&config, &lines);
+        assert_eq!(report.rules.len(), 1);
+        assert!(report.rules[0].message.contains("line 2"));
+    }
+}
@@ -0,0 +1,126 @@
+//! Bool toggle detector.
+//!
+//! Detects variables set to TRUE and FALSE by unconditional assignments in
+//! sequence, such as `Out := TRUE; Out := FALSE;` (C0027). Nothing between
+//! the assignments sees the earlier value, so only the last one takes
+//! effect; usually a condition is missing.
+
+use iecst::{check_bool_toggles, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::BoolToggleConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for bool toggles.
+pub struct BoolToggleDetector<'a> {
+    config: &'a BoolToggleConfig,
+}
+
+impl<'a> BoolToggleDetector<'a> {
+    /// Create a new bool toggle detector with the given configuration.
+    pub fn new(config: &'a BoolToggleConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_bool_toggles(pou) {
+                let DiagnosticKind::BoolToggle { ref path, last, .. } = diagnostic.kind else {
+                    continue;
+                };
+                report.add(
+                    Rule::new(
+                        RuleKind::BoolToggle,
+                        diagnostic.severity.into(),
+                        unit.location.clone(),
+                        path.clone(),
+                        format!(
+                            "Routine '{}' lines {}-{}: {}",
+                            unit.name,
+                            unit.line_of(diagnostic.span.start),
+                            unit.line_of(last.start),
+                            diagnostic.kind
+                        ),
+                    )
+                    .in_routine(&unit.name),
+                );
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for BoolToggleDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_plcopen_toggle() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Main" pouType="program">
+      <interface>
+        <outputVars><variable name="Valve"><type><BOOL/></type></variable></outputVars>
+        <localVars>
+          <variable name="Start"><type><BOOL/></type></variable>
+          <variable name="Count"><type><INT/></type></variable>
+        </localVars>
+      </interface>
+      <body><ST><![CDATA[Valve := TRUE;
+Count := Count + 1;
+Valve := FALSE;
+IF Start THEN
+    Valve := TRUE;
+ELSE
+    Valve := FALSE;
+END_IF;]]></ST></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let mut report = Report::new();
+        BoolToggleDetector::new(&BoolToggleConfig::default()).detect(&st_units, &mut report);
+
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::BoolToggle);
+        assert_eq!(report.rules[0].identifier, "Valve");
+        assert_eq!(
+            report.rules[0].message,
+            "Routine 'Main' lines 1-3: 'Valve' is set to both TRUE and FALSE with no condition in between; \
+             the earlier assignments have no effect and it ends FALSE"
+        );
+    }
+}
@@ -0,0 +1,132 @@
+//! Comment marker detector.
+//!
+//! Detects leftover `TODO`, `FIXME`, `XXX` and `HACK` markers in ST
+//! comments, L5X descriptions and rung comments, and PLCopen documentation
+//! (S0018), for release readiness checks.
+
+use crate::analysis::{find_markers, texts_from_controller, texts_from_plcopen, texts_from_st, ProjectText, StUnits};
+use crate::config::CommentMarkersConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for marker keywords in comments and descriptions.
+pub struct CommentMarkersDetector<'a> {
+    config: &'a CommentMarkersConfig,
+}
+
+impl<'a> CommentMarkersDetector<'a> {
+    /// Create a new comment markers detector with the given configuration.
+    pub fn new(config: &'a CommentMarkersConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the comments and descriptions of a project and add
+    /// findings to the report.
+    pub fn detect(&self, texts: &[ProjectText], report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let severity = Severity::parse(&self.config.severity).unwrap_or(Severity::Info);
+        for marker in find_markers(texts, &self.config.keywords) {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&marker.location) {
+                continue;
+            }
+
+            let at = match marker.line {
+                Some(line) => format!(" at line {}", line),
+                None => String::new(),
+            };
+            report.add(Rule::new(
+                RuleKind::CommentMarker,
+                severity,
+                marker.location.clone(),
+                marker.keyword.clone(),
+                format!("{} marker{}: {}", marker.keyword, at, marker.snippet),
+            ));
+        }
+    }
+
+    /// Check if a location matches any ignore pattern.
+    fn matches_ignore_pattern(&self, location: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, location)
+    }
+}
+
+impl Detector for CommentMarkersDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        let mut texts = texts_from_st(&StUnits::from_project(project));
+        if let Some(ref controller) = project.l5x_controller {
+            texts.extend(texts_from_controller(controller));
+        } else if let Some(ref plcopen) = project.plcopen_project {
+            texts.extend(texts_from_plcopen(plcopen));
+        }
+        findings(|report| self.detect(&texts, report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{texts_from_plcopen, texts_from_st, StUnits};
+
+    #[test]
+    fn test_markers_in_plcopen() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"/>
+  <types>
+    <dataTypes/>
+    <pous>
+      <pou name="Main" pouType="program">
+        <interface>
+          <localVars>
+            <variable name="level"><type><REAL/></type><documentation>FIXME: unit is mm, not %</documentation></variable>
+          </localVars>
+        </interface>
+        <body><ST><![CDATA[level := 0.0;
+(* TODO: read the sensor
+   once it is wired *)
+level := level + 1.0; // HACK]]></ST></body>
+      </pou>
+    </pous>
+  </types>
+  <instances><configurations/></instances>
+</project>"#;
+
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let plcopen = project.plcopen_project.as_ref().unwrap();
+        let mut texts = texts_from_st(&StUnits::from_plcopen(plcopen));
+        texts.extend(texts_from_plcopen(plcopen));
+
+        let mut config = CommentMarkersConfig {
+            severity: "warning".to_string(),
+            ..CommentMarkersConfig::default()
+        };
+        let mut report = Report::new();
+        CommentMarkersDetector::new(&config).detect(&texts, &mut report);
+
+        let found: Vec<_> = report
+            .rules
+            .iter()
+            .map(|r| (r.location.as_str(), r.message.as_str(), r.severity))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("Main", "TODO marker at line 2: (* TODO: read the sensor", Severity::Warning),
+                ("Main", "HACK marker at line 4: // HACK", Severity::Warning),
+                ("Main/level", "FIXME marker: FIXME: unit is mm, not %", Severity::Warning),
+            ]
+        );
+
+        config.ignore_patterns = vec!["Main/*".to_string()];
+        let mut report = Report::new();
+        CommentMarkersDetector::new(&config).detect(&texts, &mut report);
+        assert_eq!(report.rules.len(), 2);
+    }
+}
@@ -0,0 +1,105 @@
+//! Comparison statement detector.
+//!
+//! Detects comparisons used as statements, like `x = y;`, which have no
+//! effect and are usually an assignment with `=` typed for `:=` (C0018).
+
+use iecst::ParseErrorKind;
+
+use crate::analysis::StUnits;
+use crate::config::ComparisonStatementConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for comparisons used as statements.
+pub struct ComparisonStatementDetector<'a> {
+    config: &'a ComparisonStatementConfig,
+}
+
+impl<'a> ComparisonStatementDetector<'a> {
+    /// Create a new comparison statement detector with the given configuration.
+    pub fn new(config: &'a ComparisonStatementConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            for error in &unit.skipped {
+                let ParseErrorKind::ComparisonStatement { ref expr, .. } = error.kind else {
+                    continue;
+                };
+                report.add(Rule::new(
+                    RuleKind::ComparisonStatement,
+                    Severity::Warning,
+                    unit.location.clone(),
+                    expr.clone(),
+                    format!("Routine '{}' line {}: {}", unit.name, unit.line_of(error.span.start), error.kind),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for ComparisonStatementDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+
+    fn detect_l5x(config: &ComparisonStatementConfig, st_lines: &[&str]) -> (StUnits, Report) {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Count" TagType="Base" DataType="DINT"/>
+               <Tag Name="Limit" TagType="Base" DataType="DINT"/>"#,
+            "Counter",
+            st_lines,
+        );
+        let mut report = Report::new();
+        ComparisonStatementDetector::new(config).detect(&st_units, &mut report);
+        (st_units, report)
+    }
+
+    #[test]
+    fn test_comparison_statement() {
+        let (st_units, report) = detect_l5x(
+            &ComparisonStatementConfig::default(),
+            &["IF Count > Limit THEN", "    Count = 0;", "END_IF;", "Limit := Count * 2;"],
+        );
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::ComparisonStatement);
+        assert_eq!(report.rules[0].severity, Severity::Warning);
+        assert_eq!(report.rules[0].identifier, "Count = 0");
+        assert!(report.rules[0]
+            .message
+            .ends_with("line 2: comparison 'Count = 0' used as a statement has no effect; did you mean 'Count := 0'?"));
+        // The rest of the routine is still analyzed by the semantic rules
+        assert!(st_units.units[0].pou.is_some());
+
+        let config = ComparisonStatementConfig {
+            enabled: false,
+            ..ComparisonStatementConfig::default()
+        };
+        assert!(detect_l5x(&config, &["Count = 0;"]).1.rules.is_empty());
+    }
+}
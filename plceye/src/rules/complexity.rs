@@ -4,9 +4,12 @@
 
 use iec61131::analysis::CfgBuilder;
 
-use crate::analysis::ProjectAnalysis;
+use crate::analysis::{analyze_controller, parse_st_bodies, ParsedSTRoutine, ProjectAnalysis};
 use crate::config::ComplexityConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
 use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
 
 /// Detector for cyclomatic complexity in ST routines.
 pub struct ComplexityDetector<'a> {
@@ -21,11 +24,16 @@ impl<'a> ComplexityDetector<'a> {
 
     /// Run detection on analyzed ST routines and add findings to the report.
     pub fn detect(&self, analysis: &ProjectAnalysis, report: &mut Report) {
+        self.detect_routines(&analysis.st_routines, report);
+    }
+
+    /// Run detection on parsed ST routines and add findings to the report.
+    pub fn detect_routines(&self, st_routines: &[ParsedSTRoutine], report: &mut Report) {
         if !self.config.enabled {
             return;
         }
 
-        for st_routine in &analysis.st_routines {
+        for st_routine in st_routines {
             // Skip if matches ignore pattern
             if self.matches_ignore_pattern(&st_routine.location.routine) {
                 continue;
@@ -40,26 +48,36 @@ impl<'a> ComplexityDetector<'a> {
             let cfg = CfgBuilder::new().build(&pou.body);
             let complexity = cfg.cyclomatic_complexity();
 
-            if complexity > self.config.max_complexity {
+            // Per-kind threshold overrides the global one
+            let kind_threshold = self.config.per_kind.get(st_routine.kind);
+            let threshold = kind_threshold.unwrap_or(self.config.max_complexity);
+
+            if complexity > threshold {
+                let applied = match kind_threshold {
+                    Some(_) => format!("max for {}: {}", st_routine.kind, threshold),
+                    None => format!("max: {}", threshold),
+                };
                 report.add(Rule::new(
                     RuleKind::CyclomaticComplexity,
-                    self.severity_for_complexity(complexity),
-                    format!("Program:{}", st_routine.location.program),
+                    self.severity_for_complexity(complexity, threshold),
+                    st_routine.scope(),
                     st_routine.location.routine.clone(),
                     format!(
-                        "Routine '{}' has cyclomatic complexity of {} (max: {})",
+                        "Routine '{}' has cyclomatic complexity of {} ({})",
                         st_routine.location.routine,
                         complexity,
-                        self.config.max_complexity
+                        applied
                     ),
-                ));
+                )
+                .in_routine(&st_routine.location.routine)
+                .with_param("complexity", complexity)
+                .with_param("threshold", threshold));
             }
         }
     }
 
     /// Determine severity based on how much complexity exceeds threshold.
-    fn severity_for_complexity(&self, complexity: usize) -> Severity {
-        let threshold = self.config.max_complexity;
+    fn severity_for_complexity(&self, complexity: usize, threshold: usize) -> Severity {
         // Severe: more than 2x the threshold
         if complexity > threshold * 2 {
             Severity::Error
@@ -73,57 +91,27 @@ impl<'a> ComplexityDetector<'a> {
 
     /// Check if a routine name matches any ignore pattern.
     fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
-        for pattern in &self.config.ignore_patterns {
-            if glob_match(pattern, routine_name) {
-                return true;
-            }
-        }
-        false
+        matches_any(&self.config.ignore_patterns, routine_name)
     }
 }
 
-/// Simple glob pattern matching supporting * and ? wildcards.
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let pattern_chars: Vec<char> = pattern.chars().collect();
-    let text_chars: Vec<char> = text.chars().collect();
-    glob_match_recursive(&pattern_chars, &text_chars, 0, 0)
-}
-
-fn glob_match_recursive(pattern: &[char], text: &[char], pi: usize, ti: usize) -> bool {
-    if pi == pattern.len() {
-        return ti == text.len();
-    }
-
-    match pattern[pi] {
-        '*' => {
-            for i in ti..=text.len() {
-                if glob_match_recursive(pattern, text, pi + 1, i) {
-                    return true;
-                }
+impl Detector for ComplexityDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(&analyze_controller(controller), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect_routines(&parse_st_bodies(plcopen), report);
             }
-            false
-        }
-        '?' => {
-            if ti < text.len() {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
-            }
-        }
-        c => {
-            if ti < text.len() && c.eq_ignore_ascii_case(&text[ti]) {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
-            }
-        }
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::analysis::{ParsedSTRoutine, STLocation, ParseStats, RoutineSummary};
+    use crate::analysis::{ParsedSTRoutine, RoutineKind, STLocation, ParseStats, RoutineSummary};
+    use crate::config::PouKindThresholds;
     use std::collections::HashMap;
 
     fn create_test_analysis(st_source: &str) -> ProjectAnalysis {
@@ -132,9 +120,11 @@ mod tests {
         let pou = crate::analysis::parse_pou(&wrapped).ok();
         let st_routine = ParsedSTRoutine {
             location: STLocation::new("MainProgram", "TestRoutine"),
+            kind: RoutineKind::Program,
             source: st_source.to_string(),
             pou,
             parse_error: None,
+            skipped: Vec::new(),
         };
 
         ProjectAnalysis {
@@ -151,10 +141,12 @@ mod tests {
                 parse_errors: 0,
                 tags_used: vec![],
                 instructions: HashMap::new(),
+                protected: false,
             }],
             instruction_usage: HashMap::new(),
             aoi_definitions: vec![],
             aoi_usage: HashMap::new(),
+            protected: vec![],
             stats: ParseStats::default(),
         }
     }
@@ -164,6 +156,7 @@ mod tests {
         let config = ComplexityConfig {
             enabled: true,
             max_complexity: 10,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = ComplexityDetector::new(&config);
@@ -182,6 +175,7 @@ mod tests {
         let config = ComplexityConfig {
             enabled: true,
             max_complexity: 2,  // Very low threshold for testing
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = ComplexityDetector::new(&config);
@@ -212,6 +206,7 @@ mod tests {
         let config = ComplexityConfig {
             enabled: false,
             max_complexity: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = ComplexityDetector::new(&config);
@@ -229,6 +224,7 @@ mod tests {
         let config = ComplexityConfig {
             enabled: true,
             max_complexity: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec!["Test*".to_string()],
         };
         let detector = ComplexityDetector::new(&config);
@@ -246,6 +242,7 @@ mod tests {
         let config = ComplexityConfig {
             enabled: true,
             max_complexity: 10,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = ComplexityDetector::new(&config);
@@ -256,13 +253,13 @@ mod tests {
         // - Error: complexity > 20
         
         // Mild violation: Info
-        assert_eq!(detector.severity_for_complexity(12), Severity::Info);
+        assert_eq!(detector.severity_for_complexity(12, 10), Severity::Info);
 
         // Moderate violation: Warning
-        assert_eq!(detector.severity_for_complexity(18), Severity::Warning);
+        assert_eq!(detector.severity_for_complexity(18, 10), Severity::Warning);
 
         // Severe violation (>2x threshold): Error
-        assert_eq!(detector.severity_for_complexity(25), Severity::Error);
+        assert_eq!(detector.severity_for_complexity(25, 10), Severity::Error);
     }
 
     #[test]
@@ -270,6 +267,7 @@ mod tests {
         let config = ComplexityConfig {
             enabled: true,
             max_complexity: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = ComplexityDetector::new(&config);
@@ -294,6 +292,7 @@ mod tests {
         let config = ComplexityConfig {
             enabled: true,
             max_complexity: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = ComplexityDetector::new(&config);
@@ -310,6 +309,7 @@ mod tests {
         let config = ComplexityConfig {
             enabled: true,
             max_complexity: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = ComplexityDetector::new(&config);
@@ -334,6 +334,7 @@ mod tests {
         let config = ComplexityConfig {
             enabled: true,
             max_complexity: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = ComplexityDetector::new(&config);
@@ -341,9 +342,11 @@ mod tests {
         // Create analysis with unparseable routine (pou = None)
         let st_routine = ParsedSTRoutine {
             location: STLocation::new("MainProgram", "BadRoutine"),
+            kind: RoutineKind::Program,
             source: "this is not valid ST {{{{".to_string(),
             pou: None,
             parse_error: None,
+            skipped: Vec::new(),
         };
 
         let analysis = ProjectAnalysis {
@@ -355,6 +358,7 @@ mod tests {
             instruction_usage: HashMap::new(),
             aoi_definitions: vec![],
             aoi_usage: HashMap::new(),
+            protected: vec![],
             stats: ParseStats::default(),
         };
 
@@ -363,4 +367,43 @@ mod tests {
 
         assert!(report.rules.is_empty(), "Unparseable routine should not trigger rule");
     }
+
+    #[test]
+    fn test_per_kind_threshold() {
+        let config = ComplexityConfig {
+            enabled: true,
+            max_complexity: 1,
+            per_kind: PouKindThresholds {
+                program: Some(5),
+                ..PouKindThresholds::default()
+            },
+            ignore_patterns: vec![],
+        };
+        let detector = ComplexityDetector::new(&config);
+        let source = "IF a THEN x := 1; ELSIF b THEN x := 2; END_IF;";
+
+        // Programs use their own threshold
+        let mut report = Report::new();
+        detector.detect(&create_test_analysis(source), &mut report);
+        assert!(report.rules.is_empty());
+
+        // AOIs fall back to the global threshold
+        let mut analysis = create_test_analysis(source);
+        analysis.st_routines[0].kind = RoutineKind::Aoi;
+        detector.detect(&analysis, &mut report);
+        assert_eq!(report.rules.len(), 1);
+        assert!(report.rules[0].message.contains("(max: 1)"));
+
+        let config = ComplexityConfig {
+            per_kind: PouKindThresholds {
+                aoi: Some(2),
+                ..PouKindThresholds::default()
+            },
+            ..config
+        };
+        let mut report = Report::new();
+        ComplexityDetector::new(&config).detect(&analysis, &mut report);
+        assert_eq!(report.rules.len(), 1);
+        assert!(report.rules[0].message.contains("(max for aoi: 2)"));
+    }
 }
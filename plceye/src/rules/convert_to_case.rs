@@ -0,0 +1,130 @@
+//! Convert to CASE detector.
+//!
+//! Detects IF/ELSIF chains whose conditions all compare one variable with
+//! distinct constants, which are clearer as a CASE statement (M0013).
+
+use iecst::{check_if_chains, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::ConvertToCaseConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for IF/ELSIF chains that should be CASE statements.
+pub struct ConvertToCaseDetector<'a> {
+    config: &'a ConvertToCaseConfig,
+}
+
+impl<'a> ConvertToCaseDetector<'a> {
+    /// Create a new convert to CASE detector with the given configuration.
+    pub fn new(config: &'a ConvertToCaseConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_if_chains(pou, self.config.min_branches) {
+                let DiagnosticKind::IfChainToCase { ref selector, branches } = diagnostic.kind else {
+                    continue;
+                };
+                report.add(Rule::new(
+                    RuleKind::ConvertToCase,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    selector.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name)
+                .with_param("branches", branches)
+                .with_param("threshold", self.config.min_branches));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for ConvertToCaseDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Severity;
+
+    fn detect(config: &ConvertToCaseConfig) -> Report {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Sequence" pouType="program">
+      <interface>
+        <localVars>
+          <variable name="Step"><type><INT/></type></variable>
+          <variable name="Speed"><type><INT/></type></variable>
+        </localVars>
+      </interface>
+      <body><ST><![CDATA[Speed := 0;
+IF Step = 10 THEN Speed := 100;
+ELSIF Step = 20 THEN Speed := 50;
+ELSIF Step = 30 THEN Speed := 10;
+END_IF;
+IF Step = 10 THEN Speed := 1; ELSIF Step = 20 THEN Speed := 2; END_IF;]]></ST></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let mut report = Report::new();
+        ConvertToCaseDetector::new(config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_if_chains_reported() {
+        let report = detect(&ConvertToCaseConfig::default());
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::ConvertToCase);
+        assert_eq!(report.rules[0].severity, Severity::Info);
+        assert_eq!(report.rules[0].identifier, "Step");
+        assert!(report.rules[0]
+            .message
+            .contains("line 2: IF/ELSIF chain of 3 branches compares 'Step' with constants; use CASE Step OF"));
+
+        let config = ConvertToCaseConfig {
+            min_branches: 2,
+            ..ConvertToCaseConfig::default()
+        };
+        assert_eq!(detect(&config).rules.len(), 2);
+    }
+}
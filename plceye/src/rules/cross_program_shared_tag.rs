@@ -0,0 +1,151 @@
+//! Cross-program shared tag detector.
+//!
+//! Detects controller-scoped tags written in one program and read in
+//! another (S0014). The reading program sees the value of the writer's
+//! current scan or of its previous one, depending on the scan order of the
+//! two programs, and programs in different tasks can interrupt each other
+//! between a write and a read. Accesses through a program-scoped tag of the
+//! same name are not counted. This is Rockwell-specific scope reasoning.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use l5x::Controller;
+
+use crate::analysis::{analyze_controller, name_key, AccessKind, StUnits, TagAccesses, TaskBindings};
+use crate::config::CrossProgramSharedTagConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for controller tags shared between a writing and a reading program.
+pub struct CrossProgramSharedTagDetector<'a> {
+    config: &'a CrossProgramSharedTagConfig,
+    case_sensitive: bool,
+}
+
+impl<'a> CrossProgramSharedTagDetector<'a> {
+    /// Create a new cross-program shared tag detector with the given configuration.
+    pub fn new(config: &'a CrossProgramSharedTagConfig) -> Self {
+        Self {
+            config,
+            case_sensitive: false,
+        }
+    }
+
+    /// Match tag names case-sensitively (`general.case_sensitive`).
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Run detection on a controller and add findings to the report.
+    ///
+    /// `bindings` gives the scan order of the programs, if the export has
+    /// tasks.
+    pub fn detect(
+        &self,
+        controller: &Controller,
+        accesses: &TagAccesses,
+        bindings: Option<&TaskBindings>,
+        report: &mut Report,
+    ) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let Some(ref tags) = controller.tags else {
+            return;
+        };
+
+        // Program-scoped tags shadow controller tags of the same name
+        let mut program_tags: HashSet<(&str, String)> = HashSet::new();
+        for program in controller.programs.iter().flat_map(|p| &p.program) {
+            for tag in program.tags.iter().flat_map(|t| &t.tag) {
+                program_tags.insert((program.name.as_str(), name_key(&tag.name, self.case_sensitive)));
+            }
+        }
+
+        // Writing and reading programs of each tag
+        let mut programs: HashMap<String, (BTreeSet<&str>, BTreeSet<&str>)> = HashMap::new();
+        for access in &accesses.accesses {
+            let key = name_key(&access.tag, self.case_sensitive);
+            if program_tags.contains(&(access.program.as_str(), key.clone())) {
+                continue;
+            }
+            let (writers, readers) = programs.entry(key).or_default();
+            match access.kind {
+                AccessKind::Write => writers.insert(access.program.as_str()),
+                AccessKind::Read => readers.insert(access.program.as_str()),
+            };
+        }
+
+        let severity = Severity::parse(&self.config.severity).unwrap_or(Severity::Info);
+        for tag in &tags.tag {
+            if self.matches_ignore_pattern(&tag.name) {
+                continue;
+            }
+            let Some((writers, readers)) = programs.get(&name_key(&tag.name, self.case_sensitive)) else {
+                continue;
+            };
+            for writer in writers {
+                for reader in readers.iter().filter(|reader| *reader != writer) {
+                    let order = bindings.map(|b| scan_order(b, writer, reader)).unwrap_or_default();
+                    report.add(Rule::new(
+                        RuleKind::CrossProgramSharedTag,
+                        severity,
+                        "Controller".to_string(),
+                        tag.name.clone(),
+                        format!(
+                            "Controller-scoped tag '{}' is written by program '{}' and read by program '{}'{}",
+                            tag.name, writer, reader, order
+                        ),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Check if a tag name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, tag_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, tag_name)
+    }
+}
+
+impl Detector for CrossProgramSharedTagDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                let accesses = TagAccesses::from_controller(&analyze_controller(controller), &StUnits::from_controller(controller));
+                self.detect(controller, &accesses, TaskBindings::from_controller(controller).as_ref(), report);
+            }
+        })
+    }
+}
+
+/// Describe when the reader runs relative to the writer, if both are scheduled.
+fn scan_order(bindings: &TaskBindings, writer: &str, reader: &str) -> String {
+    let position = |program: &str| {
+        bindings.tasks.iter().find_map(|task| {
+            let index = task.programs.iter().position(|p| p == program)?;
+            Some((task.name.as_str(), index))
+        })
+    };
+    match (position(writer), position(reader)) {
+        (Some((task, w)), Some((reader_task, r))) if task == reader_task => {
+            if r < w {
+                format!(
+                    "; '{}' runs before '{}' in task '{}' and reads the value of the previous scan",
+                    reader, writer, task
+                )
+            } else {
+                format!("; '{}' runs after '{}' in task '{}'", reader, writer, task)
+            }
+        }
+        (Some((writer_task, _)), Some((reader_task, _))) => format!(
+            "; they run in tasks '{}' and '{}', which can interrupt each other",
+            writer_task, reader_task
+        ),
+        _ => String::new(),
+    }
+}
@@ -0,0 +1,120 @@
+//! Dead store detector.
+//!
+//! Detects assignments whose value is overwritten on every path before it
+//! is read (C0019), such as a computed output that a later unconditional
+//! assignment replaces in the same scan.
+
+use iecst::{check_dead_stores, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::DeadStoreConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for assignments overwritten before they are read.
+pub struct DeadStoreDetector<'a> {
+    config: &'a DeadStoreConfig,
+}
+
+impl<'a> DeadStoreDetector<'a> {
+    /// Create a new dead store detector with the given configuration.
+    pub fn new(config: &'a DeadStoreConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_dead_stores(pou) {
+                let DiagnosticKind::DeadStore { ref name, overwritten_by } = diagnostic.kind else {
+                    continue;
+                };
+                report.add(Rule::new(
+                    RuleKind::DeadStore,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {} (overwritten at line {})",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind,
+                        unit.line_of(overwritten_by.start)
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for DeadStoreDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+
+    fn detect_l5x(st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Level" TagType="Base" DataType="DINT"/>
+               <Tag Name="FillValve" TagType="Base" DataType="BOOL"/>
+               <Tag Name="Count" TagType="Base" DataType="DINT"/>"#,
+            "Fill",
+            st_lines,
+        );
+        let config = DeadStoreConfig::default();
+        let mut report = Report::new();
+        DeadStoreDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_clobbered_output() {
+        let report = detect_l5x(&[
+            "FillValve := Level < 100;",
+            "Count := Count + 1;",
+            "FillValve := FALSE;",
+        ]);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::DeadStore);
+        assert_eq!(report.rules[0].identifier, "FillValve");
+        assert!(report.rules[0].message.contains("line 1"));
+        assert!(report.rules[0].message.contains("overwritten at line 3"));
+    }
+
+    #[test]
+    fn test_read_in_between() {
+        let report = detect_l5x(&[
+            "Count := Level;",
+            "Count := Count * 2;",
+            "IF Level > 90 THEN FillValve := FALSE; END_IF;",
+        ]);
+        assert!(report.rules.is_empty());
+    }
+}
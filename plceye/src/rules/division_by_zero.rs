@@ -0,0 +1,130 @@
+//! Division by zero detector.
+//!
+//! Detects `/` and `MOD` by a constant zero, and by variables that are not
+//! checked against zero before the division (C0014). A zero divisor faults
+//! the controller or yields an undefined REAL result.
+
+use iecst::{check_division_by_zero, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::DivisionByZeroConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for divisions by zero and unguarded divisors.
+pub struct DivisionByZeroDetector<'a> {
+    config: &'a DivisionByZeroConfig,
+}
+
+impl<'a> DivisionByZeroDetector<'a> {
+    /// Create a new division by zero detector with the given configuration.
+    pub fn new(config: &'a DivisionByZeroConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_division_by_zero(pou) {
+                if !self.config.check_variables && matches!(diagnostic.kind, DiagnosticKind::UnguardedDivision { .. }) {
+                    continue;
+                }
+                report.add(Rule::new(
+                    RuleKind::DivisionByZero,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for DivisionByZeroDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Severity;
+
+    fn detect(config: &DivisionByZeroConfig) -> Report {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Flow" pouType="program">
+      <interface><localVars>
+        <variable name="Volume"><type><REAL/></type></variable>
+        <variable name="Seconds"><type><REAL/></type></variable>
+        <variable name="Rate"><type><REAL/></type></variable>
+      </localVars></interface>
+      <body><ST><![CDATA[IF Seconds > 0.0 THEN
+    Rate := Volume / Seconds;
+END_IF;
+Rate := Volume / Seconds;
+Rate := Volume / 0;]]></ST></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let mut report = Report::new();
+        DivisionByZeroDetector::new(config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_zero_and_unguarded_divisors() {
+        let report = detect(&DivisionByZeroConfig::default());
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.rules[0].severity, Severity::Warning);
+        assert!(report.rules[0].message.contains("line 4: divisor 'Seconds' is not checked for zero"));
+        assert_eq!(report.rules[1].severity, Severity::Error);
+        assert!(report.rules[1].message.contains("line 5: division by zero"));
+    }
+
+    #[test]
+    fn test_constant_zero_only() {
+        let config = DivisionByZeroConfig {
+            check_variables: false,
+            ..DivisionByZeroConfig::default()
+        };
+        let report = detect(&config);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].severity, Severity::Error);
+    }
+}
@@ -0,0 +1,156 @@
+//! Duplicate logic detector.
+//!
+//! Detects routines whose logic is identical or nearly identical to
+//! another routine once tag names, comments and whitespace are abstracted
+//! (M0007). Copy-pasted routines drift apart when only one copy is fixed;
+//! they are candidates for an AOI or function block.
+
+use crate::analysis::{analyze_controller, parse_st_bodies, DuplicateGroup, DuplicateLogic};
+use crate::config::DuplicateLogicConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for routines with duplicated logic.
+pub struct DuplicateLogicDetector<'a> {
+    config: &'a DuplicateLogicConfig,
+}
+
+impl<'a> DuplicateLogicDetector<'a> {
+    /// Create a new duplicate logic detector with the given configuration.
+    pub fn new(config: &'a DuplicateLogicConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the normalized routines of a project and add findings to the report.
+    pub fn detect(&self, logic: &DuplicateLogic, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let mut logic = logic.clone();
+        logic.routines.retain(|routine| !self.matches_ignore_pattern(&routine.path));
+
+        for group in logic.groups(self.config.min_size, self.config.min_similarity) {
+            report.add(Rule::new(
+                RuleKind::DuplicateLogic,
+                Severity::Info,
+                group.paths[0].clone(),
+                group.paths[0].clone(),
+                Self::message(&group),
+            ));
+        }
+    }
+
+    fn message(group: &DuplicateGroup) -> String {
+        let likeness = if group.similarity >= 1.0 {
+            "identical".to_string()
+        } else {
+            format!("{:.0}% similar", group.similarity * 100.0)
+        };
+        format!(
+            "{} routines with {} logic ({} rungs/statements): {}",
+            group.paths.len(),
+            likeness,
+            group.size,
+            group.paths.join(", ")
+        )
+    }
+
+    /// Check if a routine path matches any ignore pattern.
+    fn matches_ignore_pattern(&self, path: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, path)
+    }
+}
+
+impl Detector for DuplicateLogicDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(&DuplicateLogic::from_controller(&analyze_controller(controller)), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&DuplicateLogic::from_plcopen(&parse_st_bodies(plcopen)), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::analyze_controller;
+
+    fn rll_routine(name: &str, tag: &str) -> String {
+        let rungs: String = (0..3)
+            .map(|n| {
+                format!(
+                    "<Rung Number=\"{n}\" Type=\"N\"><Comment><![CDATA[{name} rung {n}]]></Comment>\
+                     <Text><![CDATA[XIC({tag}_Run)[XIO({tag}_Fault{n}),TON({tag}_Timer{n},?,?)]OTE({tag}_Out{n});]]></Text></Rung>"
+                )
+            })
+            .collect();
+        format!("<Routine Name=\"{name}\" Type=\"RLL\"><RLLContent>{rungs}</RLLContent></Routine>")
+    }
+
+    fn detect(config: &DuplicateLogicConfig) -> Report {
+        let xml = format!(
+            r#"<?xml version="1.0"?>
+            <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+                <Controller Name="Test">
+                    <Programs>
+                        <Program Name="Main">
+                            <Routines>
+                                {}
+                                {}
+                                <Routine Name="Other" Type="RLL">
+                                    <RLLContent>
+                                        <Rung Number="0" Type="N"><Text><![CDATA[XIC(A)OTE(B);]]></Text></Rung>
+                                        <Rung Number="1" Type="N"><Text><![CDATA[XIC(C)OTL(D);]]></Text></Rung>
+                                        <Rung Number="2" Type="N"><Text><![CDATA[XIC(E)OTU(D);]]></Text></Rung>
+                                    </RLLContent>
+                                </Routine>
+                            </Routines>
+                        </Program>
+                    </Programs>
+                </Controller>
+            </RSLogix5000Content>"#,
+            rll_routine("Conveyor1", "CV1"),
+            rll_routine("Conveyor2", "CV2"),
+        );
+        let project = LoadedProject::from_str(&xml, None).unwrap();
+        let analysis = analyze_controller(project.l5x_controller.as_ref().unwrap());
+        let logic = DuplicateLogic::from_controller(&analysis);
+
+        let mut report = Report::new();
+        DuplicateLogicDetector::new(config).detect(&logic, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_copied_routines_reported() {
+        let config = DuplicateLogicConfig {
+            min_size: 3,
+            ..DuplicateLogicConfig::default()
+        };
+        let report = detect(&config);
+        assert_eq!(report.rules().len(), 1);
+        let rule = &report.rules()[0];
+        assert_eq!(rule.kind, RuleKind::DuplicateLogic);
+        assert_eq!(rule.location, "Main/Conveyor1");
+        assert!(rule.message.contains("identical"));
+        assert!(rule.message.contains("Main/Conveyor2"));
+    }
+
+    #[test]
+    fn test_min_size_and_ignore() {
+        assert!(detect(&DuplicateLogicConfig::default()).is_empty());
+
+        let config = DuplicateLogicConfig {
+            min_size: 3,
+            ignore_patterns: vec!["*/Conveyor2".to_string()],
+            ..DuplicateLogicConfig::default()
+        };
+        assert!(detect(&config).is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+//! Empty branch detector.
+//!
+//! Detects IF/ELSIF/ELSE branches, loop bodies and CASE branches without
+//! statements (S0006).
+
+use iecst::check_empty_bodies;
+
+use crate::analysis::StUnits;
+use crate::config::EmptyBranchesConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for empty branches and loop bodies.
+pub struct EmptyBranchesDetector<'a> {
+    config: &'a EmptyBranchesConfig,
+}
+
+impl<'a> EmptyBranchesDetector<'a> {
+    /// Create a new empty branch detector with the given configuration.
+    pub fn new(config: &'a EmptyBranchesConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_empty_bodies(pou) {
+                report.add(Rule::new(
+                    RuleKind::EmptyBranch,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for EmptyBranchesDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+    use crate::report::Severity;
+
+    fn detect_l5x(st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Idx" TagType="Base" DataType="DINT"/>
+               <Tag Name="Total" TagType="Base" DataType="DINT"/>
+               <Tag Name="Values" TagType="Base" DataType="DINT" Dimensions="10"/>"#,
+            "Sum",
+            st_lines,
+        );
+        let config = EmptyBranchesConfig::default();
+        let mut report = Report::new();
+        EmptyBranchesDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_empty_then_and_loop() {
+        let report = detect_l5x(&[
+            "IF Total > 100 THEN",
+            "// TODO: alarm",
+            "END_IF;",
+            "FOR Idx := 0 TO 9 DO",
+            "END_FOR;",
+        ]);
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.rules[0].kind, RuleKind::EmptyBranch);
+        assert_eq!(report.rules[0].severity, Severity::Info);
+        assert!(report.rules[0].message.contains("line 1: empty IF body"));
+        assert!(report.rules[1].message.contains("line 4: empty FOR body"));
+    }
+
+    #[test]
+    fn test_case_noop_branch() {
+        let report = detect_l5x(&[
+            "CASE Idx OF",
+            "0: ;",
+            "1: Total := 0;",
+            "ELSE",
+            "Total := Values[Idx];",
+            "END_CASE;",
+        ]);
+        assert!(report.rules.is_empty());
+    }
+}
@@ -4,10 +4,13 @@
 
 use l5x::Controller;
 
-use crate::analysis::ProjectAnalysis;
+use crate::analysis::{analyze_controller, ProjectAnalysis};
 
 use crate::config::EmptyRoutinesConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
 use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
 
 /// Detector for empty routines.
 pub struct EmptyRoutinesDetector<'a> {
@@ -27,8 +30,8 @@ impl<'a> EmptyRoutinesDetector<'a> {
         }
 
         for routine in &analysis.routines {
-            // Skip if matches ignore pattern
-            if self.matches_ignore_pattern(&routine.routine) {
+            // Skip if matches ignore pattern, or protected with no logic to check
+            if self.matches_ignore_pattern(&routine.routine) || routine.protected {
                 continue;
             }
 
@@ -49,68 +52,24 @@ impl<'a> EmptyRoutinesDetector<'a> {
                         "Routine '{}' in program '{}' appears to be empty (type: {})",
                         routine.routine, routine.program, routine.routine_type
                     ),
-                ));
+                )
+                .in_routine(&routine.routine));
             }
         }
     }
 
     /// Check if a routine name matches any ignore pattern.
     fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
-        for pattern in &self.config.ignore_patterns {
-            if glob_match(pattern, routine_name) {
-                return true;
-            }
-        }
-        false
+        matches_any(&self.config.ignore_patterns, routine_name)
     }
 }
 
-/// Simple glob pattern matching supporting * and ? wildcards.
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let pattern_chars: Vec<char> = pattern.chars().collect();
-    let text_chars: Vec<char> = text.chars().collect();
-    glob_match_recursive(&pattern_chars, &text_chars, 0, 0)
-}
-
-fn glob_match_recursive(pattern: &[char], text: &[char], pi: usize, ti: usize) -> bool {
-    if pi == pattern.len() {
-        return ti == text.len();
-    }
-
-    match pattern[pi] {
-        '*' => {
-            for i in ti..=text.len() {
-                if glob_match_recursive(pattern, text, pi + 1, i) {
-                    return true;
-                }
-            }
-            false
-        }
-        '?' => {
-            if ti < text.len() {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
+impl Detector for EmptyRoutinesDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(controller, &analyze_controller(controller), report);
             }
-        }
-        c => {
-            if ti < text.len() && c.eq_ignore_ascii_case(&text[ti]) {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
-            }
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_glob_match() {
-        assert!(glob_match("Main*", "MainRoutine"));
-        assert!(glob_match("*_Test", "Unit_Test"));
-        assert!(!glob_match("Main*", "OtherRoutine"));
+        })
     }
 }
@@ -0,0 +1,128 @@
+//! Enum value conflict detector.
+//!
+//! Detects enum members whose value, explicit or one more than the previous
+//! member, equals that of an earlier member, and explicit values lower than
+//! the preceding member's (C0021).
+
+use iecst::{check_enum_values, DiagnosticKind, TypeDecl};
+
+use crate::analysis::StUnits;
+use crate::config::EnumValueConflictConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for conflicting enum member values.
+pub struct EnumValueConflictDetector<'a> {
+    config: &'a EnumValueConflictConfig,
+}
+
+impl<'a> EnumValueConflictDetector<'a> {
+    /// Create a new enum value conflict detector with the given configuration.
+    pub fn new(config: &'a EnumValueConflictConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the type declarations of ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        // Skip types matching an ignore pattern
+        let types: Vec<TypeDecl> = st_units
+            .types
+            .iter()
+            .filter(|decl| !self.matches_ignore_pattern(&decl.name))
+            .cloned()
+            .collect();
+
+        for diagnostic in check_enum_values(&types) {
+            let (DiagnosticKind::DuplicateEnumValue { type_name, .. }
+            | DiagnosticKind::DecreasingEnumValue { type_name, .. }) = &diagnostic.kind
+            else {
+                continue;
+            };
+            report.add(Rule::new(
+                RuleKind::EnumValueConflict,
+                diagnostic.severity.into(),
+                "DataTypes".to_string(),
+                type_name.clone(),
+                format!("DataType '{}': {}", type_name, diagnostic.kind),
+            ));
+        }
+    }
+
+    /// Check if a type name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, type_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, type_name)
+    }
+}
+
+impl Detector for EnumValueConflictDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Severity;
+
+    fn detect(values: &str, config: &EnumValueConflictConfig) -> Report {
+        let xml = format!(
+            r#"<?xml version="1.0"?>
+            <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+                <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+                <contentHeader name="Test"/>
+                <types>
+                    <dataTypes>
+                        <dataType name="Mode">
+                            <baseType><enum><values>{}</values></enum></baseType>
+                        </dataType>
+                    </dataTypes>
+                    <pous/>
+                </types>
+            </project>"#,
+            values
+        );
+
+        let project = LoadedProject::from_str(&xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let mut report = Report::new();
+        EnumValueConflictDetector::new(config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_duplicate_and_decreasing_values() {
+        let report = detect(
+            r#"<value name="Idle" value="2"/><value name="Manual" value="1"/><value name="Auto"/>"#,
+            &EnumValueConflictConfig::default(),
+        );
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.rules[0].kind, RuleKind::EnumValueConflict);
+        assert_eq!(report.rules[0].severity, Severity::Info);
+        assert!(report.rules[0].message.contains("'Manual' = 1 is lower than the preceding 'Idle' = 2"));
+        assert_eq!(report.rules[1].severity, Severity::Warning);
+        assert_eq!(
+            report.rules[1].message,
+            "DataType 'Mode': enum 'Mode' member 'Auto' has value 2, the same as 'Idle'"
+        );
+    }
+
+    #[test]
+    fn test_sequential_and_ignored() {
+        let report = detect(
+            r#"<value name="Idle"/><value name="Manual" value="4"/><value name="Auto"/>"#,
+            &EnumValueConflictConfig::default(),
+        );
+        assert!(report.rules.is_empty());
+
+        let config = EnumValueConflictConfig { ignore_patterns: vec!["Mo*".to_string()], ..Default::default() };
+        let report = detect(r#"<value name="Idle" value="1"/><value name="Auto" value="1"/>"#, &config);
+        assert!(report.rules.is_empty());
+    }
+}
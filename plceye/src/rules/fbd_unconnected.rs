@@ -0,0 +1,140 @@
+//! Unconnected FBD pin detector.
+//!
+//! Detects FBD blocks whose required inputs have no connection, function
+//! outputs that feed nothing, and parts of a network that reach no output
+//! (C0013). This is the FBD counterpart of unused variables: the wire was
+//! forgotten, so the block computes with a default or its result is lost.
+
+use crate::analysis::{fbd_networks_from_plcopen, FbdIssueKind, FbdNetwork};
+use crate::config::FbdUnconnectedConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for wiring mistakes in FBD networks.
+pub struct FbdUnconnectedDetector<'a> {
+    config: &'a FbdUnconnectedConfig,
+}
+
+impl<'a> FbdUnconnectedDetector<'a> {
+    /// Create a new unconnected FBD pin detector with the given configuration.
+    pub fn new(config: &'a FbdUnconnectedConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the FBD bodies of a project and add findings to the report.
+    pub fn detect(&self, networks: &[FbdNetwork], report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for network in networks {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&network.pou) {
+                continue;
+            }
+
+            for issue in &network.issues {
+                let pin = issue.pin.as_deref().unwrap_or_default();
+                let (enabled, severity, message) = match issue.kind {
+                    FbdIssueKind::UnconnectedInput => (
+                        self.config.check_inputs,
+                        Severity::Warning,
+                        format!("POU '{}': input '{}' of '{}' is not connected", network.pou, pin, issue.element),
+                    ),
+                    FbdIssueKind::FloatingOutput => (
+                        self.config.check_outputs,
+                        Severity::Warning,
+                        format!("POU '{}': output '{}' of '{}' feeds nothing", network.pou, pin, issue.element),
+                    ),
+                    FbdIssueKind::Disconnected => (
+                        self.config.check_disconnected,
+                        Severity::Info,
+                        format!("POU '{}': network part '{}' reaches no output", network.pou, issue.element),
+                    ),
+                };
+                if !enabled {
+                    continue;
+                }
+                let identifier = match &issue.pin {
+                    Some(pin) => format!("{}.{}", issue.element, pin),
+                    None => issue.element.clone(),
+                };
+                report.add(Rule::new(RuleKind::FbdUnconnected, severity, network.pou.clone(), identifier, message));
+            }
+        }
+    }
+
+    /// Check if a POU name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for FbdUnconnectedDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&fbd_networks_from_plcopen(plcopen), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::FbdIssue;
+
+    fn detect(config: &FbdUnconnectedConfig) -> Report {
+        let issue = |kind, element: &str, pin: Option<&str>| FbdIssue {
+            kind,
+            element: element.to_string(),
+            pin: pin.map(str::to_string),
+        };
+        let network = FbdNetwork {
+            pou: "Mixer".to_string(),
+            issues: vec![
+                issue(FbdIssueKind::Disconnected, "Spare", None),
+                issue(FbdIssueKind::UnconnectedInput, "FillTimer", Some("PT")),
+                issue(FbdIssueKind::FloatingOutput, "ADD #4", Some("OUT")),
+            ],
+        };
+        let mut report = Report::new();
+        FbdUnconnectedDetector::new(config).detect(&[network], &mut report);
+        report
+    }
+
+    #[test]
+    fn test_unconnected_pins() {
+        let report = detect(&FbdUnconnectedConfig::default());
+        let findings: Vec<_> = report.rules.iter().map(|r| (r.identifier.as_str(), r.message.as_str())).collect();
+        assert_eq!(
+            findings,
+            [
+                ("Spare", "POU 'Mixer': network part 'Spare' reaches no output"),
+                ("FillTimer.PT", "POU 'Mixer': input 'PT' of 'FillTimer' is not connected"),
+                ("ADD #4.OUT", "POU 'Mixer': output 'OUT' of 'ADD #4' feeds nothing"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_checks_optional() {
+        let config = FbdUnconnectedConfig {
+            check_outputs: false,
+            check_disconnected: false,
+            ..FbdUnconnectedConfig::default()
+        };
+        let report = detect(&config);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].identifier, "FillTimer.PT");
+
+        let config = FbdUnconnectedConfig {
+            ignore_patterns: vec!["Mix*".to_string()],
+            ..FbdUnconnectedConfig::default()
+        };
+        assert!(detect(&config).rules.is_empty());
+    }
+}
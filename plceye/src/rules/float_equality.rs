@@ -0,0 +1,107 @@
+//! Float equality detector.
+//!
+//! Detects `=` and `<>` comparisons with REAL or LREAL operands, which
+//! should use a tolerance instead (C0010).
+
+use iecst::check_float_equality;
+
+use crate::analysis::StUnits;
+use crate::config::FloatEqualityConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for floating-point equality comparisons.
+pub struct FloatEqualityDetector<'a> {
+    config: &'a FloatEqualityConfig,
+}
+
+impl<'a> FloatEqualityDetector<'a> {
+    /// Create a new float equality detector with the given configuration.
+    pub fn new(config: &'a FloatEqualityConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_float_equality(pou, &st_units.functions) {
+                report.add(Rule::new(
+                    RuleKind::FloatEquality,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for FloatEqualityDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+    use crate::report::Severity;
+
+    fn detect_l5x(st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Level" TagType="Base" DataType="REAL"/>
+               <Tag Name="Count" TagType="Base" DataType="DINT"/>
+               <Tag Name="Full" TagType="Base" DataType="BOOL"/>"#,
+            "Tank",
+            st_lines,
+        );
+        let config = FloatEqualityConfig::default();
+        let mut report = Report::new();
+        FloatEqualityDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_real_equality() {
+        let report = detect_l5x(&["Full := Count = 10;", "IF Level = 3.14 THEN", "Full := TRUE;", "END_IF;"]);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::FloatEquality);
+        assert_eq!(report.rules[0].severity, Severity::Info);
+        assert!(report.rules[0].message.contains("line 2"));
+    }
+
+    #[test]
+    fn test_tolerance_comparison() {
+        let report = detect_l5x(&["Full := ABS(Level - 3.14) < 0.001;", "Full := Level >= 100.0;"]);
+        assert!(report.rules.is_empty());
+    }
+}
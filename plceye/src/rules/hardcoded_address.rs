@@ -0,0 +1,129 @@
+//! Hardcoded address detector.
+//!
+//! Detects direct addresses such as `%MW100` or `%QX2.3` used in ST logic
+//! instead of named variables mapped to them with `AT` (M0012).
+
+use iecst::{check_direct_addresses, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::HardcodedAddressConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for direct addresses in logic.
+pub struct HardcodedAddressDetector<'a> {
+    config: &'a HardcodedAddressConfig,
+}
+
+impl<'a> HardcodedAddressDetector<'a> {
+    /// Create a new hardcoded address detector with the given configuration.
+    pub fn new(config: &'a HardcodedAddressConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_direct_addresses(pou) {
+                let DiagnosticKind::HardcodedAddress { ref address } = diagnostic.kind else {
+                    continue;
+                };
+                if matches_any(&self.config.allowed_addresses, address) {
+                    continue;
+                }
+                report.add(Rule::new(
+                    RuleKind::HardcodedAddress,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    address.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for HardcodedAddressDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Severity;
+
+    fn detect(config: &HardcodedAddressConfig) -> Report {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Filling" pouType="program">
+      <interface>
+        <localVars>
+          <variable name="Valve" address="%QX2.3"><type><BOOL/></type></variable>
+          <variable name="Level"><type><INT/></type></variable>
+        </localVars>
+      </interface>
+      <body><ST><![CDATA[Level := %IW4;
+Valve := Level < 100;
+%MW100 := Level;]]></ST></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let mut report = Report::new();
+        HardcodedAddressDetector::new(config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_hardcoded_addresses_reported() {
+        let report = detect(&HardcodedAddressConfig::default());
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.rules[0].kind, RuleKind::HardcodedAddress);
+        assert_eq!(report.rules[0].severity, Severity::Info);
+        assert_eq!(report.rules[0].identifier, "%IW4");
+        assert!(report.rules[0].message.contains("line 1: direct address '%IW4' used in logic"));
+        assert_eq!(report.rules[1].identifier, "%MW100");
+
+        let config = HardcodedAddressConfig {
+            allowed_addresses: vec!["%MW*".to_string()],
+            ..HardcodedAddressConfig::default()
+        };
+        let report = detect(&config);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].identifier, "%IW4");
+    }
+}
@@ -0,0 +1,167 @@
+//! Identifier detector.
+//!
+//! Detects names longer than the configured maximum length (N0007) and
+//! names that are IEC 61131-3 reserved words (N0009). Keywords are matched
+//! case-insensitively, as IEC 61131-3 identifiers are.
+
+use iec61131::is_keyword;
+
+use crate::analysis::{names_from_controller, names_from_plcopen, NamedItem};
+use crate::config::IdentifiersConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for overlong names and reserved words used as names.
+pub struct IdentifiersDetector<'a> {
+    config: &'a IdentifiersConfig,
+}
+
+impl<'a> IdentifiersDetector<'a> {
+    /// Create a new identifier detector with the given configuration.
+    pub fn new(config: &'a IdentifiersConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the named items of a project and add findings to the report.
+    pub fn detect(&self, names: &[NamedItem], report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for item in names {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&item.name) {
+                continue;
+            }
+
+            let length = item.name.chars().count();
+            if length > self.config.max_length {
+                report.add(Rule::new(
+                    RuleKind::NameTooLong,
+                    Severity::Warning,
+                    item.location.clone(),
+                    item.name.clone(),
+                    format!(
+                        "{} '{}' is {} characters long (maximum {})",
+                        item.category.describe(),
+                        item.name,
+                        length,
+                        self.config.max_length
+                    ),
+                )
+                .with_param("length", length)
+                .with_param("threshold", self.config.max_length));
+            }
+
+            if self.config.check_reserved_words && is_keyword(&item.name) {
+                report.add(Rule::new(
+                    RuleKind::ReservedWordName,
+                    Severity::Warning,
+                    item.location.clone(),
+                    item.name.clone(),
+                    format!(
+                        "{} '{}' is an IEC 61131-3 reserved word",
+                        item.category.describe(),
+                        item.name
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Check if a name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for IdentifiersDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(&names_from_controller(controller), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&names_from_plcopen(plcopen), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{names_from_controller, names_from_plcopen};
+
+    fn detect(xml: &str, config: &IdentifiersConfig) -> Report {
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let names = match project.l5x_controller {
+            Some(ref controller) => names_from_controller(controller),
+            None => names_from_plcopen(project.plcopen_project.as_ref().unwrap()),
+        };
+        let mut report = Report::new();
+        IdentifiersDetector::new(config).detect(&names, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_l5x_long_and_reserved_names() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <Tags>
+                    <Tag Name="Conveyor_Infeed_Photoeye_Blocked_Timer" TagType="Base" DataType="TIMER"/>
+                    <Tag Name="Time" TagType="Base" DataType="DINT"/>
+                    <Tag Name="Motor1" TagType="Base" DataType="BOOL"/>
+                </Tags>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let report = detect(xml, &IdentifiersConfig::default());
+        assert_eq!(report.rules.len(), 2);
+
+        let long = report.rules.iter().find(|r| r.kind == RuleKind::NameTooLong).unwrap();
+        assert_eq!(long.identifier, "Conveyor_Infeed_Photoeye_Blocked_Timer");
+        assert!(long.message.contains("38 characters long (maximum 30)"));
+
+        let reserved = report.rules.iter().find(|r| r.kind == RuleKind::ReservedWordName).unwrap();
+        assert_eq!(reserved.identifier, "Time");
+        assert_eq!(reserved.location, "Controller");
+    }
+
+    #[test]
+    fn test_plcopen_config() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="Test" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Main" pouType="program">
+                        <interface>
+                            <localVars>
+                                <variable name="method"><type><INT/></type></variable>
+                                <variable name="LongCounterName"><type><INT/></type></variable>
+                            </localVars>
+                        </interface>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        let report = detect(xml, &IdentifiersConfig::default());
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::ReservedWordName);
+        assert!(report.rules[0].message.contains("'method'"));
+
+        let config = IdentifiersConfig {
+            max_length: 10,
+            check_reserved_words: false,
+            ..IdentifiersConfig::default()
+        };
+        let report = detect(xml, &config);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].identifier, "LongCounterName");
+    }
+}
@@ -0,0 +1,119 @@
+//! Impure function detector.
+//!
+//! Detects FUNCTIONs that write VAR_GLOBAL or VAR_EXTERNAL variables or
+//! call function block instances (S0012). Functions are assumed to have no
+//! state and no side effects, so they can be called from any task.
+
+use iecst::{check_function_purity, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::ImpureFunctionConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for functions with side effects.
+pub struct ImpureFunctionDetector<'a> {
+    config: &'a ImpureFunctionConfig,
+}
+
+impl<'a> ImpureFunctionDetector<'a> {
+    /// Create a new impure function detector with the given configuration.
+    pub fn new(config: &'a ImpureFunctionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_function_purity(pou) {
+                let identifier = match diagnostic.kind {
+                    DiagnosticKind::GlobalWriteInFunction { ref name, .. } => name,
+                    DiagnosticKind::StatefulCallInFunction { ref instance, .. } => instance,
+                    _ => continue,
+                };
+                report.add(Rule::new(
+                    RuleKind::ImpureFunction,
+                    Severity::Warning,
+                    unit.location.clone(),
+                    identifier.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for ImpureFunctionDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_impure_function_reported() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Scale" pouType="function">
+      <interface>
+        <returnType><REAL/></returnType>
+        <inputVars><variable name="Raw"><type><INT/></type></variable></inputVars>
+        <externalVars><variable name="Calls"><type><DINT/></type></variable></externalVars>
+      </interface>
+      <body><ST><![CDATA[Calls := Calls + 1;
+Scale := INT_TO_REAL(Raw) * 0.1;]]></ST></body>
+    </pou>
+    <pou name="Counter" pouType="functionBlock">
+      <interface>
+        <externalVars><variable name="Calls"><type><DINT/></type></variable></externalVars>
+      </interface>
+      <body><ST><![CDATA[Calls := Calls + 1;]]></ST></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let mut report = Report::new();
+        ImpureFunctionDetector::new(&ImpureFunctionConfig::default()).detect(&st_units, &mut report);
+
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::ImpureFunction);
+        assert_eq!(report.rules[0].severity, Severity::Warning);
+        assert_eq!(report.rules[0].identifier, "Calls");
+        assert!(report.rules[0].message.contains("line 1: function 'Scale' writes global variable 'Calls'"));
+    }
+}
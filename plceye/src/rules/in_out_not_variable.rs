@@ -0,0 +1,323 @@
+//! VAR_IN_OUT binding detector.
+//!
+//! Detects ST calls of user-defined functions, function blocks and AOIs that
+//! bind a VAR_IN_OUT parameter to a literal, an arithmetic expression or a
+//! function result (C0022). In-out parameters are passed by reference, so
+//! the argument must be a variable. Named and positional arguments are
+//! checked; a called instance is resolved through its declared type.
+
+use iecst::visitor::{walk_expr, walk_stmt};
+use iecst::{Expr, ExprKind, Pou, Stmt, StmtKind, TypeKind, Visitor};
+
+use crate::analysis::{Parameter, ParameterKind, Signature, StUnits};
+use crate::config::InOutNotVariableConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for VAR_IN_OUT parameters bound to something other than a variable.
+pub struct InOutNotVariableDetector<'a> {
+    config: &'a InOutNotVariableConfig,
+}
+
+impl<'a> InOutNotVariableDetector<'a> {
+    /// Create a new VAR_IN_OUT binding detector with the given configuration.
+    pub fn new(config: &'a InOutNotVariableConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            let mut bindings = Bindings {
+                st_units,
+                pou,
+                found: Vec::new(),
+            };
+            bindings.visit_body(&pou.body);
+
+            for binding in bindings.found {
+                let text = unit
+                    .source
+                    .get(binding.argument.span.start..binding.argument.span.end)
+                    .unwrap_or("?");
+                report.add(Rule::new(
+                    RuleKind::InOutNotVariable,
+                    Severity::Error,
+                    unit.location.clone(),
+                    format!("{}.{}", binding.signature.name, binding.parameter.name),
+                    format!(
+                        "Routine '{}' line {}: call of '{}' binds VAR_IN_OUT parameter '{}' to {} '{}'; \
+                         in-out parameters are passed by reference and need a variable",
+                        unit.name,
+                        unit.line_of(binding.argument.span.start),
+                        binding.callee,
+                        binding.parameter.name,
+                        describe(binding.argument),
+                        text
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for InOutNotVariableDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+/// An in-out parameter bound to something other than a variable.
+struct Binding<'ast> {
+    /// Called POU or instance as written
+    callee: &'ast str,
+    signature: &'ast Signature,
+    parameter: &'ast Parameter,
+    argument: &'ast Expr,
+}
+
+/// Collects the in-out bindings of the calls in a body.
+struct Bindings<'ast> {
+    st_units: &'ast StUnits,
+    pou: &'ast Pou,
+    found: Vec<Binding<'ast>>,
+}
+
+impl<'ast> Bindings<'ast> {
+    /// Check the arguments of a call: (parameter name, value, output binding).
+    fn check_call(
+        &mut self,
+        callee: &'ast str,
+        args: impl Iterator<Item = (Option<&'ast str>, Option<&'ast Expr>, bool)>,
+    ) {
+        let Some((signature, skip)) = self.resolve(callee) else {
+            return;
+        };
+
+        let mut position = 0;
+        for (name, value, output) in args {
+            let parameter = match name {
+                Some(name) => signature.parameter(name),
+                None => {
+                    position += 1;
+                    (position > skip).then(|| signature.positional(position - 1 - skip)).flatten()
+                }
+            };
+            let (Some(parameter), Some(argument)) = (parameter, value) else {
+                continue;
+            };
+            if !output && parameter.kind == ParameterKind::InOut && !is_variable(argument) {
+                self.found.push(Binding {
+                    callee,
+                    signature,
+                    parameter,
+                    argument,
+                });
+            }
+        }
+    }
+
+    /// The signature of a called POU, or of the type of a called instance,
+    /// and the number of leading positional arguments that are not parameters.
+    fn resolve(&self, callee: &str) -> Option<(&'ast Signature, usize)> {
+        if let Some(signature) = self.st_units.signature(callee) {
+            return Some((signature, usize::from(signature.instance_argument)));
+        }
+        let instance = self
+            .pou
+            .var_blocks
+            .iter()
+            .flat_map(|block| &block.vars)
+            .find(|var| var.name.eq_ignore_ascii_case(callee))?;
+        match &instance.var_type.kind {
+            TypeKind::Simple(type_name) => Some((self.st_units.signature(type_name)?, 0)),
+            _ => None,
+        }
+    }
+}
+
+impl<'ast> Visitor<'ast> for Bindings<'ast> {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        if let StmtKind::Call { name, args } = &stmt.kind {
+            self.check_call(
+                name,
+                args.iter().map(|arg| (arg.name.as_deref(), arg.value.as_ref(), arg.output)),
+            );
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let ExprKind::FunctionCall { name, args } = &expr.kind {
+            self.check_call(
+                name,
+                args.iter().map(|arg| (arg.name.as_deref(), arg.value.as_ref(), arg.output)),
+            );
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Check if an expression denotes a variable that can be passed by reference.
+fn is_variable(expr: &Expr) -> bool {
+    match &expr.kind {
+        ExprKind::Ident(_) | ExprKind::DirectAddress(_) => true,
+        ExprKind::ArrayIndex { array: base, .. } | ExprKind::MemberAccess { expr: base, .. } | ExprKind::Paren(base) => {
+            is_variable(base)
+        }
+        _ => false,
+    }
+}
+
+/// Describe what a non-variable argument is.
+fn describe(expr: &Expr) -> &'static str {
+    match &expr.kind {
+        ExprKind::IntLiteral(_)
+        | ExprKind::RealLiteral(_)
+        | ExprKind::StringLiteral(_)
+        | ExprKind::WStringLiteral(_)
+        | ExprKind::BoolLiteral(_)
+        | ExprKind::TimeLiteral(_)
+        | ExprKind::DateLiteral(_)
+        | ExprKind::TodLiteral(_)
+        | ExprKind::DateTimeLiteral(_) => "literal",
+        ExprKind::FunctionCall { .. } => "the result of",
+        ExprKind::Paren(inner) => describe(inner),
+        _ => "expression",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(st_units: &StUnits) -> Vec<(String, String)> {
+        let mut report = Report::new();
+        InOutNotVariableDetector::new(&InOutNotVariableConfig::default()).detect(st_units, &mut report);
+        report.rules.into_iter().map(|r| (r.identifier, r.message)).collect()
+    }
+
+    #[test]
+    fn test_plcopen_named_and_positional_bindings() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Scale" pouType="functionBlock">
+      <interface>
+        <inputVars><variable name="Factor"><type><INT/></type></variable></inputVars>
+        <outputVars><variable name="Done"><type><BOOL/></type></variable></outputVars>
+        <inOutVars><variable name="Value"><type><INT/></type></variable></inOutVars>
+      </interface>
+      <body><ST><![CDATA[Value := Value * Factor;]]></ST></body>
+    </pou>
+    <pou name="Clamp" pouType="function">
+      <interface>
+        <returnType><INT/></returnType>
+        <inputVars><variable name="Limit"><type><INT/></type></variable></inputVars>
+        <inOutVars><variable name="Buffer"><type><INT/></type></variable></inOutVars>
+      </interface>
+      <body><ST><![CDATA[Buffer := MIN(Buffer, Limit); Clamp := Buffer;]]></ST></body>
+    </pou>
+    <pou name="Main" pouType="program">
+      <interface><localVars>
+        <variable name="Scaler"><type><derived name="Scale"/></type></variable>
+        <variable name="Level"><type><INT/></type></variable>
+        <variable name="Data"><type><array><dimension lower="0" upper="9"/><baseType><INT/></baseType></array></type></variable>
+        <variable name="Ok"><type><BOOL/></type></variable>
+        <variable name="x"><type><INT/></type></variable>
+      </localVars></interface>
+      <body><ST><![CDATA[Scaler(Factor := 2, Value := 5);
+Scaler(2, Level + 1);
+Scaler(Factor := 2, Value := Data[Level], Done => Ok);
+x := Clamp(100, ABS(Level));
+x := Clamp(Limit := 100, Buffer := Data[2]);]]></ST></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        assert_eq!(
+            messages(&st_units),
+            [
+                (
+                    "Scale.Value".to_string(),
+                    "Routine 'Main' line 1: call of 'Scaler' binds VAR_IN_OUT parameter 'Value' to literal '5'; \
+                     in-out parameters are passed by reference and need a variable"
+                        .to_string()
+                ),
+                (
+                    "Scale.Value".to_string(),
+                    "Routine 'Main' line 2: call of 'Scaler' binds VAR_IN_OUT parameter 'Value' to expression \
+                     'Level + 1'; in-out parameters are passed by reference and need a variable"
+                        .to_string()
+                ),
+                (
+                    "Clamp.Buffer".to_string(),
+                    "Routine 'Main' line 4: call of 'Clamp' binds VAR_IN_OUT parameter 'Buffer' to the result of \
+                     'ABS(Level)'; in-out parameters are passed by reference and need a variable"
+                        .to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_l5x_aoi_skips_instance_argument() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+<Controller Name="Test">
+<AddOnInstructionDefinitions>
+<AddOnInstructionDefinition Name="Filter">
+<Parameters>
+<Parameter Name="EnableIn" TagType="Base" DataType="BOOL" Usage="Input" Required="false"/>
+<Parameter Name="Gain" TagType="Base" DataType="REAL" Usage="Input" Required="true"/>
+<Parameter Name="Trim" TagType="Base" DataType="REAL" Usage="Input" Required="false"/>
+<Parameter Name="Signal" TagType="Base" DataType="REAL" Usage="InOut" Required="true"/>
+</Parameters>
+</AddOnInstructionDefinition>
+</AddOnInstructionDefinitions>
+<Programs>
+<Program Name="Line">
+<Routines>
+<Routine Name="Calc" Type="ST"><STContent>
+<Line Number="0">Filter(Filter_01, 0.5, Raw);</Line>
+<Line Number="1">Filter(Filter_02, 0.5, 1.0);</Line>
+</STContent></Routine>
+</Routines>
+</Program>
+</Programs>
+</Controller>
+</RSLogix5000Content>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_controller(project.l5x_controller.as_ref().unwrap());
+        let found = messages(&st_units);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, "Filter.Signal");
+        assert!(found[0].1.starts_with("Routine 'Calc' line 2: call of 'Filter' binds VAR_IN_OUT parameter 'Signal' to literal '1.0'"));
+    }
+}
@@ -0,0 +1,172 @@
+//! Invalid array range detector.
+//!
+//! Detects array declarations whose range has a high bound below its low
+//! bound (`ARRAY[10..1]`), leaving the array without elements, and
+//! optionally single-element ranges (`ARRAY[5..5]`) (C0023).
+
+use iecst::{check_array_ranges, check_type_ranges, Diagnostic, DiagnosticKind, Pou, TypeDecl};
+
+use crate::analysis::StUnits;
+use crate::config::InvalidArrayRangeConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for empty and single-element array ranges.
+pub struct InvalidArrayRangeDetector<'a> {
+    config: &'a InvalidArrayRangeConfig,
+}
+
+impl<'a> InvalidArrayRangeDetector<'a> {
+    /// Create a new invalid array range detector with the given configuration.
+    pub fn new(config: &'a InvalidArrayRangeConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the declarations and type declarations of ST units
+    /// and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        // Each POU's declarations once: AOI scan-mode routines share the AOI's
+        let pous = st_units
+            .units
+            .iter()
+            .filter(|unit| unit.main_body)
+            .filter_map(|unit| unit.pou.as_ref().map(|pou| (unit.location.as_str(), pou)))
+            .chain(st_units.declarations.iter().map(|pou| (pou.name.as_str(), pou)));
+
+        for (location, pou) in pous {
+            if self.matches_ignore_pattern(&pou.name) {
+                continue;
+            }
+            for diagnostic in check_array_ranges(pou) {
+                self.add(report, &diagnostic, location, pou);
+            }
+        }
+
+        // Skip types matching an ignore pattern
+        let types: Vec<TypeDecl> = st_units
+            .types
+            .iter()
+            .filter(|decl| !self.matches_ignore_pattern(&decl.name))
+            .cloned()
+            .collect();
+
+        for diagnostic in check_type_ranges(&types) {
+            let Some(name) = self.array_name(&diagnostic) else {
+                continue;
+            };
+            let type_name = name.split('.').next().unwrap_or(name);
+            report.add(Rule::new(
+                RuleKind::InvalidArrayRange,
+                diagnostic.severity.into(),
+                "DataTypes".to_string(),
+                name.to_string(),
+                format!("DataType '{}': {}", type_name, diagnostic.kind),
+            ));
+        }
+    }
+
+    fn add(&self, report: &mut Report, diagnostic: &Diagnostic, location: &str, pou: &Pou) {
+        let Some(name) = self.array_name(diagnostic) else {
+            return;
+        };
+        report.add(Rule::new(
+            RuleKind::InvalidArrayRange,
+            diagnostic.severity.into(),
+            location.to_string(),
+            format!("{}.{}", pou.name, name),
+            format!("POU '{}': {}", pou.name, diagnostic.kind),
+        ));
+    }
+
+    /// Name of the array a diagnostic reports, if it is reported.
+    fn array_name<'d>(&self, diagnostic: &'d Diagnostic) -> Option<&'d str> {
+        match &diagnostic.kind {
+            DiagnosticKind::EmptyArrayRange { name, .. } => Some(name),
+            DiagnosticKind::SingleElementArrayRange { name, .. } if self.config.single_element => Some(name),
+            _ => None,
+        }
+    }
+
+    /// Check if a POU or type name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for InvalidArrayRangeDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Severity;
+
+    const XML: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types>
+    <dataTypes>
+      <dataType name="Buffer"><baseType><array><dimension lower="7" upper="0"/><baseType><BYTE/></baseType></array></baseType></dataType>
+    </dataTypes>
+    <pous>
+      <pou name="Main" pouType="program">
+        <interface><localVars>
+          <variable name="Data"><type><array><dimension lower="10" upper="1"/><baseType><INT/></baseType></array></type></variable>
+          <variable name="Grid"><type><array><dimension lower="0" upper="3"/><dimension lower="5" upper="5"/><baseType><INT/></baseType></array></type></variable>
+          <variable name="Ok"><type><array><dimension lower="0" upper="9"/><baseType><INT/></baseType></array></type></variable>
+        </localVars></interface>
+        <body><FBD/></body>
+      </pou>
+    </pous>
+  </types>
+  <instances><configurations/></instances>
+</project>"#;
+
+    fn detect(config: &InvalidArrayRangeConfig) -> Report {
+        let project = LoadedProject::from_str(XML, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let mut report = Report::new();
+        InvalidArrayRangeDetector::new(config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_descending_ranges() {
+        let report = detect(&InvalidArrayRangeConfig::default());
+        let findings: Vec<(&str, &str, Severity)> = report
+            .rules
+            .iter()
+            .map(|r| (r.identifier.as_str(), r.message.as_str(), r.severity))
+            .collect();
+        assert_eq!(
+            findings,
+            [
+                ("Main.Data", "POU 'Main': declared range of 'Data' [10..1] has no elements", Severity::Error),
+                ("Buffer", "DataType 'Buffer': declared range of 'Buffer' [7..0] has no elements", Severity::Error),
+            ]
+        );
+        assert_eq!(report.rules[0].kind, RuleKind::InvalidArrayRange);
+    }
+
+    #[test]
+    fn test_single_element_and_ignored() {
+        let config = InvalidArrayRangeConfig { single_element: true, ..Default::default() };
+        let report = detect(&config);
+        assert_eq!(report.rules.len(), 3);
+        assert_eq!(report.rules[1].severity, Severity::Warning);
+        assert!(report.rules[1].message.contains("'Grid' [5..5] in dimension 2 has a single element"));
+
+        let config = InvalidArrayRangeConfig { ignore_patterns: vec!["Main".to_string(), "Buf*".to_string()], ..Default::default() };
+        assert!(detect(&config).rules.is_empty());
+    }
+}
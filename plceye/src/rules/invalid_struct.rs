@@ -0,0 +1,176 @@
+//! Invalid struct detector.
+//!
+//! Detects structs that declare a member name twice and structs that contain
+//! themselves by value, which would have infinite size (C0024).
+
+use iecst::{check_struct_types, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::InvalidStructConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for duplicate struct members and recursive structs.
+pub struct InvalidStructDetector<'a> {
+    config: &'a InvalidStructConfig,
+}
+
+impl<'a> InvalidStructDetector<'a> {
+    /// Create a new invalid struct detector with the given configuration.
+    pub fn new(config: &'a InvalidStructConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the type declarations of ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        // Skip types matching an ignore pattern; they still take part in
+        // the cycles of other types
+        for diagnostic in check_struct_types(&st_units.types) {
+            let (type_name, identifier) = match &diagnostic.kind {
+                DiagnosticKind::DuplicateStructMember { type_name, member, .. } => {
+                    (type_name, format!("{}.{}", type_name, member))
+                }
+                DiagnosticKind::RecursiveStruct { type_name, .. } => (type_name, type_name.clone()),
+                _ => continue,
+            };
+            if self.matches_ignore_pattern(type_name) {
+                continue;
+            }
+            report.add(Rule::new(
+                RuleKind::InvalidStruct,
+                diagnostic.severity.into(),
+                "DataTypes".to_string(),
+                identifier,
+                format!("DataType '{}': {}", type_name, diagnostic.kind),
+            ));
+        }
+    }
+
+    /// Check if a type name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, type_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, type_name)
+    }
+}
+
+impl Detector for InvalidStructDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Severity;
+
+    fn detect(data_types: &str, config: &InvalidStructConfig) -> Report {
+        let xml = format!(
+            r#"<?xml version="1.0"?>
+            <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+                <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+                <contentHeader name="Test"/>
+                <types>
+                    <dataTypes>{}</dataTypes>
+                    <pous/>
+                </types>
+            </project>"#,
+            data_types
+        );
+
+        let project = LoadedProject::from_str(&xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let mut report = Report::new();
+        InvalidStructDetector::new(config).detect(&st_units, &mut report);
+        report
+    }
+
+    const TYPES: &str = r#"
+        <dataType name="Axis">
+            <baseType><struct>
+                <variable name="Pos"><type><REAL/></type></variable>
+                <variable name="pos"><type><INT/></type></variable>
+            </struct></baseType>
+        </dataType>
+        <dataType name="Node">
+            <baseType><struct>
+                <variable name="Next"><type><derived name="Link"/></type></variable>
+            </struct></baseType>
+        </dataType>
+        <dataType name="Link">
+            <baseType><struct>
+                <variable name="Target"><type><derived name="Node"/></type></variable>
+            </struct></baseType>
+        </dataType>
+        <dataType name="List">
+            <baseType><struct>
+                <variable name="Head"><type><pointer><baseType><derived name="List"/></baseType></pointer></type></variable>
+            </struct></baseType>
+        </dataType>"#;
+
+    #[test]
+    fn test_duplicate_member_and_cycle() {
+        let report = detect(TYPES, &InvalidStructConfig::default());
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.rules[0].kind, RuleKind::InvalidStruct);
+        assert_eq!(report.rules[0].severity, Severity::Error);
+        assert_eq!(report.rules[0].identifier, "Axis.pos");
+        assert_eq!(report.rules[0].message, "DataType 'Axis': structure 'Axis' declares member 'pos' more than once");
+        assert_eq!(
+            report.rules[1].message,
+            "DataType 'Node': structure 'Node' contains itself by value: Node -> Link -> Node"
+        );
+    }
+
+    #[test]
+    fn test_l5x_data_types() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <DataTypes>
+                    <DataType Name="Valve" Family="NoFamily" Class="User">
+                        <Members>
+                            <Member Name="ZZZZZZZZZZValve0" DataType="SINT" Dimension="0" Hidden="true"/>
+                            <Member Name="Open" DataType="BIT" Dimension="0" Hidden="false" Target="ZZZZZZZZZZValve0" BitNumber="0"/>
+                            <Member Name="OPEN" DataType="DINT" Dimension="0" Hidden="false"/>
+                        </Members>
+                    </DataType>
+                    <DataType Name="Station" Family="NoFamily" Class="User">
+                        <Members>
+                            <Member Name="Valves" DataType="Valve" Dimension="4" Hidden="false"/>
+                            <Member Name="Next" DataType="Station" Dimension="0" Hidden="false"/>
+                        </Members>
+                    </DataType>
+                </DataTypes>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let rules = Detector::detect(&InvalidStructDetector::new(&InvalidStructConfig::default()), &project);
+        let found: Vec<_> = rules.iter().map(|r| r.identifier.as_str()).collect();
+        assert_eq!(found, ["Valve.OPEN", "Station"]);
+        assert_eq!(rules[1].message, "DataType 'Station': structure 'Station' contains itself by value: Station -> Station");
+    }
+
+    #[test]
+    fn test_ignore_patterns_and_disabled() {
+        let config = InvalidStructConfig {
+            ignore_patterns: vec!["Ax*".to_string()],
+            ..Default::default()
+        };
+        let report = detect(TYPES, &config);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].identifier, "Node");
+
+        let config = InvalidStructConfig {
+            enabled: false,
+            ..Default::default()
+        };
+        assert!(detect(TYPES, &config).rules.is_empty());
+    }
+}
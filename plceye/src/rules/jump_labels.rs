@@ -0,0 +1,114 @@
+//! IL jump label detector.
+//!
+//! Detects IL jumps to labels that are not defined in the POU (C0008) and
+//! labels that no jump targets (C0009). The SCL equivalent is
+//! `plcscl::scan_labels`, since SCL declares its labels differently.
+
+use crate::analysis::{il_labels_from_plcopen, IlLabels};
+use crate::config::JumpLabelsConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for undefined jump targets and unused labels in IL.
+pub struct JumpLabelsDetector<'a> {
+    config: &'a JumpLabelsConfig,
+}
+
+impl<'a> JumpLabelsDetector<'a> {
+    /// Create a new jump label detector with the given configuration.
+    pub fn new(config: &'a JumpLabelsConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the IL bodies of a project and add findings to the report.
+    pub fn detect(&self, il_bodies: &[IlLabels], report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for body in il_bodies {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&body.pou) {
+                continue;
+            }
+
+            for jump in body.undefined_jumps() {
+                report.add(Rule::new(
+                    RuleKind::UndefinedJumpTarget,
+                    Severity::Error,
+                    body.pou.clone(),
+                    jump.target.clone(),
+                    format!(
+                        "POU '{}' line {}: {} to undefined label '{}'",
+                        body.pou, jump.line, jump.instruction, jump.target
+                    ),
+                ));
+            }
+
+            if self.config.check_unused_labels {
+                for label in body.unused_labels() {
+                    report.add(Rule::new(
+                        RuleKind::UnusedLabel,
+                        Severity::Info,
+                        body.pou.clone(),
+                        label.name.clone(),
+                        format!("POU '{}' line {}: label '{}' is never jumped to", body.pou, label.line, label.name),
+                    ));
+                }
+            }
+        }
+    }
+
+    /// Check if a POU name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for JumpLabelsDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&il_labels_from_plcopen(plcopen), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(config: &JumpLabelsConfig) -> Report {
+        let body = IlLabels::scan("Counter", "    LD Count\n    JMPC Skip\nLoop: ADD 1\nDone: ST Count\n    JMP Done\n");
+        let mut report = Report::new();
+        JumpLabelsDetector::new(config).detect(&[body], &mut report);
+        report
+    }
+
+    #[test]
+    fn test_undefined_target_and_unused_label() {
+        let report = detect(&JumpLabelsConfig::default());
+        let findings: Vec<_> = report.rules.iter().map(|r| (r.kind.clone(), r.message.as_str())).collect();
+        assert_eq!(
+            findings,
+            [
+                (RuleKind::UndefinedJumpTarget, "POU 'Counter' line 2: JMPC to undefined label 'Skip'"),
+                (RuleKind::UnusedLabel, "POU 'Counter' line 3: label 'Loop' is never jumped to"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_labels_optional() {
+        let config = JumpLabelsConfig {
+            check_unused_labels: false,
+            ..JumpLabelsConfig::default()
+        };
+        let report = detect(&config);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::UndefinedJumpTarget);
+    }
+}
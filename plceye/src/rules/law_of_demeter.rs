@@ -0,0 +1,111 @@
+//! Law of Demeter detector.
+//!
+//! Detects ST access chains like `A.B.C.D.E.F` deeper than the configured
+//! maximum, which couple logic to the internal structure of the data it
+//! reaches through (M0008).
+
+use iecst::{check_member_access_depth, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::LawOfDemeterConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for deeply nested member access.
+pub struct LawOfDemeterDetector<'a> {
+    config: &'a LawOfDemeterConfig,
+}
+
+impl<'a> LawOfDemeterDetector<'a> {
+    /// Create a new law of Demeter detector with the given configuration.
+    pub fn new(config: &'a LawOfDemeterConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_member_access_depth(pou, self.config.max_depth) {
+                let DiagnosticKind::DeepMemberAccess { ref path, depth, max_depth } = diagnostic.kind else {
+                    continue;
+                };
+                report.add(Rule::new(
+                    RuleKind::LawOfDemeter,
+                    Severity::Info,
+                    unit.location.clone(),
+                    path.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name)
+                .with_param("depth", depth)
+                .with_param("threshold", max_depth));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for LawOfDemeterDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+
+    fn detect_l5x(config: &LawOfDemeterConfig, st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units("", "Cell", st_lines);
+        let mut report = Report::new();
+        LawOfDemeterDetector::new(config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_deep_access_reported() {
+        let lines = [
+            "Ready := Line.Station[2].Axis.Drive.Status.Ready;",
+            "Ready := Line.Station[2].Axis.Ready;",
+        ];
+        let report = detect_l5x(&LawOfDemeterConfig::default(), &lines);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::LawOfDemeter);
+        assert_eq!(report.rules[0].severity, Severity::Info);
+        assert_eq!(report.rules[0].identifier, "Line.Station[2].Axis.Drive.Status.Ready");
+        assert!(report.rules[0].message.contains("line 1: access path"));
+        assert!(report.rules[0].message.contains("has depth 6 (max 4)"));
+
+        let config = LawOfDemeterConfig {
+            max_depth: 3,
+            ..LawOfDemeterConfig::default()
+        };
+        assert_eq!(detect_l5x(&config, &lines).rules.len(), 2);
+    }
+}
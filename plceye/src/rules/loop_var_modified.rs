@@ -0,0 +1,111 @@
+//! Loop variable modification detector.
+//!
+//! Detects FOR loops whose control variable is written inside the loop
+//! body (C0032).
+
+use iecst::check_loop_variables;
+
+use crate::analysis::StUnits;
+use crate::config::LoopVarModifiedConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for FOR loop variables modified inside the loop.
+pub struct LoopVarModifiedDetector<'a> {
+    config: &'a LoopVarModifiedConfig,
+}
+
+impl<'a> LoopVarModifiedDetector<'a> {
+    /// Create a new loop variable modification detector with the given configuration.
+    pub fn new(config: &'a LoopVarModifiedConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_loop_variables(pou) {
+                report.add(Rule::new(
+                    RuleKind::LoopVarModified,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for LoopVarModifiedDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+
+    fn detect_l5x(st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Idx" TagType="Base" DataType="DINT"/>
+               <Tag Name="Total" TagType="Base" DataType="DINT"/>
+               <Tag Name="Values" TagType="Base" DataType="DINT" Dimensions="10"/>"#,
+            "Sum",
+            st_lines,
+        );
+        let config = LoopVarModifiedConfig::default();
+        let mut report = Report::new();
+        LoopVarModifiedDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_loop_var_assigned() {
+        let report = detect_l5x(&[
+            "FOR Idx := 0 TO 9 DO",
+            "Total := Total + Values[Idx];",
+            "IF Values[Idx] < 0 THEN Idx := 10; END_IF;",
+            "END_FOR;",
+        ]);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::LoopVarModified);
+        assert!(report.rules[0].message.contains("line 3"));
+        assert!(report.rules[0].message.contains("'Idx'"));
+    }
+
+    #[test]
+    fn test_loop_var_only_read() {
+        let report = detect_l5x(&["FOR Idx := 0 TO 9 DO", "Values[Idx] := Idx;", "END_FOR;", "Idx := 0;"]);
+        assert!(report.rules.is_empty());
+    }
+}
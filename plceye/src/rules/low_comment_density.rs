@@ -0,0 +1,131 @@
+//! Comment density detector.
+//!
+//! Detects ST routines whose comments make up less than the configured
+//! percentage of their non-whitespace characters, and routines over a size
+//! threshold without any comment (M0006). Like the style check it works on
+//! the source text, so routines that fail to parse are covered too.
+
+use crate::analysis::{analyze_controller, parse_st_bodies, CommentDensity, ParsedSTRoutine, ProjectAnalysis};
+use crate::config::LowCommentDensityConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for ST routines with too few comments.
+pub struct LowCommentDensityDetector<'a> {
+    config: &'a LowCommentDensityConfig,
+}
+
+impl<'a> LowCommentDensityDetector<'a> {
+    /// Create a new comment density detector with the given configuration.
+    pub fn new(config: &'a LowCommentDensityConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on analyzed ST routines and add findings to the report.
+    pub fn detect(&self, analysis: &ProjectAnalysis, report: &mut Report) {
+        self.detect_routines(&analysis.st_routines, report);
+    }
+
+    /// Run detection on parsed ST routines and add findings to the report.
+    pub fn detect_routines(&self, st_routines: &[ParsedSTRoutine], report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for st_routine in st_routines {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&st_routine.location.routine) {
+                continue;
+            }
+
+            let density = CommentDensity::of(&st_routine.source);
+            let name = &st_routine.location.routine;
+            let message = if density.comments == 0 && density.lines > self.config.max_uncommented_lines {
+                format!("Routine '{}' has {} lines and no comments", name, density.lines)
+            } else if density.lines >= self.config.min_lines && density.percent() < self.config.min_percent {
+                format!(
+                    "Routine '{}' has comment density of {:.1}% (min: {}%)",
+                    name,
+                    density.percent(),
+                    self.config.min_percent
+                )
+            } else {
+                continue;
+            };
+
+            report.add(Rule::new(
+                RuleKind::LowCommentDensity,
+                Severity::Info,
+                st_routine.scope(),
+                name.clone(),
+                message,
+            )
+            .in_routine(&st_routine.location.routine));
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for LowCommentDensityDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(&analyze_controller(controller), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect_routines(&parse_st_bodies(plcopen), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{RoutineKind, STLocation};
+
+    fn routine(source: &str) -> ParsedSTRoutine {
+        ParsedSTRoutine {
+            location: STLocation::new("Main", "Logic"),
+            kind: RoutineKind::Program,
+            source: source.to_string(),
+            pou: None,
+            parse_error: None,
+            skipped: Vec::new(),
+        }
+    }
+
+    fn detect(source: &str) -> Vec<String> {
+        let config = LowCommentDensityConfig {
+            min_lines: 3,
+            max_uncommented_lines: 4,
+            ..LowCommentDensityConfig::default()
+        };
+        let mut report = Report::new();
+        LowCommentDensityDetector::new(&config).detect_routines(&[routine(source)], &mut report);
+        report.rules.iter().map(|r| r.message.clone()).collect()
+    }
+
+    #[test]
+    fn test_no_comments() {
+        let source = "a := 1;\nb := 2;\nc := 3;\n\nd := 4;\ne := 5;\n";
+        assert_eq!(detect(source), ["Routine 'Logic' has 5 lines and no comments"]);
+    }
+
+    #[test]
+    fn test_low_density() {
+        let source = "// x\nLongVariableA := LongVariableB + LongVariableC;\nLongVariableD := LongVariableE;\n";
+        assert_eq!(detect(source), ["Routine 'Logic' has comment density of 4.0% (min: 5%)"]);
+    }
+
+    #[test]
+    fn test_commented_and_short_routines() {
+        assert!(detect("(* Reset (* all *) counters *)\na := 0;\nb := 0;\n").is_empty());
+        assert!(detect("a := 1;\nb := 2;\n").is_empty());
+    }
+}
@@ -1,26 +1,228 @@
 //! Individual rule detectors.
 
+mod argument_conversion;
+mod array_bounds;
+mod bit_operation;
+mod bool_toggle;
+mod comment_markers;
+mod comparison_statement;
 mod complexity;
+mod convert_to_case;
+mod cross_program_shared_tag;
+mod dead_store;
+mod division_by_zero;
+mod duplicate_logic;
+mod empty_branches;
 mod empty_routines;
+mod enum_value_conflict;
+mod fbd_unconnected;
+mod float_equality;
+mod hardcoded_address;
+mod identifiers;
+mod impure_function;
+mod in_out_not_variable;
+mod invalid_array_range;
+mod invalid_struct;
+mod jump_labels;
+mod law_of_demeter;
+mod loop_var_modified;
+mod low_comment_density;
+mod naming;
 mod nesting;
+mod non_exhaustive_case;
+mod nonstandard_function;
+mod overlapping_case_labels;
 mod plcopen_rules;
+mod redundant_boolean;
+mod required_init;
+mod self_assignment;
+mod string_concat_in_loop;
+mod string_truncation;
+mod style;
+mod tag_scope;
+mod timer_no_reset;
+mod type_mismatch;
+mod unbound_input;
+mod undefined_datatype_ref;
 mod undefined_tags;
+mod uninitialized_read;
+mod uninvoked_instance;
+mod unreachable_pou;
+mod unscheduled_program;
 mod unused_aois;
 mod unused_datatypes;
+mod unused_method_parameter;
 mod unused_tags;
+mod unused_variables;
 
 // L5X-specific detectors
+pub use argument_conversion::ArgumentConversionDetector;
+pub use array_bounds::ArrayBoundsDetector;
+pub use bit_operation::BitOperationDetector;
+pub use bool_toggle::BoolToggleDetector;
+pub use comment_markers::CommentMarkersDetector;
+pub use comparison_statement::ComparisonStatementDetector;
 pub use complexity::ComplexityDetector;
+pub use convert_to_case::ConvertToCaseDetector;
+pub use cross_program_shared_tag::CrossProgramSharedTagDetector;
+pub use dead_store::DeadStoreDetector;
+pub use division_by_zero::DivisionByZeroDetector;
+pub use duplicate_logic::DuplicateLogicDetector;
+pub use empty_branches::EmptyBranchesDetector;
 pub use empty_routines::EmptyRoutinesDetector;
+pub use enum_value_conflict::EnumValueConflictDetector;
+pub use fbd_unconnected::FbdUnconnectedDetector;
+pub use float_equality::FloatEqualityDetector;
+pub use hardcoded_address::HardcodedAddressDetector;
+pub use identifiers::IdentifiersDetector;
+pub use impure_function::ImpureFunctionDetector;
+pub use in_out_not_variable::InOutNotVariableDetector;
+pub use invalid_array_range::InvalidArrayRangeDetector;
+pub use invalid_struct::InvalidStructDetector;
+pub use jump_labels::JumpLabelsDetector;
+pub use law_of_demeter::LawOfDemeterDetector;
+pub use loop_var_modified::LoopVarModifiedDetector;
+pub use low_comment_density::LowCommentDensityDetector;
+pub use naming::NamingDetector;
 pub use nesting::NestingDetector;
+pub use non_exhaustive_case::NonExhaustiveCaseDetector;
+pub use nonstandard_function::NonstandardFunctionDetector;
+pub use overlapping_case_labels::OverlappingCaseLabelsDetector;
+pub use redundant_boolean::RedundantBooleanDetector;
+pub use required_init::RequiredInitDetector;
+pub use self_assignment::SelfAssignmentDetector;
+pub use string_concat_in_loop::StringConcatInLoopDetector;
+pub use string_truncation::StringTruncationDetector;
+pub use style::StyleDetector;
+pub use tag_scope::TagScopeSuggestionDetector;
+pub use timer_no_reset::TimerNoResetDetector;
+pub use type_mismatch::TypeMismatchDetector;
+pub use unbound_input::UnboundInputDetector;
+pub use undefined_datatype_ref::UndefinedDataTypeRefDetector;
 pub use undefined_tags::UndefinedTagsDetector;
+pub use uninitialized_read::UninitializedReadDetector;
+pub use uninvoked_instance::UninvokedInstanceDetector;
+pub use unreachable_pou::UnreachablePouDetector;
+pub use unscheduled_program::UnscheduledProgramDetector;
 pub use unused_aois::UnusedAoisDetector;
 pub use unused_datatypes::UnusedDataTypesDetector;
+pub use unused_method_parameter::UnusedMethodParameterDetector;
 pub use unused_tags::UnusedTagsDetector;
+pub use unused_variables::UnusedVariablesDetector;
 
 // PLCopen-specific detectors
 pub use plcopen_rules::{
     PlcopenUnusedVarsDetector,
     PlcopenUndefinedVarsDetector,
+    PlcopenUnusedGlobalsDetector,
+    PlcopenUndeclaredGlobalsDetector,
     PlcopenEmptyPousDetector,
 };
+
+use crate::report::{Report, Rule};
+
+/// Collect the findings a detector adds to a fresh report, for the
+/// [`Detector`](crate::Detector) implementations of the built-in rules.
+pub(crate) fn findings(detect: impl FnOnce(&mut Report)) -> Vec<Rule> {
+    let mut report = Report::new();
+    detect(&mut report);
+    report.rules
+}
+
+/// Whether `text` matches a glob pattern: `*` matches any characters, `?`
+/// one character, and letters match regardless of case.
+pub(crate) fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    glob_match_from(&pattern, &text)
+}
+
+fn glob_match_from(pattern: &[char], text: &[char]) -> bool {
+    match pattern.split_first() {
+        None => text.is_empty(),
+        Some(('*', rest)) => (0..=text.len()).any(|i| glob_match_from(rest, &text[i..])),
+        Some(('?', rest)) => !text.is_empty() && glob_match_from(rest, &text[1..]),
+        Some((c, rest)) => text.first().is_some_and(|t| c.eq_ignore_ascii_case(t)) && glob_match_from(rest, &text[1..]),
+    }
+}
+
+/// Whether `text` matches any of the glob patterns.
+pub(crate) fn matches_any(patterns: &[String], text: &str) -> bool {
+    patterns.iter().any(|pattern| glob_match(pattern, text))
+}
+
+/// ST units of a test L5X project: program `Main` with the `<Tag>` elements
+/// `tags` and an ST routine `routine` of `st_lines`.
+#[cfg(test)]
+pub(crate) fn l5x_st_units(tags: &str, routine: &str, st_lines: &[&str]) -> crate::analysis::StUnits {
+    let lines: String = st_lines
+        .iter()
+        .enumerate()
+        .map(|(i, line)| format!("<Line Number=\"{}\"><![CDATA[{}]]></Line>", i, line))
+        .collect();
+    let xml = format!(
+        r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <Programs>
+                    <Program Name="Main">
+                        <Tags>{}</Tags>
+                        <Routines>
+                            <Routine Name="{}" Type="ST">
+                                <STContent>{}</STContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#,
+        tags, routine, lines
+    );
+
+    let project = crate::loader::LoadedProject::from_str(&xml, None).expect("Should parse");
+    crate::analysis::StUnits::from_controller(project.l5x_controller.as_ref().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_glob_match_literal() {
+        assert!(glob_match("hello", "hello"));
+        assert!(glob_match("hello", "HELLO"));
+        assert!(!glob_match("hello", "world"));
+    }
+
+    #[test]
+    fn test_glob_match_star() {
+        assert!(glob_match("*", "anything"));
+        assert!(glob_match("_*", "_internal"));
+        assert!(glob_match("_*", "_"));
+        assert!(!glob_match("_*", "public"));
+        assert!(glob_match("HMI_*", "HMI_Button1"));
+        assert!(glob_match("Main*", "MainRoutine"));
+        assert!(!glob_match("Main*", "OtherRoutine"));
+        assert!(glob_match("*_Type", "MyCustom_Type"));
+        assert!(!glob_match("UDT_*", "Motor_UDT"));
+        assert!(glob_match("*_temp", "var_temp"));
+        assert!(glob_match("*AOI", "MyTestAOI"));
+    }
+
+    #[test]
+    fn test_glob_match_question() {
+        assert!(glob_match("Tag?", "Tag1"));
+        assert!(!glob_match("Tag?", "Tag"));
+        assert!(!glob_match("Tag?", "Tag12"));
+        assert!(glob_match("?otor", "Motor"));
+    }
+
+    #[test]
+    fn test_matches_any() {
+        let patterns = vec!["HMI_*".to_string(), "Spare?".to_string()];
+        assert!(matches_any(&patterns, "hmi_start"));
+        assert!(matches_any(&patterns, "Spare1"));
+        assert!(!matches_any(&patterns, "Motor"));
+        assert!(!matches_any(&[], "Motor"));
+    }
+}
@@ -0,0 +1,207 @@
+//! Naming convention detector.
+//!
+//! Checks tag, variable, data type and POU names against the regex pattern
+//! configured for their category (S0010).
+
+use crate::analysis::{names_from_controller, names_from_plcopen, NamedItem};
+use crate::config::NamingConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for names that do not follow the configured conventions.
+pub struct NamingDetector<'a> {
+    config: &'a NamingConfig,
+}
+
+impl<'a> NamingDetector<'a> {
+    /// Create a new naming convention detector with the given configuration.
+    pub fn new(config: &'a NamingConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the named items of a project and add findings to the report.
+    pub fn detect(&self, names: &[NamedItem], report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for item in names {
+            let Some(pattern) = self.config.pattern(item.category) else {
+                continue;
+            };
+
+            if pattern.is_match(&item.name) {
+                continue;
+            }
+
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&item.name) {
+                continue;
+            }
+
+            report.add(Rule::new(
+                RuleKind::NamingConvention,
+                Severity::Info,
+                item.location.clone(),
+                item.name.clone(),
+                format!(
+                    "{} '{}' does not match naming pattern '{}'",
+                    item.category.describe(),
+                    item.name,
+                    pattern.as_str()
+                ),
+            ));
+        }
+    }
+
+    /// Check if a name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for NamingDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(&names_from_controller(controller), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&names_from_plcopen(plcopen), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{names_from_controller, names_from_plcopen};
+    use crate::config::RuleConfig;
+
+    const CONFIG: &str = r#"
+[naming]
+fb_instance = "^fb[A-Z]"
+input = "^i[A-Z]"
+output = "^q[A-Z]"
+data_type = "^[A-Z][A-Za-z0-9]*$"
+"#;
+
+    fn detect(xml: &str, config: &str) -> Report {
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let names = match project.l5x_controller {
+            Some(ref controller) => names_from_controller(controller),
+            None => names_from_plcopen(project.plcopen_project.as_ref().unwrap()),
+        };
+        let config = RuleConfig::parse(config).unwrap();
+        let mut report = Report::new();
+        NamingDetector::new(&config.naming).detect(&names, &mut report);
+        report
+    }
+
+    fn flagged(report: &Report) -> Vec<&str> {
+        report.rules.iter().map(|r| r.identifier.as_str()).collect()
+    }
+
+    #[test]
+    fn test_l5x_categories() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <DataTypes>
+                    <DataType Name="motor_data"/>
+                </DataTypes>
+                <AddOnInstructionDefinitions>
+                    <AddOnInstructionDefinition Name="Valve">
+                        <Parameters>
+                            <Parameter Name="EnableIn" DataType="BOOL" Usage="Input"/>
+                            <Parameter Name="iOpen" DataType="BOOL" Usage="Input"/>
+                            <Parameter Name="Close" DataType="BOOL" Usage="Input"/>
+                            <Parameter Name="Opened" DataType="BOOL" Usage="Output"/>
+                        </Parameters>
+                    </AddOnInstructionDefinition>
+                </AddOnInstructionDefinitions>
+                <Programs>
+                    <Program Name="Main">
+                        <Tags>
+                            <Tag Name="fbValve1" TagType="Base" DataType="Valve"/>
+                            <Tag Name="Valve2" TagType="Base" DataType="Valve"/>
+                            <Tag Name="Counter" TagType="Base" DataType="DINT"/>
+                        </Tags>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let report = detect(xml, CONFIG);
+        let mut names = flagged(&report);
+        names.sort();
+        assert_eq!(names, vec!["Close", "Opened", "Valve2", "motor_data"]);
+
+        let opened = report.rules.iter().find(|r| r.identifier == "Opened").unwrap();
+        assert_eq!(opened.kind, RuleKind::NamingConvention);
+        assert_eq!(opened.location, "AOI:Valve");
+        assert!(opened.message.contains("'^q[A-Z]'"));
+    }
+
+    #[test]
+    fn test_plcopen_categories() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="Test" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <dataTypes/>
+                <pous>
+                    <pou name="Main" pouType="program">
+                        <interface>
+                            <inputVars>
+                                <variable name="iStart"><type><BOOL/></type></variable>
+                                <variable name="Stop"><type><BOOL/></type></variable>
+                            </inputVars>
+                            <outputVars>
+                                <variable name="qRunning"><type><BOOL/></type></variable>
+                            </outputVars>
+                            <localVars>
+                                <variable name="Delay"><type><derived name="TON"/></type></variable>
+                                <variable name="count"><type><INT/></type></variable>
+                            </localVars>
+                        </interface>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        let report = detect(xml, CONFIG);
+        let mut names = flagged(&report);
+        names.sort();
+        assert_eq!(names, vec!["Delay", "Stop"]);
+        let delay = report.rules.iter().find(|r| r.identifier == "Delay").unwrap();
+        assert!(delay.message.starts_with("FB instance 'Delay'"));
+    }
+
+    #[test]
+    fn test_unconfigured_and_ignored() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="Test" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Main" pouType="program">
+                        <interface>
+                            <inputVars>
+                                <variable name="Legacy_Start"><type><BOOL/></type></variable>
+                            </inputVars>
+                        </interface>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        assert!(detect(xml, "").rules.is_empty());
+        let config = format!("{}ignore_patterns = [\"Legacy_*\"]\n", CONFIG);
+        assert!(detect(xml, &config).rules.is_empty());
+    }
+}
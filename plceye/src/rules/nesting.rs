@@ -4,9 +4,12 @@
 
 use iec61131::analysis::max_nesting_depth;
 
-use crate::analysis::ProjectAnalysis;
+use crate::analysis::{analyze_controller, parse_st_bodies, ParsedSTRoutine, ProjectAnalysis};
 use crate::config::NestingConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
 use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
 
 /// Detector for deep nesting in ST routines.
 pub struct NestingDetector<'a> {
@@ -21,11 +24,16 @@ impl<'a> NestingDetector<'a> {
 
     /// Run detection on analyzed ST routines and add findings to the report.
     pub fn detect(&self, analysis: &ProjectAnalysis, report: &mut Report) {
+        self.detect_routines(&analysis.st_routines, report);
+    }
+
+    /// Run detection on parsed ST routines and add findings to the report.
+    pub fn detect_routines(&self, st_routines: &[ParsedSTRoutine], report: &mut Report) {
         if !self.config.enabled {
             return;
         }
 
-        for st_routine in &analysis.st_routines {
+        for st_routine in st_routines {
             // Skip if matches ignore pattern
             if self.matches_ignore_pattern(&st_routine.location.routine) {
                 continue;
@@ -39,26 +47,36 @@ impl<'a> NestingDetector<'a> {
             // Calculate maximum nesting depth
             let depth = max_nesting_depth(&pou.body);
 
-            if depth > self.config.max_depth {
+            // Per-kind threshold overrides the global one
+            let kind_threshold = self.config.per_kind.get(st_routine.kind);
+            let threshold = kind_threshold.unwrap_or(self.config.max_depth);
+
+            if depth > threshold {
+                let applied = match kind_threshold {
+                    Some(_) => format!("max for {}: {}", st_routine.kind, threshold),
+                    None => format!("max: {}", threshold),
+                };
                 report.add(Rule::new(
                     RuleKind::DeepNesting,
-                    self.severity_for_depth(depth),
-                    format!("Program:{}", st_routine.location.program),
+                    self.severity_for_depth(depth, threshold),
+                    st_routine.scope(),
                     st_routine.location.routine.clone(),
                     format!(
-                        "Routine '{}' has nesting depth of {} (max: {})",
+                        "Routine '{}' has nesting depth of {} ({})",
                         st_routine.location.routine,
                         depth,
-                        self.config.max_depth
+                        applied
                     ),
-                ));
+                )
+                .in_routine(&st_routine.location.routine)
+                .with_param("depth", depth)
+                .with_param("threshold", threshold));
             }
         }
     }
 
     /// Determine severity based on how much depth exceeds threshold.
-    fn severity_for_depth(&self, depth: usize) -> Severity {
-        let threshold = self.config.max_depth;
+    fn severity_for_depth(&self, depth: usize, threshold: usize) -> Severity {
         // Severe: more than 2x the threshold
         if depth > threshold * 2 {
             Severity::Error
@@ -72,57 +90,27 @@ impl<'a> NestingDetector<'a> {
 
     /// Check if a routine name matches any ignore pattern.
     fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
-        for pattern in &self.config.ignore_patterns {
-            if glob_match(pattern, routine_name) {
-                return true;
-            }
-        }
-        false
+        matches_any(&self.config.ignore_patterns, routine_name)
     }
 }
 
-/// Simple glob pattern matching supporting * and ? wildcards.
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let pattern_chars: Vec<char> = pattern.chars().collect();
-    let text_chars: Vec<char> = text.chars().collect();
-    glob_match_recursive(&pattern_chars, &text_chars, 0, 0)
-}
-
-fn glob_match_recursive(pattern: &[char], text: &[char], pi: usize, ti: usize) -> bool {
-    if pi == pattern.len() {
-        return ti == text.len();
-    }
-
-    match pattern[pi] {
-        '*' => {
-            for i in ti..=text.len() {
-                if glob_match_recursive(pattern, text, pi + 1, i) {
-                    return true;
-                }
-            }
-            false
-        }
-        '?' => {
-            if ti < text.len() {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
+impl Detector for NestingDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(&analyze_controller(controller), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect_routines(&parse_st_bodies(plcopen), report);
             }
-        }
-        c => {
-            if ti < text.len() && c.eq_ignore_ascii_case(&text[ti]) {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
-            }
-        }
+        })
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::analysis::{ParsedSTRoutine, STLocation, ParseStats, RoutineSummary};
+    use crate::analysis::{ParsedSTRoutine, RoutineKind, STLocation, ParseStats, RoutineSummary};
+    use crate::config::PouKindThresholds;
     use std::collections::HashMap;
 
     fn create_test_analysis(st_source: &str) -> ProjectAnalysis {
@@ -131,9 +119,11 @@ mod tests {
         let pou = crate::analysis::parse_pou(&wrapped).ok();
         let st_routine = ParsedSTRoutine {
             location: STLocation::new("MainProgram", "TestRoutine"),
+            kind: RoutineKind::Program,
             source: st_source.to_string(),
             pou,
             parse_error: None,
+            skipped: Vec::new(),
         };
 
         ProjectAnalysis {
@@ -150,10 +140,12 @@ mod tests {
                 parse_errors: 0,
                 tags_used: vec![],
                 instructions: HashMap::new(),
+                protected: false,
             }],
             instruction_usage: HashMap::new(),
             aoi_definitions: vec![],
             aoi_usage: HashMap::new(),
+            protected: vec![],
             stats: ParseStats::default(),
         }
     }
@@ -163,6 +155,7 @@ mod tests {
         let config = NestingConfig {
             enabled: true,
             max_depth: 4,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = NestingDetector::new(&config);
@@ -181,6 +174,7 @@ mod tests {
         let config = NestingConfig {
             enabled: true,
             max_depth: 2,  // Low threshold for testing
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = NestingDetector::new(&config);
@@ -211,6 +205,7 @@ mod tests {
         let config = NestingConfig {
             enabled: false,
             max_depth: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = NestingDetector::new(&config);
@@ -228,6 +223,7 @@ mod tests {
         let config = NestingConfig {
             enabled: true,
             max_depth: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec!["Test*".to_string()],
         };
         let detector = NestingDetector::new(&config);
@@ -245,6 +241,7 @@ mod tests {
         let config = NestingConfig {
             enabled: true,
             max_depth: 4,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = NestingDetector::new(&config);
@@ -255,13 +252,13 @@ mod tests {
         // - Error: depth > 8
         
         // Mild violation: Info
-        assert_eq!(detector.severity_for_depth(5), Severity::Info);
+        assert_eq!(detector.severity_for_depth(5, 4), Severity::Info);
 
         // Moderate violation: Warning
-        assert_eq!(detector.severity_for_depth(7), Severity::Warning);
+        assert_eq!(detector.severity_for_depth(7, 4), Severity::Warning);
 
         // Severe violation (>2x threshold): Error
-        assert_eq!(detector.severity_for_depth(10), Severity::Error);
+        assert_eq!(detector.severity_for_depth(10, 4), Severity::Error);
     }
 
     #[test]
@@ -269,6 +266,7 @@ mod tests {
         let config = NestingConfig {
             enabled: true,
             max_depth: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = NestingDetector::new(&config);
@@ -295,6 +293,7 @@ mod tests {
         let config = NestingConfig {
             enabled: true,
             max_depth: 2,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = NestingDetector::new(&config);
@@ -323,6 +322,7 @@ mod tests {
         let config = NestingConfig {
             enabled: true,
             max_depth: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = NestingDetector::new(&config);
@@ -349,6 +349,7 @@ mod tests {
         let config = NestingConfig {
             enabled: true,
             max_depth: 1,
+            per_kind: PouKindThresholds::default(),
             ignore_patterns: vec![],
         };
         let detector = NestingDetector::new(&config);
@@ -356,9 +357,11 @@ mod tests {
         // Create analysis with unparseable routine (pou = None)
         let st_routine = ParsedSTRoutine {
             location: STLocation::new("MainProgram", "BadRoutine"),
+            kind: RoutineKind::Program,
             source: "not valid ST {{{{".to_string(),
             pou: None,
             parse_error: None,
+            skipped: Vec::new(),
         };
 
         let analysis = ProjectAnalysis {
@@ -370,6 +373,7 @@ mod tests {
             instruction_usage: HashMap::new(),
             aoi_definitions: vec![],
             aoi_usage: HashMap::new(),
+            protected: vec![],
             stats: ParseStats::default(),
         };
 
@@ -378,4 +382,30 @@ mod tests {
 
         assert!(report.rules.is_empty(), "Unparseable routine should not trigger rule");
     }
+
+    #[test]
+    fn test_per_kind_threshold() {
+        let config = NestingConfig {
+            enabled: true,
+            max_depth: 1,
+            per_kind: PouKindThresholds {
+                function: Some(3),
+                ..PouKindThresholds::default()
+            },
+            ignore_patterns: vec![],
+        };
+        let detector = NestingDetector::new(&config);
+        let source = "IF a THEN IF b THEN x := 1; END_IF; END_IF;";
+
+        let mut analysis = create_test_analysis(source);
+        let mut report = Report::new();
+        detector.detect(&analysis, &mut report);
+        assert_eq!(report.rules.len(), 1);
+        assert!(report.rules[0].message.contains("(max: 1)"));
+
+        analysis.st_routines[0].kind = RoutineKind::Function;
+        let mut report = Report::new();
+        detector.detect(&analysis, &mut report);
+        assert!(report.rules.is_empty());
+    }
 }
@@ -0,0 +1,138 @@
+//! Non-exhaustive CASE detector.
+//!
+//! Detects CASE statements over enum selectors that do not cover every
+//! enum member and have no ELSE branch (C0002).
+
+use iecst::check_case_coverage;
+
+use crate::analysis::StUnits;
+use crate::config::NonExhaustiveCaseConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for CASE statements missing enum members.
+pub struct NonExhaustiveCaseDetector<'a> {
+    config: &'a NonExhaustiveCaseConfig,
+}
+
+impl<'a> NonExhaustiveCaseDetector<'a> {
+    /// Create a new non-exhaustive CASE detector with the given configuration.
+    pub fn new(config: &'a NonExhaustiveCaseConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_case_coverage(pou, &st_units.types) {
+                report.add(Rule::new(
+                    RuleKind::NonExhaustiveCase,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for NonExhaustiveCaseDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(st_source: &str) -> Report {
+        let xml = format!(
+            r#"<?xml version="1.0"?>
+            <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+                <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+                <contentHeader name="Test"/>
+                <types>
+                    <dataTypes>
+                        <dataType name="Mode">
+                            <baseType>
+                                <enum>
+                                    <values>
+                                        <value name="Manual"/>
+                                        <value name="Auto"/>
+                                        <value name="Maintenance"/>
+                                    </values>
+                                </enum>
+                            </baseType>
+                        </dataType>
+                    </dataTypes>
+                    <pous>
+                        <pou name="Main" pouType="program">
+                            <interface>
+                                <localVars>
+                                    <variable name="mode"><type><derived name="Mode"/></type></variable>
+                                    <variable name="x"><type><INT/></type></variable>
+                                </localVars>
+                            </interface>
+                            <body><ST><![CDATA[{}]]></ST></body>
+                        </pou>
+                    </pous>
+                </types>
+            </project>"#,
+            st_source
+        );
+
+        let project = LoadedProject::from_str(&xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let config = NonExhaustiveCaseConfig::default();
+        let mut report = Report::new();
+        NonExhaustiveCaseDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_missing_enum_member() {
+        let report = detect("x := 0;\nCASE mode OF\n  Manual: x := 1;\n  Auto: x := 2;\nEND_CASE;");
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::NonExhaustiveCase);
+        assert!(report.rules[0].message.contains("line 2"));
+        assert!(report.rules[0].message.contains("Maintenance"));
+    }
+
+    #[test]
+    fn test_all_members_or_else() {
+        let report = detect("CASE mode OF\n  Manual, Auto, Maintenance: x := 1;\nEND_CASE;");
+        assert!(report.rules.is_empty());
+
+        let report = detect("CASE mode OF\n  Manual: x := 1;\nELSE\n  x := 0;\nEND_CASE;");
+        assert!(report.rules.is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+//! Nonstandard function detector.
+//!
+//! Detects ST calls of functions and function blocks that are neither in
+//! the IEC 61131-3 standard library nor defined by the project (M0009).
+//! These vendor extensions have to be replaced when the project moves to
+//! another platform.
+
+use crate::analysis::{analyze_controller, analyze_plcopen_project, vendor_calls, StUnits};
+use crate::config::NonstandardFunctionConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for calls of vendor extensions.
+pub struct NonstandardFunctionDetector<'a> {
+    config: &'a NonstandardFunctionConfig,
+}
+
+impl<'a> NonstandardFunctionDetector<'a> {
+    /// Create a new nonstandard function detector with the given configuration.
+    pub fn new(config: &'a NonstandardFunctionConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    ///
+    /// `project_pous` are the names of the project's own POUs or AOIs.
+    pub fn detect<'p>(&self, st_units: &StUnits, project_pous: impl IntoIterator<Item = &'p str>, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for call in vendor_calls(st_units, project_pous, &self.config.standard_functions) {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&call.routine) {
+                continue;
+            }
+
+            let called = match call.instance {
+                Some(ref instance) => format!("function block '{}' (instance '{}')", call.name, instance),
+                None => format!("function '{}'", call.name),
+            };
+            report.add(Rule::new(
+                RuleKind::NonstandardFunction,
+                Severity::Info,
+                call.location.clone(),
+                call.name.clone(),
+                format!(
+                    "Routine '{}' line {}: vendor extension: {} is not in the IEC 61131-3 standard library",
+                    call.routine, call.line, called
+                ),
+            ));
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for NonstandardFunctionDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        let st_units = StUnits::from_project(project);
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                let aois = analyze_controller(controller).aoi_definitions;
+                self.detect(&st_units, aois.iter().map(String::as_str), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                let pous = analyze_plcopen_project(plcopen).pou_names;
+                self.detect(&st_units, pous.iter().map(String::as_str), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_vendor_calls_in_l5x() {
+        let xml = r#"<?xml version="1.0"?>
+            <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+                <Controller Name="Test">
+                    <AddOnInstructionDefinitions>
+                        <AddOnInstructionDefinition Name="Valve"/>
+                    </AddOnInstructionDefinitions>
+                    <Programs>
+                        <Program Name="Main">
+                            <Tags>
+                                <Tag Name="Level" TagType="Base" DataType="REAL"/>
+                                <Tag Name="V1" TagType="Base" DataType="Valve"/>
+                            </Tags>
+                            <Routines>
+                                <Routine Name="Logic" Type="ST">
+                                    <STContent>
+                                        <Line Number="0"><![CDATA[Level := LIMIT(0.0, Level, 100.0);]]></Line>
+                                        <Line Number="1"><![CDATA[Valve(V1);]]></Line>
+                                        <Line Number="2"><![CDATA[JSR(Routine := Alarms);]]></Line>
+                                    </STContent>
+                                </Routine>
+                            </Routines>
+                        </Program>
+                    </Programs>
+                </Controller>
+            </RSLogix5000Content>"#;
+
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_controller(project.l5x_controller.as_ref().unwrap());
+        let config = NonstandardFunctionConfig {
+            enabled: true,
+            ..NonstandardFunctionConfig::default()
+        };
+        let mut report = Report::new();
+        NonstandardFunctionDetector::new(&config).detect(&st_units, ["Valve"], &mut report);
+
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::NonstandardFunction);
+        assert_eq!(report.rules[0].identifier, "JSR");
+        assert!(report.rules[0].message.contains("line 3"));
+
+        // Disabled by default
+        let mut report = Report::new();
+        NonstandardFunctionDetector::new(&NonstandardFunctionConfig::default()).detect(&st_units, ["Valve"], &mut report);
+        assert!(report.rules.is_empty());
+    }
+}
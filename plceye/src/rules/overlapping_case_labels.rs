@@ -0,0 +1,124 @@
+//! Overlapping CASE label detector.
+//!
+//! Detects CASE labels that match a value already handled by an earlier
+//! label, such as a repeated value or overlapping ranges (C0003).
+
+use iecst::{check_case_labels, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::OverlappingCaseLabelsConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for duplicate and overlapping CASE labels.
+pub struct OverlappingCaseLabelsDetector<'a> {
+    config: &'a OverlappingCaseLabelsConfig,
+}
+
+impl<'a> OverlappingCaseLabelsDetector<'a> {
+    /// Create a new overlapping CASE label detector with the given configuration.
+    pub fn new(config: &'a OverlappingCaseLabelsConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_case_labels(pou) {
+                let DiagnosticKind::OverlappingCaseLabels { original, .. } = &diagnostic.kind else {
+                    continue;
+                };
+                report.add(Rule::new(
+                    RuleKind::OverlappingCaseLabels,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {} (branch at line {})",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind,
+                        unit.line_of(original.start)
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for OverlappingCaseLabelsDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+
+    fn detect_l5x(st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Step" TagType="Base" DataType="DINT"/>
+               <Tag Name="Output" TagType="Base" DataType="DINT"/>"#,
+            "Sequence",
+            st_lines,
+        );
+        let config = OverlappingCaseLabelsConfig::default();
+        let mut report = Report::new();
+        OverlappingCaseLabelsDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_copy_pasted_state() {
+        let report = detect_l5x(&[
+            "CASE Step OF",
+            "10: Output := 1;",
+            "20: Output := 2;",
+            "10: Output := 3;",
+            "END_CASE;",
+        ]);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::OverlappingCaseLabels);
+        assert_eq!(report.rules[0].location, "Program:Main");
+        assert!(report.rules[0].message.contains("line 4"));
+        assert!(report.rules[0].message.contains("duplicate CASE label '10'"));
+        assert!(report.rules[0].message.contains("branch at line 2"));
+    }
+
+    #[test]
+    fn test_overlapping_ranges() {
+        let report = detect_l5x(&[
+            "CASE Step OF",
+            "1..10: Output := 1;",
+            "11..20: Output := 2;",
+            "15..30: Output := 3;",
+            "END_CASE;",
+        ]);
+        assert_eq!(report.rules.len(), 1);
+        assert!(report.rules[0].message.contains("'15..30' overlaps '11..20'"));
+    }
+}
@@ -1,17 +1,32 @@
 //! PLCopen-specific rule detectors.
 
-use crate::analysis::PlcopenAnalysis;
+use std::collections::HashSet;
+
+use crate::analysis::{analyze_plcopen_project, name_key, PlcopenAnalysis};
 use crate::config::{EmptyRoutinesConfig, UndefinedTagsConfig, UnusedTagsConfig};
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
 use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
 
 /// Detect unused variables in PLCopen projects.
 pub struct PlcopenUnusedVarsDetector<'a> {
     config: &'a UnusedTagsConfig,
+    case_sensitive: bool,
 }
 
 impl<'a> PlcopenUnusedVarsDetector<'a> {
     pub fn new(config: &'a UnusedTagsConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            case_sensitive: false,
+        }
+    }
+
+    /// Match variable names case-sensitively (`general.case_sensitive`).
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
     }
 
     pub fn detect(&self, analysis: &PlcopenAnalysis, report: &mut Report) {
@@ -19,7 +34,7 @@ impl<'a> PlcopenUnusedVarsDetector<'a> {
             return;
         }
 
-        for var in analysis.unused_variables() {
+        for var in analysis.unused_variables(self.case_sensitive) {
             // Skip if matches ignore pattern
             if self.matches_ignore_pattern(&var.name) {
                 continue;
@@ -36,23 +51,38 @@ impl<'a> PlcopenUnusedVarsDetector<'a> {
     }
 
     fn matches_ignore_pattern(&self, name: &str) -> bool {
-        for pattern in &self.config.ignore_patterns {
-            if glob_match(pattern, name) {
-                return true;
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for PlcopenUnusedVarsDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&analyze_plcopen_project(plcopen), report);
             }
-        }
-        false
+        })
     }
 }
 
 /// Detect undefined variables in PLCopen projects.
 pub struct PlcopenUndefinedVarsDetector<'a> {
     config: &'a UndefinedTagsConfig,
+    case_sensitive: bool,
 }
 
 impl<'a> PlcopenUndefinedVarsDetector<'a> {
     pub fn new(config: &'a UndefinedTagsConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            case_sensitive: false,
+        }
+    }
+
+    /// Match variable names case-sensitively (`general.case_sensitive`).
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
     }
 
     pub fn detect(&self, analysis: &PlcopenAnalysis, report: &mut Report) {
@@ -60,9 +90,18 @@ impl<'a> PlcopenUndefinedVarsDetector<'a> {
             return;
         }
 
-        for var_name in analysis.undefined_variables() {
+        let pou_names: HashSet<String> =
+            analysis.pou_names.iter().map(|name| name_key(name, self.case_sensitive)).collect();
+        for var_name in analysis.undefined_variables(self.case_sensitive) {
             // Skip if it's a POU name (function/FB call)
-            if analysis.pou_names.contains(var_name) {
+            if pou_names.contains(&name_key(var_name, self.case_sensitive)) {
+                continue;
+            }
+
+            // Skip if matches ignore pattern or is known to be external
+            if matches_any(&self.config.ignore_patterns, var_name)
+                || matches_any(&self.config.allow, var_name)
+            {
                 continue;
             }
 
@@ -74,6 +113,140 @@ impl<'a> PlcopenUndefinedVarsDetector<'a> {
                 format!("Variable '{}' is used but not defined (may be external)", var_name),
             ));
         }
+
+        for call_name in analysis.undefined_calls(self.case_sensitive) {
+            // Skip if provided by a library outside the project
+            if matches_any(&self.config.allow_calls, call_name) {
+                continue;
+            }
+
+            report.add(Rule::new(
+                RuleKind::UndefinedTag,
+                Severity::Info,
+                "project".to_string(),
+                call_name.clone(),
+                format!("Function or FB '{}' is called but not defined (may be a library)", call_name),
+            ));
+        }
+    }
+}
+
+impl Detector for PlcopenUndefinedVarsDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&analyze_plcopen_project(plcopen), report);
+            }
+        })
+    }
+}
+
+/// Detect configuration and resource globals that no POU uses.
+pub struct PlcopenUnusedGlobalsDetector<'a> {
+    config: &'a UnusedTagsConfig,
+    case_sensitive: bool,
+}
+
+impl<'a> PlcopenUnusedGlobalsDetector<'a> {
+    pub fn new(config: &'a UnusedTagsConfig) -> Self {
+        Self {
+            config,
+            case_sensitive: false,
+        }
+    }
+
+    /// Match variable names case-sensitively (`general.case_sensitive`).
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    pub fn detect(&self, analysis: &PlcopenAnalysis, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for var in analysis.unused_globals(self.case_sensitive) {
+            // Skip if matches ignore pattern or in ignored scope
+            if matches_any(&self.config.ignore_patterns, &var.name)
+                || self.config.ignore_scopes.iter().any(|scope| scope == &var.pou_name)
+            {
+                continue;
+            }
+
+            report.add(Rule::new(
+                RuleKind::UnusedTag,
+                Severity::Warning,
+                var.pou_name.clone(),
+                var.name.clone(),
+                format!("Global variable '{}' of '{}' is not used by any POU", var.name, var.pou_name),
+            ));
+        }
+    }
+}
+
+impl Detector for PlcopenUnusedGlobalsDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&analyze_plcopen_project(plcopen), report);
+            }
+        })
+    }
+}
+
+/// Detect VAR_EXTERNAL variables without a configuration or resource global.
+pub struct PlcopenUndeclaredGlobalsDetector<'a> {
+    config: &'a UndefinedTagsConfig,
+    case_sensitive: bool,
+}
+
+impl<'a> PlcopenUndeclaredGlobalsDetector<'a> {
+    pub fn new(config: &'a UndefinedTagsConfig) -> Self {
+        Self {
+            config,
+            case_sensitive: false,
+        }
+    }
+
+    /// Match variable names case-sensitively (`general.case_sensitive`).
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    pub fn detect(&self, analysis: &PlcopenAnalysis, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for var in analysis.undeclared_externals(self.case_sensitive) {
+            // Skip if matches ignore pattern or is known to be external
+            if matches_any(&self.config.ignore_patterns, &var.name) || matches_any(&self.config.allow, &var.name) {
+                continue;
+            }
+
+            report.add(Rule::new(
+                RuleKind::UndefinedTag,
+                Severity::Warning,
+                var.pou_name.clone(),
+                var.name.clone(),
+                format!(
+                    "External variable '{}' of '{}' is not declared as a global in any configuration or resource",
+                    var.name, var.pou_name
+                ),
+            ));
+        }
+    }
+}
+
+impl Detector for PlcopenUndeclaredGlobalsDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&analyze_plcopen_project(plcopen), report);
+            }
+        })
     }
 }
 
@@ -109,61 +282,82 @@ impl<'a> PlcopenEmptyPousDetector<'a> {
     }
 
     fn matches_ignore_pattern(&self, name: &str) -> bool {
-        for pattern in &self.config.ignore_patterns {
-            if glob_match(pattern, name) {
-                return true;
-            }
-        }
-        false
-    }
-}
-
-/// Simple glob pattern matching (supports * and ?).
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let mut pattern_chars = pattern.chars().peekable();
-    let mut text_chars = text.chars().peekable();
-
-    while let Some(p) = pattern_chars.next() {
-        match p {
-            '*' => {
-                if pattern_chars.peek().is_none() {
-                    return true;
-                }
-                let rest_pattern: String = pattern_chars.collect();
-                let rest_text: String = text_chars.collect();
-                for i in 0..=rest_text.len() {
-                    if glob_match(&rest_pattern, &rest_text[i..]) {
-                        return true;
-                    }
-                }
-                return false;
-            }
-            '?' => {
-                if text_chars.next().is_none() {
-                    return false;
-                }
-            }
-            c => {
-                if text_chars.next() != Some(c) {
-                    return false;
-                }
-            }
-        }
+        matches_any(&self.config.ignore_patterns, name)
     }
+}
 
-    text_chars.peek().is_none()
+impl Detector for PlcopenEmptyPousDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&analyze_plcopen_project(plcopen), report);
+            }
+        })
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::analysis::analyze_plcopen_project;
 
     #[test]
-    fn test_glob_match() {
-        assert!(glob_match("*", "anything"));
-        assert!(glob_match("Motor*", "Motor_1"));
-        assert!(glob_match("*_temp", "var_temp"));
-        assert!(glob_match("?otor", "Motor"));
-        assert!(!glob_match("Motor", "Pump"));
+    fn test_configuration_globals() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Main" pouType="program">
+                        <interface>
+                            <externalVars>
+                                <variable name="SPEED"><type><INT/></type></variable>
+                                <variable name="Alarm"><type><BOOL/></type></variable>
+                            </externalVars>
+                        </interface>
+                        <body><ST><![CDATA[Speed := Speed + Setpoint; Alarm := FALSE;]]></ST></body>
+                    </pou>
+                </pous>
+            </types>
+            <instances>
+                <configurations>
+                    <configuration name="Config">
+                        <globalVars>
+                            <variable name="Speed"><type><INT/></type></variable>
+                            <variable name="Spare"><type><INT/></type></variable>
+                        </globalVars>
+                        <resource name="Res">
+                            <globalVars>
+                                <variable name="Setpoint"><type><INT/></type></variable>
+                            </globalVars>
+                        </resource>
+                    </configuration>
+                </configurations>
+            </instances>
+        </project>"#;
+
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let analysis = analyze_plcopen_project(project.plcopen_project.as_ref().unwrap());
+        let mut report = Report::new();
+        PlcopenUnusedGlobalsDetector::new(&UnusedTagsConfig::default()).detect(&analysis, &mut report);
+        PlcopenUndeclaredGlobalsDetector::new(&UndefinedTagsConfig::default()).detect(&analysis, &mut report);
+        let found: Vec<_> = report
+            .rules
+            .iter()
+            .map(|r| (r.kind.code(), r.location.as_str(), r.identifier.as_str()))
+            .collect();
+        assert_eq!(found, [("S0001", "Config", "Spare"), ("S0002", "Main", "Alarm")]);
+
+        // Setpoint is used without a VAR_EXTERNAL but is not undefined
+        assert!(analysis.undefined_variables(false).is_empty());
+
+        let mut report = Report::new();
+        PlcopenUndeclaredGlobalsDetector::new(&UndefinedTagsConfig::default())
+            .case_sensitive(true)
+            .detect(&analysis, &mut report);
+        let undeclared: Vec<_> = report.rules.iter().map(|r| r.identifier.as_str()).collect();
+        assert_eq!(undeclared, ["SPEED", "Alarm"]);
     }
+
 }
@@ -0,0 +1,128 @@
+//! Redundant boolean detector.
+//!
+//! Detects boolean expressions with a repeated operand (`Enable AND Enable`),
+//! an operand and its negation (`a OR NOT a`), or a double negation, which
+//! simplify to an operand or a constant (C0020).
+
+use iecst::{check_redundant_booleans, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::RedundantBooleanConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{FixHint, Report, Rule, RuleKind, TextEdit};
+use super::{findings, matches_any};
+
+/// Detector for redundant boolean expressions.
+pub struct RedundantBooleanDetector<'a> {
+    config: &'a RedundantBooleanConfig,
+}
+
+impl<'a> RedundantBooleanDetector<'a> {
+    /// Create a new redundant boolean detector with the given configuration.
+    pub fn new(config: &'a RedundantBooleanConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_redundant_booleans(pou) {
+                let DiagnosticKind::RedundantBoolean { ref simplified, .. } = diagnostic.kind else {
+                    continue;
+                };
+                let line = unit.line_of(diagnostic.span.start);
+                report.add(
+                    Rule::new(
+                        RuleKind::RedundantBoolean,
+                        diagnostic.severity.into(),
+                        unit.location.clone(),
+                        unit.name.clone(),
+                        format!("Routine '{}' line {}: {}", unit.name, line, diagnostic.kind),
+                    )
+                    .in_routine(&unit.name)
+                    .with_fix(FixHint {
+                        description: format!("simplify to '{}'", simplified),
+                        edit: Some(TextEdit {
+                            line,
+                            start: diagnostic.span.start,
+                            end: diagnostic.span.end,
+                            replacement: simplified.clone(),
+                        }),
+                    }),
+                );
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for RedundantBooleanDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+
+    fn detect_l5x(st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Counter" TagType="Base" DataType="DINT"/>
+               <Tag Name="Enable" TagType="Base" DataType="BOOL"/>
+               <Tag Name="Ready" TagType="Base" DataType="BOOL"/>"#,
+            "Logic",
+            st_lines,
+        );
+        let config = RedundantBooleanConfig::default();
+        let mut report = Report::new();
+        RedundantBooleanDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_redundant_booleans() {
+        let report = detect_l5x(&[
+            "Ready := Enable AND Enable;",
+            "Ready := Enable OR NOT Enable;",
+            "Counter := Counter AND NOT Counter;",
+        ]);
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.rules[0].kind, RuleKind::RedundantBoolean);
+        assert!(report.rules[0].message.contains("line 1: 'Enable AND Enable' simplifies to 'Enable'"));
+        assert!(report.rules[1].message.contains("line 2: 'Enable OR NOT Enable' simplifies to 'TRUE'"));
+
+        let fix = report.rules[1].fix().unwrap();
+        assert_eq!(fix.description, "simplify to 'TRUE'");
+        let edit = fix.edit.as_ref().unwrap();
+        assert_eq!((edit.line, edit.replacement.as_str()), (2, "TRUE"));
+        let source = "Ready := Enable AND Enable;\nReady := Enable OR NOT Enable;";
+        assert_eq!(&source[edit.start..edit.end], "Enable OR NOT Enable");
+    }
+
+    #[test]
+    fn test_calls_not_compared() {
+        let report = detect_l5x(&["Ready := ABS(Counter) > 0 AND ABS(Counter) > 0;", "Ready := Enable AND Ready;"]);
+        assert!(report.rules.is_empty());
+    }
+}
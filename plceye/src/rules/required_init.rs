@@ -0,0 +1,151 @@
+//! Required initial value detector.
+//!
+//! Detects tags and variables of configured data types or names that have
+//! no initial value (C0006). Nothing is checked unless `types` or
+//! `name_patterns` is set.
+
+use crate::analysis::{variables_from_controller, variables_from_plcopen, DeclaredVariable};
+use crate::config::RequiredInitConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for tags without a required initial value.
+pub struct RequiredInitDetector<'a> {
+    config: &'a RequiredInitConfig,
+}
+
+impl<'a> RequiredInitDetector<'a> {
+    /// Create a new required initial value detector with the given configuration.
+    pub fn new(config: &'a RequiredInitConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the declared variables of a project and add findings to the report.
+    pub fn detect(&self, variables: &[DeclaredVariable], report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for var in variables {
+            if var.initialized || !self.is_required(var) {
+                continue;
+            }
+
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&var.name) {
+                continue;
+            }
+
+            report.add(Rule::new(
+                RuleKind::MissingInitialValue,
+                Severity::Warning,
+                var.location.clone(),
+                var.name.clone(),
+                format!("Tag '{}' of type {} has no initial value", var.name, var.data_type),
+            ));
+        }
+    }
+
+    /// Check if a variable's type or name requires an initial value.
+    fn is_required(&self, var: &DeclaredVariable) -> bool {
+        self.config.types.iter().any(|t| t.eq_ignore_ascii_case(&var.data_type))
+            || matches_any(&self.config.name_patterns, &var.name)
+    }
+
+    /// Check if a tag name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for RequiredInitDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(&variables_from_controller(controller), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&variables_from_plcopen(plcopen), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{variables_from_controller, variables_from_plcopen};
+
+    fn detect(xml: &str, config: &RequiredInitConfig) -> Report {
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let variables = match project.l5x_controller {
+            Some(ref controller) => variables_from_controller(controller),
+            None => variables_from_plcopen(project.plcopen_project.as_ref().unwrap()),
+        };
+        let mut report = Report::new();
+        RequiredInitDetector::new(config).detect(&variables, &mut report);
+        report
+    }
+
+    fn flagged(report: &Report) -> Vec<&str> {
+        report.rules.iter().map(|r| r.identifier.as_str()).collect()
+    }
+
+    #[test]
+    fn test_l5x_by_type_and_name() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <Tags>
+                    <Tag Name="SP_Speed" TagType="Base" DataType="DINT"/>
+                    <Tag Name="Gain" TagType="Base" DataType="REAL"/>
+                    <Tag Name="Offset" TagType="Base" DataType="REAL">
+                        <Data Format="Decorated"><DataValue DataType="REAL" Radix="Float" Value="1.5"/></Data>
+                    </Tag>
+                    <Tag Name="Count" TagType="Base" DataType="DINT"/>
+                </Tags>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        assert!(detect(xml, &RequiredInitConfig::default()).rules.is_empty());
+
+        let config = RequiredInitConfig {
+            types: vec!["real".to_string()],
+            name_patterns: vec!["SP_*".to_string()],
+            ..RequiredInitConfig::default()
+        };
+        let report = detect(xml, &config);
+        assert_eq!(flagged(&report), vec!["SP_Speed", "Gain"]);
+        assert_eq!(report.rules[1].message, "Tag 'Gain' of type REAL has no initial value");
+    }
+
+    #[test]
+    fn test_plcopen_initial_value() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Main" pouType="program">
+                        <interface>
+                            <localVars>
+                                <variable name="Limit"><type><REAL/></type><initialValue><simpleValue value="10.0"/></initialValue></variable>
+                                <variable name="Setpoint"><type><REAL/></type></variable>
+                            </localVars>
+                        </interface>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        let config = RequiredInitConfig {
+            types: vec!["REAL".to_string()],
+            ..RequiredInitConfig::default()
+        };
+        let report = detect(xml, &config);
+        assert_eq!(flagged(&report), vec!["Setpoint"]);
+        assert_eq!(report.rules[0].location, "Main");
+    }
+}
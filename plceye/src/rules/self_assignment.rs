@@ -0,0 +1,128 @@
+//! Self-assignment detector.
+//!
+//! Detects assignments of a variable path to itself, such as
+//! `Counter := Counter;`, which have no effect (C0004).
+
+use iecst::check_self_assignments;
+
+use crate::analysis::StUnits;
+use crate::config::SelfAssignmentConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{FixHint, Report, Rule, RuleKind, TextEdit};
+use super::{findings, matches_any};
+
+/// Detector for self-assignments.
+pub struct SelfAssignmentDetector<'a> {
+    config: &'a SelfAssignmentConfig,
+}
+
+impl<'a> SelfAssignmentDetector<'a> {
+    /// Create a new self-assignment detector with the given configuration.
+    pub fn new(config: &'a SelfAssignmentConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_self_assignments(pou) {
+                let line = unit.line_of(diagnostic.span.start);
+                // The statement has no effect, so deleting it is safe
+                let statement = unit.source.get(diagnostic.span.start..diagnostic.span.end).unwrap_or_default();
+                report.add(
+                    Rule::new(
+                        RuleKind::SelfAssignment,
+                        diagnostic.severity.into(),
+                        unit.location.clone(),
+                        unit.name.clone(),
+                        format!("Routine '{}' line {}: {}", unit.name, line, diagnostic.kind),
+                    )
+                    .in_routine(&unit.name)
+                    .with_fix(FixHint {
+                        description: format!("remove '{}'", statement.trim()),
+                        edit: Some(TextEdit {
+                            line,
+                            start: diagnostic.span.start,
+                            end: diagnostic.span.end,
+                            replacement: String::new(),
+                        }),
+                    }),
+                );
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for SelfAssignmentDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+
+    fn detect_l5x(st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Counter" TagType="Base" DataType="DINT"/>
+               <Tag Name="Motor" TagType="Base" DataType="MotorData"/>
+               <Tag Name="Values" TagType="Base" DataType="DINT" Dimensions="10"/>"#,
+            "Logic",
+            st_lines,
+        );
+        let config = SelfAssignmentConfig::default();
+        let mut report = Report::new();
+        SelfAssignmentDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_self_assignments() {
+        let report = detect_l5x(&[
+            "Counter := Counter + 1;",
+            "Counter := Counter;",
+            "Motor.Speed := Motor.Speed;",
+            "Values[2] := Values[2];",
+        ]);
+        assert_eq!(report.rules.len(), 3);
+        assert_eq!(report.rules[0].kind, RuleKind::SelfAssignment);
+        assert!(report.rules[0].message.contains("line 2: assignment of 'Counter'"));
+        assert!(report.rules[1].message.contains("'Motor.Speed'"));
+        assert!(report.rules[2].message.contains("'Values[2]'"));
+
+        let fix = report.rules[0].fix().unwrap();
+        assert_eq!(fix.description, "remove 'Counter := Counter;'");
+        let edit = fix.edit.as_ref().unwrap();
+        assert_eq!((edit.line, edit.replacement.as_str()), (2, ""));
+        let source = "Counter := Counter + 1;\nCounter := Counter;";
+        assert_eq!(&source[edit.start..edit.end], "Counter := Counter;");
+    }
+
+    #[test]
+    fn test_different_elements() {
+        let report = detect_l5x(&["Values[1] := Values[2];", "Motor.Speed := Motor.Setpoint;"]);
+        assert!(report.rules.is_empty());
+    }
+}
@@ -0,0 +1,114 @@
+//! String concatenation in loop detector.
+//!
+//! Detects ST assignments in FOR, WHILE and REPEAT bodies that extend a
+//! string with itself, such as `Msg := CONCAT(Msg, Part);`, which copy the
+//! whole string on every iteration (M0011).
+
+use iecst::{check_string_concat_in_loops, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::StringConcatInLoopConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for strings built up in loops.
+pub struct StringConcatInLoopDetector<'a> {
+    config: &'a StringConcatInLoopConfig,
+}
+
+impl<'a> StringConcatInLoopDetector<'a> {
+    /// Create a new string concatenation in loop detector with the given configuration.
+    pub fn new(config: &'a StringConcatInLoopConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_string_concat_in_loops(pou) {
+                let DiagnosticKind::StringConcatInLoop { ref target, loop_span, .. } = diagnostic.kind else {
+                    continue;
+                };
+                report.add(Rule::new(
+                    RuleKind::StringConcatInLoop,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    target.clone(),
+                    format!(
+                        "Routine '{}' line {}: {} (loop at line {})",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind,
+                        unit.line_of(loop_span.start)
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for StringConcatInLoopDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+    use crate::report::Severity;
+
+    fn detect_l5x(config: &StringConcatInLoopConfig, st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units("", "Report", st_lines);
+        let mut report = Report::new();
+        StringConcatInLoopDetector::new(config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_concat_in_loop_reported() {
+        let lines = [
+            "Msg := CONCAT(Msg, Header);",
+            "FOR i := 0 TO 9 DO",
+            "    Msg := CONCAT(Msg, Parts[i]);",
+            "    Line := CONCAT(Header, Parts[i]);",
+            "END_FOR;",
+        ];
+        let report = detect_l5x(&StringConcatInLoopConfig::default(), &lines);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::StringConcatInLoop);
+        assert_eq!(report.rules[0].severity, Severity::Info);
+        assert_eq!(report.rules[0].identifier, "Msg");
+        assert!(report.rules[0].message.contains("line 3: 'Msg' is extended with CONCAT"));
+        assert!(report.rules[0].message.ends_with("(loop at line 2)"));
+
+        let config = StringConcatInLoopConfig {
+            ignore_patterns: vec!["Rep*".to_string()],
+            ..StringConcatInLoopConfig::default()
+        };
+        assert!(detect_l5x(&config, &lines).rules.is_empty());
+    }
+}
@@ -0,0 +1,126 @@
+//! String truncation detector.
+//!
+//! Detects STRING/WSTRING assignments whose source is longer than the
+//! declared length of the target, which silently truncates (C0012).
+
+use iecst::check_string_truncation;
+
+use crate::analysis::StUnits;
+use crate::config::StringTruncationConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for string assignments that truncate.
+pub struct StringTruncationDetector<'a> {
+    config: &'a StringTruncationConfig,
+}
+
+impl<'a> StringTruncationDetector<'a> {
+    /// Create a new string truncation detector with the given configuration.
+    pub fn new(config: &'a StringTruncationConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_string_truncation(pou) {
+                report.add(Rule::new(
+                    RuleKind::StringTruncation,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for StringTruncationDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+
+    fn detect_l5x(st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Message" TagType="Base" DataType="STRING"/>"#,
+            "Messages",
+            st_lines,
+        );
+        let config = StringTruncationConfig::default();
+        let mut report = Report::new();
+        StringTruncationDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_literal_longer_than_logix_string() {
+        let long = "x".repeat(90);
+        let line = format!("Message := '{}';", long);
+        let report = detect_l5x(&["Message := 'Ready';", &line]);
+        assert_eq!(report.rules.len(), 1);
+        assert!(report.rules[0].message.contains("line 2"));
+        assert!(report.rules[0].message.contains("truncated to 82 characters"));
+    }
+
+    #[test]
+    fn test_plcopen_declared_lengths() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Main" pouType="program">
+      <interface><localVars>
+        <variable name="Short"><type><string length="4"/></type></variable>
+        <variable name="Long"><type><string length="40"/></type></variable>
+      </localVars></interface>
+      <body><ST><![CDATA[Short := Long;
+Long := Short;
+Short := 'OK';]]></ST></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let mut report = Report::new();
+        StringTruncationDetector::new(&StringTruncationConfig::default()).detect(&st_units, &mut report);
+        assert_eq!(report.rules.len(), 1);
+        assert!(report.rules[0].message.contains("string of length 40 assigned to STRING[4]"));
+    }
+}
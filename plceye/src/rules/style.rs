@@ -0,0 +1,157 @@
+//! ST style detector.
+//!
+//! Detects ST lines longer than the configured maximum and lines with tabs
+//! or trailing whitespace (S0017). The check works on the source text, not
+//! the AST, so it also covers routines that fail to parse.
+
+use crate::analysis::{analyze_controller, parse_st_bodies, ParsedSTRoutine, ProjectAnalysis};
+use crate::config::StyleConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for ST text style issues.
+pub struct StyleDetector<'a> {
+    config: &'a StyleConfig,
+}
+
+impl<'a> StyleDetector<'a> {
+    /// Create a new style detector with the given configuration.
+    pub fn new(config: &'a StyleConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on analyzed ST routines and add findings to the report.
+    pub fn detect(&self, analysis: &ProjectAnalysis, report: &mut Report) {
+        self.detect_routines(&analysis.st_routines, report);
+    }
+
+    /// Run detection on parsed ST routines and add findings to the report.
+    pub fn detect_routines(&self, st_routines: &[ParsedSTRoutine], report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for st_routine in st_routines {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&st_routine.location.routine) {
+                continue;
+            }
+
+            for (index, line) in st_routine.source.lines().enumerate() {
+                for issue in self.line_issues(line) {
+                    report.add(Rule::new(
+                        RuleKind::StyleViolation,
+                        Severity::Info,
+                        st_routine.scope(),
+                        st_routine.location.routine.clone(),
+                        format!("Routine '{}' line {}: {}", st_routine.location.routine, index + 1, issue),
+                    )
+                    .in_routine(&st_routine.location.routine));
+                }
+            }
+        }
+    }
+
+    /// Describe the style issues of a single line.
+    fn line_issues(&self, line: &str) -> Vec<String> {
+        let mut issues = Vec::new();
+
+        let length = line.chars().count();
+        if length > self.config.max_line_length {
+            issues.push(format!(
+                "line is {} characters long (max: {})",
+                length, self.config.max_line_length
+            ));
+        }
+        if !self.config.allow_tabs && line.contains('\t') {
+            issues.push("line contains a tab character".to_string());
+        }
+        if self.config.check_trailing_whitespace && line.ends_with(char::is_whitespace) {
+            issues.push("line has trailing whitespace".to_string());
+        }
+
+        issues
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for StyleDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(&analyze_controller(controller), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect_routines(&parse_st_bodies(plcopen), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{RoutineKind, STLocation};
+
+    fn routine(source: &str) -> ParsedSTRoutine {
+        ParsedSTRoutine {
+            location: STLocation::new("Main", "Logic"),
+            kind: RoutineKind::Program,
+            source: source.to_string(),
+            pou: None,
+            parse_error: None,
+            skipped: Vec::new(),
+        }
+    }
+
+    fn detect(source: &str, config: &StyleConfig) -> Report {
+        let mut report = Report::new();
+        StyleDetector::new(config).detect_routines(&[routine(source)], &mut report);
+        report
+    }
+
+    fn enabled() -> StyleConfig {
+        StyleConfig {
+            enabled: true,
+            max_line_length: 20,
+            ..StyleConfig::default()
+        }
+    }
+
+    #[test]
+    fn test_style_issues_with_line_numbers() {
+        let source = "x := 1;\nLongVariableName := OtherName;\n\ty := 2;\nz := 3;  ";
+        let report = detect(source, &enabled());
+        let messages: Vec<&str> = report.rules.iter().map(|r| r.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Routine 'Logic' line 2: line is 30 characters long (max: 20)",
+                "Routine 'Logic' line 3: line contains a tab character",
+                "Routine 'Logic' line 4: line has trailing whitespace",
+            ]
+        );
+        assert_eq!(report.rules[0].kind, RuleKind::StyleViolation);
+    }
+
+    #[test]
+    fn test_policy_options() {
+        let source = "\ty := 2;  ";
+        let config = StyleConfig {
+            allow_tabs: true,
+            check_trailing_whitespace: false,
+            ..enabled()
+        };
+        assert!(detect(source, &config).rules.is_empty());
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        assert!(detect("\tx := 1;   ", &StyleConfig::default()).rules.is_empty());
+    }
+}
@@ -0,0 +1,130 @@
+//! Tag scope suggestion detector.
+//!
+//! Detects controller-scoped tags that only one program references (S0013).
+//! Such tags are candidates for program scope, which keeps them out of the
+//! other programs' namespace. This is Rockwell-specific scope reasoning.
+
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use l5x::Controller;
+
+use crate::analysis::{analyze_controller, name_key, StUnits, TagAccesses};
+use crate::config::TagScopeSuggestionConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for controller-scoped tags used by a single program.
+pub struct TagScopeSuggestionDetector<'a> {
+    config: &'a TagScopeSuggestionConfig,
+    external: &'a [String],
+    case_sensitive: bool,
+}
+
+impl<'a> TagScopeSuggestionDetector<'a> {
+    /// Create a new tag scope detector with the given configuration.
+    pub fn new(config: &'a TagScopeSuggestionConfig) -> Self {
+        Self {
+            config,
+            external: &[],
+            case_sensitive: false,
+        }
+    }
+
+    /// Glob patterns for tags used by HMIs or external systems
+    /// (`undefined_tags.allow`), whose controller scope is intentional.
+    pub fn external(mut self, patterns: &'a [String]) -> Self {
+        self.external = patterns;
+        self
+    }
+
+    /// Match tag names case-sensitively (`general.case_sensitive`).
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
+    }
+
+    /// Run detection on a controller and add findings to the report.
+    ///
+    /// `accesses` are the RLL and ST tag accesses of the programs.
+    pub fn detect(&self, controller: &Controller, accesses: &TagAccesses, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let Some(ref tags) = controller.tags else {
+            return;
+        };
+
+        // Program-scoped tags shadow controller tags of the same name
+        let mut program_tags: HashSet<(&str, String)> = HashSet::new();
+        for program in controller.programs.iter().flat_map(|p| &p.program) {
+            for tag in program.tags.iter().flat_map(|t| &t.tag) {
+                program_tags.insert((program.name.as_str(), name_key(&tag.name, self.case_sensitive)));
+            }
+        }
+
+        // Programs referencing each tag; AOIs can't reach controller tags
+        let mut programs: HashMap<String, BTreeSet<&str>> = HashMap::new();
+        for access in &accesses.accesses {
+            let key = name_key(&access.tag, self.case_sensitive);
+            if program_tags.contains(&(access.program.as_str(), key.clone())) {
+                continue;
+            }
+            programs.entry(key).or_default().insert(access.program.as_str());
+        }
+
+        for tag in &tags.tag {
+            // Produced and consumed tags must be controller-scoped
+            if matches!(tag.tag_type.as_deref(), Some("Produced" | "Consumed")) {
+                continue;
+            }
+
+            // Skip if matches ignore pattern or is used outside the project
+            if self.matches_ignore_pattern(&tag.name) || self.is_external(&tag.name) {
+                continue;
+            }
+
+            let Some(users) = programs.get(&name_key(&tag.name, self.case_sensitive)) else {
+                continue;
+            };
+            let mut users = users.iter();
+            let (Some(program), None) = (users.next(), users.next()) else {
+                continue;
+            };
+
+            report.add(Rule::new(
+                RuleKind::TagScopeSuggestion,
+                Severity::Info,
+                "Controller".to_string(),
+                tag.name.clone(),
+                format!(
+                    "Controller-scoped tag '{}' is only used by program '{}'; consider making it program-scoped",
+                    tag.name, program
+                ),
+            ));
+        }
+    }
+
+    /// Check if a tag name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, tag_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, tag_name)
+    }
+
+    /// Check if a tag name matches any pattern of externally used tags.
+    fn is_external(&self, tag_name: &str) -> bool {
+        matches_any(self.external, tag_name)
+    }
+}
+
+impl Detector for TagScopeSuggestionDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                let accesses = TagAccesses::from_controller(&analyze_controller(controller), &StUnits::from_controller(controller));
+                self.detect(controller, &accesses, report);
+            }
+        })
+    }
+}
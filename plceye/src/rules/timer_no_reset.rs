@@ -0,0 +1,133 @@
+//! Timer without reset detector.
+//!
+//! Detects retentive timers and counters that are never reset, and
+//! `TON`/`TOF` timers whose enable is always true (C0016). A retentive
+//! instance that is never reset saturates at its preset after the first
+//! cycle; a timer that is always enabled is done once and stays done.
+
+use crate::analysis::{analyze_controller, StUnits, TimerUsage, TimerUse};
+use crate::config::TimerNoResetConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for timers and counters without a reset path.
+pub struct TimerNoResetDetector<'a> {
+    config: &'a TimerNoResetConfig,
+}
+
+impl<'a> TimerNoResetDetector<'a> {
+    /// Create a new timer without reset detector with the given configuration.
+    pub fn new(config: &'a TimerNoResetConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the timers and counters of a project and add findings to the report.
+    pub fn detect(&self, usage: &TimerUsage, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        if self.config.check_retentive {
+            for timer in usage.never_reset() {
+                if self.matches_ignore_pattern(&timer.name) {
+                    continue;
+                }
+                report.add(self.finding(
+                    timer,
+                    Severity::Warning,
+                    format!("{} '{}' is never reset and keeps its accumulated value", timer.instruction, timer.name),
+                ));
+            }
+        }
+
+        if self.config.check_always_enabled {
+            for timer in &usage.always_enabled {
+                if self.matches_ignore_pattern(&timer.name) {
+                    continue;
+                }
+                report.add(self.finding(
+                    timer,
+                    Severity::Info,
+                    format!("{} '{}' is always enabled and never restarts", timer.instruction, timer.name),
+                ));
+            }
+        }
+    }
+
+    fn finding(&self, timer: &TimerUse, severity: Severity, message: String) -> Rule {
+        Rule::new(RuleKind::TimerNoReset, severity, timer.location.clone(), timer.name.clone(), message)
+    }
+
+    /// Check if an instance name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for TimerNoResetDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                let analysis = analyze_controller(controller);
+                self.detect(&TimerUsage::from_controller(&analysis.rungs, &StUnits::from_controller(controller)), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&TimerUsage::from_plcopen(&StUnits::from_plcopen(plcopen)), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{analyze_controller, StUnits};
+
+    const XML: &str = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <Programs>
+                    <Program Name="Main">
+                        <Routines>
+                            <Routine Name="Logic" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0"><Text>XIC(Run)RTO(RunTime,?,?);</Text></Rung>
+                                    <Rung Number="1"><Text>XIC(Part)CTU(Parts,?,?);</Text></Rung>
+                                    <Rung Number="2"><Text>XIC(Reset)RES(Parts);</Text></Rung>
+                                    <Rung Number="3"><Text>TON(Pulse,?,?);</Text></Rung>
+                                </RLLContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+    fn detect(config: &TimerNoResetConfig) -> Report {
+        let project = LoadedProject::from_str(XML, None).expect("Should parse");
+        let controller = project.l5x_controller.as_ref().unwrap();
+        let analysis = analyze_controller(controller);
+        let usage = TimerUsage::from_controller(&analysis.rungs, &StUnits::from_controller(controller));
+        let mut report = Report::new();
+        TimerNoResetDetector::new(config).detect(&usage, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_detects_unreset_and_always_enabled() {
+        let report = detect(&TimerNoResetConfig::default());
+        let found: Vec<_> = report.rules.iter().map(|r| (r.identifier.as_str(), r.severity)).collect();
+        assert_eq!(found, [("RunTime", Severity::Warning), ("Pulse", Severity::Info)]);
+    }
+
+    #[test]
+    fn test_config_switches() {
+        let config = TimerNoResetConfig {
+            check_always_enabled: false,
+            ignore_patterns: vec!["Run*".to_string()],
+            ..Default::default()
+        };
+        assert!(detect(&config).rules.is_empty());
+    }
+}
@@ -0,0 +1,160 @@
+//! Type mismatch detector.
+//!
+//! Detects ST assignments that narrow a value implicitly and comparisons
+//! between incompatible types (C0001).
+
+use iecst::{check_types, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::TypeMismatchConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for type mismatches in ST code.
+pub struct TypeMismatchDetector<'a> {
+    config: &'a TypeMismatchConfig,
+}
+
+impl<'a> TypeMismatchDetector<'a> {
+    /// Create a new type mismatch detector with the given configuration.
+    pub fn new(config: &'a TypeMismatchConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_types(pou, &st_units.functions) {
+                if !matches!(
+                    diagnostic.kind,
+                    DiagnosticKind::TypeMismatch { .. } | DiagnosticKind::IncompatibleTypes { .. }
+                ) {
+                    continue;
+                }
+
+                report.add(Rule::new(
+                    RuleKind::TypeMismatch,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for TypeMismatchDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::l5x_st_units;
+
+    fn detect_l5x(st_lines: &[&str]) -> Report {
+        let st_units = l5x_st_units(
+            r#"<Tag Name="Speed" TagType="Base" DataType="REAL"/>
+               <Tag Name="Count" TagType="Base" DataType="INT"/>
+               <Tag Name="Total" TagType="Base" DataType="DINT"/>
+               <Tag Name="Running" TagType="Base" DataType="BOOL"/>"#,
+            "Logic",
+            st_lines,
+        );
+        let config = TypeMismatchConfig::default();
+        let mut report = Report::new();
+        TypeMismatchDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_narrowing_assignment() {
+        let report = detect_l5x(&["Total := Count;", "Count := Speed;"]);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::TypeMismatch);
+        assert_eq!(report.rules[0].location, "Program:Main");
+        assert!(report.rules[0].message.contains("line 2"));
+        assert!(report.rules[0].message.contains("'INT'"));
+    }
+
+    #[test]
+    fn test_bool_int_comparison() {
+        let report = detect_l5x(&["IF Running = Count THEN", "Total := 0;", "END_IF;"]);
+        assert_eq!(report.rules.len(), 1);
+        assert!(report.rules[0].message.contains("'BOOL'"));
+    }
+
+    #[test]
+    fn test_conversion_function_accepted() {
+        let report = detect_l5x(&["Count := REAL_TO_INT(Speed);", "Speed := Count * 2;"]);
+        assert!(report.rules.is_empty());
+    }
+
+    #[test]
+    fn test_plcopen_function_return_type() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Scale" pouType="function">
+                        <interface><returnType><REAL/></returnType></interface>
+                        <body><ST><![CDATA[Scale := 1.5;]]></ST></body>
+                    </pou>
+                    <pou name="Main" pouType="program">
+                        <interface>
+                            <localVars>
+                                <variable name="Count"><type><INT/></type></variable>
+                                <variable name="Total"><type><DINT/></type></variable>
+                            </localVars>
+                        </interface>
+                        <body><ST><![CDATA[Total := Count;
+Count := Scale();]]></ST></body>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let config = TypeMismatchConfig::default();
+        let mut report = Report::new();
+        TypeMismatchDetector::new(&config).detect(&st_units, &mut report);
+
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].location, "Main");
+        assert!(report.rules[0].message.contains("line 2"));
+        assert!(report.rules[0].message.contains("'REAL'"));
+    }
+}
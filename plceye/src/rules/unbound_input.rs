@@ -0,0 +1,392 @@
+//! Unbound input detector.
+//!
+//! Detects ST calls of function blocks and AOIs that leave VAR_INPUT
+//! parameters unbound (C0025). An unbound input keeps the value of the
+//! previous call, which is stale when the caller expects it to be set; an
+//! input without an initial value is a warning, one with an initial value
+//! is info. Named and positional arguments are matched against the callee's
+//! parameters, and an input assigned through the instance anywhere in the
+//! body (`Motor_01.Speed := x;`) counts as bound. AOI inputs that are not
+//! required cannot be passed in an ST call and are not checked; function
+//! calls are not checked, as function inputs do not keep values.
+
+use std::collections::HashSet;
+
+use iecst::visitor::{walk_expr, walk_stmt};
+use iecst::{Expr, ExprKind, Pou, Stmt, StmtKind, TypeKind, Visitor};
+
+use crate::analysis::{Parameter, ParameterKind, Signature, StUnits};
+use crate::config::UnboundInputConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for function block and AOI calls with unbound inputs.
+pub struct UnboundInputDetector<'a> {
+    config: &'a UnboundInputConfig,
+}
+
+impl<'a> UnboundInputDetector<'a> {
+    /// Create a new unbound input detector with the given configuration.
+    pub fn new(config: &'a UnboundInputConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            let mut assigned = Assignments::default();
+            assigned.visit_body(&pou.body);
+            let mut calls = Calls {
+                st_units,
+                pou,
+                found: Vec::new(),
+            };
+            calls.visit_body(&pou.body);
+
+            for call in calls.found {
+                let (uninitialized, initialized): (Vec<&Parameter>, Vec<&Parameter>) = call
+                    .unbound
+                    .into_iter()
+                    .filter(|p| !assigned.contains(call.instance, &p.name))
+                    .partition(|p| !p.initialized);
+                let kind = if call.signature.instance_argument { "AOI" } else { "function block" };
+
+                if !uninitialized.is_empty() {
+                    report.add(Rule::new(
+                        RuleKind::UnboundInput,
+                        Severity::Warning,
+                        unit.location.clone(),
+                        call.signature.name.clone(),
+                        format!(
+                            "Routine '{}' line {}: call of '{}' ({} '{}') leaves {} unbound; without an \
+                             initial value it keeps the value of the previous call",
+                            unit.name,
+                            unit.line_of(call.offset),
+                            call.instance,
+                            kind,
+                            call.signature.name,
+                            list(&uninitialized)
+                        ),
+                    )
+                    .in_routine(&unit.name));
+                }
+                if !initialized.is_empty() && self.config.initialized_inputs {
+                    report.add(Rule::new(
+                        RuleKind::UnboundInput,
+                        Severity::Info,
+                        unit.location.clone(),
+                        call.signature.name.clone(),
+                        format!(
+                            "Routine '{}' line {}: call of '{}' ({} '{}') leaves {} unbound; it keeps its \
+                             initial value or the value of the previous call",
+                            unit.name,
+                            unit.line_of(call.offset),
+                            call.instance,
+                            kind,
+                            call.signature.name,
+                            list(&initialized)
+                        ),
+                    )
+                    .in_routine(&unit.name));
+                }
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for UnboundInputDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+/// "input 'A'" or "inputs 'A', 'B'".
+fn list(parameters: &[&Parameter]) -> String {
+    let names: Vec<String> = parameters.iter().map(|p| format!("'{}'", p.name)).collect();
+    let noun = if names.len() == 1 { "input" } else { "inputs" };
+    format!("{} {}", noun, names.join(", "))
+}
+
+/// A call that leaves inputs unbound.
+struct Call<'ast> {
+    /// Called instance as written
+    instance: &'ast str,
+    signature: &'ast Signature,
+    /// Checked inputs that no argument binds, in declaration order
+    unbound: Vec<&'ast Parameter>,
+    /// Byte offset of the call
+    offset: usize,
+}
+
+/// Collects the function block and AOI calls of a body.
+struct Calls<'ast> {
+    st_units: &'ast StUnits,
+    pou: &'ast Pou,
+    found: Vec<Call<'ast>>,
+}
+
+impl<'ast> Calls<'ast> {
+    /// Check the arguments of a call: (parameter name, value, output binding).
+    fn check_call(
+        &mut self,
+        callee: &'ast str,
+        offset: usize,
+        args: Vec<(Option<&'ast str>, Option<&'ast Expr>, bool)>,
+    ) {
+        let Some((signature, instance, skip)) = self.resolve(callee, &args) else {
+            return;
+        };
+
+        let mut bound: Vec<&Parameter> = Vec::new();
+        let mut position = 0;
+        for (name, _, output) in &args {
+            let parameter = match name {
+                Some(name) if !output => signature.parameter(name),
+                Some(_) => None,
+                None => {
+                    position += 1;
+                    (position > skip).then(|| signature.positional(position - 1 - skip)).flatten()
+                }
+            };
+            bound.extend(parameter);
+        }
+
+        let unbound: Vec<&Parameter> = signature
+            .parameters
+            .iter()
+            .filter(|p| p.kind == ParameterKind::Input)
+            .filter(|p| !signature.instance_argument || p.positional)
+            .filter(|p| !bound.contains(p))
+            .collect();
+        if !unbound.is_empty() {
+            self.found.push(Call {
+                instance,
+                signature,
+                unbound,
+                offset,
+            });
+        }
+    }
+
+    /// The signature of a called instance, the instance name and the number
+    /// of leading positional arguments that are not parameters: the
+    /// callee's declared type, or for a call by AOI name the AOI and its
+    /// instance argument.
+    fn resolve(
+        &self,
+        callee: &'ast str,
+        args: &[(Option<&'ast str>, Option<&'ast Expr>, bool)],
+    ) -> Option<(&'ast Signature, &'ast str, usize)> {
+        if let Some(signature) = self.st_units.signature(callee) {
+            if !signature.instance_argument {
+                return None;
+            }
+            let instance = match args.first()? {
+                (None, Some(Expr { kind: ExprKind::Ident(name), .. }), _) => name.as_str(),
+                _ => return None,
+            };
+            return Some((signature, instance, 1));
+        }
+        let instance = self
+            .pou
+            .var_blocks
+            .iter()
+            .flat_map(|block| &block.vars)
+            .find(|var| var.name.eq_ignore_ascii_case(callee))?;
+        let signature = match &instance.var_type.kind {
+            TypeKind::Simple(type_name) => self.st_units.signature(type_name)?,
+            _ => return None,
+        };
+        (!signature.function).then_some((signature, callee, 0))
+    }
+}
+
+impl<'ast> Visitor<'ast> for Calls<'ast> {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        if let StmtKind::Call { name, args } = &stmt.kind {
+            self.check_call(
+                name,
+                stmt.span.start,
+                args.iter().map(|arg| (arg.name.as_deref(), arg.value.as_ref(), arg.output)).collect(),
+            );
+        }
+        walk_stmt(self, stmt);
+    }
+
+    fn visit_expr(&mut self, expr: &'ast Expr) {
+        if let ExprKind::FunctionCall { name, args } = &expr.kind {
+            self.check_call(
+                name,
+                expr.span.start,
+                args.iter().map(|arg| (arg.name.as_deref(), arg.value.as_ref(), arg.output)).collect(),
+            );
+        }
+        walk_expr(self, expr);
+    }
+}
+
+/// Instance members assigned in a body (`Inst.Member := ...`), lowercase.
+#[derive(Default)]
+struct Assignments {
+    members: HashSet<(String, String)>,
+}
+
+impl Assignments {
+    fn contains(&self, instance: &str, member: &str) -> bool {
+        self.members.contains(&(instance.to_lowercase(), member.to_lowercase()))
+    }
+}
+
+impl<'ast> Visitor<'ast> for Assignments {
+    fn visit_stmt(&mut self, stmt: &'ast Stmt) {
+        if let StmtKind::Assignment { target, .. } = &stmt.kind {
+            if let ExprKind::MemberAccess { expr, member } = &target.kind {
+                if let ExprKind::Ident(instance) = &expr.kind {
+                    self.members.insert((instance.to_lowercase(), member.to_lowercase()));
+                }
+            }
+        }
+        walk_stmt(self, stmt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn findings(st_units: &StUnits, config: &UnboundInputConfig) -> Vec<(Severity, String)> {
+        let mut report = Report::new();
+        UnboundInputDetector::new(config).detect(st_units, &mut report);
+        report.rules.into_iter().map(|r| (r.severity, r.message)).collect()
+    }
+
+    #[test]
+    fn test_plcopen_function_block_calls() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Motor" pouType="functionBlock">
+      <interface>
+        <inputVars>
+          <variable name="Enable"><type><BOOL/></type></variable>
+          <variable name="Speed"><type><INT/></type></variable>
+          <variable name="Ramp"><type><INT/></type><initialValue><simpleValue value="10"/></initialValue></variable>
+        </inputVars>
+        <outputVars><variable name="Running"><type><BOOL/></type></variable></outputVars>
+      </interface>
+      <body><ST><![CDATA[Running := Enable AND Speed > 0;]]></ST></body>
+    </pou>
+    <pou name="Limit" pouType="function">
+      <interface>
+        <returnType><INT/></returnType>
+        <inputVars><variable name="Value"><type><INT/></type></variable><variable name="Max"><type><INT/></type></variable></inputVars>
+      </interface>
+      <body><ST><![CDATA[Limit := MIN(Value, Max);]]></ST></body>
+    </pou>
+    <pou name="Main" pouType="program">
+      <interface><localVars>
+        <variable name="M1"><type><derived name="Motor"/></type></variable>
+        <variable name="M2"><type><derived name="Motor"/></type></variable>
+        <variable name="On"><type><BOOL/></type></variable>
+        <variable name="x"><type><INT/></type></variable>
+      </localVars></interface>
+      <body><ST><![CDATA[M1(Speed := 5, Running => On);
+M2.Enable := TRUE;
+M2(TRUE, 10, 3);
+M2(Speed := 10);
+x := Limit(Value := 5);]]></ST></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        assert_eq!(
+            findings(&st_units, &UnboundInputConfig::default()),
+            [
+                (
+                    Severity::Warning,
+                    "Routine 'Main' line 1: call of 'M1' (function block 'Motor') leaves input 'Enable' unbound; \
+                     without an initial value it keeps the value of the previous call"
+                        .to_string()
+                ),
+                (
+                    Severity::Info,
+                    "Routine 'Main' line 1: call of 'M1' (function block 'Motor') leaves input 'Ramp' unbound; \
+                     it keeps its initial value or the value of the previous call"
+                        .to_string()
+                ),
+                (
+                    Severity::Info,
+                    "Routine 'Main' line 4: call of 'M2' (function block 'Motor') leaves input 'Ramp' unbound; \
+                     it keeps its initial value or the value of the previous call"
+                        .to_string()
+                ),
+            ]
+        );
+
+        let config = UnboundInputConfig {
+            initialized_inputs: false,
+            ..Default::default()
+        };
+        assert_eq!(findings(&st_units, &config).len(), 1);
+    }
+
+    #[test]
+    fn test_l5x_aoi_required_inputs() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+<Controller Name="Test">
+<AddOnInstructionDefinitions>
+<AddOnInstructionDefinition Name="Filter">
+<Parameters>
+<Parameter Name="EnableIn" TagType="Base" DataType="BOOL" Usage="Input" Required="false"/>
+<Parameter Name="Gain" TagType="Base" DataType="REAL" Usage="Input" Required="true"/>
+<Parameter Name="Offset" TagType="Base" DataType="REAL" Usage="Input" Required="true"/>
+<Parameter Name="Trim" TagType="Base" DataType="REAL" Usage="Input" Required="false"/>
+</Parameters>
+</AddOnInstructionDefinition>
+</AddOnInstructionDefinitions>
+<Programs>
+<Program Name="Line">
+<Routines>
+<Routine Name="Calc" Type="ST"><STContent>
+<Line Number="0">Filter(Filter_01, 0.5, 1.0);</Line>
+<Line Number="1">Filter(Filter_02);</Line>
+</STContent></Routine>
+</Routines>
+</Program>
+</Programs>
+</Controller>
+</RSLogix5000Content>"#;
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = StUnits::from_controller(project.l5x_controller.as_ref().unwrap());
+        let found = findings(&st_units, &UnboundInputConfig::default());
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].0, Severity::Warning);
+        assert!(found[0].1.starts_with("Routine 'Calc' line 2: call of 'Filter_02' (AOI 'Filter') leaves inputs 'Gain', 'Offset' unbound"));
+    }
+}
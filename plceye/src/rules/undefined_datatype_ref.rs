@@ -0,0 +1,158 @@
+//! Undefined data type reference detector.
+//!
+//! Detects tags, AOI parameters, variables and structure members whose data
+//! type, or array element type, is neither predefined nor defined in the
+//! project (S0015). The export is broken or incomplete: the type was
+//! renamed, deleted, or lives in a library that was not exported with it.
+
+use crate::analysis::DataTypeIndex;
+use crate::config::UndefinedDataTypeRefConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for references to undefined data types.
+pub struct UndefinedDataTypeRefDetector<'a> {
+    config: &'a UndefinedDataTypeRefConfig,
+}
+
+impl<'a> UndefinedDataTypeRefDetector<'a> {
+    /// Create a new undefined data type reference detector with the given configuration.
+    pub fn new(config: &'a UndefinedDataTypeRefConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on a data type index and add findings to the report.
+    pub fn detect(&self, index: &DataTypeIndex, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for reference in index.undefined() {
+            // Skip types matching an ignore pattern
+            if self.matches_ignore_pattern(&reference.type_name) {
+                continue;
+            }
+
+            let suggestion = index
+                .similar(&reference.type_name)
+                .map(|name| format!("; did you mean '{}'?", name))
+                .unwrap_or_default();
+            report.add(Rule::new(
+                RuleKind::UndefinedDataTypeRef,
+                Severity::Error,
+                reference.location.clone(),
+                reference.name.clone(),
+                format!(
+                    "{} '{}' has undefined data type '{}'{}",
+                    reference.kind.describe(),
+                    reference.name,
+                    reference.type_name,
+                    suggestion
+                ),
+            ));
+        }
+    }
+
+    /// Check if a type name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, type_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, type_name)
+    }
+}
+
+impl Detector for UndefinedDataTypeRefDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(&DataTypeIndex::from_controller(controller), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&DataTypeIndex::from_plcopen(plcopen), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(xml: &str, config: &UndefinedDataTypeRefConfig) -> Vec<String> {
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let index = match project.l5x_controller {
+            Some(ref controller) => DataTypeIndex::from_controller(controller),
+            None => DataTypeIndex::from_plcopen(project.plcopen_project.as_ref().unwrap()),
+        };
+        let mut report = Report::new();
+        UndefinedDataTypeRefDetector::new(config).detect(&index, &mut report);
+        report.rules.into_iter().map(|r| format!("{}: {}", r.location, r.message)).collect()
+    }
+
+    #[test]
+    fn test_l5x_undefined_types() {
+        let xml = r#"<?xml version="1.0" encoding="UTF-8"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+<Controller Name="Test">
+<DataTypes>
+<DataType Name="Motor_UDT" Family="NoFamily" Class="User"><Members>
+<Member Name="Speed" DataType="REAL" Dimension="0" Radix="Float" Hidden="false"/>
+<Member Name="Drive" DataType="DriveData" Dimension="0" Radix="NullType" Hidden="false"/>
+</Members></DataType>
+</DataTypes>
+<Tags>
+<Tag Name="Motors" TagType="Base" DataType="Motor_UTD" Dimensions="10"/>
+<Tag Name="Delay" TagType="Base" DataType="TIMER"/>
+<Tag Name="Vendor" TagType="Base" DataType="Lib_Valve"/>
+</Tags>
+<Programs><Program Name="Main"><Tags>
+<Tag Name="Local" TagType="Base" DataType="motor_udt"/>
+</Tags></Program></Programs>
+</Controller>
+</RSLogix5000Content>"#;
+        let config = UndefinedDataTypeRefConfig {
+            ignore_patterns: vec!["Lib_*".to_string()],
+            ..UndefinedDataTypeRefConfig::default()
+        };
+        assert_eq!(
+            detect(xml, &config),
+            [
+                "DataTypes: Member 'Motor_UDT.Drive' has undefined data type 'DriveData'",
+                "Controller: Tag 'Motors' has undefined data type 'Motor_UTD'; did you mean 'Motor_UDT'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_plcopen_undefined_types() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types>
+    <dataTypes>
+      <dataType name="Cell"><baseType><struct>
+        <variable name="Sensors"><type><array><dimension lower="1" upper="4"/><baseType><derived name="Sensor"/></baseType></array></type></variable>
+      </struct></baseType></dataType>
+    </dataTypes>
+    <pous>
+      <pou name="Main" pouType="program">
+        <interface><localVars>
+          <variable name="Cells"><type><array><dimension lower="1" upper="2"/><baseType><derived name="Cel"/></baseType></array></type></variable>
+          <variable name="Pulse"><type><derived name="TON"/></type></variable>
+          <variable name="Line"><type><derived name="Cell"/></type></variable>
+        </localVars></interface>
+        <body><ST><![CDATA[]]></ST></body>
+      </pou>
+    </pous>
+  </types>
+  <instances><configurations/></instances>
+</project>"#;
+        assert_eq!(
+            detect(xml, &UndefinedDataTypeRefConfig::default()),
+            [
+                "dataTypes: Member 'Cell.Sensors' has undefined data type 'Sensor'",
+                "Main: Variable 'Cells' has undefined data type 'Cel'; did you mean 'Cell'?",
+            ]
+        );
+    }
+}
@@ -1,26 +1,43 @@
 //! Undefined tags detector.
 //!
 //! Detects tags that are referenced in code but not declared.
-//! Note: Some "undefined" tags may be valid (aliases, module I/O, etc.)
+//! Note: Some "undefined" tags may be valid (aliases, external systems, etc.)
+//!
+//! Module I/O references (`Local:1:I.Data`, `Drive1:O`) are checked against
+//! the tags the export's `Modules` section defines. Exports without one
+//! cannot resolve them, so they are skipped.
 
 use std::collections::HashSet;
 
 use l5x::{Controller, UDIDefinitionContent};
 
-use crate::analysis::ProjectAnalysis;
+use crate::analysis::{analyze_controller, name_key, IoTags, ProjectAnalysis};
 
 use crate::config::UndefinedTagsConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
 use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
 
 /// Detector for undefined tags.
 pub struct UndefinedTagsDetector<'a> {
     config: &'a UndefinedTagsConfig,
+    case_sensitive: bool,
 }
 
 impl<'a> UndefinedTagsDetector<'a> {
     /// Create a new undefined tags detector with the given configuration.
     pub fn new(config: &'a UndefinedTagsConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            case_sensitive: false,
+        }
+    }
+
+    /// Match tag names case-sensitively (`general.case_sensitive`).
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
     }
 
     /// Run detection on a controller and add findings to the report.
@@ -31,30 +48,41 @@ impl<'a> UndefinedTagsDetector<'a> {
 
         // Collect all defined tags
         let defined_tags = self.collect_defined_tags(controller);
-        
+
+        // Collect the tags defined by I/O modules, if the export has modules
+        let module_tags: Option<HashSet<String>> = if self.config.module_tags {
+            IoTags::from_controller(controller).map(|io| {
+                io.module_tags().map(|tag| name_key(&tag.name, self.case_sensitive)).collect()
+            })
+        } else {
+            None
+        };
+
         // Collect all AOI names (they can appear as instruction-like references)
         let aoi_names: HashSet<String> = analysis.aoi_definitions
             .iter()
-            .cloned()
+            .map(|name| name_key(name, self.case_sensitive))
             .collect();
 
-        // Check each referenced tag
-        for tag_ref in analysis.unique_tags() {
+        // Check each referenced tag, once per spelling that matches
+        let mut reported = HashSet::new();
+        for tag_ref in referenced_names(analysis) {
             // Extract base tag name (before any dots or brackets)
-            let base_name = extract_base_name(tag_ref);
+            let base_name = extract_base_name(&tag_ref);
+            let key = name_key(base_name, self.case_sensitive);
             
             // Skip if defined
-            if defined_tags.contains(base_name) {
+            if defined_tags.contains(&key) {
                 continue;
             }
             
             // Skip if it's an AOI name
-            if aoi_names.contains(base_name) {
+            if aoi_names.contains(&key) {
                 continue;
             }
 
-            // Skip if matches ignore pattern
-            if self.matches_ignore_pattern(base_name) {
+            // Skip if matches ignore pattern or is known to be external
+            if self.matches_ignore_pattern(base_name) || self.is_allowed(base_name) {
                 continue;
             }
 
@@ -63,17 +91,32 @@ impl<'a> UndefinedTagsDetector<'a> {
                 continue;
             }
 
+            // Module I/O tags; ':' never appears in a declared tag name
+            let is_module_tag = self.config.module_tags && base_name.contains(':');
+            if is_module_tag && module_tags.as_ref().map_or(true, |tags| tags.contains(&key)) {
+                continue;
+            }
+
+            if !reported.insert(key) {
+                continue;
+            }
+
+            let message = if is_module_tag {
+                format!("Tag '{}' is referenced but no module in the export defines it", base_name)
+            } else {
+                format!("Tag '{}' is referenced but not defined (may be alias or I/O)", base_name)
+            };
             report.add(Rule::new(
                 RuleKind::UndefinedTag,
                 Severity::Warning,
                 "Controller".to_string(),
                 base_name.to_string(),
-                format!("Tag '{}' is referenced but not defined (may be alias or I/O)", base_name),
+                message,
             ));
         }
     }
 
-    /// Collect all defined tag names (base names only).
+    /// Collect the keys of all defined tag names (base names only).
     fn collect_defined_tags(&self, controller: &Controller) -> HashSet<String> {
         let mut tags = HashSet::new();
 
@@ -116,18 +159,53 @@ impl<'a> UndefinedTagsDetector<'a> {
             }
         }
 
-        tags
+        tags.into_iter().map(|name| name_key(&name, self.case_sensitive)).collect()
     }
 
     /// Check if a tag name matches any ignore pattern.
     fn matches_ignore_pattern(&self, tag_name: &str) -> bool {
-        for pattern in &self.config.ignore_patterns {
-            if glob_match(pattern, tag_name) {
-                return true;
+        matches_any(&self.config.ignore_patterns, tag_name)
+    }
+
+    /// Check if a tag name is on the allowlist of external tags.
+    fn is_allowed(&self, tag_name: &str) -> bool {
+        matches_any(&self.config.allow, tag_name)
+    }
+}
+
+impl Detector for UndefinedTagsDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(controller, &analyze_controller(controller), report);
+            }
+        })
+    }
+}
+
+/// Names referenced in code, sorted: base tag names, and module I/O tags
+/// (`Local:1:I`) for references through a module, whose base name is only
+/// the module part (`Local`).
+fn referenced_names(analysis: &ProjectAnalysis) -> Vec<String> {
+    let mut names = Vec::new();
+    for base in analysis.unique_tags() {
+        let references = analysis.references_to(base);
+        let mut plain = references.is_empty();
+        for reference in references {
+            let path = extract_base_name(reference.full_operand().trim());
+            if path.contains(':') {
+                names.push(path.to_string());
+            } else {
+                plain = true;
             }
         }
-        false
+        if plain {
+            names.push(base.to_string());
+        }
     }
+    names.sort();
+    names.dedup();
+    names
 }
 
 /// Extract base tag name from a potentially qualified reference.
@@ -153,44 +231,6 @@ fn is_builtin_tag(name: &str) -> bool {
     )
 }
 
-/// Simple glob pattern matching supporting * and ? wildcards.
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let pattern_chars: Vec<char> = pattern.chars().collect();
-    let text_chars: Vec<char> = text.chars().collect();
-    glob_match_recursive(&pattern_chars, &text_chars, 0, 0)
-}
-
-fn glob_match_recursive(pattern: &[char], text: &[char], pi: usize, ti: usize) -> bool {
-    if pi == pattern.len() {
-        return ti == text.len();
-    }
-
-    match pattern[pi] {
-        '*' => {
-            for i in ti..=text.len() {
-                if glob_match_recursive(pattern, text, pi + 1, i) {
-                    return true;
-                }
-            }
-            false
-        }
-        '?' => {
-            if ti < text.len() {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
-            }
-        }
-        c => {
-            if ti < text.len() && c.eq_ignore_ascii_case(&text[ti]) {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
-            }
-        }
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
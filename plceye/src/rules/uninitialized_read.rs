@@ -0,0 +1,129 @@
+//! Uninitialized read detector.
+//!
+//! Detects reads of local variables on a path where they have no prior
+//! assignment and no initializer (C0005).
+
+use iecst::check_uninitialized_reads;
+
+use crate::analysis::StUnits;
+use crate::config::UninitializedReadConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for reads of uninitialized locals.
+pub struct UninitializedReadDetector<'a> {
+    config: &'a UninitializedReadConfig,
+}
+
+impl<'a> UninitializedReadDetector<'a> {
+    /// Create a new uninitialized read detector with the given configuration.
+    pub fn new(config: &'a UninitializedReadConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+
+            for diagnostic in check_uninitialized_reads(pou) {
+                report.add(Rule::new(
+                    RuleKind::UninitializedRead,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    format!(
+                        "Routine '{}' line {}: {}",
+                        unit.name,
+                        unit.line_of(diagnostic.span.start),
+                        diagnostic.kind
+                    ),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for UninitializedReadDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect_plcopen(vars: &str, body: &str) -> Report {
+        let xml = format!(
+            r#"<?xml version="1.0"?>
+            <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+                <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+                <contentHeader name="Test"/>
+                <types>
+                    <pous>
+                        <pou name="Scale" pouType="function">
+                            <interface>
+                                <returnType><INT/></returnType>
+                                <inputVars>
+                                    <variable name="Raw"><type><INT/></type></variable>
+                                </inputVars>
+                                <localVars>{}</localVars>
+                            </interface>
+                            <body><ST><![CDATA[{}]]></ST></body>
+                        </pou>
+                    </pous>
+                </types>
+            </project>"#,
+            vars, body
+        );
+
+        let project = LoadedProject::from_str(&xml, None).expect("Should parse");
+        let st_units = StUnits::from_plcopen(project.plcopen_project.as_ref().unwrap());
+        let config = UninitializedReadConfig::default();
+        let mut report = Report::new();
+        UninitializedReadDetector::new(&config).detect(&st_units, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_read_on_unassigned_path() {
+        let report = detect_plcopen(
+            r#"<variable name="Offset"><type><INT/></type></variable>"#,
+            "IF Raw > 0 THEN\n  Offset := 1;\nEND_IF;\nScale := Raw + Offset;",
+        );
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].kind, RuleKind::UninitializedRead);
+        assert!(report.rules[0].message.contains("line 4"));
+        assert!(report.rules[0].message.contains("'Offset'"));
+    }
+
+    #[test]
+    fn test_initial_value_exempt() {
+        let report = detect_plcopen(
+            r#"<variable name="Offset"><type><INT/></type><initialValue><simpleValue value="5"/></initialValue></variable>"#,
+            "Scale := Raw + Offset;",
+        );
+        assert!(report.rules.is_empty());
+    }
+}
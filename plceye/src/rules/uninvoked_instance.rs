@@ -0,0 +1,187 @@
+//! Uninvoked instance detector.
+//!
+//! Detects FB and AOI instances that are declared but never called (S0016).
+//! Unlike unused tags, an instance whose members are read but which is
+//! never invoked is still reported, since its outputs never update.
+
+use crate::analysis::{analyze_controller, analyze_plcopen_project, InstanceUsage, StUnits};
+use crate::config::UninvokedInstanceConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for FB and AOI instances that are never invoked.
+pub struct UninvokedInstanceDetector<'a> {
+    config: &'a UninvokedInstanceConfig,
+}
+
+impl<'a> UninvokedInstanceDetector<'a> {
+    /// Create a new uninvoked instance detector with the given configuration.
+    pub fn new(config: &'a UninvokedInstanceConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the instances of a project and add findings to the report.
+    pub fn detect(&self, usage: &InstanceUsage, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for instance in usage.uninvoked() {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&instance.name) {
+                continue;
+            }
+
+            report.add(Rule::new(
+                RuleKind::UninvokedInstance,
+                Severity::Warning,
+                instance.location.clone(),
+                instance.name.clone(),
+                format!("Instance '{}' of {} is declared but never invoked", instance.name, instance.fb_type),
+            ));
+        }
+    }
+
+    /// Check if an instance name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for UninvokedInstanceDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                let st_units = StUnits::from_controller(controller);
+                self.detect(&InstanceUsage::from_controller(controller, &analyze_controller(controller), &st_units), report);
+            } else if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&InstanceUsage::from_plcopen(plcopen, &analyze_plcopen_project(plcopen)), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{analyze_controller, analyze_plcopen_project, StUnits};
+
+    fn detect(xml: &str) -> Report {
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let usage = match project.l5x_controller {
+            Some(ref controller) => {
+                let analysis = analyze_controller(controller);
+                InstanceUsage::from_controller(controller, &analysis, &StUnits::from_controller(controller))
+            }
+            None => {
+                let plcopen = project.plcopen_project.as_ref().unwrap();
+                InstanceUsage::from_plcopen(plcopen, &analyze_plcopen_project(plcopen))
+            }
+        };
+        let mut report = Report::new();
+        UninvokedInstanceDetector::new(&UninvokedInstanceConfig::default()).detect(&usage, &mut report);
+        report
+    }
+
+    #[test]
+    fn test_l5x_aoi_instances() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <AddOnInstructionDefinitions>
+                    <AddOnInstructionDefinition Name="Valve"/>
+                </AddOnInstructionDefinitions>
+                <Programs>
+                    <Program Name="Main">
+                        <Tags>
+                            <Tag Name="Valve1" TagType="Base" DataType="Valve"/>
+                            <Tag Name="Valve2" TagType="Base" DataType="Valve"/>
+                            <Tag Name="Valve3" TagType="Base" DataType="Valve"/>
+                            <Tag Name="Open" TagType="Base" DataType="BOOL"/>
+                        </Tags>
+                        <Routines>
+                            <Routine Name="Ladder" Type="RLL">
+                                <RLLContent>
+                                    <Rung Number="0">
+                                        <Text>Valve(Valve1,Open);</Text>
+                                    </Rung>
+                                    <Rung Number="1">
+                                        <Text>XIC(Valve3.Opened)OTE(Open);</Text>
+                                    </Rung>
+                                </RLLContent>
+                            </Routine>
+                            <Routine Name="Logic" Type="ST">
+                                <STContent>
+                                    <Line Number="0"><![CDATA[Valve(Valve2, Open);]]></Line>
+                                </STContent>
+                            </Routine>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let report = detect(xml);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].identifier, "Valve3");
+        assert_eq!(report.rules[0].location, "Program:Main");
+        assert_eq!(report.rules[0].message, "Instance 'Valve3' of Valve is declared but never invoked");
+    }
+
+    #[test]
+    fn test_l5x_fbd_program_skipped() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <AddOnInstructionDefinitions>
+                    <AddOnInstructionDefinition Name="Valve"/>
+                </AddOnInstructionDefinitions>
+                <Programs>
+                    <Program Name="Main">
+                        <Tags>
+                            <Tag Name="Valve1" TagType="Base" DataType="Valve"/>
+                        </Tags>
+                        <Routines>
+                            <Routine Name="Sheet" Type="FBD"/>
+                        </Routines>
+                    </Program>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        assert!(detect(xml).rules.is_empty());
+    }
+
+    #[test]
+    fn test_plcopen_fb_instances() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Main" pouType="program">
+                        <interface>
+                            <localVars>
+                                <variable name="StartDelay"><type><derived name="TON"/></type></variable>
+                                <variable name="StopDelay"><type><derived name="TON"/></type></variable>
+                                <variable name="Running"><type><BOOL/></type></variable>
+                            </localVars>
+                        </interface>
+                        <body>
+                            <ST><![CDATA[StartDelay(IN := TRUE, PT := T#1s);
+Running := StartDelay.Q AND NOT StopDelay.Q;]]></ST>
+                        </body>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+        let report = detect(xml);
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].identifier, "StopDelay");
+        assert_eq!(report.rules[0].message, "Instance 'StopDelay' of TON is declared but never invoked");
+    }
+}
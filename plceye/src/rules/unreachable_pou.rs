@@ -0,0 +1,169 @@
+//! Unreachable POU detector.
+//!
+//! Detects POUs with no call path from an entry point (M0010): the
+//! programs the tasks run and the POUs configured as `entry_points`, such as
+//! library POUs that other projects call. A POU only called from other
+//! unreachable POUs is dead code as well. Programs are not reported, as
+//! those without a task are unscheduled programs (S0011).
+
+use std::collections::BTreeSet;
+
+use crate::analysis::{analyze_controller, analyze_plcopen_project, CallGraph, TaskBindings};
+use crate::config::UnreachablePouConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for POUs that no entry point reaches.
+pub struct UnreachablePouDetector<'a> {
+    config: &'a UnreachablePouConfig,
+    location_prefix: &'a str,
+    skip_uncalled: bool,
+}
+
+impl<'a> UnreachablePouDetector<'a> {
+    /// Create a new unreachable POU detector with the given configuration.
+    pub fn new(config: &'a UnreachablePouConfig) -> Self {
+        Self {
+            config,
+            location_prefix: "",
+            skip_uncalled: false,
+        }
+    }
+
+    /// Prefix of finding locations before the POU name (`AOI:` for L5X).
+    pub fn location_prefix(mut self, prefix: &'a str) -> Self {
+        self.location_prefix = prefix;
+        self
+    }
+
+    /// Skip POUs that nothing calls, when another rule reports them (unused
+    /// AOIs).
+    pub fn skip_uncalled(mut self, skip: bool) -> Self {
+        self.skip_uncalled = skip;
+        self
+    }
+
+    /// Run detection on the call graph and task bindings of a project and
+    /// add findings to the report.
+    pub fn detect(&self, graph: &CallGraph, bindings: &TaskBindings, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        let programs: BTreeSet<String> = bindings.programs.iter().map(|p| p.to_lowercase()).collect();
+        let entry_points: BTreeSet<String> = bindings.entry_points().map(str::to_lowercase).collect();
+        let roots = graph.pous().filter(|pou| {
+            entry_points.contains(&pou.to_lowercase())
+                || matches_any(&self.config.entry_points, pou)
+        });
+        let reachable = graph.reachable_from(roots);
+
+        for pou in graph.pous() {
+            if reachable.contains(pou) || programs.contains(&pou.to_lowercase()) {
+                continue;
+            }
+
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(pou) {
+                continue;
+            }
+
+            let called = graph.is_called(pou);
+            if !called && self.skip_uncalled {
+                continue;
+            }
+            let how = if called { "is only called from unreachable code" } else { "is never called" };
+
+            report.add(Rule::new(
+                RuleKind::UnreachablePou,
+                Severity::Info,
+                format!("{}{}", self.location_prefix, pou),
+                pou.to_string(),
+                format!("POU '{}' {} and has no call path from any entry point", pou, how),
+            ));
+        }
+    }
+
+    /// Check if a POU name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, pou: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, pou)
+    }
+}
+
+impl Detector for UnreachablePouDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        let (graph, bindings) = if let Some(ref controller) = project.l5x_controller {
+            (analyze_controller(controller).call_graph(), TaskBindings::from_controller(controller))
+        } else if let Some(ref plcopen) = project.plcopen_project {
+            (analyze_plcopen_project(plcopen).call_graph(), TaskBindings::from_plcopen(plcopen))
+        } else {
+            return Vec::new();
+        };
+        findings(|report| {
+            if let Some(ref bindings) = bindings {
+                self.detect(&graph, bindings, report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::Task;
+    use crate::analysis::TaskTrigger;
+
+    fn project() -> (CallGraph, TaskBindings) {
+        let mut graph = CallGraph::new();
+        graph.add_call("Main", "Conveyor");
+        graph.add_call("Conveyor", "Motor");
+        graph.add_call("OldLine", "Valve");
+        graph.add_call("Valve", "Filter");
+        graph.add_call("Lib_Scale", "Clamp");
+        graph.add_pou("Spare");
+        let bindings = TaskBindings {
+            tasks: vec![Task {
+                name: "MainTask".to_string(),
+                trigger: TaskTrigger::Continuous,
+                programs: vec!["MAIN".to_string()],
+            }],
+            programs: vec!["Main".to_string(), "OldLine".to_string()],
+            unbound_instances: Vec::new(),
+        };
+        (graph, bindings)
+    }
+
+    fn detect(config: &UnreachablePouConfig, skip_uncalled: bool) -> Vec<(String, String)> {
+        let (graph, bindings) = project();
+        let mut report = Report::new();
+        UnreachablePouDetector::new(config)
+            .skip_uncalled(skip_uncalled)
+            .detect(&graph, &bindings, &mut report);
+        report.rules.into_iter().map(|r| (r.identifier, r.message)).collect()
+    }
+
+    #[test]
+    fn test_unreachable_pous() {
+        let found = detect(&UnreachablePouConfig::default(), false);
+        let names: Vec<&str> = found.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["Clamp", "Filter", "Lib_Scale", "Spare", "Valve"]);
+        assert_eq!(
+            found[1].1,
+            "POU 'Filter' is only called from unreachable code and has no call path from any entry point"
+        );
+        assert_eq!(found[3].1, "POU 'Spare' is never called and has no call path from any entry point");
+    }
+
+    #[test]
+    fn test_entry_points_and_skipped_uncalled() {
+        let config = UnreachablePouConfig {
+            entry_points: vec!["Lib_*".to_string()],
+            ..UnreachablePouConfig::default()
+        };
+        let found = detect(&config, true);
+        let names: Vec<&str> = found.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(names, ["Filter", "Valve"]);
+    }
+}
@@ -0,0 +1,190 @@
+//! Unscheduled program detector.
+//!
+//! Detects programs that no task runs, which never execute, and tasks that
+//! run no programs (S0011).
+
+use crate::analysis::TaskBindings;
+use crate::config::UnscheduledProgramConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for programs not bound to any task.
+pub struct UnscheduledProgramDetector<'a> {
+    config: &'a UnscheduledProgramConfig,
+}
+
+impl<'a> UnscheduledProgramDetector<'a> {
+    /// Create a new unscheduled program detector with the given configuration.
+    pub fn new(config: &'a UnscheduledProgramConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on the task bindings of a project and add findings to the report.
+    pub fn detect(&self, bindings: &TaskBindings, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for program in bindings.unscheduled_programs() {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(program) {
+                continue;
+            }
+
+            let mut message = format!("Program '{}' is not scheduled by any task and never runs", program);
+            if let Some(task) = bindings.suggest_task(program) {
+                message.push_str(&format!(" (likely belongs to task '{}')", task.name));
+            }
+
+            report.add(Rule::new(
+                RuleKind::UnscheduledProgram,
+                Severity::Warning,
+                format!("Program:{}", program),
+                program.to_string(),
+                message,
+            ));
+        }
+
+        if !self.config.check_empty_tasks {
+            return;
+        }
+
+        for task in bindings.empty_tasks() {
+            if self.matches_ignore_pattern(&task.name) {
+                continue;
+            }
+
+            report.add(Rule::new(
+                RuleKind::UnscheduledProgram,
+                Severity::Info,
+                format!("Task:{}", task.name),
+                task.name.clone(),
+                format!("Task '{}' ({}) runs no programs", task.name, task.trigger),
+            ));
+        }
+    }
+
+    /// Check if a program or task name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for UnscheduledProgramDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        let bindings = if let Some(ref controller) = project.l5x_controller {
+            TaskBindings::from_controller(controller)
+        } else if let Some(ref plcopen) = project.plcopen_project {
+            TaskBindings::from_plcopen(plcopen)
+        } else {
+            None
+        };
+        findings(|report| {
+            if let Some(ref bindings) = bindings {
+                self.detect(bindings, report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn detect(xml: &str, config: &UnscheduledProgramConfig) -> Report {
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let bindings = match project.l5x_controller {
+            Some(ref controller) => TaskBindings::from_controller(controller),
+            None => TaskBindings::from_plcopen(project.plcopen_project.as_ref().unwrap()),
+        };
+        let mut report = Report::new();
+        if let Some(bindings) = bindings {
+            UnscheduledProgramDetector::new(config).detect(&bindings, &mut report);
+        }
+        report
+    }
+
+    #[test]
+    fn test_l5x_unscheduled_program() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <Tasks>
+                    <Task Name="MainTask" Type="CONTINUOUS">
+                        <ScheduledPrograms>
+                            <ScheduledProgram Name="Conveyor_Main"/>
+                        </ScheduledPrograms>
+                    </Task>
+                    <Task Name="Fast" Type="PERIODIC" Rate="10"/>
+                </Tasks>
+                <Programs>
+                    <Program Name="Conveyor_Main"/>
+                    <Program Name="Conveyor_Aux"/>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        let report = detect(xml, &UnscheduledProgramConfig::default());
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.rules[0].identifier, "Conveyor_Aux");
+        assert_eq!(
+            report.rules[0].message,
+            "Program 'Conveyor_Aux' is not scheduled by any task and never runs (likely belongs to task 'MainTask')"
+        );
+        assert_eq!(report.rules[1].message, "Task 'Fast' (periodic (10)) runs no programs");
+
+        let config = UnscheduledProgramConfig {
+            check_empty_tasks: false,
+            ..UnscheduledProgramConfig::default()
+        };
+        assert_eq!(detect(xml, &config).rules.len(), 1);
+    }
+
+    #[test]
+    fn test_l5x_without_tasks_section() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <Programs>
+                    <Program Name="Main"/>
+                </Programs>
+            </Controller>
+        </RSLogix5000Content>"#;
+
+        assert!(detect(xml, &UnscheduledProgramConfig::default()).rules.is_empty());
+    }
+
+    #[test]
+    fn test_plcopen_resource_tasks() {
+        let xml = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Main" pouType="program"/>
+                    <pou name="Background" pouType="program"/>
+                    <pou name="Orphan" pouType="program"/>
+                </pous>
+            </types>
+            <instances>
+                <configurations>
+                    <configuration name="Config">
+                        <resource name="Res">
+                            <task name="Cyclic" interval="T#10ms" priority="1">
+                                <pouInstance name="MainInst" typeName="Main"/>
+                            </task>
+                            <pouInstance name="BgInst" typeName="Background"/>
+                        </resource>
+                    </configuration>
+                </configurations>
+            </instances>
+        </project>"#;
+
+        let report = detect(xml, &UnscheduledProgramConfig::default());
+        assert_eq!(report.rules.len(), 1);
+        assert_eq!(report.rules[0].identifier, "Orphan");
+    }
+}
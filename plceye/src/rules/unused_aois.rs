@@ -4,9 +4,12 @@
 
 use std::collections::HashSet;
 
-use crate::analysis::ProjectAnalysis;
+use crate::analysis::{analyze_controller, ProjectAnalysis};
 use crate::config::UnusedAoisConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
 use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
 
 /// Detector for unused AOIs.
 pub struct UnusedAoisDetector<'a> {
@@ -54,50 +57,17 @@ impl<'a> UnusedAoisDetector<'a> {
 
     /// Check if an AOI name matches any ignore pattern.
     fn matches_ignore_pattern(&self, aoi_name: &str) -> bool {
-        for pattern in &self.config.ignore_patterns {
-            if glob_match(pattern, aoi_name) {
-                return true;
-            }
-        }
-        false
+        matches_any(&self.config.ignore_patterns, aoi_name)
     }
 }
 
-/// Simple glob pattern matching supporting * and ? wildcards.
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let pattern_chars: Vec<char> = pattern.chars().collect();
-    let text_chars: Vec<char> = text.chars().collect();
-    glob_match_recursive(&pattern_chars, &text_chars, 0, 0)
-}
-
-fn glob_match_recursive(pattern: &[char], text: &[char], pi: usize, ti: usize) -> bool {
-    if pi == pattern.len() {
-        return ti == text.len();
-    }
-
-    match pattern[pi] {
-        '*' => {
-            for i in ti..=text.len() {
-                if glob_match_recursive(pattern, text, pi + 1, i) {
-                    return true;
-                }
+impl Detector for UnusedAoisDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(&analyze_controller(controller), report);
             }
-            false
-        }
-        '?' => {
-            if ti < text.len() {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
-            }
-        }
-        c => {
-            if ti < text.len() && c.eq_ignore_ascii_case(&text[ti]) {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
-            }
-        }
+        })
     }
 }
 
@@ -122,6 +92,7 @@ mod tests {
             instruction_usage: Default::default(),
             aoi_definitions: vec!["UsedAOI".to_string(), "UnusedAOI".to_string()],
             aoi_usage: Default::default(),
+            protected: vec![],
             stats: ParseStats::default(),
         };
         
@@ -162,6 +133,7 @@ mod tests {
             instruction_usage: Default::default(),
             aoi_definitions: vec!["Test_AOI".to_string()],
             aoi_usage: Default::default(),
+            protected: vec![],
             stats: ParseStats::default(),
         };
         
@@ -172,12 +144,4 @@ mod tests {
         assert_eq!(report.rules.len(), 0);
     }
 
-    #[test]
-    fn test_glob_match() {
-        assert!(glob_match("Test*", "TestAOI"));
-        assert!(glob_match("*AOI", "MyTestAOI"));
-        assert!(glob_match("*", "anything"));
-        assert!(glob_match("Test?", "TestA"));
-        assert!(!glob_match("Test?", "TestAB"));
-    }
 }
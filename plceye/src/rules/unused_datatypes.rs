@@ -2,12 +2,14 @@
 //!
 //! Detects user-defined data types that are never used by any tag or AOI.
 
-use std::collections::HashSet;
-
-use l5x::{Controller, UDIDefinitionContent};
+use l5x::Controller;
 
+use crate::analysis::DataTypeIndex;
 use crate::config::UnusedDataTypesConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
 use crate::report::{Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
 
 /// Detector for unused DataTypes.
 pub struct UnusedDataTypesDetector<'a> {
@@ -26,15 +28,11 @@ impl<'a> UnusedDataTypesDetector<'a> {
             return;
         }
 
-        // Collect defined user data types
-        let defined_types = self.collect_defined_datatypes(controller);
-        
-        // Collect all used data types (from tags, AOI parameters, etc.)
-        let used_types = self.collect_used_datatypes(controller);
+        // Types referenced by tags, AOI parameters, local tags and members
+        let index = DataTypeIndex::from_controller(controller);
 
-        // Find unused types
-        for type_name in &defined_types {
-            if used_types.contains(type_name.as_str()) {
+        for type_name in &index.defined {
+            if index.is_used(type_name) {
                 continue;
             }
 
@@ -53,139 +51,18 @@ impl<'a> UnusedDataTypesDetector<'a> {
         }
     }
 
-    /// Collect all defined user data types.
-    fn collect_defined_datatypes(&self, controller: &Controller) -> Vec<String> {
-        let mut types = Vec::new();
-
-        if let Some(ref datatypes) = controller.data_types {
-            for dt in &datatypes.data_type {
-                types.push(dt.name.clone());
-            }
-        }
-
-        types
-    }
-
-    /// Collect all used data types from tags, AOI parameters, etc.
-    fn collect_used_datatypes(&self, controller: &Controller) -> HashSet<String> {
-        let mut used = HashSet::new();
-
-        // Check controller-scope tags
-        if let Some(ref tags) = controller.tags {
-            for tag in &tags.tag {
-                if let Some(ref dt) = tag.data_type {
-                    used.insert(dt.clone());
-                }
-            }
-        }
-
-        // Check program-scope tags
-        if let Some(ref programs) = controller.programs {
-            for program in &programs.program {
-                if let Some(ref tags) = program.tags {
-                    for tag in &tags.tag {
-                        if let Some(ref dt) = tag.data_type {
-                            used.insert(dt.clone());
-                        }
-                    }
-                }
-            }
-        }
-
-        // Check AOI parameters and local tags
-        if let Some(ref aois) = controller.add_on_instruction_definitions {
-            for aoi in &aois.add_on_instruction_definition {
-                for content in &aoi.content {
-                    match content {
-                        UDIDefinitionContent::Parameters(params) => {
-                            for param in &params.parameter {
-                                if let Some(ref dt) = param.data_type {
-                                    used.insert(dt.clone());
-                                }
-                            }
-                        }
-                        UDIDefinitionContent::LocalTags(local_tags) => {
-                            for tag in &local_tags.local_tag {
-                                used.insert(tag.data_type.clone());
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
-        }
-
-        // Check data type members (for nested types)
-        if let Some(ref datatypes) = controller.data_types {
-            for dt in &datatypes.data_type {
-                if let Some(ref members) = dt.members {
-                    for member in &members.member {
-                        used.insert(member.data_type.clone());
-                    }
-                }
-            }
-        }
-
-        used
-    }
-
     /// Check if a type name matches any ignore pattern.
     fn matches_ignore_pattern(&self, type_name: &str) -> bool {
-        for pattern in &self.config.ignore_patterns {
-            if glob_match(pattern, type_name) {
-                return true;
-            }
-        }
-        false
+        matches_any(&self.config.ignore_patterns, type_name)
     }
 }
 
-/// Simple glob pattern matching supporting * and ? wildcards.
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let pattern_chars: Vec<char> = pattern.chars().collect();
-    let text_chars: Vec<char> = text.chars().collect();
-    glob_match_recursive(&pattern_chars, &text_chars, 0, 0)
-}
-
-fn glob_match_recursive(pattern: &[char], text: &[char], pi: usize, ti: usize) -> bool {
-    if pi == pattern.len() {
-        return ti == text.len();
-    }
-
-    match pattern[pi] {
-        '*' => {
-            for i in ti..=text.len() {
-                if glob_match_recursive(pattern, text, pi + 1, i) {
-                    return true;
-                }
+impl Detector for UnusedDataTypesDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(controller, report);
             }
-            false
-        }
-        '?' => {
-            if ti < text.len() {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
-            }
-        }
-        c => {
-            if ti < text.len() && c.eq_ignore_ascii_case(&text[ti]) {
-                glob_match_recursive(pattern, text, pi + 1, ti + 1)
-            } else {
-                false
-            }
-        }
-    }
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_glob_match() {
-        assert!(glob_match("UDT_*", "UDT_Motor"));
-        assert!(glob_match("*_Type", "MyCustom_Type"));
-        assert!(!glob_match("UDT_*", "Motor_UDT"));
+        })
     }
 }
@@ -0,0 +1,124 @@
+//! Unused method parameter detector.
+//!
+//! Detects `VAR_INPUT` parameters of function block and class methods that
+//! the method never reads (S0019), which are dead interface surface. An
+//! `OVERRIDE` method has to keep the signature of the base method, so its
+//! unread parameters are reported as info, or skipped with
+//! `ignore_overrides`.
+
+use iec61131::analysis::unused_method_parameters;
+use iec61131::CompilationUnit;
+
+use crate::analysis::st_declarations;
+use crate::config::UnusedMethodParameterConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind, Severity};
+use super::{findings, matches_any};
+
+/// Detector for method input parameters that are never read.
+pub struct UnusedMethodParameterDetector<'a> {
+    config: &'a UnusedMethodParameterConfig,
+}
+
+impl<'a> UnusedMethodParameterDetector<'a> {
+    /// Create a new unused method parameter detector with the given configuration.
+    pub fn new(config: &'a UnusedMethodParameterConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on parsed declarations, by the POU holding them, and
+    /// add findings to the report.
+    pub fn detect(&self, declarations: &[(String, CompilationUnit)], report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for (pou, unit) in declarations {
+            for unused in unused_method_parameters(unit) {
+                if unused.is_override && self.config.ignore_overrides {
+                    continue;
+                }
+                if self.matches_ignore_pattern(&unused.parameter) {
+                    continue;
+                }
+
+                let (severity, note) = if unused.is_override {
+                    (Severity::Info, "; the method overrides a base method and keeps its signature")
+                } else {
+                    (Severity::Warning, "")
+                };
+                report.add(Rule::new(
+                    RuleKind::UnusedMethodParameter,
+                    severity,
+                    pou.clone(),
+                    format!("{}.{}", unused.method, unused.parameter),
+                    format!(
+                        "Input parameter '{}' of method '{}.{}' is never read{}",
+                        unused.parameter, unused.owner, unused.method, note
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Check if a parameter name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, name)
+    }
+}
+
+impl Detector for UnusedMethodParameterDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref plcopen) = project.plcopen_project {
+                self.detect(&st_declarations(plcopen), report);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = "CLASS Motor EXTENDS Drive
+        METHOD Start : BOOL
+            VAR_INPUT speed : INT; ramp : TIME; END_VAR
+            Start := speed > 0;
+        END_METHOD
+        METHOD PUBLIC OVERRIDE Stop
+            VAR_INPUT mode : INT; END_VAR
+            running := FALSE;
+        END_METHOD
+    END_CLASS";
+
+    fn detect(config: &UnusedMethodParameterConfig) -> Report {
+        let unit = iec61131::Parser::new(SOURCE).parse().unwrap();
+        let mut report = Report::new();
+        UnusedMethodParameterDetector::new(config).detect(&[("Drives".to_string(), unit)], &mut report);
+        report
+    }
+
+    #[test]
+    fn test_unused_parameters() {
+        let report = detect(&UnusedMethodParameterConfig::default());
+        assert_eq!(report.rules.len(), 2);
+        assert_eq!(report.rules[0].identifier, "Start.ramp");
+        assert_eq!(report.rules[0].location, "Drives");
+        assert_eq!(report.rules[0].severity, Severity::Warning);
+        assert_eq!(report.rules[0].message, "Input parameter 'ramp' of method 'Motor.Start' is never read");
+        assert_eq!(report.rules[1].identifier, "Stop.mode");
+        assert_eq!(report.rules[1].severity, Severity::Info);
+    }
+
+    #[test]
+    fn test_suppression() {
+        let config = UnusedMethodParameterConfig {
+            ignore_overrides: true,
+            ignore_patterns: vec!["r*".to_string()],
+            ..UnusedMethodParameterConfig::default()
+        };
+        assert!(detect(&config).rules.is_empty());
+    }
+}
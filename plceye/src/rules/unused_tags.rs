@@ -4,26 +4,42 @@
 
 use std::collections::HashSet;
 
-use l5x::Controller;
-
-use crate::analysis::ProjectAnalysis;
+use l5x::{Controller, Tag};
 
+use crate::analysis::{analyze_controller, name_key, ProjectAnalysis, StUnits, TagUsage};
 use crate::config::UnusedTagsConfig;
-use crate::report::{Report, Severity, Rule, RuleKind};
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{FixHint, Report, Severity, Rule, RuleKind};
+use super::{findings, matches_any};
 
 /// Detector for unused tags.
 pub struct UnusedTagsDetector<'a> {
     config: &'a UnusedTagsConfig,
+    case_sensitive: bool,
 }
 
 impl<'a> UnusedTagsDetector<'a> {
     /// Create a new unused tags detector with the given configuration.
     pub fn new(config: &'a UnusedTagsConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            case_sensitive: false,
+        }
+    }
+
+    /// Match tag names case-sensitively (`general.case_sensitive`).
+    pub fn case_sensitive(mut self, case_sensitive: bool) -> Self {
+        self.case_sensitive = case_sensitive;
+        self
     }
 
     /// Run detection on a controller and add findings to the report.
-    pub fn detect(&self, controller: &Controller, analysis: &ProjectAnalysis, report: &mut Report) {
+    ///
+    /// Tags are unused when no RLL rung names them. Removal is only offered
+    /// for tags that no RLL, ST or AOI logic names either, and not at all
+    /// when some logic could not be read.
+    pub fn detect(&self, controller: &Controller, analysis: &ProjectAnalysis, st_units: &StUnits, report: &mut Report) {
         if !self.config.enabled {
             return;
         }
@@ -32,16 +48,28 @@ impl<'a> UnusedTagsDetector<'a> {
         let defined_tags = self.collect_defined_tags(controller);
         
         // Collect all used tags from analysis
-        let used_tags: HashSet<&str> = analysis
+        let used_tags: HashSet<String> = analysis
             .tag_xref
             .keys()
-            .map(|s| s.as_str())
+            .map(|s| name_key(s, self.case_sensitive))
+            .collect();
+
+        // Tags other tags are aliases of
+        let aliased: HashSet<String> = defined_tags
+            .iter()
+            .filter_map(|(tag, _)| tag.alias_for.as_deref())
+            .map(|target| name_key(target.split(['.', '[']).next().unwrap_or(target), self.case_sensitive))
             .collect();
 
+        // References in all logic, for deciding whether removal is safe
+        let usage = TagUsage::from_controller(controller, analysis, st_units);
+
         // Find unused tags
-        for (tag_name, scope) in &defined_tags {
+        for (tag, scope) in &defined_tags {
+            let tag_name = &tag.name;
             // Skip if tag is used
-            if used_tags.contains(tag_name.as_str()) {
+            let key = name_key(tag_name, self.case_sensitive);
+            if used_tags.contains(&key) {
                 continue;
             }
 
@@ -55,24 +83,31 @@ impl<'a> UnusedTagsDetector<'a> {
                 continue;
             }
 
-            report.add(Rule::new(
+            let mut rule = Rule::new(
                 RuleKind::UnusedTag,
                 Severity::Info,
                 scope.clone(),
                 tag_name.clone(),
                 format!("Tag '{}' is defined but never used", tag_name),
-            ));
+            );
+            if usage.is_complete() && !usage.references(tag_name) && is_removable(tag, aliased.contains(&key)) {
+                rule = rule.with_fix(FixHint {
+                    description: format!("remove unused tag '{}'", tag_name),
+                    edit: None,
+                });
+            }
+            report.add(rule);
         }
     }
 
     /// Collect all defined tags from controller and program scopes.
-    fn collect_defined_tags(&self, controller: &Controller) -> Vec<(String, String)> {
+    fn collect_defined_tags<'c>(&self, controller: &'c Controller) -> Vec<(&'c Tag, String)> {
         let mut tags = Vec::new();
 
         // Controller-scope tags
         if let Some(ref tag_collection) = controller.tags {
             for tag in &tag_collection.tag {
-                tags.push((tag.name.clone(), "Controller".to_string()));
+                tags.push((tag, "Controller".to_string()));
             }
         }
 
@@ -82,7 +117,7 @@ impl<'a> UnusedTagsDetector<'a> {
                 if let Some(ref tag_collection) = program.tags {
                     for tag in &tag_collection.tag {
                         let scope = format!("Program:{}", program.name);
-                        tags.push((tag.name.clone(), scope));
+                        tags.push((tag, scope));
                     }
                 }
             }
@@ -93,12 +128,7 @@ impl<'a> UnusedTagsDetector<'a> {
 
     /// Check if a tag name matches any ignore pattern.
     fn matches_ignore_pattern(&self, tag_name: &str) -> bool {
-        for pattern in &self.config.ignore_patterns {
-            if glob_match(pattern, tag_name) {
-                return true;
-            }
-        }
-        false
+        matches_any(&self.config.ignore_patterns, tag_name)
     }
 
     /// Check if a scope matches any ignored scope.
@@ -107,72 +137,29 @@ impl<'a> UnusedTagsDetector<'a> {
     }
 }
 
-/// Simple glob matching (supports * and ? wildcards).
-fn glob_match(pattern: &str, text: &str) -> bool {
-    let pattern = pattern.to_lowercase();
-    let text = text.to_lowercase();
-    
-    let mut p_chars = pattern.chars().peekable();
-    let mut t_chars = text.chars().peekable();
-
-    while let Some(p) = p_chars.next() {
-        match p {
-            '*' => {
-                // * matches zero or more characters
-                if p_chars.peek().is_none() {
-                    return true;
-                }
-                let remaining_pattern: String = p_chars.collect();
-                let remaining_text: String = t_chars.collect();
-                for i in 0..=remaining_text.len() {
-                    if glob_match(&remaining_pattern, &remaining_text[i..]) {
-                        return true;
-                    }
-                }
-                return false;
-            }
-            '?' => {
-                if t_chars.next().is_none() {
-                    return false;
-                }
+impl Detector for UnusedTagsDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| {
+            if let Some(ref controller) = project.l5x_controller {
+                self.detect(controller, &analyze_controller(controller), &StUnits::from_controller(controller), report);
             }
-            c => {
-                if t_chars.next() != Some(c) {
-                    return false;
-                }
-            }
-        }
+        })
     }
+}
 
-    t_chars.next().is_none()
+/// Whether removing an unused tag can't change behavior: no HMI or other
+/// external access (`ExternalAccess="None"`), not produced or consumed by
+/// another controller, and no alias tag points to it.
+fn is_removable(tag: &Tag, aliased: bool) -> bool {
+    tag.external_access.as_deref() == Some("None")
+        && !matches!(tag.tag_type.as_deref(), Some("Produced" | "Consumed"))
+        && !aliased
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_glob_match_literal() {
-        assert!(glob_match("hello", "hello"));
-        assert!(glob_match("hello", "HELLO"));
-        assert!(!glob_match("hello", "world"));
-    }
-
-    #[test]
-    fn test_glob_match_star() {
-        assert!(glob_match("_*", "_internal"));
-        assert!(glob_match("_*", "_"));
-        assert!(!glob_match("_*", "public"));
-        assert!(glob_match("HMI_*", "HMI_Button1"));
-    }
-
-    #[test]
-    fn test_glob_match_question() {
-        assert!(glob_match("Tag?", "Tag1"));
-        assert!(!glob_match("Tag?", "Tag"));
-        assert!(!glob_match("Tag?", "Tag12"));
-    }
-
     #[test]
     fn test_matches_ignore_pattern() {
         let config = UnusedTagsConfig {
@@ -186,4 +173,82 @@ mod tests {
         assert!(detector.matches_ignore_pattern("HMI_Button"));
         assert!(!detector.matches_ignore_pattern("MyTag"));
     }
+
+    #[test]
+    fn test_fix_only_for_removable_tags() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Line">
+                <Tags>
+                    <Tag Name="Scratch" DataType="DINT" ExternalAccess="None"/>
+                    <Tag Name="Display" DataType="DINT" ExternalAccess="Read/Write"/>
+                    <Tag Name="Shared" TagType="Produced" DataType="DINT" ExternalAccess="None"/>
+                    <Tag Name="Target" DataType="DINT" ExternalAccess="None"/>
+                    <Tag Name="Alias" TagType="Alias" AliasFor="Target.0" ExternalAccess="None"/>
+                </Tags>
+            </Controller>
+        </RSLogix5000Content>"#;
+        let project = crate::loader::LoadedProject::from_str(xml, None).expect("Should parse");
+        let controller = project.l5x_controller.as_ref().unwrap();
+        let analysis = crate::analysis::analyze_controller(controller);
+        let mut report = Report::new();
+        UnusedTagsDetector::new(&UnusedTagsConfig::default()).detect(controller, &analysis, &StUnits::from_controller(controller), &mut report);
+
+        let fixes: Vec<_> = report
+            .rules
+            .iter()
+            .map(|r| (r.identifier.as_str(), r.fix().map(|fix| fix.description.as_str())))
+            .collect();
+        assert_eq!(
+            fixes,
+            [
+                ("Scratch", Some("remove unused tag 'Scratch'")),
+                ("Display", None),
+                ("Shared", None),
+                ("Target", None),
+                ("Alias", Some("remove unused tag 'Alias'")),
+            ]
+        );
+    }
+
+    fn fixes(st_lines: &str) -> Vec<(String, bool)> {
+        let xml = format!(
+            r#"<?xml version="1.0"?>
+            <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+                <Controller Name="Line">
+                    <Tags>
+                        <Tag Name="Setpoint" DataType="DINT" ExternalAccess="None"/>
+                        <Tag Name="Out" DataType="DINT" ExternalAccess="None"/>
+                        <Tag Name="Scratch" DataType="DINT" ExternalAccess="None"/>
+                    </Tags>
+                    <Programs>
+                        <Program Name="Main">
+                            <Routines>
+                                <Routine Name="Calc" Type="ST"><STContent>{}</STContent></Routine>
+                            </Routines>
+                        </Program>
+                    </Programs>
+                </Controller>
+            </RSLogix5000Content>"#,
+            st_lines
+        );
+        let project = crate::loader::LoadedProject::from_str(&xml, None).expect("Should parse");
+        Detector::detect(&UnusedTagsDetector::new(&UnusedTagsConfig::default()), &project)
+            .iter()
+            .map(|r| (r.identifier.clone(), r.fix().is_some()))
+            .collect()
+    }
+
+    #[test]
+    fn test_no_fix_for_tags_used_in_st() {
+        let found = fixes(r#"<Line Number="0"><![CDATA[Out := Setpoint * 2;]]></Line>"#);
+        assert_eq!(
+            found,
+            [("Setpoint".to_string(), false), ("Out".to_string(), false), ("Scratch".to_string(), true)]
+        );
+
+        // Usage is unknown when a routine doesn't parse
+        let found = fixes(r#"<Line Number="0"><![CDATA[Out := Setpoint * ;]]></Line>"#);
+        assert!(found.iter().all(|(_, fix)| !fix));
+    }
 }
@@ -0,0 +1,179 @@
+//! Unused variables detector.
+//!
+//! Detects unused variables by VAR block kind: inputs that are never read
+//! (S0007), outputs that are never assigned (S0008) and VAR_TEMP variables
+//! that are never used (S0009).
+
+use iecst::{check_unused_variables, DiagnosticKind};
+
+use crate::analysis::StUnits;
+use crate::config::UnusedVariablesConfig;
+use crate::detector::Detector;
+use crate::loader::LoadedProject;
+use crate::report::{Report, Rule, RuleKind};
+use super::{findings, matches_any};
+
+/// Detector for unused inputs, unassigned outputs and unused temporaries.
+pub struct UnusedVariablesDetector<'a> {
+    config: &'a UnusedVariablesConfig,
+}
+
+impl<'a> UnusedVariablesDetector<'a> {
+    /// Create a new unused variables detector with the given configuration.
+    pub fn new(config: &'a UnusedVariablesConfig) -> Self {
+        Self { config }
+    }
+
+    /// Run detection on ST units and add findings to the report.
+    pub fn detect(&self, st_units: &StUnits, report: &mut Report) {
+        if !self.config.enabled {
+            return;
+        }
+
+        for unit in &st_units.units {
+            // Skip if matches ignore pattern
+            if self.matches_ignore_pattern(&unit.name) {
+                continue;
+            }
+
+            // Need a parsed POU to analyze; scan-mode routines don't own the interface
+            let Some(ref pou) = unit.pou else {
+                continue;
+            };
+            if !unit.main_body {
+                continue;
+            }
+
+            for diagnostic in check_unused_variables(pou) {
+                let kind = match diagnostic.kind {
+                    DiagnosticKind::UnusedInput { .. } if self.config.check_inputs => RuleKind::UnusedInput,
+                    DiagnosticKind::UnassignedOutput { .. } if self.config.check_outputs => {
+                        RuleKind::UnassignedOutput
+                    }
+                    DiagnosticKind::UnusedTemp { .. } if self.config.check_temps => RuleKind::UnusedTemp,
+                    _ => continue,
+                };
+                report.add(Rule::new(
+                    kind,
+                    diagnostic.severity.into(),
+                    unit.location.clone(),
+                    unit.name.clone(),
+                    // Declarations are synthesized, so there is no source line
+                    format!("Routine '{}': {}", unit.name, diagnostic.kind),
+                )
+                .in_routine(&unit.name));
+            }
+        }
+    }
+
+    /// Check if a routine name matches any ignore pattern.
+    fn matches_ignore_pattern(&self, routine_name: &str) -> bool {
+        matches_any(&self.config.ignore_patterns, routine_name)
+    }
+}
+
+impl Detector for UnusedVariablesDetector<'_> {
+    fn detect(&self, project: &LoadedProject) -> Vec<Rule> {
+        findings(|report| self.detect(&StUnits::from_project(project), report))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::Severity;
+
+    fn detect(xml: &str, config: &UnusedVariablesConfig) -> Report {
+        let project = LoadedProject::from_str(xml, None).expect("Should parse");
+        let st_units = match (&project.l5x_controller, &project.plcopen_project) {
+            (Some(controller), _) => StUnits::from_controller(controller),
+            (_, Some(plcopen)) => StUnits::from_plcopen(plcopen),
+            _ => panic!("No project loaded"),
+        };
+        let mut report = Report::new();
+        UnusedVariablesDetector::new(config).detect(&st_units, &mut report);
+        report
+    }
+
+    const PLCOPEN_FB: &str = r#"<?xml version="1.0"?>
+        <project xmlns="http://www.plcopen.org/xml/tc6_0200">
+            <fileHeader companyName="Test" productName="TestProject" productVersion="1.0" creationDateTime="2024-01-01T00:00:00"/>
+            <contentHeader name="Test"/>
+            <types>
+                <pous>
+                    <pou name="Valve" pouType="functionBlock">
+                        <interface>
+                            <inputVars>
+                                <variable name="Open"><type><BOOL/></type></variable>
+                                <variable name="Spare"><type><BOOL/></type></variable>
+                            </inputVars>
+                            <outputVars>
+                                <variable name="IsOpen"><type><BOOL/></type></variable>
+                                <variable name="Fault"><type><BOOL/></type></variable>
+                            </outputVars>
+                            <tempVars>
+                                <variable name="Scratch"><type><INT/></type></variable>
+                            </tempVars>
+                        </interface>
+                        <body><ST><![CDATA[IsOpen := Open;]]></ST></body>
+                    </pou>
+                </pous>
+            </types>
+        </project>"#;
+
+    #[test]
+    fn test_block_kinds_map_to_rules() {
+        let report = detect(PLCOPEN_FB, &UnusedVariablesConfig::default());
+        let mut found: Vec<_> = report.rules.iter().map(|r| (r.kind.clone(), r.severity)).collect();
+        found.sort_by_key(|(kind, _)| kind.code());
+        assert_eq!(
+            found,
+            vec![
+                (RuleKind::UnusedInput, Severity::Info),
+                (RuleKind::UnassignedOutput, Severity::Warning),
+                (RuleKind::UnusedTemp, Severity::Warning),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unused_inputs_can_be_disabled() {
+        let config = UnusedVariablesConfig {
+            check_inputs: false,
+            ..UnusedVariablesConfig::default()
+        };
+        let report = detect(PLCOPEN_FB, &config);
+        assert_eq!(report.rules.len(), 2);
+        assert!(report.rules.iter().all(|r| r.kind != RuleKind::UnusedInput));
+        assert!(report.rules.iter().any(|r| r.message == "Routine 'Valve': output 'Fault' is never assigned"));
+    }
+
+    #[test]
+    fn test_aoi_logic_routine_only() {
+        let xml = r#"<?xml version="1.0"?>
+        <RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+            <Controller Name="Test">
+                <AddOnInstructionDefinitions>
+                    <AddOnInstructionDefinition Name="Scale">
+                        <Parameters>
+                            <Parameter Name="EnableIn" TagType="Base" DataType="BOOL" Usage="Input"/>
+                            <Parameter Name="EnableOut" TagType="Base" DataType="BOOL" Usage="Output"/>
+                            <Parameter Name="Raw" TagType="Base" DataType="REAL" Usage="Input"/>
+                            <Parameter Name="Scaled" TagType="Base" DataType="REAL" Usage="Output"/>
+                        </Parameters>
+                        <Routines>
+                            <Routine Name="Logic" Type="ST">
+                                <STContent><Line Number="0"><![CDATA[Scaled := Raw * 2.0;]]></Line></STContent>
+                            </Routine>
+                            <Routine Name="Prescan" Type="ST">
+                                <STContent><Line Number="0"><![CDATA[Raw := 0.0;]]></Line></STContent>
+                            </Routine>
+                        </Routines>
+                    </AddOnInstructionDefinition>
+                </AddOnInstructionDefinitions>
+            </Controller>
+        </RSLogix5000Content>"#;
+        let report = detect(xml, &UnusedVariablesConfig::default());
+        assert!(report.rules.is_empty(), "{:?}", report.rules);
+    }
+}
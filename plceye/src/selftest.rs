@@ -0,0 +1,213 @@
+//! Corpus self-test: load and analyze every project file of a folder.
+//!
+//! [`Corpus::run`] walks a folder and its subfolders, loads each L5X and
+//! PLCopen file (also gzip-compressed) and runs the rules on it, catching
+//! panics. The result tells users how well plceye reads their own files
+//! before they rely on its findings, and shows regressions when a new
+//! export variant breaks loading. `.xml` files that are not PLCopen
+//! projects are skipped; ZIP archives and bundles are not expanded.
+
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+
+use crate::detector::RuleDetector;
+use crate::loader::{detect_file_format, FileFormat};
+
+/// Result of loading and analyzing one corpus file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CorpusOutcome {
+    /// Loaded and analyzed
+    Passed,
+    /// Loading or analysis returned an error
+    Failed(String),
+    /// Loading or analysis panicked, with the panic message
+    Panicked(String),
+}
+
+/// One file of a corpus run.
+#[derive(Debug, Clone)]
+pub struct CorpusFile {
+    /// Path of the file
+    pub path: PathBuf,
+    /// Detected format, `None` if it could not be read or recognized
+    pub format: Option<FileFormat>,
+    /// How loading and analysis went
+    pub outcome: CorpusOutcome,
+}
+
+impl CorpusFile {
+    /// Whether the file was loaded and analyzed.
+    pub fn passed(&self) -> bool {
+        self.outcome == CorpusOutcome::Passed
+    }
+}
+
+/// File counts of one format in a corpus run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatCounts {
+    /// Format, `None` for unrecognized files
+    pub format: Option<FileFormat>,
+    /// Files of the format
+    pub files: usize,
+    /// Files loaded and analyzed
+    pub passed: usize,
+    /// Files that failed with an error
+    pub failed: usize,
+    /// Files that panicked
+    pub panicked: usize,
+}
+
+/// Results of loading and analyzing every project file of a folder.
+#[derive(Debug, Clone, Default)]
+pub struct Corpus {
+    /// Files in path order
+    pub files: Vec<CorpusFile>,
+}
+
+impl Corpus {
+    /// Load and analyze the L5X and PLCopen files of `dir` and its
+    /// subfolders with `detector`.
+    pub fn run(dir: &Path, detector: &RuleDetector) -> Self {
+        let mut paths = Vec::new();
+        collect_corpus_files(dir, &mut paths);
+        paths.sort();
+
+        let files = paths
+            .into_iter()
+            .filter_map(|path| {
+                let format = detect_file_format(&path);
+                // Plain .xml files are only corpus files if they are PLCopen projects
+                if format.is_none() && has_extension(&path, "xml") {
+                    return None;
+                }
+                let outcome = match panic::catch_unwind(AssertUnwindSafe(|| detector.analyze_file(&path))) {
+                    Ok(Ok(_)) => CorpusOutcome::Passed,
+                    Ok(Err(e)) => CorpusOutcome::Failed(e.to_string()),
+                    Err(payload) => CorpusOutcome::Panicked(panic_message(payload.as_ref())),
+                };
+                Some(CorpusFile { path, format, outcome })
+            })
+            .collect();
+        Corpus { files }
+    }
+
+    /// Counts per format: L5X, PLCopen, then unrecognized files; formats
+    /// without files are left out.
+    pub fn counts(&self) -> Vec<FormatCounts> {
+        [Some(FileFormat::L5x), Some(FileFormat::PlcOpen), None]
+            .into_iter()
+            .map(|format| {
+                let mut counts = FormatCounts {
+                    format,
+                    files: 0,
+                    passed: 0,
+                    failed: 0,
+                    panicked: 0,
+                };
+                for file in self.files.iter().filter(|f| f.format == format) {
+                    counts.files += 1;
+                    match file.outcome {
+                        CorpusOutcome::Passed => counts.passed += 1,
+                        CorpusOutcome::Failed(_) => counts.failed += 1,
+                        CorpusOutcome::Panicked(_) => counts.panicked += 1,
+                    }
+                }
+                counts
+            })
+            .filter(|counts| counts.files > 0)
+            .collect()
+    }
+
+    /// Files that failed or panicked.
+    pub fn failures(&self) -> impl Iterator<Item = &CorpusFile> {
+        self.files.iter().filter(|f| !f.passed())
+    }
+
+    /// Number of files loaded and analyzed.
+    pub fn passed(&self) -> usize {
+        self.files.iter().filter(|f| f.passed()).count()
+    }
+}
+
+/// Collect the `.l5x` and `.xml` files, also gzip-compressed, of a folder
+/// and its subfolders.
+fn collect_corpus_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.filter_map(|entry| entry.ok().map(|e| e.path())) {
+        if path.is_dir() {
+            collect_corpus_files(&path, files);
+        } else {
+            let inner = if has_extension(&path, "gz") { path.with_extension("") } else { path.clone() };
+            if has_extension(&inner, "l5x") || has_extension(&inner, "xml") {
+                files.push(path);
+            }
+        }
+    }
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| e.eq_ignore_ascii_case(extension))
+}
+
+/// Text of a panic payload (`panic!` with a literal or a formatted message).
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "panic with a non-string payload".to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_corpus_run() {
+        let dir = std::env::temp_dir().join(format!("plceye_corpus_{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("line")).unwrap();
+        std::fs::write(
+            dir.join("line/good.L5X"),
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+<RSLogix5000Content SchemaRevision="1.0" SoftwareRevision="32.00">
+<Controller Name="Test"><Programs/></Controller>
+</RSLogix5000Content>"#,
+        )
+        .unwrap();
+        std::fs::write(dir.join("broken.L5X"), "<RSLogix5000Content><Controller").unwrap();
+        std::fs::write(dir.join("settings.xml"), "<settings/>").unwrap();
+        std::fs::write(dir.join("notes.txt"), "not a project").unwrap();
+
+        let corpus = Corpus::run(&dir, &RuleDetector::new());
+        std::fs::remove_dir_all(&dir).ok();
+
+        assert_eq!(corpus.files.len(), 2);
+        assert_eq!(corpus.passed(), 1);
+        assert_eq!(
+            corpus.counts(),
+            [FormatCounts {
+                format: Some(FileFormat::L5x),
+                files: 2,
+                passed: 1,
+                failed: 1,
+                panicked: 0,
+            }]
+        );
+        let failures: Vec<_> = corpus.failures().collect();
+        assert_eq!(failures.len(), 1);
+        assert!(failures[0].path.ends_with("broken.L5X"));
+        assert!(matches!(failures[0].outcome, CorpusOutcome::Failed(_)));
+    }
+
+    #[test]
+    fn test_panic_message() {
+        let payload = panic::catch_unwind(|| panic!("bad {}", "file")).unwrap_err();
+        assert_eq!(panic_message(payload.as_ref()), "bad file");
+    }
+}
@@ -0,0 +1,108 @@
+//! Watch mode: re-run the analysis when input files change.
+
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::mpsc;
+use std::time::Duration;
+
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use crate::is_project_file;
+
+/// Quiet period after the last change before re-analyzing, so that a burst
+/// of saves triggers a single run.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Run `analyze` now and again after every change to `paths`.
+///
+/// Files are watched through their parent directory because editors often
+/// save by replacing the file. Directories are watched recursively for L5X
+/// and XML files, and for `.ACD` files with `acd`. On a terminal the screen
+/// is cleared before each run; `color` styles the PASS/FAIL status. Only
+/// returns if the watcher cannot be started or stops.
+pub fn run(paths: &[PathBuf], acd: bool, color: bool, mut analyze: impl FnMut() -> ExitCode) -> ExitCode {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(e) => {
+            eprintln!("Error: Failed to start file watcher: {}", e);
+            return ExitCode::from(2);
+        }
+    };
+
+    let targets: Vec<PathBuf> = paths
+        .iter()
+        .map(|path| std::fs::canonicalize(path).unwrap_or_else(|_| path.clone()))
+        .collect();
+
+    for target in &targets {
+        let result = if target.is_dir() {
+            watcher.watch(target, RecursiveMode::Recursive)
+        } else {
+            let parent = target.parent().filter(|p| !p.as_os_str().is_empty());
+            watcher.watch(parent.unwrap_or(Path::new(".")), RecursiveMode::NonRecursive)
+        };
+        if let Err(e) = result {
+            eprintln!("Error: Cannot watch {}: {}", target.display(), e);
+            return ExitCode::from(2);
+        }
+    }
+
+    let terminal = std::io::stdout().is_terminal();
+    loop {
+        if terminal {
+            clear_screen();
+        }
+        let passed = analyze() == ExitCode::SUCCESS;
+        print_status(passed, color);
+
+        // Wait for a change to one of the inputs
+        loop {
+            match rx.recv() {
+                Ok(Ok(event)) if is_relevant(&event, &targets, acd) => break,
+                Ok(_) => continue,
+                Err(_) => return ExitCode::from(2),
+            }
+        }
+
+        // Debounce: wait until the changes settle
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+    }
+}
+
+/// Check if an event changes one of the watched inputs.
+fn is_relevant(event: &Event, targets: &[PathBuf], acd: bool) -> bool {
+    if !matches!(
+        event.kind,
+        EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+    ) {
+        return false;
+    }
+
+    event.paths.iter().any(|path| {
+        targets.iter().any(|target| {
+            if target.is_dir() {
+                path.starts_with(target) && is_project_file(path, acd)
+            } else {
+                path == target
+            }
+        })
+    })
+}
+
+fn clear_screen() {
+    print!("\x1b[2J\x1b[H");
+    let _ = std::io::stdout().flush();
+}
+
+fn print_status(passed: bool, color: bool) {
+    println!();
+    let status = match (passed, color) {
+        (true, true) => "\x1b[1;32m✔ PASS\x1b[0m",
+        (false, true) => "\x1b[1;31m✘ FAIL\x1b[0m",
+        (true, false) => "PASS",
+        (false, false) => "FAIL",
+    };
+    println!("{}  Watching for changes... (Ctrl+C to stop)", status);
+}
@@ -0,0 +1,369 @@
+//! Minimal ZIP archive reader.
+//!
+//! Reads the central directory and extracts entries stored without
+//! compression or with DEFLATE, which covers archives written by common
+//! tools. ZIP64, encryption and other compression methods are rejected.
+
+use crate::error::{BundleErrorKind, DecompressErrorKind};
+
+/// Extract the file entries of a ZIP archive as (name, content) pairs, in
+/// archive order. Directory entries are skipped, and archives whose entries
+/// are larger than `max_size` bytes once decompressed, alone or together,
+/// are rejected.
+pub(crate) fn read_entries(bytes: &[u8], max_size: usize) -> Result<Vec<(String, Vec<u8>)>, BundleErrorKind> {
+    let eocd = find_end_of_central_directory(bytes).ok_or(BundleErrorKind::InvalidArchive)?;
+    let count = u16_at(bytes, eocd + 10)? as usize;
+    let mut offset = u32_at(bytes, eocd + 16)? as usize;
+
+    let mut entries = Vec::with_capacity(count);
+    let mut total = 0;
+    for _ in 0..count {
+        if u32_at(bytes, offset)? != 0x0201_4b50 {
+            return Err(BundleErrorKind::InvalidArchive);
+        }
+        let flags = u16_at(bytes, offset + 8)?;
+        let method = u16_at(bytes, offset + 10)?;
+        let compressed_size = u32_at(bytes, offset + 20)?;
+        let size = u32_at(bytes, offset + 24)?;
+        let name_len = u16_at(bytes, offset + 28)? as usize;
+        let extra_len = u16_at(bytes, offset + 30)? as usize;
+        let comment_len = u16_at(bytes, offset + 32)? as usize;
+        let header = u32_at(bytes, offset + 42)? as usize;
+        let name = bytes
+            .get(offset + 46..offset + 46 + name_len)
+            .ok_or(BundleErrorKind::InvalidArchive)?;
+        let name = String::from_utf8_lossy(name).into_owned();
+        offset += 46 + name_len + extra_len + comment_len;
+
+        if name.ends_with('/') {
+            continue;
+        }
+        if flags & 1 != 0 || compressed_size == u32::MAX || size == u32::MAX {
+            return Err(BundleErrorKind::UnsupportedEntry { name });
+        }
+
+        // Local header: fixed part, then its own name and extra field
+        if u32_at(bytes, header)? != 0x0403_4b50 {
+            return Err(BundleErrorKind::InvalidArchive);
+        }
+        let start = header + 30 + u16_at(bytes, header + 26)? as usize + u16_at(bytes, header + 28)? as usize;
+        let data = bytes
+            .get(start..start + compressed_size as usize)
+            .ok_or(BundleErrorKind::InvalidArchive)?;
+        // Only the rest of the limit is left for this entry
+        let too_large = |name| match total {
+            0 => BundleErrorKind::EntryTooLarge { name, limit: max_size },
+            _ => BundleErrorKind::TooLarge { limit: max_size },
+        };
+        let remaining = max_size - total;
+        let content = match method {
+            0 if data.len() > remaining => return Err(too_large(name)),
+            0 => data.to_vec(),
+            8 => match inflate(data, remaining) {
+                Ok(content) => content,
+                Err(DecompressErrorKind::TooLarge { .. }) => return Err(too_large(name)),
+                Err(_) => return Err(BundleErrorKind::InvalidArchive),
+            },
+            _ => return Err(BundleErrorKind::UnsupportedEntry { name }),
+        };
+        total += content.len();
+        entries.push((name, content));
+    }
+    Ok(entries)
+}
+
+/// Offset of the end of central directory record, searched from the end
+/// past a trailing archive comment.
+fn find_end_of_central_directory(bytes: &[u8]) -> Option<usize> {
+    let last = bytes.len().checked_sub(22)?;
+    let first = last.saturating_sub(u16::MAX as usize);
+    (first..=last).rev().find(|&i| bytes[i..i + 4] == [0x50, 0x4b, 0x05, 0x06])
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Result<u16, BundleErrorKind> {
+    let b = bytes.get(offset..offset + 2).ok_or(BundleErrorKind::InvalidArchive)?;
+    Ok(u16::from_le_bytes([b[0], b[1]]))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Result<u32, BundleErrorKind> {
+    let b = bytes.get(offset..offset + 4).ok_or(BundleErrorKind::InvalidArchive)?;
+    Ok(u32::from_le_bytes([b[0], b[1], b[2], b[3]]))
+}
+
+// ============================================================================
+// DEFLATE (RFC 1951)
+// ============================================================================
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097,
+    6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13];
+/// Order of the code length code lengths in a dynamic block header.
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Bit reader over a DEFLATE stream, least significant bit first.
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl Bits<'_> {
+    fn read(&mut self, n: u32) -> Option<u32> {
+        while self.count < n {
+            self.buffer |= u32::from(*self.data.get(self.pos)?) << self.count;
+            self.pos += 1;
+            self.count += 8;
+        }
+        let value = self.buffer & ((1u32 << n) - 1);
+        self.buffer >>= n;
+        self.count -= n;
+        Some(value)
+    }
+
+    /// Drop the bits up to the next byte boundary.
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+/// Canonical Huffman code: number of codes per length and the symbols
+/// ordered by code.
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Self {
+        let mut counts = [0u16; 16];
+        for &len in lengths {
+            counts[len as usize] += 1;
+        }
+        counts[0] = 0;
+        let mut offsets = [0u16; 16];
+        for len in 1..16 {
+            offsets[len] = offsets[len - 1] + counts[len - 1];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, &len) in lengths.iter().enumerate() {
+            if len != 0 {
+                symbols[offsets[len as usize] as usize] = symbol as u16;
+                offsets[len as usize] += 1;
+            }
+        }
+        Self { counts, symbols }
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Option<u16> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= bits.read(1)? as i32;
+            let count = i32::from(self.counts[len]);
+            if code - first < count {
+                return self.symbols.get((index + code - first) as usize).copied();
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+        None
+    }
+}
+
+/// Decompress a raw DEFLATE stream of at most `max_size` bytes of output,
+/// so a small crafted stream can't exhaust memory.
+pub(crate) fn inflate(data: &[u8], max_size: usize) -> Result<Vec<u8>, DecompressErrorKind> {
+    let mut out = Vec::new();
+    match inflate_into(data, &mut out, max_size) {
+        Some(()) => Ok(out),
+        None if out.len() > max_size => Err(DecompressErrorKind::TooLarge { limit: max_size }),
+        None => Err(DecompressErrorKind::InvalidStream),
+    }
+}
+
+/// Decompress the blocks of a DEFLATE stream into `out`, stopping once it
+/// exceeds `max_size` bytes.
+fn inflate_into(data: &[u8], out: &mut Vec<u8>, max_size: usize) -> Option<()> {
+    let mut bits = Bits { data, pos: 0, buffer: 0, count: 0 };
+    loop {
+        let last = bits.read(1)? == 1;
+        match bits.read(2)? {
+            0 => {
+                bits.align();
+                let header = bits.data.get(bits.pos..bits.pos + 4)?;
+                let len = u16::from_le_bytes([header[0], header[1]]) as usize;
+                bits.pos += 4;
+                out.extend_from_slice(bits.data.get(bits.pos..bits.pos + len)?);
+                bits.pos += len;
+            }
+            1 => {
+                let mut lengths = [8u8; 288];
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                inflate_block(&mut bits, out, max_size, &Huffman::new(&lengths), &Huffman::new(&[5u8; 30]))?;
+            }
+            2 => {
+                let (literals, distances) = dynamic_codes(&mut bits)?;
+                inflate_block(&mut bits, out, max_size, &literals, &distances)?;
+            }
+            _ => return None,
+        }
+        if out.len() > max_size {
+            return None;
+        }
+        if last {
+            return Some(());
+        }
+    }
+}
+
+/// Read the literal/length and distance codes of a dynamic block.
+fn dynamic_codes(bits: &mut Bits) -> Option<(Huffman, Huffman)> {
+    let literal_count = bits.read(5)? as usize + 257;
+    let distance_count = bits.read(5)? as usize + 1;
+    let code_count = bits.read(4)? as usize + 4;
+
+    let mut code_lengths = [0u8; 19];
+    for &index in &CODE_LENGTH_ORDER[..code_count] {
+        code_lengths[index] = bits.read(3)? as u8;
+    }
+    let code_lengths = Huffman::new(&code_lengths);
+
+    let mut lengths = Vec::with_capacity(literal_count + distance_count);
+    while lengths.len() < literal_count + distance_count {
+        let (value, repeat) = match code_lengths.decode(bits)? {
+            symbol @ 0..=15 => (symbol as u8, 1),
+            16 => (*lengths.last()?, 3 + bits.read(2)?),
+            17 => (0, 3 + bits.read(3)?),
+            18 => (0, 11 + bits.read(7)?),
+            _ => return None,
+        };
+        lengths.extend(std::iter::repeat(value).take(repeat as usize));
+    }
+    if lengths.len() != literal_count + distance_count {
+        return None;
+    }
+    Some((Huffman::new(&lengths[..literal_count]), Huffman::new(&lengths[literal_count..])))
+}
+
+/// Decode the symbols of a compressed block up to its end-of-block code.
+fn inflate_block(
+    bits: &mut Bits,
+    out: &mut Vec<u8>,
+    max_size: usize,
+    literals: &Huffman,
+    distances: &Huffman,
+) -> Option<()> {
+    loop {
+        if out.len() > max_size {
+            return None;
+        }
+        let symbol = literals.decode(bits)? as usize;
+        match symbol {
+            0..=255 => out.push(symbol as u8),
+            256 => return Some(()),
+            _ => {
+                let index = symbol - 257;
+                let len = *LENGTH_BASE.get(index)? as usize + bits.read(u32::from(LENGTH_EXTRA[index]))? as usize;
+                let index = distances.decode(bits)? as usize;
+                let distance = *DIST_BASE.get(index)? as usize + bits.read(u32::from(DIST_EXTRA[index]))? as usize;
+                let start = out.len().checked_sub(distance)?;
+                for i in 0..len {
+                    out.push(out[start + i]);
+                }
+            }
+        }
+    }
+}
+
+/// Build a ZIP archive with stored entries (for tests).
+#[cfg(test)]
+pub(crate) fn stored_zip(files: &[(&str, &str)]) -> Vec<u8> {
+    let mut zip = Vec::new();
+    let mut central = Vec::new();
+    for (name, content) in files {
+        let header = zip.len() as u32;
+        let size = (content.len() as u32).to_le_bytes();
+        zip.extend_from_slice(&[0x50, 0x4b, 0x03, 0x04, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        zip.extend_from_slice(&size);
+        zip.extend_from_slice(&size);
+        zip.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        zip.extend_from_slice(&[0, 0]);
+        zip.extend_from_slice(name.as_bytes());
+        zip.extend_from_slice(content.as_bytes());
+
+        central.extend_from_slice(&[0x50, 0x4b, 0x01, 0x02, 20, 0, 20, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+        central.extend_from_slice(&size);
+        central.extend_from_slice(&size);
+        central.extend_from_slice(&(name.len() as u16).to_le_bytes());
+        central.extend_from_slice(&[0; 12]);
+        central.extend_from_slice(&header.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+    let offset = zip.len() as u32;
+    zip.extend_from_slice(&central);
+    zip.extend_from_slice(&[0x50, 0x4b, 0x05, 0x06, 0, 0, 0, 0]);
+    zip.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    zip.extend_from_slice(&(files.len() as u16).to_le_bytes());
+    zip.extend_from_slice(&(central.len() as u32).to_le_bytes());
+    zip.extend_from_slice(&offset.to_le_bytes());
+    zip.extend_from_slice(&[0, 0]);
+    zip
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stored_entries() {
+        let zip = stored_zip(&[("Programs/Main.L5X", "<Main/>"), ("Types.L5X", "<Types/>")]);
+        let entries = read_entries(&zip, usize::MAX).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].0, "Programs/Main.L5X");
+        assert_eq!(entries[1].1, b"<Types/>");
+        assert_eq!(read_entries(b"not a zip", usize::MAX), Err(BundleErrorKind::InvalidArchive));
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let zip = stored_zip(&[("Main.L5X", "<Main/>"), ("Types.L5X", "<Types/>")]);
+        assert!(read_entries(&zip, 15).is_ok());
+        assert_eq!(read_entries(&zip, 14), Err(BundleErrorKind::TooLarge { limit: 14 }));
+        assert_eq!(
+            read_entries(&zip, 6),
+            Err(BundleErrorKind::EntryTooLarge {
+                name: "Main.L5X".to_string(),
+                limit: 6
+            })
+        );
+    }
+
+    #[test]
+    fn test_inflate() {
+        // Fixed Huffman block
+        let fixed = [0x0b, 0xc9, 0xc8, 0x2c, 0x56, 0x00, 0xa2, 0x44, 0x85, 0x92, 0xd4, 0xe2, 0x12, 0x00];
+        assert_eq!(inflate(&fixed, usize::MAX).unwrap(), b"This is a test");
+
+        // Dynamic Huffman block with back-references
+        let text: String = (0..14).map(|i| format!("XIC(Start_{})OTE(Motor_{});", i, i % 7)).collect();
+        let dynamic = [
+            0x75, 0xce, 0x21, 0x0e, 0x80, 0x30, 0x10, 0x44, 0xd1, 0xeb, 0x14, 0xd7, 0xe9, 0xb6, 0x05, 0x82, 0x24,
+            0x08, 0x04, 0x41, 0x80, 0xa8, 0x23, 0x9c, 0xa0, 0x49, 0xb3, 0xf7, 0x0f, 0x28, 0x32, 0x88, 0x75, 0x3f,
+            0x79, 0xe6, 0x97, 0x75, 0x76, 0x87, 0xde, 0x4d, 0x2f, 0xdf, 0xed, 0xe7, 0xe2, 0xb6, 0xaa, 0xb5, 0xbd,
+            0x3d, 0x95, 0x0f, 0x40, 0x00, 0x86, 0x40, 0x10, 0x18, 0x84, 0x40, 0x18, 0x22, 0x41, 0x64, 0x48, 0x04,
+            0x89, 0x21, 0x13, 0x64, 0x86, 0xde, 0xda, 0x1d, 0xac, 0xdd, 0xd1, 0xda, 0x85, 0xb7, 0x7e, 0x01, 0x6b,
+            0x18, 0xc1, 0x3a, 0x86, 0xfc, 0x97, 0x1f,
+        ];
+        assert_eq!(inflate(&dynamic, usize::MAX).unwrap(), text.as_bytes());
+        assert_eq!(inflate(&dynamic, 100), Err(DecompressErrorKind::TooLarge { limit: 100 }));
+    }
+}
@@ -0,0 +1,385 @@
+//! Diagnostics of ST and SCL documents.
+//!
+//! ST documents are split into blocks (`PROGRAM`, `FUNCTION`,
+//! `FUNCTION_BLOCK` and `TYPE`, up to the matching `END_*` keyword) that are
+//! parsed on their own, so a syntax error only loses its block and an edit
+//! only reparses the blocks whose text changed. The parsed POUs are run
+//! through the `iecst` checks with the document's types and function return
+//! types. A document without blocks is parsed as a statement list.
+//!
+//! SCL documents are parsed with `plcscl`'s block recovery; the parse errors
+//! and the label checks are reported.
+
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use iecst::{Pou, PouKind, Span, Token, Type, TypeDecl};
+
+use crate::line_index::{LineIndex, Range};
+
+/// Severity of a finding, numbered as in LSP.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error = 1,
+    Warning = 2,
+    Information = 3,
+    Hint = 4,
+}
+
+impl From<iecst::Severity> for Severity {
+    fn from(severity: iecst::Severity) -> Self {
+        match severity {
+            iecst::Severity::Error => Severity::Error,
+            iecst::Severity::Warning => Severity::Warning,
+            iecst::Severity::Hint => Severity::Hint,
+        }
+    }
+}
+
+/// A diagnostic ready to publish.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Finding {
+    pub range: Range,
+    pub severity: Severity,
+    /// Producing library ("iecst" or "plcscl")
+    pub source: &'static str,
+    pub message: String,
+}
+
+/// Language of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    /// IEC 61131-3 Structured Text
+    St,
+    /// Siemens SCL
+    Scl,
+}
+
+impl Language {
+    /// Language of a document from its LSP language id, or else from the
+    /// extension of its URI. Anything not SCL is analyzed as ST.
+    pub fn detect(uri: &str, language_id: Option<&str>) -> Self {
+        let scl = match language_id {
+            Some(id) if !id.is_empty() => id.eq_ignore_ascii_case("scl"),
+            _ => uri.rsplit('.').next().is_some_and(|ext| ext.eq_ignore_ascii_case("scl")),
+        };
+        if scl {
+            Language::Scl
+        } else {
+            Language::St
+        }
+    }
+}
+
+/// A parsed ST block.
+#[derive(Debug)]
+enum Block {
+    Pou(Pou),
+    Types(Vec<TypeDecl>),
+    Error(iecst::ParseError),
+}
+
+/// Analyzer state of one document, keeping parsed ST blocks between edits.
+#[derive(Debug)]
+pub struct Analyzer {
+    language: Language,
+    /// Parsed blocks by block source
+    cache: HashMap<String, Rc<Block>>,
+}
+
+impl Analyzer {
+    pub fn new(language: Language) -> Self {
+        Self { language, cache: HashMap::new() }
+    }
+
+    pub fn language(&self) -> Language {
+        self.language
+    }
+
+    /// Number of parsed blocks kept for the next analysis.
+    pub fn cached_blocks(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Analyze the current text of the document.
+    pub fn analyze(&mut self, text: &str) -> Vec<Finding> {
+        let index = LineIndex::new(text);
+        let mut findings = match self.language {
+            Language::St => self.analyze_st(text, &index),
+            Language::Scl => analyze_scl(text, &index),
+        };
+        findings.sort_by_key(|f| (f.range.start, f.range.end));
+        findings.dedup();
+        findings
+    }
+
+    fn analyze_st(&mut self, text: &str, index: &LineIndex) -> Vec<Finding> {
+        let spans = st_blocks(text);
+        if spans.is_empty() {
+            self.cache.clear();
+            return analyze_statements(text, index);
+        }
+
+        let mut cache = HashMap::new();
+        let blocks: Vec<(usize, Rc<Block>)> = spans
+            .into_iter()
+            .map(|(start, end)| {
+                let source = &text[start..end];
+                let block = match self.cache.get(source) {
+                    Some(block) => Rc::clone(block),
+                    None => Rc::new(parse_st_block(source)),
+                };
+                cache.insert(source.to_string(), Rc::clone(&block));
+                (start, block)
+            })
+            .collect();
+        // Blocks no longer in the document are dropped
+        self.cache = cache;
+
+        let types: Vec<TypeDecl> = blocks
+            .iter()
+            .filter_map(|(_, block)| match block.as_ref() {
+                Block::Types(types) => Some(types.iter().cloned()),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        let functions: HashMap<String, Type> = blocks
+            .iter()
+            .filter_map(|(_, block)| match block.as_ref() {
+                Block::Pou(pou) => Some((pou.name.clone(), Type::from_spec(pou.return_type.as_ref()?))),
+                _ => None,
+            })
+            .collect();
+
+        let mut findings = Vec::new();
+        for (offset, block) in &blocks {
+            let diagnostics = match block.as_ref() {
+                Block::Pou(pou) => check_pou(pou, &types, &functions),
+                Block::Types(types) => {
+                    let mut diagnostics = iecst::check_enum_values(types);
+                    diagnostics.extend(iecst::check_type_ranges(types));
+                    diagnostics
+                }
+                Block::Error(error) => {
+                    findings.push(parse_error(error, *offset, text, index));
+                    continue;
+                }
+            };
+            findings.extend(diagnostics.into_iter().map(|diagnostic| Finding {
+                range: span_range(diagnostic.span, *offset, text, index),
+                severity: diagnostic.severity.into(),
+                source: "iecst",
+                message: diagnostic.kind.to_string(),
+            }));
+        }
+        findings
+    }
+}
+
+/// Run the `iecst` checks on a POU.
+fn check_pou(pou: &Pou, types: &[TypeDecl], functions: &HashMap<String, Type>) -> Vec<iecst::Diagnostic> {
+    let mut diagnostics = iecst::analyze_pou(pou);
+    diagnostics.extend(iecst::check_array_bounds(pou, types));
+    diagnostics.extend(iecst::check_array_ranges(pou));
+    diagnostics.extend(iecst::check_case_coverage(pou, types));
+    diagnostics.extend(iecst::check_case_labels(pou));
+    diagnostics.extend(iecst::check_dead_stores(pou));
+    diagnostics.extend(iecst::check_division_by_zero(pou));
+    diagnostics.extend(iecst::check_empty_bodies(pou));
+    diagnostics.extend(iecst::check_float_equality(pou, functions));
+    diagnostics.extend(iecst::check_loop_variables(pou));
+    diagnostics.extend(iecst::check_redundant_booleans(pou));
+    diagnostics.extend(iecst::check_self_assignments(pou));
+    diagnostics.extend(iecst::check_string_truncation(pou));
+    diagnostics.extend(iecst::check_uninitialized_reads(pou));
+    diagnostics
+}
+
+/// Parse and check a document that is a bare statement list, like the
+/// body of an L5X ST routine. Without declarations only the checks that
+/// need no symbols run.
+fn analyze_statements(text: &str, index: &LineIndex) -> Vec<Finding> {
+    let (body, errors) = iecst::parse_statements_recovering(text);
+    let pou = Pou {
+        kind: PouKind::Program,
+        name: String::new(),
+        return_type: None,
+        var_blocks: Vec::new(),
+        body,
+        span: Span::default(),
+    };
+    let mut diagnostics = iecst::check_case_labels(&pou);
+    diagnostics.extend(iecst::check_division_by_zero(&pou));
+    diagnostics.extend(iecst::check_empty_bodies(&pou));
+    diagnostics.extend(iecst::check_redundant_booleans(&pou));
+    diagnostics.extend(iecst::check_self_assignments(&pou));
+
+    errors
+        .iter()
+        .map(|error| parse_error(error, 0, text, index))
+        .chain(diagnostics.into_iter().map(|diagnostic| Finding {
+            range: span_range(diagnostic.span, 0, text, index),
+            severity: diagnostic.severity.into(),
+            source: "iecst",
+            message: diagnostic.kind.to_string(),
+        }))
+        .collect()
+}
+
+fn parse_st_block(source: &str) -> Block {
+    let first = iecst::tokenize(source, false).into_iter().next().map(|t| t.token);
+    let parsed = if first == Some(Token::Type) {
+        iecst::parse_type_block(source).map(Block::Types)
+    } else {
+        iecst::parse_pou(source).map(Block::Pou)
+    };
+    parsed.unwrap_or_else(Block::Error)
+}
+
+/// Byte spans of the blocks of an ST document.
+///
+/// A block runs from its keyword to the matching end keyword. A block whose
+/// end keyword is missing ends where the next block starts, or at the end of
+/// the text.
+fn st_blocks(text: &str) -> Vec<(usize, usize)> {
+    let mut blocks = Vec::new();
+    let mut open: Option<(usize, Token)> = None;
+    for token in iecst::tokenize(text, false) {
+        let end_token = match token.token {
+            Token::Program => Some(Token::EndProgram),
+            Token::Function => Some(Token::EndFunction),
+            Token::FunctionBlock => Some(Token::EndFunctionBlock),
+            Token::Type => Some(Token::EndType),
+            _ => None,
+        };
+        if let Some(end_token) = end_token {
+            if let Some((start, _)) = open.take() {
+                blocks.push((start, token.span.start));
+            }
+            open = Some((token.span.start, end_token));
+        } else if open.as_ref().is_some_and(|(_, end)| *end == token.token) {
+            if let Some((start, _)) = open.take() {
+                blocks.push((start, token.span.end));
+            }
+        } else if token.token == Token::Eof {
+            if let Some((start, _)) = open.take() {
+                blocks.push((start, text.len()));
+            }
+        }
+    }
+    blocks
+}
+
+fn parse_error(error: &iecst::ParseError, offset: usize, text: &str, index: &LineIndex) -> Finding {
+    Finding {
+        range: span_range(error.span, offset, text, index),
+        severity: Severity::Error,
+        source: "iecst",
+        message: error.kind.to_string(),
+    }
+}
+
+fn span_range(span: Span, offset: usize, text: &str, index: &LineIndex) -> Range {
+    index.range(text, offset + span.start, offset + span.end)
+}
+
+fn analyze_scl(text: &str, index: &LineIndex) -> Vec<Finding> {
+    let result = plcscl::parse_scl_partial(text);
+    let mut findings: Vec<Finding> = result
+        .errors
+        .iter()
+        .map(|error| Finding {
+            range: index.range(text, error.span.0, error.span.1),
+            severity: Severity::Error,
+            source: "plcscl",
+            message: error.kind.to_string(),
+        })
+        .collect();
+
+    let mut reported = HashSet::new();
+    for block in plcscl::scan_labels(text) {
+        let jumps = block.undefined_jumps().into_iter().map(|site| {
+            (site, Severity::Error, format!("GOTO target '{}' is not a label of block {}", site.name, block.block))
+        });
+        let labels = block.unused_labels().into_iter().map(|site| {
+            (site, Severity::Warning, format!("label '{}' is never targeted", site.name))
+        });
+        for (site, severity, message) in jumps.chain(labels) {
+            if reported.insert((site.line, message.clone())) {
+                findings.push(Finding {
+                    range: index.line_range(text, site.line.saturating_sub(1)),
+                    severity,
+                    source: "plcscl",
+                    message,
+                });
+            }
+        }
+    }
+    findings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::line_index::Position;
+
+    #[test]
+    fn test_language_detection() {
+        assert_eq!(Language::detect("file:///a/Main.scl", None), Language::Scl);
+        assert_eq!(Language::detect("file:///a/Main.SCL", Some("")), Language::Scl);
+        assert_eq!(Language::detect("file:///a/Main.scl", Some("iecst")), Language::St);
+        assert_eq!(Language::detect("untitled:1", Some("scl")), Language::Scl);
+        assert_eq!(Language::detect("file:///a/Main.st", None), Language::St);
+    }
+
+    #[test]
+    fn test_st_blocks() {
+        let text = "TYPE E : (A, B); END_TYPE\nPROGRAM Main\nx := 1;\nFUNCTION_BLOCK Fb\nEND_FUNCTION_BLOCK\n";
+        let blocks: Vec<&str> = st_blocks(text).into_iter().map(|(s, e)| &text[s..e]).collect();
+        assert_eq!(
+            blocks,
+            ["TYPE E : (A, B); END_TYPE", "PROGRAM Main\nx := 1;\n", "FUNCTION_BLOCK Fb\nEND_FUNCTION_BLOCK"]
+        );
+    }
+
+    #[test]
+    fn test_st_findings_and_recovery() {
+        let text = "PROGRAM Broken\nVAR x : INT; END_VAR\nx := ;\nEND_PROGRAM\n\
+                    PROGRAM Main\nVAR y : INT; END_VAR\ny := y / 0;\nEND_PROGRAM\n";
+        let mut analyzer = Analyzer::new(Language::St);
+        let findings = analyzer.analyze(text);
+        assert_eq!(findings[0].severity, Severity::Error);
+        assert_eq!(findings[0].range.start.line, 2);
+        let division = findings.iter().find(|f| f.message.contains("division by zero")).expect("division finding");
+        assert_eq!(division.range.start, Position::new(6, 5));
+        assert_eq!(division.source, "iecst");
+        assert_eq!(analyzer.cached_blocks(), 2);
+    }
+
+    #[test]
+    fn test_cached_blocks_follow_the_text() {
+        let mut analyzer = Analyzer::new(Language::St);
+        analyzer.analyze("PROGRAM A\nEND_PROGRAM\nPROGRAM B\nEND_PROGRAM\n");
+        assert_eq!(analyzer.cached_blocks(), 2);
+        analyzer.analyze("PROGRAM A\nEND_PROGRAM\n");
+        assert_eq!(analyzer.cached_blocks(), 1);
+    }
+
+    #[test]
+    fn test_statement_list() {
+        let findings = Analyzer::new(Language::St).analyze("x := x;\ny := ;\n");
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[1].range.start.line, 1);
+    }
+
+    #[test]
+    fn test_scl_findings() {
+        let text = "FUNCTION_BLOCK Jump\nBEGIN\n    GOTO Missing;\nEND_FUNCTION_BLOCK\n\
+                    FUNCTION_BLOCK Bad\nBEGIN\n    x := ;\nEND_FUNCTION_BLOCK\n";
+        let findings = Analyzer::new(Language::Scl).analyze(text);
+        let lines: Vec<(u32, &str)> = findings.iter().map(|f| (f.range.start.line, f.source)).collect();
+        assert_eq!(lines, [(2, "plcscl"), (2, "plcscl"), (6, "plcscl")]);
+        assert!(findings.iter().any(|f| f.message == "GOTO target 'Missing' is not a label of block Jump"));
+    }
+}
@@ -0,0 +1,32 @@
+//! # plclsp
+//!
+//! Language server for IEC 61131-3 Structured Text and Siemens SCL.
+//!
+//! The `plc-lsp` binary speaks LSP over stdin/stdout and publishes the
+//! diagnostics of the `iecst` and `plcscl` analyses for open documents as
+//! they are edited. Completion, hover and the other language features are
+//! not provided.
+//!
+//! - [`Analyzer`] - diagnostics of one ST or SCL document, reparsing only the
+//!   ST blocks that changed
+//! - [`LineIndex`] - byte offsets to LSP line/UTF-16 column positions
+//! - [`Server`] - the JSON-RPC message loop
+//!
+//! ## Example
+//!
+//! ```
+//! use plclsp::{Analyzer, Language};
+//!
+//! let mut analyzer = Analyzer::new(Language::St);
+//! let findings = analyzer.analyze("PROGRAM Main\nVAR x : INT; END_VAR\nx := x / 0;\nEND_PROGRAM\n");
+//! let division = findings.iter().find(|f| f.message == "division by zero").unwrap();
+//! assert_eq!((division.range.start.line, division.range.start.character), (2, 5));
+//! ```
+
+pub mod analysis;
+pub mod line_index;
+pub mod server;
+
+pub use analysis::{Analyzer, Finding, Language, Severity};
+pub use line_index::{LineIndex, Position, Range};
+pub use server::Server;
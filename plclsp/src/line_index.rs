@@ -0,0 +1,127 @@
+//! Byte offset to LSP position conversion.
+//!
+//! The parsers report spans as byte offsets; LSP positions are a 0-based
+//! line and a column counted in UTF-16 code units.
+
+/// A 0-based line and UTF-16 column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+impl Position {
+    pub fn new(line: u32, character: u32) -> Self {
+        Self { line, character }
+    }
+}
+
+/// A range between two positions, end exclusive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+/// Line start offsets of a document.
+#[derive(Debug, Clone)]
+pub struct LineIndex {
+    /// Byte offset of the start of each line
+    starts: Vec<usize>,
+}
+
+impl LineIndex {
+    /// Index the lines of `text`. Lines end at `\n`; a `\r` before it
+    /// belongs to the line.
+    pub fn new(text: &str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { starts }
+    }
+
+    /// Number of lines.
+    pub fn line_count(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Position of a byte offset. Offsets past the end map to the end of
+    /// the text, and offsets inside a character to its start.
+    pub fn position(&self, text: &str, offset: usize) -> Position {
+        let offset = floor_char_boundary(text, offset.min(text.len()));
+        let line = self.starts.partition_point(|&start| start <= offset) - 1;
+        let character: usize = text[self.starts[line]..offset].chars().map(char::len_utf16).sum();
+        Position::new(line as u32, character as u32)
+    }
+
+    /// Range of a byte span.
+    pub fn range(&self, text: &str, start: usize, end: usize) -> Range {
+        Range {
+            start: self.position(text, start),
+            end: self.position(text, end.max(start)),
+        }
+    }
+
+    /// Range of a whole 0-based line, without its line break.
+    pub fn line_range(&self, text: &str, line: usize) -> Range {
+        let line = line.min(self.starts.len() - 1);
+        let start = self.starts[line];
+        let end = self.starts.get(line + 1).map_or(text.len(), |&next| next - 1);
+        let end = if text[start..end].ends_with('\r') { end - 1 } else { end };
+        self.range(text, start, end)
+    }
+
+    /// Byte offset of a position, clamped to its line and to the text.
+    pub fn offset(&self, text: &str, position: Position) -> usize {
+        let Some(&start) = self.starts.get(position.line as usize) else {
+            return text.len();
+        };
+        let end = self.starts.get(position.line as usize + 1).map_or(text.len(), |&next| next - 1);
+        let mut units = 0;
+        for (i, c) in text[start..end].char_indices() {
+            if units >= position.character as usize {
+                return start + i;
+            }
+            units += c.len_utf16();
+        }
+        end
+    }
+}
+
+fn floor_char_boundary(text: &str, mut offset: usize) -> usize {
+    while !text.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_positions() {
+        let text = "ab\r\nc\n\nd";
+        let index = LineIndex::new(text);
+        assert_eq!(index.line_count(), 4);
+        assert_eq!(index.position(text, 0), Position::new(0, 0));
+        assert_eq!(index.position(text, 4), Position::new(1, 0));
+        assert_eq!(index.position(text, 7), Position::new(3, 0));
+        assert_eq!(index.position(text, 100), Position::new(3, 1));
+        assert_eq!(index.line_range(text, 0).end, Position::new(0, 2));
+        assert_eq!(index.line_range(text, 2).end, Position::new(2, 0));
+    }
+
+    #[test]
+    fn test_utf16_columns() {
+        let text = "x := 'ä😀';";
+        let index = LineIndex::new(text);
+        let end = text.len();
+        // 'ä' is one UTF-16 unit, the emoji two
+        assert_eq!(index.position(text, end), Position::new(0, 11));
+        assert_eq!(index.position(text, 8), Position::new(0, 7));
+        assert_eq!(index.offset(text, Position::new(0, 7)), 8);
+        assert_eq!(index.offset(text, Position::new(0, 50)), end);
+        assert_eq!(index.offset(text, Position::new(5, 0)), end);
+    }
+}
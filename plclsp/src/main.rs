@@ -0,0 +1,23 @@
+//! plc-lsp - Language server for Structured Text and SCL
+//!
+//! Speaks LSP over stdin/stdout; configure it as the language server for
+//! `.st` and `.scl` files in the editor.
+
+use std::io;
+use std::process::ExitCode;
+
+use plclsp::Server;
+
+fn main() -> ExitCode {
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    match Server::new().run(&mut stdin.lock(), &mut stdout.lock()) {
+        Ok(true) => ExitCode::SUCCESS,
+        // `exit` without `shutdown`, or the client went away
+        Ok(false) => ExitCode::from(1),
+        Err(e) => {
+            eprintln!("plc-lsp: {}", e);
+            ExitCode::from(1)
+        }
+    }
+}
@@ -0,0 +1,373 @@
+//! Language server over JSON-RPC.
+//!
+//! Messages are framed with a `Content-Length` header, as LSP specifies for
+//! stdio. The server handles `initialize`, `shutdown` and `exit`, and keeps
+//! the open documents from `textDocument/didOpen`, `didChange` (full or
+//! incremental edits) and `didClose`. After each open or change it publishes
+//! the document's diagnostics with `textDocument/publishDiagnostics`; on close
+//! it clears them.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Read, Write};
+
+use serde_json::{json, Value};
+
+use crate::analysis::{Analyzer, Finding, Language};
+use crate::line_index::{LineIndex, Position};
+
+/// JSON-RPC error code for unparsable messages
+const PARSE_ERROR: i64 = -32700;
+/// JSON-RPC error code for unknown request methods
+const METHOD_NOT_FOUND: i64 = -32601;
+/// LSP error code for requests after `shutdown`
+const INVALID_REQUEST: i64 = -32600;
+
+/// Largest message body read into memory, 64 MiB
+pub const MAX_CONTENT_LENGTH: usize = 64 * 1024 * 1024;
+
+/// Incremental text document sync (`TextDocumentSyncKind.Incremental`)
+const SYNC_INCREMENTAL: u8 = 2;
+
+/// An open document.
+struct Document {
+    text: String,
+    version: Option<i64>,
+    analyzer: Analyzer,
+}
+
+/// Language server state.
+#[derive(Default)]
+pub struct Server {
+    documents: HashMap<String, Document>,
+    shutdown: bool,
+}
+
+impl Server {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Serve messages from `input` until `exit` or the end of the input.
+    ///
+    /// Returns whether `shutdown` came before `exit`, which decides the exit
+    /// code of the process.
+    pub fn run(&mut self, input: &mut impl BufRead, output: &mut impl Write) -> io::Result<bool> {
+        while let Some(message) = read_message(input)? {
+            let content = match message {
+                Message::Content(content) => content,
+                Message::TooLarge(length) => {
+                    let error = format!("message of {} bytes exceeds the limit of {} bytes", length, MAX_CONTENT_LENGTH);
+                    write_message(output, &error_response(Value::Null, INVALID_REQUEST, &error))?;
+                    continue;
+                }
+            };
+            let message: Value = match serde_json::from_slice(&content) {
+                Ok(message) => message,
+                Err(e) => {
+                    write_message(output, &error_response(Value::Null, PARSE_ERROR, &e.to_string()))?;
+                    continue;
+                }
+            };
+            if message["method"] == "exit" {
+                return Ok(self.shutdown);
+            }
+            for reply in self.handle(&message) {
+                write_message(output, &reply)?;
+            }
+        }
+        Ok(self.shutdown)
+    }
+
+    /// Handle one message and return the messages to send back.
+    pub fn handle(&mut self, message: &Value) -> Vec<Value> {
+        let method = message["method"].as_str().unwrap_or_default();
+        let params = &message["params"];
+        let Some(id) = message.get("id").cloned() else {
+            return self.notification(method, params);
+        };
+
+        if self.shutdown {
+            return vec![error_response(id, INVALID_REQUEST, "server is shut down")];
+        }
+        let result = match method {
+            "initialize" => json!({
+                "capabilities": {
+                    "textDocumentSync": { "openClose": true, "change": SYNC_INCREMENTAL },
+                },
+                "serverInfo": { "name": "plc-lsp", "version": env!("CARGO_PKG_VERSION") },
+            }),
+            "shutdown" => {
+                self.shutdown = true;
+                Value::Null
+            }
+            _ => return vec![error_response(id, METHOD_NOT_FOUND, &format!("unknown method '{}'", method))],
+        };
+        vec![json!({ "jsonrpc": "2.0", "id": id, "result": result })]
+    }
+
+    fn notification(&mut self, method: &str, params: &Value) -> Vec<Value> {
+        let document = &params["textDocument"];
+        let Some(uri) = document["uri"].as_str() else {
+            return Vec::new();
+        };
+        match method {
+            "textDocument/didOpen" => {
+                let language = Language::detect(uri, document["languageId"].as_str());
+                let text = document["text"].as_str().unwrap_or_default().to_string();
+                self.documents.insert(
+                    uri.to_string(),
+                    Document { text, version: document["version"].as_i64(), analyzer: Analyzer::new(language) },
+                );
+            }
+            "textDocument/didChange" => {
+                let Some(open) = self.documents.get_mut(uri) else {
+                    return Vec::new();
+                };
+                for change in params["contentChanges"].as_array().into_iter().flatten() {
+                    apply_change(&mut open.text, change);
+                }
+                open.version = document["version"].as_i64();
+            }
+            "textDocument/didClose" => {
+                self.documents.remove(uri);
+                return vec![publish(uri, None, &[])];
+            }
+            _ => return Vec::new(),
+        }
+        self.diagnostics(uri).into_iter().collect()
+    }
+
+    /// Analyze an open document and build its `publishDiagnostics`
+    /// notification.
+    fn diagnostics(&mut self, uri: &str) -> Option<Value> {
+        let document = self.documents.get_mut(uri)?;
+        let findings = document.analyzer.analyze(&document.text);
+        Some(publish(uri, document.version, &findings))
+    }
+}
+
+/// Apply a `TextDocumentContentChangeEvent`: replace the whole text, or the
+/// given range of it.
+fn apply_change(text: &mut String, change: &Value) {
+    let Some(new_text) = change["text"].as_str() else {
+        return;
+    };
+    let range = &change["range"];
+    if range.is_null() {
+        *text = new_text.to_string();
+        return;
+    }
+    let index = LineIndex::new(text);
+    let position = |value: &Value| {
+        Position::new(
+            value["line"].as_u64().unwrap_or_default() as u32,
+            value["character"].as_u64().unwrap_or_default() as u32,
+        )
+    };
+    let start = index.offset(text, position(&range["start"]));
+    let end = index.offset(text, position(&range["end"])).max(start);
+    text.replace_range(start..end, new_text);
+}
+
+fn publish(uri: &str, version: Option<i64>, findings: &[Finding]) -> Value {
+    let diagnostics: Vec<Value> = findings
+        .iter()
+        .map(|finding| {
+            json!({
+                "range": {
+                    "start": { "line": finding.range.start.line, "character": finding.range.start.character },
+                    "end": { "line": finding.range.end.line, "character": finding.range.end.character },
+                },
+                "severity": finding.severity as u8,
+                "source": finding.source,
+                "message": finding.message,
+            })
+        })
+        .collect();
+    let mut params = json!({ "uri": uri, "diagnostics": diagnostics });
+    if let Some(version) = version {
+        params["version"] = json!(version);
+    }
+    json!({ "jsonrpc": "2.0", "method": "textDocument/publishDiagnostics", "params": params })
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": { "code": code, "message": message } })
+}
+
+/// One framed message read from the input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Message {
+    /// The message body
+    Content(Vec<u8>),
+    /// Length of a body over [`MAX_CONTENT_LENGTH`], skipped without reading
+    /// it into memory
+    TooLarge(usize),
+}
+
+/// Read one message, or `None` at the end of the input.
+///
+/// A message without a valid `Content-Length` header is an error, as the
+/// stream cannot be resynchronized.
+pub fn read_message(input: &mut impl BufRead) -> io::Result<Option<Message>> {
+    let mut length = None;
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if input.read_line(&mut line)? == 0 {
+            return Ok(None);
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            if length.is_some() {
+                break;
+            }
+            // Blank lines between messages
+            continue;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("Content-Length") {
+                length = value.trim().parse::<usize>().ok();
+            }
+        }
+    }
+    let length = length.ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, "missing Content-Length"))?;
+    if length > MAX_CONTENT_LENGTH {
+        let skipped = io::copy(&mut input.by_ref().take(length as u64), &mut io::sink())?;
+        if skipped < length as u64 {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        return Ok(Some(Message::TooLarge(length)));
+    }
+    let mut content = vec![0; length];
+    input.read_exact(&mut content)?;
+    Ok(Some(Message::Content(content)))
+}
+
+/// Write one message with its `Content-Length` header.
+pub fn write_message(output: &mut impl Write, message: &Value) -> io::Result<()> {
+    let content = message.to_string();
+    write!(output, "Content-Length: {}\r\n\r\n{}", content.len(), content)?;
+    output.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn frame(messages: &[Value]) -> Vec<u8> {
+        let mut input = Vec::new();
+        for message in messages {
+            write_message(&mut input, message).unwrap();
+        }
+        input
+    }
+
+    fn replies(output: &[u8]) -> Vec<Value> {
+        let mut input = output;
+        let mut replies = Vec::new();
+        while let Some(Message::Content(content)) = read_message(&mut input).unwrap() {
+            replies.push(serde_json::from_slice(&content).unwrap());
+        }
+        replies
+    }
+
+    #[test]
+    fn test_session() {
+        let uri = "file:///project/Main.st";
+        let input = frame(&[
+            json!({ "jsonrpc": "2.0", "id": 1, "method": "initialize", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "initialized", "params": {} }),
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": { "textDocument": {
+                "uri": uri, "languageId": "st", "version": 1,
+                "text": "PROGRAM Main\nVAR x : INT; END_VAR\nx := x / 0;\nEND_PROGRAM\n",
+            }}}),
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didChange", "params": {
+                "textDocument": { "uri": uri, "version": 2 },
+                "contentChanges": [{ "range": {
+                    "start": { "line": 2, "character": 9 }, "end": { "line": 2, "character": 10 },
+                }, "text": "2" }],
+            }}),
+            json!({ "jsonrpc": "2.0", "id": 2, "method": "textDocument/hover", "params": {} }),
+            json!({ "jsonrpc": "2.0", "id": 3, "method": "shutdown" }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]);
+        let mut output = Vec::new();
+        let clean = Server::new().run(&mut input.as_slice(), &mut output).unwrap();
+        assert!(clean);
+
+        let replies = replies(&output);
+        assert_eq!(replies.len(), 5);
+        assert_eq!(replies[0]["result"]["capabilities"]["textDocumentSync"]["change"], 2);
+
+        let opened = &replies[1]["params"];
+        assert_eq!(opened["uri"], uri);
+        assert_eq!(opened["version"], 1);
+        let division = opened["diagnostics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|d| d["message"] == "division by zero")
+            .expect("division by zero diagnostic");
+        assert_eq!(division["range"]["start"], json!({ "line": 2, "character": 5 }));
+
+        let changed = &replies[2]["params"];
+        assert_eq!(changed["version"], 2);
+        assert!(changed["diagnostics"].as_array().unwrap().iter().all(|d| d["message"] != "division by zero"));
+
+        assert_eq!(replies[3]["error"]["code"], METHOD_NOT_FOUND);
+        assert_eq!(replies[4]["id"], 3);
+        assert!(replies[4]["result"].is_null());
+    }
+
+    #[test]
+    fn test_full_change_close_and_exit_without_shutdown() {
+        let uri = "file:///project/Main.scl";
+        let input = frame(&[
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didOpen", "params": { "textDocument": {
+                "uri": uri, "text": "FUNCTION_BLOCK A\nBEGIN\nx := ;\nEND_FUNCTION_BLOCK\n",
+            }}}),
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didChange", "params": {
+                "textDocument": { "uri": uri },
+                "contentChanges": [{ "text": "FUNCTION_BLOCK A\nBEGIN\nx := 1;\nEND_FUNCTION_BLOCK\n" }],
+            }}),
+            json!({ "jsonrpc": "2.0", "method": "textDocument/didClose", "params": { "textDocument": { "uri": uri } } }),
+            json!({ "jsonrpc": "2.0", "method": "exit" }),
+        ]);
+        let mut output = Vec::new();
+        let clean = Server::new().run(&mut input.as_slice(), &mut output).unwrap();
+        assert!(!clean);
+
+        let replies = replies(&output);
+        let counts: Vec<usize> =
+            replies.iter().map(|r| r["params"]["diagnostics"].as_array().unwrap().len()).collect();
+        assert_eq!(counts, [1, 0, 0]);
+        assert_eq!(replies[0]["params"]["diagnostics"][0]["source"], "plcscl");
+        assert!(replies[0]["params"].get("version").is_none());
+    }
+
+    #[test]
+    fn test_invalid_json() {
+        let mut input: &[u8] = b"Content-Length: 5\r\n\r\n{oops";
+        let mut output = Vec::new();
+        Server::new().run(&mut input, &mut output).unwrap();
+        assert_eq!(replies(&output)[0]["error"]["code"], PARSE_ERROR);
+    }
+
+    #[test]
+    fn test_oversized_message() {
+        let mut input = format!("Content-Length: {}\r\n\r\n", MAX_CONTENT_LENGTH + 1).into_bytes();
+        input.resize(input.len() + MAX_CONTENT_LENGTH + 1, b' ');
+        input.extend(frame(&[json!({ "jsonrpc": "2.0", "id": 1, "method": "shutdown" })]));
+        let mut output = Vec::new();
+        Server::new().run(&mut input.as_slice(), &mut output).unwrap();
+
+        let replies = replies(&output);
+        assert_eq!(replies.len(), 2);
+        assert_eq!(replies[0]["error"]["code"], INVALID_REQUEST);
+        assert_eq!(replies[1]["id"], 1);
+
+        let mut truncated: &[u8] = b"Content-Length: 999999999999\r\n\r\n{}";
+        assert!(read_message(&mut truncated).is_err());
+    }
+}
@@ -0,0 +1,114 @@
+//! Vendor content of `addData` blocks.
+//!
+//! The schema allows any XML inside `<addData><data>`, and vendor tools
+//! (CODESYS, Beremiz, TIA Portal) store methods, attributes, object IDs and
+//! project structure there. Serde cannot deserialize arbitrary element
+//! names or text mixed with elements, so before parsing the content of each
+//! `data` element is escaped into text: it arrives as raw XML in
+//! [`AddData_data_Inline::text`](crate::AddData_data_Inline). Serializing
+//! writes it back unescaped, so the vendor content survives a roundtrip.
+
+use std::borrow::Cow;
+use std::ops::Range;
+
+use quick_xml::escape::{escape, unescape};
+use quick_xml::events::Event;
+use quick_xml::Reader;
+
+/// Escape the content of every `addData/data` element into text.
+pub(crate) fn capture(xml: &str) -> Cow<'_, str> {
+    rewrite(xml, |content| escape(content).into_owned())
+}
+
+/// Unescape the text of every `addData/data` element back to raw XML.
+pub(crate) fn restore(xml: String) -> String {
+    match rewrite(&xml, |content| unescape(content).map_or_else(|_| content.to_string(), Cow::into_owned)) {
+        Cow::Borrowed(_) => xml,
+        Cow::Owned(restored) => restored,
+    }
+}
+
+/// Replace the content of each `addData/data` element with `f(content)`.
+///
+/// Returns the input unchanged if no `data` element has content, or if the
+/// XML is malformed; parsing then reports the error.
+fn rewrite(xml: &str, f: impl Fn(&str) -> String) -> Cow<'_, str> {
+    let Some(ranges) = content_ranges(xml) else {
+        return Cow::Borrowed(xml);
+    };
+    if ranges.is_empty() {
+        return Cow::Borrowed(xml);
+    }
+
+    let mut result = String::with_capacity(xml.len());
+    let mut copied = 0;
+    for range in ranges {
+        result.push_str(&xml[copied..range.start]);
+        result.push_str(&f(&xml[range.clone()]));
+        copied = range.end;
+    }
+    result.push_str(&xml[copied..]);
+    Cow::Owned(result)
+}
+
+/// Byte ranges of the non-empty content of the `data` children of
+/// `addData` elements, outermost only, or `None` for malformed XML.
+fn content_ranges(xml: &str) -> Option<Vec<Range<usize>>> {
+    let mut reader = Reader::from_str(xml);
+    let mut stack: Vec<Vec<u8>> = Vec::new();
+    // Depth and content start of the `data` element being captured
+    let mut open: Option<(usize, usize)> = None;
+    let mut ranges = Vec::new();
+
+    loop {
+        let before = reader.buffer_position() as usize;
+        match reader.read_event().ok()? {
+            Event::Start(start) => {
+                let name = start.local_name().as_ref().to_vec();
+                if open.is_none()
+                    && name == b"data"
+                    && stack.last().is_some_and(|parent| parent.as_slice() == b"addData")
+                {
+                    open = Some((stack.len(), reader.buffer_position() as usize));
+                }
+                stack.push(name);
+            }
+            Event::End(_) => {
+                stack.pop();
+                if let Some((depth, start)) = open {
+                    if stack.len() == depth {
+                        if !xml[start..before].trim().is_empty() {
+                            ranges.push(start..before);
+                        }
+                        open = None;
+                    }
+                }
+            }
+            Event::Eof => return Some(ranges),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_capture_and_restore() {
+        let xml = r#"<pou><addData><data name="a" handleUnknown="discard">text <Foo x="1">&amp;</Foo><addData><data name="b">y</data></addData></data><data name="c"/></addData></pou>"#;
+        let captured = capture(xml);
+        assert_eq!(
+            captured,
+            r#"<pou><addData><data name="a" handleUnknown="discard">text &lt;Foo x=&quot;1&quot;&gt;&amp;amp;&lt;/Foo&gt;&lt;addData&gt;&lt;data name=&quot;b&quot;&gt;y&lt;/data&gt;&lt;/addData&gt;</data><data name="c"/></addData></pou>"#
+        );
+        assert_eq!(restore(captured.into_owned()), xml);
+    }
+
+    #[test]
+    fn test_unchanged_without_content() {
+        let xml = r#"<pou><addData><data name="c"/></addData><data>not vendor data</data></pou>"#;
+        assert!(matches!(capture(xml), Cow::Borrowed(_)));
+        assert!(matches!(capture("<pou><unclosed></pou>"), Cow::Borrowed(_)));
+    }
+}
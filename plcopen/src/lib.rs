@@ -49,7 +49,8 @@
 
 use quick_xml::de::from_str as xml_from_str;
 use quick_xml::se::to_string as xml_to_string;
-use serde::{Deserialize, Serialize};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 
 // Include pre-generated types (no build.rs needed)
 #[path = "../generated/generated.rs"]
@@ -59,6 +60,12 @@ pub use generated::*;
 // ST extraction and parsing
 pub mod st;
 
+// Source protection of POUs
+pub mod protection;
+
+// Vendor content of addData blocks
+mod add_data;
+
 // Security limits and validation
 pub mod security;
 pub use security::{SecurityError, SecurityLimits, validate_xml};
@@ -68,9 +75,10 @@ pub type Project = Root_project_Inline;
 
 /// Parse PLCopen XML string into a typed structure.
 ///
-/// Uses quick-xml with serde for fast, type-safe parsing.
-pub fn from_str<'a, T: Deserialize<'a>>(xml: &'a str) -> Result<T, quick_xml::DeError> {
-    xml_from_str(xml)
+/// Uses quick-xml with serde for fast, type-safe parsing. Vendor content of
+/// `addData` blocks is kept as raw XML in the `text` of its `data` element.
+pub fn from_str<T: DeserializeOwned>(xml: &str) -> Result<T, quick_xml::DeError> {
+    xml_from_str(&add_data::capture(xml))
 }
 
 /// Parse PLCopen XML string with security limits.
@@ -95,15 +103,15 @@ pub fn from_str<'a, T: Deserialize<'a>>(xml: &'a str) -> Result<T, quick_xml::De
 /// let xml = std::fs::read_to_string("untrusted.xml")?;
 /// let project = from_str_secure::<Project>(&xml, SecurityLimits::strict())?;
 /// ```
-pub fn from_str_secure<'a, T: Deserialize<'a>>(
-    xml: &'a str,
+pub fn from_str_secure<T: DeserializeOwned>(
+    xml: &str,
     limits: SecurityLimits,
 ) -> Result<T, SecureParseError> {
     // Validate XML against security limits
     validate_xml(xml, &limits)?;
     
     // Parse with quick-xml
-    xml_from_str(xml).map_err(SecureParseError::Parse)
+    from_str(xml).map_err(SecureParseError::Parse)
 }
 
 /// Error type for secure parsing
@@ -117,8 +125,10 @@ pub enum SecureParseError {
 }
 
 /// Serialize a structure to PLCopen XML string.
+///
+/// The raw vendor content of `addData` blocks is written back as XML.
 pub fn to_string<T: Serialize>(value: &T) -> Result<String, quick_xml::SeError> {
-    xml_to_string(value)
+    xml_to_string(value).map(add_data::restore)
 }
 
 #[cfg(test)]
@@ -163,6 +173,59 @@ mod tests {
         assert!(project.content_header.is_some());
     }
 
+    #[test]
+    fn test_parse_vendor_add_data() {
+        // CODESYS stores methods and project structure as vendor XML, with text mixed in
+        let xml = r#"<?xml version='1.0' encoding='utf-8'?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0200">
+  <fileHeader companyName="CODESYS" productName="CODESYS" productVersion="3.5" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test">
+    <coordinateInfo>
+      <fbd><scaling x="1" y="1"/></fbd>
+      <ld><scaling x="1" y="1"/></ld>
+      <sfc><scaling x="1" y="1"/></sfc>
+    </coordinateInfo>
+  </contentHeader>
+  <types>
+    <dataTypes/>
+    <pous>
+      <pou name="Motor" pouType="functionBlock">
+        <interface>
+          <inputVars><variable name="Start"><type><BOOL/></type></variable></inputVars>
+        </interface>
+        <body><ST><xhtml xmlns="http://www.w3.org/1999/xhtml">Start := TRUE;</xhtml></ST></body>
+        <addData>
+          <data name="http://www.3s-software.com/plcopenxml/method" handleUnknown="implementation">
+            <Method name="Reset" ObjectId="1">
+              <interface><returnType><BOOL/></returnType></interface>
+              <addData/>
+            </Method>
+          </data>
+          <data name="http://example.com/vendor/notes" handleUnknown="discard">Checked by <b>QA</b> &amp; released</data>
+        </addData>
+      </pou>
+    </pous>
+  </types>
+  <instances>
+    <configurations/>
+  </instances>
+</project>"#;
+
+        let project: Project = from_str(xml).expect("vendor addData should parse");
+        let pous = project.types.as_ref().and_then(|t| t.pous.as_ref()).expect("Missing pous");
+        let data = &pous.pou[0].add_data.as_ref().expect("Missing addData").data;
+        assert_eq!(data.len(), 2);
+        assert!(data[0].text.as_deref().is_some_and(|t| t.starts_with(r#"<Method name="Reset" ObjectId="1">"#)));
+        assert_eq!(data[1].text.as_deref(), Some("Checked by <b>QA</b> &amp; released"));
+
+        // The vendor XML is written back as XML, not as escaped text
+        let written = to_string(&project).expect("Failed to serialize");
+        assert!(written.contains("Checked by <b>QA</b> &amp; released</data>"));
+        let reparsed: Project = from_str(&written).expect("Failed to reparse");
+        let reparsed_pous = reparsed.types.and_then(|t| t.pous).expect("Missing pous");
+        assert_eq!(reparsed_pous.pou[0].add_data.as_ref().map(|a| &a.data), Some(data));
+    }
+
     #[test]
     fn test_parse_bbr_file() {
         // Test with real PLCopen file if available
@@ -0,0 +1,47 @@
+//! Source protection of POUs.
+//!
+//! PLCopen XML has no standard element for protected POUs; tools that
+//! export a protected or encrypted POU leave out its body and mark it with
+//! vendor `addData`. A POU is treated as protected when an `addData` entry
+//! of the POU or of one of its bodies is named after protection or
+//! encryption (e.g. `http://www.3s-software.com/plcopenxml/protected`).
+
+use crate::{AddData, Root_project_InlineType_types_InlineType_pous_InlineType_pou_Inline as Pou};
+
+impl Pou {
+    /// Returns true if the POU is marked as source protected or encrypted.
+    pub fn is_source_protected(&self) -> bool {
+        is_protection_marker(self.add_data.as_ref())
+            || self.body.iter().any(|body| is_protection_marker(body.add_data.as_ref()))
+    }
+}
+
+/// Check if vendor data marks its element as protected.
+fn is_protection_marker(add_data: Option<&AddData>) -> bool {
+    add_data.is_some_and(|add_data| {
+        add_data.data.iter().any(|data| {
+            let name = data.name.to_lowercase();
+            name.contains("protect") || name.contains("encrypt")
+        })
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_protected_pou() {
+        let pou: Pou = crate::from_str(
+            r#"<pou name="Secret" pouType="functionBlock">
+                <addData><data name="http://www.3s-software.com/plcopenxml/protected" handleUnknown="discard"/></addData>
+            </pou>"#,
+        )
+        .expect("Should parse");
+        assert!(pou.is_source_protected());
+
+        let pou: Pou = crate::from_str(r#"<pou name="Open" pouType="function"><body><ST><![CDATA[x := 1;]]></ST></body></pou>"#)
+            .expect("Should parse");
+        assert!(!pou.is_source_protected());
+    }
+}
@@ -1,4 +1,4 @@
-use plcscl::{parse_scl_secure, security::ParserLimits};
+use plcscl::{parse_scl_partial, parse_scl_secure, security::ParserLimits};
 use std::fs;
 use std::path::PathBuf;
 use std::env;
@@ -15,6 +15,8 @@ fn main() {
     let mut passed = 0;
     let mut failed = 0;
     let mut security_blocked = 0;
+    let mut blocks_parsed = 0;
+    let mut blocks_failed = 0;
     
     // Test with balanced limits
     let limits = ParserLimits::balanced();
@@ -36,6 +38,10 @@ fn main() {
         match fs::read_to_string(path) {
             Ok(content) => {
                 // Test with security
+                let partial = parse_scl_partial(&content);
+                blocks_parsed += partial.blocks_parsed();
+                blocks_failed += partial.blocks_failed();
+
                 match parse_scl_secure(&content, limits.clone()) {
                     Ok(_) => {
                         passed += 1;
@@ -47,7 +53,13 @@ fn main() {
                             println!("[SEC] {} - {}", rel_path.display(), e);
                         } else {
                             failed += 1;
-                            println!("[FAIL] {} - {}", rel_path.display(), e);
+                            println!(
+                                "[FAIL] {} - {} ({} blocks parsed, {} failed)",
+                                rel_path.display(),
+                                e,
+                                partial.blocks_parsed(),
+                                partial.blocks_failed()
+                            );
                         }
                     }
                 }
@@ -64,4 +76,5 @@ fn main() {
     println!("Passed: {} ({:.1}%)", passed, (passed as f64 / total as f64) * 100.0);
     println!("Failed: {} ({:.1}%)", failed, (failed as f64 / total as f64) * 100.0);
     println!("Security blocked: {} ({:.1}%)", security_blocked, (security_blocked as f64 / total as f64) * 100.0);
+    println!("Blocks parsed: {}, failed: {}", blocks_parsed, blocks_failed);
 }
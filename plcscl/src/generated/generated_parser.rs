@@ -65,6 +65,14 @@ impl std::fmt::Display for ParseErrorKind {
 
 impl std::error::Error for ParseErrorKind {}
 
+impl ParseErrorKind {
+    /// Whether the error is a security limit, which stops parsing instead of
+    /// skipping the failed block
+    pub fn is_limit(&self) -> bool {
+        !matches!(self, ParseErrorKind::UnexpectedToken { .. } | ParseErrorKind::UnexpectedEof)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct ParseError {
     pub kind: ParseErrorKind,
@@ -368,13 +376,68 @@ impl Parser {
                     self.current().span,
                 ));
             }
-            blocks.push(self.parse_block()?);
+            let start = self.pos;
+            let depth = self.recursion_depth;
+            match self.parse_block() {
+                Ok(block) => blocks.push(block),
+                Err(error) if error.kind.is_limit() => {
+                    self.record_error(error.clone());
+                    return Err(error);
+                }
+                Err(error) => {
+                    self.record_error(error);
+                    self.recursion_depth = depth;
+                    self.synchronize(start);
+                }
+            }
         }
         
         self.uncheck_recursion();
+        if blocks.is_empty() {
+            if let Some(error) = self.errors.first() {
+                return Err(error.clone());
+            }
+        }
         Ok(Program { blocks })
     }
     
+    /// Skip the rest of a block that failed to parse.
+    ///
+    /// Tokens are skipped up to the next block declaration, or past the next
+    /// block end keyword (`END_FUNCTION_BLOCK`, `END_TYPE`, ...), whichever
+    /// comes first. At least one token is skipped when the block failed on
+    /// its first token.
+    fn synchronize(&mut self, start: usize) {
+        if self.pos == start {
+            self.advance();
+        }
+        loop {
+            match self.peek() {
+                TokenKind::Eof
+                | TokenKind::FunctionBlock
+                | TokenKind::Function
+                | TokenKind::DataBlock
+                | TokenKind::Type
+                | TokenKind::OrganizationBlock
+                | TokenKind::Program
+                | TokenKind::Class
+                | TokenKind::Interface => break,
+                TokenKind::EndFunctionBlock
+                | TokenKind::EndFunction
+                | TokenKind::EndDataBlock
+                | TokenKind::EndType
+                | TokenKind::EndOrganizationBlock
+                | TokenKind::EndProgram
+                | TokenKind::EndClass
+                | TokenKind::EndInterface => {
+                    self.advance();
+                    break;
+                }
+                _ => self.advance(),
+            }
+        }
+    }
+    
     fn parse_block(&mut self) -> Result<Block, ParseError> {
         self.check_recursion()?;
         let result = match self.peek() {
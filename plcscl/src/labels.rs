@@ -0,0 +1,200 @@
+//! GOTO labels and jump targets of SCL blocks
+//!
+//! SCL declares labels in a `LABEL ... END_LABEL` section, places them in
+//! front of a statement (`Lbl1: x := 1;`) and jumps with `GOTO Lbl1;`.
+//! Labels are local to the block that declares them.
+//!
+//! The scan works on tokens, so it also covers code the parser rejects:
+//! - a jump whose target is not placed in the block is undefined
+//! - a declared or placed label that no `GOTO` targets is unused
+
+use crate::generated::{Lexer, TokenKind};
+
+/// A label or jump in SCL source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LabelSite {
+    /// Label name as written
+    pub name: String,
+    /// Line number (1-based)
+    pub line: usize,
+}
+
+/// Labels and jumps of one SCL block
+#[derive(Debug, Clone, Default)]
+pub struct BlockLabels {
+    /// Block name
+    pub block: String,
+    /// Labels declared in `LABEL ... END_LABEL`
+    pub declared: Vec<LabelSite>,
+    /// Labels placed in front of a statement
+    pub placed: Vec<LabelSite>,
+    /// Targets of `GOTO` statements
+    pub jumps: Vec<LabelSite>,
+}
+
+impl BlockLabels {
+    /// Get the jumps whose target label is not placed in the block
+    pub fn undefined_jumps(&self) -> Vec<&LabelSite> {
+        self.jumps
+            .iter()
+            .filter(|jump| !contains(&self.placed, &jump.name))
+            .collect()
+    }
+
+    /// Get the labels that no `GOTO` targets
+    ///
+    /// A label that is both declared and placed is reported once, at its
+    /// declaration.
+    pub fn unused_labels(&self) -> Vec<&LabelSite> {
+        let mut unused: Vec<&LabelSite> = Vec::new();
+        for label in self.declared.iter().chain(&self.placed) {
+            if !contains(&self.jumps, &label.name) && !unused.iter().any(|l| l.name.eq_ignore_ascii_case(&label.name)) {
+                unused.push(label);
+            }
+        }
+        unused
+    }
+}
+
+/// Collect the labels and jumps of each block in SCL source
+pub fn scan_labels(input: &str) -> Vec<BlockLabels> {
+    let mut lexer = Lexer::new(input);
+    let mut tokens = Vec::new();
+    loop {
+        let token = lexer.next_token();
+        if token.kind == TokenKind::Eof {
+            break;
+        }
+        tokens.push(token);
+    }
+
+    // Token spans are character offsets
+    let newlines: Vec<usize> = input.chars().enumerate().filter(|&(_, c)| c == '\n').map(|(i, _)| i).collect();
+    let line_of = |offset: usize| newlines.partition_point(|&n| n < offset) + 1;
+
+    let mut blocks: Vec<BlockLabels> = Vec::new();
+    let mut in_declaration = false;
+    let mut in_label_section = false;
+    let mut case_depth = 0usize;
+
+    for (i, token) in tokens.iter().enumerate() {
+        let next = tokens.get(i + 1).map(|t| &t.kind);
+        match &token.kind {
+            TokenKind::Function
+            | TokenKind::FunctionBlock
+            | TokenKind::Program
+            | TokenKind::OrganizationBlock
+            | TokenKind::Method => {
+                let block = match next {
+                    Some(TokenKind::Identifier(name)) | Some(TokenKind::StringLit(name)) => name.clone(),
+                    _ => String::new(),
+                };
+                blocks.push(BlockLabels { block, ..BlockLabels::default() });
+                in_declaration = false;
+                in_label_section = false;
+                case_depth = 0;
+            }
+            TokenKind::Var
+            | TokenKind::VarInput
+            | TokenKind::VarOutput
+            | TokenKind::VarInOut
+            | TokenKind::VarTemp
+            | TokenKind::VarExternal
+            | TokenKind::VarAccess
+            | TokenKind::Type => in_declaration = true,
+            TokenKind::EndVar | TokenKind::EndType => in_declaration = false,
+            TokenKind::Case => case_depth += 1,
+            TokenKind::EndCase => case_depth = case_depth.saturating_sub(1),
+            TokenKind::Goto => {
+                if let (Some(TokenKind::Identifier(name)), Some(block)) = (next, blocks.last_mut()) {
+                    block.jumps.push(LabelSite { name: name.clone(), line: line_of(tokens[i + 1].span.0) });
+                }
+            }
+            TokenKind::Identifier(word) if word.eq_ignore_ascii_case("LABEL") => in_label_section = true,
+            TokenKind::Identifier(word) if word.eq_ignore_ascii_case("END_LABEL") => in_label_section = false,
+            TokenKind::Identifier(name) => {
+                let Some(block) = blocks.last_mut() else {
+                    continue;
+                };
+                let site = || LabelSite { name: name.clone(), line: line_of(token.span.0) };
+                if in_label_section {
+                    block.declared.push(site());
+                } else if !in_declaration && matches!(next, Some(TokenKind::Operator(op)) if op == ":") {
+                    let at_statement_start = i == 0
+                        || matches!(
+                            &tokens[i - 1].kind,
+                            TokenKind::Operator(op) if op == ";"
+                        )
+                        || matches!(
+                            tokens[i - 1].kind,
+                            TokenKind::Begin | TokenKind::Then | TokenKind::Else | TokenKind::Do | TokenKind::Repeat
+                        );
+                    // Inside CASE, `Name :` is a case label unless it is a declared jump label
+                    let is_case_label = case_depth > 0 && !contains(&block.declared, name);
+                    if at_statement_start && !is_case_label {
+                        block.placed.push(site());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    blocks
+}
+
+fn contains(sites: &[LabelSite], name: &str) -> bool {
+    sites.iter().any(|site| site.name.eq_ignore_ascii_case(name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SOURCE: &str = r#"FUNCTION_BLOCK "Sequencer"
+LABEL
+    Start, Done, Spare;
+END_LABEL
+VAR
+    step : INT;
+END_VAR
+BEGIN
+Start: step := step + 1;
+    CASE step OF
+        1: GOTO Done;
+        2: GOTO Missing;
+    END_CASE;
+    GOTO start;
+Done: step := 0;
+END_FUNCTION_BLOCK
+"#;
+
+    #[test]
+    fn test_scan_labels() {
+        let blocks = scan_labels(SOURCE);
+        assert_eq!(blocks.len(), 1);
+        let labels = &blocks[0];
+        assert_eq!(labels.block, "Sequencer");
+        assert_eq!(labels.declared.len(), 3);
+        let placed: Vec<_> = labels.placed.iter().map(|l| (l.name.as_str(), l.line)).collect();
+        assert_eq!(placed, [("Start", 9), ("Done", 15)]);
+        assert_eq!(labels.jumps.len(), 3);
+    }
+
+    #[test]
+    fn test_undefined_and_unused() {
+        let blocks = scan_labels(SOURCE);
+        let undefined: Vec<_> = blocks[0].undefined_jumps().iter().map(|l| (l.name.as_str(), l.line)).collect();
+        assert_eq!(undefined, [("Missing", 12)]);
+        let unused: Vec<_> = blocks[0].unused_labels().iter().map(|l| l.name.as_str()).collect();
+        assert_eq!(unused, ["Spare"]);
+    }
+
+    #[test]
+    fn test_labels_are_local_to_block() {
+        let source = "FUNCTION A : INT\nBEGIN\nL1: A := 1;\nEND_FUNCTION\nFUNCTION B : INT\nBEGIN\nGOTO L1;\nEND_FUNCTION\n";
+        let blocks = scan_labels(source);
+        assert_eq!(blocks[0].unused_labels().len(), 1);
+        assert_eq!(blocks[1].undefined_jumps()[0].line, 7);
+    }
+}
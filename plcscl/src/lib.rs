@@ -44,15 +44,80 @@
 //! ```
 
 pub mod generated;
+pub mod labels;
 pub mod security;
 
 pub use generated::*;
+pub use labels::{scan_labels, BlockLabels, LabelSite};
 pub use security::{ParserLimits, ParserState, SecurityError};
 
 /// Parse SCL source code into an AST
+///
+/// Fails on the first block that does not parse; use [`parse_scl_partial`]
+/// to keep the blocks that do.
 pub fn parse_scl(input: &str) -> Result<Program, ParseError> {
     let mut parser = Parser::new(input);
-    parser.parse_program()
+    let program = parser.parse_program()?;
+    match parser.get_errors().first() {
+        Some(error) => Err(error.clone()),
+        None => Ok(program),
+    }
+}
+
+/// Blocks parsed from a source with some malformed blocks.
+#[derive(Debug, Clone)]
+pub struct PartialProgram {
+    /// Blocks that parsed
+    pub program: Program,
+    /// One error per block that failed to parse, in source order
+    pub errors: Vec<ParseError>,
+}
+
+impl PartialProgram {
+    /// Number of blocks that parsed
+    pub fn blocks_parsed(&self) -> usize {
+        self.program.blocks.len()
+    }
+
+    /// Number of blocks that failed to parse
+    pub fn blocks_failed(&self) -> usize {
+        self.errors.len()
+    }
+}
+
+/// Parse SCL source code, skipping blocks that fail to parse
+///
+/// When a block does not parse (for example a `FUNCTION_BLOCK` closed by
+/// `END_FUNCTION`), its error is recorded and parsing resumes at the next
+/// block declaration or after the next block end keyword. Security limits
+/// still stop parsing; their error is the last one in `errors`.
+///
+/// # Example
+///
+/// ```
+/// use plcscl::parse_scl_partial;
+///
+/// let source = "
+/// FUNCTION_BLOCK Good
+/// BEGIN
+///     x := 1;
+/// END_FUNCTION_BLOCK
+///
+/// FUNCTION_BLOCK Bad
+/// BEGIN
+///     x := ;
+/// END_FUNCTION_BLOCK
+/// ";
+/// let result = parse_scl_partial(source);
+/// assert_eq!((result.blocks_parsed(), result.blocks_failed()), (1, 1));
+/// ```
+pub fn parse_scl_partial(input: &str) -> PartialProgram {
+    let mut parser = Parser::new(input);
+    let program = parser.parse_program().unwrap_or(Program { blocks: Vec::new() });
+    PartialProgram {
+        program,
+        errors: parser.get_errors().to_vec(),
+    }
 }
 
 /// Parse SCL source code with security limits to prevent DoS attacks
@@ -99,8 +164,12 @@ pub fn parse_scl_secure(input: &str, limits: ParserLimits) -> Result<Program, Se
     // Convert to internal parser limits and parse with security checks
     let parser_limits = limits.to_parser_limits();
     let mut parser = Parser::with_limits(input, parser_limits);
-    parser.parse_program()
-        .map_err(|e| SecureParseError::Parse(e.message().to_string()))
+    let program = parser.parse_program()
+        .map_err(|e| SecureParseError::Parse(e.message().to_string()))?;
+    match parser.get_errors().first() {
+        Some(e) => Err(SecureParseError::Parse(e.message().to_string())),
+        None => Ok(program),
+    }
 }
 
 /// Error type for secure parsing that can be either a parse error or security violation
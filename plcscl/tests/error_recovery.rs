@@ -1,12 +1,103 @@
 //! Tests for parser error recovery.
-//! 
-//! NOTE: Error recovery is not yet implemented in the parser.
-//! These tests are commented out until error recovery support is added.
+//!
+//! Recovery works at block level: a block that fails to parse is skipped.
+//! Recovery inside statements and variable declarations is not implemented
+//! yet; those tests are commented out until it is.
 
-use plcscl::parse_scl;
+use plcscl::{parse_scl, parse_scl_partial, Block};
 
-// Error recovery is not yet implemented
-// Uncomment these tests when error recovery is added to the parser
+fn block_names(source: &str) -> Vec<String> {
+    parse_scl_partial(source)
+        .program
+        .blocks
+        .iter()
+        .map(|block| match block {
+            Block::FunctionBlock(fb) => fb.name.clone(),
+            Block::Function(f) => f.name.clone(),
+            other => format!("{:?}", other),
+        })
+        .collect()
+}
+
+#[test]
+fn test_multiple_block_recovery() {
+    let source = r#"
+FUNCTION_BLOCK "Good1"
+VAR
+    x : INT;
+END_VAR
+BEGIN
+    x := 1;
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK "Bad"
+VAR
+    y : INT;
+END_VAR
+BEGIN
+    invalid syntax here !@#$
+END_FUNCTION_BLOCK
+
+FUNCTION_BLOCK "Good2"
+VAR
+    z : INT;
+END_VAR
+BEGIN
+    z := 2;
+END_FUNCTION_BLOCK
+"#;
+
+    let result = parse_scl_partial(source);
+    assert_eq!(result.blocks_parsed(), 2);
+    assert_eq!(result.blocks_failed(), 1);
+    assert_eq!(block_names(source), ["Good1", "Good2"]);
+    assert!(parse_scl(source).is_err());
+}
+
+#[test]
+fn test_mismatched_end_recovery() {
+    // END_FUNCTION closes a FUNCTION_BLOCK; the next block still parses
+    let source = r#"
+FUNCTION_BLOCK "Broken"
+BEGIN
+    x := 1;
+END_FUNCTION
+
+FUNCTION "Next" : INT
+BEGIN
+    Next := 2;
+END_FUNCTION
+"#;
+
+    let result = parse_scl_partial(source);
+    assert_eq!(block_names(source), ["Next"]);
+    assert_eq!(result.blocks_failed(), 1);
+    assert!(result.errors[0].message().contains("EndFunction"));
+}
+
+#[test]
+fn test_missing_end_recovery() {
+    // A block without its end keyword stops at the next block declaration
+    let source = r#"
+FUNCTION_BLOCK "Open"
+BEGIN
+    x := 1;
+
+FUNCTION_BLOCK "Closed"
+BEGIN
+    y := 2;
+END_FUNCTION_BLOCK
+"#;
+
+    assert_eq!(block_names(source), ["Closed"]);
+}
+
+#[test]
+fn test_no_valid_blocks() {
+    let result = parse_scl_partial("garbage tokens only");
+    assert_eq!(result.blocks_parsed(), 0);
+    assert_eq!(result.blocks_failed(), 1);
+}
 
 /*
 #[test]
@@ -69,49 +160,6 @@ END_FUNCTION_BLOCK
     assert!(var_count >= 2, "Expected at least 2 variables, got {}", var_count);
 }
 
-#[test]
-fn test_multiple_block_recovery() {
-    // Multiple blocks with errors in some
-    let source = r#"
-FUNCTION_BLOCK "Good1"
-VAR
-    x : INT;
-END_VAR
-BEGIN
-    x := 1;
-END_FUNCTION_BLOCK
-
-FUNCTION_BLOCK "Bad"
-VAR
-    y : INT;
-END_VAR
-BEGIN
-    invalid syntax here !@#$
-END_FUNCTION_BLOCK
-
-FUNCTION_BLOCK "Good2"
-VAR
-    z : INT;
-END_VAR
-BEGIN
-    z := 2;
-END_FUNCTION_BLOCK
-"#;
-
-    let lexer = Lexer::new(source);
-    let mut parser = Parser::new(lexer).unwrap().with_error_recovery();
-    
-    let result = parser.parse();
-    assert!(result.is_ok());
-    
-    let ast = result.unwrap();
-    // Should parse all 3 blocks even with errors in one
-    assert!(ast.blocks.len() >= 2, "Expected at least 2 blocks, got {}", ast.blocks.len());
-    
-    // Should have collected errors
-    assert!(!parser.errors.is_empty(), "Expected errors to be collected");
-}
-
 #[test]
 fn test_error_recovery_continues_parsing() {
     // Verify parser continues after errors
@@ -144,11 +192,3 @@ END_FUNCTION_BLOCK
     }
 }
 */
-
-// Placeholder test so the test file isn't empty
-#[test]
-fn test_placeholder() {
-    // This test exists so the file has at least one test
-    // Remove this when error recovery tests are uncommented
-    assert!(true);
-}
@@ -14,6 +14,10 @@ pub enum GraphType {
     DataFlow,
     /// Combined structure + calls
     Combined,
+    /// Ladder diagram of one RLL routine
+    Ladder,
+    /// SFC chart of one routine or POU
+    Sfc,
 }
 
 impl FromStr for GraphType {
@@ -25,7 +29,9 @@ impl FromStr for GraphType {
             "call" | "callgraph" | "calls" | "c" => Ok(GraphType::CallGraph),
             "dataflow" | "data" | "flow" | "d" => Ok(GraphType::DataFlow),
             "combined" | "all" | "a" => Ok(GraphType::Combined),
-            _ => Err(format!("Unknown graph type: '{}'. Valid: structure, call, dataflow, combined", s)),
+            "ladder" | "rll" | "l" => Ok(GraphType::Ladder),
+            "sfc" | "chart" => Ok(GraphType::Sfc),
+            _ => Err(format!("Unknown graph type: '{}'. Valid: structure, call, dataflow, combined, ladder, sfc", s)),
         }
     }
 }
@@ -37,6 +43,58 @@ impl GraphType {
             GraphType::CallGraph => "Call graph (JSR, AOI calls)",
             GraphType::DataFlow => "Data flow (Tag read/write)",
             GraphType::Combined => "Combined structure + calls",
+            GraphType::Ladder => "Ladder diagram (RLL rungs)",
+            GraphType::Sfc => "SFC chart (steps and transitions)",
+        }
+    }
+}
+
+/// Output format of graphs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// SVG drawing
+    #[default]
+    Svg,
+    /// Graphviz DOT source
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "svg" => Ok(OutputFormat::Svg),
+            "dot" | "graphviz" | "gv" => Ok(OutputFormat::Dot),
+            "mermaid" | "mmd" => Ok(OutputFormat::Mermaid),
+            _ => Err(format!("Unknown output format: '{}'. Valid: svg, dot, mermaid", s)),
+        }
+    }
+}
+
+/// How nodes are grouped into labeled cluster boxes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClusterMode {
+    /// No clusters
+    #[default]
+    None,
+    /// One box per program around its routines
+    ByProgram,
+    /// One box per task around the programs it runs and their routines
+    ByTask,
+}
+
+impl FromStr for ClusterMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" | "off" => Ok(ClusterMode::None),
+            "by-program" | "program" => Ok(ClusterMode::ByProgram),
+            "by-task" | "task" => Ok(ClusterMode::ByTask),
+            _ => Err(format!("Unknown cluster mode: '{}'. Valid: none, by-program, by-task", s)),
         }
     }
 }
@@ -114,6 +172,7 @@ pub struct VizConfig {
     pub filter: ElementFilter,
     pub show_labels: bool,
     pub compact: bool,
+    pub cluster: ClusterMode,
 }
 
 impl Default for VizConfig {
@@ -123,6 +182,7 @@ impl Default for VizConfig {
             filter: ElementFilter::default(),
             show_labels: true,
             compact: false,
+            cluster: ClusterMode::None,
         }
     }
 }
@@ -169,4 +229,9 @@ impl VizConfig {
         self.filter.tags = true;
         self
     }
+
+    pub fn with_clusters(mut self, mode: ClusterMode) -> Self {
+        self.cluster = mode;
+        self
+    }
 }
@@ -0,0 +1,116 @@
+//! Graphviz DOT output for L5X graphs
+//!
+//! Serializes the graph model (nodes, edges, labels, clusters) to DOT,
+//! independent of the SVG renderer. Node types map to the same fill and
+//! stroke colors as the SVG output; edge types to the same line styles.
+
+use super::{EdgeType, L5xGraph, L5xNodeType};
+
+impl L5xGraph {
+    /// Serialize the graph to Graphviz DOT
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::new();
+        dot.push_str("digraph plcviz {\n");
+        dot.push_str("    rankdir=TB;\n");
+        dot.push_str("    node [fontname=\"sans-serif\", fontsize=12];\n");
+        dot.push_str("    edge [fontname=\"sans-serif\", fontsize=10];\n");
+
+        // Clustered nodes go into `subgraph cluster_N` blocks
+        let mut clusters: Vec<&str> = Vec::new();
+        for node in self.nodes() {
+            match self.cluster_of(&node.id) {
+                Some(cluster) if !clusters.contains(&cluster) => clusters.push(cluster),
+                Some(_) => {}
+                None => dot.push_str(&format!("    {}\n", node_statement(&node.id, &node.label, node.node_type))),
+            }
+        }
+        for (index, cluster) in clusters.iter().enumerate() {
+            dot.push_str(&format!("    subgraph cluster_{} {{\n", index));
+            dot.push_str(&format!("        label=\"{}\";\n", escape(cluster)));
+            dot.push_str("        style=\"rounded,dashed\";\n        color=\"#90A4AE\";\n");
+            for node in self.nodes().iter().filter(|n| self.cluster_of(&n.id) == Some(cluster)) {
+                dot.push_str(&format!("        {}\n", node_statement(&node.id, &node.label, node.node_type)));
+            }
+            dot.push_str("    }\n");
+        }
+
+        for edge in self.edges() {
+            let style = match edge.edge_type {
+                EdgeType::Structure => "color=\"#757575\", style=dashed",
+                EdgeType::Call => "color=\"#1565C0\"",
+                EdgeType::DataFlow => "color=\"#2E7D32\", style=dashed",
+            };
+            let label = edge
+                .label
+                .as_deref()
+                .map(|l| format!(", label=\"{}\"", escape(l)))
+                .unwrap_or_default();
+            dot.push_str(&format!(
+                "    \"{}\" -> \"{}\" [{}{}];\n",
+                escape(&edge.from),
+                escape(&edge.to),
+                style,
+                label
+            ));
+        }
+
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// DOT statement of one node
+fn node_statement(id: &str, label: &str, node_type: L5xNodeType) -> String {
+    let style = if node_type.corner_radius() > 2 { "filled,rounded" } else { "filled" };
+    format!(
+        "\"{}\" [label=\"{}\", shape=box, style=\"{}\", fillcolor=\"#{:06X}\", color=\"#{:06X}\"];",
+        escape(id),
+        escape(label),
+        style,
+        node_type.fill_color(),
+        node_type.stroke_color()
+    )
+}
+
+/// Escape a DOT string literal
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_dot() {
+        let mut graph = L5xGraph::new();
+        graph.add_program("Main");
+        graph.add_routine("Main", "Init");
+        graph.add_node("Tag \"A\"", "Tag \"A\"", L5xNodeType::Tag);
+        graph.add_edge("Main", "Main.Init", None);
+        graph.add_call("Main.Init", "Main.Other");
+        graph.add_edge("Main.Init", "Tag \"A\"", Some("write"));
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph plcviz {"));
+        assert!(dot.contains("\"Main\" [label=\"Main\", shape=box, style=\"filled,rounded\", fillcolor=\"#BBDEFB\", color=\"#1565C0\"];"));
+        assert!(dot.contains("\"Tag \\\"A\\\"\" [label=\"Tag \\\"A\\\"\""));
+        assert!(dot.contains("\"Main.Init\" -> \"Main.Other\" [color=\"#1565C0\"];"));
+        assert!(dot.contains("label=\"write\""));
+        assert!(dot.trim_end().ends_with('}'));
+    }
+
+    #[test]
+    fn test_to_dot_clusters() {
+        let mut graph = L5xGraph::new();
+        graph.add_program("Main");
+        graph.add_routine("Main", "Init");
+        graph.add_node("Valve", "Valve", L5xNodeType::Aoi);
+        graph.cluster_by_program();
+
+        let dot = graph.to_dot();
+        assert!(dot.contains("subgraph cluster_0 {"));
+        assert!(dot.contains("label=\"Main\";"));
+        assert_eq!(dot.matches("subgraph").count(), 1);
+    }
+}
@@ -19,6 +19,8 @@ pub struct L5xGraph {
     nodes: Vec<L5xNode>,
     edges: Vec<L5xEdge>,
     node_index: HashMap<String, usize>,
+    /// Cluster name of clustered nodes
+    clusters: HashMap<String, String>,
 }
 
 /// A node in the L5X graph
@@ -113,6 +115,7 @@ impl L5xGraph {
             nodes: Vec::new(),
             edges: Vec::new(),
             node_index: HashMap::new(),
+            clusters: HashMap::new(),
         }
     }
 
@@ -177,6 +180,58 @@ impl L5xGraph {
         });
     }
 
+    /// Put a node into a named cluster box
+    pub fn set_cluster(&mut self, id: &str, cluster: &str) {
+        self.clusters.insert(id.to_string(), cluster.to_string());
+    }
+
+    /// Get the cluster of a node
+    pub fn cluster_of(&self, id: &str) -> Option<&str> {
+        self.clusters.get(id).map(|s| s.as_str())
+    }
+
+    /// Cluster nodes by the program they belong to
+    ///
+    /// A program node and everything below it through parent links share a
+    /// cluster. Nodes whose parent is not part of the graph (e.g. routines
+    /// in a call graph) are clustered by that parent.
+    pub fn cluster_by_program(&mut self) {
+        for node in &self.nodes {
+            if let Some(program) = self.program_of(node) {
+                self.clusters.insert(node.id.clone(), program.to_string());
+            }
+        }
+    }
+
+    /// Cluster nodes by the task that runs their program
+    ///
+    /// `program_tasks` maps program node IDs to task names. Programs that no
+    /// task runs are not clustered.
+    pub fn cluster_by_task(&mut self, program_tasks: &HashMap<String, String>) {
+        for node in &self.nodes {
+            if let Some(task) = self.program_of(node).and_then(|program| program_tasks.get(program)) {
+                self.clusters.insert(node.id.clone(), task.clone());
+            }
+        }
+    }
+
+    /// Find the program a node belongs to through its parent links
+    fn program_of<'a>(&'a self, node: &'a L5xNode) -> Option<&'a str> {
+        let mut current = node;
+        // Bounded walk in case of parent cycles
+        for _ in 0..self.nodes.len() {
+            if current.node_type == L5xNodeType::Program {
+                return Some(&current.id);
+            }
+            let parent = current.parent.as_deref()?;
+            match self.node_index.get(parent) {
+                Some(&idx) => current = &self.nodes[idx],
+                None => return Some(parent),
+            }
+        }
+        None
+    }
+
     /// Render the graph to SVG using layout-rs for layout, custom SVG output
     pub fn render_svg(&self) -> String {
         let mut vg = VisualGraph::new(Orientation::TopToBottom);
@@ -184,8 +239,12 @@ impl L5xGraph {
         // Map our node IDs to layout-rs handles
         let mut handles: HashMap<String, layout::adt::dag::NodeHandle> = HashMap::new();
 
+        // Add clustered nodes next to each other so their boxes stay compact
+        let mut nodes: Vec<&L5xNode> = self.nodes.iter().collect();
+        nodes.sort_by_key(|node| self.cluster_of(&node.id));
+
         // Add nodes to the visual graph
-        for node in &self.nodes {
+        for node in nodes {
             let shape = ShapeKind::Box(node.label.clone());
             
             // Use node-type-specific colors
@@ -203,7 +262,16 @@ impl L5xGraph {
             let width = text_width.clamp(80.0, 350.0); // min 80, max 350
             let sz = Point::new(width, 40.);
 
-            let element = Element::create(shape, look, Orientation::LeftToRight, sz);
+            let element = match self.cluster_of(&node.id) {
+                Some(cluster) => Element::create_with_properties(
+                    shape,
+                    look,
+                    Orientation::LeftToRight,
+                    sz,
+                    format!("__CLUSTER__\n{}", cluster),
+                ),
+                None => Element::create(shape, look, Orientation::LeftToRight, sz),
+            };
 
             let handle = vg.add_node(element);
             handles.insert(node.id.clone(), handle);
@@ -239,6 +307,16 @@ impl L5xGraph {
         renderer.finalize()
     }
 
+    /// Get the nodes in insertion order
+    pub fn nodes(&self) -> &[L5xNode] {
+        &self.nodes
+    }
+
+    /// Get the edges in insertion order
+    pub fn edges(&self) -> &[L5xEdge] {
+        &self.edges
+    }
+
     /// Get node count
     pub fn node_count(&self) -> usize {
         self.nodes.len()
@@ -279,6 +357,38 @@ mod tests {
         assert!(svg.contains("xmlns"));
     }
 
+    #[test]
+    fn test_cluster_by_program() {
+        let mut graph = L5xGraph::new();
+        graph.add_program("Prog1");
+        graph.add_routine("Prog1", "Main");
+        graph.add_node_with_parent("Prog2.Main", "Main", L5xNodeType::Routine, "Prog2");
+        graph.add_node("Valve", "Valve", L5xNodeType::Aoi);
+        graph.cluster_by_program();
+
+        assert_eq!(graph.cluster_of("Prog1"), Some("Prog1"));
+        assert_eq!(graph.cluster_of("Prog1.Main"), Some("Prog1"));
+        assert_eq!(graph.cluster_of("Prog2.Main"), Some("Prog2"));
+        assert_eq!(graph.cluster_of("Valve"), None);
+
+        let svg = graph.render_svg();
+        assert!(svg.contains(r#"class="cluster""#));
+        assert!(svg.contains(">Prog2</text>"));
+    }
+
+    #[test]
+    fn test_cluster_by_task() {
+        let mut graph = L5xGraph::new();
+        graph.add_program("Prog1");
+        graph.add_routine("Prog1", "Main");
+        graph.add_program("Prog2");
+        let tasks = HashMap::from([("Prog1".to_string(), "FastTask".to_string())]);
+        graph.cluster_by_task(&tasks);
+
+        assert_eq!(graph.cluster_of("Prog1.Main"), Some("FastTask"));
+        assert_eq!(graph.cluster_of("Prog2"), None);
+    }
+
     #[test]
     fn test_svg_valid_structure() {
         let mut graph = L5xGraph::new();
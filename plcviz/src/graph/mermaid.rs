@@ -0,0 +1,178 @@
+//! Mermaid output for L5X graphs
+//!
+//! Serializes the graph model to a Mermaid `flowchart` for embedding in
+//! Markdown. Node IDs are replaced by `n0`, `n1`, ... since Mermaid only
+//! accepts plain identifiers; the original names become quoted labels.
+//! Call edges are solid arrows, structure edges dotted and data flow
+//! edges thick.
+
+use std::collections::HashMap;
+
+use super::{EdgeType, L5xGraph, L5xNodeType};
+
+/// Node types with their Mermaid class name
+const NODE_CLASSES: &[(L5xNodeType, &str)] = &[
+    (L5xNodeType::Controller, "controller"),
+    (L5xNodeType::Task, "task"),
+    (L5xNodeType::Program, "program"),
+    (L5xNodeType::Routine, "routine"),
+    (L5xNodeType::Aoi, "aoi"),
+    (L5xNodeType::Tag, "tag"),
+    (L5xNodeType::Udt, "udt"),
+];
+
+impl L5xGraph {
+    /// Serialize the graph to a Mermaid flowchart
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("flowchart TB\n");
+
+        let mut ids: HashMap<&str, String> = HashMap::new();
+        for node in self.nodes() {
+            let next = format!("n{}", ids.len());
+            ids.entry(&node.id).or_insert(next);
+        }
+        // Edge endpoints that are not nodes still need an identifier
+        for edge in self.edges() {
+            for end in [&edge.from, &edge.to] {
+                let next = format!("n{}", ids.len());
+                ids.entry(end).or_insert(next);
+            }
+        }
+
+        let mut clusters: Vec<&str> = Vec::new();
+        for node in self.nodes() {
+            match self.cluster_of(&node.id) {
+                Some(cluster) if !clusters.contains(&cluster) => clusters.push(cluster),
+                Some(_) => {}
+                None => out.push_str(&format!("    {}\n", node_statement(&ids[node.id.as_str()], &node.label, node.node_type))),
+            }
+        }
+        for (index, cluster) in clusters.iter().enumerate() {
+            out.push_str(&format!("    subgraph c{}[\"{}\"]\n", index, escape(cluster)));
+            for node in self.nodes().iter().filter(|n| self.cluster_of(&n.id) == Some(cluster)) {
+                out.push_str(&format!("        {}\n", node_statement(&ids[node.id.as_str()], &node.label, node.node_type)));
+            }
+            out.push_str("    end\n");
+        }
+        let mut declared: Vec<&str> = self.nodes().iter().map(|n| n.id.as_str()).collect();
+        for end in self.edges().iter().flat_map(|e| [e.from.as_str(), e.to.as_str()]) {
+            if !declared.contains(&end) {
+                out.push_str(&format!("    {}[\"{}\"]\n", ids[end], escape(end)));
+                declared.push(end);
+            }
+        }
+
+        for edge in self.edges() {
+            let arrow = match edge.edge_type {
+                EdgeType::Call => "-->",
+                EdgeType::Structure => "-.->",
+                EdgeType::DataFlow => "==>",
+            };
+            let label = edge
+                .label
+                .as_deref()
+                .map(|l| format!("|\"{}\"|", escape(l)))
+                .unwrap_or_default();
+            out.push_str(&format!(
+                "    {} {}{} {}\n",
+                ids[edge.from.as_str()],
+                arrow,
+                label,
+                ids[edge.to.as_str()]
+            ));
+        }
+
+        // Colors mirror the SVG node styles
+        for (node_type, class) in NODE_CLASSES {
+            let members: Vec<&str> = self
+                .nodes()
+                .iter()
+                .filter(|n| n.node_type == *node_type)
+                .map(|n| ids[n.id.as_str()].as_str())
+                .collect();
+            if members.is_empty() {
+                continue;
+            }
+            out.push_str(&format!(
+                "    classDef {} fill:#{:06X},stroke:#{:06X}\n",
+                class,
+                node_type.fill_color(),
+                node_type.stroke_color()
+            ));
+            out.push_str(&format!("    class {} {}\n", members.join(","), class));
+        }
+        out
+    }
+}
+
+/// Mermaid statement of one node; AOIs are drawn with round edges
+fn node_statement(id: &str, label: &str, node_type: L5xNodeType) -> String {
+    if node_type == L5xNodeType::Aoi {
+        format!("{}(\"{}\")", id, escape(label))
+    } else {
+        format!("{}[\"{}\"]", id, escape(label))
+    }
+}
+
+/// Escape characters Mermaid treats as syntax inside a quoted label
+fn escape(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("#quot;"),
+            '(' => escaped.push_str("#40;"),
+            ')' => escaped.push_str("#41;"),
+            '[' => escaped.push_str("#91;"),
+            ']' => escaped.push_str("#93;"),
+            '{' => escaped.push_str("#123;"),
+            '}' => escaped.push_str("#125;"),
+            '|' => escaped.push_str("#124;"),
+            '<' => escaped.push_str("#lt;"),
+            '>' => escaped.push_str("#gt;"),
+            '\n' => escaped.push_str("<br/>"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_mermaid() {
+        let mut graph = L5xGraph::new();
+        graph.add_program("Main");
+        graph.add_routine("Main", "Init");
+        graph.add_node("Valve", "Valve (v2)", L5xNodeType::Aoi);
+        graph.add_edge("Main", "Main.Init", None);
+        graph.add_call("Main.Init", "Valve");
+        graph.add_call("Main.Init", "Main.Missing");
+        graph.add_call("Valve", "Main.Missing");
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.starts_with("flowchart TB\n"));
+        assert!(mermaid.contains("    n0[\"Main\"]\n"));
+        assert!(mermaid.contains("    n2(\"Valve #40;v2#41;\")\n"));
+        assert_eq!(mermaid.matches("n3[\"Main.Missing\"]").count(), 1);
+        assert!(mermaid.contains("    n0 -.-> n1\n"));
+        assert!(mermaid.contains("    n1 --> n2\n"));
+        assert!(mermaid.contains("    classDef program fill:#BBDEFB,stroke:#1565C0\n"));
+        assert!(mermaid.contains("    class n2 aoi\n"));
+    }
+
+    #[test]
+    fn test_to_mermaid_clusters_and_labels() {
+        let mut graph = L5xGraph::new();
+        graph.add_program("Main");
+        graph.add_routine("Main", "Init");
+        graph.add_node("Speed", "Speed", L5xNodeType::Tag);
+        graph.add_edge("Speed", "Main.Init", Some("read \"x\""));
+        graph.cluster_by_program();
+
+        let mermaid = graph.to_mermaid();
+        assert!(mermaid.contains("    subgraph c0[\"Main\"]\n        n0[\"Main\"]\n        n1[\"Init\"]\n    end\n"));
+        assert!(mermaid.contains("    n2 -.->|\"read #quot;x#quot;\"| n1\n"));
+    }
+}
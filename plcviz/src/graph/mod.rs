@@ -2,8 +2,10 @@
 //!
 //! Provides a graph abstraction for L5X elements that can be rendered to SVG.
 
+mod dot;
 mod l5x_graph;
+mod mermaid;
 mod renderer;
 
-pub use l5x_graph::{L5xGraph, L5xNode, L5xNodeType, L5xEdge};
+pub use l5x_graph::{EdgeType, L5xGraph, L5xNode, L5xNodeType, L5xEdge};
 pub use renderer::SvgRenderer;
@@ -4,11 +4,18 @@ use layout::core::format::{ClipHandle, RenderBackend};
 use layout::core::geometry::Point;
 use layout::core::style::StyleAttr;
 
+/// Space between a cluster box and its nodes
+const CLUSTER_PAD: f64 = 12.0;
+/// Height of a cluster label above its nodes
+const CLUSTER_LABEL_H: f64 = 14.0;
+
 /// Custom SVG renderer that produces clean, valid SVG
 pub struct SvgRenderer {
     content: String,
     width: f64,
     height: f64,
+    /// Cluster bounding boxes as (name, min x, min y, max x, max y)
+    clusters: Vec<(String, f64, f64, f64, f64)>,
 }
 
 impl SvgRenderer {
@@ -17,6 +24,7 @@ impl SvgRenderer {
             content: String::new(),
             width: 0.0,
             height: 0.0,
+            clusters: Vec::new(),
         }
     }
 
@@ -87,6 +95,21 @@ impl SvgRenderer {
         svg.push_str(r##"<rect width="100%" height="100%" fill="#fafafa"/>
 "##);
 
+        // Cluster boxes behind the nodes and edges
+        for (name, x0, y0, x1, y1) in &self.clusters {
+            svg.push_str(&format!(
+                r##"<g class="cluster"><rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="8" fill="#ECEFF1" fill-opacity="0.6" stroke="#90A4AE" stroke-dasharray="6,3"/><text x="{:.1}" y="{:.1}" font-family="sans-serif" font-size="12" font-weight="bold" fill="#546E7A">{}</text></g>
+"##,
+                x0 - CLUSTER_PAD,
+                y0 - CLUSTER_PAD - CLUSTER_LABEL_H,
+                x1 - x0 + 2.0 * CLUSTER_PAD,
+                y1 - y0 + 2.0 * CLUSTER_PAD + CLUSTER_LABEL_H,
+                x0 - CLUSTER_PAD + 6.0,
+                y0 - CLUSTER_PAD - 4.0,
+                Self::escape_xml(name)
+            ));
+        }
+
         // Content
         svg.push_str(&self.content);
 
@@ -137,6 +160,126 @@ impl SvgRenderer {
             d, stroke, dash, marker, line_width
         ));
     }
+
+    /// Grow the bounding box of a cluster by a node rectangle
+    fn add_to_cluster(&mut self, cluster: &str, xy: Point, size: Point) {
+        let (x1, y1) = (xy.x + size.x, xy.y + size.y);
+        match self.clusters.iter_mut().find(|c| c.0 == cluster) {
+            Some(c) => {
+                c.1 = c.1.min(xy.x);
+                c.2 = c.2.min(xy.y);
+                c.3 = c.3.max(x1);
+                c.4 = c.4.max(y1);
+            }
+            None => self.clusters.push((cluster.to_string(), xy.x, xy.y, x1, y1)),
+        }
+        self.grow(x1 + CLUSTER_PAD, y1 + CLUSTER_PAD);
+    }
+
+    /// Draw an SFC chart element encoded in the node properties
+    ///
+    /// The first line of `props` is the element marker, the following
+    /// lines its text. Returns `false` for other markers.
+    fn draw_sfc_element(&mut self, xy: Point, size: Point, look: &StyleAttr, props: &str) -> bool {
+        let mut lines = props.lines();
+        let marker = lines.next().unwrap_or("");
+        let text: Vec<&str> = lines.collect();
+        let font = look.font_size;
+        let cx = xy.x + size.x / 2.0;
+        let cy = xy.y + size.y / 2.0;
+
+        match marker {
+            "__STEP__" | "__INITIAL_STEP__" => {
+                self.content.push_str(&format!(
+                    r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="#E3F2FD" stroke="#1565C0" stroke-width="2"/>
+"##,
+                    xy.x, xy.y, size.x, size.y
+                ));
+                if marker == "__INITIAL_STEP__" {
+                    self.content.push_str(&format!(
+                        r##"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" fill="none" stroke="#1565C0" stroke-width="1"/>
+"##,
+                        xy.x + 4.0,
+                        xy.y + 4.0,
+                        size.x - 8.0,
+                        size.y - 8.0
+                    ));
+                }
+                // Step name on top, actions below a separator
+                let mut y = xy.y + 20.0;
+                for (i, line) in text.iter().enumerate() {
+                    if i == 1 {
+                        self.content.push_str(&format!(
+                            r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#90CAF9" stroke-width="1"/>
+"##,
+                            xy.x + 6.0,
+                            y + 10.0,
+                            xy.x + size.x - 6.0,
+                            y + 10.0
+                        ));
+                        y += 4.0;
+                    }
+                    let weight = if i == 0 { r#" font-weight="bold""# } else { "" };
+                    self.content.push_str(&format!(
+                        r#"<text x="{:.1}" y="{:.1}" text-anchor="middle" dominant-baseline="middle" font-family="sans-serif" font-size="{}"{}>{}</text>
+"#,
+                        cx, y, font, weight, Self::escape_xml(line)
+                    ));
+                    y += 18.0;
+                }
+            }
+            "__TRANSITION__" => {
+                self.content.push_str(&format!(
+                    r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#333" stroke-width="4"/>
+"##,
+                    cx - 20.0, cy, cx + 20.0, cy
+                ));
+                if let Some(condition) = text.first().filter(|c| !c.is_empty()) {
+                    self.content.push_str(&format!(
+                        r#"<text x="{:.1}" y="{:.1}" dominant-baseline="middle" font-family="sans-serif" font-size="{}">{}</text>
+"#,
+                        cx + 28.0, cy, font, Self::escape_xml(condition)
+                    ));
+                }
+            }
+            "__SELECTION__" => {
+                self.content.push_str(&format!(
+                    r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#333" stroke-width="2"/>
+"##,
+                    xy.x, cy, xy.x + size.x, cy
+                ));
+            }
+            "__SIMULTANEOUS__" => {
+                for y in [cy - 2.0, cy + 2.0] {
+                    self.content.push_str(&format!(
+                        r##"<line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="#333" stroke-width="1.5"/>
+"##,
+                        xy.x, y, xy.x + size.x, y
+                    ));
+                }
+            }
+            "__JUMP__" | "__STOP__" => {
+                let fill = if marker == "__JUMP__" { "#333" } else { "#C62828" };
+                self.content.push_str(&format!(
+                    r#"<polygon points="{:.1},{:.1} {:.1},{:.1} {:.1},{:.1}" fill="{}"/>
+"#,
+                    xy.x + 4.0, xy.y + 8.0, xy.x + 20.0, xy.y + 8.0, xy.x + 12.0, xy.y + 22.0, fill
+                ));
+                let label = match (marker, text.first()) {
+                    ("__STOP__", Some(name)) if !name.is_empty() => *name,
+                    ("__STOP__", _) => "STOP",
+                    (_, name) => name.copied().unwrap_or(""),
+                };
+                self.content.push_str(&format!(
+                    r#"<text x="{:.1}" y="{:.1}" dominant-baseline="middle" font-family="sans-serif" font-size="{}">{}</text>
+"#,
+                    xy.x + 26.0, cy, font, Self::escape_xml(label)
+                ));
+            }
+            _ => return false,
+        }
+        true
+    }
 }
 
 impl Default for SvgRenderer {
@@ -151,11 +294,19 @@ impl RenderBackend for SvgRenderer {
         xy: Point,
         size: Point,
         look: &StyleAttr,
-        _properties: Option<String>,
+        properties: Option<String>,
         _clip: Option<ClipHandle>,
     ) {
         self.grow(xy.x + size.x, xy.y + size.y);
 
+        if let Some(props) = properties.as_deref().filter(|p| p.starts_with("__")) {
+            if let Some(cluster) = props.strip_prefix("__CLUSTER__\n") {
+                self.add_to_cluster(cluster, xy, size);
+            } else if self.draw_sfc_element(xy, size, look, props) {
+                return;
+            }
+        }
+
         let fill = Self::fill_to_svg(look.fill_color);
         let stroke = Self::color_to_svg(&look.line_color);
         let rx = if look.rounded > 0 { look.rounded } else { 4 };
@@ -252,6 +403,9 @@ impl RenderBackend for SvgRenderer {
         } else if props.contains("__DATA__") {
             // Data flow edges: dashed green
             ("#2E7D32", r#" stroke-dasharray="5,3""#, "arrowhead-data")
+        } else if props.contains("__SFC__") {
+            // SFC links: solid dark
+            ("#333", "", "arrowhead")
         } else if props.contains("__STRUCT__") {
             // Structure edges: gray dashed
             ("#757575", r#" stroke-dasharray="4,2""#, "arrowhead-struct")
@@ -0,0 +1,389 @@
+//! Ladder diagram rendering for L5X RLL routines
+//!
+//! Lays out parsed RLL rungs between a left and a right power rail:
+//! instructions in series are placed left to right, the legs of a parallel
+//! branch are stacked below each other and joined by vertical wires.
+//!
+//! Contacts (`XIC`, `XIO`) and coils (`OTE`, `OTL`, `OTU`) use the ladder
+//! symbols; all other instructions are drawn as boxes listing their
+//! operands. Rungs that fail to parse are shown as their raw text.
+
+use l5x::rll::{parse_rung, Branch, Instruction, Rung, RungElement};
+
+use crate::svg::{Color, Line, Path, Rect, Style, SvgBuilder, Text, TextAnchor};
+
+/// Width of one instruction cell
+const CELL_W: f64 = 120.0;
+/// Minimum height of a row
+const ROW_H: f64 = 60.0;
+/// Offset of the wire from the top of a row
+const WIRE_Y: f64 = 30.0;
+/// Horizontal gap between a parallel branch and its vertical wires
+const BRANCH_GAP: f64 = 10.0;
+/// Line height of box operands
+const OPERAND_H: f64 = 14.0;
+/// Line height of rung comments
+const COMMENT_H: f64 = 16.0;
+/// X position of the left rail
+const RAIL_X: f64 = 50.0;
+/// Wire between a rail and the rung logic
+const LEAD: f64 = 20.0;
+/// Height of the title area
+const TITLE_H: f64 = 40.0;
+/// Maximum characters of an operand label
+const LABEL_CHARS: usize = 18;
+
+/// A rung to draw
+#[derive(Debug, Clone)]
+pub struct LadderRung {
+    /// Rung number
+    pub number: usize,
+    /// Rung comment
+    pub comment: Option<String>,
+    /// Parsed rung
+    pub rung: Rung,
+}
+
+/// Ladder diagram of one routine
+#[derive(Debug, Clone, Default)]
+pub struct LadderDiagram {
+    /// Diagram title (e.g., "MainProgram.MainRoutine")
+    pub title: String,
+    /// Rungs in routine order
+    pub rungs: Vec<LadderRung>,
+}
+
+impl LadderDiagram {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            rungs: Vec::new(),
+        }
+    }
+
+    /// Build the diagram of an RLL routine
+    ///
+    /// Returns `None` if the routine has no RLL content.
+    pub fn from_routine(title: &str, routine: &l5x::Routine) -> Option<Self> {
+        let rll = routine.content.iter().find_map(|content| match content {
+            l5x::RoutineContent::RLLContent(rll) => Some(rll),
+            _ => None,
+        })?;
+
+        let mut diagram = Self::new(title);
+        for (index, rung) in rll.rung.iter().enumerate() {
+            let number = rung.number.as_ref().and_then(|n| n.parse().ok()).unwrap_or(index);
+            let mut text = String::new();
+            let mut comment = None;
+            for content in &rung.content {
+                match content {
+                    l5x::RungContent::Text(text_wide) => {
+                        for item in &text_wide.content {
+                            if let l5x::TextWideContent::TextContent(s) = item {
+                                text.push_str(s);
+                            }
+                        }
+                    }
+                    l5x::RungContent::Comment(comment_wide) => {
+                        let joined: String = comment_wide
+                            .content
+                            .iter()
+                            .filter_map(|item| match item {
+                                l5x::CommentWideContent::TextContent(s) => Some(s.as_str()),
+                                _ => None,
+                            })
+                            .collect();
+                        if !joined.trim().is_empty() {
+                            comment = Some(joined.trim().to_string());
+                        }
+                    }
+                    _ => {}
+                }
+            }
+            diagram.add_rung(number, comment, parse_rung(text.trim()));
+        }
+        Some(diagram)
+    }
+
+    /// Add a rung to the diagram
+    pub fn add_rung(&mut self, number: usize, comment: Option<String>, rung: Rung) {
+        self.rungs.push(LadderRung { number, comment, rung });
+    }
+
+    /// Render the diagram to SVG
+    pub fn render_svg(&self) -> String {
+        let logic_width = self
+            .rungs
+            .iter()
+            .filter_map(|r| r.rung.content.as_ref())
+            .map(|content| series_size(&content.elements).0)
+            .fold(4.0 * CELL_W, f64::max);
+        let right_rail = RAIL_X + LEAD + logic_width + LEAD;
+        let width = right_rail + RAIL_X;
+        let height = TITLE_H + self.rungs.iter().map(rung_height).sum::<f64>() + ROW_H / 2.0;
+
+        let mut svg = SvgBuilder::new(width, height);
+        svg.add(
+            Text::new(RAIL_X, 25.0, &self.title)
+                .style(Style::default().fill(Color::BLACK).font("sans-serif", 16.0))
+                .to_svg(),
+        );
+        let rail = Style::default().stroke(Color::BLACK).stroke_width(3.0);
+        svg.add(Line::new(RAIL_X, TITLE_H, RAIL_X, height - ROW_H / 4.0).style(rail.clone()).to_svg());
+        svg.add(Line::new(right_rail, TITLE_H, right_rail, height - ROW_H / 4.0).style(rail).to_svg());
+
+        let mut out = Vec::new();
+        let mut y = TITLE_H;
+        for ladder_rung in &self.rungs {
+            let comment_lines = comment_lines(ladder_rung);
+            for (i, line) in comment_lines.iter().enumerate() {
+                out.push(
+                    Text::new(RAIL_X + LEAD, y + COMMENT_H * (i as f64 + 1.0) - 4.0, line)
+                        .style(Style::default().fill(Color::GRAY).font("sans-serif", 11.0))
+                        .to_svg(),
+                );
+            }
+            let top = y + COMMENT_H * comment_lines.len() as f64;
+            out.push(
+                Text::new(RAIL_X - 10.0, top + WIRE_Y + 4.0, &ladder_rung.number.to_string())
+                    .anchor(TextAnchor::End)
+                    .style(Style::default().fill(Color::BLACK).font("sans-serif", 11.0))
+                    .to_svg(),
+            );
+
+            let start = RAIL_X + LEAD;
+            match &ladder_rung.rung.content {
+                Some(content) => {
+                    let (logic_w, _) = series_size(&content.elements);
+                    wire(&mut out, RAIL_X, start, top + WIRE_Y);
+                    draw_series(&mut out, &content.elements, start, top);
+                    wire(&mut out, start + logic_w, right_rail, top + WIRE_Y);
+                }
+                None => {
+                    out.push(
+                        Text::new(start, top + WIRE_Y + 4.0, &ladder_rung.rung.raw_text)
+                            .style(Style::default().fill(Color::rgb(200, 0, 0)).font("monospace", 11.0))
+                            .to_svg(),
+                    );
+                }
+            }
+            y += rung_height(ladder_rung);
+        }
+
+        for element in out {
+            svg.add(element);
+        }
+        svg.build()
+    }
+}
+
+/// Height of a rung including its comment
+fn rung_height(ladder_rung: &LadderRung) -> f64 {
+    let logic_h = match &ladder_rung.rung.content {
+        Some(content) => series_size(&content.elements).1,
+        None => ROW_H,
+    };
+    COMMENT_H * comment_lines(ladder_rung).len() as f64 + logic_h
+}
+
+fn comment_lines(ladder_rung: &LadderRung) -> Vec<&str> {
+    ladder_rung
+        .comment
+        .as_deref()
+        .map(|c| c.lines().map(str::trim).filter(|l| !l.is_empty()).collect())
+        .unwrap_or_default()
+}
+
+/// Size of instructions and branches placed in series
+fn series_size(elements: &[RungElement]) -> (f64, f64) {
+    elements.iter().map(element_size).fold((0.0, ROW_H), |(w, h), (ew, eh)| (w + ew, h.max(eh)))
+}
+
+fn element_size(element: &RungElement) -> (f64, f64) {
+    match element {
+        RungElement::Instruction(instruction) => instruction_size(instruction),
+        RungElement::Parallel(branches) => parallel_size(branches),
+    }
+}
+
+/// Size of parallel legs stacked below each other
+fn parallel_size(branches: &[Branch]) -> (f64, f64) {
+    let (width, height) = branches
+        .iter()
+        .map(|branch| series_size(&branch.elements))
+        .fold((0.0, 0.0), |(w, h), (bw, bh)| (f64::max(w, bw), h + bh));
+    (width + 2.0 * BRANCH_GAP, height)
+}
+
+fn instruction_size(instruction: &Instruction) -> (f64, f64) {
+    match symbol(instruction) {
+        Symbol::Box => {
+            let box_bottom = WIRE_Y - 15.0 + box_height(instruction);
+            (CELL_W, ROW_H.max(box_bottom + 10.0))
+        }
+        _ => (CELL_W, ROW_H),
+    }
+}
+
+fn box_height(instruction: &Instruction) -> f64 {
+    18.0 + OPERAND_H * instruction.operands.len() as f64
+}
+
+/// How an instruction is drawn
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Symbol {
+    Contact { negated: bool },
+    Coil { letter: Option<char> },
+    Box,
+}
+
+fn symbol(instruction: &Instruction) -> Symbol {
+    match instruction.mnemonic.to_uppercase().as_str() {
+        "XIC" => Symbol::Contact { negated: false },
+        "XIO" => Symbol::Contact { negated: true },
+        "OTE" => Symbol::Coil { letter: None },
+        "OTL" => Symbol::Coil { letter: Some('L') },
+        "OTU" => Symbol::Coil { letter: Some('U') },
+        _ => Symbol::Box,
+    }
+}
+
+fn draw_series(out: &mut Vec<String>, elements: &[RungElement], x: f64, y: f64) {
+    let mut x = x;
+    for element in elements {
+        match element {
+            RungElement::Instruction(instruction) => draw_instruction(out, instruction, x, y),
+            RungElement::Parallel(branches) => draw_parallel(out, branches, x, y),
+        }
+        x += element_size(element).0;
+    }
+}
+
+fn draw_parallel(out: &mut Vec<String>, branches: &[Branch], x: f64, y: f64) {
+    let (width, _) = parallel_size(branches);
+    let left = x + BRANCH_GAP;
+    let right = x + width - BRANCH_GAP;
+    wire(out, x, left, y + WIRE_Y);
+    wire(out, right, x + width, y + WIRE_Y);
+
+    let mut leg_y = y;
+    let mut last_wire = y + WIRE_Y;
+    for branch in branches {
+        let (leg_w, leg_h) = series_size(&branch.elements);
+        draw_series(out, &branch.elements, left, leg_y);
+        wire(out, left + leg_w, right, leg_y + WIRE_Y);
+        last_wire = leg_y + WIRE_Y;
+        leg_y += leg_h;
+    }
+    let style = Style::default().stroke(Color::BLACK);
+    out.push(Line::new(left, y + WIRE_Y, left, last_wire).style(style.clone()).to_svg());
+    out.push(Line::new(right, y + WIRE_Y, right, last_wire).style(style).to_svg());
+}
+
+fn draw_instruction(out: &mut Vec<String>, instruction: &Instruction, x: f64, y: f64) {
+    let cx = x + CELL_W / 2.0;
+    let wy = y + WIRE_Y;
+    let stroke = Style::default().stroke(Color::BLACK).fill(Color::NONE);
+    let label = |text: &str, ly: f64| {
+        Text::new(cx, ly, &fit(text))
+            .anchor(TextAnchor::Middle)
+            .style(Style::default().fill(Color::BLACK).font("sans-serif", 10.0))
+            .to_svg()
+    };
+    let operand = instruction.operands.first().and_then(|o| o.as_value()).unwrap_or("?");
+
+    match symbol(instruction) {
+        Symbol::Contact { negated } => {
+            wire(out, x, cx - 8.0, wy);
+            wire(out, cx + 8.0, x + CELL_W, wy);
+            out.push(Line::new(cx - 8.0, wy - 10.0, cx - 8.0, wy + 10.0).style(stroke.clone()).to_svg());
+            out.push(Line::new(cx + 8.0, wy - 10.0, cx + 8.0, wy + 10.0).style(stroke.clone()).to_svg());
+            if negated {
+                out.push(Line::new(cx - 6.0, wy + 9.0, cx + 6.0, wy - 9.0).style(stroke).to_svg());
+            }
+            out.push(label(operand, wy - 16.0));
+        }
+        Symbol::Coil { letter } => {
+            wire(out, x, cx - 10.0, wy);
+            wire(out, cx + 10.0, x + CELL_W, wy);
+            let d = format!(
+                "M {} {} A 14 14 0 0 0 {} {} M {} {} A 14 14 0 0 1 {} {}",
+                cx - 6.0, wy - 10.0, cx - 6.0, wy + 10.0,
+                cx + 6.0, wy - 10.0, cx + 6.0, wy + 10.0
+            );
+            out.push(Path::new(&d).style(stroke).to_svg());
+            if let Some(letter) = letter {
+                out.push(label(&letter.to_string(), wy + 4.0));
+            }
+            out.push(label(operand, wy - 16.0));
+        }
+        Symbol::Box => {
+            let top = wy - 15.0;
+            wire(out, x, x + 10.0, wy);
+            wire(out, x + CELL_W - 10.0, x + CELL_W, wy);
+            out.push(
+                Rect::new(x + 10.0, top, CELL_W - 20.0, box_height(instruction))
+                    .style(Style::default().stroke(Color::BLACK).fill(Color::YELLOW))
+                    .to_svg(),
+            );
+            out.push(label(&instruction.mnemonic, top + 13.0));
+            for (i, op) in instruction.operands.iter().enumerate() {
+                out.push(label(op.as_value().unwrap_or("?"), top + 13.0 + OPERAND_H * (i as f64 + 1.0)));
+            }
+        }
+    }
+}
+
+/// Draw a horizontal wire
+fn wire(out: &mut Vec<String>, x1: f64, x2: f64, y: f64) {
+    if x2 > x1 {
+        out.push(Line::new(x1, y, x2, y).style(Style::default().stroke(Color::BLACK)).to_svg());
+    }
+}
+
+/// Shorten a label to fit an instruction cell
+fn fit(text: &str) -> String {
+    if text.chars().count() > LABEL_CHARS {
+        let head: String = text.chars().take(LABEL_CHARS - 1).collect();
+        format!("{}…", head)
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn content(text: &str) -> Vec<RungElement> {
+        parse_rung(text).content.unwrap().elements
+    }
+
+    #[test]
+    fn test_series_and_parallel_size() {
+        assert_eq!(series_size(&content("XIC(A)XIO(B)OTE(C);")), (3.0 * CELL_W, ROW_H));
+
+        let (width, height) = series_size(&content("[XIC(A),XIC(B)XIC(C),]OTE(D);"));
+        assert_eq!(width, 2.0 * CELL_W + 2.0 * BRANCH_GAP + CELL_W);
+        assert_eq!(height, 3.0 * ROW_H);
+    }
+
+    #[test]
+    fn test_box_height() {
+        let (_, height) = series_size(&content("TON(Timer1,5000,0);"));
+        assert!(height > ROW_H);
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let mut diagram = LadderDiagram::new("Main.Logic");
+        diagram.add_rung(0, Some("Start motor".to_string()), parse_rung("XIC(Start)[XIO(Stop),XIC(Jog)]OTE(Motor);"));
+        diagram.add_rung(1, None, parse_rung("XIC(("));
+        let svg = diagram.render_svg();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains("Main.Logic"));
+        assert!(svg.contains("Start motor"));
+        assert!(svg.contains(">Motor</text>"));
+        assert!(svg.contains("XIC(("));
+    }
+}
@@ -15,6 +15,8 @@
 //! - **CallGraph**: Execution flow (Routine → Routine via JSR/function calls)
 //! - **DataFlow**: Tag relationships (L5X) or DataType dependencies (PLCopen)
 //! - **Combined**: Both structure and calls
+//! - **Ladder**: Rungs of one RLL routine as a ladder diagram (L5X)
+//! - **Sfc**: Steps and transitions of one SFC routine or POU (L5X, PLCopen)
 //!
 //! # CLI Usage
 //!
@@ -25,8 +27,11 @@
 //!   <FILE>  L5X or PLCopen XML file to visualize
 //!
 //! Options:
-//!   -t, --type <TYPE>  Graph type: structure, call, dataflow, combined [default: structure]
-//!   -a, --aois         Include AOIs in the graph (L5X only)
+//!   -t, --type <TYPE>        Graph type: structure, call, dataflow, combined, ladder, sfc [default: structure]
+//!   -a, --aois               Include AOIs in the graph (L5X only)
+//!   -r, --routine <ROUTINE>  Routine to draw: NAME or PROGRAM.NAME (ladder, sfc)
+//!       --cluster <MODE>     Box the nodes of each program or task: none, by-program, by-task
+//!   -f, --format <FORMAT>    Output format: svg, dot, mermaid [default: svg]
 //!   -h, --help         Print help
 //! ```
 //!
@@ -41,14 +46,33 @@
 //!
 //! # Generate combined graph with AOIs
 //! plcviz -t combined -a project.L5X > combined.svg
+//!
+//! # Draw one RLL routine as a ladder diagram
+//! plcviz -t ladder -r MainProgram.MainRoutine project.L5X > ladder.svg
+//!
+//! # Call graph as Graphviz DOT
+//! plcviz -t call -f dot project.L5X | dot -Tpng > calls.png
+//!
+//! # Call graph as a Mermaid flowchart for Markdown
+//! plcviz -t call -f mermaid project.L5X > calls.mmd
+//!
+//! # Call graph with one box per task
+//! plcviz -t call --cluster by-task project.L5X > calls.svg
+//!
+//! # Draw an SFC POU as a step/transition chart
+//! plcviz -t sfc -r FillSequence project.xml > sfc.svg
 //! ```
 
 pub mod config;
 pub mod graph;
+pub mod ladder;
 pub mod svg;
 pub mod plcopen_graph;
+pub mod sfc;
 
 // Re-export main types
-pub use config::{GraphType, VizConfig, ElementFilter, NodeStyle, NodeStyles};
-pub use graph::{L5xGraph, L5xNode, L5xNodeType, L5xEdge};
+pub use config::{ClusterMode, GraphType, OutputFormat, VizConfig, ElementFilter, NodeStyle, NodeStyles};
+pub use graph::{EdgeType, L5xGraph, L5xNode, L5xNodeType, L5xEdge};
+pub use ladder::{LadderDiagram, LadderRung};
+pub use sfc::{SfcChart, SfcNode, SfcNodeKind};
 pub use plcopen_graph::{PlcopenGraphBuilder, PlcopenGraphType};
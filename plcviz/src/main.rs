@@ -1,10 +1,11 @@
 //! plcviz CLI - Generate SVG diagrams from L5X and PLCopen files
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::fs;
 
 use clap::{Parser, Subcommand, ValueEnum};
-use plcviz::{L5xGraph, L5xNodeType, GraphType, PlcopenGraphBuilder, PlcopenGraphType};
+use plcviz::{ClusterMode, L5xGraph, OutputFormat, L5xNodeType, GraphType, LadderDiagram, PlcopenGraphBuilder, PlcopenGraphType, SfcChart, SfcNodeKind};
 
 #[derive(Parser)]
 #[command(name = "plcviz")]
@@ -24,6 +25,18 @@ struct Cli {
     /// Include AOIs in the graph (L5X only)
     #[arg(short = 'a', long = "aois")]
     show_aois: bool,
+
+    /// Routine to draw: NAME or PROGRAM.NAME (ladder, sfc); POU name for PLCopen SFC
+    #[arg(short = 'r', long = "routine", value_name = "ROUTINE")]
+    routine: Option<String>,
+
+    /// Draw a labeled box around the nodes of each program or task
+    #[arg(long = "cluster", value_name = "MODE", default_value = "none")]
+    cluster: ClusterArg,
+
+    /// Output format (ladder and sfc diagrams are SVG only)
+    #[arg(short = 'f', long = "format", value_name = "FORMAT", default_value = "svg")]
+    format: FormatArg,
 }
 
 #[derive(Subcommand)]
@@ -44,6 +57,54 @@ enum GraphTypeArg {
     Dataflow,
     /// Structure + call edges combined
     Combined,
+    /// Ladder diagram of one RLL routine (L5X only, needs --routine)
+    Ladder,
+    /// SFC chart of one routine or POU (needs --routine)
+    Sfc,
+}
+
+/// Output format for CLI argument parsing
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum FormatArg {
+    /// SVG drawing
+    #[default]
+    Svg,
+    /// Graphviz DOT source
+    Dot,
+    /// Mermaid flowchart
+    Mermaid,
+}
+
+impl From<FormatArg> for OutputFormat {
+    fn from(arg: FormatArg) -> Self {
+        match arg {
+            FormatArg::Svg => OutputFormat::Svg,
+            FormatArg::Dot => OutputFormat::Dot,
+            FormatArg::Mermaid => OutputFormat::Mermaid,
+        }
+    }
+}
+
+/// Cluster mode for CLI argument parsing
+#[derive(Clone, Copy, Default, ValueEnum)]
+enum ClusterArg {
+    /// No cluster boxes
+    #[default]
+    None,
+    /// One box per program
+    ByProgram,
+    /// One box per task (programs it runs and their routines)
+    ByTask,
+}
+
+impl From<ClusterArg> for ClusterMode {
+    fn from(arg: ClusterArg) -> Self {
+        match arg {
+            ClusterArg::None => ClusterMode::None,
+            ClusterArg::ByProgram => ClusterMode::ByProgram,
+            ClusterArg::ByTask => ClusterMode::ByTask,
+        }
+    }
 }
 
 impl From<GraphTypeArg> for GraphType {
@@ -53,6 +114,8 @@ impl From<GraphTypeArg> for GraphType {
             GraphTypeArg::Call => GraphType::CallGraph,
             GraphTypeArg::Dataflow => GraphType::DataFlow,
             GraphTypeArg::Combined => GraphType::Combined,
+            GraphTypeArg::Ladder => GraphType::Ladder,
+            GraphTypeArg::Sfc => GraphType::Sfc,
         }
     }
 }
@@ -60,16 +123,23 @@ impl From<GraphTypeArg> for GraphType {
 fn main() {
     let cli = Cli::parse();
     let graph_type: GraphType = cli.graph_type.into();
+    let cluster: ClusterMode = cli.cluster.into();
+    let format: OutputFormat = cli.format.into();
+
+    if format != OutputFormat::Svg && matches!(graph_type, GraphType::Ladder | GraphType::Sfc) {
+        eprintln!("Error: {} is only available as SVG", graph_type.description());
+        std::process::exit(1);
+    }
 
     // Handle subcommands
     if let Some(Commands::Example) = cli.command {
-        run_example(graph_type);
+        run_example(graph_type, cluster, format);
         return;
     }
 
     // Need a file for normal operation
     if let Some(path) = cli.file {
-        generate_from_l5x(path.to_str().unwrap(), graph_type, cli.show_aois);
+        generate_from_l5x(path.to_str().unwrap(), graph_type, cli.show_aois, cli.routine.as_deref(), cluster, format);
     } else {
         eprintln!("Error: No input file specified");
         eprintln!("Usage: plcviz <FILE> or plcviz example");
@@ -78,9 +148,42 @@ fn main() {
     }
 }
 
-fn run_example(graph_type: GraphType) {
+fn run_example(graph_type: GraphType, cluster: ClusterMode, format: OutputFormat) {
     eprintln!("Generating example: {}", graph_type.description());
     
+    if graph_type == GraphType::Ladder {
+        let mut diagram = LadderDiagram::new("MainProgram.MotorControl");
+        diagram.add_rung(0, Some("Start/stop latch".to_string()), l5x::rll::parse_rung("[XIC(Start),XIC(Motor)]XIO(Stop)XIO(Fault)OTE(Motor);"));
+        diagram.add_rung(1, None, l5x::rll::parse_rung("XIC(Motor)TON(RunTimer,5000,0);"));
+        diagram.add_rung(2, Some("Fault on missing feedback".to_string()), l5x::rll::parse_rung("XIC(RunTimer.DN)XIO(MotorFb)OTL(Fault);"));
+        diagram.add_rung(3, None, l5x::rll::parse_rung("XIC(Reset)OTU(Fault);"));
+        println!("{}", diagram.render_svg());
+        return;
+    }
+
+    if graph_type == GraphType::Sfc {
+        let mut chart = SfcChart::new("MainProgram.FillSequence");
+        chart.add_step("1", "Idle", true);
+        chart.add_transition("2", Some("Start AND NOT Fault"));
+        chart.add_node("3", "", SfcNodeKind::SimultaneousDivergence);
+        chart.add_step("4", "Fill", false);
+        chart.add_action("4", "N InletValve");
+        chart.add_step("5", "Heat", false);
+        chart.add_action("5", "N Heater");
+        chart.add_action("5", "L AlarmTimer");
+        chart.add_node("6", "", SfcNodeKind::SimultaneousConvergence);
+        chart.add_transition("7", Some("Full AND Hot"));
+        chart.add_step("8", "Drain", false);
+        chart.add_action("8", "N OutletValve");
+        chart.add_transition("9", Some("Empty"));
+        chart.add_node("10", "", SfcNodeKind::Jump { target: "Idle".to_string() });
+        for (from, to) in [("1", "2"), ("2", "3"), ("3", "4"), ("3", "5"), ("4", "6"), ("5", "6"), ("6", "7"), ("7", "8"), ("8", "9"), ("9", "10")] {
+            chart.add_link(from, to);
+        }
+        println!("{}", chart.render_svg());
+        return;
+    }
+
     let mut graph = L5xGraph::new();
     
     match graph_type {
@@ -142,13 +245,28 @@ fn run_example(graph_type: GraphType) {
             graph.add_edge("MotorFb", "FaultHandler", Some("read"));
             graph.add_edge("FaultHandler", "Fault", Some("write"));
         }
+        GraphType::Ladder | GraphType::Sfc => {}
     }
+
+    let tasks = HashMap::from([
+        ("MainProgram".to_string(), "MainTask".to_string()),
+        ("CommProgram".to_string(), "CommTask".to_string()),
+    ]);
+    apply_clusters(&mut graph, cluster, &tasks);
     
-    let svg = graph.render_svg();
-    println!("{}", svg);
+    println!("{}", render_graph(&graph, format));
 }
 
-fn generate_from_l5x(path: &str, graph_type: GraphType, show_aois: bool) {
+/// Render a graph in the chosen output format
+fn render_graph(graph: &L5xGraph, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Svg => graph.render_svg(),
+        OutputFormat::Dot => graph.to_dot(),
+        OutputFormat::Mermaid => graph.to_mermaid(),
+    }
+}
+
+fn generate_from_l5x(path: &str, graph_type: GraphType, show_aois: bool, routine: Option<&str>, cluster: ClusterMode, format: OutputFormat) {
     let path = PathBuf::from(path);
     
     if !path.exists() {
@@ -169,9 +287,9 @@ fn generate_from_l5x(path: &str, graph_type: GraphType, show_aois: bool) {
     
     // Detect format and dispatch
     if is_plcopen_format(&content) {
-        generate_from_plcopen(&content, graph_type);
+        generate_from_plcopen(&content, graph_type, routine, cluster, format);
     } else {
-        generate_from_l5x_content(&content, graph_type, show_aois);
+        generate_from_l5x_content(&content, graph_type, show_aois, routine, cluster, format);
     }
 }
 
@@ -182,7 +300,7 @@ fn is_plcopen_format(content: &str) -> bool {
 }
 
 /// Generate from L5X content
-fn generate_from_l5x_content(content: &str, graph_type: GraphType, show_aois: bool) {
+fn generate_from_l5x_content(content: &str, graph_type: GraphType, show_aois: bool, routine: Option<&str>, cluster: ClusterMode, format: OutputFormat) {
     let project: l5x::Project = match l5x::from_str(content) {
         Ok(p) => p,
         Err(e) => {
@@ -195,17 +313,26 @@ fn generate_from_l5x_content(content: &str, graph_type: GraphType, show_aois: bo
     let target_type = project.target_type.as_deref().unwrap_or("Controller");
     eprintln!("L5X export type: {}", target_type);
     
-    let svg = match target_type {
-        "Program" => generate_program_export(&project, graph_type, show_aois),
+    if graph_type == GraphType::Ladder {
+        println!("{}", generate_ladder(&project, routine));
+        return;
+    }
+    if graph_type == GraphType::Sfc {
+        println!("{}", generate_sfc(&project, routine));
+        return;
+    }
+    
+    let graph = match target_type {
+        "Program" => generate_program_export(&project, graph_type, show_aois, cluster),
         "AddOnInstructionDefinition" => generate_aoi_export(&project, graph_type),
-        _ => generate_controller_export(&project, graph_type, show_aois),
+        _ => generate_controller_export(&project, graph_type, show_aois, cluster),
     };
     
-    println!("{}", svg);
+    println!("{}", render_graph(&graph, format));
 }
 
 /// Generate from PLCopen content
-fn generate_from_plcopen(content: &str, graph_type: GraphType) {
+fn generate_from_plcopen(content: &str, graph_type: GraphType, pou: Option<&str>, cluster: ClusterMode, format: OutputFormat) {
     let project: plcopen::Project = match plcopen::from_str(content) {
         Ok(p) => p,
         Err(e) => {
@@ -224,18 +351,38 @@ fn generate_from_plcopen(content: &str, graph_type: GraphType) {
         GraphType::Structure | GraphType::Combined => PlcopenGraphType::Structure,
         GraphType::CallGraph => PlcopenGraphType::CallGraph,
         GraphType::DataFlow => PlcopenGraphType::DataTypeDeps,
+        GraphType::Ladder => {
+            eprintln!("Error: Ladder diagrams are only available for L5X files");
+            std::process::exit(1);
+        }
+        GraphType::Sfc => {
+            println!("{}", generate_plcopen_sfc(&project, pou));
+            return;
+        }
     };
     
+    // Task bindings refer to program POUs by type name
+    let mut tasks = HashMap::new();
+    if let Some(configurations) = project.instances.as_ref().and_then(|i| i.configurations.as_ref()) {
+        for resource in configurations.configuration.iter().flat_map(|c| &c.resource) {
+            for task in &resource.task {
+                for instance in &task.pou_instance {
+                    tasks.insert(format!("pou_{}", instance.type_name), task.name.clone());
+                }
+            }
+        }
+    }
+
     // Pass raw XML for ST code extraction
     let builder = PlcopenGraphBuilder::with_xml(project, plcopen_graph_type, content.to_string());
-    let graph = builder.build();
-    let svg = graph.render_svg();
+    let mut graph = builder.build();
+    apply_clusters(&mut graph, cluster, &tasks);
     
-    println!("{}", svg);
+    println!("{}", render_graph(&graph, format));
 }
 
 /// Generate graph from Controller export (full project)
-fn generate_controller_export(project: &l5x::Project, graph_type: GraphType, show_aois: bool) -> String {
+fn generate_controller_export(project: &l5x::Project, graph_type: GraphType, show_aois: bool, cluster: ClusterMode) -> L5xGraph {
     let mut graph = L5xGraph::new();
     
     if let Some(ref controller) = project.controller {
@@ -255,11 +402,12 @@ fn generate_controller_export(project: &l5x::Project, graph_type: GraphType, sho
         }
     }
     
-    graph.render_svg()
+    apply_clusters(&mut graph, cluster, &l5x_program_tasks(project));
+    graph
 }
 
 /// Generate graph from Program export (single program)
-fn generate_program_export(project: &l5x::Project, graph_type: GraphType, show_aois: bool) -> String {
+fn generate_program_export(project: &l5x::Project, graph_type: GraphType, show_aois: bool, cluster: ClusterMode) -> L5xGraph {
     let mut graph = L5xGraph::new();
     
     if let Some(ref controller) = project.controller {
@@ -286,11 +434,36 @@ fn generate_program_export(project: &l5x::Project, graph_type: GraphType, show_a
         }
     }
     
-    graph.render_svg()
+    apply_clusters(&mut graph, cluster, &l5x_program_tasks(project));
+    graph
+}
+
+/// Map the programs of an L5X export to the tasks that run them
+fn l5x_program_tasks(project: &l5x::Project) -> HashMap<String, String> {
+    let mut tasks = HashMap::new();
+    if let Some(controller_tasks) = project.controller.as_ref().and_then(|c| c.tasks.as_ref()) {
+        for task in &controller_tasks.task {
+            if let Some(ref scheduled) = task.scheduled_programs {
+                for program in &scheduled.scheduled_program {
+                    tasks.insert(program.name.clone(), task.name.clone());
+                }
+            }
+        }
+    }
+    tasks
+}
+
+/// Group the graph nodes into cluster boxes
+fn apply_clusters(graph: &mut L5xGraph, cluster: ClusterMode, tasks: &HashMap<String, String>) {
+    match cluster {
+        ClusterMode::None => {}
+        ClusterMode::ByProgram => graph.cluster_by_program(),
+        ClusterMode::ByTask => graph.cluster_by_task(tasks),
+    }
 }
 
 /// Generate graph from AddOnInstructionDefinition export (single AOI)
-fn generate_aoi_export(project: &l5x::Project, graph_type: GraphType) -> String {
+fn generate_aoi_export(project: &l5x::Project, graph_type: GraphType) -> L5xGraph {
     let mut graph = L5xGraph::new();
     
     if let Some(ref controller) = project.controller {
@@ -328,7 +501,107 @@ fn generate_aoi_export(project: &l5x::Project, graph_type: GraphType) -> String
         }
     }
     
-    graph.render_svg()
+    graph
+}
+
+/// Collect the routines of programs and AOIs as (Container.Routine, routine)
+fn collect_routines(project: &l5x::Project) -> Vec<(String, &l5x::Routine)> {
+    let mut routines: Vec<(String, &l5x::Routine)> = Vec::new();
+    if let Some(ref controller) = project.controller {
+        if let Some(ref programs) = controller.programs {
+            for program in &programs.program {
+                if let Some(ref program_routines) = program.routines {
+                    for r in &program_routines.routine {
+                        routines.push((format!("{}.{}", program.name, r.name), r));
+                    }
+                }
+            }
+        }
+        if let Some(ref aois) = controller.add_on_instruction_definitions {
+            for aoi in &aois.add_on_instruction_definition {
+                for item in &aoi.content {
+                    if let l5x::UDIDefinitionContent::Routines(ref aoi_routines) = item {
+                        for r in &aoi_routines.routine {
+                            routines.push((format!("{}.{}", aoi.name, r.name), r));
+                        }
+                    }
+                }
+            }
+        }
+    }
+    routines
+}
+
+/// Find a routine by Container.Routine or routine name, listing the candidates if none is given
+fn find_routine<'a>(routines: &'a [(String, &'a l5x::Routine)], routine: Option<&str>, kind: &str) -> Option<&'a (String, &'a l5x::Routine)> {
+    let Some(wanted) = routine else {
+        eprintln!("Error: --routine is required for {} diagrams", kind.to_lowercase());
+        eprintln!("{} routines:", kind);
+        for (name, _) in routines {
+            eprintln!("  {}", name);
+        }
+        std::process::exit(1);
+    };
+
+    routines.iter().find(|(name, r)| {
+        name.eq_ignore_ascii_case(wanted) || r.name.eq_ignore_ascii_case(wanted)
+    })
+}
+
+/// Generate a ladder diagram of one RLL routine
+fn generate_ladder(project: &l5x::Project, routine: Option<&str>) -> String {
+    let mut routines = collect_routines(project);
+    routines.retain(|(_, r)| r.r#type == "RLL");
+
+    let found = find_routine(&routines, routine, "RLL");
+    match found.and_then(|(name, r)| LadderDiagram::from_routine(name, r)) {
+        Some(diagram) => diagram.render_svg(),
+        None => {
+            eprintln!("Error: RLL routine not found: {}", routine.unwrap_or_default());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Generate an SFC chart of one SFC routine
+fn generate_sfc(project: &l5x::Project, routine: Option<&str>) -> String {
+    let mut routines = collect_routines(project);
+    routines.retain(|(_, r)| r.r#type == "SFC");
+
+    let found = find_routine(&routines, routine, "SFC");
+    match found.and_then(|(name, r)| SfcChart::from_routine(name, r)) {
+        Some(chart) => chart.render_svg(),
+        None => {
+            eprintln!("Error: SFC routine not found: {}", routine.unwrap_or_default());
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Generate an SFC chart of one PLCopen POU with an SFC body
+fn generate_plcopen_sfc(project: &plcopen::Project, pou: Option<&str>) -> String {
+    let pous = project.types.as_ref().and_then(|t| t.pous.as_ref()).map(|p| p.pou.as_slice()).unwrap_or_default();
+    let sfc_pous: Vec<(&str, &plcopen::Body_SFC_Inline)> = pous
+        .iter()
+        .filter_map(|p| p.body.iter().find_map(|body| body.sfc.as_ref()).map(|sfc| (p.name.as_str(), &**sfc)))
+        .collect();
+
+    let Some(wanted) = pou else {
+        eprintln!("Error: --routine is required for sfc diagrams");
+        eprintln!("SFC POUs:");
+        for (name, _) in &sfc_pous {
+            eprintln!("  {}", name);
+        }
+        std::process::exit(1);
+    };
+
+    match sfc_pous.iter().find(|(name, _)| name.eq_ignore_ascii_case(wanted)) {
+        Some((name, sfc)) => SfcChart::from_plcopen(name, sfc).render_svg(),
+        None => {
+            eprintln!("Error: SFC POU not found: {}", wanted);
+            std::process::exit(1);
+        }
+    }
 }
 
 /// Add a program and its routines to the graph
@@ -350,7 +623,7 @@ fn add_program_to_graph(program: &l5x::AProgram, graph: &mut L5xGraph, graph_typ
                 graph.add_edge(prog_name, &routine_id, None);
             } else {
                 // For call/dataflow, just add routine nodes
-                graph.add_node(&routine_id, &routine.name, L5xNodeType::Routine);
+                graph.add_node_with_parent(&routine_id, &routine.name, L5xNodeType::Routine, prog_name);
             }
             
             // Extract JSR calls for call graph
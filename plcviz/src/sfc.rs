@@ -0,0 +1,447 @@
+//! SFC chart rendering for PLCopen and L5X SFC bodies
+//!
+//! Builds a chart of steps, transitions and branches from a PLCopen SFC
+//! body or an L5X SFC routine and lays it out top to bottom with layout-rs:
+//! steps are boxes (initial steps with a double border) listing their
+//! actions, transitions are bars with their condition, selection branches
+//! are single lines and simultaneous branches double lines.
+
+use std::collections::HashMap;
+
+use layout::core::base::Orientation;
+use layout::core::geometry::Point;
+use layout::core::style::StyleAttr;
+use layout::std_shapes::shapes::{Arrow, Element, ShapeKind};
+use layout::topo::layout::VisualGraph;
+
+use crate::graph::SvgRenderer;
+
+/// Line height of step names and actions
+const LINE_H: f64 = 18.0;
+/// Approximate width of one character
+const CHAR_W: f64 = 8.0;
+/// Length of a transition bar
+const BAR_W: f64 = 40.0;
+/// Maximum width of a node
+const MAX_W: f64 = 400.0;
+
+/// Kind of an SFC chart node
+#[derive(Debug, Clone, PartialEq)]
+pub enum SfcNodeKind {
+    /// Step with its actions (e.g., "N Fill")
+    Step { initial: bool, actions: Vec<String> },
+    /// Transition with its condition
+    Transition { condition: Option<String> },
+    /// Start of alternative branches
+    SelectionDivergence,
+    /// End of alternative branches
+    SelectionConvergence,
+    /// Start of parallel branches
+    SimultaneousDivergence,
+    /// End of parallel branches
+    SimultaneousConvergence,
+    /// Jump to a named step
+    Jump { target: String },
+    /// Stop element (L5X)
+    Stop,
+}
+
+/// A node of an SFC chart
+#[derive(Debug, Clone)]
+pub struct SfcNode {
+    /// Node ID (local ID in the source)
+    pub id: String,
+    /// Step or transition name
+    pub name: String,
+    /// Node kind
+    pub kind: SfcNodeKind,
+}
+
+/// SFC chart of one POU or routine
+#[derive(Debug, Clone, Default)]
+pub struct SfcChart {
+    /// Chart title (e.g., "MainProgram.Sequence")
+    pub title: String,
+    /// Nodes in source order
+    pub nodes: Vec<SfcNode>,
+    /// Directed links as (from, to) node IDs
+    pub links: Vec<(String, String)>,
+}
+
+impl SfcChart {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// Build the chart of a PLCopen SFC body
+    pub fn from_plcopen(title: &str, sfc: &plcopen::Body_SFC_Inline) -> Self {
+        let mut chart = Self::new(title);
+
+        for step in &sfc.step {
+            let initial = step.initial_step.as_deref() == Some("true");
+            chart.add_step(&step.local_id, &step.name, initial);
+            chart.link_from(step.connection_point_in.iter(), &step.local_id);
+        }
+        for step in &sfc.macro_step {
+            let name = step.name.as_deref().unwrap_or("Macro");
+            chart.add_step(&step.local_id, name, false);
+            chart.link_from(step.connection_point_in.iter(), &step.local_id);
+        }
+        for transition in &sfc.transition {
+            let condition = transition.condition.as_ref().and_then(|condition| {
+                condition.reference.as_ref().map(|r| r.name.clone()).or_else(|| {
+                    condition
+                        .inline
+                        .as_ref()
+                        .and_then(|inline| inline.st.as_ref())
+                        .and_then(|st| st.text.as_deref())
+                        .map(|text| text.trim().to_string())
+                })
+            });
+            chart.add_node(&transition.local_id, "", SfcNodeKind::Transition { condition });
+            chart.link_from(transition.connection_point_in.iter(), &transition.local_id);
+        }
+        for branch in &sfc.selection_divergence {
+            chart.add_node(&branch.local_id, "", SfcNodeKind::SelectionDivergence);
+            chart.link_from(branch.connection_point_in.iter(), &branch.local_id);
+        }
+        for branch in &sfc.selection_convergence {
+            chart.add_node(&branch.local_id, "", SfcNodeKind::SelectionConvergence);
+            for point in &branch.connection_point_in {
+                for connection in &point.connection {
+                    chart.add_link(&connection.ref_local_id, &branch.local_id);
+                }
+            }
+        }
+        for branch in &sfc.simultaneous_divergence {
+            chart.add_node(&branch.local_id, "", SfcNodeKind::SimultaneousDivergence);
+            chart.link_from(branch.connection_point_in.iter(), &branch.local_id);
+        }
+        for branch in &sfc.simultaneous_convergence {
+            chart.add_node(&branch.local_id, "", SfcNodeKind::SimultaneousConvergence);
+            chart.link_from(branch.connection_point_in.iter(), &branch.local_id);
+        }
+        for jump in &sfc.jump_step {
+            let target = jump.target_name.clone();
+            chart.add_node(&jump.local_id, "", SfcNodeKind::Jump { target });
+            chart.link_from(jump.connection_point_in.iter(), &jump.local_id);
+        }
+
+        // Action blocks hang off the step they are connected to
+        for block in &sfc.action_block {
+            let steps: Vec<&str> = block
+                .connection_point_in
+                .iter()
+                .flat_map(|point| &point.connection)
+                .map(|connection| connection.ref_local_id.as_str())
+                .collect();
+            for action in &block.action {
+                let name = action.reference.as_ref().map(|r| r.name.as_str()).unwrap_or("inline");
+                let qualifier = action.qualifier.as_deref().unwrap_or("N");
+                for step in &steps {
+                    chart.add_action(step, &format!("{} {}", qualifier, name));
+                }
+            }
+        }
+        chart
+    }
+
+    /// Build the chart of an L5X SFC routine
+    ///
+    /// Returns `None` if the routine has no SFC content.
+    pub fn from_routine(title: &str, routine: &l5x::Routine) -> Option<Self> {
+        let sfc = routine.content.iter().find_map(|content| match content {
+            l5x::RoutineContent::SFCContent(sfc) => Some(sfc),
+            _ => None,
+        })?;
+
+        let mut chart = Self::new(title);
+        for step in &sfc.step {
+            let name = step.operand.as_deref().unwrap_or(&step.id);
+            let initial = step.initial_step.as_deref().is_some_and(|v| v.eq_ignore_ascii_case("true"));
+            chart.add_step(&step.id, name, initial);
+            for action in &step.action {
+                let name = action.operand.as_deref().unwrap_or(&action.id);
+                let qualifier = action.qualifier.as_deref().unwrap_or("N");
+                chart.add_action(&step.id, &format!("{} {}", qualifier, name));
+            }
+        }
+        for transition in &sfc.transition {
+            let condition = transition
+                .condition
+                .as_ref()
+                .and_then(|block| block.stcontent.as_ref())
+                .map(st_text)
+                .filter(|text| !text.is_empty());
+            let name = transition.operand.as_deref().unwrap_or("");
+            chart.add_node(&transition.id, name, SfcNodeKind::Transition { condition });
+        }
+
+        // Links may end at a branch leg; map legs to their branch
+        let mut legs: HashMap<&str, &str> = HashMap::new();
+        for branch in &sfc.branch {
+            let selection = branch.branch_type.eq_ignore_ascii_case("Selection");
+            let diverge = branch.branch_flow.eq_ignore_ascii_case("Diverge");
+            let kind = match (selection, diverge) {
+                (true, true) => SfcNodeKind::SelectionDivergence,
+                (true, false) => SfcNodeKind::SelectionConvergence,
+                (false, true) => SfcNodeKind::SimultaneousDivergence,
+                (false, false) => SfcNodeKind::SimultaneousConvergence,
+            };
+            chart.add_node(&branch.id, "", kind);
+            for leg in &branch.leg {
+                legs.insert(&leg.id, &branch.id);
+            }
+        }
+        for stop in &sfc.stop {
+            chart.add_node(&stop.id, stop.operand.as_deref().unwrap_or(""), SfcNodeKind::Stop);
+        }
+
+        let resolve = |id: &str| legs.get(id).copied().unwrap_or(id).to_string();
+        for link in &sfc.directed_link {
+            let (from, to) = (resolve(&link.from_id), resolve(&link.to_id));
+            if from != to {
+                chart.add_link(&from, &to);
+            }
+        }
+        Some(chart)
+    }
+
+    /// Add a node to the chart
+    pub fn add_node(&mut self, id: &str, name: &str, kind: SfcNodeKind) {
+        self.nodes.push(SfcNode {
+            id: id.to_string(),
+            name: name.to_string(),
+            kind,
+        });
+    }
+
+    /// Add a step to the chart
+    pub fn add_step(&mut self, id: &str, name: &str, initial: bool) {
+        self.add_node(id, name, SfcNodeKind::Step { initial, actions: Vec::new() });
+    }
+
+    /// Add a transition to the chart
+    pub fn add_transition(&mut self, id: &str, condition: Option<&str>) {
+        let condition = condition.map(|c| c.to_string());
+        self.add_node(id, "", SfcNodeKind::Transition { condition });
+    }
+
+    /// Add an action (e.g., "N Fill") to a step
+    pub fn add_action(&mut self, step: &str, action: &str) {
+        let node = self.nodes.iter_mut().find(|node| node.id == step);
+        if let Some(SfcNode { kind: SfcNodeKind::Step { actions, .. }, .. }) = node {
+            actions.push(action.to_string());
+        }
+    }
+
+    /// Add a directed link between two nodes
+    pub fn add_link(&mut self, from: &str, to: &str) {
+        self.links.push((from.to_string(), to.to_string()));
+    }
+
+    /// Link every connection of the given input points to `to`
+    fn link_from<'a>(&mut self, points: impl Iterator<Item = &'a plcopen::ConnectionPointIn>, to: &str) {
+        for point in points {
+            for connection in &point.connection {
+                self.add_link(&connection.ref_local_id, to);
+            }
+        }
+    }
+
+    /// Render the chart to SVG using layout-rs, steps top to bottom
+    pub fn render_svg(&self) -> String {
+        let mut vg = VisualGraph::new(Orientation::TopToBottom);
+        let mut handles: HashMap<&str, layout::adt::dag::NodeHandle> = HashMap::new();
+
+        for node in &self.nodes {
+            let (size, properties) = node_shape(node);
+            let element = Element::create_with_properties(
+                ShapeKind::Box(String::new()),
+                StyleAttr::simple(),
+                Orientation::LeftToRight,
+                size,
+                properties,
+            );
+            handles.insert(&node.id, vg.add_node(element));
+        }
+
+        for (from, to) in &self.links {
+            if let (Some(&from_h), Some(&to_h)) = (handles.get(from.as_str()), handles.get(to.as_str())) {
+                let arrow = Arrow::with_properties(
+                    layout::std_shapes::shapes::LineEndKind::None,
+                    layout::std_shapes::shapes::LineEndKind::Arrow,
+                    layout::core::style::LineStyleKind::Normal,
+                    "",
+                    &StyleAttr::simple(),
+                    "__SFC__",
+                    &None,
+                    &None,
+                );
+                vg.add_edge(arrow, from_h, to_h);
+            }
+        }
+
+        let mut renderer = SvgRenderer::new();
+        if !self.nodes.is_empty() {
+            vg.do_it(false, false, false, &mut renderer);
+        }
+        renderer.finalize()
+    }
+
+    /// Get node count
+    pub fn node_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Get link count
+    pub fn link_count(&self) -> usize {
+        self.links.len()
+    }
+}
+
+/// Size of a node and the properties telling the renderer how to draw it
+///
+/// Properties are a marker line followed by the text lines of the node.
+fn node_shape(node: &SfcNode) -> (Point, String) {
+    let text_width = |text: &str| text.chars().count() as f64 * CHAR_W;
+    match &node.kind {
+        SfcNodeKind::Step { initial, actions } => {
+            let marker = if *initial { "__INITIAL_STEP__" } else { "__STEP__" };
+            let width = std::iter::once(node.name.as_str())
+                .chain(actions.iter().map(|a| a.as_str()))
+                .map(text_width)
+                .fold(0.0, f64::max);
+            let size = Point::new((width + 30.0).clamp(80.0, MAX_W), 40.0 + LINE_H * actions.len() as f64);
+            let mut lines = vec![marker, node.name.as_str()];
+            lines.extend(actions.iter().map(|a| a.as_str()));
+            (size, lines.join("\n"))
+        }
+        SfcNodeKind::Transition { condition } => {
+            let label = condition.as_deref().unwrap_or(node.name.as_str());
+            let label = label.lines().next().unwrap_or("");
+            // The bar stays centered, the condition is written to its right
+            let width = (BAR_W + 2.0 * (text_width(label) + 10.0)).min(MAX_W);
+            (Point::new(width, 24.0), format!("__TRANSITION__\n{}", label))
+        }
+        SfcNodeKind::SelectionDivergence | SfcNodeKind::SelectionConvergence => {
+            (Point::new(160.0, 8.0), "__SELECTION__".to_string())
+        }
+        SfcNodeKind::SimultaneousDivergence | SfcNodeKind::SimultaneousConvergence => {
+            (Point::new(160.0, 8.0), "__SIMULTANEOUS__".to_string())
+        }
+        SfcNodeKind::Jump { target } => {
+            let width = (text_width(target) + 40.0).clamp(60.0, MAX_W);
+            (Point::new(width, 30.0), format!("__JUMP__\n{}", target))
+        }
+        SfcNodeKind::Stop => {
+            let width = (text_width(&node.name) + 40.0).clamp(60.0, MAX_W);
+            (Point::new(width, 30.0), format!("__STOP__\n{}", node.name))
+        }
+    }
+}
+
+/// Join the lines of an L5X ST content block
+fn st_text(st: &l5x::STContent) -> String {
+    let lines: Vec<&str> = st
+        .content
+        .iter()
+        .filter_map(|content| match content {
+            l5x::STContentContent::Line(line) => line.text.as_deref().or(line.value.as_deref()),
+            l5x::STContentContent::TextContent(text) => Some(text.as_str()),
+            _ => None,
+        })
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .collect();
+    lines.join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> SfcChart {
+        let mut chart = SfcChart::new("Sequence");
+        chart.add_step("1", "Init", true);
+        chart.add_transition("2", Some("Start"));
+        chart.add_step("3", "Fill", false);
+        chart.add_action("3", "N OpenValve");
+        chart.add_transition("4", Some("Full"));
+        chart.add_node("5", "", SfcNodeKind::Jump { target: "Init".to_string() });
+        chart.add_link("1", "2");
+        chart.add_link("2", "3");
+        chart.add_link("3", "4");
+        chart.add_link("4", "5");
+        chart
+    }
+
+    #[test]
+    fn test_build_chart() {
+        let chart = sample();
+        assert_eq!(chart.node_count(), 5);
+        assert_eq!(chart.link_count(), 4);
+        assert_eq!(
+            chart.nodes[2].kind,
+            SfcNodeKind::Step { initial: false, actions: vec!["N OpenValve".to_string()] }
+        );
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let svg = sample().render_svg();
+        assert!(svg.contains("<svg"));
+        assert!(svg.contains(">Init</text>"));
+        assert!(svg.contains(">N OpenValve</text>"));
+        assert!(svg.contains(">Full</text>"));
+        assert!(svg.contains("<path"));
+    }
+
+    #[test]
+    fn test_from_plcopen() {
+        let xml = r#"<?xml version="1.0" encoding="utf-8"?>
+<project xmlns="http://www.plcopen.org/xml/tc6_0201">
+  <fileHeader companyName="Test" productName="Test" productVersion="1" creationDateTime="2024-01-01T00:00:00"/>
+  <contentHeader name="Test"><coordinateInfo><fbd><scaling x="1" y="1"/></fbd><ld><scaling x="1" y="1"/></ld><sfc><scaling x="1" y="1"/></sfc></coordinateInfo></contentHeader>
+  <types><dataTypes/><pous>
+    <pou name="Seq" pouType="program">
+      <body><SFC>
+        <step localId="1" name="Init" initialStep="true"/>
+        <transition localId="2">
+          <connectionPointIn><connection refLocalId="1"/></connectionPointIn>
+          <condition><inline name=""><ST><xhtml xmlns="http://www.w3.org/1999/xhtml">Start</xhtml></ST></inline></condition>
+        </transition>
+        <selectionDivergence localId="3">
+          <connectionPointIn><connection refLocalId="2"/></connectionPointIn>
+        </selectionDivergence>
+        <step localId="4" name="Run">
+          <connectionPointIn><connection refLocalId="3"/></connectionPointIn>
+        </step>
+        <actionBlock localId="5">
+          <connectionPointIn><connection refLocalId="4"/></connectionPointIn>
+          <action localId="6" qualifier="N"><reference name="Motor"/></action>
+        </actionBlock>
+        <jumpStep localId="7" targetName="Init">
+          <connectionPointIn><connection refLocalId="4"/></connectionPointIn>
+        </jumpStep>
+      </SFC></body>
+    </pou>
+  </pous></types>
+  <instances><configurations/></instances>
+</project>"#;
+        let project: plcopen::Project = plcopen::from_str(xml).unwrap();
+        let pou = &project.types.as_ref().unwrap().pous.as_ref().unwrap().pou[0];
+        let sfc = pou.body[0].sfc.as_ref().unwrap();
+        let chart = SfcChart::from_plcopen("Seq", sfc);
+
+        assert_eq!(chart.node_count(), 5);
+        assert_eq!(chart.link_count(), 4);
+        let init = chart.nodes.iter().find(|n| n.name == "Init").unwrap();
+        assert!(matches!(init.kind, SfcNodeKind::Step { initial: true, .. }));
+        let run = chart.nodes.iter().find(|n| n.name == "Run").unwrap();
+        assert_eq!(run.kind, SfcNodeKind::Step { initial: false, actions: vec!["N Motor".to_string()] });
+    }
+}